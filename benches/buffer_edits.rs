@@ -0,0 +1,85 @@
+//! Benchmarks for multi-line edits in a large character buffer
+//!
+//! Compares pasting a block of lines through `insert_text`, which splices the
+//! new lines in once, with inserting the same text one character at a time.
+
+use blueline::repl::models::CharacterBuffer;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+const BUFFER_LINES: usize = 20_000;
+const PASTE_LINES: usize = 500;
+
+fn large_buffer() -> CharacterBuffer {
+    let lines: Vec<String> = (0..BUFFER_LINES)
+        .map(|i| format!("    \"field_{i}\": \"value number {i}\","))
+        .collect();
+    CharacterBuffer::from_lines(&lines)
+}
+
+fn paste_text() -> String {
+    (0..PASTE_LINES)
+        .map(|i| format!("  \"pasted_{i}\": [1, 2, 3],"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn insert_chars(buffer: &mut CharacterBuffer, line: usize, col: usize, text: &str) {
+    let (mut line, mut col) = (line, col);
+    for ch in text.chars() {
+        buffer.insert_char(line, col, ch);
+        if ch == '\n' {
+            line += 1;
+            col = 0;
+        } else {
+            col += 1;
+        }
+    }
+}
+
+fn bench_paste(c: &mut Criterion) {
+    let buffer = large_buffer();
+    let text = paste_text();
+    let middle = BUFFER_LINES / 2;
+
+    let mut group = c.benchmark_group("paste_into_large_buffer");
+    group.bench_function("insert_text", |b| {
+        b.iter_batched(
+            || buffer.clone(),
+            |mut buffer| {
+                buffer.insert_text(middle, 4, black_box(&text));
+                buffer
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("insert_char", |b| {
+        b.iter_batched(
+            || buffer.clone(),
+            |mut buffer| {
+                insert_chars(&mut buffer, middle, 4, black_box(&text));
+                buffer
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_delete(c: &mut Criterion) {
+    let buffer = large_buffer();
+    let middle = BUFFER_LINES / 2;
+
+    c.bench_function("delete_range_across_lines", |b| {
+        b.iter_batched(
+            || buffer.clone(),
+            |mut buffer| {
+                buffer.delete_range((middle, 4), black_box((middle + PASTE_LINES, 4)));
+                buffer
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, bench_paste, bench_delete);
+criterion_main!(benches);
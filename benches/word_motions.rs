@@ -0,0 +1,51 @@
+//! Benchmarks for word motions on a very long display line
+//!
+//! `w`, `b` and `e` scan the display line in place; these runs repeat each
+//! motion across a line mixing ASCII and double-width Japanese text.
+
+use blueline::repl::models::{CharacterBuffer, DisplayChar, DisplayLine};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const WORDS: usize = 50_000;
+
+fn long_line() -> DisplayLine {
+    let text: String = (0..WORDS)
+        .map(|i| if i % 4 == 0 { "日本語 " } else { "word, " })
+        .collect();
+    let mut buffer = CharacterBuffer::from_lines(&[text]);
+    buffer.refresh_line_word_boundaries(0);
+
+    let mut chars = Vec::new();
+    let mut screen_col = 0;
+    for buffer_char in buffer.get_line(0).unwrap().chars() {
+        let display_char = DisplayChar::from_buffer_char(buffer_char.clone(), (0, screen_col));
+        screen_col += display_char.display_width();
+        chars.push(display_char);
+    }
+    let char_count = chars.len();
+    DisplayLine::new(chars, 0, 0, char_count, false)
+}
+
+fn bench_word_motions(c: &mut Criterion) {
+    let line = long_line();
+    let middle = line.display_width() / 2;
+    let end = line.display_width().saturating_sub(1);
+
+    let mut group = c.benchmark_group("word_motions_on_long_line");
+    group.bench_function("next_word_start", |b| {
+        b.iter(|| line.find_next_word_start(black_box(middle)))
+    });
+    group.bench_function("previous_word_start", |b| {
+        b.iter(|| line.find_previous_word_start(black_box(middle)))
+    });
+    group.bench_function("previous_word_start_from_end", |b| {
+        b.iter(|| line.find_previous_word_start(black_box(end)))
+    });
+    group.bench_function("next_word_end", |b| {
+        b.iter(|| line.find_next_word_end(black_box(middle)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_word_motions);
+criterion_main!(benches);
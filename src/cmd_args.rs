@@ -10,12 +10,52 @@ struct ClapArgs {
     /// If the profile is not configured, the request will fail.
     #[clap(short = 'p', long, default_value = "default", help = "profile name")]
     profile: String,
+
+    /// Disable file logging
+    /// Optional. When set, no log file is written to the current directory.
+    #[clap(long = "no-log", help = "disable writing the log file")]
+    no_log: bool,
+
+    /// Log file path
+    /// Optional. Overrides the BLUELINE_LOG_FILE environment variable.
+    #[clap(long = "log-file", value_name = "PATH", help = "log file path")]
+    log_file: Option<String>,
+
+    /// Log level
+    /// Optional. Overrides the BLUELINE_LOG_LEVEL environment variable.
+    #[clap(long = "log-level", value_name = "LEVEL", help = "log level")]
+    log_level: Option<String>,
+
+    /// Session file
+    /// Optional. Restores a session saved with :mksession, including its profile.
+    #[clap(long, value_name = "PATH", help = "session file to restore")]
+    session: Option<String>,
+
+    /// Init file
+    /// Optional. Ex commands to run at startup instead of the config file, or NONE to skip it.
+    #[clap(
+        short = 'u',
+        value_name = "FILE",
+        help = "init file to run instead of the config file"
+    )]
+    init: Option<String>,
+
+    /// Skip the init file
+    /// Optional. Starts without running the config file, like `-u NONE`.
+    #[clap(long = "no-init", help = "do not run the config file at startup")]
+    no_init: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct CommandLineArgs {
     #[allow(dead_code)] // Used by profile() method
     profile: String,
+    no_log: bool,
+    log_file: Option<String>,
+    log_level: Option<String>,
+    session: Option<String>,
+    init: Option<String>,
+    no_init: bool,
 }
 
 impl CommandLineArgs {
@@ -24,6 +64,12 @@ impl CommandLineArgs {
         let args = ClapArgs::parse();
         Self {
             profile: args.profile,
+            no_log: args.no_log,
+            log_file: args.log_file,
+            log_level: args.log_level,
+            session: args.session,
+            init: args.init,
+            no_init: args.no_init,
         }
     }
 
@@ -36,6 +82,12 @@ impl CommandLineArgs {
         let args = ClapArgs::parse_from(itr);
         Self {
             profile: args.profile,
+            no_log: args.no_log,
+            log_file: args.log_file,
+            log_level: args.log_level,
+            session: args.session,
+            init: args.init,
+            no_init: args.no_init,
         }
     }
 
@@ -43,6 +95,32 @@ impl CommandLineArgs {
     pub fn profile(&self) -> &String {
         &self.profile
     }
+
+    pub fn no_log(&self) -> bool {
+        self.no_log
+    }
+
+    pub fn log_file(&self) -> Option<&str> {
+        self.log_file.as_deref()
+    }
+
+    pub fn log_level(&self) -> Option<&str> {
+        self.log_level.as_deref()
+    }
+
+    pub fn session(&self) -> Option<&str> {
+        self.session.as_deref()
+    }
+
+    /// Init file given with `-u`, unless it is `NONE`
+    pub fn init(&self) -> Option<&str> {
+        self.init.as_deref().filter(|path| *path != "NONE")
+    }
+
+    /// Whether startup should skip the init file (`--no-init` or `-u NONE`)
+    pub fn no_init(&self) -> bool {
+        self.no_init || self.init.as_deref() == Some("NONE")
+    }
 }
 
 #[cfg(test)]
@@ -65,5 +143,46 @@ mod test {
     fn test_default_values() {
         let args = CommandLineArgs::parse_from(["program"]);
         assert_eq!(args.profile(), "default");
+        assert!(!args.no_log());
+    }
+
+    #[test]
+    fn test_parse_args_log_flags() {
+        let args = CommandLineArgs::parse_from([
+            "program",
+            "--log-file",
+            "debug.log",
+            "--log-level",
+            "debug",
+        ]);
+        assert_eq!(args.log_file(), Some("debug.log"));
+        assert_eq!(args.log_level(), Some("debug"));
+    }
+
+    #[test]
+    fn test_parse_args_session() {
+        let args = CommandLineArgs::parse_from(["program", "--session", "Session.json"]);
+        assert_eq!(args.session(), Some("Session.json"));
+        assert_eq!(CommandLineArgs::parse_from(["program"]).session(), None);
+    }
+
+    #[test]
+    fn test_parse_args_init() {
+        let args = CommandLineArgs::parse_from(["program", "-u", "test.vim"]);
+        assert_eq!(args.init(), Some("test.vim"));
+        assert!(!args.no_init());
+
+        let args = CommandLineArgs::parse_from(["program", "-u", "NONE"]);
+        assert_eq!(args.init(), None);
+        assert!(args.no_init());
+
+        assert!(CommandLineArgs::parse_from(["program", "--no-init"]).no_init());
+        assert!(!CommandLineArgs::parse_from(["program"]).no_init());
+    }
+
+    #[test]
+    fn test_parse_args_no_log() {
+        let args = CommandLineArgs::parse_from(["program", "--no-log"]);
+        assert!(args.no_log());
     }
 }
@@ -6,6 +6,7 @@
 use crate::cmd_args::CommandLineArgs;
 use std::fs;
 use std::path::PathBuf;
+use tracing::level_filters::LevelFilter;
 
 /// Default profile file path for blueline
 pub const DEFAULT_PROFILE_PATH: &str = "~/.blueline/profile";
@@ -19,6 +20,24 @@ pub const DEFAULT_CONFIG_PATH: &str = "~/.blueline/config";
 /// Environment variable name for overriding the config path
 pub const CONFIG_PATH_ENV_VAR: &str = "BLUELINE_CONFIG_PATH";
 
+/// Default directory for request templates
+pub const DEFAULT_TEMPLATES_DIR: &str = "~/.blueline/templates";
+
+/// Environment variable name for overriding the templates directory
+pub const TEMPLATES_DIR_ENV_VAR: &str = "BLUELINE_TEMPLATES_DIR";
+
+/// Environment variable name for disabling file logging
+pub const NO_LOG_ENV_VAR: &str = "BLUELINE_NO_LOG";
+
+/// Environment variable name for the log file path
+pub const LOG_FILE_ENV_VAR: &str = "BLUELINE_LOG_FILE";
+
+/// Environment variable name for the log level
+pub const LOG_LEVEL_ENV_VAR: &str = "BLUELINE_LOG_LEVEL";
+
+/// Log file written in the current directory when no path is configured
+pub const DEFAULT_LOG_FILE: &str = "blueline.log";
+
 /// Unified application configuration
 #[derive(Debug, Clone)]
 pub struct AppConfig {
@@ -26,8 +45,10 @@ pub struct AppConfig {
     profile_name: String,
     /// Path to the profile file
     profile_path: String,
-    /// Initial ex commands to execute on startup (from config file)
+    /// Initial ex commands to execute on startup (from the config file or `-u`)
     initial_commands: Vec<String>,
+    /// Session file to restore on startup (from `--session`)
+    session_path: Option<String>,
 }
 
 impl AppConfig {
@@ -36,7 +57,14 @@ impl AppConfig {
         Self {
             profile_name: cmd_args.profile().to_string(),
             profile_path: get_profile_path(),
-            initial_commands: load_config_commands(),
+            initial_commands: if cmd_args.no_init() {
+                Vec::new()
+            } else {
+                load_config_commands_from(
+                    &cmd_args.init().map_or_else(get_config_path, str::to_string),
+                )
+            },
+            session_path: cmd_args.session().map(str::to_string),
         }
     }
 
@@ -46,6 +74,7 @@ impl AppConfig {
             profile_name,
             profile_path,
             initial_commands,
+            session_path: None,
         }
     }
 
@@ -63,6 +92,83 @@ impl AppConfig {
     pub fn initial_commands(&self) -> &[String] {
         &self.initial_commands
     }
+
+    /// Get the session file to restore, if any
+    pub fn session_path(&self) -> Option<&str> {
+        self.session_path.as_deref()
+    }
+}
+
+/// Logging configuration
+///
+/// Command line flags take precedence over the `BLUELINE_LOG_FILE` and
+/// `BLUELINE_LOG_LEVEL` environment variables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogConfig {
+    /// Whether a log file is written at all
+    enabled: bool,
+    /// Log file path, when one was configured
+    file: Option<String>,
+    /// Valid log level, when one was configured
+    level: Option<LevelFilter>,
+    /// Configured log level that could not be parsed
+    invalid_level: Option<String>,
+}
+
+impl LogConfig {
+    /// Create LogConfig from command line arguments and environment variables
+    pub fn from_args(cmd_args: &CommandLineArgs) -> Self {
+        let file = cmd_args
+            .log_file()
+            .map(str::to_string)
+            .or_else(|| env_string(LOG_FILE_ENV_VAR));
+        let level = cmd_args
+            .log_level()
+            .map(str::to_string)
+            .or_else(|| env_string(LOG_LEVEL_ENV_VAR));
+
+        let (level, invalid_level) = match level {
+            Some(level) => match level.parse::<LevelFilter>() {
+                Ok(filter) => (Some(filter), None),
+                Err(_) => (None, Some(level)),
+            },
+            None => (None, None),
+        };
+
+        Self {
+            enabled: is_file_logging_enabled(cmd_args.no_log()),
+            file,
+            level,
+            invalid_level,
+        }
+    }
+
+    /// Check whether a log file should be written
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Get the configured log file path, if any
+    ///
+    /// Without one, a quiet log is written to [`DEFAULT_LOG_FILE`].
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Get the configured log level, if any
+    pub fn level(&self) -> Option<LevelFilter> {
+        self.level
+    }
+
+    /// Get the configured log level that was not a valid level, if any
+    pub fn invalid_level(&self) -> Option<&str> {
+        self.invalid_level.as_deref()
+    }
+}
+
+/// Read an environment variable as a UTF-8 string
+fn env_string(name: &str) -> Option<String> {
+    std::env::var_os(name).and_then(|val| val.into_string().ok())
 }
 
 /// Get the profile file path, checking environment variable first, then falling back to default
@@ -79,11 +185,33 @@ pub fn get_config_path() -> String {
         .unwrap_or_else(|| DEFAULT_CONFIG_PATH.to_string())
 }
 
+/// Get the templates directory, checking environment variable first, then falling back to default
+///
+/// The returned path has `~` already expanded.
+pub fn get_templates_dir() -> PathBuf {
+    let templates_dir = std::env::var_os(TEMPLATES_DIR_ENV_VAR)
+        .and_then(|val| val.into_string().ok())
+        .unwrap_or_else(|| DEFAULT_TEMPLATES_DIR.to_string());
+    PathBuf::from(shellexpand::tilde(&templates_dir).as_ref())
+}
+
+/// Check whether a log file should be written
+///
+/// File logging is on unless disabled by `--no-log` or the `BLUELINE_NO_LOG`
+/// environment variable.
+pub fn is_file_logging_enabled(no_log: bool) -> bool {
+    !no_log && std::env::var_os(NO_LOG_ENV_VAR).is_none()
+}
+
 /// Load configuration commands from the config file
 /// Returns a vector of ex commands to execute, or an empty vector if file doesn't exist
 pub fn load_config_commands() -> Vec<String> {
-    let config_path = get_config_path();
-    let expanded = shellexpand::tilde(&config_path);
+    load_config_commands_from(&get_config_path())
+}
+
+/// Load configuration commands from `config_path`, such as an init file given with `-u`
+pub fn load_config_commands_from(config_path: &str) -> Vec<String> {
+    let expanded = shellexpand::tilde(config_path);
     let expanded_path = PathBuf::from(expanded.as_ref());
 
     tracing::debug!("Loading config from: {:?}", expanded_path);
@@ -206,6 +334,23 @@ mod tests {
         }
     }
 
+    #[test]
+    #[serial]
+    fn test_get_templates_dir_env_override() {
+        // Save current env var state
+        let original = std::env::var_os(TEMPLATES_DIR_ENV_VAR);
+
+        let test_path = "/custom/templates";
+        std::env::set_var(TEMPLATES_DIR_ENV_VAR, test_path);
+        assert_eq!(get_templates_dir(), PathBuf::from(test_path));
+
+        // Restore original state
+        match original {
+            Some(val) => std::env::set_var(TEMPLATES_DIR_ENV_VAR, val),
+            None => std::env::remove_var(TEMPLATES_DIR_ENV_VAR),
+        }
+    }
+
     #[test]
     fn test_shellexpand_tilde() {
         // Test that shellexpand properly expands tilde
@@ -284,4 +429,81 @@ mod tests {
         // initial_commands could be empty if no config file exists
         let _ = config.initial_commands();
     }
+
+    #[test]
+    fn test_app_config_from_args_init_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let init_path = temp_dir.path().join("init");
+        fs::write(&init_path, "# Init\nset number on\n").unwrap();
+        let init_path = init_path.to_str().unwrap();
+
+        let config = AppConfig::from_args(CommandLineArgs::parse_from(["test", "-u", init_path]));
+        assert_eq!(config.initial_commands(), ["set number on"]);
+
+        for args in [vec!["test", "--no-init"], vec!["test", "-u", "NONE"]] {
+            let config = AppConfig::from_args(CommandLineArgs::parse_from(args));
+            assert!(config.initial_commands().is_empty());
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_is_file_logging_enabled() {
+        // Save current env var state
+        let original = std::env::var_os(NO_LOG_ENV_VAR);
+
+        std::env::remove_var(NO_LOG_ENV_VAR);
+        assert!(is_file_logging_enabled(false));
+        assert!(!is_file_logging_enabled(true));
+
+        std::env::set_var(NO_LOG_ENV_VAR, "1");
+        assert!(!is_file_logging_enabled(false));
+
+        // Restore original state
+        match original {
+            Some(val) => std::env::set_var(NO_LOG_ENV_VAR, val),
+            None => std::env::remove_var(NO_LOG_ENV_VAR),
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_log_config_flags_override_env() {
+        // Save current env var state
+        let original_file = std::env::var_os(LOG_FILE_ENV_VAR);
+        let original_level = std::env::var_os(LOG_LEVEL_ENV_VAR);
+
+        std::env::set_var(LOG_FILE_ENV_VAR, "env.log");
+        std::env::set_var(LOG_LEVEL_ENV_VAR, "warn");
+
+        let args =
+            CommandLineArgs::parse_from(["test", "--log-file", "cli.log", "--log-level", "debug"]);
+        let config = LogConfig::from_args(&args);
+        assert_eq!(config.file(), Some("cli.log"));
+        assert_eq!(config.level(), Some(LevelFilter::DEBUG));
+
+        let config = LogConfig::from_args(&CommandLineArgs::parse_from(["test"]));
+        assert_eq!(config.file(), Some("env.log"));
+        assert_eq!(config.level(), Some(LevelFilter::WARN));
+
+        // Restore original state
+        match original_file {
+            Some(val) => std::env::set_var(LOG_FILE_ENV_VAR, val),
+            None => std::env::remove_var(LOG_FILE_ENV_VAR),
+        }
+        match original_level {
+            Some(val) => std::env::set_var(LOG_LEVEL_ENV_VAR, val),
+            None => std::env::remove_var(LOG_LEVEL_ENV_VAR),
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_log_config_invalid_level_falls_back() {
+        let args = CommandLineArgs::parse_from(["test", "--log-level", "loud"]);
+        let config = LogConfig::from_args(&args);
+
+        assert_eq!(config.level(), None);
+        assert_eq!(config.invalid_level(), Some("loud"));
+    }
 }
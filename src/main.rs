@@ -5,19 +5,19 @@
 use anyhow::Result;
 use blueline::{
     cmd_args::CommandLineArgs,
-    config::AppConfig,
-    repl::io::{TerminalEventStream, TerminalRenderStream},
+    config::{AppConfig, LogConfig, DEFAULT_LOG_FILE},
+    repl::io::{install_panic_hook, TerminalEventStream, TerminalRenderStream},
     AppController,
 };
-use std::env;
 use tracing_subscriber::{fmt::time::ChronoLocal, EnvFilter};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    init_tracing_subscriber();
-
     let cmd_args = CommandLineArgs::parse();
+    init_tracing_subscriber(&LogConfig::from_args(&cmd_args));
+
     let config = AppConfig::from_args(cmd_args);
+    install_panic_hook();
 
     // Explicit dependency injection - clear what implementations are being used
     let mut app = AppController::with_io_streams(
@@ -30,34 +30,40 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-fn init_tracing_subscriber() {
-    // Check if we should use file logging
-    let log_file = env::var_os("BLUELINE_LOG_FILE").and_then(|s| s.into_string().ok());
+fn init_tracing_subscriber(config: &LogConfig) {
+    // Without a subscriber tracing macros are no-ops, so no log file is created
+    if !config.is_enabled() {
+        return;
+    }
 
-    let env_filter = EnvFilter::try_from_env("BLUELINE_LOG_LEVEL")
-        .or_else(|_| EnvFilter::try_from_env("RUST_LOG"))
-        .unwrap_or_else(|_| EnvFilter::new("info"))
-        .add_directive("reqwest=warn".parse().unwrap())
-        .add_directive("hyper=warn".parse().unwrap())
-        .add_directive("tokio=warn".parse().unwrap())
-        .add_directive("tracing=warn".parse().unwrap())
-        .add_directive("tracing_subscriber=warn".parse().unwrap())
-        .add_directive("tower_http=warn".parse().unwrap())
-        .add_directive("tower=warn".parse().unwrap())
-        .add_directive("tokio_util=warn".parse().unwrap())
-        .add_directive("tokio_rustls=warn".parse().unwrap())
-        .add_directive("rustls=warn".parse().unwrap())
-        .add_directive("rustls_pemfile=warn".parse().unwrap())
-        .add_directive("native_tls=warn".parse().unwrap())
-        .add_directive("tokio_stream=warn".parse().unwrap())
-        .add_directive("tokio_io=warn".parse().unwrap())
-        .add_directive("tokio_timer=warn".parse().unwrap())
-        .add_directive("tokio_sync=warn".parse().unwrap())
-        .add_directive("tokio_task=warn".parse().unwrap())
-        .add_directive("tokio_reactor=warn".parse().unwrap());
+    // Use the configured level, then RUST_LOG, then the given default
+    let base_filter = |default: &str| match config.level() {
+        Some(level) => EnvFilter::new(level.to_string()),
+        None => EnvFilter::try_from_env("RUST_LOG").unwrap_or_else(|_| EnvFilter::new(default)),
+    };
 
-    if let Some(log_file_path) = log_file {
+    if let Some(log_file_path) = config.file() {
         // Use file logging
+        let env_filter = base_filter("info")
+            .add_directive("reqwest=warn".parse().unwrap())
+            .add_directive("hyper=warn".parse().unwrap())
+            .add_directive("tokio=warn".parse().unwrap())
+            .add_directive("tracing=warn".parse().unwrap())
+            .add_directive("tracing_subscriber=warn".parse().unwrap())
+            .add_directive("tower_http=warn".parse().unwrap())
+            .add_directive("tower=warn".parse().unwrap())
+            .add_directive("tokio_util=warn".parse().unwrap())
+            .add_directive("tokio_rustls=warn".parse().unwrap())
+            .add_directive("rustls=warn".parse().unwrap())
+            .add_directive("rustls_pemfile=warn".parse().unwrap())
+            .add_directive("native_tls=warn".parse().unwrap())
+            .add_directive("tokio_stream=warn".parse().unwrap())
+            .add_directive("tokio_io=warn".parse().unwrap())
+            .add_directive("tokio_timer=warn".parse().unwrap())
+            .add_directive("tokio_sync=warn".parse().unwrap())
+            .add_directive("tokio_task=warn".parse().unwrap())
+            .add_directive("tokio_reactor=warn".parse().unwrap());
+
         let file_appender = tracing_appender::rolling::never(".", log_file_path);
         let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
 
@@ -72,9 +78,7 @@ fn init_tracing_subscriber() {
     } else {
         // In REPL mode, minimize logging to prevent any potential background scrolling
         // Use the most restrictive filter and file output only
-        let minimal_filter = EnvFilter::try_from_env("BLUELINE_LOG_LEVEL")
-            .or_else(|_| EnvFilter::try_from_env("RUST_LOG"))
-            .unwrap_or_else(|_| EnvFilter::new("off")) // Default to no logging
+        let minimal_filter = base_filter("off") // Default to no logging
             .add_directive("blueline=warn".parse().unwrap()) // Only warnings and errors from our code
             .add_directive("reqwest=off".parse().unwrap())
             .add_directive("hyper=off".parse().unwrap())
@@ -90,7 +94,7 @@ fn init_tracing_subscriber() {
             .add_directive("native_tls=off".parse().unwrap())
             .add_directive("tokio_stream=off".parse().unwrap());
 
-        let file_appender = tracing_appender::rolling::never(".", "blueline.log");
+        let file_appender = tracing_appender::rolling::never(".", DEFAULT_LOG_FILE);
         let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
 
         tracing_subscriber::fmt()
@@ -102,4 +106,8 @@ fn init_tracing_subscriber() {
         // Leak the guard to ensure logs are flushed on exit
         Box::leak(Box::new(_guard));
     }
+
+    if let Some(level) = config.invalid_level() {
+        tracing::warn!("Invalid log level '{level}', falling back to the default level");
+    }
 }
@@ -2,6 +2,7 @@
 //!
 //! Commands for controlling the application lifecycle such as quit/terminate operations.
 
+use crate::repl::events::EditorMode;
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
@@ -25,11 +26,95 @@ impl Command for AppTerminateCommand {
     }
 }
 
+/// Show buffer name and cursor line in the status line (Ctrl+G)
+pub struct FileInfoCommand;
+
+impl Command for FileInfoCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('g'))
+            && event.modifiers.contains(KeyModifiers::CONTROL)
+            && context.state.current_mode == EditorMode::Normal
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::FileInfoRequested])
+    }
+
+    fn name(&self) -> &'static str {
+        "FileInfo"
+    }
+}
+
+/// Enter Z prefix mode on first 'Z' press (for ZZ and ZQ)
+pub struct EnterZPrefixCommand;
+
+impl Command for EnterZPrefixCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('Z'))
+            && context.state.current_mode == EditorMode::Normal
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::mode_change(EditorMode::ZPrefix)])
+    }
+
+    fn name(&self) -> &'static str {
+        "EnterZPrefix"
+    }
+}
+
+/// Write the request buffer if modified and quit (ZZ, like :x)
+pub struct WriteQuitCommand;
+
+impl Command for WriteQuitCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('Z'))
+            && context.state.current_mode == EditorMode::ZPrefix
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![
+            CommandEvent::mode_change(EditorMode::Normal),
+            CommandEvent::WriteQuitRequested {
+                always_write: false,
+            },
+        ])
+    }
+
+    fn name(&self) -> &'static str {
+        "WriteQuit"
+    }
+}
+
+/// Quit without writing (ZQ, like :q!)
+pub struct QuitWithoutWritingCommand;
+
+impl Command for QuitWithoutWritingCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('Q'))
+            && context.state.current_mode == EditorMode::ZPrefix
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![
+            CommandEvent::mode_change(EditorMode::Normal),
+            CommandEvent::QuitRequested,
+        ])
+    }
+
+    fn name(&self) -> &'static str {
+        "QuitWithoutWriting"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::repl::commands::ViewModelSnapshot;
-    use crate::repl::events::{EditorMode, LogicalPosition, Pane};
+    use crate::repl::events::{EditorMode, KeyBinding, LogicalPosition, Pane};
 
     fn create_test_key_event(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
         KeyEvent::new(code, modifiers)
@@ -45,6 +130,10 @@ mod tests {
             terminal_dimensions: (80, 24),
             expand_tab: false,
             tab_width: 4,
+            pending_count: None,
+            command_window_open: false,
+            whichwrap: String::new(),
+            execute_key: KeyBinding::ENTER,
         };
         CommandContext::new(snapshot)
     }
@@ -86,4 +175,75 @@ mod tests {
         assert_eq!(events.len(), 1);
         assert_eq!(events[0], CommandEvent::QuitRequested);
     }
+
+    #[test]
+    fn file_info_should_be_relevant_for_ctrl_g_in_normal_mode() {
+        let mut context = create_test_context();
+        let cmd = FileInfoCommand;
+        let event = create_test_key_event(KeyCode::Char('g'), KeyModifiers::CONTROL);
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert!(!cmd.is_relevant(
+            &context,
+            &create_test_key_event(KeyCode::Char('g'), KeyModifiers::NONE)
+        ));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![CommandEvent::FileInfoRequested]
+        );
+
+        context.state.current_mode = EditorMode::Insert;
+        assert!(!cmd.is_relevant(&context, &event));
+    }
+
+    #[test]
+    fn enter_z_prefix_should_be_relevant_for_shift_z_in_normal_mode() {
+        let context = create_test_context();
+        let cmd = EnterZPrefixCommand;
+        let event = create_test_key_event(KeyCode::Char('Z'), KeyModifiers::SHIFT);
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::ZPrefix)]
+        );
+    }
+
+    #[test]
+    fn zz_should_request_write_if_modified_and_quit() {
+        let mut context = create_test_context();
+        context.state.current_mode = EditorMode::ZPrefix;
+        let cmd = WriteQuitCommand;
+        let event = create_test_key_event(KeyCode::Char('Z'), KeyModifiers::SHIFT);
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert!(!QuitWithoutWritingCommand.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::WriteQuitRequested {
+                    always_write: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn zq_should_quit_without_writing() {
+        let mut context = create_test_context();
+        context.state.current_mode = EditorMode::ZPrefix;
+        let cmd = QuitWithoutWritingCommand;
+        let event = create_test_key_event(KeyCode::Char('Q'), KeyModifiers::SHIFT);
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert!(!WriteQuitCommand.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::QuitRequested,
+            ]
+        );
+    }
 }
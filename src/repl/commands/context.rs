@@ -3,7 +3,7 @@
 //! Context and service access for commands.
 //! Uses trait-based access to provide type-safe, minimal exposure to services.
 
-use crate::repl::events::{EditorMode, LogicalPosition, Pane};
+use crate::repl::events::{EditorMode, KeyBinding, LogicalPosition, Pane};
 use crate::repl::view_models::ViewModel;
 use bluenote::HttpClient;
 
@@ -18,6 +18,12 @@ pub struct ViewModelSnapshot {
     pub terminal_dimensions: (u16, u16),
     pub expand_tab: bool,
     pub tab_width: usize,
+    pub pending_count: Option<usize>,
+    pub command_window_open: bool,
+    /// Keys allowed to move across line boundaries (`:set whichwrap`)
+    pub whichwrap: String,
+    /// Key that sends the request (`:set executekey`)
+    pub execute_key: KeyBinding,
 }
 
 impl ViewModelSnapshot {
@@ -32,6 +38,10 @@ impl ViewModelSnapshot {
             terminal_dimensions: view_model.terminal_size(),
             expand_tab: view_model.pane_manager().get_expand_tab(),
             tab_width: view_model.pane_manager().get_tab_width(),
+            pending_count: view_model.get_pending_count(),
+            command_window_open: view_model.is_command_window_open(),
+            whichwrap: view_model.get_whichwrap().to_string(),
+            execute_key: view_model.get_execute_key(),
         }
     }
 }
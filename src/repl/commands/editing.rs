@@ -7,7 +7,7 @@ use crate::repl::events::{EditorMode, Pane};
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-use super::{Command, CommandContext, CommandEvent};
+use super::{CaseChange, Command, CommandContext, CommandEvent};
 
 /// Insert character in insert mode
 pub struct InsertCharCommand;
@@ -170,11 +170,556 @@ impl Command for DeleteCharAtCursorCommand {
     }
 }
 
+/// Delete word before cursor (Ctrl-w in insert mode)
+pub struct DeleteWordBeforeCursorCommand;
+
+impl Command for DeleteWordBeforeCursorCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('w'))
+            && event.modifiers.contains(KeyModifiers::CONTROL)
+            && context.state.current_mode == EditorMode::Insert
+            && context.state.current_pane == Pane::Request
+    }
+
+    fn execute(&self, _event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        use super::MovementDirection;
+        Ok(vec![CommandEvent::TextDeleteRequested {
+            position: context.state.cursor_position,
+            amount: 1,
+            direction: MovementDirection::WordBackward,
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "DeleteWordBeforeCursor"
+    }
+}
+
+/// Delete back to the first non-blank character (Ctrl-u in insert mode)
+pub struct DeleteToLineStartCommand;
+
+impl Command for DeleteToLineStartCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('u'))
+            && event.modifiers.contains(KeyModifiers::CONTROL)
+            && context.state.current_mode == EditorMode::Insert
+            && context.state.current_pane == Pane::Request
+    }
+
+    fn execute(&self, _event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        use super::MovementDirection;
+        Ok(vec![CommandEvent::TextDeleteRequested {
+            position: context.state.cursor_position,
+            amount: 1,
+            direction: MovementDirection::LineStart,
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "DeleteToLineStart"
+    }
+}
+
+/// Start register insertion (Ctrl-r in insert mode)
+pub struct EnterInsertRegisterPrefixCommand;
+
+impl Command for EnterInsertRegisterPrefixCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('r'))
+            && event.modifiers.contains(KeyModifiers::CONTROL)
+            && context.state.current_mode == EditorMode::Insert
+            && context.state.current_pane == Pane::Request
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::mode_change(
+            EditorMode::InsertRegisterPrefix,
+        )])
+    }
+
+    fn name(&self) -> &'static str {
+        "EnterInsertRegisterPrefix"
+    }
+}
+
+/// Insert register contents after Ctrl-r (any other key cancels back to insert mode)
+pub struct InsertRegisterCommand;
+
+impl Command for InsertRegisterCommand {
+    fn is_relevant(&self, context: &CommandContext, _event: &KeyEvent) -> bool {
+        context.state.current_mode == EditorMode::InsertRegisterPrefix
+    }
+
+    fn execute(&self, event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let mut events = vec![CommandEvent::mode_change(EditorMode::Insert)];
+        if let KeyCode::Char(register) = event.code {
+            events.push(CommandEvent::InsertRegisterRequested { register });
+        }
+        Ok(events)
+    }
+
+    fn name(&self) -> &'static str {
+        "InsertRegister"
+    }
+}
+
+/// Start literal insertion (Ctrl-v in insert mode)
+pub struct EnterInsertLiteralPrefixCommand;
+
+impl Command for EnterInsertLiteralPrefixCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('v'))
+            && event.modifiers.contains(KeyModifiers::CONTROL)
+            && context.state.current_mode == EditorMode::Insert
+            && context.state.current_pane == Pane::Request
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::mode_change(
+            EditorMode::InsertLiteralPrefix,
+        )])
+    }
+
+    fn name(&self) -> &'static str {
+        "EnterInsertLiteralPrefix"
+    }
+}
+
+/// Insert the key after Ctrl-v as-is, or collect a code point after `u` (`Ctrl-v u00e9`)
+pub struct InsertLiteralCommand;
+
+impl InsertLiteralCommand {
+    /// Character a key stands for when inserted literally
+    fn literal_char(event: &KeyEvent) -> Option<char> {
+        match event.code {
+            KeyCode::Char(ch) if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                // Ctrl-a through Ctrl-z, Ctrl-[ and friends are ASCII control codes
+                let upper = ch.to_ascii_uppercase();
+                ('@'..='_')
+                    .contains(&upper)
+                    .then(|| char::from(upper as u8 - b'@'))
+            }
+            KeyCode::Char(ch) => Some(ch),
+            KeyCode::Tab => Some('\t'),
+            KeyCode::Enter => Some('\r'),
+            KeyCode::Esc => Some('\x1b'),
+            KeyCode::Backspace => Some('\x08'),
+            _ => None,
+        }
+    }
+}
+
+impl Command for InsertLiteralCommand {
+    fn is_relevant(&self, context: &CommandContext, _event: &KeyEvent) -> bool {
+        context.state.current_mode == EditorMode::InsertLiteralPrefix
+    }
+
+    fn execute(&self, event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::InsertLiteralRequested {
+            ch: Self::literal_char(&event),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "InsertLiteral"
+    }
+}
+
+/// Insert the text typed in the previous insert session (Ctrl-a in insert mode)
+pub struct InsertLastInsertedCommand;
+
+impl Command for InsertLastInsertedCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('a'))
+            && event.modifiers.contains(KeyModifiers::CONTROL)
+            && context.state.current_mode == EditorMode::Insert
+            && context.state.current_pane == Pane::Request
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::InsertLastInsertedRequested])
+    }
+
+    fn name(&self) -> &'static str {
+        "InsertLastInserted"
+    }
+}
+
+/// Indent the current line by one shiftwidth (Ctrl-t in insert mode)
+pub struct IndentLineCommand;
+
+impl Command for IndentLineCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('t'))
+            && event.modifiers.contains(KeyModifiers::CONTROL)
+            && context.state.current_mode == EditorMode::Insert
+            && context.state.current_pane == Pane::Request
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::IndentLineRequested])
+    }
+
+    fn name(&self) -> &'static str {
+        "IndentLine"
+    }
+}
+
+/// Dedent the current line by one shiftwidth (Ctrl-d in insert mode)
+pub struct DedentLineCommand;
+
+impl Command for DedentLineCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('d'))
+            && event.modifiers.contains(KeyModifiers::CONTROL)
+            && context.state.current_mode == EditorMode::Insert
+            && context.state.current_pane == Pane::Request
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::DedentLineRequested])
+    }
+
+    fn name(&self) -> &'static str {
+        "DedentLine"
+    }
+}
+
+/// Join current line with the next line (J in normal mode)
+pub struct JoinLinesCommand;
+
+impl Command for JoinLinesCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('J'))
+            && context.state.current_mode == EditorMode::Normal
+            && context.state.current_pane == Pane::Request
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::join_lines()])
+    }
+
+    fn name(&self) -> &'static str {
+        "JoinLines"
+    }
+}
+
+/// Make the last change again at the cursor (. in normal mode)
+pub struct RepeatLastChangeCommand;
+
+impl Command for RepeatLastChangeCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('.'))
+            && context.state.current_mode == EditorMode::Normal
+            && context.state.current_pane == Pane::Request
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::RepeatLastChangeRequested])
+    }
+
+    fn name(&self) -> &'static str {
+        "RepeatLastChange"
+    }
+}
+
+/// Check whether the editor is in one of the visual modes
+fn is_visual_mode(mode: EditorMode) -> bool {
+    matches!(
+        mode,
+        EditorMode::Visual | EditorMode::VisualLine | EditorMode::VisualBlock
+    )
+}
+
+/// Accumulate a count for the next command (the 3 in `3>`, `3fa` or `d3fa`)
+///
+/// `0` only extends a count that has already been started.
+pub struct CountDigitCommand;
+
+impl Command for CountDigitCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        let KeyCode::Char(ch) = event.code else {
+            return false;
+        };
+        let is_count_digit =
+            matches!(ch, '1'..='9') || (ch == '0' && context.state.pending_count.is_some());
+
+        let accepts_count = is_visual_mode(context.state.current_mode)
+            || matches!(
+                context.state.current_mode,
+                EditorMode::Normal | EditorMode::DPrefix
+            );
+
+        is_count_digit && accepts_count && event.modifiers.is_empty()
+    }
+
+    fn execute(&self, event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        match event.code {
+            KeyCode::Char(ch) => Ok(ch
+                .to_digit(10)
+                .map(|digit| CommandEvent::CountDigitRequested {
+                    digit: digit as usize,
+                })
+                .into_iter()
+                .collect()),
+            _ => Ok(vec![]),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "CountDigit"
+    }
+}
+
+/// Indent the selected lines (> in visual mode), repeated by the pending count
+pub struct IndentSelectionCommand;
+
+impl Command for IndentSelectionCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('>'))
+            && is_visual_mode(context.state.current_mode)
+            && context.state.current_pane == Pane::Request
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, _event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::IndentSelectionRequested {
+            count: context.state.pending_count.unwrap_or(1),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "IndentSelection"
+    }
+}
+
+/// Dedent the selected lines (< in visual mode), repeated by the pending count
+pub struct DedentSelectionCommand;
+
+impl Command for DedentSelectionCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('<'))
+            && is_visual_mode(context.state.current_mode)
+            && context.state.current_pane == Pane::Request
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, _event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::DedentSelectionRequested {
+            count: context.state.pending_count.unwrap_or(1),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "DedentSelection"
+    }
+}
+
+/// Enter = prefix mode on '=' press, waiting for the lines to reindent
+pub struct EnterEqualPrefixCommand;
+
+impl Command for EnterEqualPrefixCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('='))
+            && context.state.current_mode == EditorMode::Normal
+            && context.state.current_pane == Pane::Request
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::mode_change(EditorMode::EqualPrefix)])
+    }
+
+    fn name(&self) -> &'static str {
+        "EnterEqualPrefix"
+    }
+}
+
+/// Reindent after '=': `==` covers the pending count of lines, `=G` runs to the end
+///
+/// Any other key cancels back to normal mode.
+pub struct ReindentLinesCommand;
+
+impl Command for ReindentLinesCommand {
+    fn is_relevant(&self, context: &CommandContext, _event: &KeyEvent) -> bool {
+        context.state.current_mode == EditorMode::EqualPrefix
+    }
+
+    fn execute(&self, event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let mut events = vec![CommandEvent::mode_change(EditorMode::Normal)];
+        let count = match event.code {
+            KeyCode::Char('=') => Some(context.state.pending_count.unwrap_or(1)),
+            KeyCode::Char('G') => None,
+            KeyCode::Char('g') if event.modifiers.contains(KeyModifiers::SHIFT) => None,
+            _ => return Ok(events),
+        };
+        events.push(CommandEvent::ReindentLinesRequested { count });
+        Ok(events)
+    }
+
+    fn name(&self) -> &'static str {
+        "ReindentLines"
+    }
+}
+
+/// Enter a shift prefix mode on '>' or '<' press, waiting for the same key again
+pub struct EnterShiftPrefixCommand;
+
+impl Command for EnterShiftPrefixCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('>' | '<'))
+            && context.state.current_mode == EditorMode::Normal
+            && context.state.current_pane == Pane::Request
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let mode = match event.code {
+            KeyCode::Char('>') => EditorMode::IndentPrefix,
+            _ => EditorMode::DedentPrefix,
+        };
+        Ok(vec![CommandEvent::mode_change(mode)])
+    }
+
+    fn name(&self) -> &'static str {
+        "EnterShiftPrefix"
+    }
+}
+
+/// Indent after '>': `>>` shifts the pending count of lines right by one shiftwidth
+///
+/// Any other key cancels back to normal mode.
+pub struct IndentLinesCommand;
+
+impl Command for IndentLinesCommand {
+    fn is_relevant(&self, context: &CommandContext, _event: &KeyEvent) -> bool {
+        context.state.current_mode == EditorMode::IndentPrefix
+    }
+
+    fn execute(&self, event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let mut events = vec![CommandEvent::mode_change(EditorMode::Normal)];
+        if event.code == KeyCode::Char('>') {
+            events.push(CommandEvent::IndentLinesRequested {
+                count: context.state.pending_count.unwrap_or(1),
+            });
+        }
+        Ok(events)
+    }
+
+    fn name(&self) -> &'static str {
+        "IndentLines"
+    }
+}
+
+/// Dedent after '<': `<<` shifts the pending count of lines left by one shiftwidth
+///
+/// Any other key cancels back to normal mode.
+pub struct DedentLinesCommand;
+
+impl Command for DedentLinesCommand {
+    fn is_relevant(&self, context: &CommandContext, _event: &KeyEvent) -> bool {
+        context.state.current_mode == EditorMode::DedentPrefix
+    }
+
+    fn execute(&self, event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let mut events = vec![CommandEvent::mode_change(EditorMode::Normal)];
+        if event.code == KeyCode::Char('<') {
+            events.push(CommandEvent::DedentLinesRequested {
+                count: context.state.pending_count.unwrap_or(1),
+            });
+        }
+        Ok(events)
+    }
+
+    fn name(&self) -> &'static str {
+        "DedentLines"
+    }
+}
+
+/// Start a case operator on 'u', 'U' or '~' after 'g' (`gu`, `gU`, `g~`)
+pub struct EnterCaseOperatorCommand;
+
+impl Command for EnterCaseOperatorCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char(key) if CaseChange::from_key(key).is_some())
+            && context.state.current_mode == EditorMode::GPrefix
+            && context.state.current_pane == Pane::Request
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let KeyCode::Char(key) = event.code else {
+            return Ok(vec![]);
+        };
+        let Some(change) = CaseChange::from_key(key) else {
+            return Ok(vec![]);
+        };
+        Ok(vec![CommandEvent::CaseOperatorRequested { change }])
+    }
+
+    fn name(&self) -> &'static str {
+        "EnterCaseOperator"
+    }
+}
+
+/// Change case over the motion typed after `gu`, `gU` or `g~`
+///
+/// `w`, `b`, `e`, `0` and `$` are the motions, made the pending count of
+/// times; the operator key again (`gUU`, `guu`, `g~~`) covers the pending
+/// count of whole lines. Any other key cancels back to normal mode.
+pub struct ChangeCaseCommand;
+
+impl Command for ChangeCaseCommand {
+    fn is_relevant(&self, context: &CommandContext, _event: &KeyEvent) -> bool {
+        CaseChange::from_mode(context.state.current_mode).is_some()
+    }
+
+    fn execute(&self, event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let mut events = vec![CommandEvent::mode_change(EditorMode::Normal)];
+        let (Some(change), KeyCode::Char(key)) = (
+            CaseChange::from_mode(context.state.current_mode),
+            event.code,
+        ) else {
+            return Ok(events);
+        };
+        if event.modifiers.contains(KeyModifiers::CONTROL) {
+            return Ok(events);
+        }
+
+        use super::MovementDirection;
+        let count = context.state.pending_count.unwrap_or(1);
+        let motion = match key {
+            key if key == change.key() => {
+                events.push(CommandEvent::ChangeCaseLinesRequested { change, count });
+                return Ok(events);
+            }
+            'w' => MovementDirection::WordForward,
+            'b' => MovementDirection::WordBackward,
+            'e' => MovementDirection::WordEnd,
+            '0' => MovementDirection::LineStart,
+            '$' => MovementDirection::LineEnd,
+            _ => return Ok(events),
+        };
+        events.push(CommandEvent::ChangeCaseRequested {
+            change,
+            motion,
+            count,
+        });
+        Ok(events)
+    }
+
+    fn name(&self) -> &'static str {
+        "ChangeCase"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::repl::commands::{CommandContext, MovementDirection, ViewModelSnapshot};
-    use crate::repl::events::{EditorMode, LogicalPosition, Pane};
+    use crate::repl::events::{EditorMode, KeyBinding, LogicalPosition, Pane};
     use crossterm::event::KeyModifiers;
 
     fn create_test_key_event(code: KeyCode) -> KeyEvent {
@@ -192,6 +737,10 @@ mod tests {
                 terminal_dimensions: (80, 24),
                 expand_tab: false,
                 tab_width: 4,
+                pending_count: None,
+                command_window_open: false,
+                whichwrap: String::new(),
+                execute_key: KeyBinding::ENTER,
             },
         }
     }
@@ -435,4 +984,356 @@ mod tests {
             panic!("Expected TextInsertRequested event");
         }
     }
+
+    #[test]
+    fn join_lines_should_be_relevant_for_shift_j_in_normal_mode() {
+        let mut context = create_test_context();
+        context.state.current_mode = EditorMode::Normal;
+        let cmd = JoinLinesCommand;
+        let event = KeyEvent::new(KeyCode::Char('J'), KeyModifiers::SHIFT);
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![CommandEvent::JoinLinesRequested]
+        );
+    }
+
+    #[test]
+    fn join_lines_should_not_be_relevant_in_insert_mode() {
+        let context = create_test_context();
+        let cmd = JoinLinesCommand;
+        let event = KeyEvent::new(KeyCode::Char('J'), KeyModifiers::SHIFT);
+
+        assert!(!cmd.is_relevant(&context, &event));
+    }
+
+    #[test]
+    fn repeat_last_change_should_be_relevant_for_dot_in_normal_mode_only() {
+        let mut context = create_test_context();
+        let cmd = RepeatLastChangeCommand;
+        let event = KeyEvent::new(KeyCode::Char('.'), KeyModifiers::NONE);
+        assert!(!cmd.is_relevant(&context, &event));
+
+        context.state.current_mode = EditorMode::Normal;
+        assert!(cmd.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![CommandEvent::RepeatLastChangeRequested]
+        );
+    }
+
+    #[test]
+    fn delete_word_before_cursor_should_request_word_backward_delete_on_ctrl_w() {
+        let context = create_test_context();
+        let cmd = DeleteWordBeforeCursorCommand;
+        let event = KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL);
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![CommandEvent::TextDeleteRequested {
+                position: LogicalPosition { line: 0, column: 0 },
+                amount: 1,
+                direction: MovementDirection::WordBackward,
+            }]
+        );
+    }
+
+    #[test]
+    fn delete_to_line_start_should_request_line_start_delete_on_ctrl_u() {
+        let context = create_test_context();
+        let cmd = DeleteToLineStartCommand;
+        let event = KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL);
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![CommandEvent::TextDeleteRequested {
+                position: LogicalPosition { line: 0, column: 0 },
+                amount: 1,
+                direction: MovementDirection::LineStart,
+            }]
+        );
+    }
+
+    #[test]
+    fn enter_insert_register_prefix_should_be_relevant_for_ctrl_r_in_insert_mode() {
+        let context = create_test_context();
+        let cmd = EnterInsertRegisterPrefixCommand;
+        let event = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL);
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::InsertRegisterPrefix)]
+        );
+    }
+
+    #[test]
+    fn insert_register_should_request_named_register_and_return_to_insert_mode() {
+        let mut context = create_test_context();
+        context.state.current_mode = EditorMode::InsertRegisterPrefix;
+        let cmd = InsertRegisterCommand;
+        let event = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Insert),
+                CommandEvent::InsertRegisterRequested { register: 'a' },
+            ]
+        );
+    }
+
+    #[test]
+    fn insert_last_inserted_should_be_relevant_for_ctrl_a_in_insert_mode_only() {
+        let mut context = create_test_context();
+        let cmd = InsertLastInsertedCommand;
+        let event = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL);
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![CommandEvent::InsertLastInsertedRequested]
+        );
+
+        context.state.current_mode = EditorMode::Normal;
+        assert!(!cmd.is_relevant(&context, &event));
+    }
+
+    #[test]
+    fn indent_and_dedent_line_should_be_relevant_for_ctrl_keys_in_insert_mode_only() {
+        let mut context = create_test_context();
+        let ctrl_t = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL);
+        let ctrl_d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+
+        assert!(IndentLineCommand.is_relevant(&context, &ctrl_t));
+        assert!(DedentLineCommand.is_relevant(&context, &ctrl_d));
+        assert!(!IndentLineCommand.is_relevant(&context, &ctrl_d));
+        assert_eq!(
+            IndentLineCommand.execute(ctrl_t, &context).unwrap(),
+            vec![CommandEvent::IndentLineRequested]
+        );
+        assert_eq!(
+            DedentLineCommand.execute(ctrl_d, &context).unwrap(),
+            vec![CommandEvent::DedentLineRequested]
+        );
+
+        context.state.current_mode = EditorMode::Normal;
+        assert!(!IndentLineCommand.is_relevant(&context, &ctrl_t));
+        assert!(!DedentLineCommand.is_relevant(&context, &ctrl_d));
+    }
+
+    #[test]
+    fn insert_register_should_cancel_on_escape() {
+        let mut context = create_test_context();
+        context.state.current_mode = EditorMode::InsertRegisterPrefix;
+        let cmd = InsertRegisterCommand;
+        let event = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::Insert)]
+        );
+    }
+
+    #[test]
+    fn count_digit_should_start_with_non_zero_digit() {
+        let mut context = create_test_context();
+        context.state.current_mode = EditorMode::VisualLine;
+        let cmd = CountDigitCommand;
+
+        assert!(cmd.is_relevant(&context, &create_test_key_event(KeyCode::Char('2'))));
+        assert!(!cmd.is_relevant(&context, &create_test_key_event(KeyCode::Char('0'))));
+
+        context.state.pending_count = Some(1);
+        let event = create_test_key_event(KeyCode::Char('0'));
+        assert!(cmd.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![CommandEvent::CountDigitRequested { digit: 0 }]
+        );
+    }
+
+    #[test]
+    fn indent_selection_should_repeat_by_pending_count() {
+        let mut context = create_test_context();
+        context.state.current_mode = EditorMode::VisualLine;
+        context.state.pending_count = Some(2);
+        let cmd = IndentSelectionCommand;
+        let event = create_test_key_event(KeyCode::Char('>'));
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![CommandEvent::IndentSelectionRequested { count: 2 }]
+        );
+    }
+
+    #[test]
+    fn dedent_selection_should_default_to_one_shiftwidth() {
+        let mut context = create_test_context();
+        context.state.current_mode = EditorMode::Visual;
+        let cmd = DedentSelectionCommand;
+        let event = create_test_key_event(KeyCode::Char('<'));
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![CommandEvent::DedentSelectionRequested { count: 1 }]
+        );
+    }
+
+    #[test]
+    fn equal_equal_should_reindent_pending_count_of_lines() {
+        let mut context = create_test_context();
+        context.state.current_mode = EditorMode::Normal;
+        let event = create_test_key_event(KeyCode::Char('='));
+
+        assert!(EnterEqualPrefixCommand.is_relevant(&context, &event));
+        assert_eq!(
+            EnterEqualPrefixCommand.execute(event, &context).unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::EqualPrefix)]
+        );
+
+        context.state.current_mode = EditorMode::EqualPrefix;
+        context.state.pending_count = Some(3);
+        assert_eq!(
+            ReindentLinesCommand.execute(event, &context).unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::ReindentLinesRequested { count: Some(3) },
+            ]
+        );
+    }
+
+    #[test]
+    fn equal_g_should_reindent_to_last_line_and_other_keys_cancel() {
+        let mut context = create_test_context();
+        context.state.current_mode = EditorMode::EqualPrefix;
+
+        let event = KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT);
+        assert!(ReindentLinesCommand.is_relevant(&context, &event));
+        assert_eq!(
+            ReindentLinesCommand.execute(event, &context).unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::ReindentLinesRequested { count: None },
+            ]
+        );
+
+        let event = create_test_key_event(KeyCode::Esc);
+        assert_eq!(
+            ReindentLinesCommand.execute(event, &context).unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::Normal)]
+        );
+    }
+
+    #[test]
+    fn shift_prefix_should_shift_pending_count_of_lines_on_repeated_key() {
+        let mut context = create_test_context();
+        context.state.current_mode = EditorMode::Normal;
+        let indent = create_test_key_event(KeyCode::Char('>'));
+        let dedent = create_test_key_event(KeyCode::Char('<'));
+
+        assert!(EnterShiftPrefixCommand.is_relevant(&context, &indent));
+        assert_eq!(
+            EnterShiftPrefixCommand.execute(indent, &context).unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::IndentPrefix)]
+        );
+        assert_eq!(
+            EnterShiftPrefixCommand.execute(dedent, &context).unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::DedentPrefix)]
+        );
+
+        context.state.current_mode = EditorMode::IndentPrefix;
+        context.state.pending_count = Some(3);
+        assert!(IndentLinesCommand.is_relevant(&context, &indent));
+        assert!(!DedentLinesCommand.is_relevant(&context, &dedent));
+        assert_eq!(
+            IndentLinesCommand.execute(indent, &context).unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::IndentLinesRequested { count: 3 },
+            ]
+        );
+        // `><` is no command, it just cancels
+        assert_eq!(
+            IndentLinesCommand.execute(dedent, &context).unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::Normal)]
+        );
+
+        context.state.current_mode = EditorMode::DedentPrefix;
+        context.state.pending_count = None;
+        assert_eq!(
+            DedentLinesCommand.execute(dedent, &context).unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::DedentLinesRequested { count: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn case_operator_should_change_case_over_motion_or_repeated_key() {
+        let mut context = create_test_context();
+        context.state.current_mode = EditorMode::GPrefix;
+        let upper = create_test_key_event(KeyCode::Char('U'));
+
+        assert!(EnterCaseOperatorCommand.is_relevant(&context, &upper));
+        assert_eq!(
+            EnterCaseOperatorCommand.execute(upper, &context).unwrap(),
+            vec![CommandEvent::CaseOperatorRequested {
+                change: CaseChange::Upper
+            }]
+        );
+        context.state.current_pane = Pane::Response;
+        assert!(!EnterCaseOperatorCommand.is_relevant(&context, &upper));
+
+        context.state.current_pane = Pane::Request;
+        context.state.current_mode = EditorMode::UpperCasePrefix;
+        context.state.pending_count = Some(2);
+        assert!(ChangeCaseCommand.is_relevant(&context, &upper));
+        assert_eq!(
+            ChangeCaseCommand
+                .execute(create_test_key_event(KeyCode::Char('w')), &context)
+                .unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::ChangeCaseRequested {
+                    change: CaseChange::Upper,
+                    motion: MovementDirection::WordForward,
+                    count: 2,
+                },
+            ]
+        );
+        assert_eq!(
+            ChangeCaseCommand.execute(upper, &context).unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::ChangeCaseLinesRequested {
+                    change: CaseChange::Upper,
+                    count: 2,
+                },
+            ]
+        );
+        // `gUx` is no command, it just cancels
+        assert_eq!(
+            ChangeCaseCommand
+                .execute(create_test_key_event(KeyCode::Char('x')), &context)
+                .unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::Normal)]
+        );
+    }
+
+    #[test]
+    fn indent_selection_should_not_be_relevant_in_normal_mode() {
+        let mut context = create_test_context();
+        context.state.current_mode = EditorMode::Normal;
+        let cmd = IndentSelectionCommand;
+
+        assert!(!cmd.is_relevant(&context, &create_test_key_event(KeyCode::Char('>'))));
+    }
 }
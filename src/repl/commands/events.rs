@@ -4,6 +4,7 @@
 //! Commands produce these events, and the controller applies them to the ViewModel.
 //! This maintains proper separation of concerns - commands suggest, controller decides.
 
+use crate::repl::commands::LineRange;
 use crate::repl::events::{EditorMode, LogicalPosition, Pane};
 
 /// Type alias for HTTP headers to reduce complexity
@@ -16,12 +17,208 @@ pub enum Setting {
     Wrap,
     /// Line numbers display setting
     LineNumbers,
+    /// Scrollbar column display setting
+    Scrollbar,
+    /// Cursor column highlight setting
+    CursorColumn,
     /// System clipboard integration
     Clipboard,
+    /// Mirror every yank to the system clipboard (`clipboard=unnamedplus`)
+    ClipboardUnnamedPlus,
     /// Tab stop width
     TabStop,
     /// Expand tab setting (insert spaces instead of tab)
     ExpandTab,
+    /// Join spaces setting (two spaces after a sentence end when joining lines)
+    JoinSpaces,
+    /// Spell check header names when sending a request
+    Spell,
+    /// Refuse to send a request that gives a header more than once
+    StrictHeaders,
+    /// Keep a backup of the previous file contents when `:w` overwrites a file
+    Backup,
+    /// Write files in place (`yes`, On) instead of renaming a new copy over them (`no`, Off)
+    BackupCopy,
+    /// Ask before destructive ex commands throw away text or changes
+    Confirm,
+    /// Briefly highlight yanked text
+    HlYank,
+    /// Flash the matching bracket when a closing bracket is typed
+    ShowMatch,
+    /// Tenths of a second the matching bracket flashes (0 turns it off)
+    MatchTime,
+    /// Periodically write the request buffer to its file
+    AutoSave,
+    /// Seconds between autosaves
+    AutoSaveInterval,
+    /// Write the request buffer to its file before sending it or leaving its pane
+    AutoWrite,
+    /// Reload the request buffer when its file is changed by another program
+    AutoRead,
+    /// Milliseconds to wait for the rest of a multi-key sequence
+    TimeoutLen,
+    /// Move the cursor to the first match while a search query is typed
+    IncSearch,
+    /// Collapse the echoed request section of a verbose response
+    FoldRequest,
+    /// Largest response body in bytes shown in the response pane (0 for no limit)
+    MaxResponse,
+    /// Rebalance the request/response split when the terminal is resized
+    EqualAlways,
+    /// Show only the focused pane, full-screen, instead of splitting
+    Compact,
+    /// Show completion candidates above the command line while cycling with Tab
+    WildMenu,
+    /// Status line visibility (`laststatus=0` hides it, `laststatus=2` shows it)
+    LastStatus,
+    /// Show the mode (`-- INSERT --`) on the left of the status line
+    ShowMode,
+    /// Set the terminal window title to the profile and request
+    Title,
+    /// Wrap long response lines while `wrap` is on, instead of scrolling sideways
+    ResponseWrap,
+}
+
+/// Operators that can act on the text up to a search match (`d/`, `y/`, `c/`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    /// Delete the text, yanking it
+    Delete,
+    /// Yank the text without changing the buffer
+    Yank,
+    /// Delete the text and enter insert mode
+    Change,
+}
+
+/// Character finds within the cursor line (`f`, `F`, `t`, `T`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharFind {
+    /// Onto the next occurrence (`f`)
+    Forward,
+    /// Onto the previous occurrence (`F`)
+    Backward,
+    /// Just before the next occurrence (`t`)
+    TillForward,
+    /// Just after the previous occurrence (`T`)
+    TillBackward,
+}
+
+impl CharFind {
+    /// Get the find started by a key, if it starts one
+    pub fn from_key(key: char) -> Option<Self> {
+        match key {
+            'f' => Some(Self::Forward),
+            'F' => Some(Self::Backward),
+            't' => Some(Self::TillForward),
+            'T' => Some(Self::TillBackward),
+            _ => None,
+        }
+    }
+
+    /// Get the key that starts this find
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Forward => "f",
+            Self::Backward => "F",
+            Self::TillForward => "t",
+            Self::TillBackward => "T",
+        }
+    }
+
+    /// Get the same find going the other way, for `,`
+    pub fn reversed(self) -> Self {
+        match self {
+            Self::Forward => Self::Backward,
+            Self::Backward => Self::Forward,
+            Self::TillForward => Self::TillBackward,
+            Self::TillBackward => Self::TillForward,
+        }
+    }
+}
+
+/// Case operators (`gu`, `gU`, `g~`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseChange {
+    /// Make letters lowercase (`gu`)
+    Lower,
+    /// Make letters uppercase (`gU`)
+    Upper,
+    /// Swap the case of letters (`g~`)
+    Toggle,
+}
+
+impl CaseChange {
+    /// Get the case operator a key after `g` starts, if it starts one
+    pub fn from_key(key: char) -> Option<Self> {
+        match key {
+            'u' => Some(Self::Lower),
+            'U' => Some(Self::Upper),
+            '~' => Some(Self::Toggle),
+            _ => None,
+        }
+    }
+
+    /// Get the operator waiting for its motion in `mode`, if any
+    pub fn from_mode(mode: EditorMode) -> Option<Self> {
+        match mode {
+            EditorMode::LowerCasePrefix => Some(Self::Lower),
+            EditorMode::UpperCasePrefix => Some(Self::Upper),
+            EditorMode::ToggleCasePrefix => Some(Self::Toggle),
+            _ => None,
+        }
+    }
+
+    /// Get the mode that waits for this operator's motion
+    pub fn prefix_mode(self) -> EditorMode {
+        match self {
+            Self::Lower => EditorMode::LowerCasePrefix,
+            Self::Upper => EditorMode::UpperCasePrefix,
+            Self::Toggle => EditorMode::ToggleCasePrefix,
+        }
+    }
+
+    /// Get the key after `g` that starts this operator, which doubled covers whole lines (`gUU`)
+    pub fn key(self) -> char {
+        match self {
+            Self::Lower => 'u',
+            Self::Upper => 'U',
+            Self::Toggle => '~',
+        }
+    }
+
+    /// Change the case of `ch`
+    ///
+    /// Characters whose other case is more than one character, like `ß`, are
+    /// kept as they are so the text keeps its length.
+    pub fn apply(self, ch: char) -> char {
+        let lower = || only_char(ch.to_lowercase()).unwrap_or(ch);
+        let upper = || only_char(ch.to_uppercase()).unwrap_or(ch);
+        match self {
+            Self::Lower => lower(),
+            Self::Upper => upper(),
+            Self::Toggle if ch.is_uppercase() => lower(),
+            Self::Toggle => upper(),
+        }
+    }
+}
+
+/// Get the only character of `chars`, if there is exactly one
+fn only_char(mut chars: impl Iterator<Item = char>) -> Option<char> {
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) => Some(ch),
+        _ => None,
+    }
+}
+
+/// Actions of the `:template` ex command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateAction {
+    /// Save the request buffer as the named template
+    Save(String),
+    /// Replace the request buffer with the named template
+    Load(String),
+    /// List saved template names
+    List,
 }
 
 /// Values for settings
@@ -83,6 +280,12 @@ pub enum CommandEvent {
     /// Request to quit application
     QuitRequested,
 
+    /// Request to write the request buffer to its file and quit (:wq, :x, ZZ)
+    ///
+    /// Unless `always_write` is set, the file is only written when the buffer has
+    /// unsaved changes.
+    WriteQuitRequested { always_write: bool },
+
     /// Request to add character to ex command buffer
     ExCommandCharRequested { ch: char },
 
@@ -92,9 +295,87 @@ pub enum CommandEvent {
     /// Request to execute ex command in buffer
     ExCommandExecuteRequested,
 
+    /// Request to complete the ex command argument or cycle its candidates
+    /// (Tab moves forward, Shift-Tab backward)
+    ExCommandCompleteRequested { forward: bool },
+
+    /// Request to open the command-line window listing the ex command history (q:)
+    CommandWindowOpenRequested,
+
+    /// Request to close the command-line window and execute the line under the cursor
+    CommandWindowExecuteRequested,
+
+    /// Request to enter search mode, forward (/ key) or backward (? key)
+    SearchStartRequested { forward: bool },
+
+    /// Request to enter search mode as the motion of an operator (`d/`, `y/`, `c/`)
+    OperatorSearchStartRequested { operator: Operator },
+
+    /// Request to add character to search query
+    SearchCharRequested { ch: char },
+
+    /// Request to backspace in search query
+    SearchBackspaceRequested,
+
+    /// Request to jump to the first match of the search query
+    SearchExecuteRequested,
+
+    /// Request to abandon the search and restore the cursor
+    SearchCancelRequested,
+
+    /// Request to jump to the next (`n`) or previous (`N`) match of the last search
+    SearchNextRequested { forward: bool },
+
     /// Request to show profile information in status bar
     ShowProfileRequested,
 
+    /// Request to show the buffer name and cursor line in the status line (Ctrl-g)
+    FileInfoRequested,
+
+    /// Request to list every setting with its current value (:set all)
+    ShowSettingsRequested,
+
+    /// Request to list the messages shown so far (`:messages`)
+    ShowMessagesRequested,
+
+    /// Request to forget the messages shown so far (`:messages clear`)
+    ClearMessagesRequested,
+
+    /// Request to list register contents, optionally only the given registers (`:registers [names]`)
+    ShowRegistersRequested { names: Option<String> },
+
+    /// Request to write the request buffer to a file (append when `append` is set)
+    WriteBufferRequested { path: String, append: bool },
+
+    /// Request to load a file into the request buffer (`:e [file]`, `:e!` to discard changes)
+    EditFileRequested { path: Option<String>, force: bool },
+
+    /// Request to save the session to a file (:mksession [file] and :mksession! [file])
+    MakeSessionRequested { path: Option<String>, force: bool },
+
+    /// Request to run each line of a file as an ex command (:source file)
+    SourceFileRequested { path: Option<String> },
+
+    /// Request to write the full body of the last response to a file (:save)
+    SaveResponseRequested { path: String },
+
+    /// Request to show the resolved request without sending it (:dryrun)
+    DryRunRequested,
+
+    /// Request to run the request in `file` and bind the value at `path` of
+    /// its response to `${variable}` (:chain)
+    ChainRequested {
+        file: String,
+        path: String,
+        variable: String,
+    },
+
+    /// Request to check request header names for typos and repeats
+    LintHeadersRequested,
+
+    /// Request to save, load or list request templates
+    TemplateRequested { action: TemplateAction },
+
     /// Request to change a setting (wrap, line numbers, etc.)
     SettingChangeRequested {
         setting: Setting,
@@ -122,6 +403,130 @@ pub enum CommandEvent {
     /// Request to yank (copy) entire current line without deleting
     YankCurrentLineRequested,
 
+    /// Request to yank the whole response body regardless of cursor position (:yankresponse)
+    YankResponseRequested,
+
+    /// Request to yank the JSON key or value under the cursor in the response (:yankvalue)
+    YankJsonValueRequested,
+
+    /// Request to append a digit to the pending count in visual mode
+    CountDigitRequested { digit: usize },
+
+    /// Request to indent the selected lines by `count` shiftwidths (> in visual mode)
+    IndentSelectionRequested { count: usize },
+
+    /// Request to dedent the selected lines by `count` shiftwidths (< in visual mode)
+    DedentSelectionRequested { count: usize },
+
+    /// Request to indent the cursor line by one shiftwidth (Ctrl-t in insert mode)
+    IndentLineRequested,
+
+    /// Request to dedent the cursor line by one shiftwidth (Ctrl-d in insert mode)
+    DedentLineRequested,
+
+    /// Request to indent `count` lines from the cursor down by one shiftwidth (`>>`)
+    IndentLinesRequested { count: usize },
+
+    /// Request to dedent `count` lines from the cursor down by one shiftwidth (`<<`)
+    DedentLinesRequested { count: usize },
+
+    /// Request to join current line with the next line
+    JoinLinesRequested,
+
+    /// Request to reindent lines from the cursor down through `equalprg` (`=`)
+    ///
+    /// `Some(count)` covers `count` lines (`3==`), `None` runs to the last line (`=G`).
+    ReindentLinesRequested { count: Option<usize> },
+
+    /// Request to set the external formatter used by `=` (`:set equalprg=<command>`)
+    EqualPrgChangeRequested { program: String },
+
+    /// Request to set the shell external commands run in (`:set shell=<path>`)
+    ShellChangeRequested { shell: String },
+
+    /// Request to set the terminal window title pattern (`:set titlestring=<pattern>`)
+    TitleStringChangeRequested { title: String },
+
+    /// Request to set which keys move across line boundaries (`:set whichwrap=<flags>`)
+    WhichWrapChangeRequested { flags: String },
+
+    /// Request to set the key that sends the request (`:set executekey=<key>`)
+    ExecuteKeyChangeRequested { key: String },
+
+    /// Request to set the encoding files are read and written in (`:set fileencoding=<name>`)
+    FileEncodingChangeRequested { encoding: String },
+
+    /// Request to insert the contents of a register at the cursor (Ctrl-r in insert mode)
+    InsertRegisterRequested { register: char },
+
+    /// Request to insert the text of the previous insert session (Ctrl-a in insert mode)
+    InsertLastInsertedRequested,
+
+    /// Key typed after Ctrl-v in insert mode, as the character it inserts literally
+    ///
+    /// `None` for keys that stand for no character, which just end the prefix.
+    InsertLiteralRequested { ch: Option<char> },
+
+    /// Request to move to the `count`-th `target` character on the line (`f` motion)
+    FindCharRequested { target: char, count: usize },
+
+    /// Request to wait for the target of a character find after `f`, `F`, `t` or `T`
+    FindCharStartRequested { find: CharFind },
+
+    /// Request to repeat the last character find (`;`), or reverse it (`,`)
+    RepeatFindCharRequested { reverse: bool, count: usize },
+
+    /// Request to jump to the start (`` `[ ``) or end (`` `] ``) of the last
+    /// inserted or pasted text
+    JumpToChangeMarkRequested { end: bool },
+
+    /// Request to remember the cursor position in the current pane as `mark` (`ma`)
+    SetMarkRequested { mark: char },
+
+    /// Request to jump to `mark`: to its exact position (`` `a ``), or with
+    /// `line_wise` to the first non-blank of its line (`'a`)
+    JumpToMarkRequested { mark: char, line_wise: bool },
+
+    /// Request to select the last inserted or pasted text (gV command)
+    SelectLastChangeRequested,
+
+    /// Request to delete through the `count`-th `target` character on the line (`df`)
+    DeleteToCharRequested { target: char, count: usize },
+
+    /// Request to delete `count` "a word" objects at the cursor (`daw`)
+    DeleteAroundWordRequested { count: usize },
+
+    /// Request to start a case operator after `g` (`gu`, `gU`, `g~`)
+    ///
+    /// Coming from a visual mode it changes the selection; otherwise it waits
+    /// for a motion.
+    CaseOperatorRequested { change: CaseChange },
+
+    /// Request to change the case of the text `count` motions cover (`gUw`, `g~2e`)
+    ChangeCaseRequested {
+        change: CaseChange,
+        motion: MovementDirection,
+        count: usize,
+    },
+
+    /// Request to change the case of `count` lines from the cursor (`gUU`, `2guu`)
+    ChangeCaseLinesRequested { change: CaseChange, count: usize },
+
+    /// Request to delete a range of lines into a register (`:[range]d [register]`)
+    DeleteLinesRequested {
+        range: LineRange,
+        register: Option<char>,
+    },
+
+    /// Request to yank a range of lines into a register (`:[range]y [register]`)
+    YankLinesRequested {
+        range: LineRange,
+        register: Option<char>,
+    },
+
+    /// Request to use a register for the next delete, yank or paste (`"` in normal mode)
+    RegisterSelectRequested { register: char },
+
     /// Request to paste yanked text after cursor
     PasteAfterRequested,
 
@@ -143,6 +548,9 @@ pub enum CommandEvent {
     /// Request to repeat the last visual selection (gv command)
     RepeatVisualSelectionRequested,
 
+    /// Request to make the last change again at the cursor (`.`)
+    RepeatLastChangeRequested,
+
     /// No action needed (for commands that only query state)
     NoAction,
 }
@@ -154,6 +562,10 @@ pub enum MovementDirection {
     Right,
     Up,
     Down,
+    /// Up one display line within wrapped text (gk)
+    DisplayLineUp,
+    /// Down one display line within wrapped text (gj)
+    DisplayLineDown,
     LineStart,
     LineEnd,
     LineEndForAppend, // Special case for 'A' command - positions AFTER last character
@@ -174,6 +586,10 @@ pub enum MovementDirection {
     HalfPageUp,
     /// Move to a specific line number (1-based)
     LineNumber(usize),
+    /// Left, continuing onto the end of the previous line at a line start (`whichwrap`)
+    LeftWrap,
+    /// Right, continuing onto the start of the next line at a line end (`whichwrap`)
+    RightWrap,
 }
 
 impl CommandEvent {
@@ -270,6 +686,11 @@ impl CommandEvent {
         Self::YankCurrentLineRequested
     }
 
+    /// Create a join lines event
+    pub fn join_lines() -> Self {
+        Self::JoinLinesRequested
+    }
+
     /// Create a paste after event
     pub fn paste_after() -> Self {
         Self::PasteAfterRequested
@@ -304,12 +725,97 @@ impl CommandEvent {
     pub fn repeat_visual_selection() -> Self {
         Self::RepeatVisualSelectionRequested
     }
+
+    /// Check whether this event changes the text of the buffer
+    pub fn is_buffer_change(&self) -> bool {
+        matches!(
+            self,
+            Self::TextInsertRequested { .. }
+                | Self::TextDeleteRequested { .. }
+                | Self::CutCharacterRequested
+                | Self::CutToEndOfLineRequested
+                | Self::CutCurrentLineRequested
+                | Self::JoinLinesRequested
+                | Self::PasteAfterRequested
+                | Self::PasteAtCursorRequested
+                | Self::DeleteToCharRequested { .. }
+                | Self::DeleteAroundWordRequested { .. }
+                | Self::ChangeCaseRequested { .. }
+                | Self::ChangeCaseLinesRequested { .. }
+                | Self::ReindentLinesRequested { .. }
+                | Self::IndentLineRequested
+                | Self::DedentLineRequested
+                | Self::IndentLinesRequested { .. }
+                | Self::DedentLinesRequested { .. }
+        )
+    }
+
+    /// Check whether this event starts an insert session
+    pub fn starts_insert(&self) -> bool {
+        *self == Self::mode_change(EditorMode::Insert)
+    }
+}
+
+/// The last change made from normal mode, for `.` to make again
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedChange {
+    /// Events of the command that made the change, with its count and target
+    pub events: Vec<CommandEvent>,
+    /// Text typed in the insert session the command started, if it started one
+    pub inserted_text: Option<String>,
+}
+
+impl RecordedChange {
+    /// Record the events of a command, waiting for the text of its insert session if it starts one
+    pub fn new(events: Vec<CommandEvent>) -> Self {
+        let inserted_text = events
+            .iter()
+            .any(CommandEvent::starts_insert)
+            .then(String::new);
+        Self {
+            events,
+            inserted_text,
+        }
+    }
+
+    /// Get the events that make the change again at the cursor
+    ///
+    /// A change that started an insert session types the same text and goes
+    /// back to normal mode, like pressing Escape did.
+    pub fn replay_events(&self) -> Vec<CommandEvent> {
+        let mut events = self.events.clone();
+        if let Some(text) = &self.inserted_text {
+            if !text.is_empty() {
+                events.push(CommandEvent::TextInsertRequested {
+                    text: text.clone(),
+                    position: LogicalPosition::zero(),
+                });
+            }
+            events.push(CommandEvent::mode_change(EditorMode::Normal));
+        }
+        events
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn case_change_should_keep_characters_without_single_other_case() {
+        assert_eq!(CaseChange::Upper.apply('é'), 'É');
+        assert_eq!(CaseChange::Lower.apply('Ä'), 'ä');
+        assert_eq!(CaseChange::Toggle.apply('Σ'), 'σ');
+        assert_eq!(CaseChange::Toggle.apply('x'), 'X');
+        // `ß` uppercases to "SS", so it stays
+        assert_eq!(CaseChange::Upper.apply('ß'), 'ß');
+        assert_eq!(CaseChange::Upper.apply('日'), '日');
+        assert_eq!(
+            CaseChange::from_key('~').map(CaseChange::prefix_mode),
+            Some(EditorMode::ToggleCasePrefix)
+        );
+    }
+
     #[test]
     fn command_event_should_create_cursor_move() {
         let event = CommandEvent::cursor_move(MovementDirection::Left);
@@ -339,6 +845,35 @@ mod tests {
         assert_eq!(event, CommandEvent::RestorePreviousModeRequested);
     }
 
+    #[test]
+    fn recorded_change_should_replay_typed_text_of_its_insert_session() {
+        let append = vec![
+            CommandEvent::cursor_move(MovementDirection::LineEndForAppend),
+            CommandEvent::mode_change(EditorMode::Insert),
+        ];
+        let mut change = RecordedChange::new(append.clone());
+        assert_eq!(change.inserted_text.as_deref(), Some(""));
+        change.inserted_text = Some(";".to_string());
+
+        let mut expected = append;
+        expected.push(CommandEvent::TextInsertRequested {
+            text: ";".to_string(),
+            position: LogicalPosition::zero(),
+        });
+        expected.push(CommandEvent::mode_change(EditorMode::Normal));
+        assert_eq!(change.replay_events(), expected);
+
+        // Changes made without inserting replay just their own events
+        let cut = RecordedChange::new(vec![CommandEvent::CutCharacterRequested]);
+        assert_eq!(cut.inserted_text, None);
+        assert_eq!(
+            cut.replay_events(),
+            vec![CommandEvent::CutCharacterRequested]
+        );
+        assert!(CommandEvent::CutCharacterRequested.is_buffer_change());
+        assert!(!CommandEvent::cursor_move(MovementDirection::Left).is_buffer_change());
+    }
+
     #[test]
     fn command_event_should_create_http_request() {
         let event =
@@ -6,7 +6,8 @@
 use anyhow::Result;
 
 use crate::repl::commands::{
-    CommandContext, CommandEvent, MovementDirection, Setting, SettingValue,
+    CommandContext, CommandEvent, LineRange, MovementDirection, Setting, SettingValue,
+    TemplateAction,
 };
 
 /// Trait for ex commands
@@ -38,6 +39,25 @@ impl ExCommand for QuitCommand {
     }
 }
 
+/// Write and quit command handler (for :wq and :x)
+pub struct WriteQuitCommand;
+
+impl ExCommand for WriteQuitCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "wq" || command == "x"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::WriteQuitRequested {
+            always_write: command == "wq",
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "WriteQuitCommand"
+    }
+}
+
 /// Set wrap command handler (for :set wrap on/off)
 pub struct SetWrapCommand;
 
@@ -66,6 +86,41 @@ impl ExCommand for SetWrapCommand {
     }
 }
 
+/// Set responsewrap command handler (for :set responsewrap on/off)
+///
+/// With `responsewrap` off the response pane keeps long lines on one row and
+/// scrolls sideways with `zl`/`zh`, while the request pane follows `wrap`.
+pub struct SetResponseWrapCommand;
+
+impl ExCommand for SetResponseWrapCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        matches!(
+            command,
+            "set responsewrap on"
+                | "set responsewrap off"
+                | "set responsewrap"
+                | "set noresponsewrap"
+        )
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = matches!(command, "set responsewrap on" | "set responsewrap");
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::ResponseWrap,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetResponseWrapCommand"
+    }
+}
+
 /// Set line numbers command handler (for :set number on/off)
 pub struct SetNumberCommand;
 
@@ -92,6 +147,58 @@ impl ExCommand for SetNumberCommand {
     }
 }
 
+/// Set scrollbar command handler (for :set scrollbar on/off)
+pub struct SetScrollbarCommand;
+
+impl ExCommand for SetScrollbarCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set scrollbar on" || command == "set scrollbar off"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set scrollbar on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::Scrollbar,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetScrollbarCommand"
+    }
+}
+
+/// Set cursor column command handler (for :set cursorcolumn on/off)
+pub struct SetCursorColumnCommand;
+
+impl ExCommand for SetCursorColumnCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set cursorcolumn on" || command == "set cursorcolumn off"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set cursorcolumn on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::CursorColumn,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetCursorColumnCommand"
+    }
+}
+
 /// Set clipboard integration command handler (for :set clipboard on/off)
 pub struct SetClipboardCommand;
 
@@ -118,6 +225,102 @@ impl ExCommand for SetClipboardCommand {
     }
 }
 
+/// Set clipboard sync command handler (for :set clipboard=unnamedplus and :set clipboard=)
+pub struct SetClipboardUnnamedPlusCommand;
+
+impl ExCommand for SetClipboardUnnamedPlusCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set clipboard=unnamedplus" || command == "set clipboard="
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set clipboard=unnamedplus";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::ClipboardUnnamedPlus,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetClipboardUnnamedPlusCommand"
+    }
+}
+
+/// List settings command handler (for :set all)
+pub struct SetAllCommand;
+
+impl ExCommand for SetAllCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set all"
+    }
+
+    fn execute(&self, _command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::ShowSettingsRequested])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetAllCommand"
+    }
+}
+
+/// Messages command handler (for :messages and :messages clear)
+pub struct MessagesCommand;
+
+impl ExCommand for MessagesCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        matches!(command, "messages" | "mes" | "messages clear" | "mes clear")
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        if command.ends_with(" clear") {
+            Ok(vec![CommandEvent::ClearMessagesRequested])
+        } else {
+            Ok(vec![CommandEvent::ShowMessagesRequested])
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "MessagesCommand"
+    }
+}
+
+/// Registers command handler (for :registers [names] and :reg [names])
+pub struct RegistersCommand;
+
+impl RegistersCommand {
+    /// Split off the register names given after the command, if any
+    fn parse(command: &str) -> Option<Option<String>> {
+        let (name, args) = command.split_once(' ').unwrap_or((command, ""));
+        if !matches!(name, "registers" | "reg") {
+            return None;
+        }
+        let names: String = args.chars().filter(|ch| !ch.is_whitespace()).collect();
+        Some((!names.is_empty()).then_some(names))
+    }
+}
+
+impl ExCommand for RegistersCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        Self::parse(command).is_some()
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        match Self::parse(command) {
+            Some(names) => Ok(vec![CommandEvent::ShowRegistersRequested { names }]),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "RegistersCommand"
+    }
+}
+
 /// Show profile command handler (for :show profile)
 pub struct ShowProfileCommand;
 
@@ -135,6 +338,28 @@ impl ExCommand for ShowProfileCommand {
     }
 }
 
+/// Set laststatus command handler (for :set laststatus=0 and :set laststatus=2)
+pub struct SetLastStatusCommand;
+
+impl ExCommand for SetLastStatusCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set laststatus=0" || command == "set laststatus=2"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let value = if command == "set laststatus=0" { 0 } else { 2 };
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::LastStatus,
+            value: SettingValue::Number(value),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetLastStatusCommand"
+    }
+}
+
 /// Set tabstop command handler (for :set tabstop <number>)
 pub struct SetTabstopCommand;
 
@@ -197,211 +422,2277 @@ impl ExCommand for SetExpandTabCommand {
     }
 }
 
-/// Type alias to reduce complexity for ex command collection
-type ExCommandCollection = Vec<Box<dyn ExCommand + Send>>;
-
-/// Go to line command handler (for :<number>)
-pub struct GoToLineCommand;
+/// Set joinspaces command handler (for :set joinspaces on/off)
+pub struct SetJoinSpacesCommand;
 
-impl ExCommand for GoToLineCommand {
+impl ExCommand for SetJoinSpacesCommand {
     fn can_handle(&self, command: &str) -> bool {
-        // Check if it's a valid line number
-        command.parse::<usize>().is_ok()
+        command == "set joinspaces on" || command == "set joinspaces off"
     }
 
     fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
-        if let Ok(line_number) = command.parse::<usize>() {
-            if line_number > 0 {
-                Ok(vec![CommandEvent::CursorMoveRequested {
-                    direction: MovementDirection::LineNumber(line_number),
-                    amount: 1,
-                }])
+        let enable = command == "set joinspaces on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::JoinSpaces,
+            value: if enable {
+                SettingValue::On
             } else {
-                tracing::warn!("Invalid line number: {}", line_number);
-                Ok(vec![])
-            }
-        } else {
-            Ok(vec![])
-        }
+                SettingValue::Off
+            },
+        }])
     }
 
     fn name(&self) -> &'static str {
-        "GoToLineCommand"
+        "SetJoinSpacesCommand"
     }
 }
 
-/// Registry for managing ex commands
-pub struct ExCommandRegistry {
-    commands: ExCommandCollection,
-}
+/// Set spell command handler (for :set spell on/off)
+pub struct SetSpellCommand;
 
-impl ExCommandRegistry {
-    /// Create a new ex command registry with all default commands
-    pub fn new() -> Self {
-        let commands: ExCommandCollection = vec![
-            Box::new(QuitCommand),
-            Box::new(SetWrapCommand),
-            Box::new(SetNumberCommand),
-            Box::new(SetClipboardCommand),
-            Box::new(SetTabstopCommand),
-            Box::new(SetExpandTabCommand),
-            Box::new(ShowProfileCommand),
-            Box::new(GoToLineCommand),
-        ];
+impl ExCommand for SetSpellCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set spell on" || command == "set spell off"
+    }
 
-        Self { commands }
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set spell on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::Spell,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
     }
 
-    /// Parse and execute an ex command string
-    pub fn execute_command(
-        &self,
-        command_str: &str,
-        context: &CommandContext,
-    ) -> Result<Vec<CommandEvent>> {
-        let trimmed = command_str.trim();
+    fn name(&self) -> &'static str {
+        "SetSpellCommand"
+    }
+}
 
-        // Empty command just exits command mode
-        if trimmed.is_empty() {
-            return Ok(vec![]);
-        }
+/// Set strictheaders command handler (for :set strictheaders on/off)
+pub struct SetStrictHeadersCommand;
 
-        // Find the first command that can handle this string
-        for command in &self.commands {
-            if command.can_handle(trimmed) {
-                tracing::debug!("Ex command '{}' handled by {}", trimmed, command.name());
-                return command.execute(trimmed, context);
-            }
-        }
+impl ExCommand for SetStrictHeadersCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set strictheaders on" || command == "set strictheaders off"
+    }
 
-        // Unknown command
-        tracing::warn!("Unknown ex command: {}", trimmed);
-        Ok(vec![])
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set strictheaders on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::StrictHeaders,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
     }
-}
 
-impl Default for ExCommandRegistry {
-    fn default() -> Self {
-        Self::new()
+    fn name(&self) -> &'static str {
+        "SetStrictHeadersCommand"
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::repl::commands::ViewModelSnapshot;
-    use crate::repl::events::{EditorMode, LogicalPosition, Pane};
+/// Set backup command handler (for :set backup on/off)
+pub struct SetBackupCommand;
 
-    fn create_test_context() -> CommandContext {
-        CommandContext {
-            state: ViewModelSnapshot {
-                current_mode: EditorMode::Normal,
-                current_pane: Pane::Request,
-                cursor_position: LogicalPosition::zero(),
-                request_text: String::new(),
-                response_text: String::new(),
-                terminal_dimensions: (80, 24),
-                expand_tab: false,
-                tab_width: 4,
-            },
-        }
+impl ExCommand for SetBackupCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set backup on" || command == "set backup off"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set backup on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::Backup,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetBackupCommand"
+    }
+}
+
+/// Set backupcopy command handler (for :set backupcopy=yes/no)
+pub struct SetBackupCopyCommand;
+
+impl ExCommand for SetBackupCopyCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set backupcopy=yes" || command == "set backupcopy=no"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let copy = command == "set backupcopy=yes";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::BackupCopy,
+            value: if copy {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetBackupCopyCommand"
+    }
+}
+
+/// Set confirm command handler (for :set confirm on/off)
+pub struct SetConfirmCommand;
+
+impl ExCommand for SetConfirmCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set confirm on" || command == "set confirm off"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set confirm on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::Confirm,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetConfirmCommand"
+    }
+}
+
+/// Set hlyank command handler (for :set hlyank on/off)
+pub struct SetHlYankCommand;
+
+impl ExCommand for SetHlYankCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set hlyank on" || command == "set hlyank off"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set hlyank on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::HlYank,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetHlYankCommand"
+    }
+}
+
+/// Set showmatch command handler (for :set showmatch on/off)
+pub struct SetShowMatchCommand;
+
+impl ExCommand for SetShowMatchCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set showmatch on" || command == "set showmatch off"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set showmatch on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::ShowMatch,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetShowMatchCommand"
+    }
+}
+
+/// Set autosave command handler (for :set autosave on/off)
+pub struct SetAutoSaveCommand;
+
+impl ExCommand for SetAutoSaveCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set autosave on" || command == "set autosave off"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set autosave on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::AutoSave,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetAutoSaveCommand"
+    }
+}
+
+/// Set autowrite command handler (for :set autowrite on/off)
+pub struct SetAutoWriteCommand;
+
+impl ExCommand for SetAutoWriteCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set autowrite on" || command == "set autowrite off"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set autowrite on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::AutoWrite,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetAutoWriteCommand"
+    }
+}
+
+/// Set autosave interval command handler (for :set autosaveinterval <seconds>)
+pub struct SetAutoSaveIntervalCommand;
+
+impl ExCommand for SetAutoSaveIntervalCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command
+            .strip_prefix("set autosaveinterval ")
+            .is_some_and(|value_str| value_str.parse::<usize>().is_ok())
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let Some(Ok(secs)) = command
+            .strip_prefix("set autosaveinterval ")
+            .map(str::parse::<usize>)
+        else {
+            return Ok(vec![]);
+        };
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::AutoSaveInterval,
+            value: SettingValue::Number(secs.max(1)),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetAutoSaveIntervalCommand"
+    }
+}
+
+/// Set autoread command handler (for :set autoread on/off)
+pub struct SetAutoReadCommand;
+
+impl ExCommand for SetAutoReadCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set autoread on" || command == "set autoread off"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set autoread on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::AutoRead,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetAutoReadCommand"
+    }
+}
+
+/// Set equalprg command handler (for :set equalprg=<command>)
+///
+/// Spaces in the command are escaped with a backslash as in Vim; an empty
+/// value goes back to the internal JSON layout.
+pub struct SetEqualPrgCommand;
+
+impl ExCommand for SetEqualPrgCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command.starts_with("set equalprg=")
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let Some(program) = command.strip_prefix("set equalprg=") else {
+            return Ok(vec![]);
+        };
+
+        Ok(vec![CommandEvent::EqualPrgChangeRequested {
+            program: program.replace("\\ ", " "),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetEqualPrgCommand"
+    }
+}
+
+/// Set shell command handler (for :set shell=<path>)
+///
+/// Spaces in the path are escaped with a backslash like `equalprg`; an empty
+/// value goes back to the default shell.
+pub struct SetShellCommand;
+
+impl ExCommand for SetShellCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command.starts_with("set shell=")
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let Some(shell) = command.strip_prefix("set shell=") else {
+            return Ok(vec![]);
+        };
+
+        Ok(vec![CommandEvent::ShellChangeRequested {
+            shell: shell.replace("\\ ", " "),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetShellCommand"
+    }
+}
+
+/// Set whichwrap command handler (for :set whichwrap=<flags>)
+///
+/// Flags are comma-separated: `h`/`l` for those keys, `<`/`>` for the arrow
+/// keys in normal and visual mode, `[`/`]` for the arrow keys in insert mode.
+pub struct SetWhichWrapCommand;
+
+impl ExCommand for SetWhichWrapCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command.starts_with("set whichwrap=")
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let Some(flags) = command.strip_prefix("set whichwrap=") else {
+            return Ok(vec![]);
+        };
+
+        Ok(vec![CommandEvent::WhichWrapChangeRequested {
+            flags: flags.trim().to_string(),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetWhichWrapCommand"
+    }
+}
+
+/// Set executekey command handler (for :set executekey=<key>)
+///
+/// The key is written in Vim's key notation, e.g. `<CR>` or `<C-CR>`.
+pub struct SetExecuteKeyCommand;
+
+impl ExCommand for SetExecuteKeyCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command.starts_with("set executekey=")
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let Some(key) = command.strip_prefix("set executekey=") else {
+            return Ok(vec![]);
+        };
+
+        Ok(vec![CommandEvent::ExecuteKeyChangeRequested {
+            key: key.trim().to_string(),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetExecuteKeyCommand"
+    }
+}
+
+/// Set fileencoding command handler (for :set fileencoding=<name>)
+///
+/// An empty value goes back to UTF-8.
+pub struct SetFileEncodingCommand;
+
+impl ExCommand for SetFileEncodingCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command.starts_with("set fileencoding=")
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let Some(encoding) = command.strip_prefix("set fileencoding=") else {
+            return Ok(vec![]);
+        };
+
+        Ok(vec![CommandEvent::FileEncodingChangeRequested {
+            encoding: encoding.trim().to_lowercase(),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetFileEncodingCommand"
+    }
+}
+
+/// Set timeoutlen command handler (for :set timeoutlen=<ms>)
+pub struct SetTimeoutLenCommand;
+
+impl ExCommand for SetTimeoutLenCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command
+            .strip_prefix("set timeoutlen=")
+            .is_some_and(|value_str| value_str.parse::<usize>().is_ok())
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let Some(Ok(ms)) = command
+            .strip_prefix("set timeoutlen=")
+            .map(str::parse::<usize>)
+        else {
+            return Ok(vec![]);
+        };
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::TimeoutLen,
+            value: SettingValue::Number(ms),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetTimeoutLenCommand"
+    }
+}
+
+/// Set matchtime command handler (for :set matchtime=<tenths>)
+pub struct SetMatchTimeCommand;
+
+impl ExCommand for SetMatchTimeCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command
+            .strip_prefix("set matchtime=")
+            .is_some_and(|value_str| value_str.parse::<usize>().is_ok())
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let Some(Ok(tenths)) = command
+            .strip_prefix("set matchtime=")
+            .map(str::parse::<usize>)
+        else {
+            return Ok(vec![]);
+        };
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::MatchTime,
+            value: SettingValue::Number(tenths),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetMatchTimeCommand"
+    }
+}
+
+/// Set maxresponse command handler (for :set maxresponse=<bytes>)
+pub struct SetMaxResponseCommand;
+
+impl ExCommand for SetMaxResponseCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command
+            .strip_prefix("set maxresponse=")
+            .is_some_and(|value_str| value_str.parse::<usize>().is_ok())
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let Some(Ok(bytes)) = command
+            .strip_prefix("set maxresponse=")
+            .map(str::parse::<usize>)
+        else {
+            return Ok(vec![]);
+        };
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::MaxResponse,
+            value: SettingValue::Number(bytes),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetMaxResponseCommand"
+    }
+}
+
+/// Set incsearch command handler (for :set incsearch on/off)
+pub struct SetIncSearchCommand;
+
+impl ExCommand for SetIncSearchCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set incsearch on" || command == "set incsearch off"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set incsearch on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::IncSearch,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetIncSearchCommand"
+    }
+}
+
+/// Set foldrequest command handler (for :set foldrequest on/off)
+pub struct SetFoldRequestCommand;
+
+impl ExCommand for SetFoldRequestCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set foldrequest on" || command == "set foldrequest off"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set foldrequest on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::FoldRequest,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetFoldRequestCommand"
+    }
+}
+
+/// Set equalalways command handler (for :set equalalways on/off)
+pub struct SetEqualAlwaysCommand;
+
+impl ExCommand for SetEqualAlwaysCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set equalalways on" || command == "set equalalways off"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set equalalways on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::EqualAlways,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetEqualAlwaysCommand"
+    }
+}
+
+/// Set compact command handler (for :set compact on/off)
+pub struct SetCompactCommand;
+
+impl ExCommand for SetCompactCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set compact on" || command == "set compact off"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set compact on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::Compact,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetCompactCommand"
+    }
+}
+
+/// Set wildmenu command handler (for :set wildmenu on/off)
+pub struct SetWildMenuCommand;
+
+impl ExCommand for SetWildMenuCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "set wildmenu on" || command == "set wildmenu off"
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = command == "set wildmenu on";
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::WildMenu,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetWildMenuCommand"
+    }
+}
+
+/// Set title command handler (for :set title on/off, also :set title and :set notitle)
+pub struct SetTitleCommand;
+
+impl ExCommand for SetTitleCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        matches!(
+            command,
+            "set title on" | "set title off" | "set title" | "set notitle"
+        )
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = matches!(command, "set title on" | "set title");
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::Title,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetTitleCommand"
+    }
+}
+
+/// Set showmode command handler (for :set showmode on/off, also :set showmode and :set noshowmode)
+pub struct SetShowModeCommand;
+
+impl ExCommand for SetShowModeCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        matches!(
+            command,
+            "set showmode on" | "set showmode off" | "set showmode" | "set noshowmode"
+        )
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let enable = matches!(command, "set showmode on" | "set showmode");
+
+        Ok(vec![CommandEvent::SettingChangeRequested {
+            setting: Setting::ShowMode,
+            value: if enable {
+                SettingValue::On
+            } else {
+                SettingValue::Off
+            },
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetShowModeCommand"
+    }
+}
+
+/// Set titlestring command handler (for :set titlestring=<pattern>)
+///
+/// Spaces are escaped with a backslash like `equalprg`; an empty value goes
+/// back to the default title.
+pub struct SetTitleStringCommand;
+
+impl ExCommand for SetTitleStringCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command.starts_with("set titlestring=")
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let Some(title) = command.strip_prefix("set titlestring=") else {
+            return Ok(vec![]);
+        };
+
+        Ok(vec![CommandEvent::TitleStringChangeRequested {
+            title: title.replace("\\ ", " "),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SetTitleStringCommand"
+    }
+}
+
+/// Yank response command handler (for :yankresponse)
+pub struct YankResponseCommand;
+
+impl ExCommand for YankResponseCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "yankresponse"
+    }
+
+    fn execute(&self, _command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::YankResponseRequested])
+    }
+
+    fn name(&self) -> &'static str {
+        "YankResponseCommand"
+    }
+}
+
+/// Yank JSON value command handler (for :yankvalue)
+pub struct YankJsonValueCommand;
+
+impl ExCommand for YankJsonValueCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "yankvalue"
+    }
+
+    fn execute(&self, _command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::YankJsonValueRequested])
+    }
+
+    fn name(&self) -> &'static str {
+        "YankJsonValueCommand"
+    }
+}
+
+/// Dry run command handler (for :dryrun)
+pub struct DryRunCommand;
+
+impl ExCommand for DryRunCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "dryrun"
+    }
+
+    fn execute(&self, _command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::DryRunRequested])
+    }
+
+    fn name(&self) -> &'static str {
+        "DryRunCommand"
+    }
+}
+
+/// Arguments of a `:chain` command
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ChainSpec {
+    /// Request file to send
+    file: String,
+    /// JSON path of the response value to capture
+    path: String,
+    /// Variable the value is stored in
+    variable: String,
+}
+
+/// Chain command handler (for :chain <file> <path> <VAR>)
+pub struct ChainCommand;
+
+impl ChainCommand {
+    /// Split the command into request file, JSON path and variable name
+    fn parse(command: &str) -> Option<ChainSpec> {
+        let mut parts = command.split_whitespace();
+        if parts.next() != Some("chain") {
+            return None;
+        }
+
+        let (file, path, variable) = (parts.next()?, parts.next()?, parts.next()?);
+        if parts.next().is_some() || !path.starts_with(['.', '[']) {
+            return None;
+        }
+        Some(ChainSpec {
+            file: file.to_string(),
+            path: path.to_string(),
+            variable: variable.to_string(),
+        })
+    }
+}
+
+impl ExCommand for ChainCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        Self::parse(command).is_some()
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        match Self::parse(command) {
+            Some(ChainSpec {
+                file,
+                path,
+                variable,
+            }) => Ok(vec![CommandEvent::ChainRequested {
+                file,
+                path,
+                variable,
+            }]),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "ChainCommand"
+    }
+}
+
+/// Lint command handler (for :lint)
+pub struct LintCommand;
+
+impl ExCommand for LintCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        command == "lint"
+    }
+
+    fn execute(&self, _command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::LintHeadersRequested])
+    }
+
+    fn name(&self) -> &'static str {
+        "LintCommand"
+    }
+}
+
+/// Template command handler (for :template save/load <name> and :template list)
+pub struct TemplateCommand;
+
+impl TemplateCommand {
+    /// Parse the template subcommand and its name argument
+    fn parse(command: &str) -> Option<TemplateAction> {
+        let mut parts = command.split_whitespace();
+        if parts.next() != Some("template") {
+            return None;
+        }
+
+        let action = match (parts.next()?, parts.next()) {
+            ("save", Some(name)) => TemplateAction::Save(name.to_string()),
+            ("load", Some(name)) => TemplateAction::Load(name.to_string()),
+            ("list", None) => TemplateAction::List,
+            _ => return None,
+        };
+
+        // Reject trailing arguments
+        parts.next().is_none().then_some(action)
+    }
+}
+
+impl ExCommand for TemplateCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        Self::parse(command).is_some()
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        match Self::parse(command) {
+            Some(action) => Ok(vec![CommandEvent::TemplateRequested { action }]),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "TemplateCommand"
+    }
+}
+
+/// Target path and append flag of a `:w` command
+type WriteTarget = (String, bool);
+
+/// Write command handler (for :w file and :w >> file)
+pub struct WriteCommand;
+
+impl WriteCommand {
+    /// Split the command into target path and append flag
+    fn parse(command: &str) -> Option<WriteTarget> {
+        let args = command.strip_prefix('w')?;
+        if !args.starts_with([' ', '>']) {
+            return None;
+        }
+
+        let args = args.trim_start();
+        let (path, append) = match args.strip_prefix(">>") {
+            Some(path) => (path.trim(), true),
+            None => (args.trim(), false),
+        };
+
+        if path.is_empty() {
+            None
+        } else {
+            Some((path.to_string(), append))
+        }
+    }
+}
+
+impl ExCommand for WriteCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        Self::parse(command).is_some()
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        match Self::parse(command) {
+            Some((path, append)) => Ok(vec![CommandEvent::WriteBufferRequested { path, append }]),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "WriteCommand"
+    }
+}
+
+/// Optional path argument and force (`!`) flag of a command
+type ForcedPathArgs = (Option<String>, bool);
+
+/// Edit command handler (for :e [file] and :e! [file])
+pub struct EditCommand;
+
+impl EditCommand {
+    /// Split the command into target path and force flag
+    fn parse(command: &str) -> Option<ForcedPathArgs> {
+        let args = ["edit", "e"]
+            .iter()
+            .find_map(|name| command.strip_prefix(name))?;
+        let (force, args) = match args.strip_prefix('!') {
+            Some(args) => (true, args),
+            None => (false, args),
+        };
+        if !args.is_empty() && !args.starts_with(' ') {
+            return None;
+        }
+
+        let path = args.trim();
+        Some(((!path.is_empty()).then(|| path.to_string()), force))
+    }
+}
+
+impl ExCommand for EditCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        Self::parse(command).is_some()
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        match Self::parse(command) {
+            Some((path, force)) => Ok(vec![CommandEvent::EditFileRequested { path, force }]),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "EditCommand"
+    }
+}
+
+/// Make session command handler (for :mksession [file] and :mksession! [file])
+pub struct MakeSessionCommand;
+
+impl MakeSessionCommand {
+    /// Split the command into session file and force flag
+    fn parse(command: &str) -> Option<ForcedPathArgs> {
+        let args = ["mksession", "mks"]
+            .iter()
+            .find_map(|name| command.strip_prefix(name))?;
+        let (force, args) = match args.strip_prefix('!') {
+            Some(args) => (true, args),
+            None => (false, args),
+        };
+        if !args.is_empty() && !args.starts_with(' ') {
+            return None;
+        }
+
+        let path = args.trim();
+        Some(((!path.is_empty()).then(|| path.to_string()), force))
+    }
+}
+
+impl ExCommand for MakeSessionCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        Self::parse(command).is_some()
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        match Self::parse(command) {
+            Some((path, force)) => Ok(vec![CommandEvent::MakeSessionRequested { path, force }]),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "MakeSessionCommand"
+    }
+}
+
+/// Source command handler (for :source file)
+pub struct SourceCommand;
+
+impl SourceCommand {
+    /// Get the script path, `None` inside when it is missing
+    fn parse(command: &str) -> Option<Option<String>> {
+        let args = ["source", "so"]
+            .iter()
+            .find_map(|name| command.strip_prefix(name))?;
+        if !args.is_empty() && !args.starts_with(' ') {
+            return None;
+        }
+
+        let path = args.trim();
+        Some((!path.is_empty()).then(|| path.to_string()))
+    }
+}
+
+impl ExCommand for SourceCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        Self::parse(command).is_some()
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        match Self::parse(command) {
+            Some(path) => Ok(vec![CommandEvent::SourceFileRequested { path }]),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "SourceCommand"
+    }
+}
+
+/// Range prefilled on the command line when `:` is pressed in visual mode
+pub const VISUAL_RANGE: &str = "'<,'>";
+
+/// Range and register of a ranged command
+type RangedCommand = (LineRange, Option<char>);
+
+/// Split a ranged command such as `:1,3d a` into its range and register
+///
+/// `names` are the accepted spellings of the command. Without a range the
+/// command applies to the cursor line.
+fn parse_ranged_command(command: &str, names: &[&str]) -> Option<RangedCommand> {
+    let (range, rest) = LineRange::parse(command);
+    let (name, register) = match rest.trim_start().split_once(' ') {
+        Some((name, register)) => (name, register.trim()),
+        None => (rest.trim_start(), ""),
+    };
+    if !names.contains(&name) {
+        return None;
+    }
+
+    let mut chars = register.chars();
+    let register = match (chars.next(), chars.next()) {
+        (None, _) => None,
+        (Some(register), None) => Some(register),
+        (Some(_), Some(_)) => return None,
+    };
+    Some((range.unwrap_or_else(LineRange::current_line), register))
+}
+
+/// Delete lines command handler (for :[range]d [register])
+pub struct DeleteLinesCommand;
+
+impl ExCommand for DeleteLinesCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        parse_ranged_command(command, &["d", "delete"]).is_some()
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        match parse_ranged_command(command, &["d", "delete"]) {
+            Some((range, register)) => {
+                Ok(vec![CommandEvent::DeleteLinesRequested { range, register }])
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "DeleteLinesCommand"
+    }
+}
+
+/// Yank lines command handler (for :[range]y [register])
+pub struct YankLinesCommand;
+
+impl ExCommand for YankLinesCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        parse_ranged_command(command, &["y", "yank"]).is_some()
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        match parse_ranged_command(command, &["y", "yank"]) {
+            Some((range, register)) => {
+                Ok(vec![CommandEvent::YankLinesRequested { range, register }])
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "YankLinesCommand"
+    }
+}
+
+/// Save response command handler (for :save file)
+pub struct SaveResponseCommand;
+
+impl SaveResponseCommand {
+    /// Get the target path of the command
+    fn parse(command: &str) -> Option<String> {
+        let path = command.strip_prefix("save ")?.trim();
+        (!path.is_empty()).then(|| path.to_string())
+    }
+}
+
+impl ExCommand for SaveResponseCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        Self::parse(command).is_some()
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        match Self::parse(command) {
+            Some(path) => Ok(vec![CommandEvent::SaveResponseRequested { path }]),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "SaveResponseCommand"
+    }
+}
+
+/// Kind of argument completed with Tab on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionKind {
+    /// File path after `:w`, `:w >>`, `:e` or `:save`
+    FilePath,
+    /// Template name after `:template load` or `:template save`
+    TemplateName,
+}
+
+/// Byte offset where a completable argument starts and what it names
+type CompletionTarget = (usize, CompletionKind);
+
+/// Find the argument to complete in a partially typed ex command
+///
+/// # Returns
+/// The byte offset where the argument starts and what it names, or `None`
+/// when the command takes no completable argument.
+pub fn completion_target(command: &str) -> Option<CompletionTarget> {
+    let kind = if command.starts_with("template load ") || command.starts_with("template save ") {
+        CompletionKind::TemplateName
+    } else if command.starts_with("w ")
+        || command.starts_with("w>>")
+        || command.starts_with("e ")
+        || command.starts_with("e! ")
+        || command.starts_with("save ")
+    {
+        CompletionKind::FilePath
+    } else {
+        return None;
+    };
+
+    let start = command.rfind([' ', '>']).map_or(0, |index| index + 1);
+    Some((start, kind))
+}
+
+/// Type alias to reduce complexity for ex command collection
+type ExCommandCollection = Vec<Box<dyn ExCommand + Send>>;
+
+/// Go to line command handler (for :<number>)
+pub struct GoToLineCommand;
+
+impl ExCommand for GoToLineCommand {
+    fn can_handle(&self, command: &str) -> bool {
+        // Check if it's a valid line number
+        command.parse::<usize>().is_ok()
+    }
+
+    fn execute(&self, command: &str, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        if let Ok(line_number) = command.parse::<usize>() {
+            if line_number > 0 {
+                Ok(vec![CommandEvent::CursorMoveRequested {
+                    direction: MovementDirection::LineNumber(line_number),
+                    amount: 1,
+                }])
+            } else {
+                tracing::warn!("Invalid line number: {}", line_number);
+                Ok(vec![])
+            }
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "GoToLineCommand"
+    }
+}
+
+/// Registry for managing ex commands
+pub struct ExCommandRegistry {
+    commands: ExCommandCollection,
+}
+
+impl ExCommandRegistry {
+    /// Create a new ex command registry with all default commands
+    pub fn new() -> Self {
+        let commands: ExCommandCollection = vec![
+            Box::new(QuitCommand),
+            Box::new(WriteQuitCommand),
+            Box::new(SetWrapCommand),
+            Box::new(SetResponseWrapCommand),
+            Box::new(SetNumberCommand),
+            Box::new(SetScrollbarCommand),
+            Box::new(SetCursorColumnCommand),
+            Box::new(SetClipboardCommand),
+            Box::new(SetClipboardUnnamedPlusCommand),
+            Box::new(SetTabstopCommand),
+            Box::new(SetLastStatusCommand),
+            Box::new(SetExpandTabCommand),
+            Box::new(SetJoinSpacesCommand),
+            Box::new(SetSpellCommand),
+            Box::new(SetStrictHeadersCommand),
+            Box::new(SetBackupCommand),
+            Box::new(SetBackupCopyCommand),
+            Box::new(SetConfirmCommand),
+            Box::new(SetHlYankCommand),
+            Box::new(SetShowMatchCommand),
+            Box::new(SetAutoSaveCommand),
+            Box::new(SetAutoWriteCommand),
+            Box::new(SetAutoSaveIntervalCommand),
+            Box::new(SetAutoReadCommand),
+            Box::new(SetEqualPrgCommand),
+            Box::new(SetShellCommand),
+            Box::new(SetFileEncodingCommand),
+            Box::new(SetWhichWrapCommand),
+            Box::new(SetExecuteKeyCommand),
+            Box::new(SetTimeoutLenCommand),
+            Box::new(SetMatchTimeCommand),
+            Box::new(SetMaxResponseCommand),
+            Box::new(SetIncSearchCommand),
+            Box::new(SetFoldRequestCommand),
+            Box::new(SetEqualAlwaysCommand),
+            Box::new(SetCompactCommand),
+            Box::new(SetWildMenuCommand),
+            Box::new(SetTitleCommand),
+            Box::new(SetTitleStringCommand),
+            Box::new(SetShowModeCommand),
+            Box::new(ShowProfileCommand),
+            Box::new(SetAllCommand),
+            Box::new(MessagesCommand),
+            Box::new(RegistersCommand),
+            Box::new(WriteCommand),
+            Box::new(EditCommand),
+            Box::new(MakeSessionCommand),
+            Box::new(SourceCommand),
+            Box::new(SaveResponseCommand),
+            Box::new(LintCommand),
+            Box::new(DryRunCommand),
+            Box::new(ChainCommand),
+            Box::new(YankResponseCommand),
+            Box::new(YankJsonValueCommand),
+            Box::new(TemplateCommand),
+            Box::new(DeleteLinesCommand),
+            Box::new(YankLinesCommand),
+            Box::new(GoToLineCommand),
+        ];
+
+        Self { commands }
+    }
+
+    /// Parse and execute an ex command string
+    pub fn execute_command(
+        &self,
+        command_str: &str,
+        context: &CommandContext,
+    ) -> Result<Vec<CommandEvent>> {
+        let trimmed = command_str.trim();
+
+        // Empty command just exits command mode
+        if trimmed.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // Find the first command that can handle this string
+        for command in &self.commands {
+            if command.can_handle(trimmed) {
+                tracing::debug!("Ex command '{}' handled by {}", trimmed, command.name());
+                return command.execute(trimmed, context);
+            }
+        }
+
+        // Unknown command
+        tracing::warn!("Unknown ex command: {}", trimmed);
+        Ok(vec![])
+    }
+
+    /// Check whether any registered command handles this command string
+    pub fn can_handle(&self, command_str: &str) -> bool {
+        let trimmed = command_str.trim();
+        self.commands
+            .iter()
+            .any(|command| command.can_handle(trimmed))
+    }
+}
+
+impl Default for ExCommandRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repl::commands::{LineAddress, ViewModelSnapshot};
+    use crate::repl::events::{EditorMode, KeyBinding, LogicalPosition, Pane};
+
+    fn create_test_context() -> CommandContext {
+        CommandContext {
+            state: ViewModelSnapshot {
+                current_mode: EditorMode::Normal,
+                current_pane: Pane::Request,
+                cursor_position: LogicalPosition::zero(),
+                request_text: String::new(),
+                response_text: String::new(),
+                terminal_dimensions: (80, 24),
+                expand_tab: false,
+                tab_width: 4,
+                pending_count: None,
+                command_window_open: false,
+                whichwrap: String::new(),
+                execute_key: KeyBinding::ENTER,
+            },
+        }
+    }
+
+    #[test]
+    fn quit_command_should_handle_q() {
+        let cmd = QuitCommand;
+        assert!(cmd.can_handle("q"));
+        assert!(cmd.can_handle("q!"));
+        assert!(!cmd.can_handle("quit"));
+    }
+
+    #[test]
+    fn quit_command_should_produce_quit_event() {
+        let cmd = QuitCommand;
+        let context = create_test_context();
+        let result = cmd.execute("q", &context).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0], CommandEvent::QuitRequested);
+    }
+
+    #[test]
+    fn set_wrap_command_should_handle_wrap_settings() {
+        let cmd = SetWrapCommand;
+        assert!(cmd.can_handle("set wrap on"));
+        assert!(cmd.can_handle("set wrap off"));
+        assert!(!cmd.can_handle("set wrap"));
+    }
+
+    #[test]
+    fn set_responsewrap_command_should_toggle_response_wrap() {
+        let context = create_test_context();
+        for (command, value) in [
+            ("set responsewrap", SettingValue::On),
+            ("set responsewrap on", SettingValue::On),
+            ("set noresponsewrap", SettingValue::Off),
+            ("set responsewrap off", SettingValue::Off),
+        ] {
+            assert!(SetResponseWrapCommand.can_handle(command));
+            assert_eq!(
+                SetResponseWrapCommand.execute(command, &context).unwrap(),
+                vec![CommandEvent::SettingChangeRequested {
+                    setting: Setting::ResponseWrap,
+                    value,
+                }]
+            );
+        }
+        assert!(!SetWrapCommand.can_handle("set responsewrap on"));
+    }
+
+    #[test]
+    fn set_tabstop_command_should_handle_tabstop_settings() {
+        let cmd = SetTabstopCommand;
+        assert!(cmd.can_handle("set tabstop 4"));
+        assert!(cmd.can_handle("set tabstop 8"));
+        assert!(cmd.can_handle("set tabstop 2"));
+        assert!(!cmd.can_handle("set tabstop"));
+        assert!(!cmd.can_handle("set tabstop abc"));
+    }
+
+    #[test]
+    fn set_tabstop_command_should_produce_setting_change_event() {
+        let cmd = SetTabstopCommand;
+        let context = create_test_context();
+
+        // Test valid tab width
+        let result = cmd.execute("set tabstop 4", &context).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0],
+            CommandEvent::SettingChangeRequested {
+                setting: Setting::TabStop,
+                value: SettingValue::Number(4),
+            }
+        );
+
+        // Test clamping to max value
+        let result = cmd.execute("set tabstop 20", &context).unwrap();
+        assert_eq!(
+            result[0],
+            CommandEvent::SettingChangeRequested {
+                setting: Setting::TabStop,
+                value: SettingValue::Number(8), // Should be clamped to 8
+            }
+        );
+    }
+
+    #[test]
+    fn set_expandtab_command_should_handle_expandtab_settings() {
+        let cmd = SetExpandTabCommand;
+        assert!(cmd.can_handle("set expandtab on"));
+        assert!(cmd.can_handle("set expandtab off"));
+        assert!(!cmd.can_handle("set expandtab"));
+        assert!(!cmd.can_handle("set expandtab yes"));
+    }
+
+    #[test]
+    fn set_expandtab_command_should_produce_setting_change_event() {
+        let cmd = SetExpandTabCommand;
+        let context = create_test_context();
+
+        // Test enabling expandtab
+        let result = cmd.execute("set expandtab on", &context).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0],
+            CommandEvent::SettingChangeRequested {
+                setting: Setting::ExpandTab,
+                value: SettingValue::On,
+            }
+        );
+
+        // Test disabling expandtab
+        let result = cmd.execute("set expandtab off", &context).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            result[0],
+            CommandEvent::SettingChangeRequested {
+                setting: Setting::ExpandTab,
+                value: SettingValue::Off,
+            }
+        );
+    }
+
+    #[test]
+    fn set_joinspaces_command_should_produce_setting_change_event() {
+        let cmd = SetJoinSpacesCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set joinspaces on"));
+        assert!(!cmd.can_handle("set joinspaces"));
+
+        let result = cmd.execute("set joinspaces on", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::JoinSpaces,
+                value: SettingValue::On,
+            }]
+        );
+    }
+
+    #[test]
+    fn write_quit_command_should_always_write_only_for_wq() {
+        let cmd = WriteQuitCommand;
+        let context = create_test_context();
+        assert!(!cmd.can_handle("w"));
+
+        let result = cmd.execute("wq", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::WriteQuitRequested { always_write: true }]
+        );
+
+        let result = cmd.execute("x", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::WriteQuitRequested {
+                always_write: false
+            }]
+        );
+    }
+
+    #[test]
+    fn set_incsearch_command_should_produce_setting_change_event() {
+        let cmd = SetIncSearchCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set incsearch on"));
+        assert!(!cmd.can_handle("set incsearch"));
+
+        let result = cmd.execute("set incsearch on", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::IncSearch,
+                value: SettingValue::On,
+            }]
+        );
+    }
+
+    #[test]
+    fn set_foldrequest_command_should_produce_setting_change_event() {
+        let cmd = SetFoldRequestCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set foldrequest off"));
+        assert!(!cmd.can_handle("set foldrequest"));
+
+        let result = cmd.execute("set foldrequest off", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::FoldRequest,
+                value: SettingValue::Off,
+            }]
+        );
+    }
+
+    #[test]
+    fn set_equalalways_command_should_produce_setting_change_event() {
+        let cmd = SetEqualAlwaysCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set equalalways off"));
+        assert!(!cmd.can_handle("set equalalways"));
+
+        let result = cmd.execute("set equalalways off", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::EqualAlways,
+                value: SettingValue::Off,
+            }]
+        );
+    }
+
+    #[test]
+    fn set_compact_command_should_produce_setting_change_event() {
+        let cmd = SetCompactCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set compact on"));
+        assert!(!cmd.can_handle("set compact"));
+
+        assert_eq!(
+            cmd.execute("set compact on", &context).unwrap(),
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::Compact,
+                value: SettingValue::On,
+            }]
+        );
+    }
+
+    #[test]
+    fn yankresponse_command_should_produce_yank_response_event() {
+        let cmd = YankResponseCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("yankresponse"));
+        assert!(!cmd.can_handle("yank"));
+
+        let result = cmd.execute("yankresponse", &context).unwrap();
+        assert_eq!(result, vec![CommandEvent::YankResponseRequested]);
+    }
+
+    #[test]
+    fn yankvalue_command_should_produce_yank_json_value_event() {
+        let cmd = YankJsonValueCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("yankvalue"));
+        assert!(!cmd.can_handle("yankresponse"));
+
+        let result = cmd.execute("yankvalue", &context).unwrap();
+        assert_eq!(result, vec![CommandEvent::YankJsonValueRequested]);
+    }
+
+    #[test]
+    fn set_scrollbar_command_should_produce_setting_change_event() {
+        let cmd = SetScrollbarCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set scrollbar on"));
+        assert!(!cmd.can_handle("set scrollbar"));
+
+        let result = cmd.execute("set scrollbar on", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::Scrollbar,
+                value: SettingValue::On,
+            }]
+        );
+    }
+
+    #[test]
+    fn set_cursorcolumn_command_should_produce_setting_change_event() {
+        let cmd = SetCursorColumnCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set cursorcolumn off"));
+        assert!(!cmd.can_handle("set cursorcolumn"));
+
+        let result = cmd.execute("set cursorcolumn off", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::CursorColumn,
+                value: SettingValue::Off,
+            }]
+        );
+    }
+
+    #[test]
+    fn set_clipboard_unnamedplus_command_should_produce_setting_change_event() {
+        let cmd = SetClipboardUnnamedPlusCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set clipboard="));
+        assert!(!cmd.can_handle("set clipboard=unnamed"));
+
+        let result = cmd.execute("set clipboard=unnamedplus", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::ClipboardUnnamedPlus,
+                value: SettingValue::On,
+            }]
+        );
+    }
+
+    #[test]
+    fn set_all_command_should_request_settings_listing() {
+        let cmd = SetAllCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set all"));
+        assert!(!cmd.can_handle("set all on"));
+
+        let result = cmd.execute("set all", &context).unwrap();
+        assert_eq!(result, vec![CommandEvent::ShowSettingsRequested]);
+    }
+
+    #[test]
+    fn set_laststatus_command_should_produce_setting_change_event() {
+        let cmd = SetLastStatusCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set laststatus=0"));
+        assert!(!cmd.can_handle("set laststatus=1"));
+
+        let result = cmd.execute("set laststatus=0", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::LastStatus,
+                value: SettingValue::Number(0),
+            }]
+        );
     }
 
     #[test]
-    fn quit_command_should_handle_q() {
-        let cmd = QuitCommand;
-        assert!(cmd.can_handle("q"));
-        assert!(cmd.can_handle("q!"));
-        assert!(!cmd.can_handle("quit"));
+    fn set_autosave_interval_command_should_produce_number_setting() {
+        let cmd = SetAutoSaveIntervalCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set autosaveinterval 60"));
+        assert!(!cmd.can_handle("set autosaveinterval soon"));
+        assert!(SetAutoSaveCommand.can_handle("set autosave on"));
+
+        assert_eq!(
+            cmd.execute("set autosaveinterval 0", &context).unwrap(),
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::AutoSaveInterval,
+                value: SettingValue::Number(1),
+            }]
+        );
     }
 
     #[test]
-    fn quit_command_should_produce_quit_event() {
-        let cmd = QuitCommand;
+    fn set_strictheaders_command_should_produce_on_off_setting() {
+        let cmd = SetStrictHeadersCommand;
         let context = create_test_context();
-        let result = cmd.execute("q", &context).unwrap();
-        assert_eq!(result.len(), 1);
-        assert_eq!(result[0], CommandEvent::QuitRequested);
+        assert!(cmd.can_handle("set strictheaders off"));
+        assert!(!cmd.can_handle("set strictheaders"));
+
+        assert_eq!(
+            cmd.execute("set strictheaders on", &context).unwrap(),
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::StrictHeaders,
+                value: SettingValue::On,
+            }]
+        );
     }
 
     #[test]
-    fn set_wrap_command_should_handle_wrap_settings() {
-        let cmd = SetWrapCommand;
-        assert!(cmd.can_handle("set wrap on"));
-        assert!(cmd.can_handle("set wrap off"));
-        assert!(!cmd.can_handle("set wrap"));
+    fn set_autoread_command_should_produce_on_off_setting() {
+        let cmd = SetAutoReadCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set autoread on"));
+        assert!(!cmd.can_handle("set autoread"));
+
+        assert_eq!(
+            cmd.execute("set autoread off", &context).unwrap(),
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::AutoRead,
+                value: SettingValue::Off,
+            }]
+        );
     }
 
     #[test]
-    fn set_tabstop_command_should_handle_tabstop_settings() {
-        let cmd = SetTabstopCommand;
-        assert!(cmd.can_handle("set tabstop 4"));
-        assert!(cmd.can_handle("set tabstop 8"));
-        assert!(cmd.can_handle("set tabstop 2"));
-        assert!(!cmd.can_handle("set tabstop"));
-        assert!(!cmd.can_handle("set tabstop abc"));
+    fn set_equalprg_command_should_unescape_spaces() {
+        let cmd = SetEqualPrgCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set equalprg="));
+        assert!(!cmd.can_handle("set equalprg"));
+
+        assert_eq!(
+            cmd.execute("set equalprg=jq\\ --indent\\ 2\\ .", &context)
+                .unwrap(),
+            vec![CommandEvent::EqualPrgChangeRequested {
+                program: "jq --indent 2 .".to_string(),
+            }]
+        );
     }
 
     #[test]
-    fn set_tabstop_command_should_produce_setting_change_event() {
-        let cmd = SetTabstopCommand;
+    fn set_shell_command_should_unescape_spaces() {
+        let cmd = SetShellCommand;
         let context = create_test_context();
+        assert!(cmd.can_handle("set shell="));
+        assert!(!cmd.can_handle("set shell"));
 
-        // Test valid tab width
-        let result = cmd.execute("set tabstop 4", &context).unwrap();
-        assert_eq!(result.len(), 1);
         assert_eq!(
-            result[0],
-            CommandEvent::SettingChangeRequested {
-                setting: Setting::TabStop,
-                value: SettingValue::Number(4),
-            }
+            cmd.execute("set shell=/opt/my\\ tools/zsh", &context)
+                .unwrap(),
+            vec![CommandEvent::ShellChangeRequested {
+                shell: "/opt/my tools/zsh".to_string(),
+            }]
         );
+    }
+
+    #[test]
+    fn set_showmode_command_should_accept_vim_forms() {
+        let context = create_test_context();
+        for (command, value) in [
+            ("set showmode", SettingValue::On),
+            ("set showmode on", SettingValue::On),
+            ("set noshowmode", SettingValue::Off),
+            ("set showmode off", SettingValue::Off),
+        ] {
+            assert!(SetShowModeCommand.can_handle(command));
+            assert_eq!(
+                SetShowModeCommand.execute(command, &context).unwrap(),
+                vec![CommandEvent::SettingChangeRequested {
+                    setting: Setting::ShowMode,
+                    value,
+                }]
+            );
+        }
+        assert!(!SetShowModeCommand.can_handle("set showmodes"));
+    }
+
+    #[test]
+    fn set_title_commands_should_toggle_title_and_unescape_titlestring() {
+        let context = create_test_context();
+        for (command, value) in [
+            ("set title", SettingValue::On),
+            ("set title on", SettingValue::On),
+            ("set notitle", SettingValue::Off),
+            ("set title off", SettingValue::Off),
+        ] {
+            assert!(SetTitleCommand.can_handle(command));
+            assert_eq!(
+                SetTitleCommand.execute(command, &context).unwrap(),
+                vec![CommandEvent::SettingChangeRequested {
+                    setting: Setting::Title,
+                    value,
+                }]
+            );
+        }
+        assert!(!SetTitleCommand.can_handle("set titlestring=x"));
 
-        // Test clamping to max value
-        let result = cmd.execute("set tabstop 20", &context).unwrap();
         assert_eq!(
-            result[0],
-            CommandEvent::SettingChangeRequested {
-                setting: Setting::TabStop,
-                value: SettingValue::Number(8), // Should be clamped to 8
-            }
+            SetTitleStringCommand
+                .execute("set titlestring=%m\\ %u", &context)
+                .unwrap(),
+            vec![CommandEvent::TitleStringChangeRequested {
+                title: "%m %u".to_string(),
+            }]
         );
     }
 
     #[test]
-    fn set_expandtab_command_should_handle_expandtab_settings() {
-        let cmd = SetExpandTabCommand;
-        assert!(cmd.can_handle("set expandtab on"));
-        assert!(cmd.can_handle("set expandtab off"));
-        assert!(!cmd.can_handle("set expandtab"));
-        assert!(!cmd.can_handle("set expandtab yes"));
+    fn set_whichwrap_command_should_pass_flags_through() {
+        let cmd = SetWhichWrapCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set whichwrap="));
+        assert!(!cmd.can_handle("set whichwrap"));
+
+        assert_eq!(
+            cmd.execute("set whichwrap=h,l,<,>", &context).unwrap(),
+            vec![CommandEvent::WhichWrapChangeRequested {
+                flags: "h,l,<,>".to_string(),
+            }]
+        );
     }
 
     #[test]
-    fn set_expandtab_command_should_produce_setting_change_event() {
-        let cmd = SetExpandTabCommand;
+    fn set_executekey_command_should_pass_key_through() {
+        let cmd = SetExecuteKeyCommand;
         let context = create_test_context();
+        assert!(cmd.can_handle("set executekey=<C-CR>"));
+        assert!(!cmd.can_handle("set executekey"));
 
-        // Test enabling expandtab
-        let result = cmd.execute("set expandtab on", &context).unwrap();
-        assert_eq!(result.len(), 1);
         assert_eq!(
-            result[0],
-            CommandEvent::SettingChangeRequested {
-                setting: Setting::ExpandTab,
-                value: SettingValue::On,
-            }
+            cmd.execute("set executekey=<C-CR>", &context).unwrap(),
+            vec![CommandEvent::ExecuteKeyChangeRequested {
+                key: "<C-CR>".to_string(),
+            }]
         );
+    }
+
+    #[test]
+    fn set_fileencoding_command_should_lowercase_name() {
+        let cmd = SetFileEncodingCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set fileencoding="));
+        assert!(!cmd.can_handle("set fileencoding"));
 
-        // Test disabling expandtab
-        let result = cmd.execute("set expandtab off", &context).unwrap();
-        assert_eq!(result.len(), 1);
         assert_eq!(
-            result[0],
-            CommandEvent::SettingChangeRequested {
-                setting: Setting::ExpandTab,
+            cmd.execute("set fileencoding=Latin1", &context).unwrap(),
+            vec![CommandEvent::FileEncodingChangeRequested {
+                encoding: "latin1".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn set_timeoutlen_command_should_produce_number_setting() {
+        let cmd = SetTimeoutLenCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set timeoutlen=500"));
+        assert!(!cmd.can_handle("set timeoutlen 500"));
+        assert!(!cmd.can_handle("set timeoutlen=soon"));
+
+        assert_eq!(
+            cmd.execute("set timeoutlen=500", &context).unwrap(),
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::TimeoutLen,
+                value: SettingValue::Number(500),
+            }]
+        );
+    }
+
+    #[test]
+    fn set_matchtime_command_should_produce_number_setting() {
+        let cmd = SetMatchTimeCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set matchtime=0"));
+        assert!(!cmd.can_handle("set matchtime=-1"));
+
+        assert_eq!(
+            cmd.execute("set matchtime=3", &context).unwrap(),
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::MatchTime,
+                value: SettingValue::Number(3),
+            }]
+        );
+    }
+
+    #[test]
+    fn set_maxresponse_command_should_produce_number_setting() {
+        let cmd = SetMaxResponseCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set maxresponse=1048576"));
+        assert!(!cmd.can_handle("set maxresponse=1MB"));
+
+        assert_eq!(
+            cmd.execute("set maxresponse=1048576", &context).unwrap(),
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::MaxResponse,
+                value: SettingValue::Number(1048576),
+            }]
+        );
+    }
+
+    #[test]
+    fn save_response_command_should_require_path() {
+        let cmd = SaveResponseCommand;
+        let context = create_test_context();
+        assert!(!cmd.can_handle("save"));
+        assert!(!cmd.can_handle("save  "));
+
+        assert_eq!(
+            cmd.execute("save body.json", &context).unwrap(),
+            vec![CommandEvent::SaveResponseRequested {
+                path: "body.json".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn completion_target_should_locate_argument_after_command() {
+        assert_eq!(
+            completion_target("w >> logs/re"),
+            Some((5, CompletionKind::FilePath))
+        );
+        assert_eq!(
+            completion_target("w>>re"),
+            Some((3, CompletionKind::FilePath))
+        );
+        assert_eq!(completion_target("w "), Some((2, CompletionKind::FilePath)));
+        assert_eq!(
+            completion_target("template load us"),
+            Some((14, CompletionKind::TemplateName))
+        );
+        assert_eq!(completion_target("set wrap"), None);
+        assert_eq!(completion_target("wq"), None);
+    }
+
+    #[test]
+    fn set_backup_command_should_produce_setting_change_event() {
+        let cmd = SetBackupCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set backup off"));
+        assert!(!cmd.can_handle("set backup"));
+
+        let result = cmd.execute("set backup off", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::Backup,
                 value: SettingValue::Off,
-            }
+            }]
+        );
+    }
+
+    #[test]
+    fn set_backupcopy_command_should_map_yes_to_on() {
+        let cmd = SetBackupCopyCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("set backupcopy=no"));
+        assert!(!cmd.can_handle("set backupcopy=auto"));
+
+        let result = cmd.execute("set backupcopy=yes", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::SettingChangeRequested {
+                setting: Setting::BackupCopy,
+                value: SettingValue::On,
+            }]
+        );
+    }
+
+    #[test]
+    fn lint_command_should_request_header_lint() {
+        let cmd = LintCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("lint"));
+        assert!(!cmd.can_handle("lint headers"));
+
+        let result = cmd.execute("lint", &context).unwrap();
+        assert_eq!(result, vec![CommandEvent::LintHeadersRequested]);
+    }
+
+    #[test]
+    fn dryrun_command_should_request_dry_run() {
+        let cmd = DryRunCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("dryrun"));
+        assert!(!cmd.can_handle("dry"));
+
+        let result = cmd.execute("dryrun", &context).unwrap();
+        assert_eq!(result, vec![CommandEvent::DryRunRequested]);
+    }
+
+    #[test]
+    fn chain_command_should_parse_file_path_and_variable() {
+        let cmd = ChainCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("chain login.http .token AUTH"));
+        assert!(!cmd.can_handle("chain login.http .token"));
+        assert!(!cmd.can_handle("chain login.http token AUTH"));
+        assert!(!cmd.can_handle("chain login.http .token AUTH extra"));
+
+        let result = cmd
+            .execute("chain login.http .token AUTH", &context)
+            .unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::ChainRequested {
+                file: "login.http".to_string(),
+                path: ".token".to_string(),
+                variable: "AUTH".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn template_command_should_parse_subcommands() {
+        let cmd = TemplateCommand;
+        let context = create_test_context();
+        assert!(!cmd.can_handle("template"));
+        assert!(!cmd.can_handle("template save"));
+        assert!(!cmd.can_handle("template list extra"));
+        assert!(!cmd.can_handle("template load a b"));
+
+        let result = cmd.execute("template save login", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::TemplateRequested {
+                action: TemplateAction::Save("login".to_string()),
+            }]
+        );
+
+        let result = cmd.execute("template load login", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::TemplateRequested {
+                action: TemplateAction::Load("login".to_string()),
+            }]
+        );
+
+        let result = cmd.execute("template list", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::TemplateRequested {
+                action: TemplateAction::List,
+            }]
+        );
+    }
+
+    #[test]
+    fn delete_lines_command_should_parse_range_and_register() {
+        let cmd = DeleteLinesCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("d"));
+        assert!(cmd.can_handle("'<,'>delete"));
+        assert!(!cmd.can_handle("dryrun"));
+        assert!(!cmd.can_handle("1,3dd"));
+        assert!(!cmd.can_handle("d ab"));
+
+        let result = cmd.execute("1,3d", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::DeleteLinesRequested {
+                range: LineRange {
+                    start: LineAddress::Number(1),
+                    end: LineAddress::Number(3),
+                },
+                register: None,
+            }]
+        );
+
+        let result = cmd.execute("d a", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::DeleteLinesRequested {
+                range: LineRange::current_line(),
+                register: Some('a'),
+            }]
+        );
+    }
+
+    #[test]
+    fn yank_lines_command_should_parse_range_and_register() {
+        let cmd = YankLinesCommand;
+        let context = create_test_context();
+        assert!(cmd.can_handle("yank"));
+        assert!(!cmd.can_handle("yankresponse"));
+
+        let result = cmd.execute(".,$y a", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::YankLinesRequested {
+                range: LineRange {
+                    start: LineAddress::Current,
+                    end: LineAddress::Last,
+                },
+                register: Some('a'),
+            }]
+        );
+
+        let result = cmd.execute("'<,'>y", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::YankLinesRequested {
+                range: LineRange {
+                    start: LineAddress::VisualStart,
+                    end: LineAddress::VisualEnd,
+                },
+                register: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn write_command_should_parse_append_and_overwrite_forms() {
+        let cmd = WriteCommand;
+        let context = create_test_context();
+        assert!(!cmd.can_handle("w"));
+        assert!(!cmd.can_handle("w >>"));
+        assert!(!cmd.can_handle("wrap"));
+
+        let result = cmd.execute("w >> requests.log", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::WriteBufferRequested {
+                path: "requests.log".to_string(),
+                append: true,
+            }]
+        );
+
+        let result = cmd.execute("w>>requests.log", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::WriteBufferRequested {
+                path: "requests.log".to_string(),
+                append: true,
+            }]
+        );
+
+        let result = cmd.execute("w request.http", &context).unwrap();
+        assert_eq!(
+            result,
+            vec![CommandEvent::WriteBufferRequested {
+                path: "request.http".to_string(),
+                append: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn registers_command_should_accept_optional_register_names() {
+        let cmd = RegistersCommand;
+        let context = create_test_context();
+        assert!(!cmd.can_handle("regs"));
+
+        assert_eq!(
+            cmd.execute("registers", &context).unwrap(),
+            vec![CommandEvent::ShowRegistersRequested { names: None }]
+        );
+        assert_eq!(
+            cmd.execute("reg a 1", &context).unwrap(),
+            vec![CommandEvent::ShowRegistersRequested {
+                names: Some("a1".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn messages_command_should_show_or_clear_history() {
+        let cmd = MessagesCommand;
+        let context = create_test_context();
+        assert!(!cmd.can_handle("messages all"));
+
+        assert_eq!(
+            cmd.execute("messages", &context).unwrap(),
+            vec![CommandEvent::ShowMessagesRequested]
+        );
+        assert_eq!(
+            cmd.execute("mes clear", &context).unwrap(),
+            vec![CommandEvent::ClearMessagesRequested]
+        );
+    }
+
+    #[test]
+    fn edit_command_should_parse_path_and_force_flag() {
+        let cmd = EditCommand;
+        let context = create_test_context();
+        assert!(!cmd.can_handle("equal"));
+        assert!(!cmd.can_handle("e!x"));
+
+        assert_eq!(
+            cmd.execute("e", &context).unwrap(),
+            vec![CommandEvent::EditFileRequested {
+                path: None,
+                force: false,
+            }]
+        );
+        assert_eq!(
+            cmd.execute("edit! users.http", &context).unwrap(),
+            vec![CommandEvent::EditFileRequested {
+                path: Some("users.http".to_string()),
+                force: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn mksession_command_should_parse_path_and_force_flag() {
+        let cmd = MakeSessionCommand;
+        let context = create_test_context();
+        assert!(!cmd.can_handle("mksessions"));
+
+        assert_eq!(
+            cmd.execute("mks", &context).unwrap(),
+            vec![CommandEvent::MakeSessionRequested {
+                path: None,
+                force: false,
+            }]
+        );
+        assert_eq!(
+            cmd.execute("mksession! work.json", &context).unwrap(),
+            vec![CommandEvent::MakeSessionRequested {
+                path: Some("work.json".to_string()),
+                force: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn source_command_should_parse_script_path() {
+        let cmd = SourceCommand;
+        let context = create_test_context();
+        assert!(!cmd.can_handle("sort"));
+
+        assert_eq!(
+            cmd.execute("so ~/.blueline/setup", &context).unwrap(),
+            vec![CommandEvent::SourceFileRequested {
+                path: Some("~/.blueline/setup".to_string()),
+            }]
+        );
+        assert_eq!(
+            cmd.execute("source", &context).unwrap(),
+            vec![CommandEvent::SourceFileRequested { path: None }]
         );
     }
 
@@ -0,0 +1,241 @@
+//! # Ex Command Ranges
+//!
+//! Parses the line range in front of ranged ex commands (`:1,3d`, `:.,$y a`,
+//! `:'<,'>d`) and resolves it to lines of the request buffer.
+
+use anyhow::{anyhow, Result};
+
+use crate::repl::models::LineSpan;
+
+/// A parsed range and the rest of the command after it
+type RangeSplit<'a> = (Option<LineRange>, &'a str);
+
+/// A parsed address and the rest of the text after it
+type AddressSplit<'a> = (LineAddress, &'a str);
+
+/// One end of a line range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineAddress {
+    /// The cursor line (`.`)
+    Current,
+    /// The last line of the buffer (`$`)
+    Last,
+    /// A 1-based line number (`N`)
+    Number(usize),
+    /// The first line of the last visual selection (`'<`)
+    VisualStart,
+    /// The last line of the last visual selection (`'>`)
+    VisualEnd,
+}
+
+/// Lines an ex command applies to, from `start` through `end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineRange {
+    pub start: LineAddress,
+    pub end: LineAddress,
+}
+
+impl LineRange {
+    /// The range used when a command is given without one: the cursor line
+    pub fn current_line() -> Self {
+        Self {
+            start: LineAddress::Current,
+            end: LineAddress::Current,
+        }
+    }
+
+    /// The whole buffer (`%`)
+    pub fn whole_buffer() -> Self {
+        Self {
+            start: LineAddress::Number(1),
+            end: LineAddress::Last,
+        }
+    }
+
+    /// Split a leading range off an ex command
+    ///
+    /// A single address covers one line; `%` covers the whole buffer.
+    ///
+    /// # Returns
+    /// The range, if the command starts with one, and the rest of the command.
+    pub fn parse(command: &str) -> RangeSplit<'_> {
+        if let Some(rest) = command.strip_prefix('%') {
+            return (Some(Self::whole_buffer()), rest);
+        }
+
+        let Some((start, rest)) = parse_address(command) else {
+            return (None, command);
+        };
+        let Some(after_comma) = rest.strip_prefix(',') else {
+            return (Some(Self { start, end: start }), rest);
+        };
+        match parse_address(after_comma) {
+            Some((end, rest)) => (Some(Self { start, end }), rest),
+            None => (None, command),
+        }
+    }
+
+    /// Resolve the range to 0-based first and last lines
+    ///
+    /// A backwards range such as `3,1` is swapped, as Vim offers to.
+    ///
+    /// # Arguments
+    /// * `cursor_line` - 0-based line of the cursor, for `.`
+    /// * `line_count` - number of lines in the buffer, for `$`
+    /// * `visual_lines` - first and last line of the last visual selection, for `'<` and `'>`
+    pub fn resolve(
+        &self,
+        cursor_line: usize,
+        line_count: usize,
+        visual_lines: Option<LineSpan>,
+    ) -> Result<LineSpan> {
+        let start = resolve_address(self.start, cursor_line, line_count, visual_lines)?;
+        let end = resolve_address(self.end, cursor_line, line_count, visual_lines)?;
+        Ok((start.min(end), start.max(end)))
+    }
+}
+
+/// Parse one address at the start of `text`, returning it and the rest
+fn parse_address(text: &str) -> Option<AddressSplit<'_>> {
+    if let Some(rest) = text.strip_prefix('.') {
+        return Some((LineAddress::Current, rest));
+    }
+    if let Some(rest) = text.strip_prefix('$') {
+        return Some((LineAddress::Last, rest));
+    }
+    if let Some(rest) = text.strip_prefix("'<") {
+        return Some((LineAddress::VisualStart, rest));
+    }
+    if let Some(rest) = text.strip_prefix("'>") {
+        return Some((LineAddress::VisualEnd, rest));
+    }
+
+    let digits = text.len()
+        - text
+            .trim_start_matches(|ch: char| ch.is_ascii_digit())
+            .len();
+    let number = text[..digits].parse().ok()?;
+    Some((LineAddress::Number(number), &text[digits..]))
+}
+
+/// Resolve one address to a 0-based line
+fn resolve_address(
+    address: LineAddress,
+    cursor_line: usize,
+    line_count: usize,
+    visual_lines: Option<LineSpan>,
+) -> Result<usize> {
+    let line = match address {
+        LineAddress::Current => cursor_line,
+        LineAddress::Last => line_count.saturating_sub(1),
+        LineAddress::Number(number) => number
+            .checked_sub(1)
+            .ok_or_else(|| anyhow!("E16: Invalid range"))?,
+        LineAddress::VisualStart => visual_lines.ok_or_else(|| anyhow!("E20: Mark not set"))?.0,
+        LineAddress::VisualEnd => visual_lines.ok_or_else(|| anyhow!("E20: Mark not set"))?.1,
+    };
+
+    if line < line_count {
+        Ok(line)
+    } else {
+        Err(anyhow!("E16: Invalid range"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_should_split_range_from_command() {
+        assert_eq!(
+            LineRange::parse("1,3d"),
+            (
+                Some(LineRange {
+                    start: LineAddress::Number(1),
+                    end: LineAddress::Number(3),
+                }),
+                "d"
+            )
+        );
+        assert_eq!(
+            LineRange::parse(".,$y a"),
+            (
+                Some(LineRange {
+                    start: LineAddress::Current,
+                    end: LineAddress::Last,
+                }),
+                "y a"
+            )
+        );
+        assert_eq!(
+            LineRange::parse("'<,'>d"),
+            (
+                Some(LineRange {
+                    start: LineAddress::VisualStart,
+                    end: LineAddress::VisualEnd,
+                }),
+                "d"
+            )
+        );
+        assert_eq!(
+            LineRange::parse("%d"),
+            (Some(LineRange::whole_buffer()), "d")
+        );
+    }
+
+    #[test]
+    fn parse_should_treat_single_address_as_one_line() {
+        assert_eq!(
+            LineRange::parse("42"),
+            (
+                Some(LineRange {
+                    start: LineAddress::Number(42),
+                    end: LineAddress::Number(42),
+                }),
+                ""
+            )
+        );
+    }
+
+    #[test]
+    fn parse_should_leave_commands_without_range_alone() {
+        assert_eq!(LineRange::parse("delete"), (None, "delete"));
+        assert_eq!(LineRange::parse("1,d"), (None, "1,d"));
+    }
+
+    #[test]
+    fn resolve_should_map_addresses_to_lines() {
+        let visual = Some((2, 4));
+
+        let range = LineRange::parse("1,3").0.unwrap();
+        assert_eq!(range.resolve(0, 5, None).unwrap(), (0, 2));
+
+        let range = LineRange::parse(".,$").0.unwrap();
+        assert_eq!(range.resolve(3, 5, None).unwrap(), (3, 4));
+
+        let range = LineRange::parse("'<,'>").0.unwrap();
+        assert_eq!(range.resolve(0, 5, visual).unwrap(), (2, 4));
+
+        let range = LineRange::parse("3,1").0.unwrap();
+        assert_eq!(range.resolve(0, 5, None).unwrap(), (0, 2));
+    }
+
+    #[test]
+    fn resolve_should_reject_lines_outside_buffer() {
+        let range = LineRange::parse("1,9").0.unwrap();
+        assert_eq!(
+            range.resolve(0, 5, None).unwrap_err().to_string(),
+            "E16: Invalid range"
+        );
+
+        let range = LineRange::parse("0").0.unwrap();
+        assert!(range.resolve(0, 5, None).is_err());
+
+        let range = LineRange::parse("'<,'>").0.unwrap();
+        assert_eq!(
+            range.resolve(0, 5, None).unwrap_err().to_string(),
+            "E20: Mark not set"
+        );
+    }
+}
@@ -12,9 +12,11 @@ use crate::repl::events::EditorMode;
 // Import and re-export command event types
 pub mod context;
 pub mod events;
+pub mod ex_range;
 
 pub use context::*;
 pub use events::*;
+pub use ex_range::{LineAddress, LineRange};
 
 /// Command trait for event-driven architecture
 pub trait Command: Send {
@@ -61,32 +63,50 @@ pub mod request;
 pub mod yank;
 
 // Re-export all commands for easy access
-pub use app::AppTerminateCommand;
+pub use app::{
+    AppTerminateCommand, EnterZPrefixCommand, FileInfoCommand, QuitWithoutWritingCommand,
+    WriteQuitCommand,
+};
 pub use editing::{
-    DeleteCharAtCursorCommand, DeleteCharCommand, InsertCharCommand, InsertNewLineCommand,
-    InsertTabCommand,
+    ChangeCaseCommand, CountDigitCommand, DedentLineCommand, DedentLinesCommand,
+    DedentSelectionCommand, DeleteCharAtCursorCommand, DeleteCharCommand, DeleteToLineStartCommand,
+    DeleteWordBeforeCursorCommand, EnterCaseOperatorCommand, EnterEqualPrefixCommand,
+    EnterInsertLiteralPrefixCommand, EnterInsertRegisterPrefixCommand, EnterShiftPrefixCommand,
+    IndentLineCommand, IndentLinesCommand, IndentSelectionCommand, InsertCharCommand,
+    InsertLastInsertedCommand, InsertLiteralCommand, InsertNewLineCommand, InsertRegisterCommand,
+    InsertTabCommand, JoinLinesCommand, ReindentLinesCommand, RepeatLastChangeCommand,
+};
+pub use ex_commands::{
+    completion_target, CompletionKind, ExCommand, ExCommandRegistry, VISUAL_RANGE,
 };
-pub use ex_commands::{ExCommand, ExCommandRegistry};
 pub use mode::{
     AppendAfterCursorCommand, AppendAtEndOfLineCommand, EnterCommandModeCommand,
-    EnterInsertModeCommand, EnterVisualBlockModeCommand, EnterVisualLineModeCommand,
-    EnterVisualModeCommand, ExCommandModeCommand, ExitInsertModeCommand,
-    ExitVisualBlockInsertModeCommand, ExitVisualModeCommand, InsertAtBeginningOfLineCommand,
-    RepeatVisualSelectionCommand, VisualBlockAppendCommand, VisualBlockInsertCommand,
+    EnterInsertModeCommand, EnterQPrefixCommand, EnterVisualBlockModeCommand,
+    EnterVisualLineModeCommand, EnterVisualModeCommand, ExCommandModeCommand,
+    ExecuteCommandWindowLineCommand, ExitInsertModeCommand, ExitVisualBlockInsertModeCommand,
+    ExitVisualModeCommand, InsertAtBeginningOfLineCommand, OpenCommandWindowCommand,
+    RepeatVisualSelectionCommand, SelectLastChangeCommand, VisualBlockAppendCommand,
+    VisualBlockInsertCommand,
 };
 pub use navigation::{
-    BeginningOfLineCommand, EndKeyCommand, EndOfLineCommand, EndOfWordCommand, EnterGPrefixCommand,
-    GoToBottomCommand, GoToTopCommand, HalfPageDownCommand, HalfPageUpCommand, HomeKeyCommand,
-    MoveCursorDownCommand, MoveCursorLeftCommand, MoveCursorRightCommand, MoveCursorUpCommand,
-    NextWordCommand, PageDownCommand, PageUpCommand, PreviousWordCommand, ScrollLeftCommand,
-    ScrollRightCommand,
+    BeginningOfLineCommand, DisplayLineDownCommand, DisplayLineUpCommand, EndKeyCommand,
+    EndOfLineCommand, EndOfWordCommand, EnterFPrefixCommand, EnterGPrefixCommand,
+    EnterMarkPrefixCommand, EnterScrollPrefixCommand, FindCharCommand, GoToBottomCommand,
+    GoToTopCommand, HalfPageDownCommand, HalfPageUpCommand, HomeKeyCommand,
+    JumpToChangeMarkCommand, JumpToMarkCommand, MoveCursorDownCommand, MoveCursorLeftCommand,
+    MoveCursorRightCommand, MoveCursorUpCommand, NextWordCommand, OperatorSearchCommand,
+    PageDownCommand, PageUpCommand, PreviousWordCommand, RepeatFindCharCommand, ScrollLeftCommand,
+    ScrollRightCommand, ScrollSidewaysCommand, SearchBackwardCommand, SearchForwardCommand,
+    SearchInputCommand, SearchNextCommand, SetMarkCommand,
 };
 pub use pane::SwitchPaneCommand;
 pub use request::ExecuteRequestCommand;
 pub use yank::{
     ChangeSelectionCommand, CutCharacterCommand, CutCurrentLineCommand, CutSelectionCommand,
-    CutToEndOfLineCommand, DeleteSelectionCommand, EnterDPrefixCommand, EnterYPrefixCommand,
-    PasteAfterCommand, PasteAtCursorCommand, YankCommand, YankCurrentLineCommand,
+    CutToEndOfLineCommand, DeleteAroundWordCommand, DeleteSelectionCommand, DeleteToCharCommand,
+    EnterCPrefixCommand, EnterDAPrefixCommand, EnterDFPrefixCommand, EnterDPrefixCommand,
+    EnterRegisterPrefixCommand, EnterYPrefixCommand, PasteAfterCommand, PasteAtCursorCommand,
+    SelectRegisterCommand, YankCommand, YankCurrentLineCommand,
 };
 
 /// Type alias for command collection to reduce complexity
@@ -101,23 +121,52 @@ impl CommandRegistry {
     /// Create new command registry with all default commands
     pub fn new() -> Self {
         let commands: CommandCollection = vec![
+            // The key after Ctrl-v in insert mode is inserted as-is, whatever it is
+            Box::new(InsertLiteralCommand),
             // App control commands (highest priority - process first)
             Box::new(AppTerminateCommand),
+            Box::new(FileInfoCommand),
+            // Z mode commands (ZZ and ZQ)
+            Box::new(WriteQuitCommand),
+            Box::new(QuitWithoutWritingCommand),
+            Box::new(EnterZPrefixCommand),
+            // Q mode commands (q: command-line window)
+            Box::new(OpenCommandWindowCommand),
+            Box::new(EnterQPrefixCommand),
+            // Command-line window must intercept Enter before request execution
+            Box::new(ExecuteCommandWindowLineCommand),
             // Request commands (high priority - must intercept Enter before other commands)
             Box::new(ExecuteRequestCommand),
             // G mode commands (high priority - must be processed before regular g handling)
             Box::new(GoToTopCommand),
+            Box::new(DisplayLineDownCommand), // gj command
+            Box::new(DisplayLineUpCommand),   // gk command
             Box::new(GoToBottomCommand),
             Box::new(RepeatVisualSelectionCommand), // gv command
+            Box::new(SelectLastChangeCommand),      // gV command
             Box::new(EnterGPrefixCommand),
+            // Find character commands (f, F, t, T, 2fx, ; and ,)
+            Box::new(EnterFPrefixCommand),
+            Box::new(FindCharCommand),
+            Box::new(RepeatFindCharCommand),
+            // Mark commands (ma, `a, 'a, `[ and `])
+            Box::new(EnterMarkPrefixCommand),
+            Box::new(SetMarkCommand),
+            Box::new(JumpToMarkCommand),
+            Box::new(JumpToChangeMarkCommand),
             // Scroll commands (higher priority than regular movement)
             Box::new(ScrollLeftCommand),
             Box::new(ScrollRightCommand),
+            // Sideways scroll commands (zl, zh, zL, zH)
+            Box::new(EnterScrollPrefixCommand),
+            Box::new(ScrollSidewaysCommand),
             // Pagination commands (high priority - Ctrl+key combinations)
             Box::new(PageDownCommand),
             Box::new(PageUpCommand),
             Box::new(HalfPageDownCommand),
             Box::new(HalfPageUpCommand),
+            // Count prefix for operators and motions (must see 0 before BeginningOfLine)
+            Box::new(CountDigitCommand),
             // Movement commands
             Box::new(MoveCursorLeftCommand),
             Box::new(MoveCursorRightCommand),
@@ -145,6 +194,12 @@ impl CommandRegistry {
             Box::new(ExitVisualModeCommand),
             Box::new(EnterCommandModeCommand),
             Box::new(ExCommandModeCommand),
+            // Search commands
+            Box::new(SearchForwardCommand),
+            Box::new(SearchBackwardCommand),
+            Box::new(SearchNextCommand),
+            Box::new(OperatorSearchCommand),
+            Box::new(SearchInputCommand),
             // Pane commands
             Box::new(SwitchPaneCommand),
             // Editing commands
@@ -153,6 +208,27 @@ impl CommandRegistry {
             Box::new(InsertTabCommand),
             Box::new(DeleteCharCommand),
             Box::new(DeleteCharAtCursorCommand),
+            Box::new(DeleteWordBeforeCursorCommand),
+            Box::new(DeleteToLineStartCommand),
+            Box::new(EnterInsertRegisterPrefixCommand),
+            Box::new(InsertRegisterCommand),
+            Box::new(EnterInsertLiteralPrefixCommand),
+            Box::new(InsertLastInsertedCommand),
+            Box::new(IndentLineCommand),
+            Box::new(DedentLineCommand),
+            Box::new(EnterRegisterPrefixCommand),
+            Box::new(SelectRegisterCommand),
+            Box::new(JoinLinesCommand),
+            Box::new(RepeatLastChangeCommand),
+            Box::new(IndentSelectionCommand),
+            Box::new(DedentSelectionCommand),
+            Box::new(EnterEqualPrefixCommand),
+            Box::new(ReindentLinesCommand),
+            Box::new(EnterShiftPrefixCommand),
+            Box::new(IndentLinesCommand),
+            Box::new(DedentLinesCommand),
+            Box::new(EnterCaseOperatorCommand),
+            Box::new(ChangeCaseCommand),
             Box::new(YankCommand),
             Box::new(DeleteSelectionCommand),
             Box::new(CutSelectionCommand),
@@ -160,9 +236,14 @@ impl CommandRegistry {
             Box::new(CutToEndOfLineCommand),
             Box::new(EnterDPrefixCommand),
             Box::new(CutCurrentLineCommand),
+            Box::new(EnterDFPrefixCommand),
+            Box::new(DeleteToCharCommand),
+            Box::new(EnterDAPrefixCommand),
+            Box::new(DeleteAroundWordCommand),
             Box::new(EnterYPrefixCommand),
             Box::new(YankCurrentLineCommand),
             Box::new(ChangeSelectionCommand),
+            Box::new(EnterCPrefixCommand),
             Box::new(PasteAfterCommand),
             Box::new(PasteAtCursorCommand),
         ];
@@ -259,7 +340,7 @@ impl Default for CommandRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::repl::events::{EditorMode, LogicalPosition, Pane};
+    use crate::repl::events::{EditorMode, KeyBinding, LogicalPosition, Pane};
     use crossterm::event::{KeyCode, KeyModifiers};
 
     fn create_test_key_event(code: KeyCode) -> KeyEvent {
@@ -277,6 +358,10 @@ mod tests {
                 terminal_dimensions: (80, 24),
                 expand_tab: false,
                 tab_width: 4,
+                pending_count: None,
+                command_window_open: false,
+                whichwrap: String::new(),
+                execute_key: KeyBinding::ENTER,
             },
         }
     }
@@ -344,7 +429,7 @@ mod tests {
         let registry = CommandRegistry::new();
         let context = create_test_context();
 
-        let event = create_test_key_event(KeyCode::Char('z')); // No command for 'z' in Normal mode
+        let event = create_test_key_event(KeyCode::Char('Q')); // No command for 'Q' in Normal mode
         let events = registry.process_event(event, &context).unwrap();
 
         assert!(events.is_empty());
@@ -399,15 +484,23 @@ mod tests {
     }
 
     #[test]
-    fn registry_should_not_handle_regular_f_as_page_down() {
+    fn registry_should_handle_regular_f_as_find_char_not_page_down() {
         let registry = CommandRegistry::new();
         let context = create_test_context();
 
         let event = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::empty());
         let events = registry.process_event(event, &context).unwrap();
 
-        // Should not produce any events (regular 'f' has no command in Normal mode)
-        assert!(events.is_empty());
+        // Regular 'f' waits for the character to find
+        assert_eq!(
+            events,
+            vec![
+                CommandEvent::FindCharStartRequested {
+                    find: CharFind::Forward
+                },
+                CommandEvent::mode_change(EditorMode::FPrefix),
+            ]
+        );
     }
 
     #[test]
@@ -206,6 +206,26 @@ impl Command for RepeatVisualSelectionCommand {
     }
 }
 
+/// Select the last inserted or pasted text (gV command, like `` `[v`] ``)
+pub struct SelectLastChangeCommand;
+
+impl Command for SelectLastChangeCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('V'))
+            && context.state.current_mode == EditorMode::GPrefix
+            && context.state.current_pane == Pane::Request
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::SelectLastChangeRequested])
+    }
+
+    fn name(&self) -> &'static str {
+        "SelectLastChange"
+    }
+}
+
 /// Enter command mode (: key)
 pub struct EnterCommandModeCommand;
 
@@ -326,6 +346,12 @@ impl Command for ExCommandModeCommand {
                 Ok(vec![CommandEvent::ExCommandCharRequested { ch }])
             }
             KeyCode::Backspace => Ok(vec![CommandEvent::ExCommandBackspaceRequested]),
+            KeyCode::Tab => Ok(vec![CommandEvent::ExCommandCompleteRequested {
+                forward: true,
+            }]),
+            KeyCode::BackTab => Ok(vec![CommandEvent::ExCommandCompleteRequested {
+                forward: false,
+            }]),
             KeyCode::Enter => Ok(vec![CommandEvent::ExCommandExecuteRequested]),
             KeyCode::Esc => Ok(vec![CommandEvent::restore_previous_mode()]),
             _ => Ok(vec![]),
@@ -337,6 +363,68 @@ impl Command for ExCommandModeCommand {
     }
 }
 
+/// Enter Q prefix mode on first 'q' press (for q:)
+pub struct EnterQPrefixCommand;
+
+impl Command for EnterQPrefixCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('q'))
+            && context.state.current_mode == EditorMode::Normal
+            && event.modifiers.is_empty()
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::mode_change(EditorMode::QPrefix)])
+    }
+
+    fn name(&self) -> &'static str {
+        "EnterQPrefix"
+    }
+}
+
+/// Open the command-line window listing the ex command history (q:)
+pub struct OpenCommandWindowCommand;
+
+impl Command for OpenCommandWindowCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char(':'))
+            && context.state.current_mode == EditorMode::QPrefix
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![
+            CommandEvent::mode_change(EditorMode::Normal),
+            CommandEvent::CommandWindowOpenRequested,
+        ])
+    }
+
+    fn name(&self) -> &'static str {
+        "OpenCommandWindow"
+    }
+}
+
+/// Execute the line under the cursor in the command-line window (Enter)
+pub struct ExecuteCommandWindowLineCommand;
+
+impl Command for ExecuteCommandWindowLineCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Enter)
+            && context.state.command_window_open
+            && context.state.current_mode == EditorMode::Normal
+            && context.state.current_pane == Pane::Request
+            && event.modifiers.is_empty()
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::CommandWindowExecuteRequested])
+    }
+
+    fn name(&self) -> &'static str {
+        "ExecuteCommandWindowLine"
+    }
+}
+
 /// Insert at beginning of Visual Block selection (Shift+I in Visual Block mode)
 pub struct VisualBlockInsertCommand;
 
@@ -393,7 +481,7 @@ impl Command for VisualBlockAppendCommand {
 mod tests {
     use super::*;
     use crate::repl::commands::ViewModelSnapshot;
-    use crate::repl::events::LogicalPosition;
+    use crate::repl::events::{KeyBinding, LogicalPosition};
     use crossterm::event::KeyModifiers;
 
     fn create_test_key_event(code: KeyCode) -> KeyEvent {
@@ -411,10 +499,28 @@ mod tests {
                 terminal_dimensions: (80, 24),
                 expand_tab: false,
                 tab_width: 4,
+                pending_count: None,
+                command_window_open: false,
+                whichwrap: String::new(),
+                execute_key: KeyBinding::ENTER,
             },
         }
     }
 
+    #[test]
+    fn g_shift_v_should_request_selecting_last_change() {
+        let mut context = create_test_context();
+        context.state.current_mode = EditorMode::GPrefix;
+        let event = KeyEvent::new(KeyCode::Char('V'), KeyModifiers::SHIFT);
+
+        assert!(SelectLastChangeCommand.is_relevant(&context, &event));
+        assert!(!RepeatVisualSelectionCommand.is_relevant(&context, &event));
+        assert_eq!(
+            SelectLastChangeCommand.execute(event, &context).unwrap(),
+            vec![CommandEvent::SelectLastChangeRequested]
+        );
+    }
+
     #[test]
     fn append_at_end_of_line_should_be_relevant_for_uppercase_a_in_normal_mode() {
         let context = create_test_context();
@@ -803,4 +909,52 @@ mod tests {
         assert_eq!(result.len(), 1);
         assert_eq!(result[0], CommandEvent::mode_change(EditorMode::Command));
     }
+
+    #[test]
+    fn enter_visual_block_mode_should_leave_ctrl_v_in_insert_mode_to_literal_insert() {
+        let mut context = create_test_context();
+        context.state.current_mode = EditorMode::Insert;
+        let cmd = EnterVisualBlockModeCommand;
+        let event = KeyEvent::new(KeyCode::Char('v'), KeyModifiers::CONTROL);
+
+        assert!(!cmd.is_relevant(&context, &event));
+    }
+
+    #[test]
+    fn q_colon_should_open_command_window() {
+        let mut context = create_test_context();
+        let q = create_test_key_event(KeyCode::Char('q'));
+        let colon = create_test_key_event(KeyCode::Char(':'));
+
+        assert!(EnterQPrefixCommand.is_relevant(&context, &q));
+        assert!(!OpenCommandWindowCommand.is_relevant(&context, &colon));
+
+        context.state.current_mode = EditorMode::QPrefix;
+        assert!(OpenCommandWindowCommand.is_relevant(&context, &colon));
+        assert!(!EnterCommandModeCommand.is_relevant(&context, &colon));
+        assert_eq!(
+            OpenCommandWindowCommand.execute(colon, &context).unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::CommandWindowOpenRequested,
+            ]
+        );
+    }
+
+    #[test]
+    fn enter_should_execute_command_window_line_only_while_window_is_open() {
+        let mut context = create_test_context();
+        let event = create_test_key_event(KeyCode::Enter);
+
+        assert!(!ExecuteCommandWindowLineCommand.is_relevant(&context, &event));
+
+        context.state.command_window_open = true;
+        assert!(ExecuteCommandWindowLineCommand.is_relevant(&context, &event));
+        assert_eq!(
+            ExecuteCommandWindowLineCommand
+                .execute(event, &context)
+                .unwrap(),
+            vec![CommandEvent::CommandWindowExecuteRequested]
+        );
+    }
 }
@@ -3,11 +3,14 @@
 //! Commands for cursor movement including basic h,j,k,l navigation
 //! and arrow key support for all modes.
 
-use crate::repl::events::EditorMode;
+use crate::repl::events::{EditorMode, Pane};
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-use super::{is_navigation_mode, Command, CommandContext, CommandEvent, MovementDirection};
+use super::{
+    is_navigation_mode, CharFind, Command, CommandContext, CommandEvent, MovementDirection,
+    Operator,
+};
 
 /// Move cursor left (h key or left arrow)
 pub struct MoveCursorLeftCommand;
@@ -24,8 +27,13 @@ impl Command for MoveCursorLeftCommand {
         }
     }
 
-    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
-        Ok(vec![CommandEvent::cursor_move(MovementDirection::Left)])
+    fn execute(&self, event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let direction = if may_wrap(context, &event, 'h', '<', '[') {
+            MovementDirection::LeftWrap
+        } else {
+            MovementDirection::Left
+        };
+        Ok(vec![CommandEvent::cursor_move(direction)])
     }
 
     fn name(&self) -> &'static str {
@@ -48,8 +56,13 @@ impl Command for MoveCursorRightCommand {
         }
     }
 
-    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
-        Ok(vec![CommandEvent::cursor_move(MovementDirection::Right)])
+    fn execute(&self, event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let direction = if may_wrap(context, &event, 'l', '>', ']') {
+            MovementDirection::RightWrap
+        } else {
+            MovementDirection::Right
+        };
+        Ok(vec![CommandEvent::cursor_move(direction)])
     }
 
     fn name(&self) -> &'static str {
@@ -57,6 +70,29 @@ impl Command for MoveCursorRightCommand {
     }
 }
 
+/// Check whether `:set whichwrap` lets a left/right key cross a line boundary
+///
+/// Like Vim, the letter key and the arrow keys have separate flags, and the
+/// arrows have one flag in normal and visual mode and another in insert mode.
+fn may_wrap(
+    context: &CommandContext,
+    event: &KeyEvent,
+    letter: char,
+    arrow: char,
+    insert_arrow: char,
+) -> bool {
+    let flag = match event.code {
+        KeyCode::Char(_) => letter,
+        _ if context.state.current_mode == EditorMode::Insert => insert_arrow,
+        _ => arrow,
+    };
+    context
+        .state
+        .whichwrap
+        .split(',')
+        .any(|item| item == flag.to_string())
+}
+
 /// Move cursor up (k key or up arrow)
 pub struct MoveCursorUpCommand;
 
@@ -151,6 +187,58 @@ impl Command for ScrollRightCommand {
     }
 }
 
+/// Enter scroll prefix mode on 'z' press, waiting for the scroll to make
+pub struct EnterScrollPrefixCommand;
+
+impl Command for EnterScrollPrefixCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('z'))
+            && context.state.current_mode == EditorMode::Normal
+            && event.modifiers.is_empty()
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::mode_change(EditorMode::ScrollPrefix)])
+    }
+
+    fn name(&self) -> &'static str {
+        "EnterScrollPrefix"
+    }
+}
+
+/// Scroll the view sideways while lines are not wrapped
+///
+/// `zl`/`zh` scroll by the count (default one column) and `zL`/`zH` by half
+/// the screen width. Any other key cancels back to normal mode.
+pub struct ScrollSidewaysCommand;
+
+impl Command for ScrollSidewaysCommand {
+    fn is_relevant(&self, context: &CommandContext, _event: &KeyEvent) -> bool {
+        context.state.current_mode == EditorMode::ScrollPrefix
+    }
+
+    fn execute(&self, event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let count = context.state.pending_count.unwrap_or(1);
+        let half_width = (context.state.terminal_dimensions.0 as usize / 2).max(1);
+        let mut events = vec![CommandEvent::mode_change(EditorMode::Normal)];
+        let scroll = match event.code {
+            KeyCode::Char('l') | KeyCode::Right => Some((MovementDirection::ScrollRight, count)),
+            KeyCode::Char('h') | KeyCode::Left => Some((MovementDirection::ScrollLeft, count)),
+            KeyCode::Char('L') => Some((MovementDirection::ScrollRight, half_width)),
+            KeyCode::Char('H') => Some((MovementDirection::ScrollLeft, half_width)),
+            _ => None,
+        };
+        if let Some((direction, amount)) = scroll {
+            events.push(CommandEvent::cursor_move_by(direction, amount));
+        }
+        Ok(events)
+    }
+
+    fn name(&self) -> &'static str {
+        "ScrollSideways"
+    }
+}
+
 /// Enter G prefix mode on first 'g' press
 pub struct EnterGPrefixCommand;
 
@@ -192,6 +280,50 @@ impl Command for GoToTopCommand {
     }
 }
 
+/// Move down one display line within wrapped text (gj command)
+pub struct DisplayLineDownCommand;
+
+impl Command for DisplayLineDownCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('j'))
+            && context.state.current_mode == EditorMode::GPrefix
+            && event.modifiers.is_empty()
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![
+            CommandEvent::cursor_move(MovementDirection::DisplayLineDown),
+            CommandEvent::mode_change(EditorMode::Normal),
+        ])
+    }
+
+    fn name(&self) -> &'static str {
+        "DisplayLineDown"
+    }
+}
+
+/// Move up one display line within wrapped text (gk command)
+pub struct DisplayLineUpCommand;
+
+impl Command for DisplayLineUpCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('k'))
+            && context.state.current_mode == EditorMode::GPrefix
+            && event.modifiers.is_empty()
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![
+            CommandEvent::cursor_move(MovementDirection::DisplayLineUp),
+            CommandEvent::mode_change(EditorMode::Normal),
+        ])
+    }
+
+    fn name(&self) -> &'static str {
+        "DisplayLineUp"
+    }
+}
+
 /// Go to bottom of current pane (G command)
 pub struct GoToBottomCommand;
 
@@ -495,11 +627,317 @@ impl Command for HalfPageUpCommand {
     }
 }
 
+/// Start a forward search (/ key)
+pub struct SearchForwardCommand;
+
+impl Command for SearchForwardCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('/'))
+            && context.state.current_mode == EditorMode::Normal
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::SearchStartRequested { forward: true }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SearchForward"
+    }
+}
+
+/// Start a backward search (? key)
+pub struct SearchBackwardCommand;
+
+impl Command for SearchBackwardCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('?'))
+            && context.state.current_mode == EditorMode::Normal
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::SearchStartRequested { forward: false }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SearchBackward"
+    }
+}
+
+/// Repeat the last search forward (n key) or backward (N key)
+pub struct SearchNextCommand;
+
+impl Command for SearchNextCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('n') | KeyCode::Char('N'))
+            && context.state.current_mode == EditorMode::Normal
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::SearchNextRequested {
+            forward: event.code == KeyCode::Char('n'),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "SearchNext"
+    }
+}
+
+/// Enter F prefix mode on 'f', 'F', 't' or 'T' press, waiting for the character to find
+pub struct EnterFPrefixCommand;
+
+impl Command for EnterFPrefixCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char(key) if CharFind::from_key(key).is_some())
+            && context.state.current_mode == EditorMode::Normal
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let KeyCode::Char(key) = event.code else {
+            return Ok(vec![]);
+        };
+        let Some(find) = CharFind::from_key(key) else {
+            return Ok(vec![]);
+        };
+        Ok(vec![
+            CommandEvent::FindCharStartRequested { find },
+            CommandEvent::mode_change(EditorMode::FPrefix),
+        ])
+    }
+
+    fn name(&self) -> &'static str {
+        "EnterFPrefix"
+    }
+}
+
+/// Move to the character typed after 'f', repeated by the pending count (`2fx`)
+///
+/// Any key other than a character cancels back to normal mode.
+pub struct FindCharCommand;
+
+impl Command for FindCharCommand {
+    fn is_relevant(&self, context: &CommandContext, _event: &KeyEvent) -> bool {
+        context.state.current_mode == EditorMode::FPrefix
+    }
+
+    fn execute(&self, event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let mut events = vec![CommandEvent::mode_change(EditorMode::Normal)];
+        if let KeyCode::Char(target) = event.code {
+            events.push(CommandEvent::FindCharRequested {
+                target,
+                count: context.state.pending_count.unwrap_or(1),
+            });
+        }
+        Ok(events)
+    }
+
+    fn name(&self) -> &'static str {
+        "FindChar"
+    }
+}
+
+/// Repeat the last character find on ';', or reverse it on ','
+pub struct RepeatFindCharCommand;
+
+impl Command for RepeatFindCharCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char(';') | KeyCode::Char(','))
+            && context.state.current_mode == EditorMode::Normal
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::RepeatFindCharRequested {
+            reverse: event.code == KeyCode::Char(','),
+            count: context.state.pending_count.unwrap_or(1),
+        }])
+    }
+
+    fn name(&self) -> &'static str {
+        "RepeatFindChar"
+    }
+}
+
+/// Enter a mark prefix mode, waiting for the mark name
+///
+/// '`' and '\'' wait for the mark to jump to, 'm' for the mark to set.
+pub struct EnterMarkPrefixCommand;
+
+impl Command for EnterMarkPrefixCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('`' | '\'' | 'm'))
+            && context.state.current_mode == EditorMode::Normal
+            && event.modifiers.is_empty()
+    }
+
+    fn execute(&self, event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let mode = match event.code {
+            KeyCode::Char('`') => EditorMode::MarkPrefix,
+            KeyCode::Char('\'') => EditorMode::MarkLinePrefix,
+            _ => EditorMode::SetMarkPrefix,
+        };
+        Ok(vec![CommandEvent::mode_change(mode)])
+    }
+
+    fn name(&self) -> &'static str {
+        "EnterMarkPrefix"
+    }
+}
+
+/// Remember the cursor position as the mark named by the letter after 'm' (`ma`)
+///
+/// Any other key cancels back to normal mode.
+pub struct SetMarkCommand;
+
+impl Command for SetMarkCommand {
+    fn is_relevant(&self, context: &CommandContext, _event: &KeyEvent) -> bool {
+        context.state.current_mode == EditorMode::SetMarkPrefix
+    }
+
+    fn execute(&self, event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let mut events = vec![CommandEvent::mode_change(EditorMode::Normal)];
+        if let KeyCode::Char(mark) = event.code {
+            if mark.is_ascii_alphabetic() {
+                events.push(CommandEvent::SetMarkRequested { mark });
+            }
+        }
+        Ok(events)
+    }
+
+    fn name(&self) -> &'static str {
+        "SetMark"
+    }
+}
+
+/// Jump to a letter mark: to its position (`` `a ``) or its line's first non-blank (`'a`)
+///
+/// Any other key after '\'' cancels back to normal mode; after '`' it is left
+/// to [`JumpToChangeMarkCommand`].
+pub struct JumpToMarkCommand;
+
+impl Command for JumpToMarkCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        match context.state.current_mode {
+            EditorMode::MarkLinePrefix => true,
+            EditorMode::MarkPrefix => {
+                matches!(event.code, KeyCode::Char(mark) if mark.is_ascii_alphabetic())
+            }
+            _ => false,
+        }
+    }
+
+    fn execute(&self, event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let mut events = vec![CommandEvent::mode_change(EditorMode::Normal)];
+        if let KeyCode::Char(mark) = event.code {
+            if mark.is_ascii_alphabetic() {
+                events.push(CommandEvent::JumpToMarkRequested {
+                    mark,
+                    line_wise: context.state.current_mode == EditorMode::MarkLinePrefix,
+                });
+            }
+        }
+        Ok(events)
+    }
+
+    fn name(&self) -> &'static str {
+        "JumpToMark"
+    }
+}
+
+/// Jump to the start (`` `[ ``) or end (`` `] ``) of the last inserted or pasted text
+///
+/// Any other key cancels back to normal mode.
+pub struct JumpToChangeMarkCommand;
+
+impl Command for JumpToChangeMarkCommand {
+    fn is_relevant(&self, context: &CommandContext, _event: &KeyEvent) -> bool {
+        context.state.current_mode == EditorMode::MarkPrefix
+    }
+
+    fn execute(&self, event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let mut events = vec![CommandEvent::mode_change(EditorMode::Normal)];
+        match event.code {
+            KeyCode::Char('[') => {
+                events.push(CommandEvent::JumpToChangeMarkRequested { end: false })
+            }
+            KeyCode::Char(']') => {
+                events.push(CommandEvent::JumpToChangeMarkRequested { end: true })
+            }
+            _ => {}
+        }
+        Ok(events)
+    }
+
+    fn name(&self) -> &'static str {
+        "JumpToChangeMark"
+    }
+}
+
+/// Use a search as the motion of a pending operator (`d/`, `y/`, `c/`)
+pub struct OperatorSearchCommand;
+
+impl Command for OperatorSearchCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('/'))
+            && matches!(
+                context.state.current_mode,
+                EditorMode::DPrefix | EditorMode::YPrefix | EditorMode::CPrefix
+            )
+            && context.state.current_pane == Pane::Request
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, _event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let operator = match context.state.current_mode {
+            EditorMode::YPrefix => Operator::Yank,
+            EditorMode::CPrefix => Operator::Change,
+            _ => Operator::Delete,
+        };
+        Ok(vec![
+            CommandEvent::mode_change(EditorMode::Normal),
+            CommandEvent::OperatorSearchStartRequested { operator },
+        ])
+    }
+
+    fn name(&self) -> &'static str {
+        "OperatorSearch"
+    }
+}
+
+/// Handle all search mode input (typing, backspace, execute, cancel)
+pub struct SearchInputCommand;
+
+impl Command for SearchInputCommand {
+    fn is_relevant(&self, context: &CommandContext, _event: &KeyEvent) -> bool {
+        context.state.current_mode == EditorMode::Search
+    }
+
+    fn execute(&self, event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        match event.code {
+            KeyCode::Char(ch) if !event.modifiers.contains(KeyModifiers::CONTROL) => {
+                Ok(vec![CommandEvent::SearchCharRequested { ch }])
+            }
+            KeyCode::Backspace => Ok(vec![CommandEvent::SearchBackspaceRequested]),
+            KeyCode::Enter => Ok(vec![CommandEvent::SearchExecuteRequested]),
+            KeyCode::Esc => Ok(vec![CommandEvent::SearchCancelRequested]),
+            _ => Ok(vec![]),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "SearchInput"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::repl::commands::context::ViewModelSnapshot;
-    use crate::repl::events::{EditorMode, LogicalPosition, Pane};
+    use crate::repl::events::{EditorMode, KeyBinding, LogicalPosition, Pane};
     use crossterm::event::KeyModifiers;
 
     fn create_test_key_event(code: KeyCode) -> KeyEvent {
@@ -516,6 +954,10 @@ mod tests {
             terminal_dimensions: (80, 24),
             expand_tab: false,
             tab_width: 4,
+            pending_count: None,
+            command_window_open: false,
+            whichwrap: String::new(),
+            execute_key: KeyBinding::ENTER,
         };
         CommandContext::new(snapshot)
     }
@@ -601,6 +1043,33 @@ mod tests {
         assert_eq!(events[1], CommandEvent::mode_change(EditorMode::Normal));
     }
 
+    #[test]
+    fn display_line_down_should_be_relevant_for_j_in_g_mode() {
+        let context = create_test_context(EditorMode::GPrefix);
+        let cmd = DisplayLineDownCommand;
+        let event = create_test_key_event(KeyCode::Char('j'));
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert!(!cmd.is_relevant(&create_test_context(EditorMode::Normal), &event));
+    }
+
+    #[test]
+    fn display_line_up_should_produce_display_line_up_and_normal_mode_events() {
+        let context = create_test_context(EditorMode::GPrefix);
+        let cmd = DisplayLineUpCommand;
+        let event = create_test_key_event(KeyCode::Char('k'));
+
+        assert!(cmd.is_relevant(&context, &event));
+        let events = cmd.execute(event, &context).unwrap();
+        assert_eq!(
+            events,
+            vec![
+                CommandEvent::cursor_move(MovementDirection::DisplayLineUp),
+                CommandEvent::mode_change(EditorMode::Normal),
+            ]
+        );
+    }
+
     #[test]
     fn move_cursor_left_should_be_relevant_for_h_in_normal_mode() {
         let context = create_test_context(EditorMode::Normal);
@@ -610,6 +1079,35 @@ mod tests {
         assert!(cmd.is_relevant(&context, &event));
     }
 
+    #[test]
+    fn move_cursor_right_should_wrap_only_for_keys_in_whichwrap() {
+        let mut context = create_test_context(EditorMode::Normal);
+        let l = create_test_key_event(KeyCode::Char('l'));
+        let right = create_test_key_event(KeyCode::Right);
+
+        assert_eq!(
+            MoveCursorRightCommand.execute(l, &context).unwrap(),
+            vec![CommandEvent::cursor_move(MovementDirection::Right)]
+        );
+
+        context.state.whichwrap = "l,[".to_string();
+        assert_eq!(
+            MoveCursorRightCommand.execute(l, &context).unwrap(),
+            vec![CommandEvent::cursor_move(MovementDirection::RightWrap)]
+        );
+        assert_eq!(
+            MoveCursorRightCommand.execute(right, &context).unwrap(),
+            vec![CommandEvent::cursor_move(MovementDirection::Right)]
+        );
+        context.state.current_mode = EditorMode::Insert;
+        assert_eq!(
+            MoveCursorLeftCommand
+                .execute(create_test_key_event(KeyCode::Left), &context)
+                .unwrap(),
+            vec![CommandEvent::cursor_move(MovementDirection::LeftWrap)]
+        );
+    }
+
     #[test]
     fn go_to_bottom_should_be_relevant_for_uppercase_g_in_normal_mode() {
         let context = create_test_context(EditorMode::Normal);
@@ -1233,4 +1731,299 @@ mod tests {
         let cmd = PageUpCommand;
         assert_eq!(cmd.name(), "PageUp");
     }
+
+    #[test]
+    fn search_forward_should_start_search_on_slash_in_normal_mode() {
+        let context = create_test_context(EditorMode::Normal);
+        let cmd = SearchForwardCommand;
+        let event = KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE);
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![CommandEvent::SearchStartRequested { forward: true }]
+        );
+        assert!(!cmd.is_relevant(&create_test_context(EditorMode::Insert), &event));
+    }
+
+    #[test]
+    fn search_backward_should_start_backward_search_on_question_mark() {
+        let context = create_test_context(EditorMode::Normal);
+        let cmd = SearchBackwardCommand;
+        let event = KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT);
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![CommandEvent::SearchStartRequested { forward: false }]
+        );
+        assert!(!cmd.is_relevant(&create_test_context(EditorMode::Search), &event));
+    }
+
+    #[test]
+    fn search_next_should_repeat_search_on_n_and_shift_n() {
+        let context = create_test_context(EditorMode::Normal);
+        let cmd = SearchNextCommand;
+        let n = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE);
+        let shift_n = KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT);
+
+        assert!(cmd.is_relevant(&context, &n));
+        assert_eq!(
+            cmd.execute(n, &context).unwrap(),
+            vec![CommandEvent::SearchNextRequested { forward: true }]
+        );
+        assert_eq!(
+            cmd.execute(shift_n, &context).unwrap(),
+            vec![CommandEvent::SearchNextRequested { forward: false }]
+        );
+        assert!(!cmd.is_relevant(&create_test_context(EditorMode::Insert), &n));
+    }
+
+    #[test]
+    fn search_input_should_map_keys_to_search_events() {
+        let context = create_test_context(EditorMode::Search);
+        let cmd = SearchInputCommand;
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        assert!(cmd.is_relevant(&context, &key(KeyCode::Char('n'))));
+        assert_eq!(
+            cmd.execute(key(KeyCode::Char('/')), &context).unwrap(),
+            vec![CommandEvent::SearchCharRequested { ch: '/' }]
+        );
+        assert_eq!(
+            cmd.execute(key(KeyCode::Backspace), &context).unwrap(),
+            vec![CommandEvent::SearchBackspaceRequested]
+        );
+        assert_eq!(
+            cmd.execute(key(KeyCode::Enter), &context).unwrap(),
+            vec![CommandEvent::SearchExecuteRequested]
+        );
+        assert_eq!(
+            cmd.execute(key(KeyCode::Esc), &context).unwrap(),
+            vec![CommandEvent::SearchCancelRequested]
+        );
+    }
+
+    #[test]
+    fn find_char_should_use_pending_count() {
+        let mut context = create_test_context(EditorMode::Normal);
+        let f = create_test_key_event(KeyCode::Char('f'));
+        assert!(EnterFPrefixCommand.is_relevant(&context, &f));
+        assert_eq!(
+            EnterFPrefixCommand.execute(f, &context).unwrap(),
+            vec![
+                CommandEvent::FindCharStartRequested {
+                    find: CharFind::Forward
+                },
+                CommandEvent::mode_change(EditorMode::FPrefix),
+            ]
+        );
+        let shift_t = KeyEvent::new(KeyCode::Char('T'), KeyModifiers::SHIFT);
+        assert!(EnterFPrefixCommand.is_relevant(&context, &shift_t));
+        assert_eq!(
+            EnterFPrefixCommand.execute(shift_t, &context).unwrap()[0],
+            CommandEvent::FindCharStartRequested {
+                find: CharFind::TillBackward
+            }
+        );
+
+        context.state.current_mode = EditorMode::FPrefix;
+        context.state.pending_count = Some(3);
+        let cmd = FindCharCommand;
+        let event = create_test_key_event(KeyCode::Char('a'));
+
+        assert!(cmd.is_relevant(&context, &event));
+        assert_eq!(
+            cmd.execute(event, &context).unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::FindCharRequested {
+                    target: 'a',
+                    count: 3
+                },
+            ]
+        );
+        assert_eq!(
+            cmd.execute(create_test_key_event(KeyCode::Esc), &context)
+                .unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::Normal)]
+        );
+    }
+
+    #[test]
+    fn semicolon_and_comma_should_repeat_last_find_both_ways() {
+        let mut context = create_test_context(EditorMode::Normal);
+        context.state.pending_count = Some(2);
+        let cmd = RepeatFindCharCommand;
+
+        assert_eq!(
+            cmd.execute(create_test_key_event(KeyCode::Char(';')), &context)
+                .unwrap(),
+            vec![CommandEvent::RepeatFindCharRequested {
+                reverse: false,
+                count: 2
+            }]
+        );
+        let comma = create_test_key_event(KeyCode::Char(','));
+        assert!(cmd.is_relevant(&context, &comma));
+        assert!(!cmd.is_relevant(&create_test_context(EditorMode::Insert), &comma));
+    }
+
+    #[test]
+    fn backtick_brackets_should_jump_to_change_marks() {
+        let context = create_test_context(EditorMode::Normal);
+        let backtick = create_test_key_event(KeyCode::Char('`'));
+        assert!(EnterMarkPrefixCommand.is_relevant(&context, &backtick));
+        assert_eq!(
+            EnterMarkPrefixCommand.execute(backtick, &context).unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::MarkPrefix)]
+        );
+
+        let context = create_test_context(EditorMode::MarkPrefix);
+        let cmd = JumpToChangeMarkCommand;
+        assert_eq!(
+            cmd.execute(create_test_key_event(KeyCode::Char('[')), &context)
+                .unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::JumpToChangeMarkRequested { end: false },
+            ]
+        );
+        assert_eq!(
+            cmd.execute(create_test_key_event(KeyCode::Char(']')), &context)
+                .unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::JumpToChangeMarkRequested { end: true },
+            ]
+        );
+        assert_eq!(
+            cmd.execute(create_test_key_event(KeyCode::Char('x')), &context)
+                .unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::Normal)]
+        );
+    }
+
+    #[test]
+    fn m_backtick_and_quote_should_set_and_jump_to_letter_marks() {
+        let context = create_test_context(EditorMode::Normal);
+        for (key, mode) in [
+            ('m', EditorMode::SetMarkPrefix),
+            ('\'', EditorMode::MarkLinePrefix),
+        ] {
+            let event = create_test_key_event(KeyCode::Char(key));
+            assert!(EnterMarkPrefixCommand.is_relevant(&context, &event));
+            assert_eq!(
+                EnterMarkPrefixCommand.execute(event, &context).unwrap(),
+                vec![CommandEvent::mode_change(mode)]
+            );
+        }
+
+        let context = create_test_context(EditorMode::SetMarkPrefix);
+        assert_eq!(
+            SetMarkCommand
+                .execute(create_test_key_event(KeyCode::Char('a')), &context)
+                .unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::SetMarkRequested { mark: 'a' },
+            ]
+        );
+        assert_eq!(
+            SetMarkCommand
+                .execute(create_test_key_event(KeyCode::Char('1')), &context)
+                .unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::Normal)]
+        );
+
+        let a = create_test_key_event(KeyCode::Char('a'));
+        let context = create_test_context(EditorMode::MarkPrefix);
+        assert!(JumpToMarkCommand.is_relevant(&context, &a));
+        assert!(
+            !JumpToMarkCommand.is_relevant(&context, &create_test_key_event(KeyCode::Char('[')))
+        );
+        assert_eq!(
+            JumpToMarkCommand.execute(a, &context).unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::JumpToMarkRequested {
+                    mark: 'a',
+                    line_wise: false
+                },
+            ]
+        );
+
+        let context = create_test_context(EditorMode::MarkLinePrefix);
+        assert_eq!(
+            JumpToMarkCommand.execute(a, &context).unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::JumpToMarkRequested {
+                    mark: 'a',
+                    line_wise: true
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn z_prefix_should_scroll_sideways_by_count_or_half_screen() {
+        let context = create_test_context(EditorMode::Normal);
+        let z = create_test_key_event(KeyCode::Char('z'));
+        assert!(EnterScrollPrefixCommand.is_relevant(&context, &z));
+        assert_eq!(
+            EnterScrollPrefixCommand.execute(z, &context).unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::ScrollPrefix)]
+        );
+
+        let mut context = create_test_context(EditorMode::ScrollPrefix);
+        context.state.pending_count = Some(3);
+        let cmd = ScrollSidewaysCommand;
+        assert_eq!(
+            cmd.execute(create_test_key_event(KeyCode::Char('l')), &context)
+                .unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::cursor_move_by(MovementDirection::ScrollRight, 3),
+            ]
+        );
+
+        context.state.pending_count = None;
+        assert_eq!(
+            cmd.execute(create_test_key_event(KeyCode::Char('H')), &context)
+                .unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::cursor_move_by(MovementDirection::ScrollLeft, 40),
+            ]
+        );
+        assert_eq!(
+            cmd.execute(create_test_key_event(KeyCode::Char('x')), &context)
+                .unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::Normal)]
+        );
+    }
+
+    #[test]
+    fn operator_search_should_pick_operator_from_prefix_mode() {
+        let cmd = OperatorSearchCommand;
+        let event = create_test_key_event(KeyCode::Char('/'));
+
+        for (mode, operator) in [
+            (EditorMode::DPrefix, Operator::Delete),
+            (EditorMode::YPrefix, Operator::Yank),
+            (EditorMode::CPrefix, Operator::Change),
+        ] {
+            let context = create_test_context(mode);
+            assert!(cmd.is_relevant(&context, &event));
+            assert_eq!(
+                cmd.execute(event, &context).unwrap(),
+                vec![
+                    CommandEvent::mode_change(EditorMode::Normal),
+                    CommandEvent::OperatorSearchStartRequested { operator },
+                ]
+            );
+        }
+        assert!(!cmd.is_relevant(&create_test_context(EditorMode::Normal), &event));
+    }
 }
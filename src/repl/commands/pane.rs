@@ -49,7 +49,7 @@ impl Command for SwitchPaneCommand {
 mod tests {
     use super::*;
     use crate::repl::commands::ViewModelSnapshot;
-    use crate::repl::events::LogicalPosition;
+    use crate::repl::events::{KeyBinding, LogicalPosition};
     use crossterm::event::KeyModifiers;
 
     fn create_test_key_event(code: KeyCode) -> KeyEvent {
@@ -67,6 +67,10 @@ mod tests {
                 terminal_dimensions: (80, 24),
                 expand_tab: false,
                 tab_width: 4,
+                pending_count: None,
+                command_window_open: false,
+                whichwrap: String::new(),
+                execute_key: KeyBinding::ENTER,
             },
         }
     }
@@ -10,21 +10,21 @@ use super::{
     Command, CommandContext, CommandEvent, HttpClientAccess, HttpCommand, HttpCommandContext,
 };
 
-/// Execute HTTP request (Enter in normal mode)
+/// Execute HTTP request (Enter, or the `executekey`, in normal mode)
 pub struct ExecuteRequestCommand;
 
 impl HttpCommand for ExecuteRequestCommand {
     fn is_relevant(&self, context: &HttpCommandContext, event: &KeyEvent) -> bool {
         let is_enter = matches!(event.code, KeyCode::Enter);
+        let is_execute_key = context.state().execute_key.matches(event);
         let is_normal_mode = context.state().current_mode == EditorMode::Normal;
-        let no_modifiers = event.modifiers.is_empty();
         let is_request_pane = context.state().current_pane == Pane::Request;
 
-        let is_relevant = is_enter && is_normal_mode && no_modifiers && is_request_pane;
+        let is_relevant = is_execute_key && is_normal_mode && is_request_pane;
 
         tracing::debug!(
-            "ExecuteRequestCommand(Http).is_relevant(): enter={}, normal_mode={}, no_modifiers={}, request_pane={}, result={}",
-            is_enter, is_normal_mode, no_modifiers, is_request_pane, is_relevant
+            "ExecuteRequestCommand(Http).is_relevant(): execute_key={}, normal_mode={}, request_pane={}, result={}",
+            is_execute_key, is_normal_mode, is_request_pane, is_relevant
         );
 
         if is_enter && !is_relevant {
@@ -80,15 +80,15 @@ impl HttpCommand for ExecuteRequestCommand {
 impl Command for ExecuteRequestCommand {
     fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
         let is_enter = matches!(event.code, KeyCode::Enter);
+        let is_execute_key = context.state.execute_key.matches(event);
         let is_normal_mode = context.state.current_mode == EditorMode::Normal;
-        let no_modifiers = event.modifiers.is_empty();
         let is_request_pane = context.state.current_pane == Pane::Request;
 
-        let is_relevant = is_enter && is_normal_mode && no_modifiers && is_request_pane;
+        let is_relevant = is_execute_key && is_normal_mode && is_request_pane;
 
         tracing::debug!(
-            "ExecuteRequestCommand.is_relevant(): enter={}, normal_mode={}, no_modifiers={}, request_pane={}, result={}",
-            is_enter, is_normal_mode, no_modifiers, is_request_pane, is_relevant
+            "ExecuteRequestCommand.is_relevant(): execute_key={}, normal_mode={}, request_pane={}, result={}",
+            is_execute_key, is_normal_mode, is_request_pane, is_relevant
         );
 
         if is_enter && !is_relevant {
@@ -139,7 +139,7 @@ impl Command for ExecuteRequestCommand {
 mod tests {
     use super::*;
     use crate::repl::commands::ViewModelSnapshot;
-    use crate::repl::events::{EditorMode, LogicalPosition, Pane};
+    use crate::repl::events::{EditorMode, KeyBinding, LogicalPosition, Pane};
     use crossterm::event::KeyModifiers;
 
     fn create_test_key_event(code: KeyCode) -> KeyEvent {
@@ -157,6 +157,10 @@ mod tests {
                 terminal_dimensions: (80, 24),
                 expand_tab: false,
                 tab_width: 4,
+                pending_count: None,
+                command_window_open: false,
+                whichwrap: String::new(),
+                execute_key: KeyBinding::ENTER,
             },
         }
     }
@@ -259,6 +263,24 @@ mod tests {
         assert!(!Command::is_relevant(&cmd, &context, &event));
     }
 
+    #[test]
+    fn execute_request_should_follow_remapped_execute_key() {
+        let mut context = create_test_context();
+        context.state.execute_key = KeyBinding::parse("<F5>").unwrap();
+        let cmd = ExecuteRequestCommand;
+
+        assert!(Command::is_relevant(
+            &cmd,
+            &context,
+            &create_test_key_event(KeyCode::F(5))
+        ));
+        assert!(!Command::is_relevant(
+            &cmd,
+            &context,
+            &create_test_key_event(KeyCode::Enter)
+        ));
+    }
+
     #[test]
     fn execute_request_should_not_be_relevant_for_shift_enter() {
         let context = create_test_context();
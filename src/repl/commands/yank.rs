@@ -187,6 +187,97 @@ impl Command for CutCurrentLineCommand {
     }
 }
 
+/// Enter DF prefix mode on 'f' after 'd', waiting for the character to delete through
+pub struct EnterDFPrefixCommand;
+
+impl Command for EnterDFPrefixCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('f'))
+            && context.state.current_mode == EditorMode::DPrefix
+            && context.state.current_pane == Pane::Request
+            && event.modifiers.is_empty()
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::mode_change(EditorMode::DFPrefix)])
+    }
+
+    fn name(&self) -> &'static str {
+        "EnterDFPrefix"
+    }
+}
+
+/// Delete through the character typed after 'df', repeated by the pending count (`d2fx`)
+///
+/// Any key other than a character cancels back to normal mode.
+pub struct DeleteToCharCommand;
+
+impl Command for DeleteToCharCommand {
+    fn is_relevant(&self, context: &CommandContext, _event: &KeyEvent) -> bool {
+        context.state.current_mode == EditorMode::DFPrefix
+    }
+
+    fn execute(&self, event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let mut events = vec![CommandEvent::mode_change(EditorMode::Normal)];
+        if let KeyCode::Char(target) = event.code {
+            events.push(CommandEvent::DeleteToCharRequested {
+                target,
+                count: context.state.pending_count.unwrap_or(1),
+            });
+        }
+        Ok(events)
+    }
+
+    fn name(&self) -> &'static str {
+        "DeleteToChar"
+    }
+}
+
+/// Enter DA prefix mode on 'a' after 'd', waiting for the text object to delete
+pub struct EnterDAPrefixCommand;
+
+impl Command for EnterDAPrefixCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('a'))
+            && context.state.current_mode == EditorMode::DPrefix
+            && context.state.current_pane == Pane::Request
+            && event.modifiers.is_empty()
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::mode_change(EditorMode::DAPrefix)])
+    }
+
+    fn name(&self) -> &'static str {
+        "EnterDAPrefix"
+    }
+}
+
+/// Delete "a word" objects after 'da', as many as the pending count (`2daw`, `d2aw`)
+///
+/// Any key other than 'w' cancels back to normal mode.
+pub struct DeleteAroundWordCommand;
+
+impl Command for DeleteAroundWordCommand {
+    fn is_relevant(&self, context: &CommandContext, _event: &KeyEvent) -> bool {
+        context.state.current_mode == EditorMode::DAPrefix
+    }
+
+    fn execute(&self, event: KeyEvent, context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let mut events = vec![CommandEvent::mode_change(EditorMode::Normal)];
+        if event.code == KeyCode::Char('w') && event.modifiers.is_empty() {
+            events.push(CommandEvent::DeleteAroundWordRequested {
+                count: context.state.pending_count.unwrap_or(1),
+            });
+        }
+        Ok(events)
+    }
+
+    fn name(&self) -> &'static str {
+        "DeleteAroundWord"
+    }
+}
+
 /// Paste yanked text at current cursor position
 pub struct PasteAtCursorCommand;
 
@@ -207,6 +298,26 @@ impl Command for PasteAtCursorCommand {
     }
 }
 
+/// Enter C prefix mode on 'c' press in normal mode (for c/pattern)
+pub struct EnterCPrefixCommand;
+
+impl Command for EnterCPrefixCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('c'))
+            && context.state.current_mode == EditorMode::Normal
+            && context.state.current_pane == Pane::Request
+            && event.modifiers.is_empty()
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::mode_change(EditorMode::CPrefix)])
+    }
+
+    fn name(&self) -> &'static str {
+        "EnterCPrefix"
+    }
+}
+
 /// Change (delete and enter insert mode) selected text in visual block mode
 pub struct ChangeSelectionCommand;
 
@@ -271,11 +382,55 @@ impl Command for YankCurrentLineCommand {
     }
 }
 
+/// Enter register prefix mode on '"' press (for `"_dd`)
+pub struct EnterRegisterPrefixCommand;
+
+impl Command for EnterRegisterPrefixCommand {
+    fn is_relevant(&self, context: &CommandContext, event: &KeyEvent) -> bool {
+        matches!(event.code, KeyCode::Char('"'))
+            && context.state.current_mode == EditorMode::Normal
+            && context.state.current_pane == Pane::Request
+            && !event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    fn execute(&self, _event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        Ok(vec![CommandEvent::mode_change(EditorMode::RegisterPrefix)])
+    }
+
+    fn name(&self) -> &'static str {
+        "EnterRegisterPrefix"
+    }
+}
+
+/// Choose the register for the next command after '"' (any other key cancels)
+///
+/// Only the unnamed (`"`), black-hole (`_`) and read-only (`%`, `:`) registers
+/// are accepted, since line cuts and yanks have no named registers to write to.
+pub struct SelectRegisterCommand;
+
+impl Command for SelectRegisterCommand {
+    fn is_relevant(&self, context: &CommandContext, _event: &KeyEvent) -> bool {
+        context.state.current_mode == EditorMode::RegisterPrefix
+    }
+
+    fn execute(&self, event: KeyEvent, _context: &CommandContext) -> Result<Vec<CommandEvent>> {
+        let mut events = vec![CommandEvent::mode_change(EditorMode::Normal)];
+        if let KeyCode::Char(register @ ('"' | '_' | '%' | ':')) = event.code {
+            events.push(CommandEvent::RegisterSelectRequested { register });
+        }
+        Ok(events)
+    }
+
+    fn name(&self) -> &'static str {
+        "SelectRegister"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::repl::commands::ViewModelSnapshot;
-    use crate::repl::events::LogicalPosition;
+    use crate::repl::events::{KeyBinding, LogicalPosition};
 
     fn create_test_context(mode: EditorMode, pane: Pane) -> CommandContext {
         CommandContext {
@@ -288,6 +443,10 @@ mod tests {
                 terminal_dimensions: (80, 24),
                 expand_tab: false,
                 tab_width: 4,
+                pending_count: None,
+                command_window_open: false,
+                whichwrap: String::new(),
+                execute_key: KeyBinding::ENTER,
             },
         }
     }
@@ -807,4 +966,108 @@ mod tests {
         assert_eq!(result[0], CommandEvent::yank_current_line());
         assert_eq!(result[1], CommandEvent::mode_change(EditorMode::Normal));
     }
+
+    #[test]
+    fn enter_register_prefix_should_be_relevant_for_double_quote_in_normal_mode() {
+        let context = create_test_context(EditorMode::Normal, Pane::Request);
+        let event = KeyEvent::new(KeyCode::Char('"'), KeyModifiers::SHIFT);
+        let command = EnterRegisterPrefixCommand;
+
+        assert!(command.is_relevant(&context, &event));
+        assert_eq!(
+            command.execute(event, &context).unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::RegisterPrefix)]
+        );
+        assert!(!command.is_relevant(
+            &create_test_context(EditorMode::Insert, Pane::Request),
+            &event
+        ));
+    }
+
+    #[test]
+    fn select_register_should_choose_black_hole_register() {
+        let context = create_test_context(EditorMode::RegisterPrefix, Pane::Request);
+        let event = KeyEvent::new(KeyCode::Char('_'), KeyModifiers::SHIFT);
+        let command = SelectRegisterCommand;
+
+        assert!(command.is_relevant(&context, &event));
+        assert_eq!(
+            command.execute(event, &context).unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::RegisterSelectRequested { register: '_' },
+            ]
+        );
+    }
+
+    #[test]
+    fn select_register_should_cancel_on_unsupported_register() {
+        let context = create_test_context(EditorMode::RegisterPrefix, Pane::Request);
+        let command = SelectRegisterCommand;
+
+        for code in [KeyCode::Char('a'), KeyCode::Esc] {
+            assert_eq!(
+                command
+                    .execute(KeyEvent::new(code, KeyModifiers::NONE), &context)
+                    .unwrap(),
+                vec![CommandEvent::mode_change(EditorMode::Normal)]
+            );
+        }
+    }
+
+    #[test]
+    fn delete_to_char_should_use_pending_count() {
+        let mut context = create_test_context(EditorMode::DPrefix, Pane::Request);
+        let f = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::empty());
+        assert!(EnterDFPrefixCommand.is_relevant(&context, &f));
+        assert!(!EnterDFPrefixCommand.is_relevant(
+            &create_test_context(EditorMode::DPrefix, Pane::Response),
+            &f
+        ));
+
+        context.state.current_mode = EditorMode::DFPrefix;
+        context.state.pending_count = Some(2);
+        let event = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::empty());
+
+        assert!(DeleteToCharCommand.is_relevant(&context, &event));
+        assert_eq!(
+            DeleteToCharCommand.execute(event, &context).unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::DeleteToCharRequested {
+                    target: 'a',
+                    count: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn delete_around_word_should_use_pending_count_and_cancel_on_other_keys() {
+        let mut context = create_test_context(EditorMode::DPrefix, Pane::Request);
+        let a = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::empty());
+        assert!(EnterDAPrefixCommand.is_relevant(&context, &a));
+        assert_eq!(
+            EnterDAPrefixCommand.execute(a, &context).unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::DAPrefix)]
+        );
+
+        context.state.current_mode = EditorMode::DAPrefix;
+        context.state.pending_count = Some(2);
+        let w = KeyEvent::new(KeyCode::Char('w'), KeyModifiers::empty());
+        assert!(DeleteAroundWordCommand.is_relevant(&context, &w));
+        assert_eq!(
+            DeleteAroundWordCommand.execute(w, &context).unwrap(),
+            vec![
+                CommandEvent::mode_change(EditorMode::Normal),
+                CommandEvent::DeleteAroundWordRequested { count: 2 },
+            ]
+        );
+
+        let x = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::empty());
+        assert_eq!(
+            DeleteAroundWordCommand.execute(x, &context).unwrap(),
+            vec![CommandEvent::mode_change(EditorMode::Normal)]
+        );
+    }
 }
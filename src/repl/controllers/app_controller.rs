@@ -3,28 +3,49 @@
 //! The controller orchestrates the REPL components and manages the event loop.
 //! It's responsible for connecting user input to commands and coordinating view updates.
 
-use crate::config::AppConfig;
+use crate::config::{get_templates_dir, AppConfig};
 use crate::repl::{
     commands::{
-        CommandContext, CommandEvent, CommandRegistry, ExCommandRegistry, MovementDirection,
-        Setting, SettingValue, ViewModelSnapshot,
+        completion_target, CaseChange, CommandContext, CommandEvent, CommandRegistry,
+        CompletionKind, ExCommandRegistry, LineRange, MovementDirection, RecordedChange, Setting,
+        SettingValue, TemplateAction, ViewModelSnapshot,
     },
-    events::{EditorMode, LogicalPosition, Pane, SimpleEventBus},
+    error::{AppError, AppResult},
+    events::{EditorMode, KeyBinding, LogicalPosition, Pane, SimpleEventBus},
     io::{EventStream, RenderStream},
-    services::{HttpResponseMessage, Services},
+    models::{is_read_only_register, register_preview, LineSpan, YankEntry, LISTED_REGISTERS},
+    services::{
+        file, filter,
+        session::{self, Session, DEFAULT_SESSION_FILE},
+        template, ExternalChange, HttpResponseMessage, Services,
+    },
+    text::indent::reindent_json,
     view_models::{
         commands::{
             events::YankType as NewYankType, Command, ExecutionContext, ModelEvent,
             UnifiedCommandRegistry,
         },
-        ViewModel,
+        on_off, ViewModel,
     },
     views::{TerminalRenderer, ViewRenderer},
 };
 use anyhow::Result;
 use bluenote::{get_blank_profile, HttpConnectionProfile, HttpRequestArgs, IniProfileStore};
-use crossterm::event::{Event, KeyEvent};
+use crossterm::event::{Event, KeyCode, KeyEvent};
 use std::time::Duration;
+
+/// A destructive ex command waiting for the answer to its `:set confirm` prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PendingConfirmation {
+    /// `:e` that would throw away unsaved changes
+    Edit { path: String },
+    /// `:[range]d` over more than one line
+    DeleteLines {
+        range: LineRange,
+        register: Option<char>,
+    },
+}
+
 /// The main application controller that orchestrates the MVVM pattern
 pub struct AppController<ES: EventStream, RS: RenderStream> {
     view_model: ViewModel,
@@ -41,6 +62,10 @@ pub struct AppController<ES: EventStream, RS: RenderStream> {
     event_stream: ES,
     should_quit: bool,
     last_render_time: std::time::Instant,
+    // Command waiting for a `(y)es/(n)o/(a)ll` answer; keys go to the prompt until answered
+    pending_confirmation: Option<PendingConfirmation>,
+    // Whether `(a)ll` was answered, skipping prompts until confirm is set again
+    confirm_all: bool,
 }
 
 impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
@@ -51,8 +76,16 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
         // Pass RenderStream ownership to the View layer (TerminalRenderer)
         let view_renderer = TerminalRenderer::with_render_stream(render_stream)?;
 
+        // A restored session brings its own profile
+        let session = config
+            .session_path()
+            .map(session::load_session)
+            .transpose()?;
+
         // Load profile from configuration first (needed for Services)
-        let profile_name = config.profile_name();
+        let profile_name = session
+            .as_ref()
+            .map_or(config.profile_name(), |session| session.profile.as_str());
         let profile_path = config.profile_path();
         let profile = Self::load_profile(profile_name, profile_path)?;
 
@@ -86,6 +119,8 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
             event_stream,
             should_quit: false,
             last_render_time: std::time::Instant::now(),
+            pending_confirmation: None,
+            confirm_all: false,
         };
 
         // Apply initial commands from config file
@@ -97,6 +132,10 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
             controller.apply_initial_commands(config.initial_commands())?;
         }
 
+        if let Some(session) = &session {
+            controller.restore_session(session)?;
+        }
+
         Ok(controller)
     }
 }
@@ -139,15 +178,27 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
         // Store profile information for display
         view_model.set_profile_info(profile_name.to_string(), profile_path.to_string());
 
+        view_model.set_shell(&filter::default_shell());
+
         // Set up event bus in view model
         view_model.set_event_bus(Box::new(SimpleEventBus::new()));
     }
 
     /// Apply initial ex commands from config file
+    ///
+    /// A command that fails is reported to `:messages` and the rest still run,
+    /// so a bad line never keeps blueline from starting.
     fn apply_initial_commands(&mut self, commands: &[String]) -> Result<()> {
         for command in commands {
             tracing::debug!("Applying config command: {}", command);
 
+            if !self.ex_command_registry.can_handle(command) {
+                self.view_model.set_error_message(&AppError::User(format!(
+                    "E492: Not an editor command: {command}"
+                )));
+                continue;
+            }
+
             // Create command context
             let context = CommandContext::new(ViewModelSnapshot::from_view_model(&self.view_model));
 
@@ -160,8 +211,31 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
                             CommandEvent::SettingChangeRequested { setting, value } => {
                                 if let Err(e) = self.handle_setting_change(setting, value) {
                                     tracing::warn!("Failed to apply setting from config: {}", e);
+                                    self.view_model
+                                        .set_error_message(&AppError::User(e.to_string()));
                                 }
                             }
+                            CommandEvent::EqualPrgChangeRequested { program } => {
+                                self.view_model.set_equalprg(&program);
+                            }
+                            CommandEvent::ShellChangeRequested { shell } => {
+                                self.handle_shell_change(shell);
+                            }
+                            CommandEvent::TitleStringChangeRequested { title } => {
+                                self.view_model.set_titlestring(&title);
+                            }
+                            CommandEvent::FileEncodingChangeRequested { encoding } => {
+                                let result = self.handle_file_encoding_change(encoding);
+                                self.report_error(result)?;
+                            }
+                            CommandEvent::WhichWrapChangeRequested { flags } => {
+                                let result = self.handle_whichwrap_change(flags);
+                                self.report_error(result)?;
+                            }
+                            CommandEvent::ExecuteKeyChangeRequested { key } => {
+                                let result = self.handle_execute_key_change(key);
+                                self.report_error(result)?;
+                            }
                             _ => {
                                 tracing::debug!(
                                     "Ignoring non-setting command event from config: {:?}",
@@ -173,6 +247,8 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
                 }
                 Err(e) => {
                     tracing::warn!("Failed to execute config command '{}': {}", command, e);
+                    self.view_model
+                        .set_error_message(&AppError::User(e.to_string()));
                 }
             }
         }
@@ -199,6 +275,7 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
         // INITIALIZATION PHASE: Setup terminal and initial display
         self.view_renderer.initialize()?;
         self.view_renderer.render_full(&self.view_model)?;
+        self.view_renderer.render_title(&self.view_model)?;
 
         // MAIN EVENT LOOP: Handle user input and update display
         while !self.should_quit {
@@ -223,18 +300,131 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
 
         // Poll for terminal events with 100ms timeout
         if !self.event_stream.poll(Duration::from_millis(100))? {
-            return Ok(());
+            self.expire_pending_key_sequence()?;
+            self.expire_yank_highlight()?;
+            self.expire_match_highlight()?;
+            self.autosave_if_due(false)?;
+            return self.autoread_if_changed(false);
         }
 
-        match self.event_stream.read()? {
-            Event::Key(key_event) => self.handle_key_event_with_unified_first(key_event).await?,
-            Event::Resize(width, height) => self.handle_resize_event(width, height)?,
-            _ => {} // Ignore other events for now
+        // A batch of typed text hands back the key that ended it, which is then
+        // handled like any other event
+        let mut next_event = Some(self.event_stream.read()?);
+        while let Some(event) = next_event.take() {
+            match event {
+                // A `:set confirm` prompt takes every key until it is answered
+                Event::Key(key_event) if self.pending_confirmation.is_some() => {
+                    self.answer_confirmation(key_event)?;
+                }
+                Event::Key(key_event) => {
+                    if let Some(text) = self.typed_text(key_event) {
+                        next_event = self.insert_typed_text(text)?;
+                        continue;
+                    }
+                    self.handle_key_event_with_unified_first(key_event).await?;
+                    self.track_pending_key_sequence();
+                }
+                Event::Resize(width, height) => self.handle_resize_event(width, height)?,
+                Event::FocusLost => self.autosave_if_due(true)?,
+                Event::FocusGained => self.autoread_if_changed(true)?,
+                _ => {} // Ignore other events for now
+            }
         }
 
         Ok(())
     }
 
+    /// Text a key inserts in Insert mode, when inserting it is all the key does
+    fn typed_text(&self, key_event: KeyEvent) -> Option<String> {
+        if self.view_model.get_mode() != EditorMode::Insert
+            || self.view_model.is_command_window_open()
+        {
+            return None;
+        }
+
+        let unified_context =
+            crate::repl::view_models::commands::CommandContext::from_view_model(&self.view_model);
+        if self
+            .unified_command_registry
+            .process_key_event(key_event, EditorMode::Insert, &unified_context)
+            .is_some()
+        {
+            return None;
+        }
+
+        let context = CommandContext::new(ViewModelSnapshot::from_view_model(&self.view_model));
+        match self
+            .command_registry
+            .process_event(key_event, &context)
+            .ok()?
+            .as_slice()
+        {
+            [CommandEvent::TextInsertRequested { text, .. }] => Some(text.clone()),
+            _ => None,
+        }
+    }
+
+    /// Insert typed text together with any keys already waiting behind it
+    ///
+    /// A fast typist or a terminal paste delivers many keys before the next
+    /// poll. Inserting them as one piece of text rebuilds the display cache and
+    /// renders once for the whole batch instead of once per key.
+    ///
+    /// # Returns
+    /// The event that ended the batch, if it was not typed text.
+    fn insert_typed_text(&mut self, mut text: String) -> Result<Option<Event>> {
+        let mut next_event = None;
+        while self.event_stream.poll(Duration::ZERO)? {
+            let event = self.event_stream.read()?;
+            if let Event::Key(key_event) = event {
+                if let Some(more) = self.typed_text(key_event) {
+                    text.push_str(&more);
+                    continue;
+                }
+            }
+            next_event = Some(event);
+            break;
+        }
+
+        self.view_model.insert_text(&text)?;
+        self.track_pending_key_sequence();
+        self.render_if_needed()?;
+
+        Ok(next_event)
+    }
+
+    /// Stand the events of the last change in for `.`, or remember a new change
+    ///
+    /// Only changes to the request buffer made from normal mode (including
+    /// operator prefixes like `d`) are remembered, together with the text of
+    /// any insert session they start; motions and visual mode edits are not.
+    /// A replayed change is not recorded again.
+    fn track_last_change(
+        &mut self,
+        context: &CommandContext,
+        events: Vec<CommandEvent>,
+    ) -> Vec<CommandEvent> {
+        if events.contains(&CommandEvent::RepeatLastChangeRequested) {
+            return self
+                .view_model
+                .get_last_change()
+                .map(RecordedChange::replay_events)
+                .unwrap_or_default();
+        }
+
+        let from_normal_mode = context.state.current_mode == EditorMode::Normal
+            || self.view_model.is_awaiting_key_sequence();
+        if from_normal_mode
+            && context.state.current_pane == Pane::Request
+            && events
+                .iter()
+                .any(|event| event.is_buffer_change() || event.starts_insert())
+        {
+            self.view_model.record_change(events.clone());
+        }
+        events
+    }
+
     /// Handle keyboard input events
     async fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<()> {
         tracing::debug!("Received key event: {:?}", key_event);
@@ -250,14 +440,19 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
         tracing::debug!("Command events generated: {:?}", events);
 
         if events.is_empty() {
+            self.view_model.clear_pending_count();
             return Ok(());
         }
+        let events = self.track_last_change(&context, events);
+        let extends_prefix = Self::extends_prefix(&events);
 
         // Apply command events to ViewModel
         for event in events {
             self.apply_command_event(event).await?;
         }
 
+        self.clear_used_prefix(extends_prefix);
+
         // Perform throttled rendering if needed
         if !self.should_quit {
             self.render_if_needed()?;
@@ -266,6 +461,31 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
         Ok(())
     }
 
+    /// Check whether `events` only add to the count or register typed so far
+    fn extends_prefix(events: &[CommandEvent]) -> bool {
+        events.iter().any(|event| {
+            matches!(
+                event,
+                CommandEvent::CountDigitRequested { .. }
+                    | CommandEvent::RegisterSelectRequested { .. }
+            )
+        })
+    }
+
+    /// Drop the count and register once the command they were typed for has run
+    ///
+    /// A count or register applies to the next command, even one spanning
+    /// several keys like `dd`, `d2fx` or `d/pattern<CR>`.
+    fn clear_used_prefix(&mut self, extends_prefix: bool) {
+        if !extends_prefix
+            && !self.view_model.is_awaiting_key_sequence()
+            && self.view_model.get_mode() != EditorMode::Search
+        {
+            self.view_model.clear_pending_count();
+            self.view_model.clear_pending_register();
+        }
+    }
+
     /// Handle key events with unified command system first, then fall back to old system
     ///
     /// This allows gradual migration by checking unified commands first, then
@@ -273,6 +493,12 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
     async fn handle_key_event_with_unified_first(&mut self, key_event: KeyEvent) -> Result<()> {
         tracing::debug!("Processing key event with unified system: {:?}", key_event);
 
+        // The command-line window borrows the request pane, so Enter there must
+        // run the ex command instead of sending the buffer as a request
+        if self.view_model.is_command_window_open() {
+            return self.handle_key_event(key_event).await;
+        }
+
         // Create command context from current state
         let context =
             crate::repl::view_models::commands::CommandContext::from_view_model(&self.view_model);
@@ -333,7 +559,6 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
                 self.view_model.set_executing_request(false);
 
                 let status = response.status().as_u16();
-                let body = response.body().to_string();
 
                 // Log the completion
                 let duration_ms = response.duration_ms();
@@ -345,10 +570,25 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
                     duration_ms
                 );
 
-                ModelEvent::HttpResponseReceived { status, body }
+                ModelEvent::HttpResponseReceived { status }
+            }
+            HttpResponseMessage::Captured {
+                variable,
+                path,
+                value,
+            } => {
+                // The setup request's response stays out of the response pane
+                if let Some(http_service) = &mut self.services.http {
+                    http_service.set_variable(variable.clone(), value);
+                }
+                self.view_model
+                    .set_status_message(format!("Captured {path} into ${{{variable}}}"));
+                self.render_if_needed()?;
+                return Ok(());
             }
-            HttpResponseMessage::Error { message } => {
+            HttpResponseMessage::Error { error } => {
                 // Update response with error message
+                let message = error.to_string();
                 self.view_model.set_response(0, message.clone());
                 self.view_model.set_executing_request(false);
 
@@ -411,55 +651,8 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
     async fn apply_command_event(&mut self, event: CommandEvent) -> Result<()> {
         match event {
             CommandEvent::CursorMoveRequested { direction, amount } => {
-                for _ in 0..amount {
-                    match direction {
-                        MovementDirection::Left => self.view_model.move_cursor_left()?,
-                        MovementDirection::Right => self.view_model.move_cursor_right()?,
-                        MovementDirection::Up => self.view_model.move_cursor_up()?,
-                        MovementDirection::Down => self.view_model.move_cursor_down()?,
-                        MovementDirection::LineEnd => {
-                            self.view_model.move_cursor_to_end_of_line()?
-                        }
-                        MovementDirection::LineEndForAppend => {
-                            self.view_model.move_cursor_to_line_end_for_append()?
-                        }
-                        MovementDirection::LineStart => {
-                            self.view_model.move_cursor_to_start_of_line()?
-                        }
-                        MovementDirection::ScrollLeft => {
-                            self.view_model.scroll_horizontally(-1, amount)?
-                        }
-                        MovementDirection::ScrollRight => {
-                            self.view_model.scroll_horizontally(1, amount)?
-                        }
-                        MovementDirection::DocumentStart => {
-                            self.view_model.move_cursor_to_document_start()?
-                        }
-                        MovementDirection::DocumentEnd => {
-                            self.view_model.move_cursor_to_document_end()?
-                        }
-                        MovementDirection::WordForward => {
-                            self.view_model.move_cursor_to_next_word()?
-                        }
-                        MovementDirection::WordBackward => {
-                            self.view_model.move_cursor_to_previous_word()?
-                        }
-                        MovementDirection::WordEnd => {
-                            self.view_model.move_cursor_to_end_of_word()?
-                        }
-                        MovementDirection::LineNumber(line_number) => {
-                            self.view_model.move_cursor_to_line(line_number)?
-                        }
-                        MovementDirection::PageDown => self.view_model.move_cursor_page_down()?,
-                        MovementDirection::PageUp => self.view_model.move_cursor_page_up()?,
-                        MovementDirection::HalfPageDown => {
-                            self.view_model.move_cursor_half_page_down()?
-                        }
-                        MovementDirection::HalfPageUp => {
-                            self.view_model.move_cursor_half_page_up()?
-                        }
-                    }
-                }
+                self.move_cursor(direction, amount)?;
+                self.view_model.show_json_path_at_cursor();
             }
             CommandEvent::CursorPositionRequested { position } => {
                 self.view_model.set_cursor_position(position)?;
@@ -520,6 +713,12 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
                                     }
                                 }
                             }
+                            MovementDirection::WordBackward => {
+                                self.view_model.delete_word_before_cursor()?;
+                            }
+                            MovementDirection::LineStart => {
+                                self.view_model.delete_to_line_start()?;
+                            }
                             _ => {
                                 tracing::warn!("Unsupported delete direction: {:?}", direction);
                             }
@@ -559,7 +758,10 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
             }
             CommandEvent::PaneSwitchRequested { target_pane } => match target_pane {
                 Pane::Request => self.view_model.switch_to_request_pane(),
-                Pane::Response => self.view_model.switch_to_response_pane(),
+                Pane::Response => {
+                    self.autowrite_request();
+                    self.view_model.switch_to_response_pane();
+                }
             },
             CommandEvent::HttpRequestRequested { .. } => {
                 // This is now handled by HttpExecuteCommand
@@ -572,72 +774,118 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
             CommandEvent::QuitRequested => {
                 self.should_quit = true;
             }
+            CommandEvent::WriteQuitRequested { always_write } => {
+                let result = self.handle_write_quit(always_write);
+                self.report_error(result)?;
+            }
             CommandEvent::ExCommandCharRequested { ch } => {
                 self.view_model.add_ex_command_char(ch)?;
             }
             CommandEvent::ExCommandBackspaceRequested => {
                 self.view_model.backspace_ex_command()?;
             }
+            CommandEvent::ExCommandCompleteRequested { forward } => {
+                self.handle_ex_command_completion(forward);
+            }
+            CommandEvent::SearchStartRequested { forward } => {
+                self.view_model.start_search(forward)?;
+            }
+            CommandEvent::OperatorSearchStartRequested { operator } => {
+                self.view_model.start_operator_search(operator)?;
+            }
+            CommandEvent::SearchCharRequested { ch } => {
+                self.view_model.add_search_char(ch)?;
+            }
+            CommandEvent::SearchBackspaceRequested => {
+                self.view_model.backspace_search()?;
+            }
+            CommandEvent::SearchExecuteRequested => {
+                self.view_model.execute_search()?;
+            }
+            CommandEvent::SearchNextRequested { forward } => {
+                self.view_model.search_next(forward)?;
+            }
+            CommandEvent::SearchCancelRequested => {
+                self.view_model.cancel_search()?;
+            }
             CommandEvent::ExCommandExecuteRequested => {
                 // Get the ex command string from the view model
                 let command_str = self.view_model.get_ex_command_buffer().to_string();
-
-                // Create command context for ex command execution
-                let context =
-                    CommandContext::new(ViewModelSnapshot::from_view_model(&self.view_model));
-
-                // Execute through the ex command registry
-                let events = self
-                    .ex_command_registry
-                    .execute_command(&command_str, &context)?;
+                let events = self.run_ex_command(&command_str)?;
 
                 // Clear the command buffer and return to previous mode after successful execution
                 self.view_model.clear_ex_command_buffer();
                 let previous_mode = self.view_model.get_previous_mode();
                 self.view_model.change_mode(previous_mode)?;
 
-                // Handle events directly to avoid recursion
-                for event in events {
-                    match event {
-                        CommandEvent::QuitRequested => {
-                            self.should_quit = true;
-                        }
-                        CommandEvent::ShowProfileRequested => {
-                            self.handle_show_profile();
-                        }
-                        CommandEvent::SettingChangeRequested { setting, value } => {
-                            // Handle setting changes from ex commands
-                            self.handle_setting_change(setting, value)?;
-                        }
-                        CommandEvent::CursorMoveRequested { direction, amount } => {
-                            // BUGFIX: Handle line navigation from ex commands like `:58`
-                            // Previously these events were unhandled, causing `:number` to not work
-                            for _ in 0..amount {
-                                match direction {
-                                    MovementDirection::LineNumber(line_number) => {
-                                        self.view_model.move_cursor_to_line(line_number)?
-                                    }
-                                    _ => {
-                                        tracing::warn!(
-                                            "Unsupported movement direction from ex command: {:?}",
-                                            direction
-                                        );
-                                    }
-                                }
-                            }
-                        }
-                        _ => {
-                            tracing::warn!(
-                                "Unhandled event from ex command execution: {:?}",
-                                event
-                            );
-                        }
-                    }
+                self.apply_ex_command_events(events)?;
+            }
+            CommandEvent::CommandWindowOpenRequested => {
+                self.view_model.open_command_window()?;
+            }
+            CommandEvent::CommandWindowExecuteRequested => {
+                if let Some(command_str) = self.view_model.close_command_window()? {
+                    let events = self.run_ex_command(&command_str)?;
+                    self.apply_ex_command_events(events)?;
                 }
             }
             CommandEvent::ShowProfileRequested => {
                 self.handle_show_profile();
             }
+            CommandEvent::FileInfoRequested => {
+                self.view_model.show_file_info();
+            }
+            CommandEvent::ShowSettingsRequested => {
+                self.handle_show_settings();
+            }
+            CommandEvent::ShowMessagesRequested => {
+                self.handle_show_messages();
+            }
+            CommandEvent::ClearMessagesRequested => {
+                self.view_model.clear_message_history();
+            }
+            CommandEvent::ShowRegistersRequested { names } => {
+                self.handle_show_registers(names.as_deref());
+            }
+            CommandEvent::WriteBufferRequested { path, append } => {
+                let result = self.handle_write_buffer(&path, append);
+                self.report_error(result)?;
+            }
+            CommandEvent::EditFileRequested { path, force } => {
+                let result = self.handle_edit_file(path, force);
+                self.report_error(result)?;
+            }
+            CommandEvent::MakeSessionRequested { path, force } => {
+                let result = self.handle_make_session(path, force);
+                self.report_error(result)?;
+            }
+            CommandEvent::SourceFileRequested { path } => {
+                let result = self.handle_source(path);
+                self.report_error(result)?;
+            }
+            CommandEvent::SaveResponseRequested { path } => {
+                let result = self.handle_save_response(&path);
+                self.report_error(result)?;
+            }
+            CommandEvent::LintHeadersRequested => {
+                self.handle_lint_headers();
+            }
+            CommandEvent::DryRunRequested => {
+                let result = self.handle_dry_run();
+                self.report_error(result)?;
+            }
+            CommandEvent::ChainRequested {
+                file,
+                path,
+                variable,
+            } => {
+                let result = self.handle_chain(&file, path, variable);
+                self.report_error(result)?;
+            }
+            CommandEvent::TemplateRequested { action } => {
+                let result = self.handle_template(action);
+                self.report_error(result)?;
+            }
             CommandEvent::SettingChangeRequested { setting, value } => {
                 self.handle_setting_change(setting, value)?;
             }
@@ -662,6 +910,114 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
             CommandEvent::YankCurrentLineRequested => {
                 self.handle_yank_current_line()?;
             }
+            CommandEvent::YankResponseRequested => {
+                self.handle_yank_response()?;
+            }
+            CommandEvent::YankJsonValueRequested => {
+                self.handle_yank_json_value()?;
+            }
+            CommandEvent::JoinLinesRequested => {
+                self.view_model.join_lines()?;
+            }
+            CommandEvent::CountDigitRequested { digit } => {
+                self.view_model.push_count_digit(digit);
+            }
+            CommandEvent::IndentSelectionRequested { count } => {
+                let shifted = self.view_model.indent_selection(count)?;
+                self.report_shifted_lines(shifted, '>', count);
+            }
+            CommandEvent::DedentSelectionRequested { count } => {
+                let shifted = self.view_model.dedent_selection(count)?;
+                self.report_shifted_lines(shifted, '<', count);
+            }
+            CommandEvent::IndentLineRequested => {
+                self.view_model.indent_cursor_line()?;
+            }
+            CommandEvent::DedentLineRequested => {
+                self.view_model.dedent_cursor_line()?;
+            }
+            CommandEvent::IndentLinesRequested { count } => {
+                let shifted = self.view_model.indent_lines_from_cursor(count)?;
+                self.report_shifted_lines(Some(shifted), '>', 1);
+            }
+            CommandEvent::DedentLinesRequested { count } => {
+                let shifted = self.view_model.dedent_lines_from_cursor(count)?;
+                self.report_shifted_lines(Some(shifted), '<', 1);
+            }
+            CommandEvent::InsertRegisterRequested { register } => {
+                self.handle_insert_register(register)?;
+            }
+            CommandEvent::DeleteLinesRequested { range, register } => {
+                self.handle_delete_lines(range, register)?;
+            }
+            CommandEvent::YankLinesRequested { range, register } => {
+                self.handle_yank_lines(range, register)?;
+            }
+            CommandEvent::EqualPrgChangeRequested { program } => {
+                self.view_model.set_equalprg(&program);
+            }
+            CommandEvent::ShellChangeRequested { shell } => {
+                self.handle_shell_change(shell);
+            }
+            CommandEvent::TitleStringChangeRequested { title } => {
+                self.view_model.set_titlestring(&title);
+            }
+            CommandEvent::FileEncodingChangeRequested { encoding } => {
+                let result = self.handle_file_encoding_change(encoding);
+                self.report_error(result)?;
+            }
+            CommandEvent::WhichWrapChangeRequested { flags } => {
+                let result = self.handle_whichwrap_change(flags);
+                self.report_error(result)?;
+            }
+            CommandEvent::ExecuteKeyChangeRequested { key } => {
+                let result = self.handle_execute_key_change(key);
+                self.report_error(result)?;
+            }
+            CommandEvent::ReindentLinesRequested { count } => {
+                self.handle_reindent_lines(count)?;
+            }
+            CommandEvent::InsertLastInsertedRequested => {
+                self.view_model.insert_last_inserted_text()?;
+            }
+            CommandEvent::InsertLiteralRequested { ch } => {
+                self.view_model.insert_literal(ch)?;
+            }
+            CommandEvent::RegisterSelectRequested { register } => {
+                self.view_model.select_register(register);
+            }
+            CommandEvent::FindCharRequested { target, count } => {
+                self.view_model.move_cursor_to_char(target, count)?;
+            }
+            CommandEvent::FindCharStartRequested { find } => {
+                self.view_model.start_find_char(find);
+            }
+            CommandEvent::RepeatFindCharRequested { reverse, count } => {
+                self.view_model.repeat_find_char(reverse, count)?;
+            }
+            CommandEvent::DeleteToCharRequested { target, count } => {
+                if !self.reject_read_only_register() {
+                    self.view_model.delete_to_char(target, count)?;
+                }
+            }
+            CommandEvent::DeleteAroundWordRequested { count } => {
+                if !self.reject_read_only_register() {
+                    self.view_model.delete_around_word(count)?;
+                }
+            }
+            CommandEvent::CaseOperatorRequested { change } => {
+                self.handle_case_operator(change)?;
+            }
+            CommandEvent::ChangeCaseRequested {
+                change,
+                motion,
+                count,
+            } => {
+                self.handle_change_case(change, motion, count)?;
+            }
+            CommandEvent::ChangeCaseLinesRequested { change, count } => {
+                self.view_model.change_case_lines(change, count)?;
+            }
             CommandEvent::ChangeSelectionRequested => {
                 self.handle_change_selection()?;
             }
@@ -674,9 +1030,34 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
             CommandEvent::ExitVisualBlockInsertRequested => {
                 self.handle_exit_visual_block_insert()?;
             }
+            CommandEvent::JumpToChangeMarkRequested { end } => {
+                if !self.view_model.jump_to_change_mark(end)? {
+                    self.view_model
+                        .set_error_message(&AppError::User("E20: Mark not set".to_string()));
+                }
+            }
+            CommandEvent::SetMarkRequested { mark } => {
+                self.view_model.set_mark(mark);
+            }
+            CommandEvent::JumpToMarkRequested { mark, line_wise } => {
+                if !self.view_model.jump_to_mark(mark, line_wise)? {
+                    self.view_model
+                        .set_error_message(&AppError::User("E20: Mark not set".to_string()));
+                }
+            }
+            CommandEvent::SelectLastChangeRequested => {
+                if !self.view_model.select_last_change()? {
+                    self.view_model.change_mode(EditorMode::Normal)?;
+                    self.view_model
+                        .set_error_message(&AppError::User("E20: Mark not set".to_string()));
+                }
+            }
             CommandEvent::RepeatVisualSelectionRequested => {
                 self.handle_repeat_visual_selection()?;
             }
+            CommandEvent::RepeatLastChangeRequested => {
+                // Replaced by the recorded change before events are applied
+            }
             CommandEvent::PasteAfterRequested => {
                 self.handle_paste_after()?;
             }
@@ -734,12 +1115,23 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
     ) -> Result<()> {
         use crate::repl::events::ViewEvent;
 
+        // Yanks for the terminal's clipboard go out even while the panes are not drawn
+        if let Some(text) = self.services.yank.take_terminal_copy() {
+            self.view_renderer.render_clipboard(&text)?;
+        }
+
+        // Only the "terminal too small" message is drawn until the terminal grows
+        if self.view_model.pane_manager().is_terminal_too_small() {
+            return self.view_renderer.render_full(&self.view_model);
+        }
+
         // Group events to avoid redundant renders
         let mut needs_full_redraw = false;
         let mut needs_status_bar = false;
         let mut needs_cursor_update = false;
         let mut needs_current_area_redraw = false;
         let mut needs_secondary_area_redraw = false;
+        let mut needs_gutter_redraw = false;
         let mut partial_redraws: std::collections::HashMap<Pane, usize> =
             std::collections::HashMap::new();
 
@@ -790,6 +1182,10 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
                 }
                 ViewEvent::ActiveCursorUpdateRequired => {
                     needs_cursor_update = true;
+                    // The highlighted column follows the cursor
+                    if self.view_model.pane_manager().is_cursor_column_visible() {
+                        needs_current_area_redraw = true;
+                    }
                 }
                 ViewEvent::CurrentAreaScrollChanged { .. } => {
                     needs_current_area_redraw = true;
@@ -799,10 +1195,33 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
                 ViewEvent::SecondaryAreaScrollChanged { .. } => {
                     needs_secondary_area_redraw = true;
                 }
+                ViewEvent::GutterRedrawRequired => {
+                    needs_gutter_redraw = true;
+                    needs_cursor_update = true;
+                }
+                ViewEvent::YankHighlighted { .. } => {
+                    // The pane redraw comes with the event; time the flash
+                    self.services.yank_flash.start();
+                }
+                ViewEvent::MatchHighlighted { .. } => {
+                    // `matchtime=0` takes the flash down before it is ever drawn
+                    if self.services.match_flash.is_enabled() {
+                        self.services.match_flash.start();
+                    } else {
+                        self.view_model.clear_match_highlight()?;
+                    }
+                }
                 ViewEvent::FocusSwitched => {
                     // Focus switch requires cursor update and status bar update
                     needs_cursor_update = true;
                     needs_status_bar = true;
+                    // Only the focused pane's line numbers take the mode color
+                    needs_gutter_redraw = true;
+                    // Move the column highlight to the newly focused pane
+                    if self.view_model.pane_manager().is_cursor_column_visible() {
+                        needs_current_area_redraw = true;
+                        needs_secondary_area_redraw = true;
+                    }
                 }
                 ViewEvent::RequestContentChanged => {
                     // Request content changed - redraw current area if we're in request pane
@@ -870,6 +1289,21 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
                     .render_pane_partial(&self.view_model, *pane, *start_line)?;
             }
 
+            // Recolor the line numbers of panes that were not redrawn anyway
+            if needs_gutter_redraw {
+                let current_pane = self.view_model.get_current_pane();
+                for pane in [Pane::Request, Pane::Response] {
+                    let redrawn = if pane == current_pane {
+                        needs_current_area_redraw
+                    } else {
+                        needs_secondary_area_redraw
+                    };
+                    if !redrawn {
+                        self.view_renderer.render_gutter(&self.view_model, pane)?;
+                    }
+                }
+            }
+
             if needs_status_bar {
                 self.view_renderer.render_status_bar(&self.view_model)?;
             }
@@ -881,6 +1315,9 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
             }
         }
 
+        // The title follows the profile and request line, which any event may change
+        self.view_renderer.render_title(&self.view_model)?;
+
         Ok(())
     }
     /// Handle showing profile information in status bar
@@ -891,1011 +1328,4006 @@ impl<ES: EventStream, RS: RenderStream> AppController<ES, RS> {
         self.view_model.set_status_message(message);
     }
 
-    /// Handle setting changes from ex commands
-    fn handle_setting_change(&mut self, setting: Setting, value: SettingValue) -> Result<()> {
-        // Handle clipboard setting through YankService
-        if setting == Setting::Clipboard {
-            let enable = value == SettingValue::On;
-            self.services.yank.set_clipboard_enabled(enable)?;
-            // Update status message
-            let message = if enable {
-                "Clipboard integration enabled"
-            } else {
-                "Clipboard integration disabled"
-            };
-            self.view_model.set_status_message(message.to_string());
-            Ok(())
-        } else {
-            // Other settings still go through ViewModel
-            self.view_model.apply_setting(setting, value)
-        }
-    }
+    /// Execute an ex command through the registry and remember it in the history
+    fn run_ex_command(&mut self, command_str: &str) -> Result<Vec<CommandEvent>> {
+        // Create command context for ex command execution
+        let context = CommandContext::new(ViewModelSnapshot::from_view_model(&self.view_model));
 
-    /// Handle yanking selected text to yank buffer
-    fn handle_yank_selection(&mut self) -> Result<()> {
-        // Get selected text from current pane
-        if let Some(text) = self.view_model.get_selected_text() {
-            // Determine yank type based on current visual mode
-            let current_mode = self.view_model.get_mode();
-            let yank_type = match current_mode {
-                EditorMode::Visual => NewYankType::Character,
-                EditorMode::VisualLine => NewYankType::Line,
-                EditorMode::VisualBlock => NewYankType::Block,
-                _ => NewYankType::Character, // Fallback for any other mode
-            };
+        // Execute through the ex command registry
+        let events = self
+            .ex_command_registry
+            .execute_command(command_str, &context)?;
+        self.view_model.push_ex_command_history(command_str);
+        Ok(events)
+    }
 
-            // Store in yank buffer using YankService (not the old ViewModel method!)
-            self.services.yank.yank(text.clone(), yank_type)?;
+    /// Apply the events produced by an ex command
+    ///
+    /// An event that fails with an [`AppError`] is shown in the status line
+    /// and the rest still apply.
+    fn apply_ex_command_events(&mut self, events: Vec<CommandEvent>) -> Result<()> {
+        for event in events {
+            let result = self.apply_ex_command_event(event);
+            self.report_error(result)?;
+        }
+        Ok(())
+    }
 
-            // Switch to Normal mode (automatically clears visual selection)
-            self.view_model.change_mode(EditorMode::Normal)?;
+    /// Show an error the user caused in the status line
+    ///
+    /// Errors other than [`AppError`] are passed on.
+    fn report_error(&mut self, result: Result<(), impl Into<anyhow::Error>>) -> Result<()> {
+        let Err(error) = result else {
+            return Ok(());
+        };
+        match error.into().downcast::<AppError>() {
+            Ok(error) => {
+                self.view_model.set_error_message(&error);
+                Ok(())
+            }
+            Err(error) => Err(error),
+        }
+    }
 
-            // Show feedback in status bar
-            let char_count = text.chars().count();
-            let line_count = text.lines().count();
-            let message = match yank_type {
-                NewYankType::Character => {
-                    if line_count > 1 {
-                        format!("{line_count} lines yanked (character-wise)")
-                    } else {
-                        format!("{char_count} characters yanked")
-                    }
-                }
-                NewYankType::Line => format!("{line_count} lines yanked (line-wise)"),
-                NewYankType::Block => {
-                    format!("Block yanked ({line_count} lines, {char_count} chars)")
+    /// Apply one event produced by an ex command
+    fn apply_ex_command_event(&mut self, event: CommandEvent) -> Result<()> {
+        // Handle events directly to avoid recursion
+        match event {
+            CommandEvent::QuitRequested => {
+                self.should_quit = true;
+            }
+            CommandEvent::WriteQuitRequested { always_write } => {
+                self.handle_write_quit(always_write)?;
+            }
+            CommandEvent::ShowProfileRequested => {
+                self.handle_show_profile();
+            }
+            CommandEvent::ShowSettingsRequested => {
+                self.handle_show_settings();
+            }
+            CommandEvent::ShowMessagesRequested => {
+                self.handle_show_messages();
+            }
+            CommandEvent::ClearMessagesRequested => {
+                self.view_model.clear_message_history();
+            }
+            CommandEvent::ShowRegistersRequested { names } => {
+                self.handle_show_registers(names.as_deref());
+            }
+            CommandEvent::WriteBufferRequested { path, append } => {
+                self.handle_write_buffer(&path, append)?;
+            }
+            CommandEvent::EditFileRequested { path, force } => {
+                self.handle_edit_file(path, force)?;
+            }
+            CommandEvent::MakeSessionRequested { path, force } => {
+                self.handle_make_session(path, force)?;
+            }
+            CommandEvent::SourceFileRequested { path } => {
+                self.handle_source(path)?;
+            }
+            CommandEvent::SaveResponseRequested { path } => {
+                self.handle_save_response(&path)?;
+            }
+            CommandEvent::LintHeadersRequested => {
+                self.handle_lint_headers();
+            }
+            CommandEvent::DryRunRequested => {
+                self.handle_dry_run()?;
+            }
+            CommandEvent::ChainRequested {
+                file,
+                path,
+                variable,
+            } => {
+                self.handle_chain(&file, path, variable)?;
+            }
+            CommandEvent::YankResponseRequested => {
+                self.handle_yank_response()?;
+            }
+            CommandEvent::YankJsonValueRequested => {
+                self.handle_yank_json_value()?;
+            }
+            CommandEvent::TemplateRequested { action } => {
+                self.handle_template(action)?;
+            }
+            CommandEvent::DeleteLinesRequested { range, register } => {
+                self.handle_delete_lines(range, register)?;
+            }
+            CommandEvent::YankLinesRequested { range, register } => {
+                self.handle_yank_lines(range, register)?;
+            }
+            CommandEvent::EqualPrgChangeRequested { program } => {
+                self.view_model.set_equalprg(&program);
+            }
+            CommandEvent::ShellChangeRequested { shell } => {
+                self.handle_shell_change(shell);
+            }
+            CommandEvent::TitleStringChangeRequested { title } => {
+                self.view_model.set_titlestring(&title);
+            }
+            CommandEvent::FileEncodingChangeRequested { encoding } => {
+                self.handle_file_encoding_change(encoding)?;
+            }
+            CommandEvent::WhichWrapChangeRequested { flags } => {
+                self.handle_whichwrap_change(flags)?;
+            }
+            CommandEvent::ExecuteKeyChangeRequested { key } => {
+                self.handle_execute_key_change(key)?;
+            }
+            CommandEvent::SettingChangeRequested { setting, value } => {
+                // Handle setting changes from ex commands
+                self.handle_setting_change(setting, value)?;
+            }
+            CommandEvent::CursorMoveRequested { direction, amount } => {
+                // BUGFIX: Handle line navigation from ex commands like `:58`
+                // Previously these events were unhandled, causing `:number` to not work
+                for _ in 0..amount {
+                    match direction {
+                        MovementDirection::LineNumber(line_number) => {
+                            self.view_model.move_cursor_to_line(line_number)?
+                        }
+                        _ => {
+                            tracing::warn!(
+                                "Unsupported movement direction from ex command: {:?}",
+                                direction
+                            );
+                        }
+                    }
                 }
-            };
-            self.view_model.set_status_message(message);
-
-            tracing::info!(
-                "Yanked {} characters ({} lines) to buffer as {:?}",
-                char_count,
-                line_count,
-                yank_type
-            );
-        } else {
-            tracing::warn!("No text selected for yanking");
-            self.view_model
-                .set_status_message("No text selected".to_string());
+            }
+            _ => {
+                tracing::warn!("Unhandled event from ex command execution: {:?}", event);
+            }
         }
-
         Ok(())
     }
 
-    /// Handle deleting selected text
-    fn handle_delete_selection(&mut self) -> Result<()> {
-        // Delete the selected text - the method now returns the deleted text directly
-        if let Some(deleted_text) = self.view_model.delete_selected_text()? {
-            // Switch to Normal mode (automatically clears visual selection)
-            self.view_model.change_mode(EditorMode::Normal)?;
+    /// Handle Tab and Shift-Tab on the command line
+    ///
+    /// The first press looks up candidates for the argument being typed
+    /// (file paths for `:w`, template names for `:template load|save`); further
+    /// presses cycle through them.
+    fn handle_ex_command_completion(&mut self, forward: bool) {
+        if self.view_model.is_completing() {
+            self.view_model.cycle_completion(forward);
+            return;
+        }
 
-            // Show feedback in status bar
-            let char_count = deleted_text.chars().count();
-            let line_count = deleted_text.lines().count();
-            let message = if line_count > 1 {
-                format!("{line_count} lines deleted")
-            } else {
-                format!("{char_count} characters deleted")
-            };
-            self.view_model.set_status_message(message);
+        let command = self.view_model.get_ex_command_buffer().to_string();
+        let Some((arg_start, kind)) = completion_target(&command) else {
+            return;
+        };
+        let prefix = &command[arg_start..];
+
+        let candidates = match kind {
+            CompletionKind::FilePath => file::complete_path(prefix),
+            CompletionKind::TemplateName => template::list_templates(&get_templates_dir())
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|name| name.starts_with(prefix))
+                .collect(),
+        };
+        self.view_model
+            .start_completion(arg_start, candidates, forward);
+    }
 
-            tracing::info!("Deleted {} characters ({} lines)", char_count, line_count);
-        } else {
-            tracing::warn!("No text selected for deletion");
-            self.view_model
-                .set_status_message("No text selected".to_string());
+    /// Handle listing the messages shown so far in the scratch overlay (:messages)
+    ///
+    /// Viewing them marks the errors among them as seen, which clears the
+    /// error count in the status line.
+    fn handle_show_messages(&mut self) {
+        let listing = std::iter::once("--- Messages ---".to_string())
+            .chain(self.view_model.message_history())
+            .collect();
+        self.view_model.open_scratch(listing);
+        self.view_model.mark_errors_seen();
+    }
+
+    /// Handle listing register contents in the scratch overlay (:registers [names])
+    ///
+    /// Only registers holding something are listed, each with a one-line
+    /// preview cut to the terminal width. With `names`, other registers are
+    /// left out.
+    fn handle_show_registers(&mut self, names: Option<&str>) {
+        let width = (self.view_model.terminal_size().0 as usize).saturating_sub(5);
+        let mut listing = vec!["--- Registers ---".to_string()];
+        for register in LISTED_REGISTERS.chars() {
+            if names.is_some_and(|names| !names.contains(register)) {
+                continue;
+            }
+            if let Some(entry) = self.read_register(register) {
+                listing.push(format!(
+                    "\"{register}   {}",
+                    register_preview(&entry.text, width)
+                ));
+            }
         }
+        self.view_model.open_scratch(listing);
+    }
 
-        Ok(())
+    /// Handle listing every setting in the scratch overlay (:set all)
+    fn handle_show_settings(&mut self) {
+        let mut settings = self.view_model.settings_listing();
+        settings.push(format!(
+            "clipboard {}",
+            on_off(self.services.yank.is_clipboard_enabled())
+        ));
+        settings.push(format!(
+            "clipboard={}",
+            if self.services.yank.is_unnamedplus_enabled() {
+                "unnamedplus"
+            } else {
+                ""
+            }
+        ));
+        settings.push(format!(
+            "autosave {}",
+            on_off(self.services.autosave.is_enabled())
+        ));
+        settings.push(format!(
+            "autosaveinterval {}",
+            self.services.autosave.interval_secs()
+        ));
+        settings.push(format!(
+            "autowrite {}",
+            on_off(self.services.autosave.is_autowrite_enabled())
+        ));
+        settings.push(format!(
+            "autoread {}",
+            on_off(self.services.autoread.is_enabled())
+        ));
+        settings.push(format!(
+            "timeoutlen={}",
+            self.services.key_timeout.timeout_ms()
+        ));
+        settings.push(format!(
+            "matchtime={}",
+            self.services.match_flash.matchtime()
+        ));
+        settings.sort();
+
+        let listing = std::iter::once("--- Settings ---".to_string())
+            .chain(settings.iter().map(|setting| format!("  {setting}")))
+            .collect();
+        self.view_model.open_scratch(listing);
     }
 
-    /// Handle cutting (delete + yank) selected text
-    fn handle_cut_selection(&mut self) -> Result<()> {
-        // Cut combines yank + delete, but we need to yank first before deleting
-        if let Some(text) = self.view_model.get_selected_text() {
-            // Determine yank type based on current visual mode BEFORE any mode changes
-            let current_mode = self.view_model.get_mode();
-            let yank_type = match current_mode {
-                EditorMode::Visual => NewYankType::Character,
-                EditorMode::VisualLine => NewYankType::Line,
-                EditorMode::VisualBlock => NewYankType::Block,
-                _ => NewYankType::Character, // Fallback for any other mode
-            };
+    /// Handle writing the request buffer to a file (:w file and :w >> file)
+    ///
+    /// With `:set backup on`, an existing file is copied to `file~` before it is
+    /// overwritten; if the backup fails the write is aborted. Unless
+    /// `:set backupcopy=yes`, overwrites go through a temporary file that is
+    /// renamed over the target. The content is encoded in `:set fileencoding`,
+    /// and nothing is written if it cannot be. Overwriting a file
+    /// from a buffer without one associates the buffer with that file.
+    ///
+    /// # Errors
+    /// When the buffer could not be written.
+    fn handle_write_buffer(&mut self, path: &str, append: bool) -> AppResult<()> {
+        if self.view_model.is_backup_enabled() && !append {
+            if let Err(e) = file::backup_file(path) {
+                tracing::error!("Failed to back up {} before writing: {}", path, e);
+                return Err(AppError::User(format!("{e}, file not written")));
+            }
+        }
 
-            // First yank to buffer using YankService
-            self.services.yank.yank(text.clone(), yank_type)?;
+        let content = self.view_model.get_request_text();
+        let encoding = self.file_encoding();
+        let written = if append || self.view_model.is_backupcopy_enabled() {
+            file::write_buffer_as(path, &content, append, encoding)
+        } else {
+            file::write_buffer_atomic_as(path, &content, encoding)
+        };
+        let bytes = written.inspect_err(|e| {
+            tracing::error!("Failed to write buffer to {}: {}", path, e);
+        })?;
 
-            // Then delete the selected text (this also returns the deleted text for verification)
-            if let Some(deleted_text) = self.view_model.delete_selected_text()? {
-                // Switch to Normal mode (automatically clears visual selection)
-                self.view_model.change_mode(EditorMode::Normal)?;
+        let message = if append {
+            format!("\"{path}\" {bytes}B appended")
+        } else {
+            if self.view_model.get_request_file().is_none() {
+                self.view_model.set_request_file(Some(path.to_string()));
+            }
+            if self.view_model.get_request_file() == Some(path) {
+                self.view_model.set_request_modified(false);
+                self.services.autoread.track(path);
+            }
+            format!("\"{path}\" {bytes}B written")
+        };
+        self.view_model.set_status_message(message);
+        Ok(())
+    }
 
-                // Show feedback in status bar
-                let char_count = deleted_text.chars().count();
-                let line_count = deleted_text.lines().count();
-                let message = match yank_type {
-                    NewYankType::Character => {
-                        if line_count > 1 {
-                            format!("{line_count} lines cut (character-wise)")
-                        } else {
-                            format!("{char_count} characters cut")
-                        }
-                    }
-                    NewYankType::Line => format!("{line_count} lines cut (line-wise)"),
-                    NewYankType::Block => {
-                        format!("Block cut ({line_count} lines, {char_count} chars)")
-                    }
-                };
-                self.view_model.set_status_message(message);
+    /// Handle loading a file into the request buffer (`:e [file]`, `:e! [file]`)
+    ///
+    /// Without a file name the buffer's own file is read again. Unsaved
+    /// changes block the load unless it is forced, or, with `:set confirm on`,
+    /// until the user agrees to discard them.
+    fn handle_edit_file(&mut self, path: Option<String>, force: bool) -> Result<()> {
+        let Some(path) = path.or_else(|| self.view_model.get_request_file().map(str::to_string))
+        else {
+            return Err(AppError::User("E32: No file name".to_string()).into());
+        };
 
-                tracing::info!(
-                    "Cut {} characters ({} lines) to buffer as {:?}",
-                    char_count,
-                    line_count,
-                    yank_type
-                );
-            } else {
-                tracing::warn!("Failed to delete selected text during cut operation");
-                self.view_model
-                    .set_status_message("Cut operation failed".to_string());
+        if !force && self.view_model.is_request_modified() {
+            if !self.view_model.is_confirm_enabled() {
+                return Err(AppError::User(
+                    "E37: No write since last change (add ! to override)".to_string(),
+                )
+                .into());
+            }
+            let question = format!("Discard changes and edit \"{path}\"?");
+            if !self.confirm_destructive(question, PendingConfirmation::Edit { path: path.clone() })
+            {
+                return Ok(());
             }
-        } else {
-            tracing::warn!("No text selected for cutting");
-            self.view_model
-                .set_status_message("No text selected".to_string());
         }
 
-        Ok(())
+        self.load_request_file(&path)
     }
 
-    /// Handle cutting (delete + yank) character at cursor
-    fn handle_cut_character(&mut self) -> Result<()> {
-        // Cut character at cursor position - the method already handles yanking
-        self.view_model.cut_char_at_cursor()?;
-
-        tracing::info!("Cut 1 character at cursor to yank buffer");
+    /// Handle saving the session to a file (:mksession [file])
+    fn handle_make_session(&mut self, path: Option<String>, force: bool) -> AppResult<()> {
+        let path = path.unwrap_or_else(|| DEFAULT_SESSION_FILE.to_string());
+        let session = Session {
+            profile: self.view_model.get_profile_name().to_string(),
+            request_file: self.view_model.get_request_file().map(str::to_string),
+            request_text: self.view_model.get_request_text(),
+            cursor: self.view_model.get_request_cursor_position(),
+        };
 
+        session::save_session(&path, &session, force).inspect_err(|e| {
+            tracing::error!("Failed to save session to {}: {}", path, e);
+        })?;
+        self.view_model
+            .set_status_message(format!("Session saved to \"{path}\""));
         Ok(())
     }
 
-    /// Handle cutting (delete + yank) from cursor to end of line
-    fn handle_cut_to_end_of_line(&mut self) -> Result<()> {
-        // Cut from cursor to end of line - the method already handles yanking
-        self.view_model.cut_to_end_of_line()?;
-
-        tracing::info!("Cut from cursor to end of line to yank buffer");
+    /// Handle running each line of a file as an ex command (:source file)
+    ///
+    /// Blank lines and `"` comments are skipped, and a leading `:` is allowed.
+    /// The first line that fails stops the script, and its error is returned
+    /// with the line number.
+    fn handle_source(&mut self, path: Option<String>) -> Result<()> {
+        let Some(path) = path else {
+            return Err(AppError::User("E471: Argument required".to_string()).into());
+        };
+        let expanded = shellexpand::tilde(&path).to_string();
+        let Some((script, _)) = file::read_buffer_as(&expanded, self.file_encoding())? else {
+            return Err(AppError::User(format!("E484: Can't open file {path}")).into());
+        };
 
+        for (index, line) in script.lines().enumerate() {
+            let command = line.trim().trim_start_matches(':').trim_start();
+            if command.is_empty() || command.starts_with('"') {
+                continue;
+            }
+            if let Err(e) = self.source_line(command) {
+                tracing::error!("Sourcing {} stopped at line {}: {}", path, index + 1, e);
+                return Err(AppError::User(format!("{path} line {}: {e}", index + 1)).into());
+            }
+        }
         Ok(())
     }
 
-    /// Handle cutting (delete + yank) entire current line
-    fn handle_cut_current_line(&mut self) -> Result<()> {
-        // Cut entire current line - the method already handles yanking
-        self.view_model.cut_current_line()?;
-
-        tracing::info!("Cut entire current line to yank buffer");
+    /// Run one line of a sourced script, failing when any of its events fails
+    fn source_line(&mut self, command: &str) -> Result<()> {
+        if !self.ex_command_registry.can_handle(command) {
+            return Err(AppError::User(format!("E492: Not an editor command: {command}")).into());
+        }
 
+        let context = CommandContext::new(ViewModelSnapshot::from_view_model(&self.view_model));
+        let events = self
+            .ex_command_registry
+            .execute_command(command, &context)?;
+        for event in events {
+            self.apply_ex_command_event(event)?;
+        }
         Ok(())
     }
 
-    /// Handle yanking (copy) entire current line without deleting
-    fn handle_yank_current_line(&mut self) -> Result<()> {
-        // Yank entire current line to yank buffer without deleting
-        self.view_model.yank_current_line()?;
+    /// Put the request buffer back as a session saved it (--session)
+    ///
+    /// The saved text is used rather than the file, so edits that were not
+    /// written come back and leave the buffer modified.
+    fn restore_session(&mut self, session: &Session) -> Result<()> {
+        self.view_model.set_request_text(&session.request_text)?;
+        if let Some(path) = &session.request_file {
+            let on_disk = file::read_buffer_as(path, self.file_encoding())
+                .ok()
+                .flatten()
+                .map(|(text, _)| text);
+            self.view_model.set_request_file(Some(path.clone()));
+            self.view_model
+                .set_request_modified(on_disk.as_deref() != Some(session.request_text.as_str()));
+            self.services.autoread.track(path);
+        }
+        self.view_model.set_cursor_position(session.cursor)?;
+        Ok(())
+    }
 
-        // Show status message
+    /// Replace the request buffer with the contents of a file and edit that file
+    ///
+    /// A file that does not exist yet gives an empty buffer, written on `:w`.
+    /// The file is decoded from `:set fileencoding`; bytes that do not decode
+    /// are replaced and flagged in the status message.
+    fn load_request_file(&mut self, path: &str) -> Result<()> {
+        let decoded = file::read_buffer_as(path, self.file_encoding()).inspect_err(|e| {
+            tracing::error!("Failed to read {}: {}", path, e);
+        })?;
+
+        let (content, message) = match decoded {
+            Some((text, had_errors)) => {
+                let illegal = if had_errors {
+                    tracing::warn!(
+                        "Replaced bytes in {} that are not valid {}",
+                        path,
+                        self.view_model.get_fileencoding()
+                    );
+                    " [ILLEGAL BYTE replaced]"
+                } else {
+                    ""
+                };
+                let message = format!(
+                    "\"{path}\"{illegal} {}L, {}B",
+                    text.lines().count(),
+                    text.len()
+                );
+                (Some(text), message)
+            }
+            None => (None, format!("\"{path}\" [New]")),
+        };
         self.view_model
-            .set_status_message("1 line yanked".to_string());
+            .set_request_text(content.as_deref().unwrap_or_default())?;
+        self.view_model.set_request_file(Some(path.to_string()));
+        self.view_model.set_request_modified(false);
+        self.services.autoread.track(path);
+        self.view_model.set_status_message(message);
+        Ok(())
+    }
 
-        tracing::info!("Yanked entire current line to yank buffer");
+    /// Ask before a destructive command goes ahead, when `:set confirm` is on
+    ///
+    /// The question is shown with a `(y)es/(n)o/(a)ll` prompt and the command
+    /// is kept until a key answers it.
+    ///
+    /// # Returns
+    /// Whether the command can go ahead right away.
+    fn confirm_destructive(&mut self, question: String, action: PendingConfirmation) -> bool {
+        if !self.view_model.is_confirm_enabled() || self.confirm_all {
+            return true;
+        }
 
-        Ok(())
+        self.view_model
+            .set_status_message(format!("{question} (y)es/(n)o/(a)ll: "));
+        self.pending_confirmation = Some(action);
+        false
     }
 
-    /// Handle change selection operation (Visual Block mode 'c' command)
+    /// Answer the pending `:set confirm` prompt with a key
     ///
-    /// This implements vim's Visual Block change command:
-    /// 1. Delete the selected rectangular block
-    /// 2. Enter Visual Block Insert mode for multi-cursor text replacement
-    /// 3. Shows multi-cursor feedback on all affected lines in real-time
-    /// 4. When Esc is pressed, exits Visual Block Insert mode
-    fn handle_change_selection(&mut self) -> Result<()> {
-        // Change operation is currently only supported in Visual Block mode
-        let current_mode = self.view_model.get_mode();
-        if current_mode != EditorMode::VisualBlock {
-            tracing::warn!("Change selection only supported in Visual Block mode, current mode: {current_mode:?}");
-            self.view_model.set_status_message(
-                "Change command only supported in Visual Block mode".to_string(),
-            );
+    /// `y` runs the command and `a` also skips later prompts until confirm is
+    /// set again; `n` or Escape cancels it. Any other key leaves the prompt up.
+    fn answer_confirmation(&mut self, key_event: KeyEvent) -> Result<()> {
+        let answer = match key_event.code {
+            KeyCode::Char(ch) => ch.to_ascii_lowercase(),
+            KeyCode::Esc => 'n',
+            _ => return Ok(()),
+        };
+        if !matches!(answer, 'y' | 'n' | 'a') {
             return Ok(());
         }
-
-        // Get the visual selection before deleting it
-        let (selection_start, selection_end, _pane) = self.view_model.get_visual_selection();
-        if selection_start.is_none() || selection_end.is_none() {
-            tracing::warn!("No visual selection for change operation");
-            self.view_model
-                .set_status_message("No text selected".to_string());
+        let Some(action) = self.pending_confirmation.take() else {
             return Ok(());
+        };
+
+        if answer == 'n' {
+            self.view_model.set_status_message("Cancelled".to_string());
+        } else {
+            self.confirm_all = answer == 'a';
+            match action {
+                PendingConfirmation::Edit { path } => {
+                    let result = self.load_request_file(&path);
+                    self.report_error(result)?;
+                }
+                PendingConfirmation::DeleteLines { range, register } => {
+                    self.delete_line_range(range, register)?
+                }
+            }
         }
+        self.view_renderer.render_full(&self.view_model)?;
+        Ok(())
+    }
 
-        let start = selection_start.unwrap();
-        let end = selection_end.unwrap();
-
-        // Calculate the cursor positions for Visual Block Insert mode
-        // This is similar to Visual Block Insert, but we start from the deleted block
-        let top_line = start.line.min(end.line);
-        let bottom_line = start.line.max(end.line);
-        let left_col = start.column.min(end.column);
-
-        // Delete the selected block text first
-        if let Some(deleted_text) = self.view_model.delete_selected_text()? {
-            // Create cursor positions for all lines in the deleted block range
-            let mut cursor_positions = Vec::new();
-            for line_num in top_line..=bottom_line {
-                cursor_positions.push(LogicalPosition::new(line_num, left_col));
-            }
-
-            // Set up Visual Block Insert mode with multi-cursor state
-            self.view_model
-                .set_visual_block_insert_cursors(cursor_positions.clone());
-
-            // Switch to VisualBlockInsert mode (not regular Insert)
-            self.view_model.change_mode(EditorMode::VisualBlockInsert)?;
-
-            // Position the main cursor at the first line of the block
-            self.view_model.set_cursor_position(cursor_positions[0])?;
-
-            // Show feedback in status bar
-            let char_count = deleted_text.chars().count();
-            let line_count = deleted_text.lines().count();
-            let message = if line_count > 1 {
-                format!("Changed {line_count} lines, Visual Block Insert mode")
-            } else {
-                format!("Changed {char_count} characters, Visual Block Insert mode")
-            };
-            self.view_model.set_status_message(message);
+    /// Close the scratch overlay on `q` or Esc, ignoring any other key
+    ///
+    /// The panes underneath were never touched, so a full redraw brings them back.
+    fn dismiss_scratch(&mut self, key_event: KeyEvent) -> Result<()> {
+        if matches!(key_event.code, KeyCode::Char('q') | KeyCode::Esc) {
+            self.view_model.close_scratch();
+            self.view_renderer.render_full(&self.view_model)?;
+        }
+        Ok(())
+    }
 
-            tracing::info!(
-                "Changed {} characters ({} lines), entered Visual Block Insert mode with {} cursors",
-                char_count,
-                line_count,
-                cursor_positions.len()
-            );
-        } else {
-            tracing::warn!("No text selected for changing");
+    /// Handle writing the full body of the last response to a file (:save)
+    ///
+    /// The body is saved whole even when `maxresponse` cut it short on screen.
+    fn handle_save_response(&mut self, path: &str) -> AppResult<()> {
+        let Some(body) = self.view_model.get_response_body().map(str::to_string) else {
             self.view_model
-                .set_status_message("No text selected".to_string());
-        }
+                .set_status_message("No response to save".to_string());
+            return Ok(());
+        };
 
+        let bytes = file::write_content(path, &body).inspect_err(|e| {
+            tracing::error!("Failed to save response to {}: {}", path, e);
+        })?;
+        self.view_model
+            .set_status_message(format!("\"{path}\" {bytes}B written"));
         Ok(())
     }
 
-    /// Handle Visual Block Insert operation ('I' in Visual Block mode)
+    /// Write the request buffer to its file when autosave is due
     ///
-    /// This implements vim's Visual Block Insert command:
-    /// 1. Remember the selected block coordinates
-    /// 2. Move cursor to the start of the first selected line in the block  
-    /// 3. Enter special VisualBlockInsert mode
-    /// 4. Text typed appears on first line, replicated to all lines on Esc
-    fn handle_visual_block_insert(&mut self) -> Result<()> {
-        // Only supported in Visual Block mode
-        let current_mode = self.view_model.get_mode();
-        if current_mode != EditorMode::VisualBlock {
-            tracing::warn!("Visual Block Insert only supported in Visual Block mode, current mode: {current_mode:?}");
-            self.view_model.set_status_message(
-                "Visual Block Insert only supported in Visual Block mode".to_string(),
-            );
+    /// Runs on idle ticks of the event loop and, with `force`, when the
+    /// terminal loses focus. Buffers without a file or without changes are
+    /// left alone, and a failed write only shows up in the status line.
+    fn autosave_if_due(&mut self, force: bool) -> Result<()> {
+        let Some(path) = self.view_model.get_request_file().map(str::to_string) else {
             return Ok(());
-        }
+        };
 
-        // Get the visual selection coordinates
-        let (start_pos, end_pos, pane) = self.view_model.get_visual_selection();
-        if let (Some(start), Some(end), Some(selected_pane)) = (start_pos, end_pos, pane) {
-            if selected_pane != self.view_model.get_current_pane() {
-                tracing::warn!("Visual selection is not in current pane");
-                return Ok(());
+        let content = self.view_model.get_request_text();
+        let modified = self.view_model.is_request_modified();
+        match self
+            .services
+            .autosave
+            .save_if_due(&path, &content, modified, force)
+        {
+            Ok(Some(_)) => {
+                self.view_model.set_request_modified(false);
+                self.services.autoread.track(&path);
+                self.view_model
+                    .set_status_message(format!("\"{path}\" autosaved"));
             }
-
-            // Calculate the block boundaries
-            let start_line = start.line.min(end.line);
-            let end_line = start.line.max(end.line);
-            let start_col = start.column.min(end.column);
-
-            // Create cursor positions for all lines in the block
-            let mut cursor_positions = Vec::new();
-            for line in start_line..=end_line {
-                cursor_positions.push(LogicalPosition::new(line, start_col));
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                tracing::error!("Failed to autosave {}: {}", path, e);
+                self.view_model
+                    .set_error_message(&AppError::User(format!("autosave failed: {e}")));
             }
+        }
 
-            // Set multi-cursor state for Visual Block Insert
-            self.view_model
-                .set_visual_block_insert_cursors(cursor_positions);
-
-            // Move primary cursor to start of block (beginning of leftmost column on first line)
-            self.view_model
-                .set_cursor_position(LogicalPosition::new(start_line, start_col))?;
-
-            // Enter Visual Block Insert mode
-            self.view_model.change_mode(EditorMode::VisualBlockInsert)?;
-
-            // Show feedback
-            let line_count = (start.line.max(end.line) - start_line) + 1;
-            self.view_model
-                .set_status_message(format!("Visual Block Insert: {line_count} lines"));
+        self.render_if_needed()
+    }
 
-            tracing::info!(
-                "Entered Visual Block Insert mode at position ({}, {}), affecting {} lines",
-                start_line,
-                start_col,
-                line_count
-            );
-        } else {
-            tracing::warn!("No visual block selection found");
-            self.view_model
-                .set_status_message("No visual block selection".to_string());
+    /// Write the request buffer before leaving its pane, with `:set autowrite on`
+    fn autowrite_request(&mut self) {
+        let mut context = ExecutionContext {
+            view_model: &mut self.view_model,
+            services: &mut self.services,
+        };
+        if let Err(e) = context.autowrite_request() {
+            tracing::error!("Failed to autowrite request buffer: {}", e);
+            self.view_model.set_error_message(&e);
         }
-
-        Ok(())
     }
 
-    /// Handle Visual Block Append operation ('A' in Visual Block mode)
+    /// Reload the request buffer when another program changed its file
     ///
-    /// This implements vim's Visual Block Append command:
-    /// 1. Remember the selected block coordinates
-    /// 2. Move cursor to the end of the first selected line in the block
-    /// 3. Enter special VisualBlockInsert mode
-    /// 4. Text typed appears on first line, replicated to all lines on Esc
-    fn handle_visual_block_append(&mut self) -> Result<()> {
-        // Only supported in Visual Block mode
-        let current_mode = self.view_model.get_mode();
-        if current_mode != EditorMode::VisualBlock {
-            tracing::warn!("Visual Block Append only supported in Visual Block mode, current mode: {current_mode:?}");
-            self.view_model.set_status_message(
-                "Visual Block Append only supported in Visual Block mode".to_string(),
-            );
+    /// Runs on idle ticks of the event loop and, with `force`, when the
+    /// terminal regains focus. A buffer with unsaved changes is kept and a
+    /// warning shown instead, as Vim does.
+    fn autoread_if_changed(&mut self, force: bool) -> Result<()> {
+        let Some(path) = self.view_model.get_request_file().map(str::to_string) else {
             return Ok(());
-        }
+        };
 
-        // Get the visual selection coordinates
-        let (start_pos, end_pos, pane) = self.view_model.get_visual_selection();
-        if let (Some(start), Some(end), Some(selected_pane)) = (start_pos, end_pos, pane) {
-            if selected_pane != self.view_model.get_current_pane() {
-                tracing::warn!("Visual selection is not in current pane");
-                return Ok(());
+        let modified = self.view_model.is_request_modified();
+        match self.services.autoread.check(&path, modified, force) {
+            Ok(Some(ExternalChange::Reload(content))) => {
+                // Writes end the file with a newline that is not part of the buffer
+                let content = content.strip_suffix('\n').unwrap_or(&content);
+                self.view_model.set_request_text(content)?;
+                self.view_model.set_request_modified(false);
+                self.view_model
+                    .set_status_message(format!("\"{path}\" reloaded"));
             }
-
-            // Calculate the block boundaries
-            let start_line = start.line.min(end.line);
-            let end_line = start.line.max(end.line);
-            let end_col = start.column.max(end.column);
-
-            // Create cursor positions for all lines in the block (AFTER the end position for append)
-            // Visual Block 'A' should position cursor after the rightmost selected character
-            let mut cursor_positions = Vec::new();
-            for line in start_line..=end_line {
-                cursor_positions.push(LogicalPosition::new(line, end_col + 1));
+            Ok(Some(ExternalChange::Conflict)) => {
+                self.view_model.set_status_message(format!(
+                    "W11: Warning: File \"{path}\" has changed since editing started"
+                ));
             }
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                tracing::error!("Failed to reload {}: {}", path, e);
+                self.view_model.set_error_message(&e);
+            }
+        }
 
-            // Set multi-cursor state for Visual Block Insert
-            self.view_model
-                .set_visual_block_insert_cursors(cursor_positions);
-
-            // Move primary cursor to after the end of block (one position after rightmost column)
-            self.view_model
-                .set_cursor_position(LogicalPosition::new(start_line, end_col + 1))?;
-
-            // Enter Visual Block Insert mode
-            self.view_model.change_mode(EditorMode::VisualBlockInsert)?;
-
-            // Show feedback
-            let line_count = (start.line.max(end.line) - start_line) + 1;
-            self.view_model
-                .set_status_message(format!("Visual Block Append: {line_count} lines"));
+        self.render_if_needed()
+    }
 
-            tracing::info!(
-                "Entered Visual Block Append mode at position ({}, {}), affecting {} lines",
-                start_line,
-                end_col,
-                line_count
-            );
+    /// Start or stop the timeout for a key sequence after a key was handled
+    ///
+    /// Every key that leaves Normal mode waiting for another key restarts the
+    /// wait; any other key ends it.
+    fn track_pending_key_sequence(&mut self) {
+        if self.view_model.is_awaiting_key_sequence() {
+            self.services.key_timeout.start();
         } else {
-            tracing::warn!("No visual block selection found");
-            self.view_model
-                .set_status_message("No visual block selection".to_string());
+            self.services.key_timeout.cancel();
+        }
+    }
+
+    /// Let a pending key sequence act alone once `timeoutlen` has passed
+    ///
+    /// Runs on idle ticks of the event loop, so the timeout is only as precise
+    /// as the poll interval.
+    fn expire_pending_key_sequence(&mut self) -> Result<()> {
+        if !self.services.key_timeout.is_expired() {
+            return Ok(());
         }
 
+        self.services.key_timeout.cancel();
+        if self.view_model.resolve_pending_key_sequence()? {
+            self.render_if_needed()?;
+        }
         Ok(())
     }
 
-    /// Handle exit from Visual Block Insert mode with text replication
+    /// Take down the yank flash once it has been shown long enough
     ///
-    /// This implements the complex vim behavior where:
-    /// 1. Text typed on the first line during Visual Block Insert is captured
-    /// 2. That text is replicated to all lines that were in the original block selection  
-    /// 3. Cursor is positioned at the end of the inserted text on the first line
-    fn handle_exit_visual_block_insert(&mut self) -> Result<()> {
-        tracing::info!("Exiting Visual Block Insert mode");
+    /// Like key timeouts, this runs on idle ticks of the event loop.
+    fn expire_yank_highlight(&mut self) -> Result<()> {
+        if !self.services.yank_flash.is_expired() {
+            return Ok(());
+        }
 
-        // Preserve cursor position at the first multi-cursor position
-        let cursor_to_preserve = self
-            .view_model
-            .get_visual_block_insert_cursors()
-            .first()
-            .copied(); // Get first cursor position before clearing
+        self.services.yank_flash.cancel();
+        self.view_model.clear_yank_highlight()?;
+        self.render_if_needed()
+    }
 
-        // Clear multi-cursor state
-        self.view_model.clear_visual_block_insert_cursors();
+    /// Take down the showmatch flash once `matchtime` has passed
+    fn expire_match_highlight(&mut self) -> Result<()> {
+        if !self.services.match_flash.is_expired() {
+            return Ok(());
+        }
 
-        // Clear visual selection that was active when we entered Visual Block Insert
-        self.view_model.clear_visual_selection()?;
+        self.services.match_flash.cancel();
+        self.view_model.clear_match_highlight()?;
+        self.render_if_needed()
+    }
 
-        // Restore cursor position to where typing was happening (first cursor)
-        if let Some(preserved_cursor) = cursor_to_preserve {
-            self.view_model.set_cursor_position(preserved_cursor)?;
-            tracing::debug!("Preserved cursor position at {:?}", preserved_cursor);
+    /// Handle writing the request buffer to its file and quitting (:wq, :x, ZZ)
+    ///
+    /// The application keeps running when a needed write fails or the buffer
+    /// has no file to write to.
+    fn handle_write_quit(&mut self, always_write: bool) -> AppResult<()> {
+        if !always_write && !self.view_model.is_request_modified() {
+            self.should_quit = true;
+            return Ok(());
         }
 
-        self.view_model.change_mode(EditorMode::Normal)?;
+        let Some(path) = self.view_model.get_request_file().map(str::to_string) else {
+            return Err(AppError::User("No file name".to_string()));
+        };
 
-        // Clear any previous status messages when exiting Visual Block Insert
-        self.view_model.clear_status_message();
+        self.handle_write_buffer(&path, false)?;
+        self.should_quit = true;
+        Ok(())
+    }
 
+    /// Handle showing the resolved request without sending it (:dryrun)
+    fn handle_dry_run(&mut self) -> AppResult<()> {
+        let request_text = self.view_model.get_request_text();
+        let summary = match &self.services.http {
+            Some(http_service) => http_service.dry_run(&request_text)?,
+            None => return Err(AppError::User("HTTP client not configured".to_string())),
+        };
+        self.view_model.set_status_message(summary);
         Ok(())
     }
 
-    /// Handle repeat visual selection (gv command)
+    /// Handle running a setup request to capture a value from its response (:chain)
     ///
-    /// Restores the last visual selection including:
-    /// 1. The selection range (start and end positions)
-    /// 2. The visual mode type (character/line/block)
-    /// 3. Cursor position at end of selection
-    fn handle_repeat_visual_selection(&mut self) -> Result<()> {
-        tracing::info!("Handling repeat visual selection (gv command)");
-
-        // First, return to Normal mode to exit GPrefix mode
-        self.view_model.change_mode(EditorMode::Normal)?;
-
-        // Try to restore the last visual selection
-        match self.view_model.restore_last_visual_selection()? {
-            Some(mode) => {
-                tracing::info!("Restored last visual selection with mode {:?}", mode);
-                // Change to the restored visual mode
-                self.view_model.change_mode(mode)?;
-            }
-            None => {
-                tracing::info!("No previous visual selection to restore");
-                // Stay in Normal mode if there's no selection to restore
-            }
-        }
+    /// The request runs in the background; the captured value is bound when
+    /// its response arrives (see `handle_http_response`).
+    fn handle_chain(&mut self, file: &str, path: String, variable: String) -> AppResult<()> {
+        let Some(request_text) = file::read_buffer(file)? else {
+            return Err(AppError::User(format!("E484: Can't open file {file}")));
+        };
 
+        let Some(http_service) = &mut self.services.http else {
+            return Err(AppError::User("HTTP client not configured".to_string()));
+        };
+        http_service.execute_chain_async(request_text, path, variable.clone());
+        self.view_model
+            .set_status_message(format!("Running \"{file}\" to capture ${{{variable}}}..."));
         Ok(())
     }
 
-    /// Handle text insertion for multi-cursor Visual Block Insert mode
-    ///
-    /// Inserts the same text at all cursor positions simultaneously,
-    /// providing live feedback across all selected lines.
-    fn handle_multi_cursor_text_insert(&mut self, text: &str) -> Result<()> {
-        let cursor_positions = self.view_model.get_visual_block_insert_cursors().to_vec();
+    /// Handle checking request header names for typos and repeats (:lint)
+    fn handle_lint_headers(&mut self) {
+        let warnings: Vec<String> = [
+            self.view_model.duplicate_header_warning(),
+            self.view_model.header_lint_warning(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let message = if warnings.is_empty() {
+            "No header issues found".to_string()
+        } else {
+            warnings.join("; ")
+        };
+        self.view_model.set_status_message(message);
+    }
 
-        if cursor_positions.is_empty() {
-            // Fallback to regular insert if no cursors are set
-            return self.view_model.insert_text(text);
+    /// Handle saving, loading and listing request templates (:template)
+    ///
+    /// A template that cannot be saved, loaded or listed fails with its [`AppError`].
+    fn handle_template(&mut self, action: TemplateAction) -> Result<()> {
+        let templates_dir = get_templates_dir();
+        let message = match action {
+            TemplateAction::Save(name) => {
+                let content = self.view_model.get_request_text();
+                template::save_template(&templates_dir, &name, &content)?;
+                format!("Template '{name}' saved")
+            }
+            TemplateAction::Load(name) => {
+                let content = template::load_template(&templates_dir, &name)?;
+                self.view_model.set_request_text(&content)?;
+                format!("Template '{name}' loaded")
+            }
+            TemplateAction::List => {
+                let names = template::list_templates(&templates_dir)?;
+                if names.is_empty() {
+                    "No templates saved".to_string()
+                } else {
+                    format!("Templates: {}", names.join(", "))
+                }
+            }
+        };
+        self.view_model.set_status_message(message);
+        Ok(())
+    }
+
+    /// Handle setting changes from ex commands
+    fn handle_setting_change(&mut self, setting: Setting, value: SettingValue) -> Result<()> {
+        // Handle clipboard setting through YankService
+        if setting == Setting::Clipboard {
+            let enable = value == SettingValue::On;
+            self.services.yank.set_clipboard_enabled(enable)?;
+            // Update status message
+            let message = if enable {
+                "Clipboard integration enabled"
+            } else {
+                "Clipboard integration disabled"
+            };
+            self.view_model.set_status_message(message.to_string());
+            Ok(())
+        } else if setting == Setting::ClipboardUnnamedPlus {
+            let enable = value == SettingValue::On;
+            let message = match (enable, self.services.yank.set_unnamedplus_enabled(enable)) {
+                (false, _) => "Clipboard sync disabled",
+                (true, true) => "Yanks and pastes use the system clipboard",
+                (true, false) => "System clipboard unavailable, yanks are sent over OSC 52",
+            };
+            self.view_model.set_status_message(message.to_string());
+            Ok(())
+        } else if setting == Setting::AutoSave {
+            self.services
+                .autosave
+                .set_enabled(value == SettingValue::On);
+            Ok(())
+        } else if setting == Setting::AutoWrite {
+            self.services
+                .autosave
+                .set_autowrite_enabled(value == SettingValue::On);
+            Ok(())
+        } else if setting == Setting::AutoSaveInterval {
+            if let SettingValue::Number(secs) = value {
+                self.services.autosave.set_interval_secs(secs);
+            }
+            Ok(())
+        } else if setting == Setting::AutoRead {
+            self.services
+                .autoread
+                .set_enabled(value == SettingValue::On);
+            Ok(())
+        } else if setting == Setting::TimeoutLen {
+            if let SettingValue::Number(ms) = value {
+                self.services.key_timeout.set_timeout_ms(ms);
+            }
+            Ok(())
+        } else if setting == Setting::MatchTime {
+            if let SettingValue::Number(tenths) = value {
+                self.services.match_flash.set_matchtime(tenths);
+            }
+            Ok(())
+        } else if setting == Setting::Confirm {
+            // Setting confirm again brings back prompts skipped with `(a)ll`
+            self.confirm_all = false;
+            self.view_model.apply_setting(setting, value)
+        } else {
+            // Other settings still go through ViewModel
+            self.view_model.apply_setting(setting, value)
         }
+    }
 
-        tracing::debug!(
-            "Multi-cursor text insert: '{}' at {} positions",
-            text,
-            cursor_positions.len()
-        );
+    /// Handle yanking selected text to yank buffer
+    fn handle_yank_selection(&mut self) -> Result<()> {
+        // Get selected text from current pane
+        if let Some(text) = self.view_model.get_selected_text() {
+            // Determine yank type based on current visual mode
+            let current_mode = self.view_model.get_mode();
+            let yank_type = match current_mode {
+                EditorMode::Visual => NewYankType::Character,
+                EditorMode::VisualLine => NewYankType::Line,
+                EditorMode::VisualBlock => NewYankType::Block {
+                    width: self.view_model.get_visual_block_width(),
+                },
+                _ => NewYankType::Character, // Fallback for any other mode
+            };
 
-        // Insert text at each cursor position
-        // We need to process in reverse order to maintain position validity
-        for position in cursor_positions.iter().rev() {
-            // Temporarily set cursor to this position and insert text
-            self.view_model.set_cursor_position(*position)?;
-            self.view_model.insert_text(text)?;
+            // Store in yank buffer using YankService (not the old ViewModel method!)
+            self.services.yank.yank(text.clone(), yank_type)?;
+
+            // Flash the yanked text while the selection still marks it
+            self.view_model.highlight_selection_yank(yank_type)?;
+
+            // Switch to Normal mode (automatically clears visual selection)
+            self.view_model.change_mode(EditorMode::Normal)?;
+
+            // Show feedback in status bar
+            let char_count = text.chars().count();
+            let line_count = text.lines().count();
+            let message = match yank_type {
+                NewYankType::Character => {
+                    if line_count > 1 {
+                        format!("{line_count} lines yanked (character-wise)")
+                    } else {
+                        format!("{char_count} characters yanked")
+                    }
+                }
+                NewYankType::Line => format!("{line_count} lines yanked (line-wise)"),
+                NewYankType::Block { .. } => {
+                    format!("Block yanked ({line_count} lines, {char_count} chars)")
+                }
+            };
+            self.view_model.set_status_message(message);
+
+            tracing::info!(
+                "Yanked {} characters ({} lines) to buffer as {:?}",
+                char_count,
+                line_count,
+                yank_type
+            );
+        } else {
+            tracing::warn!("No text selected for yanking");
+            self.view_model
+                .set_status_message("No text selected".to_string());
         }
 
-        // Update all cursor positions to reflect the inserted text
-        let text_len = text.chars().count(); // Handle multi-byte characters correctly
-        let updated_positions: Vec<LogicalPosition> = cursor_positions
-            .iter()
-            .map(|pos| LogicalPosition::new(pos.line, pos.column + text_len))
-            .collect();
+        Ok(())
+    }
 
-        // Set the primary cursor to the first position before updating positions
-        if let Some(first_pos) = updated_positions.first() {
-            self.view_model.set_cursor_position(*first_pos)?;
+    /// Handle deleting selected text
+    fn handle_delete_selection(&mut self) -> Result<()> {
+        // Delete the selected text - the method now returns the deleted text directly
+        if let Some(deleted_text) = self.view_model.delete_selected_text()? {
+            // Switch to Normal mode (automatically clears visual selection)
+            self.view_model.change_mode(EditorMode::Normal)?;
+
+            // Show feedback in status bar
+            let char_count = deleted_text.chars().count();
+            let line_count = deleted_text.lines().count();
+            let message = if line_count > 1 {
+                format!("{line_count} lines deleted")
+            } else {
+                format!("{char_count} characters deleted")
+            };
+            self.view_model.set_status_message(message);
+
+            tracing::info!("Deleted {} characters ({} lines)", char_count, line_count);
+        } else {
+            tracing::warn!("No text selected for deletion");
+            self.view_model
+                .set_status_message("No text selected".to_string());
         }
 
+        Ok(())
+    }
+
+    /// Show Vim's "3 lines >ed 2 times" feedback after a visual shift
+    fn report_shifted_lines(&mut self, shifted: Option<usize>, operator: char, count: usize) {
+        let Some(line_count) = shifted else {
+            return;
+        };
+        let lines = if line_count == 1 { "line" } else { "lines" };
+        let times = if count == 1 { "time" } else { "times" };
         self.view_model
-            .update_visual_block_insert_cursors(updated_positions);
+            .set_status_message(format!("{line_count} {lines} {operator}ed {count} {times}"));
+    }
 
-        tracing::debug!("Multi-cursor text insert completed, updated cursor positions");
+    /// Handle `==` and `=G`: reformat lines from the cursor down
+    ///
+    /// The lines go through `equalprg` when one is set, otherwise through the
+    /// internal JSON layout, which leaves text that is not JSON alone.
+    fn handle_reindent_lines(&mut self, count: Option<usize>) -> Result<()> {
+        if self.view_model.get_current_pane() != Pane::Request {
+            return Ok(());
+        }
+        let first = self.view_model.get_cursor_position().line;
+        let last = match count {
+            Some(count) => first.saturating_add(count.max(1) - 1),
+            None => usize::MAX,
+        };
+        let Some(text) = self.view_model.get_lines_text(first, last) else {
+            return Ok(());
+        };
+
+        let program = self.view_model.get_equalprg().to_string();
+        let formatted = if program.is_empty() {
+            let text = text.strip_suffix('\n').unwrap_or(&text);
+            match reindent_json(text, &self.view_model.indent_unit()) {
+                Some(formatted) => formatted,
+                None => text.to_string(),
+            }
+        } else {
+            match filter::run_filter(self.view_model.get_shell(), &program, &text) {
+                Ok(output) => output.strip_suffix('\n').unwrap_or(&output).to_string(),
+                Err(e) => {
+                    self.view_model.set_error_message(&e);
+                    return Ok(());
+                }
+            }
+        };
+
+        let line_count = self.view_model.replace_lines(first, last, &formatted)?;
+        let lines = if line_count == 1 { "line" } else { "lines" };
+        self.view_model
+            .set_status_message(format!("{line_count} {lines} indented"));
         Ok(())
     }
 
-    /// Handle text deletion for multi-cursor Visual Block Insert mode
-    fn handle_multi_cursor_text_delete(
+    /// Handle `:set shell=<path>`; an empty path restores the default shell
+    fn handle_shell_change(&mut self, shell: String) {
+        let shell = if shell.is_empty() {
+            filter::default_shell()
+        } else {
+            shell
+        };
+        self.view_model.set_shell(&shell);
+    }
+
+    /// Handle `:set fileencoding=<name>`; an empty name restores UTF-8
+    ///
+    /// Names the encoding library does not know are rejected, keeping the
+    /// current encoding.
+    fn handle_file_encoding_change(&mut self, encoding: String) -> AppResult<()> {
+        let encoding = if encoding.is_empty() {
+            "utf-8".to_string()
+        } else {
+            encoding
+        };
+        if file::encoding_for_label(&encoding).is_none() {
+            return Err(AppError::User(format!(
+                "E474: Invalid argument: fileencoding={encoding}"
+            )));
+        }
+        self.view_model.set_fileencoding(&encoding);
+        Ok(())
+    }
+
+    /// Handle `:set whichwrap=<flags>`, rejecting flags that name no key
+    fn handle_whichwrap_change(&mut self, flags: String) -> AppResult<()> {
+        if let Some(flag) = flags
+            .split(',')
+            .filter(|flag| !flag.is_empty())
+            .find(|flag| !matches!(*flag, "h" | "l" | "<" | ">" | "[" | "]"))
+        {
+            return Err(AppError::User(format!(
+                "E474: Invalid argument: whichwrap={flags} ({flag:?} is not h, l, <, >, [ or ])"
+            )));
+        }
+        self.view_model.set_whichwrap(&flags);
+        Ok(())
+    }
+
+    /// Handle `:set executekey=<key>`, rejecting notation that names no key
+    fn handle_execute_key_change(&mut self, key: String) -> AppResult<()> {
+        let binding = KeyBinding::parse(&key)
+            .ok_or_else(|| AppError::User(format!("E474: Invalid argument: executekey={key}")))?;
+        self.view_model.set_execute_key(binding);
+        Ok(())
+    }
+
+    /// The encoding `:e` and `:w` use, from `:set fileencoding`
+    fn file_encoding(&self) -> &'static encoding_rs::Encoding {
+        file::encoding_for_label(self.view_model.get_fileencoding()).unwrap_or(encoding_rs::UTF_8)
+    }
+
+    /// Resolve an ex command range against the request buffer
+    ///
+    /// Invalid ranges are reported in the status bar and give `None`.
+    fn resolve_line_range(&mut self, range: LineRange) -> Option<LineSpan> {
+        let cursor_line = self.view_model.get_cursor_position().line;
+        let line_count = self.view_model.get_request_text().split('\n').count();
+        let visual_lines = self.view_model.get_last_visual_line_range();
+        match range.resolve(cursor_line, line_count, visual_lines) {
+            Ok(lines) => Some(lines),
+            Err(e) => {
+                self.view_model.set_status_message(e.to_string());
+                None
+            }
+        }
+    }
+
+    /// Yank the lines of `range` into `register`, returning the lines yanked
+    ///
+    /// With `delete`, the lines are about to be deleted and also go to the
+    /// numbered registers. Failures such as an invalid range or a read-only
+    /// register are reported in the status bar and give `None`.
+    fn yank_line_range(
         &mut self,
-        amount: usize,
-        direction: MovementDirection,
-    ) -> Result<()> {
-        let cursor_positions = self.view_model.get_visual_block_insert_cursors().to_vec();
-        let start_columns = self
-            .view_model
-            .get_visual_block_insert_start_columns()
-            .to_vec();
+        range: LineRange,
+        register: Option<char>,
+        delete: bool,
+    ) -> Option<LineSpan> {
+        let (first, last) = self.resolve_line_range(range)?;
+        let text = self.view_model.get_lines_text(first, last)?;
+        let register = register.unwrap_or('"');
+        let yanked = if delete {
+            self.services
+                .yank
+                .delete_to_register(register, text, NewYankType::Line)
+        } else {
+            self.services
+                .yank
+                .yank_to_register(register, text, NewYankType::Line)
+        };
+        if let Err(e) = yanked {
+            self.view_model.set_status_message(e.to_string());
+            return None;
+        }
+        Some((first, last))
+    }
 
-        if cursor_positions.is_empty() {
-            // Fallback to regular delete if no cursors are set
-            for _ in 0..amount {
-                match direction {
-                    MovementDirection::Left => {
-                        self.view_model.delete_char_before_cursor()?;
-                    }
-                    MovementDirection::Right => {
-                        self.view_model.delete_char_after_cursor()?;
-                    }
-                    _ => {
-                        tracing::warn!("Unsupported delete direction: {:?}", direction);
-                    }
-                }
+    /// Handle deleting a range of lines into a register (`:[range]d [register]`)
+    ///
+    /// With `:set confirm on`, deleting more than one line asks first.
+    fn handle_delete_lines(&mut self, range: LineRange, register: Option<char>) -> Result<()> {
+        if let Some((first, last)) = self.resolve_line_range(range) {
+            let count = last - first + 1;
+            let question = format!("Delete {count} lines?");
+            if count > 1
+                && !self.confirm_destructive(
+                    question,
+                    PendingConfirmation::DeleteLines { range, register },
+                )
+            {
+                return Ok(());
             }
+        }
+        self.delete_line_range(range, register)
+    }
+
+    /// Delete a range of lines into a register without asking
+    fn delete_line_range(&mut self, range: LineRange, register: Option<char>) -> Result<()> {
+        let Some((first, last)) = self.yank_line_range(range, register, true) else {
             return Ok(());
+        };
+
+        let deleted = self.view_model.delete_lines(first, last)?;
+        if deleted > 2 {
+            self.view_model
+                .set_status_message(format!("{deleted} fewer lines"));
+        }
+        Ok(())
+    }
+
+    /// Handle yanking a range of lines into a register (`:[range]y [register]`)
+    fn handle_yank_lines(&mut self, range: LineRange, register: Option<char>) -> Result<()> {
+        if let Some((first, last)) = self.yank_line_range(range, register, false) {
+            self.view_model.highlight_yank(
+                Pane::Request,
+                LogicalPosition::new(first, 0),
+                LogicalPosition::new(last, 0),
+                NewYankType::Line,
+            )?;
+            let count = last - first + 1;
+            let lines = if count == 1 { "line" } else { "lines" };
+            self.view_model
+                .set_status_message(format!("{count} {lines} yanked"));
+        }
+        Ok(())
+    }
+
+    /// Handle cutting (delete + yank) selected text
+    fn handle_cut_selection(&mut self) -> Result<()> {
+        // Cut combines yank + delete, but we need to yank first before deleting
+        if let Some(text) = self.view_model.get_selected_text() {
+            // Determine yank type based on current visual mode BEFORE any mode changes
+            let current_mode = self.view_model.get_mode();
+            let yank_type = match current_mode {
+                EditorMode::Visual => NewYankType::Character,
+                EditorMode::VisualLine => NewYankType::Line,
+                EditorMode::VisualBlock => NewYankType::Block {
+                    width: self.view_model.get_visual_block_width(),
+                },
+                _ => NewYankType::Character, // Fallback for any other mode
+            };
+
+            // First yank to buffer using YankService
+            self.services
+                .yank
+                .delete_to_register('"', text.clone(), yank_type)?;
+
+            // Then delete the selected text (this also returns the deleted text for verification)
+            if let Some(deleted_text) = self.view_model.delete_selected_text()? {
+                // Switch to Normal mode (automatically clears visual selection)
+                self.view_model.change_mode(EditorMode::Normal)?;
+
+                // Show feedback in status bar
+                let char_count = deleted_text.chars().count();
+                let line_count = deleted_text.lines().count();
+                let message = match yank_type {
+                    NewYankType::Character => {
+                        if line_count > 1 {
+                            format!("{line_count} lines cut (character-wise)")
+                        } else {
+                            format!("{char_count} characters cut")
+                        }
+                    }
+                    NewYankType::Line => format!("{line_count} lines cut (line-wise)"),
+                    NewYankType::Block { .. } => {
+                        format!("Block cut ({line_count} lines, {char_count} chars)")
+                    }
+                };
+                self.view_model.set_status_message(message);
+
+                tracing::info!(
+                    "Cut {} characters ({} lines) to buffer as {:?}",
+                    char_count,
+                    line_count,
+                    yank_type
+                );
+            } else {
+                tracing::warn!("Failed to delete selected text during cut operation");
+                self.view_model
+                    .set_status_message("Cut operation failed".to_string());
+            }
+        } else {
+            tracing::warn!("No text selected for cutting");
+            self.view_model
+                .set_status_message("No text selected".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Handle cutting (delete + yank) character at cursor
+    fn handle_cut_character(&mut self) -> Result<()> {
+        if self.reject_read_only_register() {
+            return Ok(());
+        }
+        // Cut character at cursor position - the method already handles yanking
+        self.view_model.cut_char_at_cursor()?;
+
+        tracing::info!("Cut 1 character at cursor to yank buffer");
+
+        Ok(())
+    }
+
+    /// Handle cutting (delete + yank) from cursor to end of line
+    fn handle_cut_to_end_of_line(&mut self) -> Result<()> {
+        if self.reject_read_only_register() {
+            return Ok(());
+        }
+        // Cut from cursor to end of line - the method already handles yanking
+        self.view_model.cut_to_end_of_line()?;
+
+        tracing::info!("Cut from cursor to end of line to yank buffer");
+
+        Ok(())
+    }
+
+    /// Handle cutting (delete + yank) entire current line
+    fn handle_cut_current_line(&mut self) -> Result<()> {
+        if self.reject_read_only_register() {
+            return Ok(());
+        }
+        // Cut entire current line - the method already handles yanking
+        self.view_model.cut_current_line()?;
+
+        tracing::info!("Cut entire current line to yank buffer");
+
+        Ok(())
+    }
+
+    /// Handle yanking (copy) entire current line without deleting
+    fn handle_yank_current_line(&mut self) -> Result<()> {
+        if self.reject_read_only_register() {
+            return Ok(());
+        }
+        // Yank entire current line to yank buffer without deleting
+        self.view_model.yank_current_line()?;
+
+        // Show status message
+        self.view_model
+            .set_status_message("1 line yanked".to_string());
+
+        tracing::info!("Yanked entire current line to yank buffer");
+
+        Ok(())
+    }
+
+    /// Handle yanking the whole response body (:yankresponse)
+    fn handle_yank_response(&mut self) -> Result<()> {
+        let Some(body) = self.view_model.get_response_body().map(str::to_string) else {
+            self.view_model
+                .set_status_message("No response to yank".to_string());
+            return Ok(());
+        };
+
+        let line_count = body.lines().count();
+        self.services.yank.yank(body, NewYankType::Character)?;
+        self.view_model
+            .set_status_message(format!("{line_count} lines of response yanked"));
+
+        Ok(())
+    }
+
+    /// Handle yanking the JSON key or value under the response cursor (:yankvalue)
+    fn handle_yank_json_value(&mut self) -> Result<()> {
+        let Some(value) = self.view_model.json_value_at_cursor() else {
+            self.view_model
+                .set_status_message("No JSON value under cursor".to_string());
+            return Ok(());
+        };
+
+        let count = value.chars().count();
+        self.services.yank.yank(value, NewYankType::Character)?;
+        self.view_model
+            .set_status_message(format!("{count} characters yanked"));
+        Ok(())
+    }
+
+    /// Handle change selection operation (Visual Block mode 'c' command)
+    ///
+    /// This implements vim's Visual Block change command:
+    /// 1. Delete the selected rectangular block
+    /// 2. Enter Visual Block Insert mode for multi-cursor text replacement
+    /// 3. Shows multi-cursor feedback on all affected lines in real-time
+    /// 4. When Esc is pressed, exits Visual Block Insert mode
+    fn handle_change_selection(&mut self) -> Result<()> {
+        // Change operation is currently only supported in Visual Block mode
+        let current_mode = self.view_model.get_mode();
+        if current_mode != EditorMode::VisualBlock {
+            tracing::warn!("Change selection only supported in Visual Block mode, current mode: {current_mode:?}");
+            self.view_model.set_status_message(
+                "Change command only supported in Visual Block mode".to_string(),
+            );
+            return Ok(());
+        }
+
+        // Get the visual selection before deleting it
+        let (selection_start, selection_end, _pane) = self.view_model.get_visual_selection();
+        if selection_start.is_none() || selection_end.is_none() {
+            tracing::warn!("No visual selection for change operation");
+            self.view_model
+                .set_status_message("No text selected".to_string());
+            return Ok(());
+        }
+
+        let start = selection_start.unwrap();
+        let end = selection_end.unwrap();
+
+        // Calculate the cursor positions for Visual Block Insert mode
+        // This is similar to Visual Block Insert, but we start from the deleted block
+        let top_line = start.line.min(end.line);
+        let bottom_line = start.line.max(end.line);
+        let left_col = start.column.min(end.column);
+
+        // Delete the selected block text first
+        if let Some(deleted_text) = self.view_model.delete_selected_text()? {
+            // Create cursor positions for all lines in the deleted block range
+            let mut cursor_positions = Vec::new();
+            for line_num in top_line..=bottom_line {
+                cursor_positions.push(LogicalPosition::new(line_num, left_col));
+            }
+
+            // Set up Visual Block Insert mode with multi-cursor state
+            self.view_model
+                .set_visual_block_insert_cursors(cursor_positions.clone());
+
+            // Switch to VisualBlockInsert mode (not regular Insert)
+            self.view_model.change_mode(EditorMode::VisualBlockInsert)?;
+
+            // Position the main cursor at the first line of the block
+            self.view_model.set_cursor_position(cursor_positions[0])?;
+
+            // Show feedback in status bar
+            let char_count = deleted_text.chars().count();
+            let line_count = deleted_text.lines().count();
+            let message = if line_count > 1 {
+                format!("Changed {line_count} lines, Visual Block Insert mode")
+            } else {
+                format!("Changed {char_count} characters, Visual Block Insert mode")
+            };
+            self.view_model.set_status_message(message);
+
+            tracing::info!(
+                "Changed {} characters ({} lines), entered Visual Block Insert mode with {} cursors",
+                char_count,
+                line_count,
+                cursor_positions.len()
+            );
+        } else {
+            tracing::warn!("No text selected for changing");
+            self.view_model
+                .set_status_message("No text selected".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Handle Visual Block Insert operation ('I' in Visual Block mode)
+    ///
+    /// This implements vim's Visual Block Insert command:
+    /// 1. Remember the selected block coordinates
+    /// 2. Move cursor to the start of the first selected line in the block  
+    /// 3. Enter special VisualBlockInsert mode
+    /// 4. Text typed appears on first line, replicated to all lines on Esc
+    fn handle_visual_block_insert(&mut self) -> Result<()> {
+        // Only supported in Visual Block mode
+        let current_mode = self.view_model.get_mode();
+        if current_mode != EditorMode::VisualBlock {
+            tracing::warn!("Visual Block Insert only supported in Visual Block mode, current mode: {current_mode:?}");
+            self.view_model.set_status_message(
+                "Visual Block Insert only supported in Visual Block mode".to_string(),
+            );
+            return Ok(());
+        }
+
+        // Get the visual selection coordinates
+        let (start_pos, end_pos, pane) = self.view_model.get_visual_selection();
+        if let (Some(start), Some(end), Some(selected_pane)) = (start_pos, end_pos, pane) {
+            if selected_pane != self.view_model.get_current_pane() {
+                tracing::warn!("Visual selection is not in current pane");
+                return Ok(());
+            }
+
+            // Calculate the block boundaries
+            let start_line = start.line.min(end.line);
+            let end_line = start.line.max(end.line);
+            let start_col = start.column.min(end.column);
+
+            // Create cursor positions for all lines in the block
+            let mut cursor_positions = Vec::new();
+            for line in start_line..=end_line {
+                cursor_positions.push(LogicalPosition::new(line, start_col));
+            }
+
+            // Set multi-cursor state for Visual Block Insert
+            self.view_model
+                .set_visual_block_insert_cursors(cursor_positions);
+
+            // Move primary cursor to start of block (beginning of leftmost column on first line)
+            self.view_model
+                .set_cursor_position(LogicalPosition::new(start_line, start_col))?;
+
+            // Enter Visual Block Insert mode
+            self.view_model.change_mode(EditorMode::VisualBlockInsert)?;
+
+            // Show feedback
+            let line_count = (start.line.max(end.line) - start_line) + 1;
+            self.view_model
+                .set_status_message(format!("Visual Block Insert: {line_count} lines"));
+
+            tracing::info!(
+                "Entered Visual Block Insert mode at position ({}, {}), affecting {} lines",
+                start_line,
+                start_col,
+                line_count
+            );
+        } else {
+            tracing::warn!("No visual block selection found");
+            self.view_model
+                .set_status_message("No visual block selection".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Handle Visual Block Append operation ('A' in Visual Block mode)
+    ///
+    /// This implements vim's Visual Block Append command:
+    /// 1. Remember the selected block coordinates
+    /// 2. Move cursor to the end of the first selected line in the block
+    /// 3. Enter special VisualBlockInsert mode
+    /// 4. Text typed appears on first line, replicated to all lines on Esc
+    fn handle_visual_block_append(&mut self) -> Result<()> {
+        // Only supported in Visual Block mode
+        let current_mode = self.view_model.get_mode();
+        if current_mode != EditorMode::VisualBlock {
+            tracing::warn!("Visual Block Append only supported in Visual Block mode, current mode: {current_mode:?}");
+            self.view_model.set_status_message(
+                "Visual Block Append only supported in Visual Block mode".to_string(),
+            );
+            return Ok(());
+        }
+
+        // Get the visual selection coordinates
+        let (start_pos, end_pos, pane) = self.view_model.get_visual_selection();
+        if let (Some(start), Some(end), Some(selected_pane)) = (start_pos, end_pos, pane) {
+            if selected_pane != self.view_model.get_current_pane() {
+                tracing::warn!("Visual selection is not in current pane");
+                return Ok(());
+            }
+
+            // Calculate the block boundaries
+            let start_line = start.line.min(end.line);
+            let end_line = start.line.max(end.line);
+            let end_col = start.column.max(end.column);
+
+            // Create cursor positions for all lines in the block (AFTER the end position for append)
+            // Visual Block 'A' should position cursor after the rightmost selected character
+            let mut cursor_positions = Vec::new();
+            for line in start_line..=end_line {
+                cursor_positions.push(LogicalPosition::new(line, end_col + 1));
+            }
+
+            // Set multi-cursor state for Visual Block Insert
+            self.view_model
+                .set_visual_block_insert_cursors(cursor_positions);
+
+            // Move primary cursor to after the end of block (one position after rightmost column)
+            self.view_model
+                .set_cursor_position(LogicalPosition::new(start_line, end_col + 1))?;
+
+            // Enter Visual Block Insert mode
+            self.view_model.change_mode(EditorMode::VisualBlockInsert)?;
+
+            // Show feedback
+            let line_count = (start.line.max(end.line) - start_line) + 1;
+            self.view_model
+                .set_status_message(format!("Visual Block Append: {line_count} lines"));
+
+            tracing::info!(
+                "Entered Visual Block Append mode at position ({}, {}), affecting {} lines",
+                start_line,
+                end_col,
+                line_count
+            );
+        } else {
+            tracing::warn!("No visual block selection found");
+            self.view_model
+                .set_status_message("No visual block selection".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Handle exit from Visual Block Insert mode with text replication
+    ///
+    /// This implements the complex vim behavior where:
+    /// 1. Text typed on the first line during Visual Block Insert is captured
+    /// 2. That text is replicated to all lines that were in the original block selection  
+    /// 3. Cursor is positioned at the end of the inserted text on the first line
+    fn handle_exit_visual_block_insert(&mut self) -> Result<()> {
+        tracing::info!("Exiting Visual Block Insert mode");
+
+        // Preserve cursor position at the first multi-cursor position
+        let cursor_to_preserve = self
+            .view_model
+            .get_visual_block_insert_cursors()
+            .first()
+            .copied(); // Get first cursor position before clearing
+
+        // Clear multi-cursor state
+        self.view_model.clear_visual_block_insert_cursors();
+
+        // Clear visual selection that was active when we entered Visual Block Insert
+        self.view_model.clear_visual_selection()?;
+
+        // Restore cursor position to where typing was happening (first cursor)
+        if let Some(preserved_cursor) = cursor_to_preserve {
+            self.view_model.set_cursor_position(preserved_cursor)?;
+            tracing::debug!("Preserved cursor position at {:?}", preserved_cursor);
+        }
+
+        self.view_model.change_mode(EditorMode::Normal)?;
+
+        // Clear any previous status messages when exiting Visual Block Insert
+        self.view_model.clear_status_message();
+
+        Ok(())
+    }
+
+    /// Move the cursor `amount` times in `direction`
+    ///
+    /// Shared by plain motions and operators like `gUw` that act over a motion.
+    fn move_cursor(&mut self, direction: MovementDirection, amount: usize) -> Result<()> {
+        for _ in 0..amount {
+            match direction {
+                MovementDirection::Left => self.view_model.move_cursor_left(false)?,
+                MovementDirection::Right => self.view_model.move_cursor_right(false)?,
+                MovementDirection::LeftWrap => self.view_model.move_cursor_left(true)?,
+                MovementDirection::RightWrap => self.view_model.move_cursor_right(true)?,
+                MovementDirection::Up => self.view_model.move_cursor_up()?,
+                MovementDirection::Down => self.view_model.move_cursor_down()?,
+                MovementDirection::DisplayLineUp => {
+                    self.view_model.move_cursor_display_line_up()?
+                }
+                MovementDirection::DisplayLineDown => {
+                    self.view_model.move_cursor_display_line_down()?
+                }
+                MovementDirection::LineEnd => self.view_model.move_cursor_to_end_of_line()?,
+                MovementDirection::LineEndForAppend => {
+                    self.view_model.move_cursor_to_line_end_for_append()?
+                }
+                MovementDirection::LineStart => self.view_model.move_cursor_to_start_of_line()?,
+                MovementDirection::ScrollLeft => self.view_model.scroll_horizontally(-1, amount)?,
+                MovementDirection::ScrollRight => self.view_model.scroll_horizontally(1, amount)?,
+                MovementDirection::DocumentStart => {
+                    self.view_model.move_cursor_to_document_start()?
+                }
+                MovementDirection::DocumentEnd => self.view_model.move_cursor_to_document_end()?,
+                MovementDirection::WordForward => self.view_model.move_cursor_to_next_word()?,
+                MovementDirection::WordBackward => {
+                    self.view_model.move_cursor_to_previous_word()?
+                }
+                MovementDirection::WordEnd => self.view_model.move_cursor_to_end_of_word()?,
+                MovementDirection::LineNumber(line_number) => {
+                    self.view_model.move_cursor_to_line(line_number)?
+                }
+                MovementDirection::PageDown => self.view_model.move_cursor_page_down()?,
+                MovementDirection::PageUp => self.view_model.move_cursor_page_up()?,
+                MovementDirection::HalfPageDown => self.view_model.move_cursor_half_page_down()?,
+                MovementDirection::HalfPageUp => self.view_model.move_cursor_half_page_up()?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle `gu`, `gU` or `g~`
+    ///
+    /// From normal mode this waits for the motion. Pressing `g` in visual mode
+    /// already ended the selection, so it is brought back like `gv` does and
+    /// changed as a whole.
+    fn handle_case_operator(&mut self, change: CaseChange) -> Result<()> {
+        let previous_mode = self.view_model.get_previous_mode();
+        if !matches!(
+            previous_mode,
+            EditorMode::Visual | EditorMode::VisualLine | EditorMode::VisualBlock
+        ) {
+            return self.view_model.change_mode(change.prefix_mode());
+        }
+
+        self.view_model.change_mode(EditorMode::Normal)?;
+        if let Some(mode) = self.view_model.restore_last_visual_selection()? {
+            self.view_model.change_mode(mode)?;
+            self.view_model.change_case_of_selection(change)?;
+        }
+        Ok(())
+    }
+
+    /// Change case from the cursor over `count` of `motion` (`gUw`, `2gue`)
+    ///
+    /// The cursor is moved with the motion to find where the text ends, the way
+    /// Vim treats it: `e` and `$` take the character they land on, and `w`
+    /// stops at the end of the line instead of running into the next one.
+    fn handle_change_case(
+        &mut self,
+        change: CaseChange,
+        motion: MovementDirection,
+        count: usize,
+    ) -> Result<()> {
+        let origin = self.view_model.get_cursor_position();
+        self.move_cursor(motion, count)?;
+        let target = self.view_model.get_cursor_position();
+
+        let text = self.view_model.get_request_text();
+        let lines: Vec<&str> = text.split('\n').collect();
+        let line_end = |line: usize| {
+            LogicalPosition::new(line, lines.get(line).map_or(0, |l| l.chars().count()))
+        };
+        let indent = |line: usize| {
+            lines
+                .get(line)
+                .map_or(0, |l| l.chars().take_while(|ch| ch.is_whitespace()).count())
+        };
+        let end = match motion {
+            MovementDirection::WordEnd => LogicalPosition::new(target.line, target.column + 1),
+            MovementDirection::LineEnd => line_end(target.line),
+            MovementDirection::WordForward if target <= origin => line_end(origin.line),
+            MovementDirection::WordForward
+                if target.line > origin.line && target.column <= indent(target.line) =>
+            {
+                line_end(target.line - 1)
+            }
+            _ => target,
+        };
+
+        self.view_model.change_case_between(change, origin, end)
+    }
+
+    /// Handle repeat visual selection (gv command)
+    ///
+    /// Restores the last visual selection including:
+    /// 1. The selection range (start and end positions)
+    /// 2. The visual mode type (character/line/block)
+    /// 3. Cursor position at end of selection
+    fn handle_repeat_visual_selection(&mut self) -> Result<()> {
+        tracing::info!("Handling repeat visual selection (gv command)");
+
+        // First, return to Normal mode to exit GPrefix mode
+        self.view_model.change_mode(EditorMode::Normal)?;
+
+        // Try to restore the last visual selection
+        match self.view_model.restore_last_visual_selection()? {
+            Some(mode) => {
+                tracing::info!("Restored last visual selection with mode {:?}", mode);
+                // Change to the restored visual mode
+                self.view_model.change_mode(mode)?;
+            }
+            None => {
+                tracing::info!("No previous visual selection to restore");
+                // Stay in Normal mode if there's no selection to restore
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handle text insertion for multi-cursor Visual Block Insert mode
+    ///
+    /// Inserts the same text at all cursor positions simultaneously,
+    /// providing live feedback across all selected lines.
+    fn handle_multi_cursor_text_insert(&mut self, text: &str) -> Result<()> {
+        let cursor_positions = self.view_model.get_visual_block_insert_cursors().to_vec();
+
+        if cursor_positions.is_empty() {
+            // Fallback to regular insert if no cursors are set
+            return self.view_model.insert_text(text);
+        }
+
+        tracing::debug!(
+            "Multi-cursor text insert: '{}' at {} positions",
+            text,
+            cursor_positions.len()
+        );
+
+        // Insert text at each cursor position
+        // We need to process in reverse order to maintain position validity
+        for position in cursor_positions.iter().rev() {
+            // Temporarily set cursor to this position and insert text
+            self.view_model.set_cursor_position(*position)?;
+            self.view_model.insert_text(text)?;
+        }
+
+        // Update all cursor positions to reflect the inserted text
+        let text_len = text.chars().count(); // Handle multi-byte characters correctly
+        let updated_positions: Vec<LogicalPosition> = cursor_positions
+            .iter()
+            .map(|pos| LogicalPosition::new(pos.line, pos.column + text_len))
+            .collect();
+
+        // Set the primary cursor to the first position before updating positions
+        if let Some(first_pos) = updated_positions.first() {
+            self.view_model.set_cursor_position(*first_pos)?;
+        }
+
+        self.view_model
+            .update_visual_block_insert_cursors(updated_positions);
+
+        tracing::debug!("Multi-cursor text insert completed, updated cursor positions");
+        Ok(())
+    }
+
+    /// Handle text deletion for multi-cursor Visual Block Insert mode
+    fn handle_multi_cursor_text_delete(
+        &mut self,
+        amount: usize,
+        direction: MovementDirection,
+    ) -> Result<()> {
+        let cursor_positions = self.view_model.get_visual_block_insert_cursors().to_vec();
+        let start_columns = self
+            .view_model
+            .get_visual_block_insert_start_columns()
+            .to_vec();
+
+        if cursor_positions.is_empty() {
+            // Fallback to regular delete if no cursors are set
+            for _ in 0..amount {
+                match direction {
+                    MovementDirection::Left => {
+                        self.view_model.delete_char_before_cursor()?;
+                    }
+                    MovementDirection::Right => {
+                        self.view_model.delete_char_after_cursor()?;
+                    }
+                    _ => {
+                        tracing::warn!("Unsupported delete direction: {:?}", direction);
+                    }
+                }
+            }
+            return Ok(());
+        }
+
+        tracing::debug!(
+            "Multi-cursor text delete: {} chars in direction {:?} at {} positions, start columns: {:?}",
+            amount,
+            direction,
+            cursor_positions.len(),
+            start_columns
+        );
+
+        // Perform deletion at each cursor position, respecting boundaries
+        // We need to process in reverse order to maintain position validity
+        for (i, position) in cursor_positions.iter().enumerate().rev() {
+            let start_column = start_columns.get(i).copied().unwrap_or(0);
+
+            // Temporarily set cursor to this position
+            self.view_model.set_cursor_position(*position)?;
+
+            // For left deletion (backspace), respect the Visual Block start boundary
+            let effective_amount = if direction == MovementDirection::Left {
+                // Calculate how many characters we can actually delete without going beyond start
+                let current_col = position.column;
+                let max_deletable = current_col.saturating_sub(start_column);
+                let effective = amount.min(max_deletable);
+                tracing::debug!(
+                    "Backspace calculation: line={}, current_col={}, start_col={}, max_deletable={}, requested={}, effective={}",
+                    position.line, current_col, start_column, max_deletable, amount, effective
+                );
+                effective
+            } else {
+                amount
+            };
+
+            for _ in 0..effective_amount {
+                match direction {
+                    MovementDirection::Left => {
+                        self.view_model.delete_char_before_cursor()?;
+                    }
+                    MovementDirection::Right => {
+                        self.view_model.delete_char_after_cursor()?;
+                    }
+                    _ => {
+                        tracing::warn!("Unsupported delete direction: {:?}", direction);
+                        break;
+                    }
+                }
+            }
+
+            tracing::debug!(
+                "Line {}: deleted {} chars (requested: {}, start_column: {}, current: {})",
+                position.line,
+                effective_amount,
+                amount,
+                start_column,
+                position.column
+            );
+        }
+
+        // Update all cursor positions to reflect the deleted text
+        let updated_positions: Vec<LogicalPosition> = match direction {
+            MovementDirection::Left => {
+                // For backspace, cursor positions move left by amount actually deleted (respecting boundaries)
+                cursor_positions
+                    .iter()
+                    .enumerate()
+                    .map(|(i, pos)| {
+                        let start_column = start_columns.get(i).copied().unwrap_or(0);
+                        let current_col = pos.column;
+                        let max_deletable = current_col.saturating_sub(start_column);
+                        let effective_amount = amount.min(max_deletable);
+                        LogicalPosition::new(pos.line, pos.column.saturating_sub(effective_amount))
+                    })
+                    .collect()
+            }
+            MovementDirection::Right => {
+                // For forward delete, cursor positions stay the same
+                cursor_positions
+            }
+            _ => cursor_positions,
+        };
+
+        // Set the primary cursor to the first position before updating positions
+        if let Some(first_pos) = updated_positions.first() {
+            self.view_model.set_cursor_position(*first_pos)?;
+        }
+
+        self.view_model
+            .update_visual_block_insert_cursors(updated_positions);
+
+        tracing::debug!("Multi-cursor text delete completed, updated cursor positions");
+        Ok(())
+    }
+
+    /// Get the contents of a register, including the read-only `%` and `:` registers
+    fn read_register(&mut self, register: char) -> Option<YankEntry> {
+        self.view_model
+            .read_only_register(register)
+            .or_else(|| self.services.yank.register(register))
+    }
+
+    /// Refuse to yank into the read-only register chosen with `"`, if any
+    ///
+    /// # Returns
+    /// Whether the command was refused and should not run.
+    fn reject_read_only_register(&mut self) -> bool {
+        let Some(register) = self
+            .view_model
+            .get_pending_register()
+            .filter(|&register| is_read_only_register(register))
+        else {
+            return false;
+        };
+        self.view_model
+            .set_status_message(format!("Register '{register}' is read-only"));
+        true
+    }
+
+    /// Handle inserting register contents at the cursor (Ctrl-r in insert mode)
+    fn handle_insert_register(&mut self, register: char) -> Result<()> {
+        match self.read_register(register) {
+            Some(entry) => {
+                self.view_model.insert_text(&entry.text)?;
+                tracing::info!(
+                    "Inserted {} characters from register '{}'",
+                    entry.text.chars().count(),
+                    register
+                );
+            }
+            None => {
+                self.view_model
+                    .set_status_message(format!("Register '{register}' is empty"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handle pasting yanked text after cursor
+    fn handle_paste_after(&mut self) -> Result<()> {
+        // Get from YankService, not the old view_model buffer!
+        let register = self.view_model.get_pending_register().unwrap_or('"');
+        if let Some(yank_entry) = self.read_register(register) {
+            // Paste the text after the current cursor position using type-aware paste
+            self.view_model.paste_after_with_type(&yank_entry)?;
+
+            let char_count = yank_entry.text.chars().count();
+            let line_count = yank_entry.text.lines().count();
+
+            // Clear any previous status message (e.g., "1 line yanked")
+            self.view_model.clear_status_message();
+
+            tracing::info!(
+                "Pasted {} characters ({} lines) after cursor as {:?}",
+                char_count,
+                line_count,
+                yank_entry.yank_type
+            );
+        } else {
+            self.view_model
+                .set_status_message("Nothing to paste".to_string());
+            tracing::warn!("No text in yank buffer to paste");
+        }
+
+        Ok(())
+    }
+
+    /// Handle pasting yanked text at current cursor position
+    fn handle_paste_at_cursor(&mut self) -> Result<()> {
+        // Get from YankService, not the old view_model buffer!
+        let register = self.view_model.get_pending_register().unwrap_or('"');
+        if let Some(yank_entry) = self.read_register(register) {
+            tracing::debug!(
+                "Retrieved yank entry with type: {:?}, text length: {}",
+                yank_entry.yank_type,
+                yank_entry.text.len()
+            );
+
+            // Paste the text at current position (before cursor) using type-aware paste
+            self.view_model.paste_with_type(&yank_entry)?;
+
+            let char_count = yank_entry.text.chars().count();
+            let line_count = yank_entry.text.lines().count();
+
+            // Clear any previous status message (e.g., "1 line yanked")
+            self.view_model.clear_status_message();
+
+            tracing::info!(
+                "Pasted {} characters ({} lines) at cursor as {:?}",
+                char_count,
+                line_count,
+                yank_entry.yank_type
+            );
+        } else {
+            self.view_model
+                .set_status_message("Nothing to paste".to_string());
+            tracing::warn!("No text in yank buffer to paste");
+        }
+
+        Ok(())
+    }
+
+    /// Process a single key event without running the full event loop (for testing)
+    pub async fn process_key_event(&mut self, key_event: KeyEvent) -> Result<()> {
+        tracing::debug!("Processing key event: {:?}", key_event);
+        tracing::debug!("AppController: process_key_event called with {key_event:?}");
+
+        // A `:set confirm` prompt takes every key until it is answered
+        if self.pending_confirmation.is_some() {
+            return self.answer_confirmation(key_event);
+        }
+
+        // The scratch overlay stays up until it is closed
+        if self.view_model.get_scratch().is_some() {
+            return self.dismiss_scratch(key_event);
+        }
+
+        // Create command context from current state
+        tracing::debug!("AppController: Creating command context");
+        let context = CommandContext::new(ViewModelSnapshot::from_view_model(&self.view_model));
+        tracing::debug!("AppController: Command context created");
+
+        // Process through command registry
+        tracing::debug!("AppController: About to call command_registry.process_event");
+        if let Ok(events) = self.command_registry.process_event(key_event, &context) {
+            let events = self.track_last_change(&context, events);
+            let extends_prefix = Self::extends_prefix(&events);
+            tracing::debug!(
+                "AppController: Command events generated: {} events",
+                events.len()
+            );
+            tracing::debug!("Command events generated: {:?}", events);
+            if !events.is_empty() {
+                // Apply events to view model (this will emit appropriate ViewEvents)
+                tracing::debug!(
+                    "AppController: About to apply {} command events",
+                    events.len()
+                );
+                for (i, event) in events.iter().enumerate() {
+                    tracing::debug!(
+                        "AppController: Applying event {}/{}: {:?}",
+                        i + 1,
+                        events.len(),
+                        event
+                    );
+                    self.apply_command_event(event.clone()).await?;
+                    tracing::debug!(
+                        "AppController: Applied event {}/{} successfully",
+                        i + 1,
+                        events.len()
+                    );
+                }
+                tracing::debug!("AppController: All command events applied successfully");
+
+                self.clear_used_prefix(extends_prefix);
+
+                // Render after processing key events
+                self.view_renderer.render_full(&self.view_model)?;
+            } else {
+                tracing::debug!("AppController: No command events generated");
+                self.view_model.clear_pending_count();
+            }
+        } else {
+            tracing::warn!("AppController: Failed to process key event: {key_event:?}");
+        }
+
+        self.track_pending_key_sequence();
+        tracing::debug!("AppController: process_key_event completed successfully");
+        Ok(())
+    }
+
+    /// Check if the application should quit (for testing)
+    pub fn should_quit(&self) -> bool {
+        self.should_quit
+    }
+
+    /// Execute a Command using the new Command Pattern
+    ///
+    /// This method allows execution of Commands that emit ModelEvents
+    /// alongside the existing command system. This enables gradual migration.
+    pub fn execute_command(&mut self, command: Box<dyn Command>) -> Result<()> {
+        tracing::debug!("Executing command: {}", command.name());
+
+        let mut exec_context = ExecutionContext {
+            view_model: &mut self.view_model,
+            services: &mut self.services,
+        };
+        let events = command.handle(&mut exec_context)?;
+
+        tracing::debug!(
+            "Command {} produced {} events",
+            command.name(),
+            events.len()
+        );
+
+        // Process each ModelEvent and convert to actual state changes
+        for event in events {
+            self.process_model_event_internal(event)?;
+        }
+
+        Ok(())
+    }
+
+    /// Process a ModelEvent and convert it to actual state changes
+    ///
+    /// This is the bridge between semantic ModelEvents and the actual
+    /// application state changes. It handles status messages, logging,
+    /// and any necessary side effects.
+    #[cfg(test)]
+    pub fn process_model_event(&mut self, event: ModelEvent) -> Result<()> {
+        self.process_model_event_internal(event)
+    }
+
+    /// Internal implementation of process_model_event
+    fn process_model_event_internal(&mut self, event: ModelEvent) -> Result<()> {
+        match event {
+            ModelEvent::TextYanked {
+                pane,
+                text,
+                yank_type,
+            } => {
+                // Store in yank buffer using YankService
+                // (No need to convert types anymore - yank_type is already NewYankType)
+                self.services.yank.yank(text.clone(), yank_type)?;
+
+                // Create appropriate status message
+                let char_count = text.chars().count();
+                let line_count = text.lines().count();
+                let message = match yank_type {
+                    NewYankType::Character => {
+                        if line_count > 1 {
+                            format!("{line_count} lines yanked (character-wise)")
+                        } else {
+                            format!("{char_count} characters yanked")
+                        }
+                    }
+                    NewYankType::Line => {
+                        format!("{line_count} lines yanked")
+                    }
+                    NewYankType::Block { .. } => {
+                        format!("Block yanked ({line_count} lines, {char_count} chars)")
+                    }
+                };
+
+                self.view_model.set_status_message(message);
+
+                tracing::info!(
+                    "Yanked {} characters ({} lines) to buffer as {:?} from {:?}",
+                    char_count,
+                    line_count,
+                    yank_type,
+                    pane
+                );
+            }
+
+            ModelEvent::ModeChanged { old_mode, new_mode } => {
+                self.view_model.change_mode(new_mode)?;
+                tracing::debug!("Mode changed from {:?} to {:?}", old_mode, new_mode);
+            }
+
+            ModelEvent::SelectionCleared { pane } => {
+                // Selection clearing happens automatically when mode changes to Normal
+                tracing::debug!("Selection cleared for {:?}", pane);
+            }
+
+            ModelEvent::StatusMessageSet { message } => {
+                self.view_model.set_status_message(message);
+            }
+
+            ModelEvent::StatusMessageCleared => {
+                self.view_model.set_status_message(String::new());
+            }
+
+            ModelEvent::HttpRequestStarted { method, url } => {
+                // Just log it - the actual execution is handled by HttpExecuteCommand
+                tracing::info!("HTTP request initiated: {method} {url}");
+            }
+
+            ModelEvent::HttpResponseReceived { status } => {
+                // The response pane was already filled from the HTTP response, so only
+                // the status message is left to update
+                let status_msg = if (200..300).contains(&status) {
+                    format!("Request completed: {status}")
+                } else {
+                    format!("Request failed: {status}")
+                };
+                self.view_model.set_status_message(status_msg);
+            }
+
+            // Handle other events as we implement them
+            _ => {
+                tracing::debug!("ModelEvent not yet implemented: {:?}", event);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd_args::CommandLineArgs;
+    use crate::repl::events::{EditorMode, Pane};
+
+    #[test]
+    fn app_controller_should_create() {
+        if crossterm::terminal::size().is_ok() {
+            let cmd_args = CommandLineArgs::parse_from(["test"]);
+            let config = AppConfig::from_args(cmd_args);
+            let controller = AppController::with_io_streams(
+                config,
+                crate::repl::io::TerminalEventStream::new(),
+                crate::repl::io::TerminalRenderStream::new(),
+            );
+            assert!(controller.is_ok());
+
+            let controller = controller.unwrap();
+            assert_eq!(controller.view_model().get_mode(), EditorMode::Normal);
+            assert_eq!(controller.view_model().get_current_pane(), Pane::Request);
+        }
+    }
+
+    #[test]
+    fn app_controller_should_execute_yank_selection_command() {
+        use crate::repl::view_models::commands::yank::YankSelectionCommand;
+
+        if crossterm::terminal::size().is_ok() {
+            let cmd_args = CommandLineArgs::parse_from(["test"]);
+            let config = AppConfig::from_args(cmd_args);
+            let mut controller = AppController::with_io_streams(
+                config,
+                crate::repl::io::TerminalEventStream::new(),
+                crate::repl::io::TerminalRenderStream::new(),
+            )
+            .unwrap();
+
+            // Test YankSelectionCommand in Normal mode (should fail as expected)
+            let command = Box::new(YankSelectionCommand::new());
+            let result = controller.execute_command(command);
+
+            // Verify the command failed as expected (not in visual mode)
+            assert!(
+                result.is_err(),
+                "Command should fail when not in visual mode"
+            );
+
+            // Verify we're still in Normal mode
+            assert_eq!(controller.view_model().get_mode(), EditorMode::Normal);
+        }
+    }
+
+    #[test]
+    fn app_controller_should_process_model_events() {
+        use crate::repl::view_models::commands::{events::YankType, ModelEvent};
+
+        if crossterm::terminal::size().is_ok() {
+            let cmd_args = CommandLineArgs::parse_from(["test"]);
+            let config = AppConfig::from_args(cmd_args);
+            let mut controller = AppController::with_io_streams(
+                config,
+                crate::repl::io::TerminalEventStream::new(),
+                crate::repl::io::TerminalRenderStream::new(),
+            )
+            .unwrap();
+
+            // Test processing a TextYanked event
+            let event = ModelEvent::TextYanked {
+                pane: Pane::Request,
+                text: "test text".to_string(),
+                yank_type: YankType::Character,
+            };
+
+            let result = controller.process_model_event(event);
+            assert!(result.is_ok(), "ModelEvent processing should succeed");
+
+            // Verify yank buffer contains the text
+            // NOTE: YankService now owns the yank buffer, not ViewModel
+            // We would need to check controller.services.yank instead
+            // For now, just verify the event was processed successfully
+        }
+    }
+
+    #[tokio::test]
+    async fn app_controller_should_use_unified_command_system() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        if crossterm::terminal::size().is_ok() {
+            let cmd_args = CommandLineArgs::parse_from(["test"]);
+            let config = AppConfig::from_args(cmd_args);
+            let mut controller = AppController::with_io_streams(
+                config,
+                crate::repl::io::TerminalEventStream::new(),
+                crate::repl::io::TerminalRenderStream::new(),
+            )
+            .unwrap();
+
+            // Verify unified command registry is initialized
+            assert!(controller.unified_command_registry.command_count() > 0);
+
+            // Test 'y' key in Normal mode - should fall back to old system (no unified command)
+            let y_key = crossterm::event::KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
+            let result = controller.handle_key_event_with_unified_first(y_key).await;
+            assert!(
+                result.is_ok(),
+                "Unified command system should handle key events gracefully"
+            );
+
+            // Verify old system handled it (y in Normal mode goes to YPrefix mode)
+            assert_eq!(controller.view_model().get_mode(), EditorMode::YPrefix);
+        }
+    }
+
+    #[tokio::test]
+    async fn zz_should_write_modified_request_then_quit() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        if crossterm::terminal::size().is_ok() {
+            let cmd_args = CommandLineArgs::parse_from(["test"]);
+            let config = AppConfig::from_args(cmd_args);
+            let mut controller = AppController::with_io_streams(
+                config,
+                crate::repl::io::TerminalEventStream::new(),
+                crate::repl::io::TerminalRenderStream::new(),
+            )
+            .unwrap();
+
+            let temp_dir = tempfile::tempdir().unwrap();
+            let path = temp_dir.path().join("request.http");
+            controller
+                .view_model
+                .set_request_file(Some(path.to_str().unwrap().to_string()));
+            controller
+                .view_model
+                .change_mode(EditorMode::Insert)
+                .unwrap();
+            controller.view_model.insert_text("GET /users").unwrap();
+            controller
+                .view_model
+                .change_mode(EditorMode::Normal)
+                .unwrap();
+
+            let z_key = crossterm::event::KeyEvent::new(KeyCode::Char('Z'), KeyModifiers::SHIFT);
+            controller.process_key_event(z_key).await.unwrap();
+            assert!(!controller.should_quit);
+            controller.process_key_event(z_key).await.unwrap();
+
+            assert!(controller.should_quit);
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), "GET /users\n");
+        }
+    }
+
+    #[tokio::test]
+    async fn pending_keys_should_show_operator_until_motion_completes() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        if crossterm::terminal::size().is_ok() {
+            let cmd_args = CommandLineArgs::parse_from(["test"]);
+            let config = AppConfig::from_args(cmd_args);
+            let mut controller = AppController::with_io_streams(
+                config,
+                crate::repl::io::TerminalEventStream::new(),
+                crate::repl::io::TerminalRenderStream::new(),
+            )
+            .unwrap();
+
+            controller
+                .view_model
+                .change_mode(EditorMode::Insert)
+                .unwrap();
+            controller.view_model.insert_text("GET /users").unwrap();
+            controller
+                .view_model
+                .change_mode(EditorMode::Normal)
+                .unwrap();
+
+            let d_key = crossterm::event::KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE);
+            controller.process_key_event(d_key).await.unwrap();
+            assert_eq!(controller.view_model.get_pending_keys(), "d");
+
+            controller.process_key_event(d_key).await.unwrap();
+            assert_eq!(controller.view_model.get_pending_keys(), "");
+        }
+    }
+
+    #[tokio::test]
+    async fn command_window_should_re_dispatch_selected_history_entry() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        if crossterm::terminal::size().is_ok() {
+            let cmd_args = CommandLineArgs::parse_from(["test"]);
+            let config = AppConfig::from_args(cmd_args);
+            let mut controller = AppController::with_io_streams(
+                config,
+                crate::repl::io::TerminalEventStream::new(),
+                crate::repl::io::TerminalRenderStream::new(),
+            )
+            .unwrap();
+            controller
+                .view_model
+                .set_request_text("GET /users")
+                .unwrap();
+
+            let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+            for command in [":set wrap on", ":set wrap off"] {
+                for ch in command.chars() {
+                    controller
+                        .process_key_event(key(KeyCode::Char(ch)))
+                        .await
+                        .unwrap();
+                }
+                controller
+                    .process_key_event(key(KeyCode::Enter))
+                    .await
+                    .unwrap();
+            }
+            assert!(!controller.view_model.pane_manager().is_wrap_enabled());
+
+            // q: lists the history with the cursor on the latest command
+            controller
+                .process_key_event(key(KeyCode::Char('q')))
+                .await
+                .unwrap();
+            controller
+                .process_key_event(key(KeyCode::Char(':')))
+                .await
+                .unwrap();
+            assert!(controller.view_model.is_command_window_open());
+            assert_eq!(
+                controller.view_model.get_request_text(),
+                "set wrap on\nset wrap off"
+            );
+
+            // Select "set wrap on" and run it again
+            controller
+                .process_key_event(key(KeyCode::Char('k')))
+                .await
+                .unwrap();
+            controller
+                .process_key_event(key(KeyCode::Enter))
+                .await
+                .unwrap();
+
+            assert!(!controller.view_model.is_command_window_open());
+            assert!(controller.view_model.pane_manager().is_wrap_enabled());
+            assert_eq!(controller.view_model.get_request_text(), "GET /users");
+            assert_eq!(
+                controller.view_model.get_ex_command_history(),
+                ["set wrap on", "set wrap off", "set wrap on"]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn ctrl_r_should_insert_file_name_and_last_ex_command() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        if crossterm::terminal::size().is_ok() {
+            let cmd_args = CommandLineArgs::parse_from(["test"]);
+            let config = AppConfig::from_args(cmd_args);
+            let mut controller = AppController::with_io_streams(
+                config,
+                crate::repl::io::TerminalEventStream::new(),
+                crate::repl::io::TerminalRenderStream::new(),
+            )
+            .unwrap();
+            controller
+                .view_model
+                .set_request_file(Some("api.http".to_string()));
+
+            let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+            for ch in ":set wrap on".chars() {
+                controller
+                    .process_key_event(key(KeyCode::Char(ch)))
+                    .await
+                    .unwrap();
+            }
+            controller
+                .process_key_event(key(KeyCode::Enter))
+                .await
+                .unwrap();
+
+            let ctrl_r = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL);
+            for key_event in [
+                key(KeyCode::Char('i')),
+                ctrl_r,
+                key(KeyCode::Char('%')),
+                key(KeyCode::Char(' ')),
+                ctrl_r,
+                key(KeyCode::Char(':')),
+            ] {
+                controller.process_key_event(key_event).await.unwrap();
+            }
+
+            assert_eq!(
+                controller.view_model.get_request_text(),
+                "api.http set wrap on"
+            );
+            assert!(controller
+                .services
+                .yank
+                .yank_to_register('%', "x".to_string(), NewYankType::Character)
+                .is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn ctrl_r_should_insert_unnamed_and_named_registers_mid_line() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        if crossterm::terminal::size().is_ok() {
+            let cmd_args = CommandLineArgs::parse_from(["test"]);
+            let config = AppConfig::from_args(cmd_args);
+            let mut controller = AppController::with_io_streams(
+                config,
+                crate::repl::io::TerminalEventStream::new(),
+                crate::repl::io::TerminalRenderStream::new(),
+            )
+            .unwrap();
+            controller
+                .services
+                .yank
+                .yank_text("v2/".to_string())
+                .unwrap();
+            controller
+                .services
+                .yank
+                .yank_to_register('a', "api/".to_string(), NewYankType::Character)
+                .unwrap();
+            controller
+                .view_model
+                .set_request_text("GET /users")
+                .unwrap();
+            controller
+                .view_model
+                .set_cursor_position(LogicalPosition::new(0, 5))
+                .unwrap();
+
+            let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+            let ctrl_r = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL);
+            for key_event in [key(KeyCode::Char('i')), ctrl_r] {
+                controller.process_key_event(key_event).await.unwrap();
+            }
+            assert_eq!(
+                controller.view_model.get_mode(),
+                EditorMode::InsertRegisterPrefix
+            );
+
+            controller
+                .process_key_event(key(KeyCode::Char('"')))
+                .await
+                .unwrap();
+            assert_eq!(controller.view_model.get_request_text(), "GET /v2/users");
+            assert_eq!(
+                controller.view_model.get_cursor_position(),
+                LogicalPosition::new(0, 8)
+            );
+            assert_eq!(controller.view_model.get_mode(), EditorMode::Insert);
+
+            for key_event in [ctrl_r, key(KeyCode::Char('a'))] {
+                controller.process_key_event(key_event).await.unwrap();
+            }
+            assert_eq!(
+                controller.view_model.get_request_text(),
+                "GET /v2/api/users"
+            );
+            assert_eq!(controller.view_model.get_mode(), EditorMode::Insert);
+        }
+    }
+
+    #[tokio::test]
+    async fn colon_in_visual_mode_should_prefill_range_for_deleting_selected_lines() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        if crossterm::terminal::size().is_ok() {
+            let cmd_args = CommandLineArgs::parse_from(["test"]);
+            let config = AppConfig::from_args(cmd_args);
+            let mut controller = AppController::with_io_streams(
+                config,
+                crate::repl::io::TerminalEventStream::new(),
+                crate::repl::io::TerminalRenderStream::new(),
+            )
+            .unwrap();
+            controller
+                .view_model
+                .change_mode(EditorMode::Insert)
+                .unwrap();
+            controller
+                .view_model
+                .insert_text("GET /users\nAccept: */*\nX-Debug: 1\n\n{}")
+                .unwrap();
+            controller
+                .view_model
+                .change_mode(EditorMode::Normal)
+                .unwrap();
+            controller
+                .view_model
+                .set_cursor_position(LogicalPosition::new(1, 3))
+                .unwrap();
+
+            let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+            for ch in "vj:".chars() {
+                controller
+                    .process_key_event(key(KeyCode::Char(ch)))
+                    .await
+                    .unwrap();
+            }
+            assert_eq!(controller.view_model.get_mode(), EditorMode::Command);
+            assert_eq!(controller.view_model.get_ex_command_buffer(), "'<,'>");
+
+            controller
+                .process_key_event(key(KeyCode::Char('d')))
+                .await
+                .unwrap();
+            controller
+                .process_key_event(key(KeyCode::Enter))
+                .await
+                .unwrap();
+
+            assert_eq!(controller.view_model.get_request_text(), "GET /users\n\n{}");
+            assert_eq!(
+                controller.services.yank.paste_text(),
+                Some("Accept: */*\nX-Debug: 1\n".to_string())
+            );
+            assert_eq!(
+                controller.view_model.get_cursor_position(),
+                LogicalPosition::new(1, 0)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn ranged_delete_and_yank_should_fill_registers() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        if crossterm::terminal::size().is_ok() {
+            let cmd_args = CommandLineArgs::parse_from(["test"]);
+            let config = AppConfig::from_args(cmd_args);
+            let mut controller = AppController::with_io_streams(
+                config,
+                crate::repl::io::TerminalEventStream::new(),
+                crate::repl::io::TerminalRenderStream::new(),
+            )
+            .unwrap();
+            controller
+                .view_model
+                .change_mode(EditorMode::Insert)
+                .unwrap();
+            controller
+                .view_model
+                .insert_text("line 1\nline 2\nline 3\nline 4\nline 5")
+                .unwrap();
+            controller
+                .view_model
+                .change_mode(EditorMode::Normal)
+                .unwrap();
+
+            let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+            for command in [":1,3d", ":.,$y a"] {
+                for ch in command.chars() {
+                    controller
+                        .process_key_event(key(KeyCode::Char(ch)))
+                        .await
+                        .unwrap();
+                }
+                controller
+                    .process_key_event(key(KeyCode::Enter))
+                    .await
+                    .unwrap();
+            }
+
+            assert_eq!(controller.view_model.get_request_text(), "line 4\nline 5");
+            assert_eq!(
+                controller.services.yank.paste_text(),
+                Some("line 1\nline 2\nline 3\n".to_string())
+            );
+            assert_eq!(
+                controller
+                    .services
+                    .yank
+                    .register('a')
+                    .map(|entry| entry.text),
+                Some("line 4\nline 5\n".to_string())
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn save_should_write_full_body_of_truncated_response() {
+        if crossterm::terminal::size().is_ok() {
+            let cmd_args = CommandLineArgs::parse_from(["test"]);
+            let config = AppConfig::from_args(cmd_args);
+            let mut controller = AppController::with_io_streams(
+                config,
+                crate::repl::io::TerminalEventStream::new(),
+                crate::repl::io::TerminalRenderStream::new(),
+            )
+            .unwrap();
+            let temp_dir = tempfile::tempdir().unwrap();
+            let path = temp_dir.path().join("body.txt");
+            let path = path.to_str().unwrap();
+            let body = "x".repeat(64);
+
+            let events = controller.run_ex_command("set maxresponse=16").unwrap();
+            controller.apply_ex_command_events(events).unwrap();
+            controller.view_model.set_response(200, body.clone());
+            assert_eq!(
+                controller.view_model.get_response_text(),
+                format!("{}\n… (truncated, 64 bytes total)", "x".repeat(16))
+            );
+
+            let events = controller.run_ex_command(&format!("save {path}")).unwrap();
+            controller.apply_ex_command_events(events).unwrap();
+            assert_eq!(std::fs::read_to_string(path).unwrap(), body);
+        }
+    }
+
+    #[tokio::test]
+    async fn pending_g_should_resolve_to_normal_after_timeoutlen() {
+        use crate::repl::services::clock::ManualClock;
+        use crate::repl::services::KeyTimeoutService;
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        if crossterm::terminal::size().is_ok() {
+            let cmd_args = CommandLineArgs::parse_from(["test"]);
+            let config = AppConfig::from_args(cmd_args);
+            let mut controller = AppController::with_io_streams(
+                config,
+                crate::repl::io::TerminalEventStream::new(),
+                crate::repl::io::TerminalRenderStream::new(),
+            )
+            .unwrap();
+            let clock = ManualClock::new();
+            controller.services.key_timeout =
+                KeyTimeoutService::with_clock(Box::new(clock.clone()));
+            controller
+                .handle_setting_change(Setting::TimeoutLen, SettingValue::Number(300))
+                .unwrap();
+
+            controller
+                .process_key_event(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE))
+                .await
+                .unwrap();
+            assert_eq!(controller.view_model.get_mode(), EditorMode::GPrefix);
+
+            clock.advance(Duration::from_millis(299));
+            controller.expire_pending_key_sequence().unwrap();
+            assert_eq!(controller.view_model.get_mode(), EditorMode::GPrefix);
+
+            clock.advance(Duration::from_millis(1));
+            controller.expire_pending_key_sequence().unwrap();
+            assert_eq!(controller.view_model.get_mode(), EditorMode::Normal);
+            assert_eq!(controller.view_model.get_pending_keys(), "");
+            assert!(!controller.services.key_timeout.is_pending());
+        }
+    }
+
+    #[tokio::test]
+    async fn zq_should_quit_without_writing() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        if crossterm::terminal::size().is_ok() {
+            let cmd_args = CommandLineArgs::parse_from(["test"]);
+            let config = AppConfig::from_args(cmd_args);
+            let mut controller = AppController::with_io_streams(
+                config,
+                crate::repl::io::TerminalEventStream::new(),
+                crate::repl::io::TerminalRenderStream::new(),
+            )
+            .unwrap();
+
+            let temp_dir = tempfile::tempdir().unwrap();
+            let path = temp_dir.path().join("request.http");
+            controller
+                .view_model
+                .set_request_file(Some(path.to_str().unwrap().to_string()));
+            controller
+                .view_model
+                .change_mode(EditorMode::Insert)
+                .unwrap();
+            controller.view_model.insert_text("GET /users").unwrap();
+            controller
+                .view_model
+                .change_mode(EditorMode::Normal)
+                .unwrap();
+
+            let z_key = crossterm::event::KeyEvent::new(KeyCode::Char('Z'), KeyModifiers::SHIFT);
+            let q_key = crossterm::event::KeyEvent::new(KeyCode::Char('Q'), KeyModifiers::SHIFT);
+            controller.process_key_event(z_key).await.unwrap();
+            controller.process_key_event(q_key).await.unwrap();
+
+            assert!(controller.should_quit);
+            assert!(!path.exists());
+        }
+    }
+
+    #[tokio::test]
+    async fn yankresponse_should_fill_unnamed_register_with_response_body() {
+        if crossterm::terminal::size().is_ok() {
+            let cmd_args = CommandLineArgs::parse_from(["test"]);
+            let config = AppConfig::from_args(cmd_args);
+            let mut controller = AppController::with_io_streams(
+                config,
+                crate::repl::io::TerminalEventStream::new(),
+                crate::repl::io::TerminalRenderStream::new(),
+            )
+            .unwrap();
+
+            let body = "{\n  \"users\": []\n}";
+            controller.view_model.set_response(200, body.to_string());
+            controller
+                .apply_command_event(CommandEvent::YankResponseRequested)
+                .await
+                .unwrap();
+
+            let entry = controller.services.yank.register('"').unwrap();
+            assert_eq!(entry.text, body);
+            assert_eq!(
+                controller.view_model.get_status_message(),
+                Some("3 lines of response yanked")
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn yankvalue_should_yank_json_value_under_cursor() {
+        let mut controller = create_mock_controller();
+        let body = "{\n  \"email\": \"a@example.com\",\n  \"age\": 42\n}";
+        controller.view_model.set_response(200, body.to_string());
+        controller.view_model.switch_to_response_pane();
+
+        for (position, value) in [
+            (LogicalPosition::new(1, 14), "a@example.com"),
+            (LogicalPosition::new(2, 10), "42"),
+        ] {
+            controller.view_model.set_cursor_position(position).unwrap();
+            let events = controller.run_ex_command("yankvalue").unwrap();
+            controller.apply_ex_command_events(events).unwrap();
+
+            let entry = controller.services.yank.register('"').unwrap();
+            assert_eq!(entry.text, value);
+        }
+        assert_eq!(
+            controller.view_model.get_status_message(),
+            Some("2 characters yanked")
+        );
+    }
+
+    #[tokio::test]
+    async fn typed_keys_waiting_in_one_batch_should_update_display_cache_once() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        let key = |ch| Event::Key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        let mut event_stream = crate::repl::io::MockEventStream::empty();
+        event_stream.push_event(key('i'));
+        for ch in "hi there".chars() {
+            event_stream.push_event(key(ch));
+        }
+
+        let cmd_args = CommandLineArgs::parse_from(["test"]);
+        let config = AppConfig::from_args(cmd_args);
+        let mut controller = AppController::with_io_streams(
+            config,
+            event_stream,
+            crate::repl::io::MockRenderStream::with_size((80, 24)),
+        )
+        .unwrap();
+
+        controller.process_next_event().await.unwrap();
+        assert_eq!(controller.view_model().get_mode(), EditorMode::Insert);
+        let updates_before = controller
+            .view_model()
+            .pane_manager()
+            .display_cache_update_count(Pane::Request);
+
+        controller.process_next_event().await.unwrap();
+
+        assert_eq!(controller.view_model().get_request_text(), "hi there");
+        assert_eq!(
+            controller
+                .view_model()
+                .pane_manager()
+                .display_cache_update_count(Pane::Request),
+            updates_before + 1
+        );
+        let cursor = controller.view_model().get_cursor_position();
+        assert_eq!((cursor.line, cursor.column), (0, 8));
+    }
+
+    #[tokio::test]
+    async fn keys_should_be_handled_while_http_request_is_in_flight() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        let key = |ch| Event::Key(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        let mut event_stream = crate::repl::io::MockEventStream::empty();
+        event_stream.push_event(key('i'));
+        event_stream.push_event(key('x'));
+
+        let cmd_args = CommandLineArgs::parse_from(["test"]);
+        let config = AppConfig::from_args(cmd_args);
+        let mut controller = AppController::with_io_streams(
+            config,
+            event_stream,
+            crate::repl::io::MockRenderStream::with_size((80, 24)),
+        )
+        .unwrap();
+
+        // A slow request stands in for a server that takes a while to answer
+        controller.view_model.set_executing_request(true);
+        controller
+            .services
+            .http
+            .as_ref()
+            .unwrap()
+            .spawn_request(async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                HttpResponseMessage::Error {
+                    error: AppError::Timeout("deadline elapsed".to_string()),
+                }
+            });
+
+        controller.process_next_event().await.unwrap();
+        controller.process_next_event().await.unwrap();
+        assert_eq!(controller.view_model().get_request_text(), "x");
+        assert!(controller.view_model().is_executing_request());
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        controller.process_next_event().await.unwrap();
+        assert!(!controller.view_model().is_executing_request());
+        assert_eq!(
+            controller.view_model().get_response_text(),
+            "Request timed out: deadline elapsed"
+        );
+    }
+
+    #[tokio::test]
+    async fn chain_capture_should_be_substituted_into_next_request_header() {
+        let mut controller = create_mock_controller();
+        let http_service = controller.services.http.as_mut().unwrap();
+        http_service.spawn_request(async {
+            HttpResponseMessage::Captured {
+                variable: "AUTH".to_string(),
+                path: ".token".to_string(),
+                value: "abc123".to_string(),
+            }
+        });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        controller.process_next_event().await.unwrap();
+        assert_eq!(
+            controller.view_model().get_status_message(),
+            Some("Captured .token into ${AUTH}")
+        );
+        assert_eq!(controller.view_model().get_current_pane(), Pane::Request);
+
+        // The header line in the buffer is what the next send uses
+        controller
+            .view_model_mut()
+            .set_request_text("GET http://example.com/users\nAuthorization: Bearer ${AUTH}\n\n{}")
+            .unwrap();
+        controller.handle_dry_run().unwrap();
+        assert_eq!(
+            controller.view_model().get_status_message(),
+            Some("GET http://example.com/users [Authorization: Bearer abc123]")
+        );
+    }
+
+    #[tokio::test]
+    async fn resize_during_http_request_should_relayout_and_keep_response() {
+        let mut event_stream = crate::repl::io::MockEventStream::empty();
+        event_stream.push_event(Event::Resize(100, 30));
+
+        let cmd_args = CommandLineArgs::parse_from(["test"]);
+        let config = AppConfig::from_args(cmd_args);
+        let mut controller = AppController::with_io_streams(
+            config,
+            event_stream,
+            crate::repl::io::MockRenderStream::with_size((80, 24)),
+        )
+        .unwrap();
+
+        controller.view_model.set_executing_request(true);
+        controller
+            .services
+            .http
+            .as_ref()
+            .unwrap()
+            .spawn_request(async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                HttpResponseMessage::Error {
+                    error: AppError::Network("connection reset".to_string()),
+                }
+            });
+
+        controller.process_next_event().await.unwrap();
+        assert!(controller.view_model().is_executing_request());
+        assert_eq!(controller.view_model().terminal_size(), (100, 30));
+        assert_eq!(
+            controller.view_model().pane_manager().request_pane_height(),
+            29
+        );
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        controller.process_next_event().await.unwrap();
+        assert!(!controller.view_model().is_executing_request());
+        assert_eq!(
+            controller.view_model().get_response_text(),
+            "Request failed: connection reset"
+        );
+        let pane_manager = controller.view_model().pane_manager();
+        assert_eq!(pane_manager.request_pane_height(), 15);
+        assert_eq!(pane_manager.response_pane_height(true), 13);
+    }
+
+    #[tokio::test]
+    async fn autowrite_should_write_dirty_buffer_before_executing_request() {
+        use crossterm::event::KeyModifiers;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("request.http");
+        let path_str = path.to_str().unwrap().to_string();
+
+        let mut event_stream = crate::repl::io::MockEventStream::empty();
+        event_stream.push_event(Event::Key(KeyEvent::new(
+            KeyCode::Enter,
+            KeyModifiers::NONE,
+        )));
+        let cmd_args = CommandLineArgs::parse_from(["test"]);
+        let config = AppConfig::from_args(cmd_args);
+        let mut controller = AppController::with_io_streams(
+            config,
+            event_stream,
+            crate::repl::io::MockRenderStream::with_size((80, 24)),
+        )
+        .unwrap();
+
+        controller
+            .view_model
+            .set_request_text("GET http://127.0.0.1:9/users")
+            .unwrap();
+        controller
+            .view_model
+            .set_request_file(Some(path_str.clone()));
+        controller.view_model.set_request_modified(true);
+        let events = controller.run_ex_command("set autowrite on").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+
+        controller.process_next_event().await.unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "GET http://127.0.0.1:9/users\n"
+        );
+        assert!(!controller.view_model.is_request_modified());
+        assert!(controller.view_model.is_executing_request());
+    }
+
+    #[tokio::test]
+    async fn autowrite_should_skip_buffer_without_file_name() {
+        let mut controller = create_mock_controller();
+        controller
+            .view_model
+            .set_request_text("GET /users")
+            .unwrap();
+        controller.view_model.set_request_modified(true);
+        let events = controller.run_ex_command("set autowrite on").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+
+        controller
+            .apply_command_event(CommandEvent::PaneSwitchRequested {
+                target_pane: Pane::Response,
+            })
+            .await
+            .unwrap();
+
+        assert!(controller.view_model.is_request_modified());
+        assert_eq!(controller.view_model.get_current_pane(), Pane::Response);
+    }
+
+    #[tokio::test]
+    async fn focus_gained_should_reload_request_file_changed_elsewhere() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("request.http");
+        let path_str = path.to_str().unwrap().to_string();
+
+        let mut event_stream = crate::repl::io::MockEventStream::empty();
+        event_stream.push_event(Event::FocusGained);
+        let cmd_args = CommandLineArgs::parse_from(["test"]);
+        let config = AppConfig::from_args(cmd_args);
+        let mut controller = AppController::with_io_streams(
+            config,
+            event_stream,
+            crate::repl::io::MockRenderStream::with_size((80, 24)),
+        )
+        .unwrap();
+
+        controller
+            .view_model
+            .change_mode(EditorMode::Insert)
+            .unwrap();
+        controller.view_model.insert_text("GET /users").unwrap();
+        controller
+            .view_model
+            .change_mode(EditorMode::Normal)
+            .unwrap();
+        controller.handle_write_buffer(&path_str, false).unwrap();
+        controller
+            .handle_setting_change(Setting::AutoRead, SettingValue::On)
+            .unwrap();
+
+        // Another editor rewrites the file a little later
+        std::fs::write(&path, "GET /orders\n").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+
+        controller.process_next_event().await.unwrap();
+
+        assert_eq!(controller.view_model().get_request_text(), "GET /orders");
+        assert!(!controller.view_model().is_request_modified());
+        assert_eq!(
+            controller.view_model().get_status_message(),
+            Some(format!("\"{path_str}\" reloaded").as_str())
+        );
+    }
+
+    fn create_mock_controller(
+    ) -> AppController<crate::repl::io::MockEventStream, crate::repl::io::MockRenderStream> {
+        let cmd_args = CommandLineArgs::parse_from(["test"]);
+        let config = AppConfig::from_args(cmd_args);
+        AppController::with_io_streams(
+            config,
+            crate::repl::io::MockEventStream::empty(),
+            crate::repl::io::MockRenderStream::with_size((80, 24)),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn equal_equal_should_lay_out_json_line_with_internal_formatter() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        let mut controller = create_mock_controller();
+        controller
+            .view_model
+            .set_request_text("POST /users\n\n{\"name\":\"a\",\"tags\":[1,2]}")
+            .unwrap();
+        controller
+            .view_model
+            .set_cursor_position(LogicalPosition::new(2, 0))
+            .unwrap();
+
+        let equal = KeyEvent::new(KeyCode::Char('='), KeyModifiers::NONE);
+        controller.process_key_event(equal).await.unwrap();
+        assert_eq!(controller.view_model.get_mode(), EditorMode::EqualPrefix);
+        controller.process_key_event(equal).await.unwrap();
+
+        assert_eq!(
+            controller.view_model().get_request_text(),
+            "POST /users\n\n{\n\t\"name\": \"a\",\n\t\"tags\": [\n\t\t1,\n\t\t2\n\t]\n}"
+        );
+        assert_eq!(controller.view_model.get_mode(), EditorMode::Normal);
+        assert_eq!(
+            controller.view_model().get_cursor_position(),
+            LogicalPosition::new(2, 0)
+        );
+        assert_eq!(
+            controller.view_model().get_status_message(),
+            Some("7 lines indented")
+        );
+    }
+
+    #[tokio::test]
+    async fn shift_keys_should_indent_count_lines_and_repeat_with_dot() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        let mut controller = create_mock_controller();
+        controller
+            .view_model
+            .set_request_text("{\n\"a\": 1,\n\"b\": 2\n}")
+            .unwrap();
+        controller
+            .view_model
+            .set_cursor_position(LogicalPosition::new(1, 0))
+            .unwrap();
+        let key = |ch| KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE);
+
+        for ch in ['2', '>'] {
+            controller.process_key_event(key(ch)).await.unwrap();
+        }
+        assert_eq!(controller.view_model.get_mode(), EditorMode::IndentPrefix);
+        controller.process_key_event(key('>')).await.unwrap();
+
+        assert_eq!(
+            controller.view_model().get_request_text(),
+            "{\n\t\"a\": 1,\n\t\"b\": 2\n}"
+        );
+        assert_eq!(controller.view_model.get_mode(), EditorMode::Normal);
+        assert_eq!(
+            controller.view_model().get_status_message(),
+            Some("2 lines >ed 1 time")
+        );
+
+        controller.process_key_event(key('.')).await.unwrap();
+        assert_eq!(
+            controller.view_model().get_request_text(),
+            "{\n\t\t\"a\": 1,\n\t\t\"b\": 2\n}"
+        );
+
+        for ch in ['<', '<'] {
+            controller.process_key_event(key(ch)).await.unwrap();
+        }
+        assert_eq!(
+            controller.view_model().get_request_text(),
+            "{\n\t\"a\": 1,\n\t\t\"b\": 2\n}"
+        );
+        assert_eq!(
+            controller.view_model().get_cursor_position(),
+            LogicalPosition::new(1, 1)
+        );
+    }
+
+    #[tokio::test]
+    async fn equal_g_should_run_equalprg_from_cursor_to_last_line() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        let mut controller = create_mock_controller();
+        controller
+            .view_model
+            .set_request_text("POST /users\n\nname\ntags")
+            .unwrap();
+        controller
+            .view_model
+            .set_cursor_position(LogicalPosition::new(2, 0))
+            .unwrap();
+        let events = controller
+            .run_ex_command("set equalprg=tr\\ a-z\\ A-Z")
+            .unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+
+        controller
+            .process_key_event(KeyEvent::new(KeyCode::Char('='), KeyModifiers::NONE))
+            .await
+            .unwrap();
+        controller
+            .process_key_event(KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            controller.view_model().get_request_text(),
+            "POST /users\n\nNAME\nTAGS"
+        );
+        assert_eq!(
+            controller.view_model().get_status_message(),
+            Some("2 lines indented")
+        );
+    }
+
+    #[test]
+    fn set_shell_should_fall_back_to_default_when_emptied() {
+        let mut controller = create_mock_controller();
+        assert_eq!(controller.view_model.get_shell(), filter::default_shell());
+
+        for (command, shell) in [
+            (
+                "set shell=/usr/local/bin/fish",
+                "/usr/local/bin/fish".to_string(),
+            ),
+            ("set shell=", filter::default_shell()),
+        ] {
+            let events = controller.run_ex_command(command).unwrap();
+            controller.apply_ex_command_events(events).unwrap();
+            assert_eq!(controller.view_model.get_shell(), shell);
         }
+    }
 
-        tracing::debug!(
-            "Multi-cursor text delete: {} chars in direction {:?} at {} positions, start columns: {:?}",
-            amount,
-            direction,
-            cursor_positions.len(),
-            start_columns
+    #[test]
+    fn fileencoding_should_round_trip_latin1_file_through_edit_and_write() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("fixture.http");
+        std::fs::write(&path, b"GET /caf\xe9\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        let mut controller = create_mock_controller();
+        for command in ["set fileencoding=latin1", &format!("e {path}")] {
+            let events = controller.run_ex_command(command).unwrap();
+            controller.apply_ex_command_events(events).unwrap();
+        }
+        assert_eq!(controller.view_model().get_request_text(), "GET /caf\u{e9}");
+
+        controller
+            .view_model
+            .set_request_text("GET /caf\u{e9}/cr\u{e8}me")
+            .unwrap();
+        let events = controller.run_ex_command(&format!("w {path}")).unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        assert_eq!(std::fs::read(path).unwrap(), b"GET /caf\xe9/cr\xe8me\n");
+
+        // Read as UTF-8 the same bytes are invalid and get replaced
+        for command in ["set fileencoding=", &format!("e! {path}")] {
+            let events = controller.run_ex_command(command).unwrap();
+            controller.apply_ex_command_events(events).unwrap();
+        }
+        assert_eq!(
+            controller.view_model().get_request_text(),
+            "GET /caf\u{fffd}/cr\u{fffd}me"
         );
+        assert!(controller
+            .view_model()
+            .get_status_message()
+            .is_some_and(|message| message.contains("[ILLEGAL BYTE replaced]")));
 
-        // Perform deletion at each cursor position, respecting boundaries
-        // We need to process in reverse order to maintain position validity
-        for (i, position) in cursor_positions.iter().enumerate().rev() {
-            let start_column = start_columns.get(i).copied().unwrap_or(0);
+        let events = controller
+            .run_ex_command("set fileencoding=klingon")
+            .unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        assert_eq!(controller.view_model().get_fileencoding(), "utf-8");
+    }
 
-            // Temporarily set cursor to this position
-            self.view_model.set_cursor_position(*position)?;
+    #[test]
+    fn registers_should_list_named_and_numbered_registers_with_previews() {
+        let mut controller = create_mock_controller();
+        controller
+            .view_model
+            .set_request_text("GET /users\nAccept:\t*/*\nX-Trace: 1")
+            .unwrap();
 
-            // For left deletion (backspace), respect the Visual Block start boundary
-            let effective_amount = if direction == MovementDirection::Left {
-                // Calculate how many characters we can actually delete without going beyond start
-                let current_col = position.column;
-                let max_deletable = current_col.saturating_sub(start_column);
-                let effective = amount.min(max_deletable);
-                tracing::debug!(
-                    "Backspace calculation: line={}, current_col={}, start_col={}, max_deletable={}, requested={}, effective={}",
-                    position.line, current_col, start_column, max_deletable, amount, effective
-                );
-                effective
-            } else {
-                amount
-            };
+        for command in ["1y a", "2d"] {
+            let events = controller.run_ex_command(command).unwrap();
+            controller.apply_ex_command_events(events).unwrap();
+        }
+        assert_eq!(
+            controller.view_model().get_request_text(),
+            "GET /users\nX-Trace: 1"
+        );
 
-            for _ in 0..effective_amount {
-                match direction {
-                    MovementDirection::Left => {
-                        self.view_model.delete_char_before_cursor()?;
-                    }
-                    MovementDirection::Right => {
-                        self.view_model.delete_char_after_cursor()?;
-                    }
-                    _ => {
-                        tracing::warn!("Unsupported delete direction: {:?}", direction);
-                        break;
-                    }
-                }
-            }
+        let events = controller.run_ex_command("registers").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        let lines = controller.view_model().get_scratch().unwrap().to_vec();
+        assert_eq!(lines[0], "--- Registers ---");
+        assert!(lines.contains(&"\"\"   Accept:^I*/*^J".to_string()));
+        assert!(lines.contains(&"\"1   Accept:^I*/*^J".to_string()));
+        assert!(lines.contains(&"\"a   GET /users^J".to_string()));
+        assert!(!lines.iter().any(|line| line.starts_with("\"2")));
+
+        let events = controller.run_ex_command("reg a").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        assert_eq!(
+            controller.view_model().get_scratch().unwrap(),
+            ["--- Registers ---", "\"a   GET /users^J"]
+        );
+    }
 
-            tracing::debug!(
-                "Line {}: deleted {} chars (requested: {}, start_column: {}, current: {})",
-                position.line,
-                effective_amount,
-                amount,
-                start_column,
-                position.column
-            );
-        }
+    #[test]
+    fn source_should_run_script_and_stop_at_first_error_line() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let script = temp_dir.path().join("setup");
+        std::fs::write(
+            &script,
+            "\" Shared settings\n:set whichwrap=h,l\n\nset fileencoding=latin1\nset nosuchthing\nset shell=/bin/zsh\n",
+        )
+        .unwrap();
+        let script = script.to_str().unwrap();
+
+        let mut controller = create_mock_controller();
+        let shell = controller.view_model().get_shell().to_string();
+        let events = controller
+            .run_ex_command(&format!("source {script}"))
+            .unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+
+        assert_eq!(controller.view_model().get_whichwrap(), "h,l");
+        assert_eq!(controller.view_model().get_fileencoding(), "latin1");
+        assert_eq!(controller.view_model().get_shell(), shell);
+        assert_eq!(
+            controller.view_model().get_status_message(),
+            Some(
+                format!("Error: {script} line 5: E492: Not an editor command: set nosuchthing")
+                    .as_str()
+            )
+        );
 
-        // Update all cursor positions to reflect the deleted text
-        let updated_positions: Vec<LogicalPosition> = match direction {
-            MovementDirection::Left => {
-                // For backspace, cursor positions move left by amount actually deleted (respecting boundaries)
-                cursor_positions
-                    .iter()
-                    .enumerate()
-                    .map(|(i, pos)| {
-                        let start_column = start_columns.get(i).copied().unwrap_or(0);
-                        let current_col = pos.column;
-                        let max_deletable = current_col.saturating_sub(start_column);
-                        let effective_amount = amount.min(max_deletable);
-                        LogicalPosition::new(pos.line, pos.column.saturating_sub(effective_amount))
-                    })
-                    .collect()
-            }
-            MovementDirection::Right => {
-                // For forward delete, cursor positions stay the same
-                cursor_positions
-            }
-            _ => cursor_positions,
+        // Errors shown by a command stop the script too
+        std::fs::write(script, "set whichwrap=z\nset whichwrap=h\n").unwrap();
+        let events = controller.run_ex_command(&format!("so {script}")).unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        assert_eq!(controller.view_model().get_whichwrap(), "h,l");
+        assert!(controller
+            .view_model()
+            .get_status_message()
+            .is_some_and(|message| message.contains(" line 1: E474")));
+    }
+
+    #[test]
+    fn init_file_should_apply_on_startup_unless_skipped() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let init_path = temp_dir.path().join("init");
+        std::fs::write(&init_path, "set whichwrap=h,l\nbogus\nset equalprg=jq\n").unwrap();
+        let init_path = init_path.to_str().unwrap();
+        let start = |args: &[&str]| {
+            AppController::with_io_streams(
+                AppConfig::from_args(CommandLineArgs::parse_from(args)),
+                crate::repl::io::MockEventStream::empty(),
+                crate::repl::io::MockRenderStream::with_size((80, 24)),
+            )
+            .unwrap()
         };
 
-        // Set the primary cursor to the first position before updating positions
-        if let Some(first_pos) = updated_positions.first() {
-            self.view_model.set_cursor_position(*first_pos)?;
-        }
+        // The bad line is reported and the lines after it still run
+        let controller = start(&["test", "-u", init_path]);
+        assert_eq!(controller.view_model().get_whichwrap(), "h,l");
+        assert_eq!(controller.view_model().get_equalprg(), "jq");
+        assert!(controller
+            .view_model()
+            .message_history()
+            .iter()
+            .any(|message| message.contains("E492: Not an editor command: bogus")));
 
-        self.view_model
-            .update_visual_block_insert_cursors(updated_positions);
+        let controller = start(&["test", "-u", init_path, "--no-init"]);
+        assert_ne!(controller.view_model().get_whichwrap(), "h,l");
+        assert!(controller.view_model().message_history().is_empty());
+    }
 
-        tracing::debug!("Multi-cursor text delete completed, updated cursor positions");
-        Ok(())
+    #[test]
+    fn session_flag_should_restore_request_saved_by_mksession() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let request_path = temp_dir.path().join("users.http");
+        let session_path = temp_dir.path().join("Session.json");
+        std::fs::write(&request_path, "GET /users").unwrap();
+        let request_path = request_path.to_str().unwrap();
+        let session_path = session_path.to_str().unwrap();
+
+        let mut controller = create_mock_controller();
+        let events = controller
+            .run_ex_command(&format!("e {request_path}"))
+            .unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        controller
+            .view_model
+            .set_request_text("GET /users\nAccept: */*")
+            .unwrap();
+        controller
+            .view_model
+            .set_cursor_position(LogicalPosition::new(1, 4))
+            .unwrap();
+        let command = format!("mksession {session_path}");
+        let events = controller.run_ex_command(&command).unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+
+        // Saving again needs ! to replace the session
+        let events = controller.run_ex_command(&command).unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        assert!(controller
+            .view_model()
+            .get_status_message()
+            .is_some_and(|message| message.contains("E189")));
+
+        let cmd_args = CommandLineArgs::parse_from(["test", "--session", session_path]);
+        let restored = AppController::with_io_streams(
+            AppConfig::from_args(cmd_args),
+            crate::repl::io::MockEventStream::empty(),
+            crate::repl::io::MockRenderStream::with_size((80, 24)),
+        )
+        .unwrap();
+        let view_model = restored.view_model();
+        assert_eq!(view_model.get_request_text(), "GET /users\nAccept: */*");
+        assert_eq!(view_model.get_request_file(), Some(request_path));
+        assert!(view_model.is_request_modified());
+        assert_eq!(view_model.get_cursor_position(), LogicalPosition::new(1, 4));
     }
 
-    /// Handle pasting yanked text after cursor
-    fn handle_paste_after(&mut self) -> Result<()> {
-        // Get from YankService, not the old view_model buffer!
-        if let Some(yank_entry) = self.services.yank.paste() {
-            // Paste the text after the current cursor position using type-aware paste
-            self.view_model.paste_after_with_type(&yank_entry)?;
+    #[tokio::test]
+    async fn scratch_overlay_should_leave_response_untouched_when_closed() {
+        use crossterm::event::{KeyCode, KeyModifiers};
 
-            let char_count = yank_entry.text.chars().count();
-            let line_count = yank_entry.text.lines().count();
+        let mut controller = create_mock_controller();
+        let body = "{\n  \"id\": 1\n}";
+        controller.view_model.set_response(200, body.to_string());
 
-            // Clear any previous status message (e.g., "1 line yanked")
-            self.view_model.clear_status_message();
+        let events = controller.run_ex_command("set all").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        assert_eq!(
+            controller.view_model().get_scratch().unwrap()[0],
+            "--- Settings ---"
+        );
+        assert_eq!(controller.view_model().get_response_text(), body);
 
-            tracing::info!(
-                "Pasted {} characters ({} lines) after cursor as {:?}",
-                char_count,
-                line_count,
-                yank_entry.yank_type
-            );
-        } else {
-            self.view_model
-                .set_status_message("Nothing to paste".to_string());
-            tracing::warn!("No text in yank buffer to paste");
-        }
+        // Other keys leave the overlay up
+        controller
+            .process_key_event(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE))
+            .await
+            .unwrap();
+        assert!(controller.view_model().get_scratch().is_some());
 
-        Ok(())
+        controller
+            .process_key_event(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE))
+            .await
+            .unwrap();
+        assert!(controller.view_model().get_scratch().is_none());
+        assert_eq!(controller.view_model().get_response_text(), body);
+        assert_eq!(
+            controller.view_model().get_response_status_code(),
+            Some(200)
+        );
     }
 
-    /// Handle pasting yanked text at current cursor position
-    fn handle_paste_at_cursor(&mut self) -> Result<()> {
-        // Get from YankService, not the old view_model buffer!
-        if let Some(yank_entry) = self.services.yank.paste() {
-            tracing::debug!(
-                "Retrieved yank entry with type: {:?}, text length: {}",
-                yank_entry.yank_type,
-                yank_entry.text.len()
-            );
+    #[tokio::test]
+    async fn whichwrap_should_let_l_cross_line_end_only_when_flag_set() {
+        use crossterm::event::{KeyCode, KeyModifiers};
 
-            // Paste the text at current position (before cursor) using type-aware paste
-            self.view_model.paste_with_type(&yank_entry)?;
+        let mut controller = create_mock_controller();
+        controller
+            .view_model
+            .set_request_text("GET /users\nAccept: */*")
+            .unwrap();
+        controller
+            .view_model
+            .set_cursor_position(LogicalPosition::new(0, 9))
+            .unwrap();
+        let l = KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE);
 
-            let char_count = yank_entry.text.chars().count();
-            let line_count = yank_entry.text.lines().count();
+        controller.process_key_event(l).await.unwrap();
+        assert_eq!(
+            controller.view_model().get_cursor_position(),
+            LogicalPosition::new(0, 9)
+        );
 
-            // Clear any previous status message (e.g., "1 line yanked")
-            self.view_model.clear_status_message();
+        let events = controller.run_ex_command("set whichwrap=h,l").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        controller.process_key_event(l).await.unwrap();
+        assert_eq!(
+            controller.view_model().get_cursor_position(),
+            LogicalPosition::new(1, 0)
+        );
 
-            tracing::info!(
-                "Pasted {} characters ({} lines) at cursor as {:?}",
-                char_count,
-                line_count,
-                yank_entry.yank_type
-            );
-        } else {
-            self.view_model
-                .set_status_message("Nothing to paste".to_string());
-            tracing::warn!("No text in yank buffer to paste");
-        }
+        controller
+            .process_key_event(KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE))
+            .await
+            .unwrap();
+        assert_eq!(
+            controller.view_model().get_cursor_position(),
+            LogicalPosition::new(0, 9)
+        );
 
-        Ok(())
+        let events = controller.run_ex_command("set whichwrap=x").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        assert_eq!(controller.view_model().get_whichwrap(), "h,l");
     }
 
-    /// Process a single key event without running the full event loop (for testing)
-    pub async fn process_key_event(&mut self, key_event: KeyEvent) -> Result<()> {
-        tracing::debug!("Processing key event: {:?}", key_event);
-        tracing::debug!("AppController: process_key_event called with {key_event:?}");
+    #[tokio::test]
+    async fn edit_on_modified_buffer_should_honor_confirm_answers() {
+        use crossterm::event::{KeyCode, KeyModifiers};
 
-        // Create command context from current state
-        tracing::debug!("AppController: Creating command context");
-        let context = CommandContext::new(ViewModelSnapshot::from_view_model(&self.view_model));
-        tracing::debug!("AppController: Command context created");
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("orders.http");
+        std::fs::write(&path, "GET /orders\n").unwrap();
+        let path = path.to_str().unwrap();
 
-        // Process through command registry
-        tracing::debug!("AppController: About to call command_registry.process_event");
-        if let Ok(events) = self.command_registry.process_event(key_event, &context) {
-            tracing::debug!(
-                "AppController: Command events generated: {} events",
-                events.len()
-            );
-            tracing::debug!("Command events generated: {:?}", events);
-            if !events.is_empty() {
-                // Apply events to view model (this will emit appropriate ViewEvents)
-                tracing::debug!(
-                    "AppController: About to apply {} command events",
-                    events.len()
-                );
-                for (i, event) in events.iter().enumerate() {
-                    tracing::debug!(
-                        "AppController: Applying event {}/{}: {:?}",
-                        i + 1,
-                        events.len(),
-                        event
-                    );
-                    self.apply_command_event(event.clone()).await?;
-                    tracing::debug!(
-                        "AppController: Applied event {}/{} successfully",
-                        i + 1,
-                        events.len()
-                    );
-                }
-                tracing::debug!("AppController: All command events applied successfully");
+        let mut controller = create_mock_controller();
+        controller
+            .view_model
+            .set_request_text("GET /users")
+            .unwrap();
+        controller.view_model.set_request_modified(true);
+
+        // Without confirm the load is refused outright
+        let events = controller.run_ex_command(&format!("e {path}")).unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        assert_eq!(
+            controller.view_model().get_status_message(),
+            Some("Error: E37: No write since last change (add ! to override)")
+        );
 
-                // Render after processing key events
-                self.view_renderer.render_full(&self.view_model)?;
-            } else {
-                tracing::debug!("AppController: No command events generated");
-            }
-        } else {
-            tracing::warn!("AppController: Failed to process key event: {key_event:?}");
-        }
+        let events = controller.run_ex_command("set confirm on").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        let events = controller.run_ex_command(&format!("e {path}")).unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        assert_eq!(
+            controller.view_model().get_status_message(),
+            Some(format!("Discard changes and edit \"{path}\"? (y)es/(n)o/(a)ll: ").as_str())
+        );
 
-        tracing::debug!("AppController: process_key_event completed successfully");
-        Ok(())
+        // n cancels, leaving the buffer alone
+        controller
+            .process_key_event(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE))
+            .await
+            .unwrap();
+        assert_eq!(controller.view_model().get_request_text(), "GET /users");
+        assert!(controller.view_model().is_request_modified());
+        assert_eq!(controller.view_model().get_mode(), EditorMode::Normal);
+
+        // y goes ahead and loads the file
+        let events = controller.run_ex_command(&format!("e {path}")).unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        controller
+            .process_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE))
+            .await
+            .unwrap();
+        assert_eq!(controller.view_model().get_request_text(), "GET /orders");
+        assert!(!controller.view_model().is_request_modified());
+        assert_eq!(controller.view_model().get_request_file(), Some(path));
     }
 
-    /// Check if the application should quit (for testing)
-    pub fn should_quit(&self) -> bool {
-        self.should_quit
+    #[tokio::test]
+    async fn ranged_delete_should_wait_for_confirm_answer() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        let mut controller = create_mock_controller();
+        controller
+            .view_model
+            .set_request_text("GET /users\nAccept: */*\n\nbody")
+            .unwrap();
+        let events = controller.run_ex_command("set confirm on").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+
+        // A single line is deleted without asking
+        let events = controller.run_ex_command("2d").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        assert_eq!(
+            controller.view_model().get_request_text(),
+            "GET /users\n\nbody"
+        );
+
+        let events = controller.run_ex_command("%d").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        assert_eq!(
+            controller.view_model().get_status_message(),
+            Some("Delete 3 lines? (y)es/(n)o/(a)ll: ")
+        );
+        controller
+            .process_key_event(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE))
+            .await
+            .unwrap();
+        assert_eq!(controller.view_model().get_request_text(), "");
+
+        // (a)ll skips later prompts
+        controller.view_model.set_request_text("a\nb").unwrap();
+        let events = controller.run_ex_command("%d").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        assert_eq!(controller.view_model().get_request_text(), "");
     }
 
-    /// Execute a Command using the new Command Pattern
-    ///
-    /// This method allows execution of Commands that emit ModelEvents
-    /// alongside the existing command system. This enables gradual migration.
-    pub fn execute_command(&mut self, command: Box<dyn Command>) -> Result<()> {
-        tracing::debug!("Executing command: {}", command.name());
+    #[test]
+    fn messages_should_list_history_and_reset_error_count() {
+        let mut controller = create_mock_controller();
+
+        controller
+            .view_model
+            .set_error_message(&AppError::User("No file name".to_string()));
+        let events = controller.run_ex_command("e").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        controller
+            .view_model
+            .set_status_message("2 lines yanked".to_string());
+        assert_eq!(controller.view_model().unseen_error_count(), 2);
+
+        let events = controller.run_ex_command("messages").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        assert_eq!(controller.view_model().unseen_error_count(), 0);
+        assert_eq!(
+            controller.view_model().get_scratch().unwrap().join("\n"),
+            "--- Messages ---\nError: No file name\nError: E32: No file name\n2 lines yanked"
+        );
+
+        let events = controller.run_ex_command("messages clear").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        assert!(controller.view_model().message_history().is_empty());
+    }
+
+    #[test]
+    fn template_errors_should_be_reported_as_errors() {
+        let mut controller = create_mock_controller();
 
-        let mut exec_context = ExecutionContext {
-            view_model: &mut self.view_model,
-            services: &mut self.services,
-        };
-        let events = command.handle(&mut exec_context)?;
+        let events = controller.run_ex_command("template load ../login").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
 
-        tracing::debug!(
-            "Command {} produced {} events",
-            command.name(),
-            events.len()
+        assert_eq!(
+            controller.view_model().get_status_message(),
+            Some("Error: Invalid template name '../login'")
         );
+        assert_eq!(controller.view_model().unseen_error_count(), 1);
+    }
 
-        // Process each ModelEvent and convert to actual state changes
-        for event in events {
-            self.process_model_event_internal(event)?;
+    #[tokio::test]
+    async fn showmatch_flash_should_last_for_matchtime() {
+        use crate::repl::services::clock::ManualClock;
+        use crate::repl::services::MatchFlashService;
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        let mut controller = create_mock_controller();
+        let clock = ManualClock::new();
+        controller.services.match_flash = MatchFlashService::with_clock(Box::new(clock.clone()));
+        for command in ["set showmatch on", "set matchtime=3"] {
+            let events = controller.run_ex_command(command).unwrap();
+            controller.apply_ex_command_events(events).unwrap();
         }
 
-        Ok(())
-    }
+        for ch in ['i', '(', 'a', ')'] {
+            controller
+                .process_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE))
+                .await
+                .unwrap();
+        }
+        let view_events = controller.view_model.collect_pending_view_events();
+        controller.process_view_events(view_events).unwrap();
+        assert_eq!(
+            controller.view_model.get_match_highlight(),
+            Some(LogicalPosition::new(0, 0))
+        );
 
-    /// Process a ModelEvent and convert it to actual state changes
-    ///
-    /// This is the bridge between semantic ModelEvents and the actual
-    /// application state changes. It handles status messages, logging,
-    /// and any necessary side effects.
-    #[cfg(test)]
-    pub fn process_model_event(&mut self, event: ModelEvent) -> Result<()> {
-        self.process_model_event_internal(event)
+        clock.advance(Duration::from_millis(299));
+        controller.expire_match_highlight().unwrap();
+        assert!(controller.view_model.get_match_highlight().is_some());
+
+        clock.advance(Duration::from_millis(1));
+        controller.expire_match_highlight().unwrap();
+        assert_eq!(controller.view_model.get_match_highlight(), None);
+
+        // A matchtime of 0 never shows the flash
+        let events = controller.run_ex_command("set matchtime=0").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        for ch in ['(', ')'] {
+            controller
+                .process_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE))
+                .await
+                .unwrap();
+        }
+        let view_events = controller.view_model.collect_pending_view_events();
+        controller.process_view_events(view_events).unwrap();
+        assert_eq!(controller.view_model.get_match_highlight(), None);
     }
 
-    /// Internal implementation of process_model_event
-    fn process_model_event_internal(&mut self, event: ModelEvent) -> Result<()> {
-        match event {
-            ModelEvent::TextYanked {
-                pane,
-                text,
-                yank_type,
-            } => {
-                // Store in yank buffer using YankService
-                // (No need to convert types anymore - yank_type is already NewYankType)
-                self.services.yank.yank(text.clone(), yank_type)?;
+    #[tokio::test]
+    async fn dot_should_repeat_last_change_at_cursor_and_skip_motions() {
+        use crossterm::event::{KeyCode, KeyModifiers};
 
-                // Create appropriate status message
-                let char_count = text.chars().count();
-                let line_count = text.lines().count();
-                let message = match yank_type {
-                    NewYankType::Character => {
-                        if line_count > 1 {
-                            format!("{line_count} lines yanked (character-wise)")
-                        } else {
-                            format!("{char_count} characters yanked")
-                        }
-                    }
-                    NewYankType::Line => {
-                        format!("{line_count} lines yanked")
-                    }
-                    NewYankType::Block => {
-                        format!("Block yanked ({line_count} lines, {char_count} chars)")
-                    }
-                };
+        let mut controller = create_mock_controller();
+        controller.view_model.set_request_text("a\nb\nc").unwrap();
+        let key = |ch: char| KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE);
+
+        // The insert session's text is part of the change
+        let keys = [
+            key('A'),
+            key(';'),
+            KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
+            key('j'),
+            key('.'),
+        ];
+        for key in keys {
+            controller.process_key_event(key).await.unwrap();
+        }
+        assert_eq!(controller.view_model.get_request_text(), "a;\nb;\nc");
+        assert_eq!(controller.view_model.get_mode(), EditorMode::Normal);
 
-                self.view_model.set_status_message(message);
+        // Repeating x on the line it emptied does nothing, and the motions in
+        // between leave x as the change to repeat
+        for ch in ['j', 'x', '.', 'k', '0', '.'] {
+            controller.process_key_event(key(ch)).await.unwrap();
+        }
+        assert_eq!(controller.view_model.get_request_text(), "a;\n;\n");
+    }
 
-                tracing::info!(
-                    "Yanked {} characters ({} lines) to buffer as {:?} from {:?}",
-                    char_count,
-                    line_count,
-                    yank_type,
-                    pane
-                );
-            }
+    #[tokio::test]
+    async fn count_daw_should_delete_words_and_repeat_with_dot() {
+        use crossterm::event::{KeyCode, KeyModifiers};
 
-            ModelEvent::ModeChanged { old_mode, new_mode } => {
-                self.view_model.change_mode(new_mode)?;
-                tracing::debug!("Mode changed from {:?} to {:?}", old_mode, new_mode);
-            }
+        let mut controller = create_mock_controller();
+        controller
+            .view_model
+            .set_request_text("GET /users?a=1 b c d e\nnext")
+            .unwrap();
+        controller
+            .view_model
+            .set_cursor_position(LogicalPosition::new(0, 15))
+            .unwrap();
+        let key = |ch: char| KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE);
 
-            ModelEvent::SelectionCleared { pane } => {
-                // Selection clearing happens automatically when mode changes to Normal
-                tracing::debug!("Selection cleared for {:?}", pane);
-            }
+        for ch in ['2', 'd', 'a'] {
+            controller.process_key_event(key(ch)).await.unwrap();
+        }
+        assert_eq!(controller.view_model.get_mode(), EditorMode::DAPrefix);
+        assert_eq!(controller.view_model.get_pending_keys(), "2da");
+        controller.process_key_event(key('w')).await.unwrap();
 
-            ModelEvent::StatusMessageSet { message } => {
-                self.view_model.set_status_message(message);
-            }
+        assert_eq!(
+            controller.view_model.get_request_text(),
+            "GET /users?a=1 d e\nnext"
+        );
+        assert_eq!(controller.view_model.get_mode(), EditorMode::Normal);
+        assert_eq!(
+            controller.view_model.get_yanked_text(),
+            Some("b c ".to_string())
+        );
 
-            ModelEvent::StatusMessageCleared => {
-                self.view_model.set_status_message(String::new());
-            }
+        // The count is part of the repeated change
+        controller.process_key_event(key('.')).await.unwrap();
+        assert_eq!(
+            controller.view_model.get_request_text(),
+            "GET /users?a=1\nnext"
+        );
+        assert_eq!(
+            controller.view_model.get_cursor_position(),
+            LogicalPosition::new(0, 13)
+        );
+    }
 
-            ModelEvent::HttpRequestStarted { method, url } => {
-                // Just log it - the actual execution is handled by HttpExecuteCommand
-                tracing::info!("HTTP request initiated: {method} {url}");
-            }
+    #[tokio::test]
+    async fn gu_word_motion_should_uppercase_to_next_word_and_repeat_with_dot() {
+        use crossterm::event::{KeyCode, KeyModifiers};
 
-            ModelEvent::HttpResponseReceived { status, body } => {
-                // Update response pane with received data
-                self.view_model.set_response(status, body);
-                self.view_model.set_executing_request(false);
-                self.view_model.switch_to_response_pane();
+        let mut controller = create_mock_controller();
+        controller
+            .view_model
+            .set_request_text("get users now\nnext")
+            .unwrap();
+        let key = |ch: char| KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE);
 
-                let status_msg = if (200..300).contains(&status) {
-                    format!("Request completed: {status}")
-                } else {
-                    format!("Request failed: {status}")
-                };
-                self.view_model.set_status_message(status_msg);
-            }
+        for ch in ['g', 'U'] {
+            controller.process_key_event(key(ch)).await.unwrap();
+        }
+        assert_eq!(
+            controller.view_model.get_mode(),
+            EditorMode::UpperCasePrefix
+        );
+        assert_eq!(controller.view_model.get_pending_keys(), "gU");
+        controller.process_key_event(key('w')).await.unwrap();
 
-            // Handle other events as we implement them
-            _ => {
-                tracing::debug!("ModelEvent not yet implemented: {:?}", event);
-            }
+        assert_eq!(
+            controller.view_model.get_request_text(),
+            "GET users now\nnext"
+        );
+        assert_eq!(controller.view_model.get_mode(), EditorMode::Normal);
+        assert_eq!(
+            controller.view_model.get_cursor_position(),
+            LogicalPosition::new(0, 0)
+        );
+
+        for ch in ['w', '.'] {
+            controller.process_key_event(key(ch)).await.unwrap();
         }
+        assert_eq!(
+            controller.view_model.get_request_text(),
+            "GET USERS now\nnext"
+        );
 
-        Ok(())
+        // On the last word `w` stops at the end of the line
+        for ch in ['w', 'g', '~', 'w'] {
+            controller.process_key_event(key(ch)).await.unwrap();
+        }
+        assert_eq!(
+            controller.view_model.get_request_text(),
+            "GET USERS NOW\nnext"
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::cmd_args::CommandLineArgs;
-    use crate::repl::events::{EditorMode, Pane};
+    #[tokio::test]
+    async fn gu_in_visual_mode_should_uppercase_selection() {
+        use crossterm::event::{KeyCode, KeyModifiers};
 
-    #[test]
-    fn app_controller_should_create() {
-        if crossterm::terminal::size().is_ok() {
-            let cmd_args = CommandLineArgs::parse_from(["test"]);
-            let config = AppConfig::from_args(cmd_args);
-            let controller = AppController::with_io_streams(
-                config,
-                crate::repl::io::TerminalEventStream::new(),
-                crate::repl::io::TerminalRenderStream::new(),
-            );
-            assert!(controller.is_ok());
+        let mut controller = create_mock_controller();
+        controller
+            .view_model
+            .set_request_text("accept: json\nnext")
+            .unwrap();
+        controller
+            .view_model
+            .set_cursor_position(LogicalPosition::new(0, 8))
+            .unwrap();
+        let key = |ch: char| KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE);
 
-            let controller = controller.unwrap();
-            assert_eq!(controller.view_model().get_mode(), EditorMode::Normal);
-            assert_eq!(controller.view_model().get_current_pane(), Pane::Request);
+        for ch in ['v', 'e', 'g', 'U'] {
+            controller.process_key_event(key(ch)).await.unwrap();
         }
+
+        assert_eq!(
+            controller.view_model.get_request_text(),
+            "accept: JSON\nnext"
+        );
+        assert_eq!(controller.view_model.get_mode(), EditorMode::Normal);
+        assert_eq!(
+            controller.view_model.get_cursor_position(),
+            LogicalPosition::new(0, 8)
+        );
     }
 
     #[test]
-    fn app_controller_should_execute_yank_selection_command() {
-        use crate::repl::view_models::commands::yank::YankSelectionCommand;
-
-        if crossterm::terminal::size().is_ok() {
-            let cmd_args = CommandLineArgs::parse_from(["test"]);
-            let config = AppConfig::from_args(cmd_args);
-            let mut controller = AppController::with_io_streams(
-                config,
-                crate::repl::io::TerminalEventStream::new(),
-                crate::repl::io::TerminalRenderStream::new(),
-            )
-            .unwrap();
-
-            // Test YankSelectionCommand in Normal mode (should fail as expected)
-            let command = Box::new(YankSelectionCommand::new());
-            let result = controller.execute_command(command);
+    fn executekey_should_move_request_execution_off_plain_enter() {
+        use crate::repl::view_models::commands::CommandContext as UnifiedContext;
+        use crossterm::event::KeyModifiers;
+
+        let mut controller = create_mock_controller();
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let ctrl_enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL);
+        let execute_command = |controller: &AppController<_, _>, key| {
+            let context = UnifiedContext::from_view_model(&controller.view_model);
+            controller
+                .unified_command_registry
+                .process_key_event(key, EditorMode::Normal, &context)
+                .map(|command| command.name())
+        };
+        assert_eq!(execute_command(&controller, enter), Some("HttpExecute"));
 
-            // Verify the command failed as expected (not in visual mode)
-            assert!(
-                result.is_err(),
-                "Command should fail when not in visual mode"
-            );
+        let events = controller.run_ex_command("set executekey=<C-CR>").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
 
-            // Verify we're still in Normal mode
-            assert_eq!(controller.view_model().get_mode(), EditorMode::Normal);
-        }
+        assert_eq!(execute_command(&controller, enter), None);
+        assert_eq!(
+            execute_command(&controller, ctrl_enter),
+            Some("HttpExecute")
+        );
+        let context =
+            CommandContext::new(ViewModelSnapshot::from_view_model(&controller.view_model));
+        assert!(controller
+            .command_registry
+            .process_event(enter, &context)
+            .unwrap()
+            .is_empty());
+
+        // Notation that names no key leaves the binding alone
+        let events = controller.run_ex_command("set executekey=<Bogus>").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+        assert_eq!(
+            controller.view_model.get_execute_key(),
+            KeyBinding::parse("<C-CR>").unwrap()
+        );
     }
 
-    #[test]
-    fn app_controller_should_process_model_events() {
-        use crate::repl::view_models::commands::{events::YankType, ModelEvent};
+    #[tokio::test]
+    async fn marks_should_follow_their_line_and_report_unset_marks() {
+        use crossterm::event::{KeyCode, KeyModifiers};
 
-        if crossterm::terminal::size().is_ok() {
-            let cmd_args = CommandLineArgs::parse_from(["test"]);
-            let config = AppConfig::from_args(cmd_args);
-            let mut controller = AppController::with_io_streams(
-                config,
-                crate::repl::io::TerminalEventStream::new(),
-                crate::repl::io::TerminalRenderStream::new(),
-            )
+        let mut controller = create_mock_controller();
+        controller
+            .view_model
+            .set_request_text("x\n  bar\nc")
+            .unwrap();
+        controller
+            .view_model
+            .set_cursor_position(LogicalPosition::new(1, 3))
             .unwrap();
+        let key = |ch: char| KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE);
 
-            // Test processing a TextYanked event
-            let event = ModelEvent::TextYanked {
-                pane: Pane::Request,
-                text: "test text".to_string(),
-                yank_type: YankType::Character,
-            };
+        // Deleting the line above moves the mark up with its line
+        for ch in "mbggdd`b".chars() {
+            controller.process_key_event(key(ch)).await.unwrap();
+        }
+        assert_eq!(controller.view_model.get_request_text(), "  bar\nc");
+        assert_eq!(
+            controller.view_model.get_cursor_position(),
+            LogicalPosition::new(0, 3)
+        );
 
-            let result = controller.process_model_event(event);
-            assert!(result.is_ok(), "ModelEvent processing should succeed");
+        for ch in "j'b".chars() {
+            controller.process_key_event(key(ch)).await.unwrap();
+        }
+        assert_eq!(
+            controller.view_model.get_cursor_position(),
+            LogicalPosition::new(0, 2)
+        );
 
-            // Verify yank buffer contains the text
-            // NOTE: YankService now owns the yank buffer, not ViewModel
-            // We would need to check controller.services.yank instead
-            // For now, just verify the event was processed successfully
+        // Deleting the marked line removes the mark
+        for ch in "dd`b".chars() {
+            controller.process_key_event(key(ch)).await.unwrap();
         }
+        assert_eq!(controller.view_model.get_request_text(), "c");
+        assert_eq!(
+            controller.view_model.get_status_message(),
+            Some("Error: E20: Mark not set")
+        );
+        assert_eq!(controller.view_model.get_mode(), EditorMode::Normal);
     }
 
     #[tokio::test]
-    async fn app_controller_should_use_unified_command_system() {
+    async fn paste_should_read_system_clipboard_with_unnamedplus() {
+        use crate::repl::models::ClipboardSink;
         use crossterm::event::{KeyCode, KeyModifiers};
+        use std::sync::{Arc, Mutex};
 
-        if crossterm::terminal::size().is_ok() {
-            let cmd_args = CommandLineArgs::parse_from(["test"]);
-            let config = AppConfig::from_args(cmd_args);
-            let mut controller = AppController::with_io_streams(
-                config,
-                crate::repl::io::TerminalEventStream::new(),
-                crate::repl::io::TerminalRenderStream::new(),
-            )
+        /// Clipboard holding whatever another application copied last
+        struct MockClipboard(Arc<Mutex<String>>);
+
+        impl ClipboardSink for MockClipboard {
+            fn set_text(&mut self, text: &str) -> anyhow::Result<()> {
+                *self.0.lock().unwrap() = text.to_string();
+                Ok(())
+            }
+
+            fn get_text(&mut self) -> anyhow::Result<String> {
+                Ok(self.0.lock().unwrap().clone())
+            }
+        }
+
+        let mut controller = create_mock_controller();
+        let clipboard = Arc::new(Mutex::new("https://example.com/users".to_string()));
+        controller
+            .services
+            .yank
+            .set_clipboard_sink(Some(Box::new(MockClipboard(Arc::clone(&clipboard)))));
+        controller
+            .view_model
+            .set_request_text("GET \nHost: example.com")
+            .unwrap();
+        controller
+            .view_model
+            .set_cursor_position(LogicalPosition::new(0, 3))
             .unwrap();
+        let key = |ch: char| KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE);
 
-            // Verify unified command registry is initialized
-            assert!(controller.unified_command_registry.command_count() > 0);
+        controller.process_key_event(key('p')).await.unwrap();
+        assert_eq!(
+            controller.view_model.get_request_text(),
+            "GET https://example.com/users\nHost: example.com"
+        );
 
-            // Test 'y' key in Normal mode - should fall back to old system (no unified command)
-            let y_key = crossterm::event::KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
-            let result = controller.handle_key_event_with_unified_first(y_key).await;
-            assert!(
-                result.is_ok(),
-                "Unified command system should handle key events gracefully"
-            );
+        // Copied text ending in a newline goes in as a line
+        *clipboard.lock().unwrap() = "Accept: */*\n".to_string();
+        controller.process_key_event(key('j')).await.unwrap();
+        controller
+            .process_key_event(KeyEvent::new(KeyCode::Char('P'), KeyModifiers::SHIFT))
+            .await
+            .unwrap();
+        assert_eq!(
+            controller.view_model.get_request_text(),
+            "GET https://example.com/users\nAccept: */*\nHost: example.com"
+        );
+    }
 
-            // Verify old system handled it (y in Normal mode goes to YPrefix mode)
-            assert_eq!(controller.view_model().get_mode(), EditorMode::YPrefix);
+    #[tokio::test]
+    async fn ctrl_v_should_insert_literal_tab_and_unicode_code_point() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        let mut controller = create_mock_controller();
+        let events = controller.run_ex_command("set expandtab on").unwrap();
+        controller.apply_ex_command_events(events).unwrap();
+
+        let ctrl_v = KeyEvent::new(KeyCode::Char('v'), KeyModifiers::CONTROL);
+        let keys = [
+            KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE),
+            ctrl_v,
+            KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE),
+            ctrl_v,
+            KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Char('0'), KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Char('0'), KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE),
+        ];
+        for key in keys {
+            controller.process_key_event(key).await.unwrap();
         }
+        assert_eq!(controller.view_model.get_pending_keys(), "^Vu00e");
+
+        controller
+            .process_key_event(KeyEvent::new(KeyCode::Char('9'), KeyModifiers::NONE))
+            .await
+            .unwrap();
+        assert_eq!(controller.view_model.get_request_text(), "\t\u{e9}");
+        assert_eq!(controller.view_model.get_mode(), EditorMode::Insert);
+
+        // Escape itself goes in literally instead of leaving insert mode
+        controller.process_key_event(ctrl_v).await.unwrap();
+        controller
+            .process_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))
+            .await
+            .unwrap();
+        assert_eq!(controller.view_model.get_request_text(), "\t\u{e9}\x1b");
+        assert_eq!(controller.view_model.get_mode(), EditorMode::Insert);
     }
 }
@@ -0,0 +1,150 @@
+//! # Application Errors
+//!
+//! Errors that reach the user, sorted by kind so the status line and the
+//! response pane show them the same way wherever they come from.
+
+use std::fmt;
+
+/// Result type for operations that fail with an [`AppError`]
+pub type AppResult<T> = std::result::Result<T, AppError>;
+
+/// An error shown to the user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppError {
+    /// The request could not be sent or no response came back
+    Network(String),
+    /// The server took too long to answer
+    Timeout(String),
+    /// The request buffer is not a valid request
+    Parse(String),
+    /// Reading or writing a file failed
+    Io { path: String, message: String },
+    /// The user asked for something that cannot be done
+    User(String),
+}
+
+impl AppError {
+    /// Wrap an I/O error on `path`
+    pub fn io(path: &str, error: &std::io::Error) -> Self {
+        Self::Io {
+            path: path.to_string(),
+            message: error.to_string(),
+        }
+    }
+
+    /// Sort an error from sending a request into a timeout or a network error
+    ///
+    /// The message keeps the whole cause chain, one cause per line.
+    pub fn from_request_error(error: &anyhow::Error) -> Self {
+        let mut message = error.to_string();
+        for cause in error.chain().skip(1) {
+            message.push_str(&format!("\n  Caused by: {cause}"));
+        }
+
+        let timed_out = error.chain().any(|cause| {
+            cause
+                .downcast_ref::<std::io::Error>()
+                .is_some_and(|io_error| io_error.kind() == std::io::ErrorKind::TimedOut)
+                || cause.to_string().contains("timed out")
+        });
+        if timed_out {
+            Self::Timeout(message)
+        } else {
+            Self::Network(message)
+        }
+    }
+
+    /// Process exit code for this error when running without the editor
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::User(_) => 1,
+            Self::Parse(_) => 2,
+            Self::Io { .. } => 3,
+            Self::Network(_) => 4,
+            Self::Timeout(_) => 5,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Network(message) => write!(f, "Request failed: {message}"),
+            Self::Timeout(message) => write!(f, "Request timed out: {message}"),
+            Self::Parse(message) => write!(f, "Invalid request: {message}"),
+            Self::Io { path, message } => write!(f, "\"{path}\": {message}"),
+            Self::User(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_should_render_user_facing_message_for_each_kind() {
+        assert_eq!(
+            AppError::Network("connection refused".to_string()).to_string(),
+            "Request failed: connection refused"
+        );
+        assert_eq!(
+            AppError::Timeout("operation timed out".to_string()).to_string(),
+            "Request timed out: operation timed out"
+        );
+        assert_eq!(
+            AppError::Parse("No request to execute".to_string()).to_string(),
+            "Invalid request: No request to execute"
+        );
+        assert_eq!(
+            AppError::Io {
+                path: "api.http".to_string(),
+                message: "Permission denied".to_string(),
+            }
+            .to_string(),
+            "\"api.http\": Permission denied"
+        );
+        assert_eq!(
+            AppError::User("No file name".to_string()).to_string(),
+            "No file name"
+        );
+    }
+
+    #[test]
+    fn from_request_error_should_tell_timeouts_from_network_errors() {
+        let timeout = anyhow::Error::new(std::io::Error::new(
+            std::io::ErrorKind::TimedOut,
+            "deadline elapsed",
+        ))
+        .context("error sending request");
+        assert_eq!(
+            AppError::from_request_error(&timeout),
+            AppError::Timeout("error sending request\n  Caused by: deadline elapsed".to_string())
+        );
+
+        let refused = anyhow::anyhow!("connection refused").context("error sending request");
+        assert_eq!(
+            AppError::from_request_error(&refused),
+            AppError::Network("error sending request\n  Caused by: connection refused".to_string())
+        );
+    }
+
+    #[test]
+    fn exit_code_should_differ_per_kind() {
+        let errors = [
+            AppError::User(String::new()),
+            AppError::Parse(String::new()),
+            AppError::Io {
+                path: String::new(),
+                message: String::new(),
+            },
+            AppError::Network(String::new()),
+            AppError::Timeout(String::new()),
+        ];
+        let mut codes: Vec<i32> = errors.iter().map(AppError::exit_code).collect();
+        codes.dedup();
+        assert_eq!(codes, vec![1, 2, 3, 4, 5]);
+    }
+}
@@ -0,0 +1,180 @@
+//! # Key Bindings
+//!
+//! A key together with its modifiers, written in Vim's key notation such as
+//! `<CR>`, `<C-CR>` or `<F5>`, for settings that rebind a key
+//! (`:set executekey=<C-CR>`).
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::fmt;
+
+/// A key and the modifiers held with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Plain Enter, the default key for sending the request
+    pub const ENTER: Self = Self {
+        code: KeyCode::Enter,
+        modifiers: KeyModifiers::NONE,
+    };
+
+    /// Read a key from Vim's key notation
+    ///
+    /// Accepts a single character (`x`) or a bracketed name with any of the
+    /// `C-`, `S-` and `M-`/`A-` prefixes (`<CR>`, `<C-Enter>`, `<F5>`,
+    /// `<C-x>`). Names are case-insensitive.
+    ///
+    /// # Returns
+    /// `None` if the notation names no key.
+    pub fn parse(notation: &str) -> Option<Self> {
+        let mut chars = notation.chars();
+        if let (Some(ch), None) = (chars.next(), chars.next()) {
+            return Some(Self {
+                code: KeyCode::Char(ch),
+                modifiers: KeyModifiers::NONE,
+            });
+        }
+
+        let mut name = notation.strip_prefix('<')?.strip_suffix('>')?;
+        let mut modifiers = KeyModifiers::NONE;
+        while let Some((prefix, rest)) = name.split_once('-').filter(|(_, rest)| !rest.is_empty()) {
+            modifiers |= match prefix.to_ascii_uppercase().as_str() {
+                "C" => KeyModifiers::CONTROL,
+                "S" => KeyModifiers::SHIFT,
+                "M" | "A" => KeyModifiers::ALT,
+                _ => return None,
+            };
+            name = rest;
+        }
+
+        let code = match name.to_ascii_lowercase().as_str() {
+            "cr" | "enter" | "return" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "space" => KeyCode::Char(' '),
+            "bs" => KeyCode::Backspace,
+            "del" => KeyCode::Delete,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "insert" => KeyCode::Insert,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            lower => match lower.strip_prefix('f').map(str::parse::<u8>) {
+                Some(Ok(number @ 1..=12)) => KeyCode::F(number),
+                _ => {
+                    let mut chars = name.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(ch), None) => KeyCode::Char(ch.to_ascii_lowercase()),
+                        _ => return None,
+                    }
+                }
+            },
+        };
+        Some(Self { code, modifiers })
+    }
+
+    /// Check whether `event` is this key with exactly these modifiers
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        event.code == self.code && event.modifiers == self.modifiers
+    }
+}
+
+impl Default for KeyBinding {
+    fn default() -> Self {
+        Self::ENTER
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    /// Write the key back in Vim's key notation
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(ch) if self.modifiers.is_empty() => return write!(f, "{ch}"),
+            KeyCode::Char(ch) => ch.to_string(),
+            KeyCode::Enter => "CR".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::Backspace => "BS".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::Insert => "Insert".to_string(),
+            KeyCode::PageUp => "PageUp".to_string(),
+            KeyCode::PageDown => "PageDown".to_string(),
+            KeyCode::F(number) => format!("F{number}"),
+            other => format!("{other:?}"),
+        };
+        let mut prefixes = String::new();
+        for (modifier, prefix) in [
+            (KeyModifiers::CONTROL, "C-"),
+            (KeyModifiers::SHIFT, "S-"),
+            (KeyModifiers::ALT, "M-"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                prefixes.push_str(prefix);
+            }
+        }
+        write!(f, "<{prefixes}{name}>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_should_read_vim_key_notation() {
+        assert_eq!(KeyBinding::parse("<CR>"), Some(KeyBinding::ENTER));
+        assert_eq!(
+            KeyBinding::parse("<c-enter>"),
+            Some(KeyBinding {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::CONTROL,
+            })
+        );
+        assert_eq!(
+            KeyBinding::parse("<F5>"),
+            Some(KeyBinding {
+                code: KeyCode::F(5),
+                modifiers: KeyModifiers::NONE,
+            })
+        );
+        assert_eq!(
+            KeyBinding::parse("<C-S-x>"),
+            Some(KeyBinding {
+                code: KeyCode::Char('x'),
+                modifiers: KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+            })
+        );
+        assert_eq!(
+            KeyBinding::parse("-"),
+            Some(KeyBinding {
+                code: KeyCode::Char('-'),
+                modifiers: KeyModifiers::NONE,
+            })
+        );
+        for notation in ["", "CR", "<F13>", "<X-CR>", "<Bogus>", "<C->"] {
+            assert_eq!(KeyBinding::parse(notation), None, "{notation}");
+        }
+    }
+
+    #[test]
+    fn display_should_round_trip_through_parse() {
+        for notation in ["<CR>", "<C-CR>", "<F5>", "<C-x>", "<Space>", "x"] {
+            let key = KeyBinding::parse(notation).unwrap();
+            assert_eq!(key.to_string(), notation);
+            assert_eq!(KeyBinding::parse(&key.to_string()), Some(key));
+        }
+    }
+
+    #[test]
+    fn matches_should_require_exact_modifiers() {
+        let ctrl_enter = KeyBinding::parse("<C-CR>").unwrap();
+
+        assert!(ctrl_enter.matches(&KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL)));
+        assert!(!ctrl_enter.matches(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+        assert!(!KeyBinding::ENTER.matches(&KeyEvent::new(KeyCode::Enter, KeyModifiers::SHIFT)));
+    }
+}
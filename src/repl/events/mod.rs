@@ -7,6 +7,7 @@
 // Import event modules
 pub mod event_bus;
 pub mod event_source;
+pub mod key_binding;
 pub mod model_events;
 pub mod terminal_event_source;
 pub mod types;
@@ -15,6 +16,7 @@ pub mod view_events;
 // Re-export all types for easy access
 pub use event_bus::{EventBus, ModelEventHandler, SimpleEventBus, ViewEventHandler};
 pub use event_source::EventSource;
+pub use key_binding::KeyBinding;
 pub use model_events::ModelEvent;
 pub use terminal_event_source::TerminalEventSource;
 pub use types::{EditorMode, LogicalPosition, LogicalRange, Pane, PaneCapabilities};
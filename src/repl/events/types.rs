@@ -29,6 +29,40 @@ pub enum EditorMode {
     DPrefix,
     /// Y prefix mode - waiting for second character after 'y' press
     YPrefix,
+    /// Z prefix mode - waiting for second character after 'Z' press (ZZ, ZQ)
+    ZPrefix,
+    /// Scroll prefix mode - waiting for second character after 'z' press (zh, zl, zH, zL)
+    ScrollPrefix,
+    /// Q prefix mode - waiting for ':' after 'q' press (q: opens the command-line window)
+    QPrefix,
+    /// C prefix mode - waiting for the motion after 'c' press (c/ changes up to a search match)
+    CPrefix,
+    /// F prefix mode - waiting for the target character after 'f' press
+    FPrefix,
+    /// DF prefix mode - waiting for the target character after 'df' press
+    DFPrefix,
+    /// DA prefix mode - waiting for the text object after 'da' press (`daw`)
+    DAPrefix,
+    /// Register prefix mode - waiting for register name after '"' press (`"_dd`)
+    RegisterPrefix,
+    /// Equal prefix mode - waiting for the motion after '=' press (`==`, `=G`)
+    EqualPrefix,
+    /// Mark prefix mode - waiting for the mark name after '`' press (`` `a ``, `` `[ ``, `` `] ``)
+    MarkPrefix,
+    /// Mark line prefix mode - waiting for the mark name after '\'' press (`'a`)
+    MarkLinePrefix,
+    /// Set mark prefix mode - waiting for the mark name after 'm' press (`ma`)
+    SetMarkPrefix,
+    /// Indent prefix mode - waiting for the second '>' after '>' press (`>>`)
+    IndentPrefix,
+    /// Dedent prefix mode - waiting for the second '<' after '<' press (`<<`)
+    DedentPrefix,
+    /// Lowercase prefix mode - waiting for the motion after 'gu' press (`guw`, `guu`)
+    LowerCasePrefix,
+    /// Uppercase prefix mode - waiting for the motion after 'gU' press (`gUw`, `gUU`)
+    UpperCasePrefix,
+    /// Toggle case prefix mode - waiting for the motion after 'g~' press (`g~w`, `g~~`)
+    ToggleCasePrefix,
     /// Visual mode - character-wise text selection mode (vim's 'v')
     Visual,
     /// Visual Line mode - line-wise text selection mode (vim's 'V')
@@ -37,6 +71,12 @@ pub enum EditorMode {
     VisualBlock,
     /// Visual Block Insert mode - special insert mode for Visual Block 'I' and 'A' commands
     VisualBlockInsert,
+    /// Search mode - typing a `/` search query in the status line
+    Search,
+    /// Insert register prefix mode - waiting for register name after Ctrl-r in insert mode
+    InsertRegisterPrefix,
+    /// Insert literal prefix mode - waiting for the key to insert as-is after Ctrl-v in insert mode
+    InsertLiteralPrefix,
 }
 
 bitflags! {
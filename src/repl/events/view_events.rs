@@ -4,6 +4,7 @@
 //! These events drive UI refreshing and handle user interactions.
 
 // Pane import removed - no longer needed for abstracted events
+use crate::repl::models::{LogicalPosition, YankHighlight};
 use crossterm::event::KeyEvent;
 
 /// Events emitted when view updates are needed
@@ -53,6 +54,15 @@ pub enum ViewEvent {
     /// Content area focus switched (for cursor style, highlighting, etc)
     FocusSwitched,
 
+    /// Line number gutters need recoloring (mode change), leaving the text alone
+    GutterRedrawRequired,
+
+    /// Text was just yanked and flashes until the highlight times out
+    YankHighlighted { highlight: YankHighlight },
+
+    /// A closing bracket was typed and its match in the request buffer flashes
+    MatchHighlighted { position: LogicalPosition },
+
     // Domain-specific events for clearer semantics
     /// Request content has been modified
     RequestContentChanged,
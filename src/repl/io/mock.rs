@@ -128,6 +128,21 @@ impl MockRenderStream {
         self.commands.lock().unwrap().contains(command)
     }
 
+    /// Check whether raw mode is on
+    pub fn is_raw_mode(&self) -> bool {
+        self.raw_mode
+    }
+
+    /// Check whether the alternate screen is shown
+    pub fn is_alternate_screen(&self) -> bool {
+        self.alternate_screen
+    }
+
+    /// Check whether the cursor is visible
+    pub fn is_cursor_visible(&self) -> bool {
+        self.cursor_visible
+    }
+
     /// Get the current buffer contents as a string
     pub fn get_buffer_string(&self) -> String {
         String::from_utf8_lossy(&self.buffer).to_string()
@@ -688,8 +703,23 @@ impl Default for VteRenderStream {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::repl::io::restore_terminal;
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
+    #[test]
+    fn restore_terminal_should_reset_mock_terminal_state() {
+        let mut stream = MockRenderStream::new();
+        stream.enable_raw_mode().unwrap();
+        stream.enter_alternate_screen().unwrap();
+        stream.hide_cursor().unwrap();
+
+        restore_terminal(&mut stream).unwrap();
+
+        assert!(!stream.is_raw_mode());
+        assert!(!stream.is_alternate_screen());
+        assert!(stream.is_cursor_visible());
+    }
+
     #[test]
     fn mock_event_stream_should_provide_events() {
         let events = vec![
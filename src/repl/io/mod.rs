@@ -31,7 +31,7 @@ pub mod terminal;
 pub mod test_bridge;
 
 // Re-export terminal implementations for convenience
-pub use terminal::{TerminalEventStream, TerminalRenderStream};
+pub use terminal::{install_panic_hook, TerminalEventStream, TerminalRenderStream};
 
 // Re-export mock implementations for testing
 pub use mock::{MockEventStream, MockRenderStream, TerminalStateInfo, VteRenderStream};
@@ -95,3 +95,17 @@ pub trait RenderStream: Write + Send {
     /// Disable terminal raw mode
     fn disable_raw_mode(&mut self) -> Result<()>;
 }
+
+/// Put the terminal back the way the shell expects it
+///
+/// Shows the cursor, leaves the alternate screen and turns raw mode off. Every
+/// step is attempted even if an earlier one fails, since this also runs from
+/// the panic hook where a half-restored terminal is the worst outcome.
+pub fn restore_terminal(stream: &mut impl RenderStream) -> Result<()> {
+    let results = [
+        stream.show_cursor(),
+        stream.leave_alternate_screen(),
+        stream.disable_raw_mode(),
+    ];
+    results.into_iter().collect()
+}
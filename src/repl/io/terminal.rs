@@ -3,7 +3,7 @@
 //! Production implementations of I/O abstractions using crossterm.
 //! All crossterm dependencies are isolated to this module.
 
-use super::{EventStream, RenderStream};
+use super::{restore_terminal, EventStream, RenderStream};
 use anyhow::Result;
 use crossterm::event::{self, Event};
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
@@ -117,6 +117,18 @@ impl<W: Write + Send> RenderStream for TerminalRenderStream<W> {
     }
 }
 
+/// Restore the terminal before a panic message is printed
+///
+/// Without this a crash leaves the shell in raw mode on the alternate screen,
+/// with the panic message drawn somewhere the user cannot see it.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal(&mut TerminalRenderStream::new());
+        default_hook(info);
+    }));
+}
+
 impl Default for TerminalEventStream {
     fn default() -> Self {
         Self::new()
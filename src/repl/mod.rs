@@ -5,6 +5,7 @@
 
 pub mod commands;
 pub mod controllers;
+pub mod error;
 pub mod events;
 pub mod io;
 pub mod models;
@@ -15,6 +16,7 @@ pub mod views;
 
 // Re-export core types
 pub use controllers::AppController;
+pub use error::{AppError, AppResult};
 pub use events::*;
 pub use view_models::*;
 pub use views::*;
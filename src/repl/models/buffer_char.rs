@@ -495,14 +495,14 @@ impl CharacterBuffer {
     }
 
     /// Create a buffer from text lines
-    pub fn from_lines(text_lines: &[String]) -> Self {
+    pub fn from_lines<S: AsRef<str>>(text_lines: &[S]) -> Self {
         if text_lines.is_empty() {
             return Self::new();
         }
 
         let lines = text_lines
             .iter()
-            .map(|line| BufferLine::from_string(line))
+            .map(|line| BufferLine::from_string(line.as_ref()))
             .collect();
 
         Self {
@@ -576,6 +576,77 @@ impl CharacterBuffer {
         self.lines[line].delete_char(logical_col).map(|bc| bc.ch)
     }
 
+    /// Insert text, which may span several lines, at a logical position
+    ///
+    /// The target line is rebuilt once and any new lines are spliced in together,
+    /// so pasting many lines into a large buffer shifts the lines after it only once.
+    ///
+    /// # Returns
+    /// The line and column just past the inserted text.
+    pub fn insert_text(&mut self, line: usize, logical_col: usize, text: &str) -> (usize, usize) {
+        // Ensure we have enough lines
+        while self.lines.len() <= line {
+            self.lines.push(BufferLine::new());
+        }
+
+        let current_line = &self.lines[line].chars;
+        let split_col = logical_col.min(current_line.len());
+        let before: String = current_line[..split_col].iter().map(|bc| bc.ch).collect();
+        let after: String = current_line[split_col..].iter().map(|bc| bc.ch).collect();
+
+        let pieces: Vec<&str> = text.split('\n').collect();
+        let last_piece = pieces[pieces.len() - 1];
+        let end_col = if pieces.len() == 1 {
+            split_col + last_piece.chars().count()
+        } else {
+            last_piece.chars().count()
+        };
+
+        let new_lines: Vec<BufferLine> = pieces
+            .iter()
+            .enumerate()
+            .map(|(i, piece)| {
+                let mut text = String::new();
+                if i == 0 {
+                    text.push_str(&before);
+                }
+                text.push_str(piece);
+                if i == pieces.len() - 1 {
+                    text.push_str(&after);
+                }
+                BufferLine::from_string(&text)
+            })
+            .collect();
+        self.lines.splice(line..=line, new_lines);
+
+        (line + pieces.len() - 1, end_col)
+    }
+
+    /// Delete the text from one logical position up to (not including) another
+    ///
+    /// Lines between the two positions are removed in one go and the ends are
+    /// joined. Ranges that run backwards or past the last line are ignored.
+    pub fn delete_range(&mut self, start: (usize, usize), end: (usize, usize)) {
+        let (start_line, start_col) = start;
+        let (end_line, end_col) = end;
+        if start_line > end_line || end_line >= self.lines.len() {
+            return;
+        }
+        if start_line == end_line && start_col >= end_col {
+            return;
+        }
+
+        let joined: String = self.lines[start_line]
+            .chars
+            .iter()
+            .take(start_col)
+            .chain(self.lines[end_line].chars.iter().skip(end_col))
+            .map(|bc| bc.ch)
+            .collect();
+        self.lines
+            .splice(start_line..=end_line, [BufferLine::from_string(&joined)]);
+    }
+
     /// Convert the buffer back to a Vec<String> for compatibility
     pub fn to_string_lines(&self) -> Vec<String> {
         self.lines.iter().map(|line| line.to_string()).collect()
@@ -895,4 +966,131 @@ mod tests {
         assert_eq!(buffer.line_count(), 2);
         assert_eq!(buffer.to_string_lines(), vec!["1234", "5678"]);
     }
+
+    /// Insert text the way the buffer used to, one character at a time
+    fn insert_text_by_char(buffer: &mut CharacterBuffer, line: usize, col: usize, text: &str) {
+        let (mut line, mut col) = (line, col);
+        for ch in text.chars() {
+            buffer.insert_char(line, col, ch);
+            if ch == '\n' {
+                line += 1;
+                col = 0;
+            } else {
+                col += 1;
+            }
+        }
+    }
+
+    /// Delete a range the way the buffer used to, one character at a time
+    fn delete_range_by_char(
+        buffer: &mut CharacterBuffer,
+        start: (usize, usize),
+        end: (usize, usize),
+    ) {
+        if start.0 == end.0 {
+            let mut col = end.1;
+            while col > start.1 {
+                col -= 1;
+                buffer.delete_char(start.0, col);
+            }
+            return;
+        }
+
+        let (mut line, mut col) = end;
+        while (line, col) != start {
+            if col > 0 {
+                col -= 1;
+                buffer.delete_char(line, col);
+            } else if line > start.0 {
+                line -= 1;
+                col = buffer.get_line(line).unwrap().char_count();
+                buffer.join_lines(line, line + 1);
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn sample_lines() -> Vec<String> {
+        vec![
+            "GET /users".to_string(),
+            "Accept: こんにちは".to_string(),
+            String::new(),
+            "{\"id\": 1}".to_string(),
+        ]
+    }
+
+    #[test]
+    fn character_buffer_insert_text_should_match_char_by_char_insertion() {
+        let cases = [
+            (0, 0, "X", (0, 1)),
+            (0, 4, "\n", (1, 0)),
+            (1, 8, "日本\n語", (2, 1)),
+            (2, 0, "a\nb\nc\n", (5, 0)),
+            (3, 9, "\n\n", (5, 0)),
+            (3, 99, "tail", (3, 13)),
+            (1, 3, "", (1, 3)),
+        ];
+
+        for (line, col, text, expected_end) in cases {
+            let mut expected = CharacterBuffer::from_lines(&sample_lines());
+            let clamped_col = col.min(expected.get_line(line).unwrap().char_count());
+            insert_text_by_char(&mut expected, line, clamped_col, text);
+
+            let mut buffer = CharacterBuffer::from_lines(&sample_lines());
+            let end = buffer.insert_text(line, col, text);
+
+            assert_eq!(
+                buffer.to_string_lines(),
+                expected.to_string_lines(),
+                "inserting {text:?} at ({line}, {col})"
+            );
+            assert_eq!(end, expected_end, "inserting {text:?} at ({line}, {col})");
+        }
+    }
+
+    #[test]
+    fn character_buffer_delete_range_should_match_char_by_char_deletion() {
+        let cases = [
+            ((0, 0), (0, 4)),
+            ((0, 4), (1, 3)),
+            ((0, 10), (1, 0)),
+            ((1, 8), (3, 2)),
+            ((0, 0), (3, 9)),
+            ((2, 0), (2, 0)),
+            ((1, 20), (1, 30)),
+        ];
+
+        for (start, end) in cases {
+            let mut expected = CharacterBuffer::from_lines(&sample_lines());
+            delete_range_by_char(&mut expected, start, end);
+
+            let mut buffer = CharacterBuffer::from_lines(&sample_lines());
+            buffer.delete_range(start, end);
+
+            assert_eq!(
+                buffer.to_string_lines(),
+                expected.to_string_lines(),
+                "deleting {start:?}..{end:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn character_buffer_should_splice_many_lines_into_large_buffer() {
+        let lines: Vec<String> = (0..100_000).map(|i| format!("line {i}")).collect();
+        let mut buffer = CharacterBuffer::from_lines(&lines);
+        let pasted: String = (0..1_000).map(|i| format!("pasted {i}\n")).collect();
+
+        let end = buffer.insert_text(50_000, 0, &pasted);
+
+        assert_eq!(end, (51_000, 0));
+        assert_eq!(buffer.line_count(), 101_000);
+        assert_eq!(buffer.get_line(50_000).unwrap().to_string(), "pasted 0");
+        assert_eq!(buffer.get_line(50_999).unwrap().to_string(), "pasted 999");
+        assert_eq!(buffer.get_line(51_000).unwrap().to_string(), "line 50000");
+
+        buffer.delete_range((50_000, 0), (51_000, 0));
+        assert_eq!(buffer.to_string_lines(), lines);
+    }
 }
@@ -83,19 +83,9 @@ impl BufferContent {
 
     /// Insert text at position, returning event
     pub fn insert_text(&mut self, pane: Pane, position: LogicalPosition, text: &str) -> ModelEvent {
-        // Use CharacterBuffer's character-aware insertion
-        let mut current_line = position.line;
-        let mut current_col = position.column;
-
-        for ch in text.chars() {
-            self.buffer.insert_char(current_line, current_col, ch);
-            if ch == '\n' {
-                current_line += 1;
-                current_col = 0;
-            } else {
-                current_col += 1;
-            }
-        }
+        // Splice the text in with one pass over the buffer, however many lines it spans
+        self.buffer
+            .insert_text(position.line, position.column, text);
 
         ModelEvent::TextInserted {
             pane,
@@ -116,42 +106,11 @@ impl BufferContent {
             return None; // Nothing to delete
         }
 
-        // Use CharacterBuffer's character-aware deletion
-        // Delete character by character from end to start of range
-
-        // Handle single-line deletion separately to avoid incorrect line joining
-        if range.start.line == range.end.line {
-            // Deletion within a single line
-            let mut pos = range.end.column;
-            while pos > range.start.column {
-                pos -= 1;
-                self.buffer.delete_char(range.start.line, pos);
-            }
-        } else {
-            // Multi-line deletion
-            let mut current_pos = range.end;
-            while current_pos != range.start {
-                // Move backwards through the range
-                if current_pos.column > 0 {
-                    current_pos.column -= 1;
-                    self.buffer
-                        .delete_char(current_pos.line, current_pos.column);
-                } else if current_pos.line > range.start.line {
-                    // Move to end of previous line
-                    current_pos.line -= 1;
-                    if let Some(line) = self.buffer.get_line(current_pos.line) {
-                        current_pos.column = line.char_count();
-                        if current_pos.line + 1 < self.buffer.line_count() {
-                            // Join the current line with the next line (removes the newline)
-                            self.buffer
-                                .join_lines(current_pos.line, current_pos.line + 1);
-                        }
-                    }
-                } else {
-                    break;
-                }
-            }
-        }
+        // Remove the whole range at once, joining its first and last lines
+        self.buffer.delete_range(
+            (range.start.line, range.start.column),
+            (range.end.line, range.end.column),
+        );
 
         Some(ModelEvent::TextDeleted { pane, range })
     }
@@ -178,10 +137,11 @@ impl BufferContent {
 
     /// Set entire content from string
     pub fn set_text(&mut self, text: &str) {
-        let lines: Vec<String> = if text.is_empty() {
-            vec![String::new()]
+        // Borrow the lines rather than copying them; only the buffer keeps the text
+        let lines: Vec<&str> = if text.is_empty() {
+            vec![""]
         } else {
-            text.lines().map(|s| s.to_string()).collect()
+            text.lines().collect()
         };
         self.buffer = CharacterBuffer::from_lines(&lines);
     }
@@ -2,31 +2,51 @@
 //!
 //! Provides display line caching for efficient word wrap rendering and cursor positioning.
 //! Maps logical lines to display lines with position tracking for navigation.
+//!
+//! Only the layout (which logical columns land on which display line) is computed
+//! up front. The `DisplayLine`s themselves are built the first time they are asked
+//! for, so loading a huge response costs little more than the rows on screen.
 
-use crate::repl::models::buffer_char::BufferLine;
+use crate::repl::models::buffer_char::{BufferChar, BufferLine};
+use crate::repl::models::display_char::DisplayChar;
 use crate::repl::models::display_line::DisplayLine;
 use crate::repl::models::geometry::Position;
-use std::collections::HashMap;
+use crate::text::word_segmenter::WordSegmenterFactory;
+use std::ops::Range;
+use std::sync::OnceLock;
 use std::time::Instant;
+use unicode_width::UnicodeWidthChar;
 
 /// Type alias for display position (display_line, display_column)
 pub type DisplayPosition = Position;
 
-/// Type alias for logical-to-display line mapping
-pub type LogicalToDisplayMap = HashMap<usize, Vec<usize>>;
-
 /// Type alias for logical position (logical_line, logical_column)  
 pub type LogicalPosition = Position;
 
-/// Cache for pre-calculated display lines with fast lookup
+/// Part of a logical line shown on one display line
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DisplaySegment {
+    logical_line: usize,
+    logical_start_col: usize,
+    logical_end_col: usize,
+    is_continuation: bool,
+}
+
+/// Cache of display lines with fast lookup, built lazily
 #[derive(Debug, Clone)]
 pub struct DisplayCache {
-    /// All display lines in order
-    pub display_lines: Vec<DisplayLine>,
-    /// Map logical line index to display line indices
-    pub logical_to_display: LogicalToDisplayMap,
-    /// Total number of display lines
-    pub total_display_lines: usize,
+    /// Layout of every display line in order
+    segments: Vec<DisplaySegment>,
+    /// Display lines, each built on first access
+    display_lines: Vec<OnceLock<DisplayLine>>,
+    /// First display line of each logical line, followed by the display line count
+    line_starts: Vec<usize>,
+    /// Logical lines the display lines are built from
+    source_lines: Vec<BufferLine>,
+    /// Logical lines with word boundaries calculated, filled on first access
+    word_lines: Vec<OnceLock<BufferLine>>,
+    /// Tab width used when building display characters
+    tab_width: usize,
     /// Content width used for this cache
     pub content_width: usize,
     /// Content hash for invalidation detection
@@ -43,9 +63,12 @@ impl DisplayCache {
     /// Create a new empty cache
     pub fn new() -> Self {
         Self {
+            segments: Vec::new(),
             display_lines: Vec::new(),
-            logical_to_display: HashMap::new(),
-            total_display_lines: 0,
+            line_starts: vec![0],
+            source_lines: Vec::new(),
+            word_lines: Vec::new(),
+            tab_width: 0,
             content_width: 0,
             content_hash: 0,
             generated_at: Instant::now(),
@@ -54,6 +77,124 @@ impl DisplayCache {
         }
     }
 
+    /// Lay out buffer lines into display lines without building them yet
+    ///
+    /// Wrapping is computed for every line here because it is cheap and the
+    /// display line count depends on it; display characters and word boundaries
+    /// are left for `get_display_line`.
+    pub fn from_buffer_lines(
+        source_lines: Vec<BufferLine>,
+        content_width: usize,
+        wrap_enabled: bool,
+        tab_width: usize,
+    ) -> Self {
+        let mut segments = Vec::with_capacity(source_lines.len());
+        let mut line_starts = Vec::with_capacity(source_lines.len() + 1);
+
+        for (logical_line, line) in source_lines.iter().enumerate() {
+            line_starts.push(segments.len());
+            segments.extend(layout_line(logical_line, line, content_width, wrap_enabled));
+        }
+        line_starts.push(segments.len());
+
+        Self {
+            display_lines: (0..segments.len()).map(|_| OnceLock::new()).collect(),
+            word_lines: (0..source_lines.len()).map(|_| OnceLock::new()).collect(),
+            segments,
+            line_starts,
+            source_lines,
+            tab_width,
+            content_width,
+            content_hash: 0, // Not used with eager invalidation strategy
+            generated_at: Instant::now(),
+            is_valid: true,
+            wrap_enabled,
+        }
+    }
+
+    /// Check whether this cache was laid out from buffer lines with these settings
+    ///
+    /// Only such a cache can be patched with [`Self::replace_lines`]; any other
+    /// has to be rebuilt.
+    pub fn is_layout_for(
+        &self,
+        content_width: usize,
+        wrap_enabled: bool,
+        tab_width: usize,
+    ) -> bool {
+        self.is_valid
+            && self.content_width == content_width
+            && self.wrap_enabled == wrap_enabled
+            && self.tab_width == tab_width
+            && self.line_starts.len() == self.source_lines.len() + 1
+    }
+
+    /// Get the logical lines this cache was laid out from
+    pub fn source_lines(&self) -> &[BufferLine] {
+        &self.source_lines
+    }
+
+    /// Get the number of logical lines laid out in this cache
+    pub fn logical_line_count(&self) -> usize {
+        self.line_starts.len() - 1
+    }
+
+    /// Replace a run of logical lines after an edit, relaying out only those lines
+    ///
+    /// `lines` is the range of old logical lines that changed and `new_lines` what
+    /// took their place, so an edit may add or remove lines. Display lines after
+    /// the edit keep their layout and are shifted; the ones already built are
+    /// dropped only if their display or logical index moved.
+    pub fn replace_lines(&mut self, lines: Range<usize>, new_lines: Vec<BufferLine>) {
+        let first_display_idx = self.line_starts[lines.start];
+        let end_display_idx = self.line_starts[lines.end];
+
+        let mut new_segments = Vec::new();
+        let mut new_line_starts = Vec::with_capacity(new_lines.len());
+        for (offset, line) in new_lines.iter().enumerate() {
+            new_line_starts.push(first_display_idx + new_segments.len());
+            new_segments.extend(layout_line(
+                lines.start + offset,
+                line,
+                self.content_width,
+                self.wrap_enabled,
+            ));
+        }
+
+        let line_delta = new_lines.len() as isize - lines.len() as isize;
+        let display_delta =
+            new_segments.len() as isize - (end_display_idx - first_display_idx) as isize;
+        let new_segment_count = new_segments.len();
+
+        // Shift everything after the edit before splicing the new lines in
+        for segment in &mut self.segments[end_display_idx..] {
+            segment.logical_line = (segment.logical_line as isize + line_delta) as usize;
+        }
+        for line_start in &mut self.line_starts[lines.end..] {
+            *line_start = (*line_start as isize + display_delta) as usize;
+        }
+
+        self.segments
+            .splice(first_display_idx..end_display_idx, new_segments);
+        self.line_starts
+            .splice(lines.start..lines.end, new_line_starts);
+        self.display_lines.splice(
+            first_display_idx..end_display_idx,
+            (0..new_segment_count).map(|_| OnceLock::new()),
+        );
+        self.word_lines
+            .splice(lines.clone(), (0..new_lines.len()).map(|_| OnceLock::new()));
+        self.source_lines.splice(lines, new_lines);
+
+        // Built display lines record their own display and logical indices
+        if line_delta != 0 || display_delta != 0 {
+            for cell in &mut self.display_lines[first_display_idx + new_segment_count..] {
+                cell.take();
+            }
+        }
+        self.generated_at = Instant::now();
+    }
+
     /// Check if cache is valid for given content, width, and wrap mode
     pub fn is_valid_for(&self, content_hash: u64, width: usize, wrap_enabled: bool) -> bool {
         self.is_valid
@@ -84,27 +225,28 @@ impl DisplayCache {
         }
 
         // Find display lines for this logical line
-        let display_line_indices = self.logical_to_display.get(&logical_line)?;
+        let first_display_idx = *self.line_starts.get(logical_line)?;
+        let end_display_idx = *self.line_starts.get(logical_line + 1)?;
 
         // Find which display line contains the logical column
-        for &display_idx in display_line_indices {
-            let display_line = self.display_lines.get(display_idx)?;
+        for display_idx in first_display_idx..end_display_idx {
+            let segment = &self.segments[display_idx];
 
             // Check if this display line contains the logical column
-            if logical_col >= display_line.logical_start_col
-                && logical_col <= display_line.logical_end_col
-            {
+            if logical_col >= segment.logical_start_col && logical_col <= segment.logical_end_col {
                 // Map the logical column to display column using character display widths
                 // Adjust logical_col to be relative to this display line's start
-                let relative_col = logical_col - display_line.logical_start_col;
+                let display_line = self.get_display_line(display_idx)?;
+                let relative_col = logical_col - segment.logical_start_col;
                 let display_col = display_line.logical_index_to_display_col(relative_col);
                 return Some(Position::new(display_idx, display_col));
             }
         }
 
         // If we couldn't find an exact match, position at end of last segment
-        if let Some(&last_display_idx) = display_line_indices.last() {
-            if let Some(last_display_line) = self.display_lines.get(last_display_idx) {
+        if end_display_idx > first_display_idx {
+            let last_display_idx = end_display_idx - 1;
+            if let Some(last_display_line) = self.get_display_line(last_display_idx) {
                 let display_width = last_display_line.display_width();
                 return Some(Position::new(last_display_idx, display_width));
             }
@@ -129,20 +271,29 @@ impl DisplayCache {
             return None;
         }
 
-        let line_info = self.display_lines.get(display_line)?;
+        let line_info = self.get_display_line(display_line)?;
         let logical_index = line_info.display_col_to_logical_index(display_col);
 
         Some(Position::new(line_info.logical_line, logical_index))
     }
 
-    /// Get a specific display line by index
+    /// Get a specific display line by index, building it on first access
     pub fn get_display_line(&self, index: usize) -> Option<&DisplayLine> {
-        self.display_lines.get(index)
+        let cell = self.display_lines.get(index)?;
+        Some(cell.get_or_init(|| self.build_display_line(index)))
     }
 
     /// Get the count of display lines
     pub fn display_line_count(&self) -> usize {
-        self.total_display_lines
+        self.segments.len()
+    }
+
+    /// Get the number of display lines built so far
+    pub fn built_line_count(&self) -> usize {
+        self.display_lines
+            .iter()
+            .filter(|cell| cell.get().is_some())
+            .count()
     }
 
     /// Move cursor up one display line
@@ -174,6 +325,44 @@ impl DisplayCache {
 
         Some(Position::new(new_row, new_col))
     }
+
+    /// Build the display line for one segment from its source line
+    fn build_display_line(&self, index: usize) -> DisplayLine {
+        let segment = self.segments[index];
+        let buffer_line = self.word_line(segment.logical_line);
+
+        // Create DisplayChars from the segment, preserving word boundaries
+        let mut display_chars = Vec::new();
+        let mut current_screen_col = 0;
+        for logical_col in segment.logical_start_col..segment.logical_end_col {
+            if let Some(buffer_char) = buffer_line.get_char(logical_col) {
+                let display_char = DisplayChar::from_buffer_char_with_tab_width(
+                    buffer_char.clone(),
+                    (index, current_screen_col),
+                    self.tab_width,
+                );
+                current_screen_col += display_char.display_width();
+                display_chars.push(display_char);
+            }
+        }
+
+        DisplayLine::new(
+            display_chars,
+            segment.logical_line,
+            segment.logical_start_col,
+            segment.logical_end_col,
+            segment.is_continuation,
+        )
+    }
+
+    /// Get a source line with its word boundaries calculated
+    fn word_line(&self, logical_line: usize) -> &BufferLine {
+        self.word_lines[logical_line].get_or_init(|| {
+            let mut line = self.source_lines[logical_line].clone();
+            line.get_word_boundaries(WordSegmenterFactory::create().as_ref());
+            line
+        })
+    }
 }
 
 impl Default for DisplayCache {
@@ -194,6 +383,109 @@ pub fn calculate_content_hash(lines: &[String]) -> u64 {
     hasher.finish()
 }
 
+/// Lay out one logical line as display segments
+fn layout_line(
+    logical_line: usize,
+    line: &BufferLine,
+    content_width: usize,
+    wrap_enabled: bool,
+) -> impl Iterator<Item = DisplaySegment> {
+    let spans = if wrap_enabled {
+        wrap_segments(line.chars(), content_width)
+    } else {
+        vec![(0, line.char_count())]
+    };
+    spans
+        .into_iter()
+        .enumerate()
+        .map(move |(segment_idx, (start, end))| DisplaySegment {
+            logical_line,
+            logical_start_col: start,
+            logical_end_col: end,
+            is_continuation: segment_idx > 0,
+        })
+}
+
+/// `(start, end)` character span of a line that fits on one display line
+type WrapSegment = (usize, usize);
+
+/// Split a line into `(start, end)` character spans that fit in `content_width`
+///
+/// Lines break after whitespace where possible. A line that exactly fills its
+/// last display line gets an empty continuation span so the cursor has
+/// somewhere to go past the end.
+fn wrap_segments(buffer_chars: &[BufferChar], content_width: usize) -> Vec<WrapSegment> {
+    let total_chars = buffer_chars.len();
+    if content_width == 0 {
+        return vec![(0, total_chars)];
+    }
+
+    let mut segments = Vec::new();
+    let mut current_char_pos = 0;
+
+    while current_char_pos < total_chars {
+        let mut current_display_width = 0;
+        let mut segment_end_char_pos = current_char_pos;
+        let mut last_word_boundary_char_pos = None;
+
+        // Find how many characters fit within content_width display columns
+        while segment_end_char_pos < total_chars && current_display_width < content_width {
+            let buffer_char = &buffer_chars[segment_end_char_pos];
+            let char_display_width = UnicodeWidthChar::width(buffer_char.ch).unwrap_or(0);
+
+            // Check if adding this character would exceed the content width
+            if current_display_width + char_display_width > content_width {
+                break;
+            }
+
+            // Mark word boundaries for better wrapping
+            if buffer_char.ch.is_whitespace() {
+                last_word_boundary_char_pos = Some(segment_end_char_pos + 1);
+            }
+
+            current_display_width += char_display_width;
+            segment_end_char_pos += 1;
+        }
+
+        // If we haven't advanced and we're not at the last character, force advance by one
+        // to prevent infinite loops with zero-width characters
+        if segment_end_char_pos == current_char_pos {
+            segment_end_char_pos = current_char_pos + 1;
+        }
+
+        // Try to break at word boundary if possible (only if we have more characters to process)
+        let actual_end = match last_word_boundary_char_pos {
+            Some(word_boundary)
+                if segment_end_char_pos < total_chars && word_boundary > current_char_pos =>
+            {
+                word_boundary
+            }
+            _ => segment_end_char_pos,
+        };
+
+        segments.push((current_char_pos, actual_end));
+        current_char_pos = actual_end;
+    }
+
+    // WRAP MODE CURSOR POSITIONING FIX: Create an empty continuation segment for cursor positioning
+    // When content exactly fills display lines, we need a place for the cursor to wrap to
+    if let Some(&(last_start, last_end)) = segments.last() {
+        let segment_display_width: usize = buffer_chars[last_start..last_end]
+            .iter()
+            .map(|bc| UnicodeWidthChar::width(bc.ch).unwrap_or(0))
+            .sum();
+        if segment_display_width == content_width {
+            segments.push((total_chars, total_chars));
+        }
+    }
+
+    if segments.is_empty() {
+        segments.push((0, 0));
+    }
+
+    segments
+}
+
 /// Segment of a wrapped line with position tracking
 struct WrappedSegment {
     content: String,
@@ -206,7 +498,7 @@ struct WrappedSegment {
 /// HIGH-LEVEL LOGIC:
 /// 1. Calculate content hash for cache invalidation
 /// 2. Process each logical line, potentially wrapping into multiple display lines
-/// 3. Build mapping from logical to display line indices
+/// 3. Record the first display line of each logical line
 /// 4. Create DisplayLine structures with proper positioning metadata
 /// 5. Return complete cache with all display lines and mappings
 ///
@@ -224,13 +516,14 @@ pub fn build_display_cache(
     }
 
     let content_hash = calculate_content_hash(lines);
+    let mut segments = Vec::new();
     let mut display_lines = Vec::new();
-    let mut logical_to_display = HashMap::new();
+    let mut line_starts = Vec::with_capacity(lines.len() + 1);
 
     for (logical_idx, line) in lines.iter().enumerate() {
-        let mut display_indices = Vec::new();
+        line_starts.push(display_lines.len());
 
-        let segments = if wrap_enabled && content_width > 0 {
+        let wrapped_segments = if wrap_enabled && content_width > 0 {
             wrap_line_with_positions(line, content_width)
         } else {
             vec![WrappedSegment {
@@ -240,10 +533,7 @@ pub fn build_display_cache(
             }]
         };
 
-        for (segment_idx, segment_info) in segments.iter().enumerate() {
-            let display_idx = display_lines.len();
-            display_indices.push(display_idx);
-
+        for (segment_idx, segment_info) in wrapped_segments.iter().enumerate() {
             // Create DisplayLine for this segment
             #[allow(deprecated)]
             let display_line = DisplayLine::from_content(
@@ -254,16 +544,24 @@ pub fn build_display_cache(
                 segment_idx > 0,
             );
 
-            display_lines.push(display_line);
+            segments.push(DisplaySegment {
+                logical_line: logical_idx,
+                logical_start_col: segment_info.logical_start,
+                logical_end_col: segment_info.logical_end,
+                is_continuation: segment_idx > 0,
+            });
+            display_lines.push(OnceLock::from(display_line));
         }
-
-        logical_to_display.insert(logical_idx, display_indices);
     }
+    line_starts.push(display_lines.len());
 
     Ok(DisplayCache {
-        total_display_lines: display_lines.len(),
+        segments,
         display_lines,
-        logical_to_display,
+        line_starts,
+        source_lines: Vec::new(),
+        word_lines: Vec::new(),
+        tab_width: 0,
         content_width,
         content_hash,
         generated_at: Instant::now(),
@@ -346,7 +644,7 @@ mod tests {
     fn display_cache_should_create_empty() {
         let cache = DisplayCache::new();
         assert!(!cache.is_valid);
-        assert_eq!(cache.display_lines.len(), 0);
+        assert_eq!(cache.display_line_count(), 0);
     }
 
     #[test]
@@ -356,11 +654,11 @@ mod tests {
 
         assert!(cache.is_valid);
         assert!(!cache.wrap_enabled);
-        assert_eq!(cache.display_lines.len(), 2);
-        assert_eq!(cache.display_lines[0].content(), "Line 1");
-        assert_eq!(cache.display_lines[1].content(), "Line 2");
-        assert!(!cache.display_lines[0].is_continuation);
-        assert!(!cache.display_lines[1].is_continuation);
+        assert_eq!(cache.display_line_count(), 2);
+        assert_eq!(cache.get_display_line(0).unwrap().content(), "Line 1");
+        assert_eq!(cache.get_display_line(1).unwrap().content(), "Line 2");
+        assert!(!cache.get_display_line(0).unwrap().is_continuation);
+        assert!(!cache.get_display_line(1).unwrap().is_continuation);
     }
 
     #[test]
@@ -370,14 +668,14 @@ mod tests {
 
         assert!(cache.is_valid);
         assert!(cache.wrap_enabled);
-        assert!(cache.display_lines.len() > 1); // Should be wrapped
+        assert!(cache.display_line_count() > 1); // Should be wrapped
 
         // First segment should not be continuation
-        assert!(!cache.display_lines[0].is_continuation);
+        assert!(!cache.get_display_line(0).unwrap().is_continuation);
 
         // Following segments should be continuations
-        for i in 1..cache.display_lines.len() {
-            assert!(cache.display_lines[i].is_continuation);
+        for i in 1..cache.display_line_count() {
+            assert!(cache.get_display_line(i).unwrap().is_continuation);
         }
     }
 
@@ -434,4 +732,150 @@ mod tests {
         assert!(!cache.is_valid_for(calculate_content_hash(&lines), 60, false)); // Different width
         assert!(!cache.is_valid_for(calculate_content_hash(&lines), 80, true)); // Different wrap mode
     }
+
+    fn buffer_lines(count: usize) -> Vec<BufferLine> {
+        (0..count)
+            .map(|i| BufferLine::from_string(&format!("{{\"id\": {i}, \"name\": \"user {i}\"}}")))
+            .collect()
+    }
+
+    #[test]
+    fn from_buffer_lines_should_not_build_display_lines_up_front() {
+        let cache = DisplayCache::from_buffer_lines(buffer_lines(100_000), 80, false, 4);
+
+        assert_eq!(cache.display_line_count(), 100_000);
+        assert_eq!(cache.built_line_count(), 0);
+
+        // Rendering a screenful only builds the rows on screen
+        for row in 50_000..50_040 {
+            cache.get_display_line(row).unwrap();
+        }
+        assert_eq!(cache.built_line_count(), 40);
+    }
+
+    #[test]
+    fn from_buffer_lines_should_map_far_rows_without_wrap() {
+        let cache = DisplayCache::from_buffer_lines(buffer_lines(100_000), 80, false, 4);
+
+        let line = cache.get_display_line(99_999).unwrap();
+        assert_eq!(line.logical_line, 99_999);
+        assert_eq!(line.content(), "{\"id\": 99999, \"name\": \"user 99999\"}");
+        // Word boundaries are calculated when the line is first built
+        assert!(
+            line.char_at_display_col(2)
+                .unwrap()
+                .buffer_char
+                .is_word_start
+        );
+
+        let pos = cache.logical_to_display_position(75_000, 3).unwrap();
+        assert_eq!((pos.row, pos.col), (75_000, 3));
+        let pos = cache.display_to_logical_position(75_000, 3).unwrap();
+        assert_eq!((pos.row, pos.col), (75_000, 3));
+        assert!(cache.move_down(99_999, 0).is_none());
+    }
+
+    #[test]
+    fn from_buffer_lines_should_map_far_rows_with_wrap() {
+        // Each 32-character line wraps into three display lines at width 15
+        let lines = (0..100_000)
+            .map(|_| BufferLine::from_string("alpha beta gamma delta epsilon z"))
+            .collect();
+        let cache = DisplayCache::from_buffer_lines(lines, 15, true, 4);
+
+        assert_eq!(cache.display_line_count(), 300_000);
+        assert_eq!(cache.built_line_count(), 0);
+
+        let pos = cache.logical_to_display_position(90_000, 17).unwrap();
+        assert_eq!((pos.row, pos.col), (270_001, 6));
+        let pos = cache.display_to_logical_position(270_001, 6).unwrap();
+        assert_eq!((pos.row, pos.col), (90_000, 17));
+
+        let line = cache.get_display_line(270_002).unwrap();
+        assert_eq!(line.logical_line, 90_000);
+        assert!(line.is_continuation);
+        assert_eq!(line.content(), "epsilon z");
+
+        // Scrolling down crosses into the next logical line
+        let pos = cache.move_down(270_002, 4).unwrap();
+        assert_eq!(pos.row, 270_003);
+        assert!(!cache.get_display_line(270_003).unwrap().is_continuation);
+    }
+
+    fn numbered_lines(count: usize) -> Vec<BufferLine> {
+        (0..count)
+            .map(|i| BufferLine::from_string(&format!("line {i}")))
+            .collect()
+    }
+
+    #[test]
+    fn replace_lines_should_relayout_only_edited_line() {
+        let mut cache = DisplayCache::from_buffer_lines(numbered_lines(1000), 20, true, 4);
+        for row in [0, 1, 2, 3, 500] {
+            cache.get_display_line(row).unwrap();
+        }
+
+        // Line 5 grows from one display line to three
+        let edited = BufferLine::from_string("line 4 now wraps onto three display lines");
+        cache.replace_lines(4..5, vec![edited]);
+
+        assert_eq!(cache.logical_line_count(), 1000);
+        assert_eq!(cache.display_line_count(), 1002);
+        // Rows before the edit stay built; rows after it moved and are rebuilt on demand
+        assert_eq!(cache.built_line_count(), 4);
+
+        let contents: Vec<String> = (4..7)
+            .map(|row| cache.get_display_line(row).unwrap().content())
+            .collect();
+        assert_eq!(
+            contents,
+            ["line 4 now wraps ", "onto three display ", "lines"]
+        );
+        assert!(cache.get_display_line(6).unwrap().is_continuation);
+
+        let pos = cache.logical_to_display_position(500, 2).unwrap();
+        assert_eq!((pos.row, pos.col), (502, 2));
+        let pos = cache.display_to_logical_position(502, 2).unwrap();
+        assert_eq!((pos.row, pos.col), (500, 2));
+        assert_eq!(cache.get_display_line(7).unwrap().content(), "line 5");
+    }
+
+    #[test]
+    fn replace_lines_should_keep_later_rows_when_layout_does_not_move() {
+        let mut cache = DisplayCache::from_buffer_lines(numbered_lines(1000), 20, true, 4);
+        cache.get_display_line(500).unwrap();
+
+        cache.replace_lines(4..5, vec![BufferLine::from_string("line four")]);
+
+        assert_eq!(cache.built_line_count(), 1);
+        assert_eq!(cache.get_display_line(4).unwrap().content(), "line four");
+        assert_eq!(cache.get_display_line(500).unwrap().content(), "line 500");
+    }
+
+    #[test]
+    fn replace_lines_should_shift_lines_after_split_and_join() {
+        let mut cache = DisplayCache::from_buffer_lines(numbered_lines(1000), 80, false, 4);
+
+        // Splitting line 5 in two
+        cache.replace_lines(
+            4..5,
+            vec![
+                BufferLine::from_string("li"),
+                BufferLine::from_string("ne 4"),
+            ],
+        );
+        assert_eq!(cache.logical_line_count(), 1001);
+        let line = cache.get_display_line(999).unwrap();
+        assert_eq!(
+            (line.logical_line, line.content()),
+            (999, "line 998".to_string())
+        );
+
+        // Joining it back together
+        cache.replace_lines(4..6, vec![BufferLine::from_string("line 4")]);
+        assert_eq!(cache.logical_line_count(), 1000);
+        let pos = cache.logical_to_display_position(999, 0).unwrap();
+        assert_eq!(pos.row, 999);
+        assert_eq!(cache.get_display_line(999).unwrap().content(), "line 999");
+    }
 }
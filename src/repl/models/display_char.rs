@@ -53,7 +53,7 @@ impl DisplayChar {
                 tab_width
             }
             '\t' => 0, // Backward compatibility: zero width for tabs when tab_width = 0
-            _ => UnicodeWidthChar::width(buffer_char.ch).unwrap_or(0),
+            ch => char_width(ch),
         };
 
         Self {
@@ -206,6 +206,29 @@ impl DisplayChar {
     }
 }
 
+/// Caret notation shown for a control character (`^[` for Escape, `^?` for Delete)
+///
+/// Control characters get into the buffer with Ctrl-v in insert mode and must
+/// never reach the terminal raw. Tabs and newlines are laid out instead.
+pub fn caret_notation(ch: char) -> Option<String> {
+    if !ch.is_control() || ch == '\t' || ch == '\n' {
+        return None;
+    }
+    Some(match ch as u32 {
+        0x7f => "^?".to_string(),
+        code if code < 0x20 => format!("^{}", char::from(code as u8 + b'@')),
+        code => format!("<{code:02x}>"),
+    })
+}
+
+/// Terminal columns taken by a character other than a tab
+pub fn char_width(ch: char) -> usize {
+    match caret_notation(ch) {
+        Some(notation) => notation.len(),
+        None => UnicodeWidthChar::width(ch).unwrap_or(0),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -301,4 +324,18 @@ mod tests {
         );
         assert_eq!(display_char.display_width(), 0); // Zero width for backward compatibility
     }
+
+    #[test]
+    fn control_characters_should_show_in_caret_notation() {
+        assert_eq!(caret_notation('\x1b').as_deref(), Some("^["));
+        assert_eq!(caret_notation('\x01').as_deref(), Some("^A"));
+        assert_eq!(caret_notation('\x7f').as_deref(), Some("^?"));
+        assert_eq!(caret_notation('\u{85}').as_deref(), Some("<85>"));
+        assert_eq!(caret_notation('\t'), None);
+        assert_eq!(caret_notation('é'), None);
+
+        assert_eq!(char_width('\x1b'), 2);
+        assert_eq!(char_width('é'), 1);
+        assert_eq!(char_width('世'), 2);
+    }
 }
@@ -119,42 +119,33 @@ impl DisplayLine {
         self.display_width()
     }
 
-    /// Build a character positions array mapping display columns to characters
-    fn build_character_positions(&self) -> Vec<CharPosition> {
-        let mut char_positions = Vec::new();
-        let mut current_pos = 0;
-        for display_char in &self.chars {
-            char_positions.push((current_pos, display_char));
-            current_pos += display_char.display_width();
-        }
-        char_positions
+    /// Iterate over the characters with the display column each one starts at
+    ///
+    /// Word motions scan this in place rather than collecting it, so moving
+    /// through a very long line does not allocate on every keystroke.
+    fn character_positions(&self) -> impl Iterator<Item = CharPosition<'_>> {
+        self.chars.iter().scan(0, |current_pos, display_char| {
+            let pos = *current_pos;
+            *current_pos += display_char.display_width();
+            Some((pos, display_char))
+        })
     }
 
     /// Find the character index corresponding to a display column
-    fn find_character_index(&self, char_positions: &[CharPosition], display_col: usize) -> usize {
-        let mut current_index = 0;
-        for (i, &(pos, dc)) in char_positions.iter().enumerate() {
-            let char_end = pos + dc.display_width();
-            // Check if this character contains the display column
-            if pos <= display_col && display_col < char_end {
-                current_index = i;
-                break;
-            }
-            // If we're past the last character, use the last index
-            if i == char_positions.len() - 1 {
-                current_index = i;
-            }
-        }
-        current_index
+    ///
+    /// Falls back to the last character when no character spans the column.
+    fn find_character_index(&self, display_col: usize) -> usize {
+        self.character_positions()
+            .position(|(pos, dc)| pos <= display_col && display_col < pos + dc.display_width())
+            .unwrap_or(self.chars.len().saturating_sub(1))
     }
 
     /// Find the next word start from the current display column position using unicode-segmentation
     ///
     /// HIGH-LEVEL LOGIC:
-    /// 1. Build array of (display_position, display_char) pairs for efficient lookup
-    /// 2. Find the character index that corresponds to current_display_col
-    /// 3. Search forward for the next character marked with is_word_start flag
-    /// 4. Return the display column of that word start, or None if not found
+    /// 1. Find the character index that corresponds to current_display_col
+    /// 2. Search forward for the next character marked with is_word_start flag
+    /// 3. Return the display column of that word start, or None if not found
     pub fn find_next_word_start(&self, current_display_col: usize) -> Option<usize> {
         tracing::debug!(
             "find_next_word_start: current_display_col={}, line_content='{}'",
@@ -162,52 +153,21 @@ impl DisplayLine {
             self.content().chars().take(50).collect::<String>()
         );
 
-        // Build character positions array for efficient lookup
-        let char_positions = self.build_character_positions();
-        if char_positions.is_empty() {
-            tracing::debug!("find_next_word_start: empty line, returning None");
-            return None;
-        }
-
-        // Find character index corresponding to current display column
-        // Note: For forward search, we use simpler logic to find starting position
-        let mut current_index = 0;
-        for (i, &(pos, _)) in char_positions.iter().enumerate() {
-            if pos >= current_display_col {
-                current_index = i;
-                break;
-            }
-        }
-
-        tracing::debug!(
-            "find_next_word_start: current_index={}, searching from char '{}'",
-            current_index,
-            char_positions
-                .get(current_index)
-                .map_or('?', |(_, dc)| dc.ch())
-        );
-
-        // Search forward for next word start using unicode-segmentation flags
-        #[allow(clippy::needless_range_loop)] // Index needed for position lookup
-        for i in (current_index + 1)..char_positions.len() {
-            let display_char = char_positions[i].1;
-            tracing::debug!(
-                "find_next_word_start: checking char at index {} (display_col={}): '{}', is_word_start={}",
-                i, char_positions[i].0, display_char.ch(), display_char.buffer_char.is_word_start
-            );
-            // WORD START CHECK: unicode-segmentation marked this character as starting a new word
-            if display_char.buffer_char.is_word_start {
-                tracing::debug!(
-                    "find_next_word_start: found word start at display_col={}, char='{}'",
-                    char_positions[i].0,
-                    display_char.ch()
-                );
-                return Some(char_positions[i].0);
-            }
-        }
-
-        tracing::debug!("find_next_word_start: no word start found, returning None");
-        None
+        // Forward search starts at the first character at or after the column
+        let current_index = self
+            .character_positions()
+            .position(|(pos, _)| pos >= current_display_col)
+            .unwrap_or(0);
+
+        // WORD START CHECK: unicode-segmentation marked the character as starting a new word
+        let word_start = self
+            .character_positions()
+            .skip(current_index + 1)
+            .find(|(_, dc)| dc.buffer_char.is_word_start)
+            .map(|(pos, _)| pos);
+
+        tracing::debug!("find_next_word_start: found {:?}", word_start);
+        word_start
     }
 
     /// Find the previous word start from the current display column position using unicode-segmentation
@@ -218,59 +178,27 @@ impl DisplayLine {
             self.content().chars().take(50).collect::<String>()
         );
 
-        // Don't return early at position 0 - we might need to check if position 0 is a word start
-        // when moving from position 1 back to position 0
-
-        // Build character positions array
-        let char_positions = self.build_character_positions();
-        if char_positions.is_empty() {
-            tracing::debug!("find_previous_word_start: empty line, returning None");
-            return None;
-        }
-
-        // Find current character index - needs special logic for backward search
-        let current_index = self.find_character_index(&char_positions, current_display_col);
-
-        tracing::debug!(
-            "find_previous_word_start: current_index={}, searching backwards from char '{}'",
-            current_index,
-            char_positions
-                .get(current_index)
-                .map_or('?', |(_, dc)| dc.ch())
-        );
-
-        // Look backwards for previous word start using unicode-segmentation boundaries
-        // Vim 'b' behavior: move to beginning of current or previous word
-        // Fix: Include all positions from current_index-1 down to 0 to reach first character
-        if current_index > 0 {
-            for i in (0..current_index).rev() {
-                let display_char = char_positions[i].1;
-                if display_char.buffer_char.is_word_start {
-                    // Skip whitespace-only word starts - we want actual word starts
-                    let ch = display_char.ch();
-                    if !ch.is_whitespace() {
-                        tracing::debug!(
-                            "find_previous_word_start: found word start at display_col={}, char='{}'",
-                            char_positions[i].0,
-                            display_char.ch()
-                        );
-                        return Some(char_positions[i].0);
-                    }
-                }
-            }
+        let first_char = self.chars.first()?;
+        let current_index = self.find_character_index(current_display_col);
+
+        // Vim 'b' behavior: move to beginning of current or previous word, which is
+        // the last non-whitespace word start before the cursor
+        let word_start = self
+            .character_positions()
+            .take(current_index)
+            .filter(|(_, dc)| dc.buffer_char.is_word_start && !dc.ch().is_whitespace())
+            .last()
+            .map(|(pos, _)| pos);
+        if word_start.is_some() {
+            tracing::debug!("find_previous_word_start: found {:?}", word_start);
+            return word_start;
         }
 
         // Special case: if position 0 is not marked as word_start but contains a non-whitespace character,
         // it should be considered a valid word start (for lines that start with words)
-        if !char_positions.is_empty() {
-            let first_char = char_positions[0].1;
-            if !first_char.ch().is_whitespace() && current_display_col > 0 {
-                tracing::debug!(
-                    "find_previous_word_start: falling back to position 0 as word start, char='{}'",
-                    first_char.ch()
-                );
-                return Some(0);
-            }
+        if !first_char.ch().is_whitespace() && current_display_col > 0 {
+            tracing::debug!("find_previous_word_start: falling back to position 0 as word start");
+            return Some(0);
         }
 
         tracing::debug!("find_previous_word_start: no word start found, returning None");
@@ -285,143 +213,50 @@ impl DisplayLine {
             self.content().chars().take(50).collect::<String>()
         );
 
-        // Build character positions array
-        let char_positions = self.build_character_positions();
-        if char_positions.is_empty() {
+        if self.chars.is_empty() {
             tracing::debug!("find_next_word_end: empty line, returning None");
             return None;
         }
+        let current_index = self.find_character_index(current_display_col);
 
-        // Find current character index - need to find which character contains the display column
-        let current_index = self.find_character_index(&char_positions, current_display_col);
-
-        tracing::debug!(
-            "find_next_word_end: current_index={}, searching from char '{}'",
-            current_index,
-            char_positions
-                .get(current_index)
-                .map_or('?', |(_, dc)| dc.ch())
-        );
-
-        // Look for next word end using unicode-segmentation boundaries
         // Vim 'e' behavior: move to end of current or next word
         // If we're already at a word end, skip to the next word end
-        let mut start_index = current_index;
-
-        // Check if we're already at a word end position
-        if current_index < char_positions.len() {
-            let current_char = char_positions[current_index].1;
-            if current_char.buffer_char.is_word_end {
-                // We're at a word end, so we need to find the next word end
-                tracing::debug!(
-                    "find_next_word_end: currently at word end '{}', searching for next word end",
-                    current_char.ch()
-                );
-                start_index = current_index + 1;
-            }
+        let start_index = if self.chars[current_index].buffer_char.is_word_end {
+            current_index + 1
+        } else {
+            current_index
+        };
+
+        // Skip whitespace/punctuation-only word ends - we want actual word ends
+        let word_end = self
+            .character_positions()
+            .skip(start_index)
+            .find(|(_, dc)| dc.buffer_char.is_word_end && dc.ch().is_alphanumeric())
+            .map(|(pos, _)| pos);
+        if word_end.is_some() {
+            tracing::debug!("find_next_word_end: found {:?}", word_end);
+            return word_end;
         }
 
-        #[allow(clippy::needless_range_loop)] // Index needed for position lookup
-        for i in start_index..char_positions.len() {
-            let display_char = char_positions[i].1;
-            if display_char.buffer_char.is_word_end {
-                // Skip whitespace/punctuation-only word ends - we want actual word ends
-                let ch = display_char.ch();
-                if ch.is_alphanumeric() || ch.is_alphabetic() {
-                    tracing::debug!(
-                        "find_next_word_end: found word end at display_col={}, char='{}'",
-                        char_positions[i].0,
-                        display_char.ch()
-                    );
-                    return Some(char_positions[i].0);
-                }
-            }
-        }
-
-        tracing::debug!(
-            "find_next_word_end: no unicode-segmentation word boundaries found, trying fallback"
-        );
-
-        // FALLBACK: Implement vim 'e' behavior with character-based detection
-        // Vim 'e' behavior:
-        // 1. If on whitespace/punctuation: skip to next word and find its end
-        // 2. If on word character: find end of current word
-        // 3. If already at end of word: skip to next word and find its end
-
-        // Make sure current_index is valid
-        if current_index >= char_positions.len() {
-            // We're past the end of the line
-            return None;
-        }
-
-        let mut pos = current_index;
-
-        if pos < char_positions.len() {
-            let current_char = char_positions[pos].1.ch();
-            if current_char.is_alphanumeric() || current_char.is_alphabetic() {
-                // We're on a word character - find end of current word first
-                let mut word_end_pos = pos;
-                while word_end_pos < char_positions.len() {
-                    let ch = char_positions[word_end_pos].1.ch();
-                    if !ch.is_alphanumeric() && !ch.is_alphabetic() {
-                        break;
-                    }
-                    word_end_pos += 1;
-                }
-                let current_word_end = word_end_pos.saturating_sub(1);
-
-                // Check if we're already at the end of the current word
-                if current_word_end == current_index {
-                    // Already at word end - skip to next word end
-                    pos = current_index + 1;
-                } else {
-                    // Not at word end - return current word end
-                    tracing::debug!(
-                        "find_next_word_end: fallback found current word end at display_col={}, char='{}'",
-                        char_positions[current_word_end].0,
-                        char_positions[current_word_end].1.ch()
-                    );
-                    return Some(char_positions[current_word_end].0);
-                }
-            } else {
-                // We're on whitespace or punctuation - skip to next alphanumeric word
-                pos = current_index + 1;
-            }
-        }
-
-        // Skip non-alphanumeric characters (whitespace and punctuation) to find next word
-        while pos < char_positions.len() {
-            let ch = char_positions[pos].1.ch();
-            if ch.is_alphanumeric() || ch.is_alphabetic() {
-                break;
-            }
-            pos += 1;
-        }
-
-        // Find end of next alphanumeric word
-        if pos < char_positions.len() {
-            // We've found the start of an alphanumeric word - find its end
-            while pos < char_positions.len() {
-                let ch = char_positions[pos].1.ch();
-                if !ch.is_alphanumeric() && !ch.is_alphabetic() {
-                    break;
-                }
-                pos += 1;
-            }
-            // Return the position of the last character of the word
-            if pos > 0 {
-                let end_pos = pos.saturating_sub(1);
-                tracing::debug!(
-                    "find_next_word_end: fallback found next word end at display_col={}, char='{}'",
-                    char_positions[end_pos].0,
-                    char_positions[end_pos].1.ch()
-                );
-                return Some(char_positions[end_pos].0);
-            }
-        }
-
-        tracing::debug!("find_next_word_end: no word end found even with fallback, returning None");
-        None
+        // FALLBACK: without word boundary flags, the end of a word is the last
+        // alphanumeric character of a run. Vim 'e' always moves forward, so the
+        // first such character after the cursor is the answer whether the cursor
+        // is inside a word, at its end, or between words.
+        let word_end = self
+            .character_positions()
+            .enumerate()
+            .skip(current_index + 1)
+            .find(|&(i, (_, dc))| {
+                dc.ch().is_alphanumeric()
+                    && !self
+                        .chars
+                        .get(i + 1)
+                        .is_some_and(|next| next.ch().is_alphanumeric())
+            })
+            .map(|(_, (pos, _))| pos);
+
+        tracing::debug!("find_next_word_end: fallback found {:?}", word_end);
+        word_end
     }
 
     /// Convert display column to logical character index within this display line
@@ -612,3 +447,66 @@ impl DisplayLine {
         self.chars.len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a display line with word boundary flags set at the given (sorted) character indices
+    fn display_line(text: &str, word_starts: &[usize], word_ends: &[usize]) -> DisplayLine {
+        let chars = BufferLine::from_string(text)
+            .chars()
+            .iter()
+            .enumerate()
+            .map(|(i, buffer_char)| {
+                let mut buffer_char = buffer_char.clone();
+                buffer_char.is_word_start = word_starts.binary_search(&i).is_ok();
+                buffer_char.is_word_end = word_ends.binary_search(&i).is_ok();
+                DisplayChar::from_buffer_char(buffer_char, (0, 0))
+            })
+            .collect();
+        DisplayLine::new(chars, 0, 0, text.chars().count(), false)
+    }
+
+    #[test]
+    fn word_motions_should_land_on_display_columns_of_multibyte_words() {
+        // Display columns: こんにちは 0-9, 世界 11-14, ok 16-17
+        let line = display_line("こんにちは 世界 ok", &[0, 6, 9], &[4, 7, 10]);
+
+        assert_eq!(line.find_next_word_start(0), Some(11));
+        assert_eq!(line.find_next_word_start(11), Some(16));
+        assert_eq!(line.find_next_word_start(16), None);
+
+        assert_eq!(line.find_previous_word_start(16), Some(11));
+        assert_eq!(line.find_previous_word_start(12), Some(0));
+        assert_eq!(line.find_previous_word_start(0), None);
+
+        assert_eq!(line.find_next_word_end(0), Some(8));
+        assert_eq!(line.find_next_word_end(8), Some(13));
+        assert_eq!(line.find_next_word_end(13), Some(17));
+        assert_eq!(line.find_next_word_end(17), None);
+    }
+
+    #[test]
+    fn find_next_word_end_should_fall_back_to_alphanumeric_runs_without_flags() {
+        // Display columns: abc 0-2, 日本 5-8
+        let line = display_line("abc, 日本", &[], &[]);
+
+        assert_eq!(line.find_next_word_end(0), Some(2));
+        assert_eq!(line.find_next_word_end(2), Some(7));
+        assert_eq!(line.find_next_word_end(3), Some(7));
+        assert_eq!(line.find_next_word_end(7), None);
+    }
+
+    #[test]
+    fn word_motions_should_work_in_the_middle_of_a_long_line() {
+        let words = 100_000;
+        let word_starts: Vec<usize> = (0..words).map(|i| i * 5).collect();
+        let word_ends: Vec<usize> = (0..words).map(|i| i * 5 + 3).collect();
+        let line = display_line(&"word ".repeat(words), &word_starts, &word_ends);
+
+        assert_eq!(line.find_next_word_start(250_000), Some(250_005));
+        assert_eq!(line.find_previous_word_start(250_000), Some(249_995));
+        assert_eq!(line.find_next_word_end(250_000), Some(250_003));
+    }
+}
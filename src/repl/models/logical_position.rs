@@ -20,6 +20,9 @@ impl LogicalPosition {
     }
 }
 
+/// First and last line of a block of whole lines, both inclusive
+pub type LineSpan = (usize, usize);
+
 /// Range in logical coordinates for text operations
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct LogicalRange {
@@ -17,7 +17,9 @@ pub mod response_model;
 pub mod screen_buffer;
 pub mod selection;
 pub mod status_line;
+pub mod undo_history;
 pub mod yank_buffer;
+pub mod yank_highlight;
 
 // Re-export all models for easy access
 pub use buffer_char::{BufferChar, BufferLine, CharacterBuffer};
@@ -26,13 +28,19 @@ pub use display_cache::{build_display_cache, DisplayCache, DisplayPosition};
 pub use display_char::DisplayChar;
 pub use display_line::DisplayLine;
 pub use geometry::{Dimensions, Position};
-pub use logical_position::{LogicalPosition, LogicalRange};
+pub use logical_position::{LineSpan, LogicalPosition, LogicalRange};
 pub use request_model::{HttpHeaders, RequestModel};
 pub use response_model::ResponseModel;
 pub use screen_buffer::{BufferCell, ScreenBuffer};
 pub use selection::Selection;
 pub use status_line::{HttpStatus, StatusLine};
-pub use yank_buffer::{ClipboardYankBuffer, MemoryYankBuffer, YankBuffer, YankEntry, YankType};
+pub use undo_history::{UndoHistory, UndoStep, DEFAULT_UNDOLEVELS};
+pub use yank_buffer::{
+    is_read_only_register, register_preview, ClipboardSink, ClipboardYankBuffer, MemoryYankBuffer,
+    Osc52ClipboardSink, SystemClipboardSink, YankBuffer, YankEntry, YankType, BLACK_HOLE_REGISTER,
+    FILE_NAME_REGISTER, LAST_COMMAND_REGISTER, LISTED_REGISTERS,
+};
+pub use yank_highlight::YankHighlight;
 
 #[cfg(test)]
 mod tests {
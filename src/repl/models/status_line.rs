@@ -4,6 +4,10 @@
 //! providing a clean interface for status bar rendering.
 
 use crate::repl::events::{EditorMode, LogicalPosition, Pane};
+use std::collections::VecDeque;
+
+/// Most messages kept for `:messages`; older ones are dropped first
+pub const MESSAGE_HISTORY_SIZE: usize = 100;
 
 /// Type alias for display position
 type DisplayPosition = (usize, usize);
@@ -25,6 +29,15 @@ pub struct StatusLine {
     /// Temporary status message to display
     status_message: Option<String>,
 
+    /// Whether the status message is transient, kept out of `:messages`
+    message_is_transient: bool,
+
+    /// Every status message shown, oldest first, for `:messages`
+    message_history: VecDeque<String>,
+
+    /// Error messages shown since `:messages` was last viewed
+    unseen_errors: usize,
+
     /// Ex command buffer (for :q, :set wrap on, etc.)
     command_buffer: String,
 
@@ -41,6 +54,9 @@ pub struct StatusLine {
     /// Previous editor mode (for restoring after command cancellation)
     previous_mode: EditorMode,
 
+    /// Whether the mode indicator (`-- INSERT --`) is shown (`showmode`)
+    show_mode: bool,
+
     /// Current pane and cursor position
     current_pane: Pane,
     cursor_position: LogicalPosition,
@@ -63,12 +79,16 @@ impl StatusLine {
     pub fn new() -> Self {
         Self {
             status_message: None,
+            message_is_transient: false,
+            message_history: VecDeque::new(),
+            unseen_errors: 0,
             command_buffer: String::new(),
             http_status: HttpStatus::default(),
             profile_name: "default".to_string(),
             profile_path: "~/.blueline/profile".to_string(),
             editor_mode: EditorMode::Normal,
             previous_mode: EditorMode::Normal,
+            show_mode: true,
             current_pane: Pane::Request,
             cursor_position: LogicalPosition::zero(),
             is_executing: false,
@@ -80,9 +100,49 @@ impl StatusLine {
 
     // === Status Message Methods ===
 
-    /// Set a temporary status message
+    /// Set a temporary status message, remembering it for `:messages`
     pub fn set_status_message<S: Into<String>>(&mut self, message: S) {
+        let message = message.into();
+        self.remember_message(&message);
+        self.status_message = Some(message);
+        self.message_is_transient = false;
+    }
+
+    /// Set an error message, remembering it for `:messages` as an unseen error
+    pub fn set_error_message<S: Into<String>>(&mut self, message: S) {
+        let message = message.into();
+        self.remember_message(&message);
+        self.unseen_errors += 1;
+        self.status_message = Some(message);
+        self.message_is_transient = false;
+    }
+
+    /// Show a message that describes the cursor position rather than an event
+    ///
+    /// It is not kept for `:messages`, and [`Self::clear_transient_message`]
+    /// removes it without touching other messages.
+    pub fn set_transient_message<S: Into<String>>(&mut self, message: S) {
         self.status_message = Some(message.into());
+        self.message_is_transient = true;
+    }
+
+    /// Clear the status message if it is a transient one
+    pub fn clear_transient_message(&mut self) {
+        if self.message_is_transient {
+            self.status_message = None;
+            self.message_is_transient = false;
+        }
+    }
+
+    /// Add a message to the `:messages` history, dropping the oldest when full
+    fn remember_message(&mut self, message: &str) {
+        if message.is_empty() {
+            return;
+        }
+        if self.message_history.len() == MESSAGE_HISTORY_SIZE {
+            self.message_history.pop_front();
+        }
+        self.message_history.push_back(message.to_string());
     }
 
     /// Clear the status message
@@ -95,6 +155,27 @@ impl StatusLine {
         self.status_message.as_deref()
     }
 
+    /// Get the messages shown so far, oldest first
+    pub fn message_history(&self) -> impl Iterator<Item = &str> {
+        self.message_history.iter().map(String::as_str)
+    }
+
+    /// Forget every message shown so far (`:messages clear`)
+    pub fn clear_message_history(&mut self) {
+        self.message_history.clear();
+        self.unseen_errors = 0;
+    }
+
+    /// Get the number of error messages shown since `:messages` was last viewed
+    pub fn unseen_error_count(&self) -> usize {
+        self.unseen_errors
+    }
+
+    /// Mark every error message as seen, after `:messages` shows them
+    pub fn mark_errors_seen(&mut self) {
+        self.unseen_errors = 0;
+    }
+
     // === Command Buffer Methods ===
 
     /// Get the command buffer content
@@ -112,6 +193,11 @@ impl StatusLine {
         self.command_buffer.pop();
     }
 
+    /// Replace the command buffer content
+    pub fn set_command_buffer(&mut self, text: String) {
+        self.command_buffer = text;
+    }
+
     /// Clear the command buffer
     pub fn clear_command_buffer(&mut self) {
         self.command_buffer.clear();
@@ -192,6 +278,35 @@ impl StatusLine {
         self.previous_mode
     }
 
+    /// Set whether the mode indicator is shown
+    pub fn set_show_mode(&mut self, show: bool) {
+        self.show_mode = show;
+    }
+
+    /// Check if the mode indicator is shown
+    pub fn is_show_mode_enabled(&self) -> bool {
+        self.show_mode
+    }
+
+    /// Get the indicator shown on the left of the status line for `mode`
+    ///
+    /// Like Vim, normal mode and the prefix modes show nothing, and with
+    /// `noshowmode` no mode is shown at all.
+    pub fn mode_indicator(&self, mode: EditorMode) -> Option<&'static str> {
+        if !self.show_mode {
+            return None;
+        }
+        match mode {
+            EditorMode::Insert
+            | EditorMode::InsertRegisterPrefix
+            | EditorMode::InsertLiteralPrefix => Some("-- INSERT --"),
+            EditorMode::Visual => Some("-- VISUAL --"),
+            EditorMode::VisualLine => Some("-- VISUAL LINE --"),
+            EditorMode::VisualBlock => Some("-- VISUAL BLOCK --"),
+            _ => None,
+        }
+    }
+
     /// Set current pane
     pub fn set_current_pane(&mut self, pane: Pane) {
         self.current_pane = pane;
@@ -327,6 +442,62 @@ mod tests {
         assert_eq!(status.profile_path(), "/etc/blueline/prod.ini");
     }
 
+    #[test]
+    fn test_errors_should_count_until_messages_are_seen() {
+        let mut status = StatusLine::new();
+
+        status.set_status_message("3 lines yanked");
+        status.set_error_message("Error: \"api.http\": Permission denied");
+        status.set_status_message("Error: only looks like one");
+        status.set_error_message("Error: E20: Mark not set");
+        assert_eq!(status.unseen_error_count(), 2);
+        assert_eq!(
+            status.message_history().collect::<Vec<_>>(),
+            vec![
+                "3 lines yanked",
+                "Error: \"api.http\": Permission denied",
+                "Error: only looks like one",
+                "Error: E20: Mark not set"
+            ]
+        );
+
+        status.mark_errors_seen();
+        assert_eq!(status.unseen_error_count(), 0);
+        assert_eq!(status.message_history().count(), 4);
+
+        status.set_error_message("Error: timeout");
+        status.clear_message_history();
+        assert_eq!(status.unseen_error_count(), 0);
+        assert_eq!(status.message_history().count(), 0);
+    }
+
+    #[test]
+    fn test_transient_message_should_stay_out_of_history() {
+        let mut status = StatusLine::new();
+
+        status.set_transient_message(".users[2].email");
+        assert_eq!(status.status_message(), Some(".users[2].email"));
+        assert_eq!(status.message_history().count(), 0);
+        status.clear_transient_message();
+        assert_eq!(status.status_message(), None);
+
+        status.set_status_message("3 lines yanked");
+        status.clear_transient_message();
+        assert_eq!(status.status_message(), Some("3 lines yanked"));
+    }
+
+    #[test]
+    fn test_message_history_should_drop_oldest_when_full() {
+        let mut status = StatusLine::new();
+
+        for i in 0..=MESSAGE_HISTORY_SIZE {
+            status.set_status_message(format!("message {i}"));
+        }
+
+        assert_eq!(status.message_history().count(), MESSAGE_HISTORY_SIZE);
+        assert_eq!(status.message_history().next(), Some("message 1"));
+    }
+
     #[test]
     fn test_editor_state_operations() {
         let mut status = StatusLine::new();
@@ -347,6 +518,25 @@ mod tests {
         assert_eq!(status.cursor_position(), pos);
     }
 
+    #[test]
+    fn test_mode_indicator_should_follow_showmode() {
+        let mut status = StatusLine::new();
+
+        assert!(status.is_show_mode_enabled());
+        assert_eq!(
+            status.mode_indicator(EditorMode::InsertRegisterPrefix),
+            Some("-- INSERT --")
+        );
+        assert_eq!(
+            status.mode_indicator(EditorMode::VisualLine),
+            Some("-- VISUAL LINE --")
+        );
+        assert_eq!(status.mode_indicator(EditorMode::Normal), None);
+
+        status.set_show_mode(false);
+        assert_eq!(status.mode_indicator(EditorMode::Insert), None);
+    }
+
     #[test]
     fn test_execution_state() {
         let mut status = StatusLine::new();
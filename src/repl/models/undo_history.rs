@@ -0,0 +1,226 @@
+//! # Undo History
+//!
+//! A bounded stack of request buffer states to undo back to, capped by
+//! `undolevels` like Vim: the oldest step is dropped once the cap is reached,
+//! `0` keeps a single step and a negative value records nothing.
+//!
+//! Every change gets a number that stays the same as older steps are dropped,
+//! and the time it was made, so `:undolist` can show the changes and
+//! `:undo N` can find the state right after change `N`.
+
+use crate::repl::models::LogicalPosition;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Undo steps kept unless `undolevels` says otherwise (Vim's default)
+pub const DEFAULT_UNDOLEVELS: i64 = 1000;
+
+/// Change number and how long ago it was made, as `:undolist` shows it
+pub type UndoListEntry = (usize, Duration);
+
+/// The request buffer as it was before a change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoStep {
+    /// Number of the change this step undoes, counting from 1
+    pub number: usize,
+    /// When the change was made
+    pub time: Instant,
+    /// Buffer text before the change
+    pub text: String,
+    /// Cursor position before the change
+    pub cursor: LogicalPosition,
+}
+
+/// Undo steps, oldest first, capped by `undolevels`
+#[derive(Debug, Clone)]
+pub struct UndoHistory {
+    levels: i64,
+    steps: VecDeque<UndoStep>,
+    last_number: usize,
+}
+
+impl UndoHistory {
+    /// Create an empty history with the default `undolevels`
+    pub fn new() -> Self {
+        Self {
+            levels: DEFAULT_UNDOLEVELS,
+            steps: VecDeque::new(),
+            last_number: 0,
+        }
+    }
+
+    /// Get the configured `undolevels`
+    pub fn levels(&self) -> i64 {
+        self.levels
+    }
+
+    /// Set `undolevels`, dropping the oldest steps that no longer fit
+    pub fn set_levels(&mut self, levels: i64) {
+        self.levels = levels;
+        while self.steps.len() > self.capacity() {
+            self.steps.pop_front();
+        }
+    }
+
+    /// Record the state before a change made at `time`, dropping the oldest step when full
+    pub fn record(&mut self, text: String, cursor: LogicalPosition, time: Instant) {
+        if self.capacity() == 0 {
+            return;
+        }
+        if self.steps.len() == self.capacity() {
+            self.steps.pop_front();
+        }
+        self.last_number += 1;
+        self.steps.push_back(UndoStep {
+            number: self.last_number,
+            time,
+            text,
+            cursor,
+        });
+    }
+
+    /// Take the most recent step to undo back to
+    pub fn pop(&mut self) -> Option<UndoStep> {
+        let step = self.steps.pop_back()?;
+        self.last_number = step.number - 1;
+        Some(step)
+    }
+
+    /// Number of the most recent change, 0 before any change
+    pub fn last_number(&self) -> usize {
+        self.last_number
+    }
+
+    /// Find the state right after change `number`, for `:undo N`
+    ///
+    /// That is the state recorded before the following change; change 0 is
+    /// the buffer before any change. Returns `None` for the most recent change,
+    /// whose result is the current buffer, and for changes no longer kept.
+    pub fn state_after(&self, number: usize) -> Option<&UndoStep> {
+        self.steps.iter().find(|step| step.number == number + 1)
+    }
+
+    /// Drop the steps after `number`, once the buffer is back in the state after it
+    pub fn truncate_after(&mut self, number: usize) {
+        self.steps.retain(|step| step.number <= number);
+        self.last_number = self.last_number.min(number);
+    }
+
+    /// List the kept changes for `:undolist`, oldest first
+    ///
+    /// Each entry is the change number and how long ago it was made.
+    pub fn changes(&self, now: Instant) -> Vec<UndoListEntry> {
+        self.steps
+            .iter()
+            .map(|step| (step.number, now.saturating_duration_since(step.time)))
+            .collect()
+    }
+
+    /// Number of steps that can be undone
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Check whether there is nothing to undo
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Most steps kept: none when disabled, and at least one otherwise
+    fn capacity(&self) -> usize {
+        match self.levels {
+            levels if levels < 0 => 0,
+            0 => 1,
+            levels => levels as usize,
+        }
+    }
+}
+
+impl Default for UndoHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history_with(texts: &[&str], start: Instant) -> UndoHistory {
+        let mut history = UndoHistory::new();
+        for (index, text) in texts.iter().enumerate() {
+            history.record(
+                text.to_string(),
+                LogicalPosition::zero(),
+                start + Duration::from_secs(index as u64),
+            );
+        }
+        history
+    }
+
+    #[test]
+    fn record_should_drop_oldest_step_beyond_undolevels() {
+        let mut history = UndoHistory::new();
+        history.set_levels(2);
+
+        for text in ["a", "ab", "abc"] {
+            history.record(text.to_string(), LogicalPosition::zero(), Instant::now());
+        }
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.pop().map(|step| step.text).as_deref(), Some("abc"));
+        assert_eq!(history.pop().map(|step| step.text).as_deref(), Some("ab"));
+        assert_eq!(history.pop(), None);
+    }
+
+    #[test]
+    fn undolevels_zero_should_keep_one_step_and_negative_none() {
+        let start = Instant::now();
+        let mut history = history_with(&["a", "ab"], start);
+
+        history.set_levels(0);
+        assert_eq!(history.len(), 1);
+        history.record("abc".to_string(), LogicalPosition::zero(), start);
+        assert_eq!(history.pop().map(|step| step.text).as_deref(), Some("abc"));
+
+        history.set_levels(-1);
+        history.record("abcd".to_string(), LogicalPosition::zero(), start);
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn state_after_should_find_buffer_after_change_number() {
+        // Before change 1 the buffer was "", before change 2 "a", before change 3 "ab"
+        let start = Instant::now();
+        let mut history = history_with(&["", "a", "ab"], start);
+        history.set_levels(2);
+
+        assert_eq!(history.last_number(), 3);
+        assert_eq!(
+            history.state_after(1).map(|step| step.text.as_str()),
+            Some("a")
+        );
+        assert_eq!(
+            history.state_after(2).map(|step| step.text.as_str()),
+            Some("ab")
+        );
+        // Change 3 is the current buffer, and the state before change 1 was dropped
+        assert_eq!(history.state_after(3), None);
+        assert_eq!(history.state_after(0), None);
+
+        history.truncate_after(1);
+        assert_eq!(history.last_number(), 1);
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn changes_should_report_numbers_and_age() {
+        let start = Instant::now();
+        let history = history_with(&["", "a"], start);
+
+        assert_eq!(
+            history.changes(start + Duration::from_secs(10)),
+            vec![(1, Duration::from_secs(10)), (2, Duration::from_secs(9))]
+        );
+    }
+}
@@ -4,9 +4,43 @@
 //! Supports both memory-based and system clipboard implementations.
 //! Enhanced with yank type metadata for proper block-wise operations.
 
+use crate::repl::models::display_char::caret_notation;
 use anyhow::Result;
 use std::sync::{Arc, Mutex};
 
+/// Register that discards everything written to it (`"_`)
+pub const BLACK_HOLE_REGISTER: char = '_';
+
+/// Read-only register holding the request buffer's file name (`"%`)
+pub const FILE_NAME_REGISTER: char = '%';
+
+/// Read-only register holding the last executed ex command (`":`)
+pub const LAST_COMMAND_REGISTER: char = ':';
+
+/// Registers `:registers` lists, in the order it lists them
+pub const LISTED_REGISTERS: &str = "\"123456789abcdefghijklmnopqrstuvwxyz%:";
+
+/// Check whether a register can only be read, never yanked into
+pub fn is_read_only_register(register: char) -> bool {
+    matches!(register, FILE_NAME_REGISTER | LAST_COMMAND_REGISTER)
+}
+
+/// One-line preview of register contents, cut to `width` characters
+///
+/// Newlines, tabs and other control characters are shown in caret notation
+/// (`^J`, `^I`, `^[`), so multi-line text stays on one line.
+pub fn register_preview(text: &str, width: usize) -> String {
+    text.chars()
+        .map(|ch| match ch {
+            '\n' => "^J".to_string(),
+            '\t' => "^I".to_string(),
+            _ => caret_notation(ch).unwrap_or_else(|| ch.to_string()),
+        })
+        .flat_map(|notation| notation.chars().collect::<Vec<_>>())
+        .take(width)
+        .collect()
+}
+
 /// Type of yank operation, determining paste behavior
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum YankType {
@@ -14,8 +48,8 @@ pub enum YankType {
     Character,
     /// Line-wise selection (visual line mode)
     Line,
-    /// Block-wise selection (visual block mode)
-    Block,
+    /// Block-wise selection (visual block mode) spanning `width` columns
+    Block { width: usize },
 }
 
 /// Yank entry containing text and metadata
@@ -238,10 +272,143 @@ impl YankBuffer for ClipboardYankBuffer {
     }
 }
 
+/// System clipboard that yanks are mirrored to and pastes are read from
+///
+/// Unlike [`YankBuffer`], a sink keeps no yank type: when the clipboard still
+/// holds the last yank, pastes take the yank buffer's entry instead so the
+/// type survives the round trip.
+pub trait ClipboardSink: Send {
+    /// Copy text to the clipboard
+    fn set_text(&mut self, text: &str) -> Result<()>;
+
+    /// Read the text currently on the clipboard
+    fn get_text(&mut self) -> Result<String>;
+
+    /// Take copied text that still has to be handed to the terminal
+    ///
+    /// Sinks that reach the clipboard by themselves have nothing to hand over.
+    fn take_terminal_copy(&mut self) -> Option<String> {
+        None
+    }
+}
+
+/// Clipboard sink backed by the system clipboard through `arboard`
+pub struct SystemClipboardSink {
+    clipboard: arboard::Clipboard,
+}
+
+impl SystemClipboardSink {
+    /// Connect to the system clipboard
+    ///
+    /// Fails on headless sessions (e.g. SSH without a display server).
+    pub fn new() -> Result<Self> {
+        let clipboard = arboard::Clipboard::new()
+            .map_err(|e| anyhow::anyhow!("Failed to access system clipboard: {}", e))?;
+        Ok(Self { clipboard })
+    }
+}
+
+impl ClipboardSink for SystemClipboardSink {
+    fn set_text(&mut self, text: &str) -> Result<()> {
+        self.clipboard
+            .set_text(text)
+            .map_err(|e| anyhow::anyhow!("Failed to set clipboard text: {}", e))
+    }
+
+    fn get_text(&mut self) -> Result<String> {
+        self.clipboard
+            .get_text()
+            .map_err(|e| anyhow::anyhow!("Failed to get clipboard text: {}", e))
+    }
+}
+
+/// Clipboard sink that hands yanks to the terminal with OSC 52
+///
+/// Used when there is no system clipboard to connect to, such as over SSH:
+/// the terminal on the user's machine puts the text on its clipboard. Reading
+/// it back is not supported, so pastes come from the yank buffer.
+///
+/// The sink only holds the latest copy; the renderer writes it out with the
+/// next screen update.
+#[derive(Debug, Default)]
+pub struct Osc52ClipboardSink {
+    pending: Option<String>,
+}
+
+impl ClipboardSink for Osc52ClipboardSink {
+    fn set_text(&mut self, text: &str) -> Result<()> {
+        self.pending = Some(text.to_string());
+        Ok(())
+    }
+
+    fn get_text(&mut self) -> Result<String> {
+        Err(anyhow::anyhow!("OSC 52 clipboard cannot be read"))
+    }
+
+    fn take_terminal_copy(&mut self) -> Option<String> {
+        self.pending.take()
+    }
+}
+
+/// Build the OSC 52 sequence that copies `text` to the terminal's clipboard
+pub fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+}
+
+/// Encode bytes as standard padded base64, as OSC 52 expects
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn register_preview_should_escape_control_characters_and_truncate() {
+        assert_eq!(
+            register_preview("a\tb\nc\x1b\n", 80),
+            "a^Ib^Jc^[^J".to_string()
+        );
+        assert_eq!(register_preview("line 1\nline 2\n", 8), "line 1^J");
+    }
+
+    #[test]
+    fn osc52_sequence_should_base64_encode_text() {
+        assert_eq!(osc52_sequence("hi"), "\x1b]52;c;aGk=\x07");
+        assert_eq!(osc52_sequence("GET /"), "\x1b]52;c;R0VUIC8=\x07");
+        assert_eq!(osc52_sequence("abc"), "\x1b]52;c;YWJj\x07");
+    }
+
+    #[test]
+    fn osc52_sink_should_hold_latest_copy_for_terminal() {
+        let mut sink = Osc52ClipboardSink::default();
+        assert_eq!(sink.take_terminal_copy(), None);
+
+        sink.set_text("first").unwrap();
+        sink.set_text("second").unwrap();
+        assert_eq!(sink.take_terminal_copy().as_deref(), Some("second"));
+        assert_eq!(sink.take_terminal_copy(), None);
+        assert!(sink.get_text().is_err());
+    }
+
     #[test]
     fn memory_yank_buffer_should_store_and_retrieve_text() {
         let mut buffer = MemoryYankBuffer::new();
@@ -261,12 +428,12 @@ mod tests {
 
         // Yank with specific type
         buffer
-            .yank_with_type("Block text".to_string(), YankType::Block)
+            .yank_with_type("Block text".to_string(), YankType::Block { width: 10 })
             .unwrap();
         assert_eq!(buffer.paste(), Some("Block text"));
         let entry = buffer.paste_entry().unwrap();
         assert_eq!(entry.text, "Block text");
-        assert_eq!(entry.yank_type, YankType::Block);
+        assert_eq!(entry.yank_type, YankType::Block { width: 10 });
 
         // Clear buffer
         buffer.clear();
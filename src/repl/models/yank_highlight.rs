@@ -0,0 +1,84 @@
+//! # Yank Highlight
+//!
+//! The region of text just yanked, flashed briefly with `:set hlyank on`.
+//! Like [`crate::repl::models::Selection`] it is pure positional data; the
+//! yank type decides whether it covers characters, whole lines or a block.
+
+use crate::repl::events::Pane;
+use crate::repl::models::{LogicalPosition, YankType};
+
+/// Text that was just yanked, with both ends inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YankHighlight {
+    /// Pane the text was yanked from
+    pub pane: Pane,
+    /// First yanked position in document order
+    pub start: LogicalPosition,
+    /// Last yanked position in document order
+    pub end: LogicalPosition,
+    /// How the region between `start` and `end` was yanked
+    pub yank_type: YankType,
+}
+
+impl YankHighlight {
+    /// Create a highlight between two positions, in either order
+    ///
+    /// For block yanks the positions are opposite corners of the block.
+    pub fn new(
+        pane: Pane,
+        from: LogicalPosition,
+        to: LogicalPosition,
+        yank_type: YankType,
+    ) -> Self {
+        let (start, end) = if from <= to { (from, to) } else { (to, from) };
+        Self {
+            pane,
+            start,
+            end,
+            yank_type,
+        }
+    }
+
+    /// Check whether a position in a pane falls inside the yanked text
+    pub fn contains(&self, pane: Pane, position: LogicalPosition) -> bool {
+        if pane != self.pane || position.line < self.start.line || position.line > self.end.line {
+            return false;
+        }
+
+        match self.yank_type {
+            YankType::Line => true,
+            YankType::Character => self.start <= position && position <= self.end,
+            YankType::Block { .. } => {
+                let left = self.start.column.min(self.end.column);
+                let right = self.start.column.max(self.end.column);
+                (left..=right).contains(&position.column)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_should_follow_the_yank_type() {
+        let from = LogicalPosition::new(2, 1);
+        let to = LogicalPosition::new(0, 3);
+
+        let character = YankHighlight::new(Pane::Request, from, to, YankType::Character);
+        assert_eq!(character.start, to);
+        assert!(character.contains(Pane::Request, LogicalPosition::new(1, 10)));
+        assert!(!character.contains(Pane::Request, LogicalPosition::new(0, 2)));
+        assert!(!character.contains(Pane::Response, LogicalPosition::new(1, 0)));
+
+        let line = YankHighlight::new(Pane::Request, from, to, YankType::Line);
+        assert!(line.contains(Pane::Request, LogicalPosition::new(2, 40)));
+        assert!(!line.contains(Pane::Request, LogicalPosition::new(3, 0)));
+
+        let block = YankHighlight::new(Pane::Request, from, to, YankType::Block { width: 3 });
+        assert!(block.contains(Pane::Request, LogicalPosition::new(1, 2)));
+        assert!(!block.contains(Pane::Request, LogicalPosition::new(1, 4)));
+        assert!(!block.contains(Pane::Request, LogicalPosition::new(0, 0)));
+    }
+}
@@ -0,0 +1,213 @@
+//! # Autoread Service
+//!
+//! Notices when the request buffer's file is changed by another program
+//! (`:set autoread on`) so the buffer can be reloaded, or the user warned when
+//! the buffer has changes of its own.
+
+use crate::repl::error::{AppError, AppResult};
+use crate::repl::services::clock::{Clock, SystemClock};
+use std::fs;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Time between modification time checks on idle ticks
+pub const AUTOREAD_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Path of the tracked file and its modification time when last seen
+type TrackedFile = (String, Option<SystemTime>);
+
+/// What a check found out about the request file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalChange {
+    /// The file changed and the buffer had no changes, so it can take the new content
+    Reload(String),
+    /// The file changed while the buffer has unsaved changes of its own
+    Conflict,
+}
+
+/// Service that tracks the modification time of the request buffer's file
+pub struct AutoreadService {
+    enabled: bool,
+    clock: Box<dyn Clock>,
+    last_check: Instant,
+    tracked: Option<TrackedFile>,
+}
+
+impl AutoreadService {
+    /// Create a disabled autoread service using the system clock
+    pub fn new() -> Self {
+        Self::with_clock(Box::new(SystemClock))
+    }
+
+    /// Create a disabled autoread service using the given clock
+    pub fn with_clock(clock: Box<dyn Clock>) -> Self {
+        let last_check = clock.now();
+        Self {
+            enabled: false,
+            clock,
+            last_check,
+            tracked: None,
+        }
+    }
+
+    /// Check if autoread is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enable or disable autoread
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Remember the current modification time of `path`
+    ///
+    /// Called after the buffer is written so its own writes are not taken for
+    /// external changes.
+    pub fn track(&mut self, path: &str) {
+        self.tracked = Some((path.to_string(), modified_time(path)));
+    }
+
+    /// Look for an external change to `path` since it was last tracked
+    ///
+    /// Idle ticks check at most once per [`AUTOREAD_CHECK_INTERVAL`]; with
+    /// `force` the interval is ignored, for checking when the terminal regains
+    /// focus. The first check of a file only starts tracking it.
+    ///
+    /// # Returns
+    /// The change found, or `None` when the file is unchanged or was not checked.
+    pub fn check(
+        &mut self,
+        path: &str,
+        modified: bool,
+        force: bool,
+    ) -> AppResult<Option<ExternalChange>> {
+        if !self.enabled {
+            return Ok(None);
+        }
+        let now = self.clock.now();
+        if !force && now - self.last_check < AUTOREAD_CHECK_INTERVAL {
+            return Ok(None);
+        }
+        self.last_check = now;
+
+        let mtime = modified_time(path);
+        let changed = match &self.tracked {
+            Some((tracked_path, tracked_mtime)) if tracked_path == path => {
+                mtime.is_some() && mtime != *tracked_mtime
+            }
+            _ => false,
+        };
+        self.tracked = Some((path.to_string(), mtime));
+        if !changed {
+            return Ok(None);
+        }
+
+        if modified {
+            return Ok(Some(ExternalChange::Conflict));
+        }
+        let expanded = shellexpand::tilde(path).into_owned();
+        let content = fs::read_to_string(&expanded).map_err(|e| AppError::io(path, &e))?;
+        Ok(Some(ExternalChange::Reload(content)))
+    }
+}
+
+impl Default for AutoreadService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Get the modification time of a file, if it exists
+fn modified_time(path: &str) -> Option<SystemTime> {
+    let path = shellexpand::tilde(path).into_owned();
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repl::services::clock::ManualClock;
+    use std::fs::File;
+
+    /// Rewrite a file and move its modification time forward, as an editor would
+    fn write_externally(path: &std::path::Path, content: &str, seconds_later: u64) {
+        fs::write(path, content).unwrap();
+        let mtime = SystemTime::now() + Duration::from_secs(seconds_later);
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(mtime)
+            .unwrap();
+    }
+
+    fn create_service(clock: &ManualClock, path: &str) -> AutoreadService {
+        let mut service = AutoreadService::with_clock(Box::new(clock.clone()));
+        service.set_enabled(true);
+        service.track(path);
+        service
+    }
+
+    #[test]
+    fn check_should_reload_clean_buffer_after_external_change() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("request.http");
+        fs::write(&file_path, "GET /users\n").unwrap();
+        let path = file_path.to_str().unwrap();
+        let clock = ManualClock::new();
+        let mut service = create_service(&clock, path);
+
+        clock.advance(AUTOREAD_CHECK_INTERVAL);
+        assert_eq!(service.check(path, false, false).unwrap(), None);
+
+        write_externally(&file_path, "GET /orders\n", 10);
+        assert_eq!(service.check(path, false, false).unwrap(), None);
+
+        clock.advance(AUTOREAD_CHECK_INTERVAL);
+        assert_eq!(
+            service.check(path, false, false).unwrap(),
+            Some(ExternalChange::Reload("GET /orders\n".to_string()))
+        );
+
+        // The reloaded content is the new baseline
+        clock.advance(AUTOREAD_CHECK_INTERVAL);
+        assert_eq!(service.check(path, false, false).unwrap(), None);
+    }
+
+    #[test]
+    fn check_should_report_conflict_for_modified_buffer() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("request.http");
+        fs::write(&file_path, "GET /users\n").unwrap();
+        let path = file_path.to_str().unwrap();
+        let clock = ManualClock::new();
+        let mut service = create_service(&clock, path);
+
+        write_externally(&file_path, "GET /orders\n", 10);
+        assert_eq!(
+            service.check(path, true, true).unwrap(),
+            Some(ExternalChange::Conflict)
+        );
+    }
+
+    #[test]
+    fn check_should_do_nothing_when_disabled_or_untracked() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("request.http");
+        fs::write(&file_path, "GET /users\n").unwrap();
+        let path = file_path.to_str().unwrap();
+        let clock = ManualClock::new();
+
+        let mut service = create_service(&clock, path);
+        service.set_enabled(false);
+        write_externally(&file_path, "GET /orders\n", 10);
+        assert_eq!(service.check(path, false, true).unwrap(), None);
+
+        // A file seen for the first time is only tracked
+        let mut service = AutoreadService::with_clock(Box::new(clock.clone()));
+        service.set_enabled(true);
+        assert_eq!(service.check(path, false, true).unwrap(), None);
+        write_externally(&file_path, "GET /users\n", 20);
+        assert!(service.check(path, false, true).unwrap().is_some());
+    }
+}
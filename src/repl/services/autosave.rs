@@ -0,0 +1,232 @@
+//! # Autosave Service
+//!
+//! Periodically writes the request buffer to its file so drafts survive a crash
+//! or a closed terminal (`:set autosave on`, `:set autosaveinterval <seconds>`).
+//! With `:set autowrite on` the buffer is also written before a request is sent
+//! or the request pane is left.
+
+use crate::repl::error::AppResult;
+use crate::repl::services::clock::{Clock, SystemClock};
+use crate::repl::services::file;
+use std::time::{Duration, Instant};
+
+/// Seconds between autosaves unless `autosaveinterval` says otherwise
+pub const DEFAULT_AUTOSAVE_INTERVAL_SECS: usize = 30;
+
+/// Service that decides when the request buffer is due for an autosave
+pub struct AutosaveService {
+    enabled: bool,
+    autowrite: bool,
+    interval: Duration,
+    clock: Box<dyn Clock>,
+    last_save: Instant,
+}
+
+impl AutosaveService {
+    /// Create a disabled autosave service using the system clock
+    pub fn new() -> Self {
+        Self::with_clock(Box::new(SystemClock))
+    }
+
+    /// Create a disabled autosave service using the given clock
+    pub fn with_clock(clock: Box<dyn Clock>) -> Self {
+        let last_save = clock.now();
+        Self {
+            enabled: false,
+            autowrite: false,
+            interval: Duration::from_secs(DEFAULT_AUTOSAVE_INTERVAL_SECS as u64),
+            clock,
+            last_save,
+        }
+    }
+
+    /// Check if autosave is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enable or disable autosave, restarting the interval when enabled
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if enabled && !self.enabled {
+            self.last_save = self.clock.now();
+        }
+        self.enabled = enabled;
+    }
+
+    /// Check if autowrite is enabled
+    pub fn is_autowrite_enabled(&self) -> bool {
+        self.autowrite
+    }
+
+    /// Enable or disable writing the buffer before requests and pane switches
+    pub fn set_autowrite_enabled(&mut self, enabled: bool) {
+        self.autowrite = enabled;
+    }
+
+    /// Get the autosave interval in seconds
+    pub fn interval_secs(&self) -> usize {
+        self.interval.as_secs() as usize
+    }
+
+    /// Set the autosave interval in seconds (at least one second)
+    pub fn set_interval_secs(&mut self, secs: usize) {
+        self.interval = Duration::from_secs(secs.max(1) as u64);
+    }
+
+    /// Check whether a modified buffer should be written now
+    pub fn is_due(&self, modified: bool) -> bool {
+        self.enabled && modified && self.clock.now() - self.last_save >= self.interval
+    }
+
+    /// Write the buffer to `path` when it is due for an autosave
+    ///
+    /// With `force` the interval is ignored, for saving when the terminal loses
+    /// focus. A clean buffer is never written.
+    ///
+    /// # Returns
+    /// The number of bytes written, or `None` when nothing was saved.
+    pub fn save_if_due(
+        &mut self,
+        path: &str,
+        content: &str,
+        modified: bool,
+        force: bool,
+    ) -> AppResult<Option<usize>> {
+        let due = if force {
+            self.enabled && modified
+        } else {
+            self.is_due(modified)
+        };
+        if !due {
+            return Ok(None);
+        }
+
+        // Restart the interval even on failure so a bad path is not retried on every tick
+        self.last_save = self.clock.now();
+        file::write_buffer(path, content, false).map(Some)
+    }
+
+    /// Write a modified buffer to `path` when autowrite is enabled
+    ///
+    /// # Returns
+    /// The number of bytes written, or `None` when nothing was written.
+    pub fn autowrite(
+        &mut self,
+        path: &str,
+        content: &str,
+        modified: bool,
+    ) -> AppResult<Option<usize>> {
+        if !self.autowrite || !modified {
+            return Ok(None);
+        }
+
+        // An autowrite counts as a save, so autosave does not follow right after
+        self.last_save = self.clock.now();
+        file::write_buffer(path, content, false).map(Some)
+    }
+}
+
+impl Default for AutosaveService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repl::services::clock::ManualClock;
+    use std::fs;
+
+    fn create_service(clock: &ManualClock) -> AutosaveService {
+        let mut service = AutosaveService::with_clock(Box::new(clock.clone()));
+        service.set_enabled(true);
+        service.set_interval_secs(10);
+        service
+    }
+
+    #[test]
+    fn save_if_due_should_write_dirty_buffer_once_interval_elapses() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("request.http");
+        let path = path.to_str().unwrap();
+        let clock = ManualClock::new();
+        let mut service = create_service(&clock);
+
+        clock.advance(Duration::from_secs(9));
+        assert_eq!(
+            service
+                .save_if_due(path, "GET /users", true, false)
+                .unwrap(),
+            None
+        );
+        assert!(!temp_dir.path().join("request.http").exists());
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(
+            service
+                .save_if_due(path, "GET /users", true, false)
+                .unwrap(),
+            Some(11)
+        );
+        assert_eq!(fs::read_to_string(path).unwrap(), "GET /users\n");
+
+        // The interval starts over after a save
+        assert!(!service.is_due(true));
+    }
+
+    #[test]
+    fn save_if_due_should_skip_clean_buffer() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("request.http");
+        let clock = ManualClock::new();
+        let mut service = create_service(&clock);
+
+        clock.advance(Duration::from_secs(60));
+        let path = path.to_str().unwrap();
+        assert_eq!(
+            service.save_if_due(path, "GET /", false, false).unwrap(),
+            None
+        );
+        assert_eq!(
+            service.save_if_due(path, "GET /", false, true).unwrap(),
+            None
+        );
+        assert!(!temp_dir.path().join("request.http").exists());
+    }
+
+    #[test]
+    fn save_if_due_should_ignore_interval_when_forced() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("request.http");
+        let path = path.to_str().unwrap();
+        let clock = ManualClock::new();
+        let mut service = create_service(&clock);
+
+        assert_eq!(
+            service.save_if_due(path, "GET /", true, true).unwrap(),
+            Some(6)
+        );
+
+        service.set_enabled(false);
+        clock.advance(Duration::from_secs(60));
+        assert!(!service.is_due(true));
+    }
+
+    #[test]
+    fn autowrite_should_write_dirty_buffer_only_when_enabled() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("request.http");
+        let path = path.to_str().unwrap();
+        let mut service = AutosaveService::with_clock(Box::new(ManualClock::new()));
+
+        assert_eq!(service.autowrite(path, "GET /", true).unwrap(), None);
+
+        service.set_autowrite_enabled(true);
+        assert_eq!(service.autowrite(path, "GET /", false).unwrap(), None);
+        assert!(!temp_dir.path().join("request.http").exists());
+
+        assert_eq!(service.autowrite(path, "GET /", true).unwrap(), Some(6));
+        assert_eq!(fs::read_to_string(path).unwrap(), "GET /\n");
+    }
+}
@@ -0,0 +1,44 @@
+//! # Clock
+//!
+//! Time source for services that act after a delay, injectable so tests can
+//! move time forward without sleeping.
+
+use std::time::Instant;
+
+/// Source of the current time, injectable so tests can advance it
+pub trait Clock: Send {
+    /// Get the current instant
+    fn now(&self) -> Instant;
+}
+
+/// Clock backed by the system's monotonic time
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Clock that only moves when a test advances it
+#[cfg(test)]
+#[derive(Clone)]
+pub struct ManualClock(std::sync::Arc<std::sync::Mutex<Instant>>);
+
+#[cfg(test)]
+impl ManualClock {
+    pub fn new() -> Self {
+        Self(std::sync::Arc::new(std::sync::Mutex::new(Instant::now())))
+    }
+
+    pub fn advance(&self, duration: std::time::Duration) {
+        *self.0.lock().unwrap() += duration;
+    }
+}
+
+#[cfg(test)]
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.0.lock().unwrap()
+    }
+}
@@ -0,0 +1,580 @@
+//! # File Service
+//!
+//! Writes buffer content to disk for the `:w` ex command, and response bodies
+//! for `:save`, reads files into the buffer for `:e`, and completes the paths
+//! typed after them. Files are UTF-8 unless `:set fileencoding` names another
+//! encoding, in which case `:e` decodes from and `:w` encodes to it.
+
+use crate::repl::error::{AppError, AppResult};
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Suffix appended to a file name to form its backup name (vim's default `backupext`)
+pub const BACKUP_SUFFIX: &str = "~";
+
+/// Write buffer content to a file, either replacing or appending to it
+///
+/// The written content always ends with a newline, like vim's line-based writes.
+/// When appending to a file that does not end with a newline, one is inserted
+/// first so the new content starts on its own line. Missing files are created.
+///
+/// # Returns
+/// The number of bytes written to the file.
+pub fn write_buffer(path: &str, content: &str, append: bool) -> AppResult<usize> {
+    write_buffer_as(path, content, append, UTF_8)
+}
+
+/// Write buffer content to a file in the given encoding
+///
+/// Behaves like [`write_buffer`], but encodes the content first. Nothing is
+/// written when the content has characters the encoding cannot represent.
+///
+/// # Returns
+/// The number of bytes written to the file.
+pub fn write_buffer_as(
+    path: &str,
+    content: &str,
+    append: bool,
+    encoding: &'static Encoding,
+) -> AppResult<usize> {
+    let path = shellexpand::tilde(path).into_owned();
+    let io_error = |e: std::io::Error| AppError::io(&path, &e);
+
+    // Encode before opening, so a failed conversion leaves the file untouched
+    let mut text = content.to_string();
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    let encoded = encode(&text, encoding, !append)?;
+    let newline = encode("\n", encoding, false)?;
+
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(!append)
+        .append(append)
+        .open(&path)
+        .map_err(io_error)?;
+
+    let mut data = Vec::new();
+    if append && needs_separator(&mut file, &newline).map_err(io_error)? {
+        data.extend_from_slice(&newline);
+    }
+    data.extend_from_slice(&encoded);
+
+    file.write_all(&data).map_err(io_error)?;
+
+    Ok(data.len())
+}
+
+/// Replace a file with buffer content by writing a temporary file and renaming it
+///
+/// The rename swaps the new content in at once, so a write cut short by a
+/// crash or a kill never leaves a half-written file behind (`:set
+/// backupcopy=no`). An existing file's permissions carry over to the new one.
+/// Symlinks, and renames the file system refuses (such as across devices),
+/// fall back to writing the file in place with [`write_buffer`].
+///
+/// # Returns
+/// The number of bytes written to the file.
+pub fn write_buffer_atomic(path: &str, content: &str) -> AppResult<usize> {
+    write_buffer_atomic_as(path, content, UTF_8)
+}
+
+/// Atomically replace a file with buffer content in the given encoding
+///
+/// Behaves like [`write_buffer_atomic`], but encodes the content first.
+///
+/// # Returns
+/// The number of bytes written to the file.
+pub fn write_buffer_atomic_as(
+    path: &str,
+    content: &str,
+    encoding: &'static Encoding,
+) -> AppResult<usize> {
+    let target = PathBuf::from(shellexpand::tilde(path).into_owned());
+    if fs::symlink_metadata(&target).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+        return write_buffer_as(path, content, false, encoding);
+    }
+
+    let mut data = content.to_string();
+    if !data.ends_with('\n') {
+        data.push('\n');
+    }
+    let data = encode(&data, encoding, true)?;
+
+    let temp = temp_path(&target);
+    let written = fs::write(&temp, &data).and_then(|_| {
+        if let Ok(metadata) = fs::metadata(&target) {
+            fs::set_permissions(&temp, metadata.permissions())?;
+        }
+        fs::File::open(&temp)?.sync_all()
+    });
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp);
+        return Err(AppError::io(&temp.to_string_lossy(), &e));
+    }
+
+    match fs::rename(&temp, &target) {
+        Ok(()) => Ok(data.len()),
+        Err(e) => {
+            tracing::warn!("Rename onto {} failed, writing in place: {}", path, e);
+            let _ = fs::remove_file(&temp);
+            write_buffer_as(path, content, false, encoding)
+        }
+    }
+}
+
+/// Write content to a file exactly as given, replacing it
+///
+/// Unlike [`write_buffer`] no newline is added, so a saved response body
+/// matches what the server sent byte for byte.
+///
+/// # Returns
+/// The number of bytes written to the file.
+pub fn write_content(path: &str, content: &str) -> AppResult<usize> {
+    let path = shellexpand::tilde(path).into_owned();
+    fs::write(&path, content).map_err(|e| AppError::io(&path, &e))?;
+    Ok(content.len())
+}
+
+/// Read a file for the request buffer
+///
+/// The trailing newline that [`write_buffer`] adds is not part of the buffer,
+/// so it is dropped.
+///
+/// # Returns
+/// The buffer content, or `None` when the file does not exist yet.
+pub fn read_buffer(path: &str) -> AppResult<Option<String>> {
+    let path = shellexpand::tilde(path).into_owned();
+    match fs::read_to_string(&path) {
+        Ok(content) => Ok(Some(
+            content.strip_suffix('\n').unwrap_or(&content).to_string(),
+        )),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(AppError::io(&path, &e)),
+    }
+}
+
+/// Decoded buffer content and whether any bytes had to be replaced
+type DecodedBuffer = (String, bool);
+
+/// Read a file for the request buffer, decoding it from the given encoding
+///
+/// Byte sequences that are not valid in the encoding are replaced with
+/// U+FFFD instead of failing the read. A byte order mark is kept as part of
+/// the content so that writing the buffer back reproduces it.
+///
+/// # Returns
+/// The buffer content and whether anything had to be replaced, or `None`
+/// when the file does not exist yet.
+pub fn read_buffer_as(path: &str, encoding: &'static Encoding) -> AppResult<Option<DecodedBuffer>> {
+    let path = shellexpand::tilde(path).into_owned();
+    let bytes = match fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(AppError::io(&path, &e)),
+    };
+
+    let (content, had_errors) = encoding.decode_without_bom_handling(&bytes);
+    let content = content.strip_suffix('\n').unwrap_or(&content).to_string();
+    Ok(Some((content, had_errors)))
+}
+
+/// Look up the encoding for a `fileencoding` name such as `utf-8` or `latin1`
+///
+/// Names follow the WHATWG Encoding Standard, so `latin1` and `iso-8859-1`
+/// both mean windows-1252, its superset. Encodings that can only be read, such
+/// as the `replacement` encoding, are not found.
+pub fn encoding_for_label(label: &str) -> Option<&'static Encoding> {
+    Encoding::for_label(label.as_bytes()).filter(|encoding| {
+        encoding.output_encoding() == *encoding || *encoding == UTF_16LE || *encoding == UTF_16BE
+    })
+}
+
+/// Encode text for writing, refusing characters the encoding cannot represent
+///
+/// encoding_rs writes UTF-16 as UTF-8, so UTF-16 is encoded here instead.
+/// Text that starts a file gets a byte order mark then, unless it already
+/// begins with one kept from reading the file.
+fn encode(text: &str, encoding: &'static Encoding, starts_file: bool) -> AppResult<Vec<u8>> {
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        let mut units = Vec::new();
+        if starts_file && !text.starts_with('\u{feff}') {
+            units.push(0xfeff);
+        }
+        units.extend(text.encode_utf16());
+        let bytes = units
+            .into_iter()
+            .flat_map(|unit| {
+                if encoding == UTF_16BE {
+                    unit.to_be_bytes()
+                } else {
+                    unit.to_le_bytes()
+                }
+            })
+            .collect();
+        return Ok(bytes);
+    }
+
+    let (bytes, _, unmappable) = encoding.encode(text);
+    if unmappable {
+        return Err(AppError::User(format!(
+            "E513: write error, conversion failed (text cannot be written as {})",
+            encoding.name()
+        )));
+    }
+    Ok(bytes.into_owned())
+}
+
+/// List the paths that complete a partially typed path
+///
+/// The directory part is kept as typed so the completion can replace the
+/// argument on the command line. Directories get a trailing `/`, and hidden
+/// entries only show up once the typed name starts with a dot.
+///
+/// # Returns
+/// Matching paths sorted by name, empty when the directory cannot be read.
+pub fn complete_path(prefix: &str) -> Vec<String> {
+    let (dir, name) = match prefix.rfind('/') {
+        Some(index) => prefix.split_at(index + 1),
+        None => ("", prefix),
+    };
+    let read_dir = if dir.is_empty() {
+        ".".to_string()
+    } else {
+        shellexpand::tilde(dir).into_owned()
+    };
+
+    let Ok(entries) = fs::read_dir(read_dir) else {
+        return Vec::new();
+    };
+
+    let mut candidates: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().into_string().ok()?;
+            if !file_name.starts_with(name)
+                || (file_name.starts_with('.') && !name.starts_with('.'))
+            {
+                return None;
+            }
+            let suffix = if entry.path().is_dir() { "/" } else { "" };
+            Some(format!("{dir}{file_name}{suffix}"))
+        })
+        .collect();
+    candidates.sort();
+    candidates
+}
+
+/// Copy an existing file to `<path>~` before it gets overwritten
+///
+/// # Returns
+/// The backup path, or `None` when there was no file to back up.
+pub fn backup_file(path: &str) -> AppResult<Option<PathBuf>> {
+    let path = PathBuf::from(shellexpand::tilde(path).into_owned());
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let backup_path = backup_path(&path);
+    fs::copy(&path, &backup_path).map_err(|e| AppError::io(&backup_path.to_string_lossy(), &e))?;
+
+    Ok(Some(backup_path))
+}
+
+/// Build the backup path for a file by appending the backup suffix
+fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(BACKUP_SUFFIX);
+    PathBuf::from(backup)
+}
+
+/// Build the temporary path an atomic write goes through, next to the file
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(".{name}.tmp"))
+}
+
+/// Check whether a non-empty file is missing its trailing `newline` bytes
+fn needs_separator(file: &mut std::fs::File, newline: &[u8]) -> std::io::Result<bool> {
+    let len = file.metadata()?.len();
+    if len == 0 {
+        return Ok(false);
+    }
+    if len < newline.len() as u64 {
+        return Ok(true);
+    }
+
+    let mut last = vec![0u8; newline.len()];
+    file.seek(SeekFrom::End(-(newline.len() as i64)))?;
+    file.read_exact(&mut last)?;
+    Ok(last != newline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_buffer_should_accumulate_content_when_appending_twice() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("requests.log");
+        let path = path.to_str().unwrap();
+
+        let first = write_buffer(path, "GET /users", true).unwrap();
+        let second = write_buffer(path, "GET /posts", true).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            "GET /users\nGET /posts\n"
+        );
+        assert_eq!(first, 11);
+        assert_eq!(second, 11);
+    }
+
+    #[test]
+    fn write_buffer_should_add_separator_when_file_lacks_trailing_newline() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("requests.log");
+        fs::write(&path, "existing").unwrap();
+        let path = path.to_str().unwrap();
+
+        let appended = write_buffer(path, "GET /users\n", true).unwrap();
+
+        assert_eq!(fs::read_to_string(path).unwrap(), "existing\nGET /users\n");
+        assert_eq!(appended, 12);
+    }
+
+    #[test]
+    fn read_buffer_should_drop_trailing_newline_and_allow_missing_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("request.http");
+        let path = path.to_str().unwrap();
+
+        assert_eq!(read_buffer(path).unwrap(), None);
+
+        write_buffer(path, "GET /users", false).unwrap();
+        assert_eq!(read_buffer(path).unwrap(), Some("GET /users".to_string()));
+    }
+
+    #[test]
+    fn write_buffer_should_overwrite_when_not_appending() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("request.http");
+        fs::write(&path, "old content\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        write_buffer(path, "GET /users", false).unwrap();
+
+        assert_eq!(fs::read_to_string(path).unwrap(), "GET /users\n");
+    }
+
+    #[test]
+    fn write_buffer_should_report_io_error_with_path() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("missing").join("request.http");
+        let path = path.to_str().unwrap();
+
+        assert!(matches!(
+            write_buffer(path, "GET /users", false),
+            Err(AppError::Io { path: error_path, .. }) if error_path == path
+        ));
+    }
+
+    #[test]
+    fn write_content_should_not_add_trailing_newline() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("body.json");
+        let path = path.to_str().unwrap();
+
+        assert_eq!(write_content(path, "{\"id\":1}").unwrap(), 8);
+        assert_eq!(fs::read_to_string(path).unwrap(), "{\"id\":1}");
+    }
+
+    #[test]
+    fn backup_file_should_keep_original_contents_before_overwrite() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("request.http");
+        fs::write(&path, "GET /original\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        let backup = backup_file(path).unwrap().unwrap();
+        write_buffer(path, "GET /updated", false).unwrap();
+
+        assert_eq!(backup, temp_dir.path().join("request.http~"));
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "GET /original\n");
+        assert_eq!(fs::read_to_string(path).unwrap(), "GET /updated\n");
+    }
+
+    #[test]
+    fn write_buffer_atomic_should_replace_file_with_full_content() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("request.http");
+        fs::write(&path, "GET /a/much/longer/original/request\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        assert_eq!(write_buffer_atomic(path, "GET /users").unwrap(), 11);
+
+        assert_eq!(fs::read_to_string(path).unwrap(), "GET /users\n");
+        assert!(!temp_dir.path().join(".request.http.tmp").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_buffer_atomic_should_keep_existing_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("request.http");
+        fs::write(&path, "GET /original\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+        let path = path.to_str().unwrap();
+
+        write_buffer_atomic(path, "GET /updated").unwrap();
+
+        let mode = fs::metadata(path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+        assert_eq!(fs::read_to_string(path).unwrap(), "GET /updated\n");
+    }
+
+    #[test]
+    fn read_and_write_buffer_should_round_trip_latin1_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("fixture.http");
+        fs::write(&path, b"POST /caf\xe9\n\n{\"name\": \"Andr\xe9\"}\n").unwrap();
+        let path = path.to_str().unwrap();
+        let latin1 = encoding_for_label("latin1").unwrap();
+
+        let (content, had_errors) = read_buffer_as(path, latin1).unwrap().unwrap();
+        assert_eq!(content, "POST /caf\u{e9}\n\n{\"name\": \"Andr\u{e9}\"}");
+        assert!(!had_errors);
+
+        write_buffer_atomic_as(path, &content.replace("Andr", "Ren"), latin1).unwrap();
+        assert_eq!(
+            fs::read(path).unwrap(),
+            b"POST /caf\xe9\n\n{\"name\": \"Ren\xe9\"}\n"
+        );
+    }
+
+    #[test]
+    fn read_and_write_buffer_should_round_trip_utf16_file_with_bom() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("fixture.http");
+        let utf16le =
+            |text: &str| -> Vec<u8> { text.encode_utf16().flat_map(u16::to_le_bytes).collect() };
+        fs::write(&path, utf16le("\u{feff}POST /caf\u{e9}\n")).unwrap();
+        let path = path.to_str().unwrap();
+        let encoding = encoding_for_label("utf-16le").unwrap();
+
+        let (content, had_errors) = read_buffer_as(path, encoding).unwrap().unwrap();
+        assert_eq!(content, "\u{feff}POST /caf\u{e9}");
+        assert!(!had_errors);
+
+        write_buffer_atomic_as(path, &content.replace("POST", "PUT"), encoding).unwrap();
+        assert_eq!(fs::read(path).unwrap(), utf16le("\u{feff}PUT /caf\u{e9}\n"));
+
+        write_buffer_as(path, "GET /", true, encoding).unwrap();
+        assert_eq!(
+            fs::read(path).unwrap(),
+            utf16le("\u{feff}PUT /caf\u{e9}\nGET /\n")
+        );
+    }
+
+    #[test]
+    fn encoding_for_label_should_skip_encodings_that_cannot_be_written() {
+        assert_eq!(encoding_for_label("utf-16"), Some(UTF_16LE));
+        assert_eq!(encoding_for_label("iso-2022-kr"), None);
+    }
+
+    #[test]
+    fn write_buffer_as_should_start_new_utf16be_file_with_bom() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("fixture.http");
+        let path = path.to_str().unwrap();
+
+        write_buffer_atomic_as(path, "GET /", encoding_for_label("utf-16be").unwrap()).unwrap();
+
+        assert_eq!(
+            fs::read(path).unwrap(),
+            b"\xfe\xff\0G\0E\0T\0 \0/\0\n".to_vec()
+        );
+    }
+
+    #[test]
+    fn read_buffer_as_should_replace_invalid_sequences() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("fixture.http");
+        fs::write(&path, b"GET /caf\xe9\n").unwrap();
+
+        let (content, had_errors) = read_buffer_as(path.to_str().unwrap(), UTF_8)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(content, "GET /caf\u{fffd}");
+        assert!(had_errors);
+    }
+
+    #[test]
+    fn write_buffer_as_should_refuse_unmappable_characters() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("fixture.http");
+        fs::write(&path, "GET /old\n").unwrap();
+        let path = path.to_str().unwrap();
+        let latin1 = encoding_for_label("latin1").unwrap();
+
+        assert!(matches!(
+            write_buffer_atomic_as(path, "GET /\u{65e5}\u{672c}", latin1),
+            Err(AppError::User(message)) if message.starts_with("E513")
+        ));
+        assert_eq!(fs::read_to_string(path).unwrap(), "GET /old\n");
+    }
+
+    #[test]
+    fn complete_path_should_list_matching_entries_in_typed_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("users.http"), "").unwrap();
+        fs::write(temp_dir.path().join("posts.http"), "").unwrap();
+        fs::write(temp_dir.path().join(".users.swp"), "").unwrap();
+        fs::create_dir(temp_dir.path().join("users")).unwrap();
+        let dir = format!("{}/", temp_dir.path().display());
+
+        assert_eq!(
+            complete_path(&format!("{dir}us")),
+            vec![format!("{dir}users.http"), format!("{dir}users/")]
+        );
+        assert_eq!(
+            complete_path(&format!("{dir}.us")),
+            vec![format!("{dir}.users.swp")]
+        );
+        assert!(complete_path(&format!("{dir}missing/")).is_empty());
+    }
+
+    #[test]
+    fn backup_file_should_skip_missing_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("new.http");
+
+        assert_eq!(backup_file(path.to_str().unwrap()).unwrap(), None);
+        assert!(!temp_dir.path().join("new.http~").exists());
+    }
+
+    #[test]
+    fn backup_file_should_fail_when_backup_cannot_be_written() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("request.http");
+        fs::write(&path, "GET /original\n").unwrap();
+        // A directory in the way of the backup path makes the copy fail
+        fs::create_dir(temp_dir.path().join("request.http~")).unwrap();
+
+        let backup_path = temp_dir.path().join("request.http~");
+        assert!(matches!(
+            backup_file(path.to_str().unwrap()),
+            Err(AppError::Io { path, .. }) if path == backup_path.to_str().unwrap()
+        ));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "GET /original\n");
+    }
+}
@@ -0,0 +1,119 @@
+//! # External Filter
+//!
+//! Runs lines of the request buffer through a shell command and returns what
+//! it prints, for the `=` operator with `:set equalprg=<command>`. Commands
+//! run in the shell chosen with `:set shell=<path>`.
+
+use crate::repl::error::{AppError, AppResult};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Shell used when `:set shell` is not given: `$SHELL`, else the platform shell
+pub fn default_shell() -> String {
+    shell_or_default(std::env::var_os("SHELL").and_then(|val| val.into_string().ok()))
+}
+
+/// Pick the shell from the `SHELL` environment value, if set and non-empty
+fn shell_or_default(env_shell: Option<String>) -> String {
+    match env_shell {
+        Some(shell) if !shell.is_empty() => shell,
+        _ if cfg!(windows) => "cmd".to_string(),
+        _ => "/bin/sh".to_string(),
+    }
+}
+
+/// Flag that makes `shell` run the command string that follows it
+fn command_flag(shell: &str) -> &'static str {
+    let name = std::path::Path::new(shell)
+        .file_stem()
+        .and_then(|name| name.to_str())
+        .unwrap_or(shell);
+    if name.eq_ignore_ascii_case("cmd") {
+        "/C"
+    } else {
+        "-c"
+    }
+}
+
+/// Pipe `input` through `command` run by `shell` (`<shell> -c`, `cmd /C`)
+///
+/// The input is written from another thread so a command that starts printing
+/// before it has read everything cannot deadlock on a full pipe.
+///
+/// # Returns
+/// The command's standard output, or an error when it could not be started
+/// or exited with a failure status.
+pub fn run_filter(shell: &str, command: &str, input: &str) -> AppResult<String> {
+    let mut child = Command::new(shell)
+        .arg(command_flag(shell))
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::io(command, &e))?;
+
+    let writer = child.stdin.take().map(|mut stdin| {
+        let input = input.to_string();
+        std::thread::spawn(move || stdin.write_all(input.as_bytes()))
+    });
+    let output = child
+        .wait_with_output()
+        .map_err(|e| AppError::io(command, &e))?;
+    // A command that exits without reading its input closes the pipe early,
+    // which only matters if it also failed
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let code = output
+            .status
+            .code()
+            .map_or_else(|| "a signal".to_string(), |code| code.to_string());
+        return Err(AppError::User(match stderr.lines().next() {
+            Some(reason) => format!("shell returned {code}: {reason}"),
+            None => format!("shell returned {code}"),
+        }));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_filter_should_return_command_output() {
+        assert_eq!(
+            run_filter("/bin/sh", "tr a-z A-Z", "get /users\n").unwrap(),
+            "GET /USERS\n"
+        );
+    }
+
+    #[test]
+    fn run_filter_should_report_failing_command() {
+        assert_eq!(
+            run_filter("/bin/sh", "echo 'bad input' >&2; exit 3", "").unwrap_err(),
+            AppError::User("shell returned 3: bad input".to_string())
+        );
+    }
+
+    #[test]
+    fn run_filter_should_use_the_configured_shell() {
+        // `echo` as the shell just prints the arguments it was started with
+        assert_eq!(run_filter("echo", "true", "").unwrap(), "-c true\n");
+    }
+
+    #[test]
+    fn default_shell_should_fall_back_when_shell_is_unset() {
+        assert_eq!(shell_or_default(Some("/bin/zsh".to_string())), "/bin/zsh");
+        let fallback = if cfg!(windows) { "cmd" } else { "/bin/sh" };
+        assert_eq!(shell_or_default(None), fallback);
+        assert_eq!(shell_or_default(Some(String::new())), fallback);
+        assert_eq!(command_flag("cmd.exe"), "/C");
+        assert_eq!(command_flag("/usr/bin/fish"), "-c");
+    }
+}
@@ -2,9 +2,15 @@
 //!
 //! Manages HTTP request execution and response handling.
 
+use crate::repl::error::{AppError, AppResult};
+use crate::repl::text::json_path::json_value_at;
+use crate::repl::text::request_headers::parse_header_block;
+use crate::repl::text::request_line::{join_request_line, resolve_url};
+use crate::repl::text::variables::substitute_variables;
 use anyhow::Result;
 use bluenote::{HttpClient, HttpConnectionProfile, HttpRequestArgs, HttpResponse, Url, UrlPath};
 use std::collections::HashMap;
+use std::future::Future;
 use tokio::sync::mpsc;
 
 /// Type alias for parsed request result
@@ -13,6 +19,9 @@ pub type ParsedRequest = (BufferRequestArgs, String);
 /// Type alias for profile information (name, path)
 type ProfileInfo = (String, String);
 
+/// Type alias for profile headers as (name, value) pairs
+type HeaderList = Vec<(String, String)>;
+
 /// Message type for async HTTP response handling
 #[derive(Debug)]
 pub enum HttpResponseMessage {
@@ -23,7 +32,13 @@ pub enum HttpResponseMessage {
         url: String,
     },
     /// Error during request execution
-    Error { message: String },
+    Error { error: AppError },
+    /// Value captured from a setup request's response by `:chain`
+    Captured {
+        variable: String,
+        path: String,
+        value: String,
+    },
 }
 
 /// HTTP request arguments parsed from the request buffer
@@ -53,17 +68,62 @@ impl HttpRequestArgs for BufferRequestArgs {
     }
 }
 
+/// Profile settings an `HttpClient` is built from
+///
+/// The client keeps its connection pool for as long as it lives, so it is only
+/// rebuilt when one of these changes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientSettings {
+    server: Option<String>,
+    user: Option<String>,
+    password: Option<String>,
+    headers: HeaderList,
+    proxy: Option<String>,
+    ca_cert: Option<String>,
+    insecure: Option<bool>,
+}
+
+impl ClientSettings {
+    /// Capture the client settings of a profile
+    pub fn from_profile(profile: &impl HttpConnectionProfile) -> Self {
+        let mut headers: HeaderList = profile
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        headers.sort();
+
+        Self {
+            server: profile.server().map(|server| server.to_string()),
+            user: profile.user().map(|user| user.to_string()),
+            password: profile.password().map(|password| password.to_string()),
+            headers,
+            proxy: profile.proxy().map(|proxy| proxy.to_string()),
+            ca_cert: profile.ca_cert().map(|ca_cert| ca_cert.to_string()),
+            insecure: profile.insecure(),
+        }
+    }
+}
+
 /// Service for managing HTTP request operations
 ///
 /// This service encapsulates HTTP client functionality and provides
 /// a clean interface for executing requests and handling responses.
 pub struct HttpService {
-    /// The underlying HTTP client
+    /// The underlying HTTP client, shared by every request so connections are pooled
     client: Option<HttpClient>,
+    /// Settings the current client was built from
+    client_settings: ClientSettings,
+    /// How many times a client has been built
+    client_builds: usize,
     /// Profile info for recreating clients if needed
     profile_info: Option<ProfileInfo>,
     /// Session headers that persist across requests
     session_headers: HashMap<String, String>,
+    /// Values captured by `:chain`, substituted for `${NAME}` in requests
+    variables: HashMap<String, String>,
+    /// Base URL of the profile's server, used to resolve relative request paths
+    base_url: Option<String>,
     /// Channel for receiving async HTTP responses
     response_receiver: mpsc::Receiver<HttpResponseMessage>,
     /// Channel sender for async tasks to send responses
@@ -82,8 +142,12 @@ impl HttpService {
 
         Ok(Self {
             client: Some(client),
+            client_settings: ClientSettings::from_profile(profile),
+            client_builds: 1,
             profile_info: None, // Will be set separately if needed
             session_headers: HashMap::new(),
+            variables: HashMap::new(),
+            base_url: profile.server().map(|server| server.to_string()),
             response_receiver,
             response_sender,
         })
@@ -94,32 +158,73 @@ impl HttpService {
         self.profile_info = Some((profile_name, profile_path));
     }
 
-    /// Reconfigure the HTTP client with a profile (used after taking the client)
+    /// Reconfigure the HTTP client with a profile
+    ///
+    /// The existing client, and with it the pooled connections, is kept unless
+    /// the profile changes a setting the client is built from.
     pub fn reconfigure(&mut self, profile: &impl HttpConnectionProfile) -> Result<()> {
-        self.client = Some(HttpClient::new(profile)?);
+        let settings = ClientSettings::from_profile(profile);
+        if self.client.is_none() || settings != self.client_settings {
+            tracing::debug!("Client settings changed, rebuilding HttpClient");
+            self.client = Some(HttpClient::new(profile)?);
+            self.client_settings = settings;
+            self.client_builds += 1;
+        }
+        self.base_url = profile.server().map(|server| server.to_string());
         Ok(())
     }
 
+    /// Get how many times an HTTP client has been built
+    pub fn client_build_count(&self) -> usize {
+        self.client_builds
+    }
+
+    /// Override the base URL used to resolve relative request paths
+    pub fn set_base_url(&mut self, base_url: Option<String>) {
+        self.base_url = base_url;
+    }
+
     /// Check if HTTP client is available
     pub fn is_available(&self) -> bool {
         self.client.is_some()
     }
 
-    /// Parse HTTP request from text content (static version for async usage)
-    fn parse_request_static(
-        text: &str,
-        session_headers: HashMap<String, String>,
-    ) -> Result<ParsedRequest> {
+    /// Parse HTTP request from text content
+    /// Returns (BufferRequestArgs, url_str) or error message
+    ///
+    /// `${NAME}` references in the text and in header values are expanded from
+    /// the captured variables first. Profile headers that reference a variable are sent
+    /// expanded, in place of the value the client adds. Header lines below the
+    /// request line are sent as headers, overriding session headers of the same
+    /// name, which in turn override profile headers; the rest is the body.
+    pub fn parse_request(&self, text: &str) -> AppResult<ParsedRequest> {
+        let variables = &self.variables;
+        let text = substitute_variables(text, variables);
+        let mut headers: HashMap<String, String> = self
+            .client_settings
+            .headers
+            .iter()
+            .filter_map(|(name, value)| {
+                let expanded = substitute_variables(value, variables);
+                (expanded != *value).then(|| (name.clone(), expanded))
+            })
+            .collect();
+        headers.extend(
+            self.session_headers
+                .iter()
+                .map(|(name, value)| (name.clone(), substitute_variables(value, variables))),
+        );
         let lines: Vec<&str> = text.lines().collect();
 
         if lines.is_empty() || lines[0].trim().is_empty() {
-            return Err(anyhow::anyhow!("No request to execute"));
+            return Err(AppError::User("No request to execute".to_string()));
         }
 
-        // Parse first line as method and URL
-        let parts: Vec<&str> = lines[0].split_whitespace().collect();
+        // Parse the request line, which may continue over several lines, as method and URL
+        let (request_line, _) = join_request_line(&lines);
+        let parts: Vec<&str> = request_line.split_whitespace().collect();
         if parts.len() < 2 {
-            return Err(anyhow::anyhow!("Invalid request format. Use: METHOD URL"));
+            return Err(AppError::Parse("expected METHOD URL".to_string()));
         }
 
         let method = parts[0].to_uppercase();
@@ -128,52 +233,67 @@ impl HttpService {
         // Parse URL
         let url = Url::parse(&url_str);
 
-        // Skip empty line after URL if it exists, then rest becomes the body
-        let body_start_idx = if lines.len() > 1 && lines[1].trim().is_empty() {
-            2
-        } else {
-            1
-        };
+        // Header lines follow the request line; the rest becomes the body
+        let header_block = parse_header_block(&lines);
+        for header in &header_block.headers {
+            headers.insert(header.name.to_string(), header.value.to_string());
+        }
 
-        let body = if lines.len() > body_start_idx {
-            Some(lines[body_start_idx..].join("\n"))
+        let body = if lines.len() > header_block.body_start {
+            Some(lines[header_block.body_start..].join("\n"))
         } else {
             None
         };
 
-        // Create request args with session headers
         let request_args = BufferRequestArgs {
             method: Some(method),
             url_path: url.to_url_path().cloned(),
             body,
-            headers: session_headers,
+            headers,
         };
 
         Ok((request_args, url_str))
     }
 
-    /// Parse HTTP request from text content
-    /// Returns (BufferRequestArgs, url_str) or error message
-    pub fn parse_request(&self, text: &str) -> Result<ParsedRequest> {
-        Self::parse_request_static(text, self.session_headers.clone())
+    /// Describe the request that would be sent, without sending it (:dryrun)
+    ///
+    /// Shows the method, the URL resolved against the profile base, and the
+    /// final headers sorted by name.
+    pub fn dry_run(&self, text: &str) -> AppResult<String> {
+        let (request_args, url_str) = self.parse_request(text)?;
+        let method = request_args.method().map_or("GET", String::as_str);
+        let url = resolve_url(self.base_url.as_deref(), &url_str);
+
+        let mut headers: Vec<String> = request_args
+            .headers()
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect();
+        headers.sort();
+
+        if headers.is_empty() {
+            Ok(format!("{method} {url}"))
+        } else {
+            Ok(format!("{method} {url} [{}]", headers.join(", ")))
+        }
     }
 
     /// Execute an HTTP request
     pub async fn execute_request(
         &self,
         request_args: &impl HttpRequestArgs,
-    ) -> Result<HttpResponse> {
+    ) -> AppResult<HttpResponse> {
         match &self.client {
             Some(client) => client
                 .request(request_args)
                 .await
-                .map_err(|e| anyhow::anyhow!("HTTP request failed: {e}")),
-            None => Err(anyhow::anyhow!("HTTP client not configured")),
+                .map_err(|e| AppError::from_request_error(&e)),
+            None => Err(AppError::User("HTTP client not configured".to_string())),
         }
     }
 
     /// Execute request from raw text
-    pub async fn execute_from_text(&self, request_text: &str) -> Result<HttpResponse> {
+    pub async fn execute_from_text(&self, request_text: &str) -> AppResult<HttpResponse> {
         let (request_args, _url) = self.parse_request(request_text)?;
         self.execute_request(&request_args).await
     }
@@ -183,7 +303,7 @@ impl HttpService {
         &self,
         request_text: &str,
         verbose: bool,
-    ) -> Result<(String, u16, Option<u64>)> {
+    ) -> AppResult<(String, u16, Option<u64>)> {
         let start_time = std::time::Instant::now();
 
         // Parse request
@@ -252,6 +372,16 @@ impl HttpService {
         &self.session_headers
     }
 
+    /// Bind a value to `${name}` for the requests that follow
+    pub fn set_variable(&mut self, name: String, value: String) {
+        self.variables.insert(name, value);
+    }
+
+    /// Get the values bound so far
+    pub fn variables(&self) -> &HashMap<String, String> {
+        &self.variables
+    }
+
     /// Check if there are any pending HTTP responses (non-blocking)
     pub fn poll_response(&mut self) -> Option<HttpResponseMessage> {
         self.response_receiver.try_recv().ok()
@@ -259,82 +389,96 @@ impl HttpService {
 
     /// Execute HTTP request asynchronously
     ///
-    /// This spawns a tokio task that executes the request and sends the result
-    /// back through the internal channel, allowing non-blocking operation.
+    /// Parsing happens here; sending the request and waiting for the response
+    /// happen on a tokio task (see `spawn_request`).
     pub fn execute_async(&mut self, request_text: String) {
         // Parse the request first (synchronously)
-        // Clone session headers before parsing to avoid lifetime issues
-        let session_headers = self.session_headers.clone();
-
-        // Clone the client if available
+        let parsed_result = self.parse_request(&request_text);
         let client = self.client.clone();
 
-        // Clone the sender for the async task
-        let result_sender = self.response_sender.clone();
-
-        // Now parse the request completely independently
-        let parsed_result = Self::parse_request_static(&request_text, session_headers);
-
-        match parsed_result {
-            Ok((request_args, url_str)) => {
-                // Check if we have a client
-                let client = match client {
-                    Some(c) => c,
-                    None => {
-                        tokio::spawn(async move {
-                            let _ = result_sender
-                                .send(HttpResponseMessage::Error {
-                                    message: "HTTP client not configured".to_string(),
-                                })
-                                .await;
-                        });
-                        return;
-                    }
+        self.spawn_request(async move {
+            let (request_args, url_str) = match parsed_result {
+                Ok(parsed) => parsed,
+                Err(error) => return HttpResponseMessage::Error { error },
+            };
+            let Some(client) = client else {
+                return HttpResponseMessage::Error {
+                    error: AppError::User("HTTP client not configured".to_string()),
                 };
-
-                // result_sender was already cloned above
-
-                // Spawn async task for HTTP execution
-                tokio::spawn(async move {
-                    // Clone for the response since we'll move it for the request
-                    let request_args_clone = request_args.clone();
-
-                    // Execute the HTTP request
-                    let response_msg = match client.request(&request_args).await {
-                        Ok(response) => HttpResponseMessage::Success {
-                            request: request_args_clone,
-                            response: Box::new(response),
-                            url: url_str,
-                        },
-                        Err(e) => {
-                            // Show full error chain using anyhow's chain iterator
-                            let mut error_message = format!("{e}");
-                            for cause in e.chain().skip(1) {
-                                error_message.push_str(&format!("\n  Caused by: {cause}"));
-                            }
-                            tracing::error!("HTTP request failed: {error_message}");
-                            HttpResponseMessage::Error {
-                                message: error_message,
-                            }
-                        }
-                    };
-
-                    // Send the result back through the channel
-                    // Ignore send errors (receiver might have been dropped)
-                    let _ = result_sender.send(response_msg).await;
-                });
+            };
+
+            // Clone for the response since we'll move it for the request
+            let request_args_clone = request_args.clone();
+
+            // Execute the HTTP request
+            match client.request(&request_args).await {
+                Ok(response) => HttpResponseMessage::Success {
+                    request: request_args_clone,
+                    response: Box::new(response),
+                    url: url_str,
+                },
+                Err(e) => {
+                    // The message keeps the full error chain
+                    let error = AppError::from_request_error(&e);
+                    tracing::error!("HTTP request failed: {error}");
+                    HttpResponseMessage::Error { error }
+                }
             }
-            Err(e) => {
-                // Send error message through channel
-                tokio::spawn(async move {
-                    let _ = result_sender
-                        .send(HttpResponseMessage::Error {
-                            message: format!("Failed to parse request: {e}"),
-                        })
-                        .await;
-                });
+        });
+    }
+
+    /// Run a setup request and capture the value at `path` of its response (:chain)
+    ///
+    /// The response is not shown; the result arrives through `poll_response` as
+    /// `HttpResponseMessage::Captured`, or as an error when the request fails or
+    /// the path is not in the response body.
+    pub fn execute_chain_async(&mut self, request_text: String, path: String, variable: String) {
+        let parsed_result = self.parse_request(&request_text);
+        let client = self.client.clone();
+
+        self.spawn_request(async move {
+            let (request_args, _url_str) = match parsed_result {
+                Ok(parsed) => parsed,
+                Err(error) => return HttpResponseMessage::Error { error },
+            };
+            let Some(client) = client else {
+                return HttpResponseMessage::Error {
+                    error: AppError::User("HTTP client not configured".to_string()),
+                };
+            };
+
+            match client.request(&request_args).await {
+                Ok(response) => match json_value_at(response.body(), &path) {
+                    Some(value) => HttpResponseMessage::Captured {
+                        variable,
+                        path,
+                        value,
+                    },
+                    None => HttpResponseMessage::Error {
+                        error: AppError::User(format!("{path} not found in response")),
+                    },
+                },
+                Err(e) => HttpResponseMessage::Error {
+                    error: AppError::from_request_error(&e),
+                },
             }
-        }
+        });
+    }
+
+    /// Run a request on its own tokio task and deliver the result through `poll_response`
+    ///
+    /// The event loop never awaits the request itself, so keys are still
+    /// handled and the screen redrawn while it is in flight.
+    pub fn spawn_request<F>(&self, request: F)
+    where
+        F: Future<Output = HttpResponseMessage> + Send + 'static,
+    {
+        let result_sender = self.response_sender.clone();
+        tokio::spawn(async move {
+            let response_msg = request.await;
+            // Ignore send errors (receiver might have been dropped)
+            let _ = result_sender.send(response_msg).await;
+        });
     }
 }
 
@@ -374,8 +518,12 @@ mod tests {
             let (response_sender, response_receiver) = mpsc::channel(10);
             HttpService {
                 client: None,
+                client_settings: ClientSettings::default(),
+                client_builds: 0,
                 profile_info: None,
                 session_headers: HashMap::new(),
+                variables: HashMap::new(),
+                base_url: None,
                 response_receiver,
                 response_sender,
             }
@@ -467,17 +615,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_request_should_substitute_captured_variables_into_headers() {
+        let mut service = create_test_service();
+        service.set_session_header("Authorization".to_string(), "Bearer ${AUTH}".to_string());
+        service.set_variable("AUTH".to_string(), "abc123".to_string());
+
+        let (args, url) = service
+            .parse_request("GET http://example.com/api/users?token=${AUTH}")
+            .unwrap();
+
+        assert_eq!(
+            args.headers().get("Authorization"),
+            Some(&"Bearer abc123".to_string())
+        );
+        assert_eq!(url, "http://example.com/api/users?token=abc123");
+    }
+
+    #[test]
+    fn parse_request_should_send_header_lines_as_headers() {
+        let mut service = create_test_service();
+        service.set_session_header("Accept".to_string(), "*/*".to_string());
+        let text = "POST http://example.com/api/users\nContent-Type: application/json\nAccept: text/plain\n\n{\"name\": \"test\"}";
+
+        let (args, _) = service.parse_request(text).unwrap();
+
+        assert_eq!(
+            args.headers().get("Content-Type"),
+            Some(&"application/json".to_string())
+        );
+        assert_eq!(
+            args.headers().get("Accept"),
+            Some(&"text/plain".to_string())
+        );
+        assert_eq!(args.body(), Some(&"{\"name\": \"test\"}".to_string()));
+    }
+
+    #[test]
+    fn parse_request_should_substitute_captured_variables_into_buffer_headers() {
+        let mut service = create_test_service();
+        service.set_variable("AUTH".to_string(), "abc123".to_string());
+        let text = "POST http://example.com/api/users\nAuthorization: Bearer ${AUTH}\n\n{}";
+
+        let (args, _) = service.parse_request(text).unwrap();
+
+        assert_eq!(
+            args.headers().get("Authorization"),
+            Some(&"Bearer abc123".to_string())
+        );
+        assert_eq!(args.body(), Some(&"{}".to_string()));
+    }
+
+    #[test]
+    fn parse_request_should_join_request_line_continued_with_backslash() {
+        let service = create_test_service();
+        let text = "GET http://example.com/search?\\\n    q=users&\\\n    page=2\n\n{\"a\": 1}";
+
+        let (args, url) = service.parse_request(text).unwrap();
+
+        assert_eq!(url, "http://example.com/search?q=users&page=2");
+        assert_eq!(args.body(), Some(&"{\"a\": 1}".to_string()));
+    }
+
+    #[test]
+    fn parse_request_should_keep_escaped_trailing_backslash() {
+        let service = create_test_service();
+        let text = "GET http://example.com/dir\\\\\nbody line\\";
+
+        let (args, url) = service.parse_request(text).unwrap();
+
+        assert_eq!(url, "http://example.com/dir\\");
+        assert_eq!(args.body(), Some(&"body line\\".to_string()));
+    }
+
     #[test]
     fn test_parse_request_empty() {
         let service = create_test_service();
         let text = "";
 
         let result = service.parse_request(text);
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("No request to execute"));
+        assert!(matches!(
+            result,
+            Err(AppError::User(message)) if message == "No request to execute"
+        ));
     }
 
     #[test]
@@ -486,10 +706,99 @@ mod tests {
         let text = "GET";
 
         let result = service.parse_request(text);
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("Invalid request format"));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid request: expected METHOD URL"
+        );
+    }
+
+    #[test]
+    fn dry_run_should_resolve_url_against_staging_base() {
+        let mut service = create_test_service();
+        service.set_base_url(Some("https://staging.example.com".to_string()));
+        service.set_session_header("X-Trace".to_string(), "1".to_string());
+        service.set_session_header("Authorization".to_string(), "Bearer abc".to_string());
+
+        let summary = service
+            .dry_run("post /api/users?notify=true\n\n{}")
+            .unwrap();
+
+        assert_eq!(
+            summary,
+            "POST https://staging.example.com/api/users?notify=true [Authorization: Bearer abc, X-Trace: 1]"
+        );
+    }
+
+    #[tokio::test]
+    async fn spawn_request_should_deliver_result_once_request_completes() {
+        let mut service = create_test_service();
+
+        service.spawn_request(async {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            HttpResponseMessage::Error {
+                error: AppError::Timeout("deadline elapsed".to_string()),
+            }
+        });
+        assert!(service.poll_response().is_none());
+
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert!(matches!(
+            service.poll_response(),
+            Some(HttpResponseMessage::Error {
+                error: AppError::Timeout(_)
+            })
+        ));
+    }
+
+    fn load_profile(store: &std::path::Path, name: &str) -> impl HttpConnectionProfile {
+        bluenote::IniProfileStore::new(store.to_str().unwrap())
+            .get_profile(name)
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn parse_request_should_substitute_captured_variables_into_profile_headers() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = temp_dir.path().join("profile");
+        std::fs::write(
+            &store,
+            "[default]\nhost = https://api.example.com\n\
+             @authorization = Bearer ${AUTH}\n@accept = application/json\n",
+        )
+        .unwrap();
+        let mut service = HttpService::new(&load_profile(&store, "default")).unwrap();
+        service.set_variable("AUTH".to_string(), "abc123".to_string());
+
+        let (args, _) = service.parse_request("GET /users").unwrap();
+
+        // Only the header that references a variable is resent, expanded
+        let headers: Vec<&String> = args.headers().values().collect();
+        assert_eq!(headers, vec!["Bearer abc123"]);
+    }
+
+    #[tokio::test]
+    async fn http_service_should_reuse_client_until_client_settings_change() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = temp_dir.path().join("profile");
+        std::fs::write(
+            &store,
+            "[default]\nhost = https://api.example.com\n\n\
+             [proxied]\nhost = https://api.example.com\nproxy = http://proxy.example.com:8080\n",
+        )
+        .unwrap();
+        let profile = load_profile(&store, "default");
+        let mut service = HttpService::new(&profile).unwrap();
+
+        // Repeated requests and reloading the same profile share one client
+        service.execute_async("GET /users".to_string());
+        service.execute_async("GET /users/1".to_string());
+        service.reconfigure(&profile).unwrap();
+        assert_eq!(service.client_build_count(), 1);
+
+        service
+            .reconfigure(&load_profile(&store, "proxied"))
+            .unwrap();
+        assert_eq!(service.client_build_count(), 2);
     }
 }
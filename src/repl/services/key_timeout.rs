@@ -0,0 +1,110 @@
+//! # Key Timeout Service
+//!
+//! Tracks how long a multi-key sequence such as `gg` or `dd` has been waiting
+//! for its next key, so a lone prefix key gives up after `:set timeoutlen=<ms>`.
+
+use crate::repl::services::clock::{Clock, SystemClock};
+use std::time::{Duration, Instant};
+
+/// Milliseconds to wait for the rest of a sequence unless `timeoutlen` says otherwise
+pub const DEFAULT_TIMEOUTLEN_MS: usize = 1000;
+
+/// Service that times out pending key sequences
+pub struct KeyTimeoutService {
+    timeout: Duration,
+    clock: Box<dyn Clock>,
+    pending_since: Option<Instant>,
+}
+
+impl KeyTimeoutService {
+    /// Create a key timeout service using the system clock
+    pub fn new() -> Self {
+        Self::with_clock(Box::new(SystemClock))
+    }
+
+    /// Create a key timeout service using the given clock
+    pub fn with_clock(clock: Box<dyn Clock>) -> Self {
+        Self {
+            timeout: Duration::from_millis(DEFAULT_TIMEOUTLEN_MS as u64),
+            clock,
+            pending_since: None,
+        }
+    }
+
+    /// Get the timeout in milliseconds
+    pub fn timeout_ms(&self) -> usize {
+        self.timeout.as_millis() as usize
+    }
+
+    /// Set the timeout in milliseconds
+    pub fn set_timeout_ms(&mut self, ms: usize) {
+        self.timeout = Duration::from_millis(ms as u64);
+    }
+
+    /// Start waiting for the next key of a sequence
+    pub fn start(&mut self) {
+        self.pending_since = Some(self.clock.now());
+    }
+
+    /// Stop waiting, because the sequence completed or was abandoned
+    pub fn cancel(&mut self) {
+        self.pending_since = None;
+    }
+
+    /// Check whether a sequence is waiting for its next key
+    pub fn is_pending(&self) -> bool {
+        self.pending_since.is_some()
+    }
+
+    /// Check whether the pending sequence has waited longer than the timeout
+    pub fn is_expired(&self) -> bool {
+        self.pending_since
+            .is_some_and(|since| self.clock.now() - since >= self.timeout)
+    }
+}
+
+impl Default for KeyTimeoutService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repl::services::clock::ManualClock;
+
+    #[test]
+    fn is_expired_should_become_true_after_timeout() {
+        let clock = ManualClock::new();
+        let mut service = KeyTimeoutService::with_clock(Box::new(clock.clone()));
+        service.set_timeout_ms(500);
+        assert!(!service.is_expired());
+
+        service.start();
+        clock.advance(Duration::from_millis(499));
+        assert!(!service.is_expired());
+
+        clock.advance(Duration::from_millis(1));
+        assert!(service.is_expired());
+
+        service.cancel();
+        assert!(!service.is_pending());
+        assert!(!service.is_expired());
+    }
+
+    #[test]
+    fn start_should_restart_the_wait() {
+        let clock = ManualClock::new();
+        let mut service = KeyTimeoutService::with_clock(Box::new(clock.clone()));
+
+        service.start();
+        clock.advance(Duration::from_millis(800));
+        service.start();
+        clock.advance(Duration::from_millis(800));
+        assert!(!service.is_expired());
+
+        clock.advance(Duration::from_millis(200));
+        assert!(service.is_expired());
+    }
+}
@@ -0,0 +1,98 @@
+//! # Match Flash Service
+//!
+//! Times the flash over the bracket matching one just typed
+//! (`:set showmatch on`), for `:set matchtime=<tenths>` tenths of a second.
+
+use crate::repl::services::clock::{Clock, SystemClock};
+use std::time::{Duration, Instant};
+
+/// Tenths of a second a matching bracket flashes unless `matchtime` says otherwise
+pub const DEFAULT_MATCHTIME: usize = 5;
+
+/// Service that times out the matching bracket flash
+pub struct MatchFlashService {
+    matchtime: usize,
+    clock: Box<dyn Clock>,
+    shown_since: Option<Instant>,
+}
+
+impl MatchFlashService {
+    /// Create a match flash service using the system clock
+    pub fn new() -> Self {
+        Self::with_clock(Box::new(SystemClock))
+    }
+
+    /// Create a match flash service using the given clock
+    pub fn with_clock(clock: Box<dyn Clock>) -> Self {
+        Self {
+            matchtime: DEFAULT_MATCHTIME,
+            clock,
+            shown_since: None,
+        }
+    }
+
+    /// Get the flash duration in tenths of a second
+    pub fn matchtime(&self) -> usize {
+        self.matchtime
+    }
+
+    /// Set the flash duration in tenths of a second; 0 turns the flash off
+    pub fn set_matchtime(&mut self, tenths: usize) {
+        self.matchtime = tenths;
+    }
+
+    /// Check whether a matching bracket should flash at all
+    pub fn is_enabled(&self) -> bool {
+        self.matchtime > 0
+    }
+
+    /// Start timing a flash that was just shown
+    pub fn start(&mut self) {
+        self.shown_since = Some(self.clock.now());
+    }
+
+    /// Stop timing, because the flash was taken down
+    pub fn cancel(&mut self) {
+        self.shown_since = None;
+    }
+
+    /// Check whether the flash has been shown for `matchtime`
+    pub fn is_expired(&self) -> bool {
+        let duration = Duration::from_millis(self.matchtime as u64 * 100);
+        self.shown_since
+            .is_some_and(|since| self.clock.now() - since >= duration)
+    }
+}
+
+impl Default for MatchFlashService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repl::services::clock::ManualClock;
+
+    #[test]
+    fn is_expired_should_follow_matchtime_in_tenths() {
+        let clock = ManualClock::new();
+        let mut service = MatchFlashService::with_clock(Box::new(clock.clone()));
+        service.set_matchtime(3);
+        assert!(!service.is_expired());
+
+        service.start();
+        clock.advance(Duration::from_millis(299));
+        assert!(!service.is_expired());
+
+        clock.advance(Duration::from_millis(1));
+        assert!(service.is_expired());
+
+        service.cancel();
+        assert!(!service.is_expired());
+
+        service.set_matchtime(0);
+        assert!(!service.is_enabled());
+    }
+}
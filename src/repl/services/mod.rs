@@ -9,12 +9,27 @@
 //! - Providing complex business logic
 //! - Abstracting external resources
 
+pub mod autoread;
+pub mod autosave;
+pub mod clock;
+pub mod file;
+pub mod filter;
 pub mod http;
+pub mod key_timeout;
+pub mod match_flash;
+pub mod session;
+pub mod template;
 pub mod yank;
+pub mod yank_flash;
 
 // Re-export service types
+pub use autoread::{AutoreadService, ExternalChange};
+pub use autosave::AutosaveService;
 pub use http::{BufferRequestArgs, HttpExecutionResult, HttpResponseMessage, HttpService};
+pub use key_timeout::KeyTimeoutService;
+pub use match_flash::MatchFlashService;
 pub use yank::YankService;
+pub use yank_flash::YankFlashService;
 
 /// Aggregates all services for convenient access
 pub struct Services {
@@ -22,6 +37,16 @@ pub struct Services {
     pub http: Option<HttpService>,
     /// Service for yank/paste operations
     pub yank: YankService,
+    /// Service for periodic writes of the request buffer
+    pub autosave: AutosaveService,
+    /// Service for noticing external changes to the request buffer's file
+    pub autoread: AutoreadService,
+    /// Service for timing out pending multi-key sequences
+    pub key_timeout: KeyTimeoutService,
+    /// Service for timing the highlight over just-yanked text
+    pub yank_flash: YankFlashService,
+    /// Service for timing the flash over a matching bracket
+    pub match_flash: MatchFlashService,
 }
 
 impl Services {
@@ -30,6 +55,11 @@ impl Services {
         Self {
             http: None,
             yank: YankService::new(),
+            autosave: AutosaveService::new(),
+            autoread: AutoreadService::new(),
+            key_timeout: KeyTimeoutService::new(),
+            yank_flash: YankFlashService::new(),
+            match_flash: MatchFlashService::new(),
         }
     }
 
@@ -0,0 +1,117 @@
+//! # Session Service
+//!
+//! Saves what is being worked on with `:mksession` so `--session` can pick it
+//! up again: the profile, the request file, the request text including unsaved
+//! edits, and the cursor position. Sessions are stored as JSON.
+
+use crate::repl::error::{AppError, AppResult};
+use crate::repl::events::LogicalPosition;
+use serde_json::{json, Value};
+use std::path::Path;
+
+use super::file;
+
+/// File `:mksession` writes when no name is given
+pub const DEFAULT_SESSION_FILE: &str = "Session.json";
+
+/// Editor state saved by `:mksession`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    /// Profile the requests were sent with
+    pub profile: String,
+    /// File the request buffer was edited from, if any
+    pub request_file: Option<String>,
+    /// Request buffer text, which may differ from the file
+    pub request_text: String,
+    /// Cursor position in the request buffer
+    pub cursor: LogicalPosition,
+}
+
+impl Session {
+    /// Lay out the session as pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        let value = json!({
+            "profile": self.profile,
+            "request_file": self.request_file,
+            "request_text": self.request_text,
+            "cursor": [self.cursor.line, self.cursor.column],
+        });
+        // Serializing a `Value` built from strings and numbers cannot fail
+        serde_json::to_string_pretty(&value).unwrap_or_default()
+    }
+
+    /// Read a session back from its JSON
+    ///
+    /// # Returns
+    /// `None` if the JSON is not a session.
+    pub fn from_json(text: &str) -> Option<Self> {
+        let value: Value = serde_json::from_str(text).ok()?;
+        let position = |index: usize| value["cursor"].get(index)?.as_u64();
+        Some(Self {
+            profile: value["profile"].as_str()?.to_string(),
+            request_file: value["request_file"].as_str().map(str::to_string),
+            request_text: value["request_text"].as_str()?.to_string(),
+            cursor: LogicalPosition::new(position(0)? as usize, position(1)? as usize),
+        })
+    }
+}
+
+/// Write `session` to `path`
+///
+/// An existing file is only replaced with `force` (`:mksession!`).
+pub fn save_session(path: &str, session: &Session, force: bool) -> AppResult<()> {
+    if !force && Path::new(path).exists() {
+        return Err(AppError::User(format!(
+            "E189: \"{path}\" exists (add ! to override)"
+        )));
+    }
+    file::write_buffer(path, &session.to_json(), false)?;
+    Ok(())
+}
+
+/// Read the session saved in `path`
+pub fn load_session(path: &str) -> AppResult<Session> {
+    let text = std::fs::read_to_string(path).map_err(|e| AppError::io(path, &e))?;
+    Session::from_json(&text)
+        .ok_or_else(|| AppError::User(format!("Not a blueline session: {path}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_session(request_file: Option<&str>) -> Session {
+        Session {
+            profile: "staging".to_string(),
+            request_file: request_file.map(str::to_string),
+            request_text: "GET /users\n\n{\"名前\": 1}".to_string(),
+            cursor: LogicalPosition::new(2, 3),
+        }
+    }
+
+    #[test]
+    fn session_should_round_trip_through_save_and_load() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("Session.json");
+        let path = path.to_str().unwrap();
+
+        for session in [sample_session(Some("users.http")), sample_session(None)] {
+            save_session(path, &session, true).unwrap();
+            assert_eq!(load_session(path).unwrap(), session);
+        }
+    }
+
+    #[test]
+    fn save_session_should_not_replace_file_without_force() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("Session.json");
+        let path = path.to_str().unwrap();
+        std::fs::write(path, "notes").unwrap();
+
+        let error = save_session(path, &sample_session(None), false).unwrap_err();
+
+        assert!(error.to_string().contains("E189"));
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "notes");
+        assert!(matches!(load_session(path), Err(AppError::User(_))));
+    }
+}
@@ -0,0 +1,123 @@
+//! # Template Service
+//!
+//! Stores reusable request skeletons as plain files in the templates directory
+//! for the `:template save/load/list` ex commands.
+
+use crate::repl::error::{AppError, AppResult};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::file;
+
+/// Save request text as the named template, replacing any existing one
+///
+/// The templates directory is created if it does not exist yet.
+pub fn save_template(dir: &Path, name: &str, content: &str) -> AppResult<PathBuf> {
+    let path = template_path(dir, name)?;
+    fs::create_dir_all(dir).map_err(|e| AppError::io(&dir.to_string_lossy(), &e))?;
+    file::write_buffer(&path.to_string_lossy(), content, false)?;
+    Ok(path)
+}
+
+/// Load the named template as request text
+pub fn load_template(dir: &Path, name: &str) -> AppResult<String> {
+    let path = template_path(dir, name)?;
+    let content = fs::read_to_string(&path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => {
+            AppError::User(format!("Template '{name}' not found in {}", dir.display()))
+        }
+        _ => AppError::io(&path.to_string_lossy(), &e),
+    })?;
+
+    // Templates are saved with a trailing newline that is not part of the request
+    Ok(content
+        .strip_suffix('\n')
+        .map(str::to_string)
+        .unwrap_or(content))
+}
+
+/// List template names in alphabetical order
+///
+/// A missing templates directory simply means no templates have been saved.
+pub fn list_templates(dir: &Path) -> AppResult<Vec<String>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(AppError::io(&dir.to_string_lossy(), &e)),
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Resolve a template name to its file, rejecting names that could escape the directory
+fn template_path(dir: &Path, name: &str) -> AppResult<PathBuf> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(AppError::User(format!("Invalid template name '{name}'")));
+    }
+    Ok(dir.join(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_should_round_trip_through_save_and_load() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().join("templates");
+        let request = "POST /login\n\n{\"user\": \"admin\"}";
+
+        save_template(&dir, "login", request).unwrap();
+
+        assert_eq!(load_template(&dir, "login").unwrap(), request);
+    }
+
+    #[test]
+    fn list_templates_should_return_sorted_names() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+
+        save_template(dir, "users", "GET /users").unwrap();
+        save_template(dir, "login", "POST /login").unwrap();
+
+        assert_eq!(list_templates(dir).unwrap(), vec!["login", "users"]);
+    }
+
+    #[test]
+    fn list_templates_should_be_empty_for_missing_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().join("missing");
+
+        assert!(list_templates(&dir).unwrap().is_empty());
+    }
+
+    #[test]
+    fn template_names_with_path_separators_should_be_rejected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        assert_eq!(
+            save_template(temp_dir.path(), "../escape", "GET /").unwrap_err(),
+            AppError::User("Invalid template name '../escape'".to_string())
+        );
+        assert!(load_template(temp_dir.path(), "nested/name").is_err());
+    }
+
+    #[test]
+    fn load_template_should_fail_for_unknown_name() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        assert_eq!(
+            load_template(temp_dir.path(), "missing").unwrap_err(),
+            AppError::User(format!(
+                "Template 'missing' not found in {}",
+                temp_dir.path().display()
+            ))
+        );
+    }
+}
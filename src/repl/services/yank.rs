@@ -1,13 +1,23 @@
 //! # Yank Service
 //!
 //! Manages yank buffer operations and switching between memory and clipboard implementations.
+//! Besides the unnamed buffer it keeps the named registers `a`-`z` and the
+//! numbered delete registers `1`-`9`.
 
 use anyhow::Result;
+use std::collections::HashMap;
 
 use crate::repl::models::yank_buffer::{
-    ClipboardYankBuffer, MemoryYankBuffer, YankBuffer, YankEntry, YankType,
+    is_read_only_register, ClipboardSink, ClipboardYankBuffer, MemoryYankBuffer,
+    Osc52ClipboardSink, SystemClipboardSink, YankBuffer, YankEntry, YankType, BLACK_HOLE_REGISTER,
 };
 
+/// Clipboard that unnamed yanks are mirrored to, if any
+type BoxedClipboardSink = Option<Box<dyn ClipboardSink>>;
+
+/// How many numbered delete registers (`1`-`9`) are kept
+const NUMBERED_REGISTER_COUNT: usize = 9;
+
 /// Service for managing yank/paste operations
 ///
 /// This service wraps the YankBuffer trait implementations and handles
@@ -17,6 +27,13 @@ pub struct YankService {
     buffer: Box<dyn YankBuffer>,
     /// Whether clipboard integration is enabled
     clipboard_enabled: bool,
+    /// Named registers (`a`-`z`), kept separate from the unnamed buffer
+    registers: HashMap<char, YankEntry>,
+    /// Numbered registers, most recent line delete (`"1`) first
+    numbered: Vec<YankEntry>,
+    /// Clipboard that mirrors every unnamed yank and feeds unnamed pastes
+    /// (`clipboard=unnamedplus`)
+    clipboard_sink: BoxedClipboardSink,
 }
 
 impl YankService {
@@ -25,6 +42,9 @@ impl YankService {
         Self {
             buffer: Box::new(MemoryYankBuffer::new()),
             clipboard_enabled: false,
+            registers: HashMap::new(),
+            numbered: Vec::new(),
+            clipboard_sink: None,
         }
     }
 
@@ -74,13 +94,63 @@ impl YankService {
         self.clipboard_enabled
     }
 
+    /// Share the unnamed register with the system clipboard (`:set clipboard=unnamedplus`)
+    ///
+    /// Without a reachable clipboard (headless or SSH sessions) yanks are
+    /// handed to the terminal with OSC 52 instead, pastes keep coming from the
+    /// internal register, and `false` is returned.
+    pub fn set_unnamedplus_enabled(&mut self, enabled: bool) -> bool {
+        if !enabled {
+            self.clipboard_sink = None;
+            return true;
+        }
+
+        match SystemClipboardSink::new() {
+            Ok(sink) => {
+                self.set_clipboard_sink(Some(Box::new(sink)));
+                true
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "YankService: Clipboard unavailable, falling back to OSC 52: {}",
+                    e
+                );
+                self.set_clipboard_sink(Some(Box::<Osc52ClipboardSink>::default()));
+                false
+            }
+        }
+    }
+
+    /// Replace the clipboard that yanks are mirrored to and pastes read from
+    pub fn set_clipboard_sink(&mut self, sink: BoxedClipboardSink) {
+        self.clipboard_sink = sink;
+    }
+
+    /// Take the latest yank that has to reach the clipboard through the terminal (OSC 52)
+    pub fn take_terminal_copy(&mut self) -> Option<String> {
+        self.clipboard_sink.as_mut()?.take_terminal_copy()
+    }
+
+    /// Check if yanks are mirrored to the system clipboard
+    pub fn is_unnamedplus_enabled(&self) -> bool {
+        self.clipboard_sink.is_some()
+    }
+
     /// Yank text with specified type
+    ///
+    /// With `clipboard=unnamedplus` the text is also copied to the system
+    /// clipboard; a failing clipboard never fails the yank itself.
     pub fn yank(&mut self, text: String, yank_type: YankType) -> Result<()> {
         tracing::debug!(
             "YankService: Yanking {} characters (type: {:?})",
             text.len(),
             yank_type
         );
+        if let Some(sink) = self.clipboard_sink.as_mut() {
+            if let Err(e) = sink.set_text(&text) {
+                tracing::warn!("YankService: Failed to sync yank to clipboard: {}", e);
+            }
+        }
         self.buffer.yank_with_type(text, yank_type)
     }
 
@@ -90,13 +160,100 @@ impl YankService {
     }
 
     /// Paste text from buffer, returning the entry with type information
+    ///
+    /// With `clipboard=unnamedplus` the text comes from the system clipboard,
+    /// so text copied in another application can be pasted. Clipboard text
+    /// ending in a newline pastes line-wise. When the clipboard still holds
+    /// the last yank, or cannot be read, the buffer's entry is used.
     pub fn paste(&mut self) -> Option<YankEntry> {
-        self.buffer.paste_entry()
+        let entry = self.buffer.paste_entry();
+        let Some(sink) = self.clipboard_sink.as_mut() else {
+            return entry;
+        };
+        match sink.get_text() {
+            Ok(text) if text.is_empty() => entry,
+            Ok(text) if entry.as_ref().is_some_and(|entry| entry.text == text) => entry,
+            Ok(text) => {
+                let yank_type = if text.ends_with('\n') {
+                    YankType::Line
+                } else {
+                    YankType::Character
+                };
+                Some(YankEntry { text, yank_type })
+            }
+            Err(e) => {
+                tracing::debug!("YankService: Pasting from internal buffer: {}", e);
+                entry
+            }
+        }
     }
 
     /// Paste text only (for backward compatibility)
     pub fn paste_text(&mut self) -> Option<String> {
-        self.buffer.paste_entry().map(|entry| entry.text)
+        self.paste().map(|entry| entry.text)
+    }
+
+    /// Store text in a register
+    ///
+    /// `"` is the unnamed register (the regular yank buffer); `a`-`z` are named registers.
+    /// The black-hole register `_` accepts the text and forgets it, while the
+    /// read-only registers `%` and `:` refuse it.
+    pub fn yank_to_register(
+        &mut self,
+        register: char,
+        text: String,
+        yank_type: YankType,
+    ) -> Result<()> {
+        match register {
+            '"' => self.yank(text, yank_type),
+            BLACK_HOLE_REGISTER => Ok(()),
+            _ if is_read_only_register(register) => {
+                Err(anyhow::anyhow!("Register '{register}' is read-only"))
+            }
+            'a'..='z' => {
+                tracing::debug!(
+                    "YankService: Yanking {} characters to register '{}'",
+                    text.len(),
+                    register
+                );
+                self.registers
+                    .insert(register, YankEntry { text, yank_type });
+                Ok(())
+            }
+            _ => Err(anyhow::anyhow!("Invalid register: {register}")),
+        }
+    }
+
+    /// Store deleted text in a register, like [`Self::yank_to_register`]
+    ///
+    /// Deletes of whole lines, or spanning lines, are also pushed onto the
+    /// numbered registers: `"1` gets the text and older deletes shift up to
+    /// `"9`, as in Vim. Deletes into the black-hole register leave them alone.
+    pub fn delete_to_register(
+        &mut self,
+        register: char,
+        text: String,
+        yank_type: YankType,
+    ) -> Result<()> {
+        self.yank_to_register(register, text.clone(), yank_type)?;
+
+        if register != BLACK_HOLE_REGISTER && (yank_type == YankType::Line || text.contains('\n')) {
+            self.numbered.insert(0, YankEntry { text, yank_type });
+            self.numbered.truncate(NUMBERED_REGISTER_COUNT);
+        }
+        Ok(())
+    }
+
+    /// Get the contents of a register, if it holds anything
+    pub fn register(&mut self, register: char) -> Option<YankEntry> {
+        match register {
+            '"' => self.paste(),
+            '1'..='9' => {
+                let index = register as usize - '1' as usize;
+                self.numbered.get(index).cloned()
+            }
+            _ => self.registers.get(&register).cloned(),
+        }
     }
 
     /// Check if buffer has content
@@ -119,6 +276,33 @@ impl Default for YankService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// Clipboard sink that records copied text instead of touching the system clipboard
+    struct RecordingSink(Arc<Mutex<Vec<String>>>);
+
+    impl ClipboardSink for RecordingSink {
+        fn set_text(&mut self, text: &str) -> Result<()> {
+            self.0.lock().unwrap().push(text.to_string());
+            Ok(())
+        }
+
+        fn get_text(&mut self) -> Result<String> {
+            self.0
+                .lock()
+                .unwrap()
+                .last()
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("Clipboard is empty"))
+        }
+    }
+
+    fn service_with_recording_sink() -> (YankService, Arc<Mutex<Vec<String>>>) {
+        let copied = Arc::new(Mutex::new(Vec::new()));
+        let mut service = YankService::new();
+        service.set_clipboard_sink(Some(Box::new(RecordingSink(Arc::clone(&copied)))));
+        (service, copied)
+    }
 
     #[test]
     fn yank_service_should_start_with_memory_buffer() {
@@ -148,7 +332,7 @@ mod tests {
 
         // Yank some text in memory mode
         service
-            .yank("preserved text".to_string(), YankType::Block)
+            .yank("preserved text".to_string(), YankType::Block { width: 14 })
             .unwrap();
 
         // Try to switch to clipboard (may fail in test environment)
@@ -158,7 +342,7 @@ mod tests {
         assert!(service.has_content());
         if let Some(entry) = service.paste() {
             assert_eq!(entry.text, "preserved text");
-            assert_eq!(entry.yank_type, YankType::Block);
+            assert_eq!(entry.yank_type, YankType::Block { width: 14 });
         } else {
             panic!("Content should be preserved after mode switch");
         }
@@ -177,4 +361,173 @@ mod tests {
         assert!(!service.has_content());
         assert!(service.paste().is_none());
     }
+
+    #[test]
+    fn register_should_return_unnamed_buffer_for_double_quote() {
+        let mut service = YankService::new();
+
+        service.yank_text("unnamed".to_string()).unwrap();
+
+        let entry = service.register('"').expect("Should have content");
+        assert_eq!(entry.text, "unnamed");
+    }
+
+    #[test]
+    fn named_register_should_not_affect_unnamed_buffer() {
+        let mut service = YankService::new();
+
+        service.yank_text("unnamed".to_string()).unwrap();
+        service
+            .yank_to_register('a', "named".to_string(), YankType::Character)
+            .unwrap();
+
+        assert_eq!(service.register('a').unwrap().text, "named");
+        assert_eq!(service.paste_text().as_deref(), Some("unnamed"));
+        assert!(service.register('b').is_none());
+    }
+
+    #[test]
+    fn delete_to_register_should_shift_numbered_registers_for_line_deletes() {
+        let mut service = YankService::new();
+
+        service
+            .delete_to_register('"', "first\n".to_string(), YankType::Line)
+            .unwrap();
+        service
+            .delete_to_register('a', "second\n".to_string(), YankType::Line)
+            .unwrap();
+        service
+            .delete_to_register('"', "word".to_string(), YankType::Character)
+            .unwrap();
+        service
+            .delete_to_register('_', "gone\n".to_string(), YankType::Line)
+            .unwrap();
+
+        assert_eq!(service.register('1').unwrap().text, "second\n");
+        assert_eq!(service.register('2').unwrap().text, "first\n");
+        assert!(service.register('3').is_none());
+        assert_eq!(service.register('a').unwrap().text, "second\n");
+        assert_eq!(service.paste_text().as_deref(), Some("word"));
+    }
+
+    #[test]
+    fn black_hole_register_should_discard_text() {
+        let (mut service, copied) = service_with_recording_sink();
+
+        service.yank_text("unnamed".to_string()).unwrap();
+        service
+            .yank_to_register('_', "gone\n".to_string(), YankType::Line)
+            .unwrap();
+
+        assert_eq!(service.paste_text().as_deref(), Some("unnamed"));
+        assert!(service.register('_').is_none());
+        assert_eq!(*copied.lock().unwrap(), vec!["unnamed"]);
+    }
+
+    #[test]
+    fn yank_to_register_should_reject_read_only_registers() {
+        let mut service = YankService::new();
+
+        for register in ['%', ':'] {
+            let error = service
+                .yank_to_register(register, "text".to_string(), YankType::Character)
+                .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                format!("Register '{register}' is read-only")
+            );
+        }
+    }
+
+    #[test]
+    fn yank_to_register_should_reject_invalid_register() {
+        let mut service = YankService::new();
+
+        assert!(service
+            .yank_to_register('!', "text".to_string(), YankType::Character)
+            .is_err());
+    }
+
+    #[test]
+    fn unnamedplus_should_copy_yanks_to_clipboard_sink() {
+        let (mut service, copied) = service_with_recording_sink();
+
+        service.yank("line\n".to_string(), YankType::Line).unwrap();
+        service
+            .yank_to_register('"', "word".to_string(), YankType::Character)
+            .unwrap();
+
+        assert_eq!(*copied.lock().unwrap(), vec!["line\n", "word"]);
+        // Pasting still keeps the yank type from the internal buffer
+        assert_eq!(service.paste().unwrap().yank_type, YankType::Character);
+    }
+
+    #[test]
+    fn unnamedplus_should_leave_named_registers_out_of_clipboard() {
+        let (mut service, copied) = service_with_recording_sink();
+
+        service
+            .yank_to_register('a', "named".to_string(), YankType::Character)
+            .unwrap();
+
+        assert!(copied.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn disabling_unnamedplus_should_stop_clipboard_sync() {
+        let (mut service, copied) = service_with_recording_sink();
+        assert!(service.is_unnamedplus_enabled());
+
+        assert!(service.set_unnamedplus_enabled(false));
+        service.yank_text("internal".to_string()).unwrap();
+
+        assert!(!service.is_unnamedplus_enabled());
+        assert!(copied.lock().unwrap().is_empty());
+        assert_eq!(service.paste_text().as_deref(), Some("internal"));
+    }
+
+    #[test]
+    fn unnamedplus_should_paste_text_copied_outside_the_editor() {
+        let (mut service, copied) = service_with_recording_sink();
+        service.yank("line\n".to_string(), YankType::Line).unwrap();
+
+        copied
+            .lock()
+            .unwrap()
+            .push("https://example.com/users".to_string());
+        let entry = service.paste().unwrap();
+        assert_eq!(entry.text, "https://example.com/users");
+        assert_eq!(entry.yank_type, YankType::Character);
+
+        copied.lock().unwrap().push("Accept: */*\n".to_string());
+        assert_eq!(service.paste().unwrap().yank_type, YankType::Line);
+    }
+
+    #[test]
+    fn unnamedplus_should_paste_internal_register_when_clipboard_is_unreadable() {
+        /// Clipboard that takes copies but cannot be read back, like OSC 52
+        struct WriteOnlySink;
+
+        impl ClipboardSink for WriteOnlySink {
+            fn set_text(&mut self, _text: &str) -> Result<()> {
+                Ok(())
+            }
+
+            fn get_text(&mut self) -> Result<String> {
+                Err(anyhow::anyhow!("Clipboard cannot be read"))
+            }
+        }
+
+        let mut service = YankService::new();
+        service.set_clipboard_sink(Some(Box::new(WriteOnlySink)));
+        assert_eq!(service.paste(), None);
+
+        service
+            .yank("internal".to_string(), YankType::Block { width: 8 })
+            .unwrap();
+
+        let entry = service.paste().unwrap();
+        assert_eq!(entry.text, "internal");
+        assert_eq!(entry.yank_type, YankType::Block { width: 8 });
+    }
 }
@@ -0,0 +1,76 @@
+//! # Yank Flash Service
+//!
+//! Times the brief highlight over just-yanked text (`:set hlyank on`), so the
+//! idle tick of the event loop knows when to take it down again.
+
+use crate::repl::services::clock::{Clock, SystemClock};
+use std::time::{Duration, Instant};
+
+/// Milliseconds yanked text stays highlighted
+pub const YANK_FLASH_MS: u64 = 200;
+
+/// Service that times out the yank highlight
+pub struct YankFlashService {
+    clock: Box<dyn Clock>,
+    shown_since: Option<Instant>,
+}
+
+impl YankFlashService {
+    /// Create a yank flash service using the system clock
+    pub fn new() -> Self {
+        Self::with_clock(Box::new(SystemClock))
+    }
+
+    /// Create a yank flash service using the given clock
+    pub fn with_clock(clock: Box<dyn Clock>) -> Self {
+        Self {
+            clock,
+            shown_since: None,
+        }
+    }
+
+    /// Start timing a highlight that was just shown
+    pub fn start(&mut self) {
+        self.shown_since = Some(self.clock.now());
+    }
+
+    /// Stop timing, because the highlight was taken down
+    pub fn cancel(&mut self) {
+        self.shown_since = None;
+    }
+
+    /// Check whether the highlight has been shown long enough to take down
+    pub fn is_expired(&self) -> bool {
+        self.shown_since
+            .is_some_and(|since| self.clock.now() - since >= Duration::from_millis(YANK_FLASH_MS))
+    }
+}
+
+impl Default for YankFlashService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repl::services::clock::ManualClock;
+
+    #[test]
+    fn is_expired_should_become_true_after_flash_time() {
+        let clock = ManualClock::new();
+        let mut service = YankFlashService::with_clock(Box::new(clock.clone()));
+        assert!(!service.is_expired());
+
+        service.start();
+        clock.advance(Duration::from_millis(YANK_FLASH_MS - 1));
+        assert!(!service.is_expired());
+
+        clock.advance(Duration::from_millis(1));
+        assert!(service.is_expired());
+
+        service.cancel();
+        assert!(!service.is_expired());
+    }
+}
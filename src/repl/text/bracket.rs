@@ -0,0 +1,66 @@
+//! # Bracket Matching
+//!
+//! Finds the opening bracket paired with a closing one, so typing `)`, `]` or
+//! `}` can flash its match (`:set showmatch on`).
+
+/// `(line, column)` of a bracket, with the column counted in characters
+pub type BracketPosition = (usize, usize);
+
+/// Find the opening bracket for the closing bracket at `line`, `column`
+///
+/// Columns are character indices. Nested pairs of the same kind are skipped
+/// over. Returns `None` when there is no closing bracket at the position or
+/// it has no opening partner before it.
+pub fn find_opening_bracket(text: &str, line: usize, column: usize) -> Option<BracketPosition> {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let close = lines.get(line)?.chars().nth(column)?;
+    let open = match close {
+        ')' => '(',
+        ']' => '[',
+        '}' => '{',
+        _ => return None,
+    };
+
+    let mut depth = 0usize;
+    for line_index in (0..=line).rev() {
+        let chars: Vec<char> = lines[line_index].chars().collect();
+        let end = if line_index == line {
+            column
+        } else {
+            chars.len()
+        };
+        for (col, &ch) in chars[..end].iter().enumerate().rev() {
+            if ch == close {
+                depth += 1;
+            } else if ch == open {
+                if depth == 0 {
+                    return Some((line_index, col));
+                }
+                depth -= 1;
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_opening_bracket_should_skip_nested_pairs_across_lines() {
+        let text = "{\n  \"tags\": [1, [2]],\n  \"id\": (3)\n}";
+
+        assert_eq!(find_opening_bracket(text, 3, 0), Some((0, 0)));
+        assert_eq!(find_opening_bracket(text, 1, 17), Some((1, 10)));
+        assert_eq!(find_opening_bracket(text, 1, 16), Some((1, 14)));
+        assert_eq!(find_opening_bracket(text, 2, 10), Some((2, 8)));
+    }
+
+    #[test]
+    fn find_opening_bracket_should_give_up_without_a_partner() {
+        assert_eq!(find_opening_bracket("a)", 0, 1), None);
+        assert_eq!(find_opening_bracket("(]", 0, 1), None);
+        assert_eq!(find_opening_bracket("(a", 0, 1), None);
+    }
+}
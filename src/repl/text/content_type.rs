@@ -0,0 +1,219 @@
+//! # Content Type Formatting
+//!
+//! Picks how a response body is laid out in the response pane from its
+//! `Content-Type` header. XML is indented one element per line; JSON arrives
+//! already formatted by the client, and HTML and anything else are shown as is.
+
+/// Indentation used for each level of nested XML elements
+const XML_INDENT: &str = "  ";
+
+/// Body formats the response pane knows how to lay out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyFormat {
+    Json,
+    Xml,
+    Html,
+    Plain,
+}
+
+impl BodyFormat {
+    /// Detect the format from a `Content-Type` header value
+    ///
+    /// Parameters such as `charset` are ignored, and structured syntax suffixes
+    /// (`application/problem+json`, `application/atom+xml`) count as their base format.
+    pub fn from_content_type(content_type: Option<&str>) -> Self {
+        let Some(content_type) = content_type else {
+            return Self::Plain;
+        };
+        let mime = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+
+        if mime == "text/html" || mime == "application/xhtml+xml" {
+            Self::Html
+        } else if mime == "application/json" || mime.ends_with("+json") {
+            Self::Json
+        } else if mime == "application/xml" || mime == "text/xml" || mime.ends_with("+xml") {
+            Self::Xml
+        } else {
+            Self::Plain
+        }
+    }
+}
+
+/// Lay out a response body according to its `Content-Type`
+///
+/// Bodies that cannot be formatted, such as malformed XML, are returned unchanged.
+pub fn format_body(body: &str, content_type: Option<&str>) -> String {
+    match BodyFormat::from_content_type(content_type) {
+        BodyFormat::Xml => indent_xml(body).unwrap_or_else(|| body.to_string()),
+        BodyFormat::Json | BodyFormat::Html | BodyFormat::Plain => body.to_string(),
+    }
+}
+
+/// Piece of an XML document between or inside angle brackets
+#[derive(Debug, PartialEq, Eq)]
+enum XmlToken<'a> {
+    Open(&'a str),
+    Close(&'a str),
+    /// Self-closing elements, declarations, comments and CDATA sections
+    Empty(&'a str),
+    Text(&'a str),
+}
+
+/// Put each XML element on its own line, indented by nesting depth
+///
+/// Elements that only wrap text stay on one line (`<name>Rex</name>`). Existing
+/// whitespace between elements is discarded, so indenting twice gives the same
+/// result.
+///
+/// # Returns
+/// `None` when the document is not well balanced.
+pub fn indent_xml(text: &str) -> Option<String> {
+    let tokens = tokenize_xml(text)?;
+    let mut lines = Vec::new();
+    let mut depth = 0usize;
+    let mut index = 0;
+
+    while index < tokens.len() {
+        let indent = XML_INDENT.repeat(depth);
+        match (&tokens[index], tokens.get(index + 1), tokens.get(index + 2)) {
+            (XmlToken::Open(open), Some(XmlToken::Text(text)), Some(XmlToken::Close(close))) => {
+                lines.push(format!("{indent}{open}{text}{close}"));
+                index += 3;
+                continue;
+            }
+            (XmlToken::Open(open), Some(XmlToken::Close(close)), _) => {
+                lines.push(format!("{indent}{open}{close}"));
+                index += 2;
+                continue;
+            }
+            (XmlToken::Open(open), _, _) => {
+                lines.push(format!("{indent}{open}"));
+                depth += 1;
+            }
+            (XmlToken::Close(close), _, _) => {
+                depth = depth.checked_sub(1)?;
+                lines.push(format!("{}{close}", XML_INDENT.repeat(depth)));
+            }
+            (XmlToken::Empty(token) | XmlToken::Text(token), _, _) => {
+                lines.push(format!("{indent}{token}"));
+            }
+        }
+        index += 1;
+    }
+
+    (depth == 0).then(|| lines.join("\n"))
+}
+
+/// Split XML into tags and trimmed text, dropping whitespace-only text
+fn tokenize_xml(text: &str) -> Option<Vec<XmlToken<'_>>> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        if !rest.starts_with('<') {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let content = rest[..end].trim();
+            if !content.is_empty() {
+                tokens.push(XmlToken::Text(content));
+            }
+            rest = &rest[end..];
+            continue;
+        }
+
+        let terminator = if rest.starts_with("<!--") {
+            "-->"
+        } else if rest.starts_with("<![CDATA[") {
+            "]]>"
+        } else {
+            ">"
+        };
+        let end = rest.find(terminator)? + terminator.len();
+        let tag = &rest[..end];
+        tokens.push(if tag.starts_with("</") {
+            XmlToken::Close(tag)
+        } else if tag.starts_with("<?") || tag.starts_with("<!") || tag.ends_with("/>") {
+            XmlToken::Empty(tag)
+        } else {
+            XmlToken::Open(tag)
+        });
+        rest = &rest[end..];
+    }
+
+    Some(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn body_format_should_follow_content_type() {
+        assert_eq!(
+            BodyFormat::from_content_type(Some("application/xml; charset=utf-8")),
+            BodyFormat::Xml
+        );
+        assert_eq!(
+            BodyFormat::from_content_type(Some("application/atom+xml")),
+            BodyFormat::Xml
+        );
+        assert_eq!(
+            BodyFormat::from_content_type(Some("application/problem+json")),
+            BodyFormat::Json
+        );
+        assert_eq!(
+            BodyFormat::from_content_type(Some("Text/HTML")),
+            BodyFormat::Html
+        );
+        assert_eq!(BodyFormat::from_content_type(None), BodyFormat::Plain);
+    }
+
+    #[test]
+    fn format_body_should_indent_xml_response() {
+        let body = r#"<?xml version="1.0"?><pets><pet id="1"><name>Rex</name><tags/></pet><!-- end --></pets>"#;
+
+        assert_eq!(
+            format_body(body, Some("text/xml")),
+            [
+                r#"<?xml version="1.0"?>"#,
+                "<pets>",
+                r#"  <pet id="1">"#,
+                "    <name>Rex</name>",
+                "    <tags/>",
+                "  </pet>",
+                "  <!-- end -->",
+                "</pets>",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn format_body_should_pass_through_unknown_types() {
+        let body = "<p><b>hi</b></p>";
+
+        assert_eq!(format_body(body, Some("application/octet-stream")), body);
+        assert_eq!(format_body(body, Some("text/html")), body);
+        assert_eq!(format_body(body, None), body);
+    }
+
+    #[test]
+    fn indent_xml_should_be_idempotent() {
+        let indented = indent_xml("<a><b>1</b><c></c></a>").unwrap();
+
+        assert_eq!(indented, "<a>\n  <b>1</b>\n  <c></c>\n</a>");
+        assert_eq!(indent_xml(&indented).unwrap(), indented);
+    }
+
+    #[test]
+    fn indent_xml_should_reject_unbalanced_documents() {
+        assert_eq!(indent_xml("<a><b></a></a></a>"), None);
+        assert_eq!(indent_xml("<a><b>"), None);
+        assert_eq!(indent_xml("<a"), None);
+        assert_eq!(format_body("<a", Some("application/xml")), "<a");
+    }
+}
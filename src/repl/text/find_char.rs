@@ -0,0 +1,69 @@
+//! # Find Character
+//!
+//! Locates the target of the `f{char}` and `F{char}` motions within a single
+//! line, for moving the cursor (`2fx`, `Fx`) and for deleting through it (`d2fx`).
+
+/// Find the `count`-th occurrence of `target` after column `from` in `line`
+///
+/// Columns are character indices. Like Vim, a count larger than the number of
+/// occurrences finds nothing rather than stopping at the last one.
+pub fn find_char_forward(line: &str, from: usize, target: char, count: usize) -> Option<usize> {
+    line.chars()
+        .enumerate()
+        .skip(from + 1)
+        .filter(|&(_, ch)| ch == target)
+        .nth(count.max(1) - 1)
+        .map(|(column, _)| column)
+}
+
+/// Find the `count`-th occurrence of `target` before column `from` in `line`
+///
+/// The mirror of [`find_char_forward`] for `F`, counting back from the cursor.
+pub fn find_char_backward(line: &str, from: usize, target: char, count: usize) -> Option<usize> {
+    let before: Vec<char> = line.chars().take(from).collect();
+    before
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|&(_, &ch)| ch == target)
+        .nth(count.max(1) - 1)
+        .map(|(column, _)| column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_char_forward_should_skip_to_counted_occurrence() {
+        let line = "a=1&a=2&a=3&a=4";
+
+        assert_eq!(find_char_forward(line, 0, 'a', 1), Some(4));
+        assert_eq!(find_char_forward(line, 0, 'a', 3), Some(12));
+        assert_eq!(find_char_forward(line, 4, 'a', 1), Some(8));
+    }
+
+    #[test]
+    fn find_char_forward_should_ignore_character_under_cursor() {
+        assert_eq!(find_char_forward("xax", 0, 'x', 1), Some(2));
+        assert_eq!(find_char_forward("x", 0, 'x', 1), None);
+    }
+
+    #[test]
+    fn find_char_backward_should_count_back_from_cursor() {
+        let line = "a=1&a=2&a=3";
+
+        assert_eq!(find_char_backward(line, 8, 'a', 1), Some(4));
+        assert_eq!(find_char_backward(line, 8, 'a', 2), Some(0));
+        assert_eq!(find_char_backward(line, 8, 'a', 3), None);
+        assert_eq!(find_char_backward("日本語の本", 4, '本', 1), Some(1));
+        assert_eq!(find_char_backward("x", 0, 'x', 1), None);
+    }
+
+    #[test]
+    fn find_char_forward_should_not_move_when_count_exceeds_occurrences() {
+        assert_eq!(find_char_forward("banana", 0, 'a', 4), None);
+        assert_eq!(find_char_forward("日本語の本", 0, '本', 3), None);
+        assert_eq!(find_char_forward("日本語の本", 0, '本', 2), Some(4));
+    }
+}
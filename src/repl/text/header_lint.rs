@@ -0,0 +1,298 @@
+//! # Header Name Lint
+//!
+//! Spell-checks header names in the request buffer against a built-in list of
+//! common HTTP headers. The header lines are those the request parser sends as
+//! headers (see [`crate::repl::text::request_headers`]).
+//! Names starting with `X-` are treated as custom headers and never flagged.
+//! Header names given more than once are reported as well, since a request
+//! with two `Content-Type` lines rarely means what it says.
+
+use crate::repl::text::request_headers::{parse_header_block, HeaderLine};
+
+/// Common request and response header names used as the spelling dictionary
+const KNOWN_HEADERS: &[&str] = &[
+    "Accept",
+    "Accept-Charset",
+    "Accept-Encoding",
+    "Accept-Language",
+    "Accept-Ranges",
+    "Access-Control-Allow-Credentials",
+    "Access-Control-Allow-Headers",
+    "Access-Control-Allow-Methods",
+    "Access-Control-Allow-Origin",
+    "Access-Control-Request-Headers",
+    "Access-Control-Request-Method",
+    "Age",
+    "Allow",
+    "Authorization",
+    "Cache-Control",
+    "Connection",
+    "Content-Disposition",
+    "Content-Encoding",
+    "Content-Language",
+    "Content-Length",
+    "Content-Location",
+    "Content-Range",
+    "Content-Type",
+    "Cookie",
+    "Date",
+    "DNT",
+    "ETag",
+    "Expect",
+    "Expires",
+    "Forwarded",
+    "From",
+    "Host",
+    "If-Match",
+    "If-Modified-Since",
+    "If-None-Match",
+    "If-Range",
+    "If-Unmodified-Since",
+    "Keep-Alive",
+    "Last-Modified",
+    "Link",
+    "Location",
+    "Max-Forwards",
+    "Origin",
+    "Pragma",
+    "Proxy-Authorization",
+    "Range",
+    "Referer",
+    "Retry-After",
+    "Server",
+    "Set-Cookie",
+    "TE",
+    "Trailer",
+    "Transfer-Encoding",
+    "Upgrade",
+    "User-Agent",
+    "Vary",
+    "Via",
+    "WWW-Authenticate",
+];
+
+/// A header name that is not in the known header list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderWarning {
+    /// Logical line of the header in the request buffer (0-based)
+    pub line: usize,
+    /// The header name as typed
+    pub name: String,
+    /// Closest known header name, if one is near enough
+    pub suggestion: Option<&'static str>,
+}
+
+impl HeaderWarning {
+    /// Format the warning for display in the status line
+    pub fn message(&self) -> String {
+        match self.suggestion {
+            Some(suggestion) => format!(
+                "Unknown header '{}' on line {}, did you mean '{suggestion}'?",
+                self.name,
+                self.line + 1
+            ),
+            None => format!("Unknown header '{}' on line {}", self.name, self.line + 1),
+        }
+    }
+}
+
+/// A header name given again after an earlier header line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateHeader {
+    /// Logical line of the repeated header in the request buffer (0-based)
+    pub line: usize,
+    /// The header name as typed on the repeated line
+    pub name: String,
+    /// Logical line where the header was first given (0-based)
+    pub first_line: usize,
+}
+
+impl DuplicateHeader {
+    /// Format the warning for display in the status line
+    pub fn message(&self) -> String {
+        format!(
+            "Duplicate header '{}' on line {} (first on line {})",
+            self.name,
+            self.line + 1,
+            self.first_line + 1
+        )
+    }
+}
+
+/// Check the header lines of a request for unknown header names
+///
+/// # Returns
+/// One warning per unknown header name, in buffer order.
+pub fn lint_headers(request_text: &str) -> Vec<HeaderWarning> {
+    header_lines(request_text)
+        .into_iter()
+        .filter(|header| !is_known_header(header.name))
+        .map(|header| HeaderWarning {
+            line: header.line,
+            name: header.name.to_string(),
+            suggestion: closest_header(header.name),
+        })
+        .collect()
+}
+
+/// Lowercased header names already seen, with the line each first appeared on
+type SeenHeaders = Vec<(String, usize)>;
+
+/// Check the header lines of a request for names given more than once
+///
+/// Names are compared case-insensitively, as HTTP does.
+///
+/// # Returns
+/// One entry per repeated header line, in buffer order.
+pub fn find_duplicate_headers(request_text: &str) -> Vec<DuplicateHeader> {
+    let mut seen: SeenHeaders = Vec::new();
+    let mut duplicates = Vec::new();
+    for HeaderLine { line, name, .. } in header_lines(request_text) {
+        let lower = name.to_ascii_lowercase();
+        match seen.iter().find(|(seen_name, _)| *seen_name == lower) {
+            Some((_, first_line)) => duplicates.push(DuplicateHeader {
+                line,
+                name: name.to_string(),
+                first_line: *first_line,
+            }),
+            None => seen.push((lower, line)),
+        }
+    }
+    duplicates
+}
+
+/// Header lines of the request, as the request parser reads them
+fn header_lines(request_text: &str) -> Vec<HeaderLine<'_>> {
+    let lines: Vec<&str> = request_text.lines().collect();
+    parse_header_block(&lines).headers
+}
+
+/// Check whether a header name is known or custom (`X-` prefixed)
+fn is_known_header(name: &str) -> bool {
+    name.to_ascii_lowercase().starts_with("x-")
+        || KNOWN_HEADERS
+            .iter()
+            .any(|known| known.eq_ignore_ascii_case(name))
+}
+
+/// Find the known header with the smallest edit distance to `name`
+///
+/// The allowed distance scales with the name length (one edit per three characters)
+/// so short unknown names are not matched to unrelated short headers.
+fn closest_header(name: &str) -> Option<&'static str> {
+    let lower = name.to_ascii_lowercase();
+    let max_distance = (lower.chars().count() / 3).max(1);
+    KNOWN_HEADERS
+        .iter()
+        .map(|known| (edit_distance(&lower, &known.to_ascii_lowercase()), *known))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+/// Levenshtein distance between two strings, counted in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_ch) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_ch) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_ch != *b_ch);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lint_headers_should_suggest_content_type_for_typo() {
+        let warnings = lint_headers("POST /users\nContent-Typ: application/json\n\n{}");
+
+        assert_eq!(
+            warnings,
+            vec![HeaderWarning {
+                line: 1,
+                name: "Content-Typ".to_string(),
+                suggestion: Some("Content-Type"),
+            }]
+        );
+        assert_eq!(
+            warnings[0].message(),
+            "Unknown header 'Content-Typ' on line 2, did you mean 'Content-Type'?"
+        );
+    }
+
+    #[test]
+    fn lint_headers_should_accept_valid_headers_in_any_case() {
+        let text = "GET /users\ncontent-type: application/json\nAccept: */*\nX-Request-Id: 42";
+        assert!(lint_headers(text).is_empty());
+    }
+
+    #[test]
+    fn lint_headers_should_stop_at_body() {
+        let text = "POST /users\nAccept: */*\n\nContent-Typ: not a header";
+        assert!(lint_headers(text).is_empty());
+    }
+
+    #[test]
+    fn lint_headers_should_omit_suggestion_when_nothing_is_close() {
+        let warnings = lint_headers("GET /\nFoo: bar");
+        assert_eq!(warnings[0].suggestion, None);
+        assert_eq!(warnings[0].message(), "Unknown header 'Foo' on line 2");
+    }
+
+    #[test]
+    fn find_duplicate_headers_should_flag_repeated_content_type_in_any_case() {
+        let text =
+            "POST /users\nContent-Type: application/json\nAccept: */*\ncontent-type: text/plain";
+        let duplicates = find_duplicate_headers(text);
+
+        assert_eq!(
+            duplicates,
+            vec![DuplicateHeader {
+                line: 3,
+                name: "content-type".to_string(),
+                first_line: 1,
+            }]
+        );
+        assert_eq!(
+            duplicates[0].message(),
+            "Duplicate header 'content-type' on line 4 (first on line 2)"
+        );
+    }
+
+    #[test]
+    fn find_duplicate_headers_should_ignore_distinct_headers_and_body() {
+        let text = "POST /users\nContent-Type: application/json\nAccept: */*\n\nAccept: */*";
+        assert!(find_duplicate_headers(text).is_empty());
+    }
+
+    #[test]
+    fn find_duplicate_headers_should_start_below_continued_request_line() {
+        let text = "GET /search?\\\n    q=users\nAccept: */*\naccept: text/plain";
+        let duplicates = find_duplicate_headers(text);
+
+        assert_eq!(
+            duplicates,
+            vec![DuplicateHeader {
+                line: 3,
+                name: "accept".to_string(),
+                first_line: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn edit_distance_should_count_insertions_and_substitutions() {
+        assert_eq!(edit_distance("content-typ", "content-type"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
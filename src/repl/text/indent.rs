@@ -0,0 +1,206 @@
+//! # Line Indentation
+//!
+//! Shifts whole lines left or right by shiftwidths for the visual `>` and `<`
+//! commands, and lays out JSON for the `=` operator when no `equalprg` is set.
+//! A shiftwidth is one tab stop, matching how tabs are rendered.
+
+use std::ops::RangeInclusive;
+
+/// Indent the given lines by `levels` copies of `unit`
+///
+/// Empty lines are left untouched so shifting does not leave trailing whitespace.
+pub fn indent_lines(text: &str, lines: RangeInclusive<usize>, levels: usize, unit: &str) -> String {
+    let indent = unit.repeat(levels);
+    map_lines(text, lines, |line| {
+        if line.is_empty() {
+            line.to_string()
+        } else {
+            format!("{indent}{line}")
+        }
+    })
+}
+
+/// Remove up to `levels` shiftwidths of leading whitespace from the given lines
+///
+/// A tab counts as a full `tab_width`; lines with less indentation lose all of it.
+pub fn dedent_lines(
+    text: &str,
+    lines: RangeInclusive<usize>,
+    levels: usize,
+    tab_width: usize,
+) -> String {
+    let target = levels * tab_width;
+    map_lines(text, lines, |line| {
+        let mut removed = 0;
+        let start = line
+            .char_indices()
+            .find(|(_, ch)| {
+                if removed >= target {
+                    return true;
+                }
+                match ch {
+                    ' ' => removed += 1,
+                    '\t' => removed += tab_width,
+                    _ => return true,
+                }
+                false
+            })
+            .map_or(line.len(), |(index, _)| index);
+        line[start..].to_string()
+    })
+}
+
+/// Lay out a JSON object or array with one member per line, `unit` per level
+///
+/// Only whitespace outside strings changes, so keys keep their order and
+/// values are copied as written. Empty containers stay on one line, and the
+/// indentation of the first line is kept as the base for every line.
+///
+/// # Returns
+/// The laid out text, or `None` when the text is not a single object or
+/// array with matching brackets.
+pub fn reindent_json(text: &str, unit: &str) -> Option<String> {
+    let trimmed = text.trim();
+    if !trimmed.starts_with(['{', '[']) {
+        return None;
+    }
+    let base: String = text
+        .chars()
+        .take_while(|ch| *ch == ' ' || *ch == '\t')
+        .collect();
+    let new_line = |out: &mut String, depth: usize| {
+        out.push('\n');
+        out.push_str(&base);
+        out.push_str(&unit.repeat(depth));
+    };
+
+    let mut out = base.clone();
+    let mut closers = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = trimmed.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if in_string {
+            out.push(ch);
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        // Nothing may follow the closing bracket of the top-level value
+        if closers.is_empty() && out.len() > base.len() && !ch.is_whitespace() {
+            return None;
+        }
+
+        match ch {
+            '"' => {
+                in_string = true;
+                out.push(ch);
+            }
+            '{' | '[' => {
+                let closer = if ch == '{' { '}' } else { ']' };
+                out.push(ch);
+                while chars.next_if(|next| next.is_whitespace()).is_some() {}
+                if chars.next_if_eq(&closer).is_some() {
+                    out.push(closer);
+                } else {
+                    closers.push(closer);
+                    new_line(&mut out, closers.len());
+                }
+            }
+            '}' | ']' => {
+                if closers.pop() != Some(ch) {
+                    return None;
+                }
+                new_line(&mut out, closers.len());
+                out.push(ch);
+            }
+            ',' => {
+                out.push(ch);
+                new_line(&mut out, closers.len());
+            }
+            ':' => out.push_str(": "),
+            _ if ch.is_whitespace() => {}
+            _ => out.push(ch),
+        }
+    }
+
+    (closers.is_empty() && !in_string).then_some(out)
+}
+
+/// Apply `f` to the lines in `lines`, keeping every other line as is
+fn map_lines(text: &str, lines: RangeInclusive<usize>, f: impl Fn(&str) -> String) -> String {
+    text.split('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            if lines.contains(&index) {
+                f(line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indent_lines_should_repeat_unit_per_level() {
+        let text = "{\n\"a\": 1,\n\"b\": 2\n}";
+
+        assert_eq!(
+            indent_lines(text, 1..=2, 2, "  "),
+            "{\n    \"a\": 1,\n    \"b\": 2\n}"
+        );
+    }
+
+    #[test]
+    fn indent_lines_should_skip_empty_lines() {
+        assert_eq!(indent_lines("a\n\nb", 0..=2, 1, "\t"), "\ta\n\n\tb");
+    }
+
+    #[test]
+    fn dedent_lines_should_remove_levels_of_indentation() {
+        let text = "        a\n      b\n\tc";
+
+        assert_eq!(dedent_lines(text, 0..=2, 1, 4), "    a\n  b\nc");
+        assert_eq!(dedent_lines(text, 0..=1, 2, 4), "a\nb\n\tc");
+    }
+
+    #[test]
+    fn reindent_json_should_put_each_member_on_its_own_line() {
+        assert_eq!(
+            reindent_json(r#"{"name": "a, b", "tags":["x",  "y"],"meta":{}}"#, "  ").unwrap(),
+            "{\n  \"name\": \"a, b\",\n  \"tags\": [\n    \"x\",\n    \"y\"\n  ],\n  \"meta\": {}\n}"
+        );
+    }
+
+    #[test]
+    fn reindent_json_should_keep_base_indentation_and_escaped_quotes() {
+        assert_eq!(
+            reindent_json("\t[{\"q\": \"say \\\"hi\\\"\"}]", "\t").unwrap(),
+            "\t[\n\t\t{\n\t\t\t\"q\": \"say \\\"hi\\\"\"\n\t\t}\n\t]"
+        );
+    }
+
+    #[test]
+    fn reindent_json_should_reject_text_that_is_not_one_json_value() {
+        assert_eq!(reindent_json("GET /users", "  "), None);
+        assert_eq!(reindent_json("{\"a\": [1}", "  "), None);
+        assert_eq!(reindent_json("{\"a\": 1", "  "), None);
+        assert_eq!(reindent_json("{} {}", "  "), None);
+    }
+
+    #[test]
+    fn dedent_lines_should_clear_shallow_indentation() {
+        assert_eq!(dedent_lines("  a\nb", 0..=1, 1, 4), "a\nb");
+        assert_eq!(dedent_lines("   ", 0..=0, 1, 4), "");
+    }
+}
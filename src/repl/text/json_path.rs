@@ -0,0 +1,335 @@
+//! # JSON Path Lookup
+//!
+//! Maps a cursor position inside a JSON document to the path of the key under it
+//! (e.g. `.users[2].email`). Response bodies are displayed as raw text, so there is
+//! no parsed tree to consult; instead this module scans the text once and tracks the
+//! container nesting, which also keeps it tolerant of partially valid or truncated bodies.
+//! Going the other way, [`json_value_at`] reads the value at such a path out of a
+//! complete document, for capturing response values (`:chain`), and
+//! [`json_scalar_at`] picks out the single string, number or literal under the
+//! cursor (`:yankvalue`).
+
+/// One level of container nesting seen while scanning
+#[derive(Debug)]
+enum Frame {
+    /// Inside an object; `key` is the key whose value is currently being scanned
+    Object {
+        key: Option<String>,
+        expecting_key: bool,
+    },
+    /// Inside an array; `index` is the element currently being scanned
+    Array { index: usize },
+}
+
+/// Find the JSON path of the object key located at the given logical position
+///
+/// # Arguments
+/// * `text` - The JSON document as displayed in the pane
+/// * `line` - Logical line of the cursor (0-based)
+/// * `column` - Logical column of the cursor in characters (0-based)
+///
+/// # Returns
+/// The path to the key (including its surrounding quotes) under the cursor,
+/// or `None` when the cursor is not on an object key.
+pub fn json_path_at(text: &str, line: usize, column: usize) -> Option<String> {
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut chars = text.chars();
+    let mut current_line = 0;
+    let mut current_col: usize = 0;
+
+    while let Some(ch) = chars.next() {
+        // Nothing after the cursor line can affect the path, so stop early on large bodies
+        if current_line > line {
+            return None;
+        }
+
+        match ch {
+            '"' => {
+                let start_line = current_line;
+                let start_col = current_col;
+                let mut content = String::new();
+                let mut escaped = false;
+                current_col += 1;
+
+                // Consume the string up to the closing quote
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        current_line += 1;
+                        current_col = 0;
+                        continue;
+                    }
+                    current_col += 1;
+                    if escaped {
+                        content.push(next);
+                        escaped = false;
+                    } else if next == '\\' {
+                        escaped = true;
+                    } else if next == '"' {
+                        break;
+                    } else {
+                        content.push(next);
+                    }
+                }
+                let end_col = current_col.saturating_sub(1);
+
+                if let Some(Frame::Object { key, expecting_key }) = stack.last_mut() {
+                    if *expecting_key {
+                        let on_key = start_line == line && (start_col..=end_col).contains(&column);
+                        *key = Some(content);
+                        *expecting_key = false;
+                        if on_key {
+                            return Some(format_path(&stack));
+                        }
+                    }
+                }
+                continue;
+            }
+            '{' => stack.push(Frame::Object {
+                key: None,
+                expecting_key: true,
+            }),
+            '[' => stack.push(Frame::Array { index: 0 }),
+            '}' | ']' => {
+                stack.pop();
+            }
+            ',' => match stack.last_mut() {
+                Some(Frame::Object { key, expecting_key }) => {
+                    *key = None;
+                    *expecting_key = true;
+                }
+                Some(Frame::Array { index }) => *index += 1,
+                None => {}
+            },
+            _ => {}
+        }
+
+        if ch == '\n' {
+            current_line += 1;
+            current_col = 0;
+        } else {
+            current_col += 1;
+        }
+    }
+
+    None
+}
+
+/// Render the container stack as a jq-style path
+fn format_path(stack: &[Frame]) -> String {
+    let mut path = String::new();
+    for frame in stack {
+        match frame {
+            Frame::Object { key: Some(key), .. } => {
+                if is_identifier(key) {
+                    path.push('.');
+                    path.push_str(key);
+                } else {
+                    path.push_str(&format!("[{key:?}]"));
+                }
+            }
+            Frame::Object { key: None, .. } => {}
+            Frame::Array { index } => path.push_str(&format!("[{index}]")),
+        }
+    }
+    path
+}
+
+/// Check whether a key can be written with dot notation
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+/// Find the scalar JSON token at the given logical position
+///
+/// Strings (keys as well as values) are returned unquoted with their escapes
+/// decoded; numbers, `true`, `false` and `null` are returned as written.
+/// Returns `None` when the cursor is on whitespace or punctuation.
+pub fn json_scalar_at(text: &str, line: usize, column: usize) -> Option<String> {
+    let chars: Vec<char> = text.split('\n').nth(line)?.chars().collect();
+    if column >= chars.len() {
+        return None;
+    }
+
+    // Find where the string around the cursor starts by scanning from the
+    // line start, since a quote alone cannot tell an opening from a closing one
+    let mut index = 0;
+    while index < chars.len() {
+        if chars[index] == '"' {
+            let start = index;
+            index += 1;
+            while index < chars.len() && chars[index] != '"' {
+                index += if chars[index] == '\\' { 2 } else { 1 };
+            }
+            // An unterminated string runs to the end of the line
+            let close = index.min(chars.len());
+            if (start..=close).contains(&column) {
+                let content: String = chars[start + 1..close].iter().collect();
+                return Some(serde_json::from_str(&format!("\"{content}\"")).unwrap_or(content));
+            }
+        } else if is_scalar_char(chars[index]) {
+            let start = index;
+            while index + 1 < chars.len() && is_scalar_char(chars[index + 1]) {
+                index += 1;
+            }
+            if (start..=index).contains(&column) {
+                return Some(chars[start..=index].iter().collect());
+            }
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Check whether a character can be part of a number or a literal
+fn is_scalar_char(ch: char) -> bool {
+    !ch.is_whitespace() && !matches!(ch, '{' | '}' | '[' | ']' | ',' | ':' | '"')
+}
+
+/// Look up the value at a jq-style path in a JSON document
+///
+/// Accepts the paths [`json_path_at`] produces: `.key`, `[2]` and `["odd-key"]`
+/// segments, with `.` alone meaning the whole document. String values are
+/// returned without their quotes; anything else is returned as compact JSON.
+/// Returns `None` when the text is not JSON or the path does not exist.
+pub fn json_value_at(text: &str, path: &str) -> Option<String> {
+    let document: serde_json::Value = serde_json::from_str(text).ok()?;
+    let mut value = &document;
+    let mut rest = path.strip_prefix('.').unwrap_or(path);
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("[\"") {
+            let end = after.find("\"]")?;
+            value = value.get(&after[..end])?;
+            rest = &after[end + 2..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']')?;
+            value = value.get(after[..end].parse::<usize>().ok()?)?;
+            rest = &after[end + 1..];
+        } else {
+            let key = rest.strip_prefix('.').unwrap_or(rest);
+            let end = key.find(['.', '[']).unwrap_or(key.len());
+            value = value.get(&key[..end])?;
+            rest = &key[end..];
+        }
+    }
+
+    match value {
+        serde_json::Value::String(text) => Some(text.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USERS_JSON: &str = r#"{
+  "users": [
+    {"name": "a", "email": "a@example.com"},
+    {"name": "b", "email": "b@example.com"},
+    {
+      "name": "c",
+      "email": "c@example.com"
+    }
+  ]
+}"#;
+
+    #[test]
+    fn json_path_at_should_return_top_level_key() {
+        let path = json_path_at(USERS_JSON, 1, 4);
+        assert_eq!(path.as_deref(), Some(".users"));
+    }
+
+    #[test]
+    fn json_path_at_should_return_nested_array_element_key() {
+        // Line 6 is `      "email": "c@example.com"`, column 8 is inside "email"
+        let path = json_path_at(USERS_JSON, 6, 8);
+        assert_eq!(path.as_deref(), Some(".users[2].email"));
+    }
+
+    #[test]
+    fn json_path_at_should_count_inline_array_elements() {
+        // Line 3 is `    {"name": "b", "email": "b@example.com"},`
+        let path = json_path_at(USERS_JSON, 3, 21);
+        assert_eq!(path.as_deref(), Some(".users[1].email"));
+    }
+
+    #[test]
+    fn json_path_at_should_return_none_on_value() {
+        // Column 15 on line 5 is inside the value "c"
+        let path = json_path_at(USERS_JSON, 5, 15);
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn json_path_at_should_return_none_for_non_json_text() {
+        assert_eq!(json_path_at("plain text response", 0, 3), None);
+    }
+
+    #[test]
+    fn json_path_at_should_quote_keys_that_are_not_identifiers() {
+        let json = r#"{"content-type": "text/plain"}"#;
+        let path = json_path_at(json, 0, 3);
+        assert_eq!(path.as_deref(), Some(r#"["content-type"]"#));
+    }
+
+    #[test]
+    fn json_path_at_should_ignore_escaped_quotes_in_strings() {
+        let json = r#"{"a": "x\"y", "b": 1}"#;
+        let path = json_path_at(json, 0, 15);
+        assert_eq!(path.as_deref(), Some(".b"));
+    }
+
+    #[test]
+    fn json_value_at_should_follow_paths_from_json_path_at() {
+        assert_eq!(
+            json_value_at(USERS_JSON, ".users[2].email").as_deref(),
+            Some("c@example.com")
+        );
+        assert_eq!(
+            json_value_at(r#"{"content-type": "text/plain"}"#, r#"["content-type"]"#).as_deref(),
+            Some("text/plain")
+        );
+        assert_eq!(
+            json_value_at(r#"{"token": "abc", "ttl": 60}"#, ".ttl").as_deref(),
+            Some("60")
+        );
+        assert_eq!(json_value_at(r#"[1, 2]"#, ".").as_deref(), Some("[1,2]"));
+    }
+
+    #[test]
+    fn json_value_at_should_return_none_for_missing_paths() {
+        assert_eq!(json_value_at(USERS_JSON, ".users[5].email"), None);
+        assert_eq!(json_value_at(USERS_JSON, ".token"), None);
+        assert_eq!(json_value_at("plain text", ".token"), None);
+    }
+
+    #[test]
+    fn json_scalar_at_should_return_unquoted_string_value() {
+        // Line 3 is `    {"name": "b", "email": "b@example.com"},`
+        assert_eq!(
+            json_scalar_at(USERS_JSON, 3, 30).as_deref(),
+            Some("b@example.com")
+        );
+        assert_eq!(json_scalar_at(USERS_JSON, 3, 8).as_deref(), Some("name"));
+        assert_eq!(
+            json_scalar_at(r#"{"a": "x\"y"}"#, 0, 8).as_deref(),
+            Some("x\"y")
+        );
+    }
+
+    #[test]
+    fn json_scalar_at_should_return_numbers_and_literals() {
+        let json = r#"{"id": -12.5e3, "ok": true}"#;
+        assert_eq!(json_scalar_at(json, 0, 9).as_deref(), Some("-12.5e3"));
+        assert_eq!(json_scalar_at(json, 0, 23).as_deref(), Some("true"));
+        assert_eq!(json_scalar_at(json, 0, 5), None);
+        assert_eq!(json_scalar_at(json, 0, 40), None);
+    }
+}
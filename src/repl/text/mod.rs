@@ -1 +1,14 @@
+pub mod bracket;
+pub mod content_type;
+pub mod find_char;
+pub mod header_lint;
+pub mod indent;
+pub mod json_path;
+pub mod request_headers;
+pub mod request_line;
+pub mod response_limit;
+pub mod response_sections;
+pub mod search;
+pub mod text_object;
+pub mod variables;
 pub mod word_segmenter;
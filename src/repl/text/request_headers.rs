@@ -0,0 +1,118 @@
+//! # Request Headers
+//!
+//! Splits what follows the request line into the header block and the body.
+//! Header lines are the `Name: value` lines directly below the request line;
+//! the block ends at the first line that is not one, and a blank line there
+//! only separates it from the body. The request parser and the header lint
+//! both read headers through here, so the lint checks what is sent.
+
+use crate::repl::text::request_line::join_request_line;
+
+/// A `Name: value` line of the header block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderLine<'a> {
+    /// Logical line in the request (0-based)
+    pub line: usize,
+    pub name: &'a str,
+    /// The value without surrounding whitespace
+    pub value: &'a str,
+}
+
+/// Header block of a request and where its body starts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderBlock<'a> {
+    /// Header lines in buffer order
+    pub headers: Vec<HeaderLine<'a>>,
+    /// Logical line the body starts on, past the end when there is no body
+    pub body_start: usize,
+}
+
+/// Read the header block below the request line at the top of `lines`
+///
+/// A request line continued with backslashes takes up several lines, all of
+/// which are skipped.
+pub fn parse_header_block<'a>(lines: &[&'a str]) -> HeaderBlock<'a> {
+    let (_, request_line_count) = join_request_line(lines);
+    let headers: Vec<HeaderLine<'a>> = lines
+        .iter()
+        .enumerate()
+        .skip(request_line_count)
+        .map_while(|(line, text)| header_line(line, *text))
+        .collect();
+
+    let end = request_line_count + headers.len();
+    let body_start = match lines.get(end) {
+        Some(line) if line.trim().is_empty() => end + 1,
+        _ => end,
+    };
+    HeaderBlock {
+        headers,
+        body_start,
+    }
+}
+
+/// Read a `Name: value` line, whose name must be a header token
+fn header_line(line: usize, text: &str) -> Option<HeaderLine<'_>> {
+    let (name, value) = text.split_once(':')?;
+    let is_token = !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_');
+    if !is_token {
+        return None;
+    }
+    Some(HeaderLine {
+        line,
+        name,
+        value: value.trim(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> HeaderBlock<'_> {
+        let lines: Vec<&str> = text.lines().collect();
+        parse_header_block(&lines)
+    }
+
+    #[test]
+    fn parse_header_block_should_split_headers_from_body() {
+        let block = parse("POST /users\nContent-Type: application/json\nAccept:*/*\n\n{\"a\": 1}");
+
+        assert_eq!(
+            block.headers,
+            vec![
+                HeaderLine {
+                    line: 1,
+                    name: "Content-Type",
+                    value: "application/json",
+                },
+                HeaderLine {
+                    line: 2,
+                    name: "Accept",
+                    value: "*/*",
+                },
+            ]
+        );
+        assert_eq!(block.body_start, 4);
+    }
+
+    #[test]
+    fn parse_header_block_should_end_at_first_non_header_line() {
+        let block = parse("POST /users\n{\"Accept\": 1}\nAccept: */*");
+
+        assert!(block.headers.is_empty());
+        assert_eq!(block.body_start, 1);
+    }
+
+    #[test]
+    fn parse_header_block_should_skip_continued_request_line() {
+        let block = parse("GET /search?\\\n    q=users\nAccept: */*");
+
+        assert_eq!(block.headers.len(), 1);
+        assert_eq!(block.headers[0].line, 2);
+        assert_eq!(block.body_start, 3);
+    }
+}
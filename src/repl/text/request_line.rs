@@ -0,0 +1,122 @@
+//! # Request Line
+//!
+//! Reads the `METHOD URL` line at the top of a request, which may continue
+//! over several lines, and resolves its URL against the profile's server.
+
+/// Method and URL read from a request line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestTarget {
+    pub method: String,
+    pub url: String,
+}
+
+/// Join a request line continued over several lines with trailing backslashes
+///
+/// Continuation lines may be indented; the indentation is dropped, so a long
+/// URL can be wrapped anywhere. A trailing `\\` stands for one literal
+/// backslash and ends the request line.
+///
+/// # Returns
+/// The joined request line and the number of lines it took up.
+pub fn join_request_line(lines: &[&str]) -> (String, usize) {
+    let mut joined = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        let line = if index == 0 { line } else { line.trim_start() }.trim_end();
+        if let Some(escaped) = line.strip_suffix("\\\\") {
+            joined.push_str(escaped);
+            joined.push('\\');
+            return (joined, index + 1);
+        }
+        match line.strip_suffix('\\') {
+            Some(continued) => joined.push_str(continued),
+            None => {
+                joined.push_str(line);
+                return (joined, index + 1);
+            }
+        }
+    }
+    (joined, lines.len())
+}
+
+/// Resolve a request URL against the profile base URL
+///
+/// Absolute URLs are returned unchanged; relative paths (with any query string)
+/// are appended to the base with exactly one `/` between them.
+pub fn resolve_url(base_url: Option<&str>, url: &str) -> String {
+    if url.contains("://") {
+        return url.to_string();
+    }
+
+    match base_url {
+        Some(base) => format!(
+            "{}/{}",
+            base.trim_end_matches('/'),
+            url.trim_start_matches('/')
+        ),
+        None => url.to_string(),
+    }
+}
+
+/// Read the method and URL from the request line at the top of `text`
+///
+/// Follows the request parser: the method is the first word, uppercased, and
+/// the URL the second.
+///
+/// # Returns
+/// `None` if the first line is blank or the request line has no URL.
+pub fn request_target(text: &str) -> Option<RequestTarget> {
+    let lines: Vec<&str> = text.lines().collect();
+    if lines.first()?.trim().is_empty() {
+        return None;
+    }
+    let (request_line, _) = join_request_line(&lines);
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next()?.to_uppercase();
+    let url = parts.next()?.to_string();
+    Some(RequestTarget { method, url })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_target_should_read_method_and_continued_url() {
+        assert_eq!(
+            request_target("get /search?\\\n    q=users\n\n{}"),
+            Some(RequestTarget {
+                method: "GET".to_string(),
+                url: "/search?q=users".to_string(),
+            })
+        );
+        for text in ["", "\nGET /users", "GET", "GET \\\n"] {
+            assert_eq!(request_target(text), None, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn resolve_url_should_join_relative_path_to_profile_base() {
+        let base = Some("https://staging.example.com/api/");
+
+        assert_eq!(
+            resolve_url(base, "/users?page=2"),
+            "https://staging.example.com/api/users?page=2"
+        );
+        assert_eq!(
+            resolve_url(base, "users"),
+            "https://staging.example.com/api/users"
+        );
+    }
+
+    #[test]
+    fn resolve_url_should_keep_absolute_url() {
+        assert_eq!(
+            resolve_url(
+                Some("https://staging.example.com"),
+                "http://localhost:8080/health"
+            ),
+            "http://localhost:8080/health"
+        );
+        assert_eq!(resolve_url(None, "/users"), "/users");
+    }
+}
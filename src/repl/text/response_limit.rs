@@ -0,0 +1,56 @@
+//! # Response Size Limit
+//!
+//! Cuts oversized response bodies down before they reach the response pane
+//! (`:set maxresponse=<bytes>`), so a huge body cannot swamp the display cache.
+//! The full body is kept elsewhere for `:save`.
+
+use std::borrow::Cow;
+
+/// Truncate `body` to at most `max_bytes`, noting the full size at the end
+///
+/// A limit of 0 means no limit. The cut falls on a character boundary, so a
+/// multi-byte character is never split. A body within the limit is borrowed,
+/// not copied.
+pub fn truncate_body(body: &str, max_bytes: usize) -> Cow<'_, str> {
+    if max_bytes == 0 || body.len() <= max_bytes {
+        return Cow::Borrowed(body);
+    }
+
+    let end = (0..=max_bytes)
+        .rev()
+        .find(|&index| body.is_char_boundary(index))
+        .unwrap_or(0);
+    Cow::Owned(format!(
+        "{}\n… (truncated, {} bytes total)",
+        &body[..end],
+        body.len()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_body_should_cut_over_limit_body_with_marker() {
+        assert_eq!(
+            truncate_body("0123456789", 4),
+            "0123\n… (truncated, 10 bytes total)"
+        );
+    }
+
+    #[test]
+    fn truncate_body_should_keep_body_within_limit() {
+        assert_eq!(truncate_body("0123", 4), "0123");
+        assert_eq!(truncate_body("0123456789", 0), "0123456789");
+    }
+
+    #[test]
+    fn truncate_body_should_not_split_multibyte_characters() {
+        // Each of these characters takes three bytes
+        assert_eq!(
+            truncate_body("日本語", 5),
+            "日\n… (truncated, 9 bytes total)"
+        );
+    }
+}
@@ -0,0 +1,72 @@
+//! # Response Sections
+//!
+//! Detects the request echo that verbose output puts above the response
+//! (`Request: ...`, `Headers:` and the blank line before `Response: ...`) so it
+//! can be folded away in the response pane.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+const REQUEST_PREFIX: &str = "Request: ";
+const RESPONSE_PREFIX: &str = "Response: ";
+
+/// Line range of the echoed request section, if the text starts with one
+pub fn request_echo_lines(text: &str) -> Option<Range<usize>> {
+    let mut lines = text.lines();
+    if !lines.next()?.starts_with(REQUEST_PREFIX) {
+        return None;
+    }
+
+    let end = lines.position(|line| line.starts_with(RESPONSE_PREFIX))? + 1;
+    Some(0..end)
+}
+
+/// Replace the echoed request section with a single fold line
+///
+/// Text without a request echo is returned unchanged, without copying it.
+pub fn fold_request_echo(text: &str) -> Cow<'_, str> {
+    let Some(range) = request_echo_lines(text) else {
+        return Cow::Borrowed(text);
+    };
+
+    let lines: Vec<&str> = text.split('\n').collect();
+    let fold_line = format!("+-- {} lines: {}", range.len(), lines[range.start]);
+
+    Cow::Owned(
+        std::iter::once(fold_line.as_str())
+            .chain(lines[range.end..].iter().copied())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VERBOSE: &str = "Request: GET /users\nHeaders:\n  Accept: */*\n\nResponse: 200 OK\n\n[]";
+
+    #[test]
+    fn request_echo_lines_should_cover_lines_before_response_status() {
+        assert_eq!(request_echo_lines(VERBOSE), Some(0..4));
+    }
+
+    #[test]
+    fn request_echo_lines_should_ignore_plain_body() {
+        assert_eq!(request_echo_lines("{\"Request: \": 1}"), None);
+        assert_eq!(request_echo_lines("Request: GET /users\n[]"), None);
+    }
+
+    #[test]
+    fn fold_request_echo_should_collapse_section_into_one_line() {
+        assert_eq!(
+            fold_request_echo(VERBOSE),
+            "+-- 4 lines: Request: GET /users\nResponse: 200 OK\n\n[]"
+        );
+    }
+
+    #[test]
+    fn fold_request_echo_should_keep_text_without_echo() {
+        assert_eq!(fold_request_echo("[]"), "[]");
+    }
+}
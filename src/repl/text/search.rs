@@ -0,0 +1,275 @@
+//! # Text Search
+//!
+//! Literal pattern search over logical text for the `/` and `?` commands. Positions are
+//! `(line, column)` pairs with columns counted in characters, so matches line up
+//! with logical cursor positions even in multi-byte text.
+//!
+//! `n`/`N` jump between matches of the last pattern through [`SearchMatches`],
+//! which finds every match once so each jump is a binary search.
+
+/// `(line, column)` of a match start, with the column counted in characters
+pub type MatchPosition = (usize, usize);
+
+/// Find the first match of `pattern` after `(line, column)`, wrapping around
+///
+/// Matches on the starting position itself are only found after wrapping, so
+/// repeated searches from a match advance to the next one.
+///
+/// # Returns
+/// The `(line, column)` of the match start, or `None` if the pattern does not occur.
+pub fn find_forward(
+    text: &str,
+    pattern: &str,
+    line: usize,
+    column: usize,
+) -> Option<MatchPosition> {
+    if pattern.is_empty() {
+        return None;
+    }
+
+    let lines: Vec<&str> = text.split('\n').collect();
+    let start = (line, column);
+
+    (line..lines.len())
+        .flat_map(|index| matches_in_line(lines[index], pattern, index))
+        .find(|position| *position > start)
+        .or_else(|| {
+            (0..=line.min(lines.len().saturating_sub(1)))
+                .flat_map(|index| matches_in_line(lines[index], pattern, index))
+                .next()
+        })
+}
+
+/// Find the last match of `pattern` before `(line, column)`, wrapping around
+///
+/// The mirror of [`find_forward`] for `?`: a match on the starting position
+/// itself is only found after wrapping.
+///
+/// # Returns
+/// The `(line, column)` of the match start, or `None` if the pattern does not occur.
+pub fn find_backward(
+    text: &str,
+    pattern: &str,
+    line: usize,
+    column: usize,
+) -> Option<MatchPosition> {
+    if pattern.is_empty() {
+        return None;
+    }
+
+    let lines: Vec<&str> = text.split('\n').collect();
+    let start = (line, column);
+    let line_matches_reversed = |index: usize| {
+        let mut positions: Vec<_> = matches_in_line(lines[index], pattern, index).collect();
+        positions.reverse();
+        positions
+    };
+
+    (0..=line.min(lines.len() - 1))
+        .rev()
+        .flat_map(line_matches_reversed)
+        .find(|position| *position < start)
+        .or_else(|| {
+            (line.min(lines.len() - 1)..lines.len())
+                .rev()
+                .flat_map(line_matches_reversed)
+                .next()
+        })
+}
+
+/// Every match of a pattern in a text, in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatches {
+    pattern: String,
+    positions: Vec<MatchPosition>,
+}
+
+impl SearchMatches {
+    /// Find all matches of `pattern` in `text`, including overlapping ones
+    pub fn new(text: &str, pattern: &str) -> Self {
+        let positions = if pattern.is_empty() {
+            Vec::new()
+        } else {
+            text.split('\n')
+                .enumerate()
+                .flat_map(|(index, text_line)| matches_in_line(text_line, pattern, index))
+                .collect()
+        };
+
+        Self {
+            pattern: pattern.to_string(),
+            positions,
+        }
+    }
+
+    /// Get the pattern these matches are for
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Get the `(line, column)` of every match start, in order
+    pub fn positions(&self) -> &[MatchPosition] {
+        &self.positions
+    }
+
+    /// First match after `(line, column)`, wrapping around to the first match
+    pub fn next_after(&self, line: usize, column: usize) -> Option<MatchPosition> {
+        let index = self
+            .positions
+            .partition_point(|position| *position <= (line, column));
+        self.positions
+            .get(index)
+            .or_else(|| self.positions.first())
+            .copied()
+    }
+
+    /// Last match before `(line, column)`, wrapping around to the last match
+    pub fn previous_before(&self, line: usize, column: usize) -> Option<MatchPosition> {
+        let index = self
+            .positions
+            .partition_point(|position| *position < (line, column));
+        index
+            .checked_sub(1)
+            .and_then(|index| self.positions.get(index))
+            .or_else(|| self.positions.last())
+            .copied()
+    }
+}
+
+/// Convert a `(line, column)` position into a character offset into `text`
+///
+/// Each line break counts as one character, so the offset can be used to split
+/// the text across lines.
+pub fn char_offset(text: &str, line: usize, column: usize) -> usize {
+    text.split('\n')
+        .take(line)
+        .map(|text_line| text_line.chars().count() + 1)
+        .sum::<usize>()
+        + column
+}
+
+/// All match start positions in one line, including overlapping matches
+fn matches_in_line<'a>(
+    text: &'a str,
+    pattern: &'a str,
+    line: usize,
+) -> impl Iterator<Item = MatchPosition> + 'a {
+    text.char_indices()
+        .enumerate()
+        .filter(move |(_, (byte_index, _))| text[*byte_index..].starts_with(pattern))
+        .map(move |(column, _)| (line, column))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_forward_should_skip_match_at_start_position() {
+        let text = "GET /users\nAccept: users";
+
+        assert_eq!(find_forward(text, "users", 0, 0), Some((0, 5)));
+        assert_eq!(find_forward(text, "users", 0, 5), Some((1, 8)));
+    }
+
+    #[test]
+    fn find_forward_should_wrap_around_to_buffer_start() {
+        let text = "GET /users\nAccept: */*";
+
+        assert_eq!(find_forward(text, "GET", 1, 3), Some((0, 0)));
+        assert_eq!(find_forward(text, "GET", 0, 0), Some((0, 0)));
+    }
+
+    #[test]
+    fn find_forward_should_count_columns_in_characters() {
+        let text = "{\"名前\": \"太郎\"}";
+
+        assert_eq!(find_forward(text, "太郎", 0, 0), Some((0, 8)));
+    }
+
+    #[test]
+    fn find_forward_should_find_overlapping_matches() {
+        assert_eq!(find_forward("aaa", "aa", 0, 0), Some((0, 1)));
+    }
+
+    #[test]
+    fn find_backward_should_skip_match_at_start_and_wrap_to_buffer_end() {
+        let text = "GET /users\n{\"名前\": \"users\"}";
+
+        assert_eq!(find_backward(text, "users", 1, 8), Some((0, 5)));
+        assert_eq!(find_backward(text, "users", 1, 9), Some((1, 8)));
+        assert_eq!(find_backward(text, "users", 0, 5), Some((1, 8)));
+        assert_eq!(find_backward(text, "posts", 1, 0), None);
+    }
+
+    #[test]
+    fn char_offset_should_count_line_breaks() {
+        let text = "GET /名前\nAccept: */*";
+
+        assert_eq!(char_offset(text, 0, 5), 5);
+        assert_eq!(char_offset(text, 1, 0), 8);
+        assert_eq!(char_offset(text, 1, 6), 14);
+    }
+
+    #[test]
+    fn find_forward_should_return_none_without_match() {
+        assert_eq!(find_forward("GET /users", "posts", 0, 0), None);
+        assert_eq!(find_forward("GET /users", "", 0, 0), None);
+    }
+
+    /// Every match start found by checking each character position in turn
+    fn naive_matches(text: &str, pattern: &str) -> Vec<(usize, usize)> {
+        let mut positions = Vec::new();
+        for (line, text_line) in text.split('\n').enumerate() {
+            let chars: Vec<char> = text_line.chars().collect();
+            let pattern: Vec<char> = pattern.chars().collect();
+            for column in 0..chars.len() {
+                if chars[column..].starts_with(&pattern) {
+                    positions.push((line, column));
+                }
+            }
+        }
+        positions
+    }
+
+    #[test]
+    fn search_matches_should_agree_with_naive_scan() {
+        let text = "{\"名前\": \"太郎\",\n \"aaa\": [\"太郎\", \"aa\"]}\n\naaaa";
+
+        for pattern in ["太郎", "aa", "\"", "名前\": ", "missing"] {
+            assert_eq!(
+                SearchMatches::new(text, pattern).positions(),
+                naive_matches(text, pattern),
+                "pattern {pattern:?}"
+            );
+        }
+        assert!(SearchMatches::new(text, "").positions().is_empty());
+    }
+
+    #[test]
+    fn search_matches_should_step_both_ways_with_wraparound() {
+        let matches = SearchMatches::new("users\nGET /users\nusers", "users");
+
+        assert_eq!(matches.next_after(0, 0), Some((1, 5)));
+        assert_eq!(matches.next_after(1, 5), Some((2, 0)));
+        assert_eq!(matches.next_after(2, 0), Some((0, 0)));
+
+        assert_eq!(matches.previous_before(1, 5), Some((0, 0)));
+        assert_eq!(matches.previous_before(1, 3), Some((0, 0)));
+        assert_eq!(matches.previous_before(0, 0), Some((2, 0)));
+
+        assert_eq!(SearchMatches::new("GET /", "users").next_after(0, 0), None);
+    }
+
+    #[test]
+    fn search_matches_next_after_should_agree_with_find_forward() {
+        let text = "GET /users\nAccept: users\n\nusers users";
+
+        for (line, column) in [(0, 0), (0, 5), (1, 3), (2, 0), (3, 6), (3, 9)] {
+            assert_eq!(
+                SearchMatches::new(text, "users").next_after(line, column),
+                find_forward(text, "users", line, column)
+            );
+        }
+    }
+}
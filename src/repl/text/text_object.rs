@@ -0,0 +1,155 @@
+//! # Text Objects
+//!
+//! Finds the text the `aw` ("a word") object covers on a line, for operators
+//! like `daw` and `2daw`. The object stays within the cursor line.
+
+/// Kind of character a word is made of, so `foo.bar` is three words like in Vim
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Blank,
+    Word,
+    Punctuation,
+}
+
+fn char_class(ch: char) -> CharClass {
+    if ch.is_whitespace() {
+        CharClass::Blank
+    } else if ch.is_alphanumeric() || ch == '_' {
+        CharClass::Word
+    } else {
+        CharClass::Punctuation
+    }
+}
+
+/// A run of characters of one class, as inclusive columns
+#[derive(Debug, Clone, Copy)]
+struct Run {
+    start: usize,
+    end: usize,
+    blank: bool,
+}
+
+fn runs(line: &str) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+    let mut last_class = None;
+    for (column, ch) in line.chars().enumerate() {
+        let class = char_class(ch);
+        match runs.last_mut() {
+            Some(run) if last_class == Some(class) => run.end = column,
+            _ => runs.push(Run {
+                start: column,
+                end: column,
+                blank: class == CharClass::Blank,
+            }),
+        }
+        last_class = Some(class);
+    }
+    runs
+}
+
+/// First and last column of a text object, both inclusive
+pub type ColumnSpan = (usize, usize);
+
+/// Find the columns `count` "a word" objects at `column` cover in `line`
+///
+/// Columns are character indices and the range is inclusive. From a word,
+/// each object is a word and the white space after it; when the last word
+/// has none, the white space before the first word is taken instead, unless
+/// it is the line's indent. From white space, each object is the white space
+/// and the word after it. A count larger than the words left on the line
+/// stops at the end of the line.
+///
+/// # Returns
+/// `None` on an empty line.
+pub fn around_word(line: &str, column: usize, count: usize) -> Option<ColumnSpan> {
+    let runs = runs(line);
+    let last = runs.last()?.end;
+    let column = column.min(last);
+    let mut index = runs
+        .iter()
+        .position(|run| run.start <= column && column <= run.end)?;
+    let first = index;
+    let start = runs[first].start;
+    let mut end = runs[index].end;
+
+    if runs[first].blank {
+        for _ in 0..count.max(1) {
+            let mut next = index + 1;
+            if runs.get(next).is_some_and(|run| run.blank) {
+                next += 1;
+            }
+            let Some(word) = runs.get(next) else {
+                break;
+            };
+            index = next;
+            end = word.end;
+        }
+        return Some((start, end));
+    }
+
+    let mut trailing_blank = false;
+    for taken in 0..count.max(1) {
+        if taken > 0 {
+            let Some(word) = runs.get(index + 1) else {
+                break;
+            };
+            index += 1;
+            end = word.end;
+        }
+        trailing_blank = runs.get(index + 1).is_some_and(|run| run.blank);
+        if trailing_blank {
+            index += 1;
+            end = runs[index].end;
+        }
+    }
+
+    let start = match first.checked_sub(1).map(|before| runs[before]) {
+        Some(before) if !trailing_blank && before.blank && before.start > 0 => before.start,
+        _ => start,
+    };
+    Some((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delete_around_word(line: &str, column: usize, count: usize) -> String {
+        let (start, end) = around_word(line, column, count).unwrap();
+        line.chars()
+            .enumerate()
+            .filter(|&(index, _)| index < start || index > end)
+            .map(|(_, ch)| ch)
+            .collect()
+    }
+
+    #[test]
+    fn around_word_should_take_counted_words_with_trailing_blanks() {
+        let line = "one two three four";
+
+        assert_eq!(around_word(line, 1, 1), Some((0, 3)));
+        assert_eq!(delete_around_word(line, 1, 2), "three four");
+        assert_eq!(delete_around_word(line, 5, 1), "one three four");
+        // Punctuation is a word of its own
+        assert_eq!(delete_around_word("a.b c", 0, 2), "b c");
+        // More words than the line has stops at its end
+        assert_eq!(delete_around_word(line, 8, 5), "one two");
+    }
+
+    #[test]
+    fn around_word_should_take_leading_blank_after_last_word_but_not_indent() {
+        assert_eq!(delete_around_word("one two", 5, 1), "one");
+        assert_eq!(delete_around_word("one two three", 4, 2), "one");
+        assert_eq!(delete_around_word("  only", 3, 1), "  ");
+    }
+
+    #[test]
+    fn around_word_from_blank_should_take_blank_and_following_word() {
+        let line = "one  two three";
+
+        assert_eq!(around_word(line, 4, 1), Some((3, 7)));
+        assert_eq!(delete_around_word(line, 3, 2), "one");
+        assert_eq!(delete_around_word("one   ", 4, 1), "one");
+        assert_eq!(around_word("", 0, 1), None);
+    }
+}
@@ -0,0 +1,58 @@
+//! # Request Variables
+//!
+//! Expands `${NAME}` references in request text with values captured by
+//! `:chain`, so a token from a login request can be reused in later requests.
+
+use std::collections::HashMap;
+
+/// Replace every `${NAME}` in `text` with the value bound to `NAME`
+///
+/// References to names that are not bound are left untouched, so a typo
+/// shows up in the sent request instead of silently becoming empty.
+pub fn substitute_variables(text: &str, variables: &HashMap<String, String>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find('}') {
+            Some(end) => {
+                match variables.get(&after[..end]) {
+                    Some(value) => result.push_str(value),
+                    None => result.push_str(&rest[start..start + end + 3]),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitute_variables_should_replace_bound_names_only() {
+        let variables = HashMap::from([("AUTH".to_string(), "abc123".to_string())]);
+
+        assert_eq!(
+            substitute_variables("Authorization: Bearer ${AUTH}", &variables),
+            "Authorization: Bearer abc123"
+        );
+        assert_eq!(
+            substitute_variables("GET /users/${ID}?t=${AUTH}", &variables),
+            "GET /users/${ID}?t=abc123"
+        );
+        assert_eq!(
+            substitute_variables("broken ${AUTH", &variables),
+            "broken ${AUTH"
+        );
+    }
+}
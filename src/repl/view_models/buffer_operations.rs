@@ -13,10 +13,19 @@
 //! - ViewEvents are emitted for selective rendering optimization
 //! - Character-by-character processing maintains semantic consistency
 
-use crate::repl::events::{EditorMode, LogicalPosition, ViewEvent};
+use crate::repl::commands::{CaseChange, CommandEvent, RecordedChange};
+use crate::repl::events::{EditorMode, LogicalPosition, LogicalRange, Pane, ViewEvent};
+use crate::repl::models::YankHighlight;
+use crate::repl::text::bracket::find_opening_bracket;
+use crate::repl::text::find_char::find_char_forward;
+use crate::repl::text::indent::{dedent_lines, indent_lines};
+use crate::repl::text::text_object::around_word;
 use crate::repl::view_models::core::ViewModel;
-use crate::repl::view_models::{YankEntry, YankType};
+use crate::repl::view_models::{
+    YankEntry, YankType, BLACK_HOLE_REGISTER, FILE_NAME_REGISTER, LAST_COMMAND_REGISTER,
+};
 use anyhow::Result;
+use std::ops::RangeInclusive;
 
 impl ViewModel {
     /// Get selected text from current pane
@@ -36,7 +45,12 @@ impl ViewModel {
     }
 
     /// Yank text to yank buffer with type information
+    ///
+    /// Text is discarded when the black-hole register was chosen with `"_`.
     pub fn yank_to_buffer_with_type(&mut self, text: String, yank_type: YankType) -> Result<()> {
+        if self.pending_register == Some(BLACK_HOLE_REGISTER) {
+            return Ok(());
+        }
         self.yank_buffer.yank_with_type(text, yank_type)
     }
 
@@ -45,11 +59,75 @@ impl ViewModel {
         self.yank_buffer.yank(text)
     }
 
+    /// Flash just-yanked text in a pane, when `:set hlyank on`
+    ///
+    /// `from` and `to` are the first and last yanked positions, in either
+    /// order; for block yanks they are opposite corners.
+    pub fn highlight_yank(
+        &mut self,
+        pane: Pane,
+        from: LogicalPosition,
+        to: LogicalPosition,
+        yank_type: YankType,
+    ) -> Result<()> {
+        if !self.hlyank_enabled {
+            return Ok(());
+        }
+
+        let highlight = YankHighlight::new(pane, from, to, yank_type);
+        self.yank_highlight = Some(highlight);
+        let redraw = if pane == self.get_current_pane() {
+            ViewEvent::CurrentAreaRedrawRequired
+        } else {
+            ViewEvent::SecondaryAreaRedrawRequired
+        };
+        self.emit_view_event([ViewEvent::YankHighlighted { highlight }, redraw])
+    }
+
+    /// Flash the visual selection about to be yanked, before it is cleared
+    pub fn highlight_selection_yank(&mut self, yank_type: YankType) -> Result<()> {
+        match self.get_visual_selection() {
+            (Some(start), Some(end), Some(pane)) => {
+                self.highlight_yank(pane, start, end, yank_type)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Get the text currently flashed after a yank, if any
+    pub fn get_yank_highlight(&self) -> Option<YankHighlight> {
+        self.yank_highlight
+    }
+
+    /// Take down the yank flash
+    pub fn clear_yank_highlight(&mut self) -> Result<()> {
+        if self.yank_highlight.take().is_some() {
+            self.emit_view_event([ViewEvent::AllContentAreasRedrawRequired])?;
+        }
+        Ok(())
+    }
+
     /// Get yank entry with type information from yank buffer
     pub fn get_yanked_entry(&mut self) -> Option<YankEntry> {
         self.yank_buffer.paste_entry()
     }
 
+    /// Get the contents of a read-only register (`%` file name, `:` last ex command)
+    ///
+    /// Returns `None` for any other register, or when there is no file or
+    /// command history yet.
+    pub fn read_only_register(&self, register: char) -> Option<YankEntry> {
+        let text = match register {
+            FILE_NAME_REGISTER => self.get_request_file()?.to_string(),
+            LAST_COMMAND_REGISTER => self.get_ex_command_history().last()?.clone(),
+            _ => return None,
+        };
+        Some(YankEntry {
+            text,
+            yank_type: YankType::Character,
+        })
+    }
+
     /// Get text from yank buffer (for backward compatibility)
     pub fn get_yanked_text(&mut self) -> Option<String> {
         self.yank_buffer.paste().map(|s| s.to_string())
@@ -102,7 +180,7 @@ impl ViewModel {
         let line_length = self.pane_manager.get_current_line_length();
         if current_pos.column < line_length {
             // We're not at the end, move one position right for insertion
-            let _ = self.move_cursor_right();
+            let _ = self.move_cursor_right(false);
         }
         // If we're at or beyond the line length, cursor is already at the right position for append
 
@@ -119,21 +197,99 @@ impl ViewModel {
     }
 
     /// Advanced paste operation that respects yank type (character, line, or block)
+    ///
+    /// The `[ and `] marks are set to the start and end of the pasted text.
     pub fn paste_with_type(&mut self, yank_entry: &YankEntry) -> Result<()> {
-        match yank_entry.yank_type {
-            YankType::Character => self.paste_text(&yank_entry.text),
-            YankType::Line => self.paste_line_wise(&yank_entry.text),
-            YankType::Block => self.paste_block_wise(&yank_entry.text),
-        }
+        let cursor = self.get_cursor_position();
+        let start = match yank_entry.yank_type {
+            YankType::Character => {
+                self.paste_text(&yank_entry.text)?;
+                cursor
+            }
+            YankType::Line => {
+                self.paste_line_wise(&yank_entry.text)?;
+                let start = LogicalPosition::new(cursor.line, 0);
+                self.move_to_first_pasted_line(start, &yank_entry.text)?;
+                start
+            }
+            YankType::Block { width } => {
+                self.paste_block_wise(&yank_entry.text, width)?;
+                cursor
+            }
+        };
+        self.mark_pasted_text(start, yank_entry);
+        Ok(())
     }
 
     /// Advanced paste after operation that respects yank type (character, line, or block)
+    ///
+    /// The `[ and `] marks are set to the start and end of the pasted text.
     pub fn paste_after_with_type(&mut self, yank_entry: &YankEntry) -> Result<()> {
-        match yank_entry.yank_type {
-            YankType::Character => self.paste_text_after(&yank_entry.text),
-            YankType::Line => self.paste_line_wise_after(&yank_entry.text),
-            YankType::Block => self.paste_block_wise_after(&yank_entry.text),
+        let cursor = self.get_cursor_position();
+        let start = match yank_entry.yank_type {
+            YankType::Character => {
+                // Mirrors paste_text_after, which appends at the end of the line
+                let line_length = self.pane_manager.get_current_line_length();
+                self.paste_text_after(&yank_entry.text)?;
+                if cursor.column < line_length {
+                    LogicalPosition::new(cursor.line, cursor.column + 1)
+                } else {
+                    cursor
+                }
+            }
+            YankType::Line => {
+                self.paste_line_wise_after(&yank_entry.text)?;
+                let start = LogicalPosition::new(cursor.line + 1, 0);
+                self.move_to_first_pasted_line(start, &yank_entry.text)?;
+                start
+            }
+            YankType::Block { width } => {
+                self.paste_block_wise_after(&yank_entry.text, width)?;
+                LogicalPosition::new(cursor.line, cursor.column + 1)
+            }
+        };
+        self.mark_pasted_text(start, yank_entry);
+        Ok(())
+    }
+
+    /// Put the cursor on the first non-blank of the first line pasted line-wise at `start`, like Vim
+    fn move_to_first_pasted_line(&mut self, start: LogicalPosition, text: &str) -> Result<()> {
+        if !self.is_in_request_pane() {
+            return Ok(());
         }
+        let column = text
+            .chars()
+            .take_while(|ch| *ch != '\n' && ch.is_whitespace())
+            .count();
+        self.set_cursor_position(LogicalPosition::new(start.line, column))
+    }
+
+    /// Set the `[ and `] marks around text pasted at `start`
+    fn mark_pasted_text(&mut self, start: LogicalPosition, yank_entry: &YankEntry) {
+        if !self.is_in_request_pane() || yank_entry.text.is_empty() {
+            return;
+        }
+        let end = match yank_entry.yank_type {
+            YankType::Character => end_of_inserted_text(start, &yank_entry.text),
+            YankType::Line => {
+                let text = &yank_entry.text;
+                end_of_inserted_text(start, text.strip_suffix('\n').unwrap_or(text))
+            }
+            YankType::Block { .. } => {
+                // The block width is in display columns; the mark needs characters
+                let widest_row = yank_entry
+                    .text
+                    .lines()
+                    .map(|row| row.chars().count())
+                    .max()
+                    .unwrap_or(0);
+                LogicalPosition::new(
+                    start.line + yank_entry.text.lines().count().max(1) - 1,
+                    start.column + widest_row.saturating_sub(1),
+                )
+            }
+        };
+        self.change_marks = Some(LogicalRange::new(start, end));
     }
 
     /// Paste text as lines (for line-wise yanks)
@@ -210,7 +366,10 @@ impl ViewModel {
     }
 
     /// Paste text in block-wise manner (rectangular paste maintaining column alignment)
-    pub fn paste_block_wise(&mut self, text: &str) -> Result<()> {
+    ///
+    /// Each block row goes on a successive line starting at the cursor column.
+    /// Rows shorter than `width` are padded so text after the block stays aligned.
+    pub fn paste_block_wise(&mut self, text: &str, width: usize) -> Result<()> {
         // Only allow pasting in Request pane
         if !self.is_in_request_pane() {
             return Ok(());
@@ -232,14 +391,16 @@ impl ViewModel {
         tracing::debug!("Calling insert_block_wise with {} lines", lines.len());
 
         // Use the new block-wise insertion method that handles positioning correctly
-        let events = self.pane_manager.insert_block_wise(current_pos, &lines);
+        let events = self
+            .pane_manager
+            .insert_block_wise(current_pos, &lines, width);
         self.emit_view_event(events)?;
 
         Ok(())
     }
 
     /// Paste text in block-wise manner after cursor position
-    pub fn paste_block_wise_after(&mut self, text: &str) -> Result<()> {
+    pub fn paste_block_wise_after(&mut self, text: &str, width: usize) -> Result<()> {
         // For block-wise paste after, move cursor one column right and paste
         let current_pos = self.get_cursor_position();
         let after_pos = LogicalPosition {
@@ -248,7 +409,7 @@ impl ViewModel {
         };
 
         self.set_cursor_position(after_pos)?;
-        self.paste_block_wise(text)
+        self.paste_block_wise(text, width)
     }
 
     /// Insert a character at current cursor position
@@ -263,11 +424,15 @@ impl ViewModel {
             return Ok(());
         }
 
+        if self.mode() == EditorMode::Insert {
+            self.insert_session_text.push(ch);
+        }
+
         // Use semantic insertion from PaneManager (handles visibility and all events)
         let events = self.pane_manager.insert_char(ch);
         self.emit_view_event(events)?;
 
-        Ok(())
+        self.show_match_after_insert()
     }
 
     /// Insert text at current cursor position
@@ -282,14 +447,109 @@ impl ViewModel {
             return Ok(());
         }
 
-        // Insert each character individually to maintain proper semantic handling
-        for ch in text.chars() {
-            self.insert_char(ch)?;
+        if self.mode() == EditorMode::Insert {
+            self.insert_session_text.push_str(text);
+        }
+
+        // Insert the text in one go so the display cache is rebuilt only once
+        let events = self.pane_manager.insert_text(text);
+        self.emit_view_event(events)?;
+
+        self.show_match_after_insert()
+    }
+
+    /// Flash the bracket matching a closing bracket just typed (`:set showmatch on`)
+    ///
+    /// Typing anything else takes down an earlier flash.
+    fn show_match_after_insert(&mut self) -> Result<()> {
+        self.clear_match_highlight()?;
+        if !self.showmatch_enabled || self.mode() != EditorMode::Insert {
+            return Ok(());
+        }
+
+        let cursor = self.get_cursor_position();
+        let Some(column) = cursor.column.checked_sub(1) else {
+            return Ok(());
+        };
+        let text = self.get_request_text();
+        if let Some((line, column)) = find_opening_bracket(&text, cursor.line, column) {
+            let position = LogicalPosition::new(line, column);
+            self.match_highlight = Some(position);
+            self.emit_view_event([
+                ViewEvent::MatchHighlighted { position },
+                ViewEvent::CurrentAreaRedrawRequired,
+            ])?;
         }
+        Ok(())
+    }
 
+    /// Get the request buffer bracket currently flashed by showmatch, if any
+    pub fn get_match_highlight(&self) -> Option<LogicalPosition> {
+        self.match_highlight
+    }
+
+    /// Take down the showmatch flash
+    pub fn clear_match_highlight(&mut self) -> Result<()> {
+        if self.match_highlight.take().is_some() {
+            self.emit_view_event([ViewEvent::AllContentAreasRedrawRequired])?;
+        }
         Ok(())
     }
 
+    /// Insert the text typed in the previous insert session (Ctrl-a in insert mode)
+    pub fn insert_last_inserted_text(&mut self) -> Result<()> {
+        let text = self.last_inserted_text.clone();
+        self.insert_text(&text)
+    }
+
+    /// Remember a change made from normal mode as the one `.` makes again
+    ///
+    /// A change that starts an insert session is only complete once the
+    /// session ends and adds the text typed in it.
+    pub fn record_change(&mut self, events: Vec<CommandEvent>) {
+        let change = RecordedChange::new(events);
+        self.recording_insert = change.inserted_text.is_some();
+        self.last_change = Some(change);
+    }
+
+    /// Get the change `.` makes again, if one was made
+    pub fn get_last_change(&self) -> Option<&RecordedChange> {
+        self.last_change.as_ref()
+    }
+
+    /// Handle the key typed after Ctrl-v in insert mode
+    ///
+    /// The character is inserted as-is, so a tab stays a tab whatever
+    /// `expandtab` says. `u` instead starts a code point of up to four hex
+    /// digits (`Ctrl-v u00e9` inserts `é`); any other key ends it early and
+    /// is dropped.
+    pub fn insert_literal(&mut self, ch: Option<char>) -> Result<()> {
+        if let Some(mut code) = self.pending_literal_code.take() {
+            if let Some(digit) = ch.filter(char::is_ascii_hexdigit) {
+                code.push(digit);
+                if code.len() < 4 {
+                    self.pending_literal_code = Some(code);
+                    return self.emit_view_event([ViewEvent::StatusBarUpdateRequired]);
+                }
+            }
+            self.change_mode(EditorMode::Insert)?;
+            return match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                Some(ch) => self.insert_text(&ch.to_string()),
+                None => Ok(()),
+            };
+        }
+
+        if ch == Some('u') {
+            self.pending_literal_code = Some(String::new());
+            return self.emit_view_event([ViewEvent::StatusBarUpdateRequired]);
+        }
+        self.change_mode(EditorMode::Insert)?;
+        match ch {
+            Some(ch) => self.insert_text(&ch.to_string()),
+            None => Ok(()),
+        }
+    }
+
     /// Delete character before cursor
     pub fn delete_char_before_cursor(&mut self) -> Result<()> {
         let current_mode = self.mode();
@@ -355,6 +615,32 @@ impl ViewModel {
         Ok(())
     }
 
+    /// Delete from cursor back to the start of the previous word (Ctrl-w in insert mode)
+    pub fn delete_word_before_cursor(&mut self) -> Result<()> {
+        // Only allow in Request pane and Insert mode
+        if !self.is_in_request_pane() || self.mode() != EditorMode::Insert {
+            return Ok(());
+        }
+
+        let events = self.pane_manager.delete_word_before_cursor();
+        self.emit_view_event(events)?;
+
+        Ok(())
+    }
+
+    /// Delete from cursor back to the first non-blank character (Ctrl-u in insert mode)
+    pub fn delete_to_line_start(&mut self) -> Result<()> {
+        // Only allow in Request pane and Insert mode
+        if !self.is_in_request_pane() || self.mode() != EditorMode::Insert {
+            return Ok(());
+        }
+
+        let events = self.pane_manager.delete_to_line_start();
+        self.emit_view_event(events)?;
+
+        Ok(())
+    }
+
     /// Cut (delete and yank) character at cursor position
     pub fn cut_char_at_cursor(&mut self) -> Result<()> {
         // Only allow in Request pane and Normal mode
@@ -446,11 +732,442 @@ impl ViewModel {
 
             // Yank the line text to the buffer as line type
             self.yank_to_buffer_with_type(line_with_newline, YankType::Line)?;
+
+            let line = LogicalPosition::new(self.get_cursor_position().line, 0);
+            self.highlight_yank(Pane::Request, line, line, YankType::Line)?;
         }
 
         Ok(())
     }
 
+    /// Delete from the cursor through the `count`-th `target` character (`d2fx`)
+    ///
+    /// The deleted text is yanked character-wise. Returns whether anything was
+    /// deleted; nothing happens when the line has fewer occurrences than `count`.
+    pub fn delete_to_char(&mut self, target: char, count: usize) -> Result<bool> {
+        if !self.is_in_request_pane() {
+            return Ok(false);
+        }
+
+        let cursor = self.get_cursor_position();
+        let text = self.get_request_text();
+        let Some(line) = text.split('\n').nth(cursor.line) else {
+            return Ok(false);
+        };
+        let Some(end) = find_char_forward(line, cursor.column, target, count) else {
+            return Ok(false);
+        };
+
+        self.cut_line_columns(&text, cursor.line, cursor.column, end)?;
+        self.set_cursor_position(cursor)?;
+
+        Ok(true)
+    }
+
+    /// Delete `count` "a word" objects at the cursor (`daw`, `2daw`)
+    ///
+    /// Each word goes with the white space after it, or before it at the end
+    /// of the line, and the deleted text is yanked character-wise. Returns
+    /// whether anything was deleted; nothing happens on an empty line.
+    pub fn delete_around_word(&mut self, count: usize) -> Result<bool> {
+        if !self.is_in_request_pane() {
+            return Ok(false);
+        }
+
+        let cursor = self.get_cursor_position();
+        let text = self.get_request_text();
+        let Some(line) = text.split('\n').nth(cursor.line) else {
+            return Ok(false);
+        };
+        let Some((start, end)) = around_word(line, cursor.column, count) else {
+            return Ok(false);
+        };
+
+        let remaining = line.chars().count() - (end + 1 - start);
+        self.cut_line_columns(&text, cursor.line, start, end)?;
+        let column = start.min(remaining.saturating_sub(1));
+        self.set_cursor_position(LogicalPosition::new(cursor.line, column))?;
+
+        Ok(true)
+    }
+
+    /// Yank columns `start` through `end` of line `line_index` in `text`
+    /// character-wise and remove them from the request buffer
+    fn cut_line_columns(
+        &mut self,
+        text: &str,
+        line_index: usize,
+        start: usize,
+        end: usize,
+    ) -> Result<()> {
+        let mut deleted = String::new();
+        let updated = text
+            .split('\n')
+            .enumerate()
+            .map(|(index, line)| {
+                if index != line_index {
+                    return line.to_string();
+                }
+                deleted = line.chars().skip(start).take(end + 1 - start).collect();
+                line.chars()
+                    .take(start)
+                    .chain(line.chars().skip(end + 1))
+                    .collect()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        self.yank_to_buffer_with_type(deleted, YankType::Character)?;
+        let mut events = self.pane_manager.set_request_content(&updated);
+        events.extend(self.pane_manager.rebuild_display_caches_and_sync());
+        events.push(ViewEvent::CurrentAreaRedrawRequired);
+        self.emit_view_event(events)
+    }
+
+    /// Get lines `first` through `last` of the request buffer for a line-wise yank
+    ///
+    /// The text ends with a newline, like lines yanked with `yy`.
+    pub fn get_lines_text(&self, first: usize, last: usize) -> Option<String> {
+        let text = self.get_request_text();
+        let lines: Vec<&str> = text.split('\n').collect();
+        let last = last.min(lines.len() - 1);
+        (first <= last).then(|| format!("{}\n", lines[first..=last].join("\n")))
+    }
+
+    /// Delete lines `first` through `last` of the request buffer (`:1,3d`)
+    ///
+    /// The cursor lands on the line that took their place. Returns the number
+    /// of lines deleted.
+    pub fn delete_lines(&mut self, first: usize, last: usize) -> Result<usize> {
+        if !self.is_in_request_pane() {
+            return Ok(0);
+        }
+
+        let text = self.get_request_text();
+        let mut lines: Vec<&str> = text.split('\n').collect();
+        if first >= lines.len() {
+            return Ok(0);
+        }
+        let last = last.min(lines.len() - 1);
+        lines.drain(first..=last);
+
+        let updated = lines.join("\n");
+        let mut events = self.pane_manager.set_request_content(&updated);
+        events.extend(self.pane_manager.rebuild_display_caches_and_sync());
+        events.push(ViewEvent::CurrentAreaRedrawRequired);
+        self.emit_view_event(events)?;
+        let line = first.min(lines.len().saturating_sub(1));
+        self.set_cursor_position(LogicalPosition::new(line, 0))?;
+
+        Ok(last - first + 1)
+    }
+
+    /// Join current line with the next line (J command)
+    pub fn join_lines(&mut self) -> Result<()> {
+        // Only allow in Request pane and Normal mode
+        if !self.is_in_request_pane() || self.mode() != EditorMode::Normal {
+            return Ok(());
+        }
+
+        let events = self.pane_manager.join_lines();
+        self.emit_view_event(events)?;
+
+        Ok(())
+    }
+
+    /// Indent the lines of the visual selection by `count` shiftwidths
+    ///
+    /// Returns the number of lines shifted, or `None` without a selection.
+    pub fn indent_selection(&mut self, count: usize) -> Result<Option<usize>> {
+        let unit = self.indent_unit();
+        self.shift_selection(|text, lines| indent_lines(text, lines, count, &unit))
+    }
+
+    /// One level of indentation: a tab, or tabstop spaces with expandtab
+    pub fn indent_unit(&self) -> String {
+        if self.pane_manager.get_expand_tab() {
+            " ".repeat(self.pane_manager.get_tab_width())
+        } else {
+            "\t".to_string()
+        }
+    }
+
+    /// Get the external formatter run by `=`, empty for the internal JSON layout
+    pub fn get_equalprg(&self) -> &str {
+        &self.equalprg
+    }
+
+    /// Set the external formatter run by `=` (`:set equalprg=<command>`)
+    pub fn set_equalprg(&mut self, program: &str) {
+        self.equalprg = program.to_string();
+    }
+
+    /// Get the shell external commands run in
+    pub fn get_shell(&self) -> &str {
+        &self.shell
+    }
+
+    /// Set the shell external commands run in (`:set shell=<path>`)
+    pub fn set_shell(&mut self, shell: &str) {
+        self.shell = shell.to_string();
+    }
+
+    /// Get the encoding files are read and written in
+    pub fn get_fileencoding(&self) -> &str {
+        &self.fileencoding
+    }
+
+    /// Set the encoding files are read and written in (`:set fileencoding=<name>`)
+    pub fn set_fileencoding(&mut self, encoding: &str) {
+        self.fileencoding = encoding.to_string();
+    }
+
+    /// Replace lines `first` through `last` of the request buffer with `text`
+    ///
+    /// Used by `=` to put back the reformatted lines. The cursor lands on the
+    /// first non-blank of the first line, like Vim. Returns the number of
+    /// lines the text now takes.
+    pub fn replace_lines(&mut self, first: usize, last: usize, text: &str) -> Result<usize> {
+        if !self.is_in_request_pane() {
+            return Ok(0);
+        }
+
+        let current = self.get_request_text();
+        let mut lines: Vec<&str> = current.split('\n').collect();
+        if first >= lines.len() {
+            return Ok(0);
+        }
+        let last = last.min(lines.len() - 1);
+        let replacement: Vec<&str> = text.split('\n').collect();
+        let line_count = replacement.len();
+        lines.splice(first..=last, replacement);
+
+        let updated = lines.join("\n");
+        if updated != current {
+            let mut events = self.pane_manager.set_request_content(&updated);
+            events.extend(self.pane_manager.rebuild_display_caches_and_sync());
+            events.push(ViewEvent::CurrentAreaRedrawRequired);
+            self.emit_view_event(events)?;
+        }
+
+        let column = lines[first]
+            .chars()
+            .take_while(|ch| ch.is_whitespace())
+            .count();
+        self.set_cursor_position(LogicalPosition::new(first, column))?;
+
+        Ok(line_count)
+    }
+
+    /// Dedent the lines of the visual selection by `count` shiftwidths
+    ///
+    /// Returns the number of lines shifted, or `None` without a selection.
+    pub fn dedent_selection(&mut self, count: usize) -> Result<Option<usize>> {
+        let tab_width = self.pane_manager.get_tab_width();
+        self.shift_selection(|text, lines| dedent_lines(text, lines, count, tab_width))
+    }
+
+    /// Indent the cursor line by one shiftwidth (Ctrl-t in insert mode)
+    ///
+    /// Unlike `>`, an empty line gets indented too, since text is about to be
+    /// typed on it. The cursor stays on the same character.
+    pub fn indent_cursor_line(&mut self) -> Result<()> {
+        let unit = self.indent_unit();
+        self.shift_cursor_line(|line| format!("{unit}{line}"))
+    }
+
+    /// Dedent the cursor line by one shiftwidth (Ctrl-d in insert mode)
+    ///
+    /// The cursor stays on the same character, or moves to the line start when
+    /// that character was part of the removed indentation.
+    pub fn dedent_cursor_line(&mut self) -> Result<()> {
+        let tab_width = self.pane_manager.get_tab_width();
+        self.shift_cursor_line(|line| dedent_lines(line, 0..=0, 1, tab_width))
+    }
+
+    /// Rewrite the cursor line with `shift`, moving the cursor along with the text
+    fn shift_cursor_line(&mut self, shift: impl FnOnce(&str) -> String) -> Result<()> {
+        if !self.is_in_request_pane() {
+            return Ok(());
+        }
+        let cursor = self.get_cursor_position();
+        let text = self.get_request_text();
+        let mut lines: Vec<&str> = text.split('\n').collect();
+        let Some(&line) = lines.get(cursor.line) else {
+            return Ok(());
+        };
+
+        let shifted = shift(line);
+        if shifted == line {
+            return Ok(());
+        }
+        let column = (cursor.column + shifted.chars().count()).saturating_sub(line.chars().count());
+        lines[cursor.line] = &shifted;
+
+        let mut events = self.pane_manager.set_request_content(&lines.join("\n"));
+        events.extend(self.pane_manager.rebuild_display_caches_and_sync());
+        events.push(ViewEvent::CurrentAreaRedrawRequired);
+        self.emit_view_event(events)?;
+        self.set_cursor_position(LogicalPosition::new(cursor.line, column))
+    }
+
+    /// Rewrite the selected lines with `shift`, then leave visual mode with the
+    /// cursor on the first non-blank of the first line (like Vim)
+    fn shift_selection(
+        &mut self,
+        shift: impl FnOnce(&str, RangeInclusive<usize>) -> String,
+    ) -> Result<Option<usize>> {
+        if !self.is_in_request_pane() {
+            return Ok(None);
+        }
+        let (Some(start), Some(end), _) = self.get_visual_selection() else {
+            return Ok(None);
+        };
+        let first_line = start.line.min(end.line);
+        let last_line = start.line.max(end.line);
+
+        self.change_mode(EditorMode::Normal)?;
+        self.shift_lines(first_line..=last_line, shift)?;
+
+        Ok(Some(last_line - first_line + 1))
+    }
+
+    /// Indent `count` lines from the cursor line down by one shiftwidth (`>>`)
+    ///
+    /// Returns the number of lines shifted, fewer than `count` near the end of
+    /// the buffer.
+    pub fn indent_lines_from_cursor(&mut self, count: usize) -> Result<usize> {
+        let unit = self.indent_unit();
+        self.shift_lines_from_cursor(count, |text, lines| indent_lines(text, lines, 1, &unit))
+    }
+
+    /// Dedent `count` lines from the cursor line down by one shiftwidth (`<<`)
+    ///
+    /// Lines with less indentation than a shiftwidth lose all of it. Returns
+    /// the number of lines shifted.
+    pub fn dedent_lines_from_cursor(&mut self, count: usize) -> Result<usize> {
+        let tab_width = self.pane_manager.get_tab_width();
+        self.shift_lines_from_cursor(count, |text, lines| dedent_lines(text, lines, 1, tab_width))
+    }
+
+    /// Rewrite `count` lines from the cursor line down with `shift`
+    fn shift_lines_from_cursor(
+        &mut self,
+        count: usize,
+        shift: impl FnOnce(&str, RangeInclusive<usize>) -> String,
+    ) -> Result<usize> {
+        if !self.is_in_request_pane() {
+            return Ok(0);
+        }
+        let first_line = self.get_cursor_position().line;
+        let line_count = self.get_request_text().split('\n').count();
+        let last_line = first_line
+            .saturating_add(count.max(1) - 1)
+            .min(line_count.saturating_sub(1));
+
+        self.shift_lines(first_line..=last_line, shift)?;
+
+        Ok(last_line + 1 - first_line)
+    }
+
+    /// Rewrite `lines` of the request buffer with `shift`, leaving the cursor
+    /// on the first non-blank of the first line (like Vim)
+    fn shift_lines(
+        &mut self,
+        lines: RangeInclusive<usize>,
+        shift: impl FnOnce(&str, RangeInclusive<usize>) -> String,
+    ) -> Result<()> {
+        let first_line = *lines.start();
+        let text = self.get_request_text();
+        let shifted = shift(&text, lines);
+
+        if shifted != text {
+            let mut events = self.pane_manager.set_request_content(&shifted);
+            events.extend(self.pane_manager.rebuild_display_caches_and_sync());
+            events.push(ViewEvent::CurrentAreaRedrawRequired);
+            self.emit_view_event(events)?;
+        }
+
+        let column = shifted.split('\n').nth(first_line).map_or(0, |line| {
+            line.chars().take_while(|ch| ch.is_whitespace()).count()
+        });
+        self.set_cursor_position(LogicalPosition::new(first_line, column))
+    }
+
+    /// Change the case of the text from `from` up to but not including `to`
+    /// (`gUw`, `gu$`)
+    ///
+    /// The positions may come in either order; the cursor lands on the first.
+    pub fn change_case_between(
+        &mut self,
+        change: CaseChange,
+        from: LogicalPosition,
+        to: LogicalPosition,
+    ) -> Result<()> {
+        if !self.is_in_request_pane() {
+            return Ok(());
+        }
+        let (start, end) = if from <= to { (from, to) } else { (to, from) };
+        let updated = change_case_where(&self.get_request_text(), change, |position| {
+            start <= position && position < end
+        });
+        self.replace_cased_text(updated, start)
+    }
+
+    /// Change the case of `count` lines from the cursor line down (`gUU`, `g~~`)
+    ///
+    /// The cursor stays where it is.
+    pub fn change_case_lines(&mut self, change: CaseChange, count: usize) -> Result<()> {
+        if !self.is_in_request_pane() {
+            return Ok(());
+        }
+        let cursor = self.get_cursor_position();
+        let last_line = cursor.line.saturating_add(count.max(1) - 1);
+        let updated = change_case_where(&self.get_request_text(), change, |position| {
+            (cursor.line..=last_line).contains(&position.line)
+        });
+        self.replace_cased_text(updated, cursor)
+    }
+
+    /// Change the case of the visual selection, then leave visual mode with the
+    /// cursor at the start of the selection (`gU` in visual mode)
+    ///
+    /// Returns whether there was a selection to change.
+    pub fn change_case_of_selection(&mut self, change: CaseChange) -> Result<bool> {
+        if !self.is_in_request_pane() {
+            return Ok(false);
+        }
+        let (Some(start), Some(end), _) = self.get_visual_selection() else {
+            return Ok(false);
+        };
+        let cursor = match self.get_mode() {
+            EditorMode::VisualLine => LogicalPosition::new(start.line.min(end.line), 0),
+            EditorMode::VisualBlock => {
+                LogicalPosition::new(start.line.min(end.line), start.column.min(end.column))
+            }
+            _ => start.min(end),
+        };
+        let updated = change_case_where(&self.get_request_text(), change, |position| {
+            self.is_position_selected(position, Pane::Request)
+        });
+
+        self.change_mode(EditorMode::Normal)?;
+        self.replace_cased_text(updated, cursor)?;
+
+        Ok(true)
+    }
+
+    /// Put case-changed text in the request buffer and move the cursor to `cursor`
+    fn replace_cased_text(&mut self, updated: String, cursor: LogicalPosition) -> Result<()> {
+        if updated != self.get_request_text() {
+            let mut events = self.pane_manager.set_request_content(&updated);
+            events.extend(self.pane_manager.rebuild_display_caches_and_sync());
+            events.push(ViewEvent::CurrentAreaRedrawRequired);
+            self.emit_view_event(events)?;
+        }
+        self.set_cursor_position(cursor)
+    }
+
     /// Convert all tab characters to spaces in the request buffer
     /// Called when expandtab is enabled
     pub fn convert_tabs_to_spaces(&mut self) -> Result<()> {
@@ -475,10 +1192,205 @@ impl ViewModel {
     }
 }
 
+/// Apply `change` to the characters of `text` at the positions `selected` accepts
+///
+/// Characters are changed one for one, so positions after them do not move.
+fn change_case_where(
+    text: &str,
+    change: CaseChange,
+    selected: impl Fn(LogicalPosition) -> bool,
+) -> String {
+    text.split('\n')
+        .enumerate()
+        .map(|(line, content)| {
+            content
+                .chars()
+                .enumerate()
+                .map(|(column, ch)| {
+                    if selected(LogicalPosition::new(line, column)) {
+                        change.apply(ch)
+                    } else {
+                        ch
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Position of the last character of `text` when it is inserted at `start`
+fn end_of_inserted_text(start: LogicalPosition, text: &str) -> LogicalPosition {
+    match text.rsplit_once('\n') {
+        Some((before, last)) => LogicalPosition::new(
+            start.line + before.matches('\n').count() + 1,
+            last.chars().count().saturating_sub(1),
+        ),
+        None => LogicalPosition::new(
+            start.line,
+            start.column + text.chars().count().saturating_sub(1),
+        ),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::repl::events::LogicalPosition;
+    use crate::repl::commands::CharFind;
+    use crate::repl::events::{LogicalPosition, Pane};
+
+    #[test]
+    fn insert_text_should_update_display_cache_once() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        let updates_before = vm.pane_manager.display_cache_update_count(Pane::Request);
+
+        vm.insert_text("GET /users\nAccept: */*").unwrap();
+
+        assert_eq!(
+            vm.pane_manager.display_cache_update_count(Pane::Request),
+            updates_before + 1
+        );
+        assert_eq!(vm.get_request_text(), "GET /users\nAccept: */*");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(1, 11));
+    }
+
+    #[test]
+    fn insert_last_inserted_text_should_repeat_previous_insert_session() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("fo").unwrap();
+        vm.insert_char('o').unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.set_cursor_position(LogicalPosition::new(0, 0)).unwrap();
+        vm.insert_last_inserted_text().unwrap();
+
+        assert_eq!(vm.get_request_text(), "foofoo");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 3));
+    }
+
+    #[test]
+    fn insert_last_inserted_text_should_insert_nothing_before_first_insert_session() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+
+        vm.insert_last_inserted_text().unwrap();
+
+        assert_eq!(vm.get_request_text(), "");
+    }
+
+    #[test]
+    fn paste_after_should_bracket_pasted_text_with_change_marks() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("GET /users").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.set_cursor_position(LogicalPosition::new(0, 3)).unwrap();
+
+        vm.paste_after_with_type(&YankEntry {
+            text: "abc\nde".to_string(),
+            yank_type: YankType::Character,
+        })
+        .unwrap();
+
+        assert_eq!(vm.get_request_text(), "GET abc\nde/users");
+        assert!(vm.jump_to_change_mark(false).unwrap());
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 4));
+        assert!(vm.jump_to_change_mark(true).unwrap());
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(1, 1));
+
+        // Pasting runs in Insert mode without replacing the text for Ctrl-a
+        assert_eq!(vm.last_inserted_text, "GET /users");
+    }
+
+    #[test]
+    fn line_wise_paste_should_mark_whole_pasted_lines() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("GET /users\n\n{}").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.set_cursor_position(LogicalPosition::new(0, 0)).unwrap();
+
+        vm.paste_after_with_type(&YankEntry {
+            text: "Accept: */*\nX-Id: 1\n".to_string(),
+            yank_type: YankType::Line,
+        })
+        .unwrap();
+
+        assert_eq!(
+            vm.get_change_marks(),
+            Some(LogicalRange::new(
+                LogicalPosition::new(1, 0),
+                LogicalPosition::new(2, 6)
+            ))
+        );
+        assert!(vm.select_last_change().unwrap());
+        assert_eq!(vm.get_mode(), EditorMode::Visual);
+        assert_eq!(
+            vm.get_selected_text().as_deref(),
+            Some("Accept: */*\nX-Id: 1")
+        );
+    }
+
+    #[test]
+    fn line_wise_paste_should_put_lines_around_cursor_line_and_go_to_first_non_blank() {
+        let line_entry = YankEntry {
+            text: "  \"id\": 1,\n  \"name\": \"a\"\n".to_string(),
+            yank_type: YankType::Line,
+        };
+        let mut vm = ViewModel::new();
+        vm.set_request_text("{\n}").unwrap();
+        vm.set_cursor_position(LogicalPosition::new(0, 1)).unwrap();
+
+        vm.paste_after_with_type(&line_entry).unwrap();
+        assert_eq!(
+            vm.get_request_text(),
+            "{\n  \"id\": 1,\n  \"name\": \"a\"\n}"
+        );
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(1, 2));
+
+        vm.set_request_text("{\n}").unwrap();
+        vm.set_cursor_position(LogicalPosition::new(1, 0)).unwrap();
+        vm.paste_with_type(&line_entry).unwrap();
+        assert_eq!(
+            vm.get_request_text(),
+            "{\n  \"id\": 1,\n  \"name\": \"a\"\n}"
+        );
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(1, 2));
+
+        // Character-wise text is still spliced into the cursor line
+        let char_entry = YankEntry {
+            text: "xy".to_string(),
+            yank_type: YankType::Character,
+        };
+        vm.set_request_text("ab").unwrap();
+        vm.set_cursor_position(LogicalPosition::new(0, 0)).unwrap();
+        vm.paste_after_with_type(&char_entry).unwrap();
+        assert_eq!(vm.get_request_text(), "axyb");
+        vm.paste_with_type(&char_entry).unwrap();
+        assert_eq!(vm.get_cursor_position().line, 0);
+        assert!(vm.get_request_text().starts_with("ax"));
+    }
+
+    #[test]
+    fn change_marks_should_be_unset_before_any_change() {
+        let mut vm = ViewModel::new();
+        assert!(!vm.jump_to_change_mark(false).unwrap());
+        assert!(!vm.select_last_change().unwrap());
+
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("hello").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        assert_eq!(
+            vm.get_change_marks(),
+            Some(LogicalRange::new(
+                LogicalPosition::new(0, 0),
+                LogicalPosition::new(0, 4)
+            ))
+        );
+    }
 
     #[test]
     fn test_visual_block_insert_mode_allows_text_insertion() {
@@ -908,6 +1820,20 @@ mod tests {
         assert!(yanked.is_none(), "Nothing should be yanked in Insert mode");
     }
 
+    #[test]
+    fn cut_current_line_should_be_refused_on_read_only_response_pane() {
+        let mut vm = ViewModel::new();
+        vm.set_response(200, "line 1\nline 2".to_string());
+        vm.switch_to_response_pane();
+
+        vm.cut_current_line().unwrap();
+        // The pane itself refuses too, as the response is not editable
+        assert_eq!(vm.pane_manager.cut_current_line(), None);
+
+        assert_eq!(vm.get_response_text(), "line 1\nline 2");
+        assert!(vm.get_yanked_text().is_none());
+    }
+
     #[test]
     fn test_cut_current_line_with_multibyte_characters() {
         let mut vm = ViewModel::new();
@@ -976,4 +1902,474 @@ mod tests {
         );
         assert_eq!(entry.yank_type, YankType::Line, "Yank type should be Line");
     }
+
+    #[test]
+    fn cut_current_line_should_leave_unnamed_register_alone_with_black_hole_register() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("keep\ndrop").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.yank_to_buffer_with_type("saved\n".to_string(), YankType::Line)
+            .unwrap();
+
+        // "_dd
+        vm.select_register(BLACK_HOLE_REGISTER);
+        assert_eq!(vm.get_pending_keys(), "\"_");
+        vm.change_mode(EditorMode::DPrefix).unwrap();
+        vm.cut_current_line().unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.clear_pending_register();
+
+        assert_eq!(vm.get_request_text(), "keep");
+        assert_eq!(vm.get_yanked_text(), Some("saved\n".to_string()));
+
+        // Without the register, dd yanks again
+        vm.cut_current_line().unwrap();
+        assert_eq!(vm.get_yanked_text(), Some("keep\n".to_string()));
+    }
+
+    #[test]
+    fn move_cursor_to_char_should_find_counted_occurrence() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("a=1&a=2&a=3&a=4").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.set_cursor_position(LogicalPosition::new(0, 0)).unwrap();
+
+        // 3fa
+        assert!(vm.move_cursor_to_char('a', 3).unwrap());
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 12));
+
+        // A count past the last occurrence does not move at all
+        assert!(!vm.move_cursor_to_char('a', 2).unwrap());
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 12));
+    }
+
+    #[test]
+    fn till_and_backward_finds_should_repeat_with_semicolon_and_comma() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("名前=1&名前=2&名前=3").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.set_cursor_position(LogicalPosition::new(0, 0)).unwrap();
+
+        // t&
+        vm.start_find_char(CharFind::TillForward);
+        assert!(vm.move_cursor_to_char('&', 1).unwrap());
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 3));
+
+        // ; skips the & right next to the cursor
+        assert!(vm.repeat_find_char(false, 1).unwrap());
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 8));
+
+        // , goes back as T&
+        assert!(vm.repeat_find_char(true, 1).unwrap());
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 5));
+
+        // F名 lands on the character, and a missing one leaves the cursor alone
+        vm.start_find_char(CharFind::Backward);
+        assert!(vm.move_cursor_to_char('名', 1).unwrap());
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 0));
+        vm.start_find_char(CharFind::Forward);
+        assert!(!vm.move_cursor_to_char('#', 1).unwrap());
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 0));
+    }
+
+    #[test]
+    fn delete_to_char_should_delete_through_counted_occurrence() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("a=1&a=2&a=3&a=4").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.set_cursor_position(LogicalPosition::new(0, 0)).unwrap();
+
+        // d2fa
+        assert!(vm.delete_to_char('a', 2).unwrap());
+        assert_eq!(vm.get_request_text(), "=3&a=4");
+        assert_eq!(vm.get_yanked_text(), Some("a=1&a=2&a".to_string()));
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 0));
+
+        assert!(!vm.delete_to_char('a', 3).unwrap());
+        assert_eq!(vm.get_request_text(), "=3&a=4");
+    }
+
+    #[test]
+    fn delete_around_word_should_yank_counted_words_and_keep_cursor_on_line() {
+        let mut vm = ViewModel::new();
+        vm.set_request_text("one two three four").unwrap();
+        vm.set_cursor_position(LogicalPosition::new(0, 5)).unwrap();
+
+        // 2daw
+        assert!(vm.delete_around_word(2).unwrap());
+        assert_eq!(vm.get_request_text(), "one four");
+        assert_eq!(vm.get_yanked_text(), Some("two three ".to_string()));
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 4));
+
+        // The last word takes the space before it, leaving the cursor on the line
+        assert!(vm.delete_around_word(1).unwrap());
+        assert_eq!(vm.get_request_text(), "one");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 2));
+
+        vm.set_request_text("").unwrap();
+        assert!(!vm.delete_around_word(1).unwrap());
+    }
+
+    #[test]
+    fn change_case_should_map_characters_in_place_including_non_ascii() {
+        let mut vm = ViewModel::new();
+        vm.set_request_text("{\"straße\": \"Émile\"}\nnext")
+            .unwrap();
+
+        // Positions may come in either order and the cursor goes to the first
+        vm.change_case_between(
+            CaseChange::Upper,
+            LogicalPosition::new(0, 9),
+            LogicalPosition::new(0, 2),
+        )
+        .unwrap();
+        assert_eq!(vm.get_request_text(), "{\"STRAßE\": \"Émile\"}\nnext");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 2));
+
+        vm.change_case_lines(CaseChange::Toggle, 5).unwrap();
+        assert_eq!(vm.get_request_text(), "{\"straße\": \"éMILE\"}\nNEXT");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 2));
+    }
+
+    #[test]
+    fn change_case_of_selection_should_leave_visual_mode_at_selection_start() {
+        let mut vm = ViewModel::new();
+        vm.set_request_text("Accept: JSON\nHost: Example").unwrap();
+        vm.set_cursor_position(LogicalPosition::new(1, 3)).unwrap();
+        vm.change_mode(EditorMode::VisualLine).unwrap();
+        vm.set_cursor_position(LogicalPosition::new(0, 5)).unwrap();
+
+        assert!(vm.change_case_of_selection(CaseChange::Lower).unwrap());
+        assert_eq!(vm.get_request_text(), "accept: json\nhost: example");
+        assert_eq!(vm.get_mode(), EditorMode::Normal);
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 0));
+
+        assert!(!vm.change_case_of_selection(CaseChange::Upper).unwrap());
+    }
+
+    #[test]
+    fn test_join_lines_after_period_with_joinspaces_off() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("Done.\n  Next").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.set_cursor_position(LogicalPosition { line: 0, column: 0 })
+            .unwrap();
+
+        vm.join_lines().unwrap();
+
+        assert_eq!(vm.get_request_text(), "Done. Next");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 5));
+    }
+
+    #[test]
+    fn test_join_lines_after_period_with_joinspaces_on() {
+        use crate::repl::commands::{Setting, SettingValue};
+
+        let mut vm = ViewModel::new();
+        vm.apply_setting(Setting::JoinSpaces, SettingValue::On)
+            .unwrap();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("Done.\n  Next").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.set_cursor_position(LogicalPosition { line: 0, column: 0 })
+            .unwrap();
+
+        vm.join_lines().unwrap();
+
+        assert_eq!(vm.get_request_text(), "Done.  Next");
+    }
+
+    #[test]
+    fn test_yank_block_and_paste_as_rectangle_elsewhere() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("abcdef\nghijkl\nmnopqr\nxy").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+
+        // Select a 2x3 block covering "bcd" and "hij"
+        vm.set_cursor_position(LogicalPosition { line: 0, column: 1 })
+            .unwrap();
+        vm.change_mode(EditorMode::VisualBlock).unwrap();
+        vm.set_cursor_position(LogicalPosition { line: 1, column: 3 })
+            .unwrap();
+        let entry = YankEntry {
+            text: vm.get_selected_text().unwrap(),
+            yank_type: YankType::Block {
+                width: vm.get_visual_block_width(),
+            },
+        };
+        assert_eq!(entry.text, "bcd\nhij");
+        assert_eq!(entry.yank_type, YankType::Block { width: 3 });
+        vm.change_mode(EditorMode::Normal).unwrap();
+
+        // Paste at column 4 of line 2; the short last line is padded to reach the column
+        vm.set_cursor_position(LogicalPosition { line: 2, column: 4 })
+            .unwrap();
+        vm.paste_with_type(&entry).unwrap();
+
+        assert_eq!(vm.get_request_text(), "abcdef\nghijkl\nmnopbcdqr\nxy  hij");
+    }
+
+    #[test]
+    fn block_width_should_count_display_columns_of_double_width_characters() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("あいう\nab\nx\ny").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+
+        vm.set_cursor_position(LogicalPosition::new(0, 0)).unwrap();
+        vm.change_mode(EditorMode::VisualBlock).unwrap();
+        vm.set_cursor_position(LogicalPosition::new(1, 1)).unwrap();
+        let entry = YankEntry {
+            text: vm.get_selected_text().unwrap(),
+            yank_type: YankType::Block {
+                width: vm.get_visual_block_width(),
+            },
+        };
+        assert_eq!(entry.text, "あい\nab");
+        assert_eq!(entry.yank_type, YankType::Block { width: 4 });
+        vm.change_mode(EditorMode::Normal).unwrap();
+
+        // The narrower row is padded so the text after the block lines up on screen
+        vm.set_cursor_position(LogicalPosition::new(2, 0)).unwrap();
+        vm.paste_with_type(&entry).unwrap();
+
+        assert_eq!(vm.get_request_text(), "あいう\nab\nあいx\nab  y");
+    }
+
+    #[test]
+    fn yank_should_emit_highlight_covering_yanked_range_with_hlyank() {
+        use crate::repl::commands::{Setting, SettingValue};
+        use crate::repl::models::YankHighlight;
+
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("abcdef\nghijkl").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.set_cursor_position(LogicalPosition::new(1, 2)).unwrap();
+
+        // Off by default
+        vm.collect_pending_view_events();
+        vm.yank_current_line().unwrap();
+        assert_eq!(vm.get_yank_highlight(), None);
+
+        vm.apply_setting(Setting::HlYank, SettingValue::On).unwrap();
+        vm.collect_pending_view_events();
+        vm.yank_current_line().unwrap();
+        let line = YankHighlight::new(
+            Pane::Request,
+            LogicalPosition::new(1, 0),
+            LogicalPosition::new(1, 0),
+            YankType::Line,
+        );
+        assert!(vm
+            .collect_pending_view_events()
+            .contains(&ViewEvent::YankHighlighted { highlight: line }));
+
+        // A block selection flashes its corners, whichever way it was made
+        vm.change_mode(EditorMode::VisualBlock).unwrap();
+        vm.set_cursor_position(LogicalPosition::new(0, 1)).unwrap();
+        vm.collect_pending_view_events();
+        vm.highlight_selection_yank(YankType::Block { width: 2 })
+            .unwrap();
+        let block = YankHighlight {
+            pane: Pane::Request,
+            start: LogicalPosition::new(0, 1),
+            end: LogicalPosition::new(1, 2),
+            yank_type: YankType::Block { width: 2 },
+        };
+        assert!(vm
+            .collect_pending_view_events()
+            .contains(&ViewEvent::YankHighlighted { highlight: block }));
+
+        vm.clear_yank_highlight().unwrap();
+        assert_eq!(vm.get_yank_highlight(), None);
+    }
+
+    #[test]
+    fn test_paste_block_pads_short_rows_to_block_width() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("mnop\nqrst").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.set_cursor_position(LogicalPosition { line: 0, column: 0 })
+            .unwrap();
+
+        let entry = YankEntry {
+            text: "bcd\nb".to_string(),
+            yank_type: YankType::Block { width: 3 },
+        };
+        vm.paste_with_type(&entry).unwrap();
+
+        assert_eq!(vm.get_request_text(), "bcdmnop\nb  qrst");
+    }
+
+    #[test]
+    fn indent_selection_should_shift_lines_by_count_shiftwidths() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("{\n\"a\": 1,\n\"b\": 2\n}").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.apply_setting(
+            crate::repl::commands::Setting::ExpandTab,
+            crate::repl::commands::SettingValue::On,
+        )
+        .unwrap();
+        vm.set_cursor_position(LogicalPosition::new(1, 0)).unwrap();
+
+        vm.change_mode(EditorMode::VisualLine).unwrap();
+        vm.move_cursor_down().unwrap();
+        assert_eq!(vm.indent_selection(2).unwrap(), Some(2));
+
+        assert_eq!(
+            vm.get_request_text(),
+            "{\n        \"a\": 1,\n        \"b\": 2\n}"
+        );
+        assert_eq!(vm.get_mode(), EditorMode::Normal);
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(1, 8));
+    }
+
+    #[test]
+    fn dedent_selection_should_remove_count_shiftwidths() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("\t\ta\n\tb").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.set_cursor_position(LogicalPosition::new(0, 0)).unwrap();
+
+        vm.change_mode(EditorMode::Visual).unwrap();
+        vm.move_cursor_down().unwrap();
+        assert_eq!(vm.dedent_selection(2).unwrap(), Some(2));
+
+        assert_eq!(vm.get_request_text(), "a\nb");
+    }
+
+    #[test]
+    fn indent_and_dedent_cursor_line_should_keep_cursor_on_same_character() {
+        let mut vm = ViewModel::new();
+        vm.apply_setting(
+            crate::repl::commands::Setting::ExpandTab,
+            crate::repl::commands::SettingValue::On,
+        )
+        .unwrap();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("{\n\"id\": 1\n}").unwrap();
+        vm.set_cursor_position(LogicalPosition::new(1, 3)).unwrap();
+
+        vm.indent_cursor_line().unwrap();
+        assert_eq!(vm.get_request_text(), "{\n    \"id\": 1\n}");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(1, 7));
+        assert_eq!(vm.get_mode(), EditorMode::Insert);
+
+        vm.dedent_cursor_line().unwrap();
+        assert_eq!(vm.get_request_text(), "{\n\"id\": 1\n}");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(1, 3));
+        assert_eq!(vm.get_mode(), EditorMode::Insert);
+    }
+
+    #[test]
+    fn indent_lines_from_cursor_should_follow_expandtab() {
+        use crate::repl::commands::{Setting, SettingValue};
+
+        for (expand_tab, indented) in [
+            (SettingValue::On, "{\n    \"a\": 1,\n    \"b\": 2\n}"),
+            (SettingValue::Off, "{\n\t\"a\": 1,\n\t\"b\": 2\n}"),
+        ] {
+            let mut vm = ViewModel::new();
+            vm.apply_setting(Setting::ExpandTab, expand_tab).unwrap();
+            vm.set_request_text("{\n\"a\": 1,\n\"b\": 2\n}").unwrap();
+            vm.set_cursor_position(LogicalPosition::new(1, 3)).unwrap();
+
+            assert_eq!(vm.indent_lines_from_cursor(2).unwrap(), 2);
+
+            assert_eq!(vm.get_request_text(), indented);
+            // The cursor stays on its line, at the first non-blank
+            let first_non_blank = if expand_tab == SettingValue::On { 4 } else { 1 };
+            assert_eq!(
+                vm.get_cursor_position(),
+                LogicalPosition::new(1, first_non_blank)
+            );
+        }
+    }
+
+    #[test]
+    fn dedent_lines_from_cursor_should_remove_at_most_one_shiftwidth() {
+        let mut vm = ViewModel::new();
+        vm.set_request_text("      deep\n  shallow\nnone").unwrap();
+
+        // A count past the last line shifts the lines that are there
+        assert_eq!(vm.dedent_lines_from_cursor(5).unwrap(), 3);
+
+        assert_eq!(vm.get_request_text(), "  deep\nshallow\nnone");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 2));
+    }
+
+    #[test]
+    fn pending_count_should_accumulate_digits() {
+        let mut vm = ViewModel::new();
+        assert_eq!(vm.get_pending_count(), None);
+
+        vm.push_count_digit(1);
+        vm.push_count_digit(2);
+        assert_eq!(vm.get_pending_count(), Some(12));
+        assert_eq!(vm.get_pending_keys(), "12");
+
+        vm.clear_pending_count();
+        assert_eq!(vm.get_pending_count(), None);
+    }
+
+    #[test]
+    fn resolve_pending_key_sequence_should_return_prefix_to_normal() {
+        let mut vm = ViewModel::new();
+        assert!(!vm.resolve_pending_key_sequence().unwrap());
+
+        vm.change_mode(EditorMode::GPrefix).unwrap();
+        assert!(vm.is_awaiting_key_sequence());
+        assert_eq!(vm.get_pending_keys(), "g");
+
+        assert!(vm.resolve_pending_key_sequence().unwrap());
+        assert_eq!(vm.get_mode(), EditorMode::Normal);
+        assert_eq!(vm.get_pending_keys(), "");
+    }
+
+    #[test]
+    fn colon_from_visual_selection_should_prefill_range_of_selected_lines() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("line 1\nline 2\nline 3\nline 4").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.set_cursor_position(LogicalPosition::new(2, 1)).unwrap();
+
+        vm.change_mode(EditorMode::Visual).unwrap();
+        vm.update_visual_selection(LogicalPosition::new(1, 4));
+        vm.change_mode(EditorMode::Command).unwrap();
+
+        assert_eq!(vm.get_ex_command_buffer(), "'<,'>");
+        assert_eq!(vm.get_last_visual_line_range(), Some((1, 2)));
+    }
+
+    #[test]
+    fn delete_lines_should_remove_range_and_keep_cursor_on_next_line() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("line 1\nline 2\nline 3\nline 4").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+
+        assert_eq!(
+            vm.get_lines_text(2, 3),
+            Some("line 3\nline 4\n".to_string())
+        );
+        assert_eq!(vm.delete_lines(2, 3).unwrap(), 2);
+        assert_eq!(vm.get_request_text(), "line 1\nline 2");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(1, 0));
+
+        assert_eq!(vm.get_lines_text(5, 6), None);
+        assert_eq!(vm.delete_lines(5, 6).unwrap(), 0);
+        assert_eq!(vm.get_request_text(), "line 1\nline 2");
+    }
 }
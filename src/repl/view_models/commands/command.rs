@@ -7,7 +7,8 @@ use anyhow::Result;
 use crossterm::event::KeyEvent;
 
 use crate::repl::{
-    events::{EditorMode, Pane},
+    error::AppResult,
+    events::{EditorMode, KeyBinding, Pane},
     services::Services,
     view_models::{commands::events::ModelEvent, ViewModel},
 };
@@ -50,6 +51,30 @@ pub struct ExecutionContext<'a> {
     pub services: &'a mut Services,
 }
 
+impl ExecutionContext<'_> {
+    /// Write the request buffer to its file when `:set autowrite on` asks for it
+    ///
+    /// A clean buffer, or one without a file name, is silently left alone.
+    pub fn autowrite_request(&mut self) -> AppResult<()> {
+        let Some(path) = self.view_model.get_request_file().map(str::to_string) else {
+            return Ok(());
+        };
+
+        let content = self.view_model.get_request_text();
+        let modified = self.view_model.is_request_modified();
+        if self
+            .services
+            .autosave
+            .autowrite(&path, &content, modified)?
+            .is_some()
+        {
+            self.view_model.set_request_modified(false);
+            self.services.autoread.track(&path);
+        }
+        Ok(())
+    }
+}
+
 /// Context for Commands containing current application state
 ///
 /// This provides Commands with read-only access to application state
@@ -64,6 +89,8 @@ pub struct CommandContext {
     pub is_read_only: bool,
     /// Whether there's an active visual selection
     pub has_selection: bool,
+    /// Key that sends the request (`:set executekey`)
+    pub execute_key: KeyBinding,
 }
 
 impl CommandContext {
@@ -74,6 +101,7 @@ impl CommandContext {
             current_pane: view_model.get_current_pane(),
             is_read_only: view_model.is_in_response_pane(), // Response pane is read-only
             has_selection: view_model.get_selected_text().is_some(),
+            execute_key: view_model.get_execute_key(),
         }
     }
 }
@@ -73,7 +73,7 @@ pub enum ModelEvent {
     HttpRequestStarted { method: String, url: String },
 
     /// HTTP response was received
-    HttpResponseReceived { status: u16, body: String },
+    HttpResponseReceived { status: u16 },
 }
 
 // Re-export YankType from models to avoid duplication
@@ -167,7 +167,7 @@ mod tests {
         // Test that YankType from models is properly re-exported
         let character = YankType::Character;
         let line = YankType::Line;
-        let block = YankType::Block;
+        let block = YankType::Block { width: 1 };
 
         // Verify all types are distinct
         assert_ne!(character, line);
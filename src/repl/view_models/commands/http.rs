@@ -4,11 +4,11 @@
 
 use crate::repl::events::EditorMode;
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::KeyEvent;
 
 use super::{Command, CommandContext, ExecutionContext, ModelEvent};
 
-/// Execute HTTP request command (Enter in Normal mode on Request pane)
+/// Execute HTTP request command (Enter, or the `executekey`, in Normal mode on Request pane)
 ///
 /// This command:
 /// 1. Parses the request from the buffer
@@ -28,16 +28,33 @@ impl Command for HttpExecuteCommand {
         // Only relevant when:
         // - In Normal mode
         // - On Request pane (not read-only)
-        // - Enter key pressed without modifiers
-        let is_enter = matches!(key_event.code, KeyCode::Enter);
-        let no_modifiers = key_event.modifiers == KeyModifiers::NONE;
+        // - The execute key (Enter unless remapped) pressed with exactly its modifiers
+        let is_execute_key = context.execute_key.matches(&key_event);
         let is_normal_mode = mode == EditorMode::Normal;
         let is_request_pane = !context.is_read_only; // Request pane is editable
 
-        is_enter && no_modifiers && is_normal_mode && is_request_pane
+        is_execute_key && is_normal_mode && is_request_pane
     }
 
     fn handle(&self, context: &mut ExecutionContext) -> Result<Vec<ModelEvent>> {
+        // Repeated headers are always reported, and block the send with strictheaders
+        let duplicate_warning = context.view_model.duplicate_header_warning();
+        if let (true, Some(warning)) = (
+            context.view_model.is_strict_headers_enabled(),
+            &duplicate_warning,
+        ) {
+            return Ok(vec![ModelEvent::StatusMessageSet {
+                message: format!("Request not sent: {warning}"),
+            }]);
+        }
+
+        // With autowrite the file on disk matches the request that is sent
+        if let Err(e) = context.autowrite_request() {
+            return Ok(vec![ModelEvent::StatusMessageSet {
+                message: format!("Request not sent: {e}"),
+            }]);
+        }
+
         // Check if HTTP service is available
         let http_service = context
             .services
@@ -48,6 +65,17 @@ impl Command for HttpExecuteCommand {
         // Get request text from the view model
         let request_text = context.view_model.get_request_text();
 
+        // Warn about misspelled header names without blocking the request
+        let spell_warning = if context.view_model.is_spell_enabled() {
+            context.view_model.header_lint_warning()
+        } else {
+            None
+        };
+        let warnings: Vec<String> = [duplicate_warning, spell_warning]
+            .into_iter()
+            .flatten()
+            .collect();
+
         // Set executing status
         context.view_model.set_executing_request(true);
 
@@ -55,9 +83,12 @@ impl Command for HttpExecuteCommand {
         http_service.execute_async(request_text);
 
         // Return event indicating request was initiated
-        Ok(vec![ModelEvent::StatusMessageSet {
-            message: "Executing HTTP request...".to_string(),
-        }])
+        let message = if warnings.is_empty() {
+            "Executing HTTP request...".to_string()
+        } else {
+            format!("Executing HTTP request... ({})", warnings.join("; "))
+        };
+        Ok(vec![ModelEvent::StatusMessageSet { message }])
     }
 
     fn name(&self) -> &'static str {
@@ -74,9 +105,10 @@ impl Default for HttpExecuteCommand {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::repl::events::Pane;
+    use crate::repl::events::{KeyBinding, Pane};
     use crate::repl::services::Services;
     use crate::repl::view_models::ViewModel;
+    use crossterm::event::{KeyCode, KeyModifiers};
 
     fn create_test_key_event(code: KeyCode) -> KeyEvent {
         KeyEvent::new(code, KeyModifiers::NONE)
@@ -89,6 +121,7 @@ mod tests {
             current_pane: Pane::Request,
             is_read_only: false, // Request pane is editable
             has_selection: false,
+            execute_key: KeyBinding::ENTER,
         };
 
         let cmd = HttpExecuteCommand::new();
@@ -104,6 +137,7 @@ mod tests {
             current_pane: Pane::Request,
             is_read_only: false,
             has_selection: false,
+            execute_key: KeyBinding::ENTER,
         };
 
         let cmd = HttpExecuteCommand::new();
@@ -119,6 +153,7 @@ mod tests {
             current_pane: Pane::Response,
             is_read_only: true, // Response pane is read-only
             has_selection: false,
+            execute_key: KeyBinding::ENTER,
         };
 
         let cmd = HttpExecuteCommand::new();
@@ -134,6 +169,7 @@ mod tests {
             current_pane: Pane::Request,
             is_read_only: false,
             has_selection: false,
+            execute_key: KeyBinding::ENTER,
         };
 
         let cmd = HttpExecuteCommand::new();
@@ -142,6 +178,27 @@ mod tests {
         assert!(!cmd.is_relevant(event, EditorMode::Normal, &context));
     }
 
+    #[test]
+    fn http_execute_should_follow_remapped_execute_key() {
+        let context = CommandContext {
+            current_mode: EditorMode::Normal,
+            current_pane: Pane::Request,
+            is_read_only: false,
+            has_selection: false,
+            execute_key: KeyBinding::parse("<C-CR>").unwrap(),
+        };
+
+        let cmd = HttpExecuteCommand::new();
+        let ctrl_enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL);
+
+        assert!(cmd.is_relevant(ctrl_enter, EditorMode::Normal, &context));
+        assert!(!cmd.is_relevant(
+            create_test_key_event(KeyCode::Enter),
+            EditorMode::Normal,
+            &context
+        ));
+    }
+
     #[tokio::test]
     async fn http_execute_should_parse_and_trigger_request() {
         use bluenote::get_blank_profile;
@@ -178,6 +235,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn http_execute_should_refuse_duplicate_headers_with_strictheaders() {
+        use crate::repl::commands::{Setting, SettingValue};
+
+        let mut view_model = ViewModel::new();
+        view_model.pane_manager.set_request_content(
+            "POST /users\nContent-Type: application/json\nContent-Type: text/plain",
+        );
+        view_model
+            .apply_setting(Setting::StrictHeaders, SettingValue::On)
+            .unwrap();
+        let mut services = Services::new();
+
+        let mut context = ExecutionContext {
+            view_model: &mut view_model,
+            services: &mut services,
+        };
+        let events = HttpExecuteCommand::new().handle(&mut context).unwrap();
+
+        assert_eq!(
+            events,
+            vec![ModelEvent::StatusMessageSet {
+                message:
+                    "Request not sent: Duplicate header 'Content-Type' on line 3 (first on line 2)"
+                        .to_string(),
+            }]
+        );
+        assert!(!view_model.is_executing_request());
+    }
+
+    #[test]
+    fn http_execute_should_not_refuse_repeated_body_lines_with_strictheaders() {
+        use crate::repl::commands::{Setting, SettingValue};
+
+        let mut view_model = ViewModel::new();
+        view_model.pane_manager.set_request_content(
+            "POST /users\nContent-Type: application/json\n\nContent-Type: a\nContent-Type: b",
+        );
+        view_model
+            .apply_setting(Setting::StrictHeaders, SettingValue::On)
+            .unwrap();
+        let mut services = Services::new();
+
+        let mut context = ExecutionContext {
+            view_model: &mut view_model,
+            services: &mut services,
+        };
+        let error = HttpExecuteCommand::new().handle(&mut context).unwrap_err();
+
+        // Past the header check, the send only stops for want of an HTTP service
+        assert_eq!(error.to_string(), "HTTP service not configured");
+    }
+
     #[tokio::test]
     async fn http_execute_should_handle_invalid_request() {
         use bluenote::get_blank_profile;
@@ -111,7 +111,7 @@ impl Default for UnifiedCommandRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::repl::events::Pane;
+    use crate::repl::events::{KeyBinding, Pane};
     use crossterm::event::{KeyCode, KeyModifiers};
 
     #[test]
@@ -142,6 +142,7 @@ mod tests {
             current_pane: Pane::Request,
             is_read_only: false,
             has_selection: true,
+            execute_key: KeyBinding::ENTER,
         };
 
         // Test 'y' key in visual mode - should find YankSelectionCommand
@@ -163,6 +164,7 @@ mod tests {
             current_pane: Pane::Request,
             is_read_only: false,
             has_selection: false,
+            execute_key: KeyBinding::ENTER,
         };
 
         // Test 'y' key in normal mode - should find no relevant command
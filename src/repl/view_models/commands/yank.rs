@@ -32,11 +32,11 @@ impl YankSelectionCommand {
     }
 
     /// Determine yank type from editor mode
-    fn determine_yank_type(mode: EditorMode) -> YankType {
+    fn determine_yank_type(mode: EditorMode, block_width: usize) -> YankType {
         match mode {
             EditorMode::Visual => YankType::Character,
             EditorMode::VisualLine => YankType::Line,
-            EditorMode::VisualBlock => YankType::Block,
+            EditorMode::VisualBlock => YankType::Block { width: block_width },
             _ => YankType::Character, // Default fallback
         }
     }
@@ -78,7 +78,8 @@ impl Command for YankSelectionCommand {
         };
 
         // Determine yank type based on current mode
-        let yank_type = Self::determine_yank_type(current_mode);
+        let yank_type =
+            Self::determine_yank_type(current_mode, context.view_model.get_visual_block_width());
 
         // Store in yank buffer using YankService
         context
@@ -86,6 +87,9 @@ impl Command for YankSelectionCommand {
             .yank
             .yank(selected_text.clone(), yank_type)?;
 
+        // Flash the yanked text while the selection still marks it
+        context.view_model.highlight_selection_yank(yank_type)?;
+
         // Clear selection directly on ViewModel
         context.view_model.clear_visual_selection()?;
 
@@ -120,6 +124,7 @@ impl Command for YankSelectionCommand {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::repl::events::KeyBinding;
 
     #[test]
     fn yank_selection_command_should_return_correct_name() {
@@ -140,6 +145,7 @@ mod tests {
             current_pane: Pane::Request,
             is_read_only: false,
             has_selection: true,
+            execute_key: KeyBinding::ENTER,
         };
 
         // Test 'y' key in visual mode - should be relevant
@@ -166,6 +172,7 @@ mod tests {
             current_pane: Pane::Request,
             is_read_only: false,
             has_selection: false,
+            execute_key: KeyBinding::ENTER,
         };
         let y_key = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
         assert!(!command.is_relevant(y_key, EditorMode::Normal, &context_normal));
@@ -176,6 +183,7 @@ mod tests {
             current_pane: Pane::Request,
             is_read_only: false,
             has_selection: false,
+            execute_key: KeyBinding::ENTER,
         };
         assert!(!command.is_relevant(y_key, EditorMode::Visual, &context_no_selection));
 
@@ -185,6 +193,7 @@ mod tests {
             current_pane: Pane::Response,
             is_read_only: true,
             has_selection: true,
+            execute_key: KeyBinding::ENTER,
         };
         assert!(!command.is_relevant(y_key, EditorMode::Visual, &context_readonly));
 
@@ -195,6 +204,7 @@ mod tests {
             current_pane: Pane::Request,
             is_read_only: false,
             has_selection: true,
+            execute_key: KeyBinding::ENTER,
         };
         assert!(!command.is_relevant(x_key, EditorMode::Visual, &context_valid));
 
@@ -206,16 +216,16 @@ mod tests {
     #[test]
     fn determine_yank_type_should_map_modes_correctly() {
         assert_eq!(
-            YankSelectionCommand::determine_yank_type(EditorMode::Visual),
+            YankSelectionCommand::determine_yank_type(EditorMode::Visual, 3),
             YankType::Character
         );
         assert_eq!(
-            YankSelectionCommand::determine_yank_type(EditorMode::VisualLine),
+            YankSelectionCommand::determine_yank_type(EditorMode::VisualLine, 3),
             YankType::Line
         );
         assert_eq!(
-            YankSelectionCommand::determine_yank_type(EditorMode::VisualBlock),
-            YankType::Block
+            YankSelectionCommand::determine_yank_type(EditorMode::VisualBlock, 3),
+            YankType::Block { width: 3 }
         );
     }
 
@@ -17,11 +17,18 @@
 //! 3. View Coordination: Emits events for efficient selective rendering
 //! 4. HTTP Operations: Manages request/response lifecycle with status updates
 
-use crate::repl::events::{EditorMode, EventBus, LogicalPosition, ModelEvent, Pane, ViewEvent};
+use crate::repl::commands::{CharFind, Operator, RecordedChange};
+use crate::repl::error::AppError;
+use crate::repl::events::{
+    EditorMode, EventBus, KeyBinding, LogicalPosition, LogicalRange, ModelEvent, Pane, ViewEvent,
+};
 use crate::repl::models::ScreenBuffer;
 use crate::repl::models::{ClipboardYankBuffer, MemoryYankBuffer, YankBuffer};
-use crate::repl::models::{ResponseModel, StatusLine};
+use crate::repl::models::{ResponseModel, StatusLine, YankHighlight};
+use crate::repl::text::search::SearchMatches;
+use crate::repl::view_models::ex_command_manager::{CommandWindow, Wildmenu};
 use crate::repl::view_models::pane_manager::PaneManager;
+use crate::repl::view_models::search_manager::SearchPrefetch;
 // use anyhow::Result; // Currently unused
 use bluenote::HttpClient;
 use std::collections::HashMap;
@@ -29,6 +36,12 @@ use std::collections::HashMap;
 /// Type alias for event bus option to reduce complexity
 type EventBusOption = Option<Box<dyn EventBus>>;
 
+/// Last character find and the character it looked for
+type LastFind = (CharFind, char);
+
+/// Matches of the last search with the pane and display cache update count they were found at
+pub(super) type CachedSearchMatches = (Pane, usize, SearchMatches);
+
 /// Type alias for display line rendering data: (content, line_number, is_continuation, logical_start_col, logical_line)
 pub type DisplayLineData = (String, Option<usize>, bool, usize, usize);
 
@@ -51,6 +64,8 @@ pub struct ViewModel {
 
     // HTTP client and configuration
     pub(super) http_client: Option<HttpClient>,
+    /// Server of the profile, which relative request URLs are resolved against
+    pub(super) base_url: Option<String>,
     pub(super) http_session_headers: HashMap<String, String>,
 
     // Event management
@@ -64,6 +79,137 @@ pub struct ViewModel {
     // Whether clipboard integration is enabled
     pub(super) clipboard_enabled: bool,
 
+    // Whether header names are spell checked when sending a request
+    pub(super) spell_enabled: bool,
+
+    // Whether a request giving a header more than once is refused instead of sent
+    pub(super) strict_headers_enabled: bool,
+
+    // Whether `:w` backs up an existing file before overwriting it
+    pub(super) backup_enabled: bool,
+
+    // Whether `:w` overwrites a file in place instead of renaming a new copy over it
+    pub(super) backupcopy_enabled: bool,
+
+    // Whether destructive ex commands ask `(y)es/(n)o/(a)ll` before going ahead
+    pub(super) confirm_enabled: bool,
+
+    // Whether yanked text is briefly highlighted
+    pub(super) hlyank_enabled: bool,
+
+    // Text just yanked, highlighted until the flash times out
+    pub(super) yank_highlight: Option<YankHighlight>,
+
+    // Whether typing a closing bracket flashes its match
+    pub(super) showmatch_enabled: bool,
+
+    // Request buffer bracket matching the one just typed, until the flash times out
+    pub(super) match_highlight: Option<LogicalPosition>,
+
+    // Search query being typed in search mode
+    pub(super) search_buffer: String,
+
+    // Count typed before a command (the 3 in `3>` or `3fa`)
+    pub(super) pending_count: Option<usize>,
+
+    // Register chosen with `"` for the next delete, yank or paste
+    pub(super) pending_register: Option<char>,
+
+    // Hex digits typed so far after Ctrl-v u in insert mode
+    pub(super) pending_literal_code: Option<String>,
+
+    // Character find waiting for its target after f, F, t or T
+    pub(super) pending_find: CharFind,
+
+    // Last character find and its target, repeated by ; and ,
+    pub(super) last_find: Option<LastFind>,
+
+    // Ex commands executed so far, oldest first
+    pub(super) ex_command_history: Vec<String>,
+
+    // Request buffer set aside while the command-line window (q:) is open
+    pub(super) command_window: Option<CommandWindow>,
+
+    // Candidates being cycled with Tab on the command line
+    pub(super) wildmenu: Option<Wildmenu>,
+
+    // Listing shown in the scratch overlay over the panes, until closed with q
+    pub(super) scratch: Option<Vec<String>>,
+
+    // Whether the candidates are shown in a menu above the command line
+    pub(super) wildmenu_enabled: bool,
+
+    // Cursor position when search mode was entered, restored on cancel
+    pub(super) search_origin: Option<LogicalPosition>,
+
+    // Operator waiting for the search to finish, for `d/pattern`
+    pub(super) search_operator: Option<Operator>,
+
+    // Whether the search being typed, or else the last one, goes forward (`/`) or backward (`?`)
+    pub(super) search_forward: bool,
+
+    // Whether the cursor previews the match while a search query is typed
+    pub(super) incsearch_enabled: bool,
+
+    // Last pattern searched for, repeated by `n` and `N`
+    pub(super) last_search_pattern: Option<String>,
+
+    // Matches of the last pattern, with the pane and its display cache update
+    // count when they were found; any edit updates the count and so invalidates them
+    pub(super) search_matches: Option<CachedSearchMatches>,
+
+    // Matches of the last pattern still being found on a background thread
+    pub(super) search_prefetch: Option<SearchPrefetch>,
+
+    // Whether the echoed request section of a verbose response is collapsed
+    pub(super) fold_request_enabled: bool,
+
+    // Largest response body shown in the response pane, in bytes (0 for no limit)
+    pub(super) max_response_bytes: usize,
+
+    // File the request buffer is written to by :wq, :x and ZZ
+    pub(super) request_file: Option<String>,
+
+    // Whether the request buffer has changes that were not written to its file
+    pub(super) request_modified: bool,
+
+    // Text typed since Insert mode was entered, and the text of the previous
+    // insert session, inserted again by Ctrl-a
+    pub(super) insert_session_text: String,
+    pub(super) last_inserted_text: String,
+
+    // Cursor position where the current insert session started
+    pub(super) insert_session_start: LogicalPosition,
+
+    // Last change made from normal mode, made again by `.`, and whether the
+    // insert session under way still has to add its text to it
+    pub(super) last_change: Option<RecordedChange>,
+    pub(super) recording_insert: bool,
+
+    // Start and end of the last inserted or pasted text (the `[ and `] marks)
+    pub(super) change_marks: Option<LogicalRange>,
+
+    // External formatter run by `=`; empty for the internal JSON layout
+    pub(super) equalprg: String,
+
+    // Shell external commands run in; the controller fills in the default
+    pub(super) shell: String,
+
+    // Whether the terminal window title follows the profile and request
+    pub(super) title_enabled: bool,
+
+    // Window title pattern from `:set titlestring`; empty for the default title
+    pub(super) titlestring: String,
+
+    // Keys that may move across line boundaries, as `:set whichwrap` flags (e.g. `h,l`)
+    pub(super) whichwrap: String,
+
+    // Encoding `:e` and `:w` decode from and encode to, as typed in `:set fileencoding`
+    pub(super) fileencoding: String,
+
+    // Key that sends the request from normal mode (`:set executekey`)
+    pub(super) execute_key: KeyBinding,
+
     // Visual Block Insert state - tracks cursor positions for multi-cursor editing
     pub(super) visual_block_insert_cursors: Vec<LogicalPosition>,
     // Original Visual Block Insert start positions - used to prevent backspace beyond boundaries
@@ -94,12 +240,57 @@ impl ViewModel {
             pane_manager: PaneManager::new(terminal_dimensions),
             status_line: StatusLine::new(),
             http_client: None,
+            base_url: None,
             http_session_headers: HashMap::new(),
             event_bus: None,
             pending_view_events: Vec::new(),
             pending_model_events: Vec::new(),
             yank_buffer: Box::new(MemoryYankBuffer::new()),
             clipboard_enabled: false,
+            spell_enabled: false,
+            strict_headers_enabled: false,
+            backup_enabled: false,
+            backupcopy_enabled: false,
+            confirm_enabled: false,
+            hlyank_enabled: false,
+            yank_highlight: None,
+            showmatch_enabled: false,
+            match_highlight: None,
+            search_buffer: String::new(),
+            pending_count: None,
+            pending_register: None,
+            pending_literal_code: None,
+            pending_find: CharFind::Forward,
+            last_find: None,
+            ex_command_history: Vec::new(),
+            command_window: None,
+            wildmenu: None,
+            scratch: None,
+            wildmenu_enabled: true,
+            search_origin: None,
+            search_operator: None,
+            search_forward: true,
+            incsearch_enabled: false,
+            last_search_pattern: None,
+            search_matches: None,
+            search_prefetch: None,
+            fold_request_enabled: false,
+            max_response_bytes: 0,
+            request_file: None,
+            request_modified: false,
+            insert_session_text: String::new(),
+            last_inserted_text: String::new(),
+            insert_session_start: LogicalPosition::zero(),
+            last_change: None,
+            recording_insert: false,
+            change_marks: None,
+            equalprg: String::new(),
+            shell: String::new(),
+            title_enabled: false,
+            titlestring: String::new(),
+            whichwrap: String::new(),
+            fileencoding: "utf-8".to_string(),
+            execute_key: KeyBinding::ENTER,
             visual_block_insert_cursors: Vec::new(),
             visual_block_insert_start_columns: Vec::new(),
             current_screen_buffer: ScreenBuffer::new(
@@ -289,6 +480,55 @@ impl ViewModel {
         self.status_line.profile_path()
     }
 
+    /// Get the file the request buffer is associated with
+    pub fn get_request_file(&self) -> Option<&str> {
+        self.request_file.as_deref()
+    }
+
+    /// Associate the request buffer with a file
+    pub fn set_request_file(&mut self, path: Option<String>) {
+        self.request_file = path;
+    }
+
+    /// Check whether the request buffer has unsaved changes
+    pub fn is_request_modified(&self) -> bool {
+        self.request_modified
+    }
+
+    /// Mark the request buffer as modified or saved
+    pub fn set_request_modified(&mut self, modified: bool) {
+        self.request_modified = modified;
+    }
+
+    /// Describe the buffer in the current pane like Vim's Ctrl-g
+    ///
+    /// Shows the buffer name, whether it has unsaved changes, the cursor line
+    /// out of the total and how far through the buffer that line is.
+    pub fn file_info(&self) -> String {
+        let (name, modified, text) = if self.is_in_request_pane() {
+            let name = self
+                .request_file
+                .as_deref()
+                .map_or_else(|| "[No Name]".to_string(), |path| format!("\"{path}\""));
+            (name, self.request_modified, self.get_request_text())
+        } else {
+            ("[Response]".to_string(), false, self.get_response_text())
+        };
+
+        let total = text.split('\n').count();
+        let line = (self.get_cursor_position().line + 1).min(total);
+        let modified = if modified { " [Modified]" } else { "" };
+        format!(
+            "{name}{modified} line {line} of {total} --{}%--",
+            line * 100 / total
+        )
+    }
+
+    /// Show the buffer info in the status line (Ctrl-g)
+    pub fn show_file_info(&mut self) {
+        self.set_status_message(self.file_info());
+    }
+
     // === Pane Methods (Semantic Operations) ===
 
     /// Get current active pane (for backward compatibility - prefer semantic operations)
@@ -340,6 +580,12 @@ impl ViewModel {
         self.status_line.set_status_message(message);
     }
 
+    /// Show an error in the status line, formatted the same wherever it came from
+    pub fn set_error_message(&mut self, error: &AppError) {
+        self.status_line
+            .set_error_message(format!("Error: {error}"));
+    }
+
     /// Clear the status message
     pub fn clear_status_message(&mut self) {
         self.status_line.clear_status_message();
@@ -350,11 +596,41 @@ impl ViewModel {
         self.status_line.status_message()
     }
 
+    /// Get the messages shown so far, oldest first (for `:messages`)
+    pub fn message_history(&self) -> Vec<String> {
+        self.status_line
+            .message_history()
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Forget every message shown so far (`:messages clear`)
+    pub fn clear_message_history(&mut self) {
+        self.status_line.clear_message_history();
+        let _ = self.emit_view_event([ViewEvent::StatusBarUpdateRequired]);
+    }
+
+    /// Get the number of errors shown since `:messages` was last viewed
+    pub fn unseen_error_count(&self) -> usize {
+        self.status_line.unseen_error_count()
+    }
+
+    /// Mark every error as seen, once `:messages` has shown them
+    pub fn mark_errors_seen(&mut self) {
+        self.status_line.mark_errors_seen();
+        let _ = self.emit_view_event([ViewEvent::StatusBarUpdateRequired]);
+    }
+
     /// Check if display cursor position is visible in status bar
     pub fn is_display_cursor_visible(&self) -> bool {
         self.status_line.is_display_cursor_visible()
     }
 
+    /// Get the mode indicator for the status line, `None` with `noshowmode`
+    pub fn get_mode_indicator(&self) -> Option<&'static str> {
+        self.status_line.mode_indicator(self.mode())
+    }
+
     // === Editor State Management ===
 
     /// Get current editor mode from the active pane
@@ -377,7 +653,9 @@ impl ViewModel {
     pub fn get_content_width(&self) -> usize {
         // Use semantic width calculation based on current area
         let line_num_width = self.pane_manager.get_current_line_number_width();
-        (self.pane_manager.terminal_dimensions.0 as usize).saturating_sub(line_num_width + 1)
+        let scrollbar_width = self.pane_manager.scrollbar_width();
+        (self.pane_manager.terminal_dimensions.0 as usize)
+            .saturating_sub(line_num_width + 1 + scrollbar_width)
     }
 
     /// Get reference to PaneManager for pane-specific operations
@@ -474,4 +752,37 @@ mod tests {
             "Display cursor should be at beginning of second display line"
         );
     }
+
+    #[test]
+    fn request_should_be_marked_modified_after_editing() {
+        let mut vm = ViewModel::new();
+        assert!(!vm.is_request_modified());
+
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("GET /users").unwrap();
+        assert!(vm.is_request_modified());
+
+        vm.set_request_modified(false);
+        assert!(!vm.is_request_modified());
+    }
+
+    #[test]
+    fn file_info_should_report_cursor_line_and_total_lines() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("GET /users\nAccept: */*\n\n{}").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.set_cursor_position(LogicalPosition::new(1, 0)).unwrap();
+
+        assert_eq!(vm.file_info(), "[No Name] [Modified] line 2 of 4 --50%--");
+
+        vm.set_request_file(Some("users.http".to_string()));
+        vm.set_request_modified(false);
+        vm.set_cursor_position(LogicalPosition::new(3, 0)).unwrap();
+        vm.show_file_info();
+        assert_eq!(
+            vm.get_status_message(),
+            Some("\"users.http\" line 4 of 4 --100%--")
+        );
+    }
 }
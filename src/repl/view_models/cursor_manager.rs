@@ -3,34 +3,127 @@
 //! Handles all cursor movement and positioning logic using semantic operations from PaneManager.
 //! This module provides high-level cursor operations that work with the current/other area abstraction.
 
-use crate::repl::events::LogicalPosition;
+use crate::repl::commands::CharFind;
+use crate::repl::events::{EditorMode, LogicalPosition, LogicalRange};
 use crate::repl::models::geometry::Position;
+use crate::repl::text::find_char::{find_char_backward, find_char_forward};
 use crate::repl::view_models::core::ViewModel;
 use anyhow::Result;
 
 impl ViewModel {
+    /// Get the start and end of the last inserted or pasted text (`[ and `])
+    pub fn get_change_marks(&self) -> Option<LogicalRange> {
+        self.change_marks
+    }
+
+    /// Move the cursor to the start (`[) or end (`]) of the last inserted or
+    /// pasted text
+    ///
+    /// Returns `false` when nothing was inserted or pasted yet. A mark past the
+    /// end of text that was edited since is moved back onto it.
+    pub fn jump_to_change_mark(&mut self, end: bool) -> Result<bool> {
+        let Some(marks) = self.change_marks else {
+            return Ok(false);
+        };
+        if !self.is_in_request_pane() {
+            return Ok(false);
+        }
+        let mark = self.clamp_to_request_text(if end { marks.end } else { marks.start });
+        self.set_cursor_position(mark)?;
+        Ok(true)
+    }
+
+    /// Remember the cursor position in the current pane as `mark` (`ma`)
+    pub fn set_mark(&mut self, mark: char) {
+        self.pane_manager.set_current_mark(mark);
+    }
+
+    /// Move the cursor to `mark` (`` `a ``), or with `line_wise` to the first
+    /// non-blank of its line (`'a`)
+    ///
+    /// Returns `false` when the mark is not set in the current pane.
+    pub fn jump_to_mark(&mut self, mark: char, line_wise: bool) -> Result<bool> {
+        let Some(position) = self.pane_manager.get_current_mark(mark, line_wise) else {
+            return Ok(false);
+        };
+        self.set_cursor_position(position)?;
+        Ok(true)
+    }
+
+    /// Select the last inserted or pasted text in Visual mode (`[v`])
+    ///
+    /// Returns `false` when nothing was inserted or pasted yet.
+    pub fn select_last_change(&mut self) -> Result<bool> {
+        let Some(marks) = self.change_marks else {
+            return Ok(false);
+        };
+        if !self.is_in_request_pane() {
+            return Ok(false);
+        }
+        let start = self.clamp_to_request_text(marks.start);
+        let end = self.clamp_to_request_text(marks.end);
+        self.set_cursor_position(start)?;
+        self.change_mode(EditorMode::Visual)?;
+        self.set_cursor_position(end)?;
+        let events = self.pane_manager.update_visual_selection(end);
+        self.emit_view_event(events)?;
+        Ok(true)
+    }
+
+    /// Move `position` onto the nearest character of the request buffer
+    fn clamp_to_request_text(&self, position: LogicalPosition) -> LogicalPosition {
+        let text = self.get_request_text();
+        let lines: Vec<&str> = text.split('\n').collect();
+        let line = position.line.min(lines.len() - 1);
+        let column = position
+            .column
+            .min(lines[line].chars().count().saturating_sub(1));
+        LogicalPosition::new(line, column)
+    }
+
     /// Get current logical cursor position for the active area
     pub fn get_cursor_position(&self) -> LogicalPosition {
         self.pane_manager.get_current_cursor_position()
     }
 
+    /// Get the Request pane cursor position, whichever pane is active
+    pub fn get_request_cursor_position(&self) -> LogicalPosition {
+        self.pane_manager.get_request_cursor_position()
+    }
+
     /// Get current display cursor position for the active area
     pub fn get_display_cursor_position(&self) -> Position {
         self.pane_manager.get_current_display_cursor()
     }
 
     /// Move cursor left in current area
-    pub fn move_cursor_left(&mut self) -> Result<()> {
-        let events = self.pane_manager.move_cursor_left();
+    ///
+    /// From a line start the cursor only goes on to the end of the previous
+    /// line with `wrap`, which the `:set whichwrap` flags decide.
+    pub fn move_cursor_left(&mut self, wrap: bool) -> Result<()> {
+        let events = self.pane_manager.move_cursor_left(wrap);
         self.emit_view_event(events)
     }
 
     /// Move cursor right in current area
-    pub fn move_cursor_right(&mut self) -> Result<()> {
-        let events = self.pane_manager.move_cursor_right();
+    ///
+    /// From a line end the cursor only goes on to the start of the next line
+    /// with `wrap`, which the `:set whichwrap` flags decide.
+    pub fn move_cursor_right(&mut self, wrap: bool) -> Result<()> {
+        let events = self.pane_manager.move_cursor_right(wrap);
         self.emit_view_event(events)
     }
 
+    /// Get the keys allowed to move across line boundaries, as comma-separated flags
+    pub fn get_whichwrap(&self) -> &str {
+        &self.whichwrap
+    }
+
+    /// Set the keys allowed to move across line boundaries (`:set whichwrap=<flags>`)
+    pub fn set_whichwrap(&mut self, flags: &str) {
+        self.whichwrap = flags.to_string();
+    }
+
     /// Move cursor up in current area
     pub fn move_cursor_up(&mut self) -> Result<()> {
         let events = self.pane_manager.move_cursor_up();
@@ -43,6 +136,18 @@ impl ViewModel {
         self.emit_view_event(events)
     }
 
+    /// Move cursor up one display line in current area (gk)
+    pub fn move_cursor_display_line_up(&mut self) -> Result<()> {
+        let events = self.pane_manager.move_cursor_display_line_up();
+        self.emit_view_event(events)
+    }
+
+    /// Move cursor down one display line in current area (gj)
+    pub fn move_cursor_display_line_down(&mut self) -> Result<()> {
+        let events = self.pane_manager.move_cursor_display_line_down();
+        self.emit_view_event(events)
+    }
+
     /// Move cursor to end of current line
     pub fn move_cursor_to_end_of_line(&mut self) -> Result<()> {
         let events = self.pane_manager.move_cursor_to_end_of_line();
@@ -97,6 +202,69 @@ impl ViewModel {
         self.emit_view_event(events)
     }
 
+    /// Wait for the target of a character find started by `f`, `F`, `t` or `T`
+    pub fn start_find_char(&mut self, find: CharFind) {
+        self.pending_find = find;
+    }
+
+    /// Move cursor to the `count`-th `target` character on the line (`2fx`, `Fx`, `tx`, `Tx`)
+    ///
+    /// The direction and where the cursor stops come from the key that started
+    /// the find, which is remembered with `target` for `;` and `,`. Returns
+    /// whether the cursor moved; it stays put when the line has fewer
+    /// occurrences than `count`.
+    pub fn move_cursor_to_char(&mut self, target: char, count: usize) -> Result<bool> {
+        let find = std::mem::replace(&mut self.pending_find, CharFind::Forward);
+        self.last_find = Some((find, target));
+        self.move_cursor_by_find(find, target, count, false)
+    }
+
+    /// Repeat the last character find (`;`), or the same find the other way (`,`)
+    ///
+    /// Returns whether the cursor moved; nothing happens before the first find.
+    pub fn repeat_find_char(&mut self, reverse: bool, count: usize) -> Result<bool> {
+        let Some((find, target)) = self.last_find else {
+            return Ok(false);
+        };
+        let find = if reverse { find.reversed() } else { find };
+        self.move_cursor_by_find(find, target, count, true)
+    }
+
+    /// Move the cursor on its line as `find` for `target` would
+    ///
+    /// A repeated `t` or `T` skips the occurrence right next to the cursor, so
+    /// `;` moves on instead of staying where the last find stopped.
+    fn move_cursor_by_find(
+        &mut self,
+        find: CharFind,
+        target: char,
+        count: usize,
+        repeat: bool,
+    ) -> Result<bool> {
+        let cursor = self.get_cursor_position();
+        let skip = usize::from(repeat);
+        let Some(column) =
+            self.pane_manager
+                .get_current_line_content()
+                .and_then(|line| match find {
+                    CharFind::Forward => find_char_forward(&line, cursor.column, target, count),
+                    CharFind::Backward => find_char_backward(&line, cursor.column, target, count),
+                    CharFind::TillForward => {
+                        find_char_forward(&line, cursor.column + skip, target, count)
+                            .map(|column| column - 1)
+                    }
+                    CharFind::TillBackward => {
+                        find_char_backward(&line, cursor.column.saturating_sub(skip), target, count)
+                            .map(|column| column + 1)
+                    }
+                })
+        else {
+            return Ok(false);
+        };
+        self.set_cursor_position(LogicalPosition::new(cursor.line, column))?;
+        Ok(true)
+    }
+
     /// Move cursor to specific line number (1-based)
     pub fn move_cursor_to_line(&mut self, line_number: usize) -> Result<()> {
         let events = self.pane_manager.move_cursor_to_line(line_number);
@@ -8,12 +8,30 @@ use crate::repl::models::geometry::Position;
 use crate::repl::models::DisplayCache;
 use crate::repl::view_models::core::{DisplayLineData, ViewModel};
 
+/// Lines of the listing shown in the scratch overlay
+type ScratchLines<'a> = &'a [String];
+
 impl ViewModel {
     /// Get display cache for a specific pane
     pub(super) fn get_display_cache(&self, pane: Pane) -> &DisplayCache {
         self.pane_manager.get_display_cache(pane)
     }
 
+    /// Get the scroll position of a pane as (first visible display line, total display lines)
+    ///
+    /// Uses the same vertical offset as [`Self::get_display_lines_for_rendering`].
+    pub fn get_scroll_extent(&self, pane: Pane) -> (usize, usize) {
+        let scroll_row = if pane == self.pane_manager.current_pane_type() {
+            self.pane_manager.get_current_scroll_offset().row
+        } else {
+            0
+        };
+        (
+            scroll_row,
+            self.get_display_cache(pane).display_line_count(),
+        )
+    }
+
     /// Get display lines for rendering a specific pane
     /// Get display lines prepared for terminal rendering with visual selection support
     ///
@@ -142,6 +160,29 @@ impl ViewModel {
         result
     }
 
+    /// Check which ends of a line have text scrolled out of view sideways
+    ///
+    /// `visible_chars` characters of `logical_line` are on screen starting at
+    /// `start_col`. Returns whether text is hidden to the left and to the right,
+    /// which only happens in panes that do not wrap.
+    pub fn get_side_scroll_markers(
+        &self,
+        pane: Pane,
+        logical_line: usize,
+        start_col: usize,
+        visible_chars: usize,
+    ) -> (bool, bool) {
+        if self.pane_manager.is_wrap_enabled_for(pane) {
+            return (false, false);
+        }
+        let display_cache = self.get_display_cache(pane);
+        let line_chars = display_cache
+            .logical_to_display_position(logical_line, 0)
+            .and_then(|display_pos| display_cache.get_display_line(display_pos.row))
+            .map_or(0, |display_line| display_line.char_count());
+        (start_col > 0, start_col + visible_chars < line_chars)
+    }
+
     /// Get cursor position for rendering
     pub fn get_cursor_for_rendering(&self, pane: Pane) -> (usize, usize) {
         let display_pos = if pane == self.pane_manager.current_pane_type() {
@@ -179,6 +220,21 @@ impl ViewModel {
         }
         Ok(())
     }
+
+    /// Show `lines` in the scratch overlay, replacing any listing already there
+    pub fn open_scratch(&mut self, lines: Vec<String>) {
+        self.scratch = Some(lines);
+    }
+
+    /// Close the scratch overlay, uncovering the panes underneath
+    pub fn close_scratch(&mut self) {
+        self.scratch = None;
+    }
+
+    /// Get the listing shown in the scratch overlay, if it is open
+    pub fn get_scratch(&self) -> Option<ScratchLines<'_>> {
+        self.scratch.as_deref()
+    }
 }
 
 #[cfg(test)]
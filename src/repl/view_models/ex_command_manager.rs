@@ -1,12 +1,33 @@
 //! # Ex Command Management
 //!
-//! Handles ex command buffer operations and command execution.
+//! Handles ex command buffer operations, command execution, Tab completion of
+//! command arguments and the command history shown in the `q:` command-line window.
 
 use crate::repl::commands::{CommandEvent, MovementDirection};
-use crate::repl::events::ViewEvent;
+use crate::repl::events::{EditorMode, LogicalPosition, Pane, ViewEvent};
 use crate::repl::view_models::core::ViewModel;
 use anyhow::Result;
 
+/// Completion candidates shown above the command line and the selected one
+type WildmenuView<'a> = (&'a [String], usize);
+
+/// Request buffer state put aside while the command-line window borrows the pane
+#[derive(Debug, Clone)]
+pub(super) struct CommandWindow {
+    pane: Pane,
+    request_text: String,
+    cursor: LogicalPosition,
+    request_modified: bool,
+}
+
+/// Completion candidates cycled with Tab and Shift-Tab on the command line
+#[derive(Debug, Clone)]
+pub(super) struct Wildmenu {
+    candidates: Vec<String>,
+    selected: usize,
+    arg_start: usize,
+}
+
 impl ViewModel {
     /// Get ex command buffer
     pub fn get_ex_command_buffer(&self) -> &str {
@@ -16,23 +37,111 @@ impl ViewModel {
     /// Add character to ex command buffer
     pub fn add_ex_command_char(&mut self, ch: char) -> Result<()> {
         self.status_line.append_to_command_buffer(ch);
-        let _ = self.emit_view_event([ViewEvent::StatusBarUpdateRequired]);
+        self.emit_command_line_update();
         Ok(())
     }
 
     /// Remove last character from ex command buffer
     pub fn backspace_ex_command(&mut self) -> Result<()> {
         self.status_line.backspace_command_buffer();
-        let _ = self.emit_view_event([ViewEvent::StatusBarUpdateRequired]);
+        self.emit_command_line_update();
         Ok(())
     }
 
     /// Clear the ex command buffer
     pub fn clear_ex_command_buffer(&mut self) {
         self.status_line.clear_command_buffer();
+        self.emit_command_line_update();
+    }
+
+    /// Check whether Tab is cycling through completion candidates
+    pub fn is_completing(&self) -> bool {
+        self.wildmenu.is_some()
+    }
+
+    /// Get the candidates to show above the command line and the selected one
+    ///
+    /// Nothing is shown with `:set wildmenu off`; Tab still cycles in place.
+    pub fn get_wildmenu(&self) -> Option<WildmenuView<'_>> {
+        self.wildmenu
+            .as_ref()
+            .filter(|_| self.wildmenu_enabled)
+            .map(|menu| (menu.candidates.as_slice(), menu.selected))
+    }
+
+    /// Complete the command argument starting at byte `arg_start`
+    ///
+    /// A single candidate is filled in directly so the next Tab can complete
+    /// further (e.g. inside a directory). Several candidates open the menu with
+    /// the first one selected, or the last one when going backward.
+    ///
+    /// # Returns
+    /// Whether any candidate was found.
+    pub fn start_completion(
+        &mut self,
+        arg_start: usize,
+        candidates: Vec<String>,
+        forward: bool,
+    ) -> bool {
+        if candidates.is_empty() {
+            return false;
+        }
+
+        let selected = if forward { 0 } else { candidates.len() - 1 };
+        let menu = Wildmenu {
+            candidates,
+            selected,
+            arg_start,
+        };
+        self.apply_completion(&menu);
+        if menu.candidates.len() > 1 {
+            self.wildmenu = Some(menu);
+        }
+        true
+    }
+
+    /// Select the next (or previous) candidate, wrapping around at the ends
+    pub fn cycle_completion(&mut self, forward: bool) {
+        let Some(mut menu) = self.wildmenu.take() else {
+            return;
+        };
+
+        let count = menu.candidates.len();
+        menu.selected = if forward {
+            (menu.selected + 1) % count
+        } else {
+            (menu.selected + count - 1) % count
+        };
+        self.apply_completion(&menu);
+        self.wildmenu = Some(menu);
+    }
+
+    /// Replace the argument on the command line with the selected candidate
+    fn apply_completion(&mut self, menu: &Wildmenu) {
+        let command = self.status_line.command_buffer();
+        let prefix = &command[..menu.arg_start.min(command.len())];
+        let completed = format!("{prefix}{}", menu.candidates[menu.selected]);
+        self.status_line.set_command_buffer(completed);
         let _ = self.emit_view_event([ViewEvent::StatusBarUpdateRequired]);
     }
 
+    /// Close the completion menu
+    ///
+    /// # Returns
+    /// Whether a visible menu was closed, leaving its row to be redrawn.
+    pub(super) fn close_wildmenu(&mut self) -> bool {
+        self.wildmenu.take().is_some() && self.wildmenu_enabled
+    }
+
+    /// Refresh the command line after typing, which ends any completion
+    fn emit_command_line_update(&mut self) {
+        let mut events = vec![ViewEvent::StatusBarUpdateRequired];
+        if self.close_wildmenu() {
+            events.push(ViewEvent::FullRedrawRequired);
+        }
+        let _ = self.emit_view_event(events);
+    }
+
     /// Execute ex command and return resulting command events
     pub fn execute_ex_command(&mut self) -> Result<Vec<CommandEvent>> {
         let command = self.status_line.command_buffer().trim().to_string();
@@ -113,4 +222,168 @@ impl ViewModel {
 
         Ok(events)
     }
+
+    /// Remember an executed ex command, skipping blanks and immediate repeats
+    pub fn push_ex_command_history(&mut self, command: &str) {
+        let command = command.trim();
+        if command.is_empty()
+            || self
+                .ex_command_history
+                .last()
+                .is_some_and(|last| last == command)
+        {
+            return;
+        }
+        self.ex_command_history.push(command.to_string());
+    }
+
+    /// Get executed ex commands, oldest first
+    pub fn get_ex_command_history(&self) -> &[String] {
+        &self.ex_command_history
+    }
+
+    /// Check whether the request pane is showing the command-line window
+    pub fn is_command_window_open(&self) -> bool {
+        self.command_window.is_some()
+    }
+
+    /// Open the command-line window (q:) in the request pane
+    ///
+    /// The request buffer is set aside and replaced by the command history with
+    /// the cursor on the latest command, so the usual motions and edits (`o` for
+    /// a new command) work on it.
+    pub fn open_command_window(&mut self) -> Result<()> {
+        if self.command_window.is_some() {
+            return Ok(());
+        }
+
+        let pane = self.get_current_pane();
+        self.switch_to_request_pane();
+        self.command_window = Some(CommandWindow {
+            pane,
+            request_text: self.get_request_text(),
+            cursor: self.get_cursor_position(),
+            request_modified: self.request_modified,
+        });
+
+        let history = self.ex_command_history.join("\n");
+        let last_line = self.ex_command_history.len().saturating_sub(1);
+        self.set_request_text(&history)?;
+        self.change_mode(EditorMode::Normal)?;
+        self.set_cursor_position(LogicalPosition::new(last_line, 0))
+    }
+
+    /// Close the command-line window and return the line under the cursor
+    ///
+    /// The request buffer, its cursor, its modified flag and the active pane are
+    /// restored as they were before the window opened.
+    pub fn close_command_window(&mut self) -> Result<Option<String>> {
+        let Some(window) = self.command_window.take() else {
+            return Ok(None);
+        };
+
+        let line = self
+            .pane_manager
+            .get_current_line_content()
+            .unwrap_or_default();
+        self.set_request_text(&window.request_text)?;
+        self.set_cursor_position(window.cursor)?;
+        self.request_modified = window.request_modified;
+        if window.pane == Pane::Response {
+            self.switch_to_response_pane();
+        }
+
+        Ok(Some(line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_command(vm: &mut ViewModel, command: &str) {
+        vm.change_mode(EditorMode::Command).unwrap();
+        for ch in command.chars() {
+            vm.add_ex_command_char(ch).unwrap();
+        }
+    }
+
+    #[test]
+    fn completion_should_cycle_candidates_in_both_directions() {
+        let mut vm = ViewModel::new();
+        type_command(&mut vm, "w re");
+        let candidates = vec!["req.http".to_string(), "res.http".to_string()];
+
+        assert!(vm.start_completion(2, candidates.clone(), true));
+        assert_eq!(vm.get_ex_command_buffer(), "w req.http");
+        assert_eq!(vm.get_wildmenu(), Some((candidates.as_slice(), 0)));
+
+        vm.cycle_completion(true);
+        assert_eq!(vm.get_ex_command_buffer(), "w res.http");
+        assert_eq!(vm.get_wildmenu(), Some((candidates.as_slice(), 1)));
+
+        vm.cycle_completion(true);
+        assert_eq!(vm.get_wildmenu(), Some((candidates.as_slice(), 0)));
+        vm.cycle_completion(false);
+        assert_eq!(vm.get_ex_command_buffer(), "w res.http");
+
+        // Typing ends the completion
+        vm.add_ex_command_char('x').unwrap();
+        assert!(!vm.is_completing());
+        assert_eq!(vm.get_wildmenu(), None);
+    }
+
+    #[test]
+    fn completion_should_fill_in_single_candidate_without_menu() {
+        let mut vm = ViewModel::new();
+        type_command(&mut vm, "w lo");
+
+        assert!(!vm.start_completion(2, Vec::new(), true));
+        assert_eq!(vm.get_ex_command_buffer(), "w lo");
+
+        assert!(vm.start_completion(2, vec!["logs/".to_string()], true));
+        assert_eq!(vm.get_ex_command_buffer(), "w logs/");
+        assert!(!vm.is_completing());
+    }
+
+    #[test]
+    fn history_should_skip_blank_and_repeated_commands() {
+        let mut vm = ViewModel::new();
+
+        vm.push_ex_command_history("set wrap");
+        vm.push_ex_command_history("set wrap");
+        vm.push_ex_command_history("  ");
+        vm.push_ex_command_history("set nu");
+        vm.push_ex_command_history("set wrap");
+
+        assert_eq!(
+            vm.get_ex_command_history(),
+            ["set wrap", "set nu", "set wrap"]
+        );
+    }
+
+    #[test]
+    fn command_window_should_list_history_and_restore_request() {
+        let mut vm = ViewModel::new();
+        vm.set_request_text("GET /users\n\n{}").unwrap();
+        vm.set_cursor_position(LogicalPosition::new(2, 1)).unwrap();
+        vm.set_request_modified(false);
+        vm.push_ex_command_history("set wrap");
+        vm.push_ex_command_history("set nu");
+
+        vm.open_command_window().unwrap();
+        assert!(vm.is_command_window_open());
+        assert_eq!(vm.get_request_text(), "set wrap\nset nu");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(1, 0));
+
+        vm.set_cursor_position(LogicalPosition::new(0, 0)).unwrap();
+        assert_eq!(
+            vm.close_command_window().unwrap().as_deref(),
+            Some("set wrap")
+        );
+        assert!(!vm.is_command_window_open());
+        assert_eq!(vm.get_request_text(), "GET /users\n\n{}");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(2, 1));
+        assert!(!vm.is_request_modified());
+    }
 }
@@ -3,14 +3,23 @@
 //! Handles HTTP client configuration, request execution, and response management.
 
 // Pane import removed - using semantic operations instead
+use crate::repl::events::{KeyBinding, LogicalPosition, ViewEvent};
+use crate::repl::text::content_type::format_body;
+use crate::repl::text::header_lint::{find_duplicate_headers, lint_headers};
+use crate::repl::text::json_path::{json_path_at, json_scalar_at};
+use crate::repl::text::request_line::{request_target, resolve_url, RequestTarget};
+use crate::repl::text::response_limit::truncate_body;
+use crate::repl::text::response_sections::fold_request_echo;
 use crate::repl::view_models::core::ViewModel;
 use anyhow::Result;
 use bluenote::{HttpClient, HttpConnectionProfile};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 impl ViewModel {
     /// Set HTTP client from profile
     pub fn set_http_client(&mut self, profile: &impl HttpConnectionProfile) -> Result<()> {
+        self.base_url = profile.server().map(|server| server.to_string());
         let client = HttpClient::new(profile)?;
         self.http_client = Some(client);
         tracing::debug!("HTTP client configured with profile");
@@ -22,6 +31,16 @@ impl ViewModel {
         self.http_client.as_ref()
     }
 
+    /// Get the key that sends the request from normal mode
+    pub fn get_execute_key(&self) -> KeyBinding {
+        self.execute_key
+    }
+
+    /// Set the key that sends the request from normal mode (`:set executekey=<key>`)
+    pub fn set_execute_key(&mut self, key: KeyBinding) {
+        self.execute_key = key;
+    }
+
     /// Get current request execution status
     pub fn is_executing_request(&self) -> bool {
         self.status_line.is_executing()
@@ -49,6 +68,17 @@ impl ViewModel {
         self.pane_manager.get_request_text()
     }
 
+    /// Replace the whole request buffer and put the cursor at its start
+    pub fn set_request_text(&mut self, text: &str) -> Result<()> {
+        let mut events = self.pane_manager.set_request_content(text);
+        events.extend(self.pane_manager.rebuild_display_caches_and_sync());
+        events.push(ViewEvent::FullRedrawRequired);
+        self.emit_view_event(events)?;
+
+        self.switch_to_request_pane();
+        self.set_cursor_position(LogicalPosition::zero())
+    }
+
     /// Set response from HTTP response
     pub fn set_response_from_http(&mut self, response: &bluenote::HttpResponse) {
         let status_code = response.status().as_u16();
@@ -58,19 +88,25 @@ impl ViewModel {
             .unwrap_or("")
             .to_string();
         let duration_ms = response.duration_ms();
-        let body = response.body().to_string();
+        let content_type = response
+            .headers()
+            .get("content-type")
+            .and_then(|value| value.to_str().ok());
+        let body = format_body(response.body(), content_type);
 
         self.response.set_status_code(status_code);
         self.response.set_status_message(status_message.clone());
         self.response.set_duration_ms(duration_ms);
-        self.response.set_body(body.clone());
 
         // Update status line with HTTP status
         self.status_line
             .set_http_status(status_code, status_message, duration_ms);
 
-        // Update response buffer content using semantic operation
-        let _events = self.pane_manager.set_response_content(&body);
+        // Fill the response buffer first so the body can then be moved, not cloned, into the model
+        let _events = self
+            .pane_manager
+            .set_response_content(&self.response_display_text(&body));
+        self.response.set_body(body);
 
         // Response content setting already resets cursor and scroll positions
 
@@ -94,10 +130,13 @@ impl ViewModel {
     /// Set response with status code and content
     pub fn set_response(&mut self, status_code: u16, content: String) {
         self.response.set_status_code(status_code);
-        self.response.set_body(content.clone());
+        let content_length = content.len();
 
-        // Update response buffer using semantic operation
-        let _events = self.pane_manager.set_response_content(&content);
+        // Update response buffer using semantic operation, then move the body into the model
+        let _events = self
+            .pane_manager
+            .set_response_content(&self.response_display_text(&content));
+        self.response.set_body(content);
 
         // Recalculate pane dimensions now that we have a response
         let (width, height) = self.pane_manager.terminal_dimensions;
@@ -111,7 +150,7 @@ impl ViewModel {
         tracing::debug!(
             "Response set: status={}, content_length={}",
             status_code,
-            content.len()
+            content_length
         );
     }
 
@@ -130,8 +169,467 @@ impl ViewModel {
         self.response.duration_ms()
     }
 
+    /// Get the body of the last response, if there is one
+    ///
+    /// Unlike [`Self::get_response_text`] this is never folded.
+    pub fn get_response_body(&self) -> Option<&str> {
+        self.response.status_code().map(|_| self.response.body())
+    }
+
     /// Get response text content
     pub fn get_response_text(&self) -> String {
         self.pane_manager.get_response_text()
     }
+
+    /// Check whether header names are spell checked when sending a request
+    pub fn is_spell_enabled(&self) -> bool {
+        self.spell_enabled
+    }
+
+    /// Check whether a request giving a header more than once is refused
+    pub fn is_strict_headers_enabled(&self) -> bool {
+        self.strict_headers_enabled
+    }
+
+    /// Method of the request in the request buffer, if the first line has one
+    ///
+    /// Follows the request parser: the first word of a `METHOD URL` line, uppercased.
+    pub fn get_request_method(&self) -> Option<String> {
+        let text = self.get_request_text();
+        let mut parts = text.lines().next()?.split_whitespace();
+        let method = parts.next()?;
+        parts.next()?;
+        Some(method.to_uppercase())
+    }
+
+    /// URL of the request in the request buffer, the second word of its first line
+    pub fn get_request_url(&self) -> Option<String> {
+        let text = self.get_request_text();
+        let mut parts = text.lines().next()?.split_whitespace();
+        parts.next()?;
+        parts.next().map(str::to_string)
+    }
+
+    /// Where the request in the request buffer would be sent, for the header above the request pane
+    ///
+    /// The method and the URL resolved against the profile's server, or `None`
+    /// when the request line does not parse.
+    pub fn get_request_target(&self) -> Option<RequestTarget> {
+        let target = request_target(&self.get_request_text())?;
+        Some(RequestTarget {
+            url: resolve_url(self.base_url.as_deref(), &target.url),
+            ..target
+        })
+    }
+
+    /// Check whether the terminal window title is set (`:set title on`)
+    pub fn is_title_enabled(&self) -> bool {
+        self.title_enabled
+    }
+
+    /// Get the window title pattern, empty for the default title
+    pub fn get_titlestring(&self) -> &str {
+        &self.titlestring
+    }
+
+    /// Set the window title pattern (`:set titlestring=<pattern>`)
+    pub fn set_titlestring(&mut self, titlestring: &str) {
+        self.titlestring = titlestring.to_string();
+    }
+
+    /// Terminal window title for the profile and request, if `title` is on
+    ///
+    /// Without a `titlestring` this is `blueline - <profile> - <METHOD> <URL>`.
+    /// In a `titlestring`, `%p` stands for the profile, `%m` for the method,
+    /// `%u` for the URL and `%%` for a literal `%`. Control characters are
+    /// left out so the request cannot end the escape sequence early.
+    pub fn get_window_title(&self) -> Option<String> {
+        if !self.title_enabled {
+            return None;
+        }
+        let profile = self.get_profile_name().to_string();
+        let method = self.get_request_method().unwrap_or_default();
+        let url = self.get_request_url().unwrap_or_default();
+
+        let title = if self.titlestring.is_empty() {
+            if method.is_empty() {
+                format!("blueline - {profile}")
+            } else {
+                format!("blueline - {profile} - {method} {url}")
+            }
+        } else {
+            let mut title = String::new();
+            let mut chars = self.titlestring.chars();
+            while let Some(ch) = chars.next() {
+                if ch != '%' {
+                    title.push(ch);
+                    continue;
+                }
+                match chars.next() {
+                    Some('p') => title.push_str(&profile),
+                    Some('m') => title.push_str(&method),
+                    Some('u') => title.push_str(&url),
+                    Some(other) => title.push(other),
+                    None => title.push('%'),
+                }
+            }
+            title
+        };
+        Some(title.chars().filter(|ch| !ch.is_control()).collect())
+    }
+
+    /// Check whether `:w` backs up an existing file before overwriting it
+    pub fn is_backup_enabled(&self) -> bool {
+        self.backup_enabled
+    }
+
+    /// Check whether `:w` overwrites a file in place (`backupcopy=yes`)
+    ///
+    /// Off by default, in which case the buffer goes to a temporary file that
+    /// is renamed over the target.
+    pub fn is_backupcopy_enabled(&self) -> bool {
+        self.backupcopy_enabled
+    }
+
+    /// Check whether destructive ex commands ask before going ahead
+    pub fn is_confirm_enabled(&self) -> bool {
+        self.confirm_enabled
+    }
+
+    /// Check whether yanked text is briefly highlighted
+    pub fn is_hlyank_enabled(&self) -> bool {
+        self.hlyank_enabled
+    }
+
+    /// Check whether typing a closing bracket flashes its match
+    pub fn is_showmatch_enabled(&self) -> bool {
+        self.showmatch_enabled
+    }
+
+    /// Check whether the echoed request section of a response is collapsed
+    pub fn is_fold_request_enabled(&self) -> bool {
+        self.fold_request_enabled
+    }
+
+    /// Collapse or expand the echoed request section in the response pane
+    pub fn set_fold_request_enabled(&mut self, enabled: bool) {
+        self.fold_request_enabled = enabled;
+        self.refresh_response_display();
+    }
+
+    /// Get the largest response body shown in the response pane, in bytes (0 for no limit)
+    pub fn get_max_response_bytes(&self) -> usize {
+        self.max_response_bytes
+    }
+
+    /// Limit the response body shown in the response pane to `bytes` (0 for no limit)
+    ///
+    /// Only the display is cut; the full body stays available for `:save`.
+    pub fn set_max_response_bytes(&mut self, bytes: usize) {
+        self.max_response_bytes = bytes;
+        self.refresh_response_display();
+    }
+
+    /// Show the current response again after a display setting changed
+    fn refresh_response_display(&mut self) {
+        if self.response.status_code().is_none() {
+            return;
+        }
+
+        let text = self.response_display_text(self.response.body());
+        let mut events = self.pane_manager.set_response_content(&text);
+        events.extend(self.pane_manager.rebuild_display_caches_and_sync());
+        events.push(ViewEvent::FullRedrawRequired);
+        let _ = self.emit_view_event(events);
+    }
+
+    /// Response body as shown in the response pane
+    ///
+    /// Borrows the body when it is shown as is, so large responses are not copied.
+    fn response_display_text<'a>(&self, body: &'a str) -> Cow<'a, str> {
+        let body = truncate_body(body, self.max_response_bytes);
+        if !self.fold_request_enabled {
+            return body;
+        }
+        match body {
+            Cow::Borrowed(text) => fold_request_echo(text),
+            Cow::Owned(text) => Cow::Owned(fold_request_echo(&text).into_owned()),
+        }
+    }
+
+    /// Describe the first unknown header name in the request buffer, if any
+    ///
+    /// Extra warnings are summarized as a count so the status line stays one line.
+    pub fn header_lint_warning(&self) -> Option<String> {
+        let warnings = lint_headers(&self.get_request_text());
+        let first = warnings.first()?;
+        match warnings.len() {
+            1 => Some(first.message()),
+            count => Some(format!("{} (+{} more)", first.message(), count - 1)),
+        }
+    }
+
+    /// Describe the first header given more than once in the request buffer, if any
+    ///
+    /// Extra duplicates are summarized as a count, like [`Self::header_lint_warning`].
+    pub fn duplicate_header_warning(&self) -> Option<String> {
+        let duplicates = find_duplicate_headers(&self.get_request_text());
+        let first = duplicates.first()?;
+        match duplicates.len() {
+            1 => Some(first.message()),
+            count => Some(format!("{} (+{} more)", first.message(), count - 1)),
+        }
+    }
+
+    /// Show the JSON path of the response key under the cursor in the status line
+    ///
+    /// Deeply nested JSON responses are hard to navigate by eye. Surfacing the path
+    /// (e.g. `.users[2].email`) of the key the cursor rests on tells the user exactly
+    /// where they are without counting braces. The path is not kept for `:messages`,
+    /// and moving off a key clears it but leaves other messages alone.
+    pub fn show_json_path_at_cursor(&mut self) {
+        if !self.is_in_response_pane() {
+            return;
+        }
+
+        // The path only depends on the text up to the cursor, so the rest of a
+        // large response is not copied on every cursor move
+        let cursor = self.get_cursor_position();
+        let text = self.pane_manager.get_response_text_through(cursor.line);
+        match json_path_at(&text, cursor.line, cursor.column) {
+            Some(path) => self.status_line.set_transient_message(path),
+            None => self.status_line.clear_transient_message(),
+        }
+        let _ = self.emit_view_event([ViewEvent::StatusBarUpdateRequired]);
+    }
+
+    /// Get the JSON key or value under the cursor in the response pane
+    ///
+    /// Strings come back unquoted; numbers, booleans and `null` as written.
+    pub fn json_value_at_cursor(&self) -> Option<String> {
+        if !self.is_in_response_pane() {
+            return None;
+        }
+
+        let cursor = self.get_cursor_position();
+        json_scalar_at(&self.get_response_text(), cursor.line, cursor.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repl::commands::{Setting, SettingValue};
+    use crate::repl::events::EditorMode;
+
+    #[test]
+    fn show_json_path_at_cursor_should_set_status_message_for_key() {
+        let mut vm = ViewModel::new();
+        vm.set_response(
+            200,
+            "{\n  \"user\": {\"email\": \"a@example.com\"}\n}".to_string(),
+        );
+        vm.switch_to_response_pane();
+        vm.set_cursor_position(LogicalPosition::new(1, 14)).unwrap();
+
+        vm.show_json_path_at_cursor();
+
+        assert_eq!(vm.get_status_message(), Some(".user.email"));
+        assert!(vm.message_history().is_empty());
+
+        // Moving off the key takes the path away
+        vm.set_cursor_position(LogicalPosition::new(2, 0)).unwrap();
+        vm.show_json_path_at_cursor();
+        assert_eq!(vm.get_status_message(), None);
+    }
+
+    #[test]
+    fn window_title_should_follow_profile_request_and_titlestring() {
+        let mut vm = ViewModel::new();
+        vm.set_profile_info("staging".to_string(), "~/.blueline/profile".to_string());
+        vm.set_request_text("post /users\n\n{}").unwrap();
+        assert_eq!(vm.get_window_title(), None);
+
+        vm.apply_setting(Setting::Title, SettingValue::On).unwrap();
+        assert_eq!(
+            vm.get_window_title().as_deref(),
+            Some("blueline - staging - POST /users")
+        );
+
+        vm.set_titlestring("%m %u (%p) 100%%");
+        assert_eq!(
+            vm.get_window_title().as_deref(),
+            Some("POST /users (staging) 100%")
+        );
+    }
+
+    #[test]
+    fn header_lint_warning_should_suggest_closest_header() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("POST /users\nContent-Typ: application/json")
+            .unwrap();
+
+        assert_eq!(
+            vm.header_lint_warning().as_deref(),
+            Some("Unknown header 'Content-Typ' on line 2, did you mean 'Content-Type'?")
+        );
+    }
+
+    #[test]
+    fn header_lint_warning_should_be_none_for_valid_headers() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("POST /users\nContent-Type: application/json")
+            .unwrap();
+
+        assert_eq!(vm.header_lint_warning(), None);
+    }
+
+    #[test]
+    fn duplicate_header_warning_should_report_repeated_content_type() {
+        let mut vm = ViewModel::new();
+        vm.set_request_text(
+            "POST /users\nContent-Type: application/json\nContent-Type: text/plain",
+        )
+        .unwrap();
+
+        assert_eq!(
+            vm.duplicate_header_warning().as_deref(),
+            Some("Duplicate header 'Content-Type' on line 3 (first on line 2)")
+        );
+
+        vm.set_request_text("POST /users\nContent-Type: application/json\nAccept: */*")
+            .unwrap();
+        assert_eq!(vm.duplicate_header_warning(), None);
+    }
+
+    #[test]
+    fn set_request_text_should_replace_buffer_and_reset_cursor() {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("GET /old\nmore").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+
+        vm.set_request_text("POST /login\n\n{}").unwrap();
+
+        assert_eq!(vm.get_request_text(), "POST /login\n\n{}");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::zero());
+    }
+
+    #[test]
+    fn fold_request_should_toggle_request_echo_in_response_pane() {
+        let verbose = "Request: GET /users\nHeaders:\n  Accept: */*\n\nResponse: 200 OK\n\n[]";
+        let mut vm = ViewModel::new();
+        vm.set_response(200, verbose.to_string());
+
+        vm.set_fold_request_enabled(true);
+        assert_eq!(
+            vm.get_response_text(),
+            "+-- 4 lines: Request: GET /users\nResponse: 200 OK\n\n[]"
+        );
+
+        vm.set_fold_request_enabled(false);
+        assert_eq!(vm.get_response_text(), verbose);
+    }
+
+    #[test]
+    fn fold_request_should_apply_to_new_responses() {
+        let mut vm = ViewModel::new();
+        vm.set_fold_request_enabled(true);
+
+        vm.set_response(200, "Request: GET /\n\nResponse: 200 OK\n\nok".to_string());
+
+        assert_eq!(
+            vm.get_response_text(),
+            "+-- 2 lines: Request: GET /\nResponse: 200 OK\n\nok"
+        );
+    }
+
+    #[test]
+    fn get_request_method_should_parse_first_line() {
+        let mut vm = ViewModel::new();
+        assert_eq!(vm.get_request_method(), None);
+
+        vm.set_request_text("post /users\n\n{}").unwrap();
+        assert_eq!(vm.get_request_method().as_deref(), Some("POST"));
+
+        vm.set_request_text("/users").unwrap();
+        assert_eq!(vm.get_request_method(), None);
+    }
+
+    #[test]
+    fn get_request_target_should_resolve_url_against_profile_and_follow_edits() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = temp_dir.path().join("profile");
+        std::fs::write(&store, "[staging]\nhost = https://staging.example.com\n").unwrap();
+        let profile = bluenote::IniProfileStore::new(store.to_str().unwrap())
+            .get_profile("staging")
+            .unwrap()
+            .unwrap();
+        let mut vm = ViewModel::new();
+        vm.set_http_client(&profile).unwrap();
+
+        vm.set_request_text("get /users\n\n{}").unwrap();
+        assert_eq!(
+            vm.get_request_target(),
+            Some(RequestTarget {
+                method: "GET".to_string(),
+                url: "https://staging.example.com/users".to_string(),
+            })
+        );
+
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.set_cursor_position(LogicalPosition::new(0, 5)).unwrap();
+        vm.insert_text("v1/").unwrap();
+        assert_eq!(
+            vm.get_request_target().map(|target| target.url).as_deref(),
+            Some("https://staging.example.com/v1/users")
+        );
+
+        vm.set_request_text("DELETE").unwrap();
+        assert_eq!(vm.get_request_target(), None);
+    }
+
+    #[test]
+    fn show_json_path_at_cursor_should_ignore_request_pane() {
+        let mut vm = ViewModel::new();
+        vm.set_response(200, "{\"user\": 1}".to_string());
+
+        vm.show_json_path_at_cursor();
+
+        assert_eq!(vm.get_status_message(), None);
+    }
+
+    #[test]
+    fn max_response_bytes_should_truncate_display_but_keep_body() {
+        let mut vm = ViewModel::new();
+        vm.apply_setting(Setting::MaxResponse, SettingValue::Number(8))
+            .unwrap();
+        vm.set_response(200, "{\"items\": [1, 2, 3]}".to_string());
+
+        assert_eq!(
+            vm.get_response_text(),
+            "{\"items\"\n… (truncated, 20 bytes total)"
+        );
+        assert_eq!(vm.get_response_body(), Some("{\"items\": [1, 2, 3]}"));
+
+        vm.apply_setting(Setting::MaxResponse, SettingValue::Number(0))
+            .unwrap();
+        assert_eq!(vm.get_response_text(), "{\"items\": [1, 2, 3]}");
+    }
+
+    #[test]
+    fn set_response_should_keep_large_body_intact() {
+        let mut vm = ViewModel::new();
+        let body = (0..10_000)
+            .map(|i| format!("{{\"id\": {i}, \"name\": \"ユーザー {i}\"}}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        vm.set_response(200, body.clone());
+
+        assert_eq!(vm.get_response_body(), Some(body.as_str()));
+        assert_eq!(vm.get_response_text(), body);
+    }
 }
@@ -14,6 +14,7 @@ mod mode_manager;
 mod pane_manager;
 mod pane_state;
 mod rendering_coordinator;
+mod search_manager;
 // screen_buffer moved to models/
 // selection moved to models/
 mod settings_manager;
@@ -26,5 +27,8 @@ pub use core::ViewModel;
 pub use core::DisplayLineData;
 pub use pane_manager::PaneManager;
 pub use pane_state::PaneState;
+pub use settings_manager::on_off;
 // Selection and YankBuffer types now imported from models
-pub use crate::repl::models::{Selection, YankEntry, YankType};
+pub use crate::repl::models::{
+    Selection, YankEntry, YankType, BLACK_HOLE_REGISTER, FILE_NAME_REGISTER, LAST_COMMAND_REGISTER,
+};
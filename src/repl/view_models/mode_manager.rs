@@ -2,7 +2,9 @@
 //!
 //! Handles editor mode transitions, visual mode selection state, and mode-related operations.
 
-use crate::repl::events::{EditorMode, LogicalPosition, Pane, ViewEvent};
+use crate::repl::commands::VISUAL_RANGE;
+use crate::repl::events::{EditorMode, LogicalPosition, LogicalRange, Pane, ViewEvent};
+use crate::repl::models::LineSpan;
 use crate::repl::view_models::core::ViewModel;
 use anyhow::Result;
 
@@ -28,12 +30,31 @@ impl ViewModel {
     pub fn change_mode(&mut self, mode: EditorMode) -> Result<()> {
         // Get current mode from the active pane
         let old_mode = self.pane_manager.get_current_pane_mode();
+        // Cursor before leaving Insert mode pulls it back, for the `]` mark
+        let old_cursor = self.pane_manager.get_current_cursor_position();
         tracing::debug!(
             "Changing mode from {:?} to {:?} for current pane",
             old_mode,
             mode
         );
 
+        let entering_visual_mode = matches!(
+            mode,
+            EditorMode::Visual | EditorMode::VisualLine | EditorMode::VisualBlock
+        );
+        let exiting_visual_mode = matches!(
+            old_mode,
+            EditorMode::Visual | EditorMode::VisualLine | EditorMode::VisualBlock
+        );
+
+        // Exiting visual mode to non-visual mode ends the selection while the
+        // pane is still in the visual mode, so `gv` (and `gU` after `g`) know
+        // which kind of selection to bring back
+        let mut selection_events = Vec::new();
+        if exiting_visual_mode && !entering_visual_mode {
+            selection_events = self.pane_manager.end_visual_selection();
+        }
+
         // Set mode for the current pane
         self.pane_manager.set_current_pane_mode(mode);
 
@@ -69,7 +90,7 @@ impl ViewModel {
                         );
 
                         // Pull cursor back by moving left
-                        let pullback_events = self.pane_manager.move_cursor_left();
+                        let pullback_events = self.pane_manager.move_cursor_left(false);
                         mode_change_events.extend(pullback_events);
 
                         // After cursor pullback, check if we need to pull back horizontal scrolling too
@@ -97,25 +118,49 @@ impl ViewModel {
             }
         }
 
+        // Ctrl-r and Ctrl-v keep the insert session going while they wait for a key
+        let is_insert_session = |mode: EditorMode| {
+            matches!(
+                mode,
+                EditorMode::Insert
+                    | EditorMode::InsertRegisterPrefix
+                    | EditorMode::InsertLiteralPrefix
+            )
+        };
+        if is_insert_session(old_mode) && !is_insert_session(mode) && self.recording_insert {
+            self.recording_insert = false;
+            if let Some(change) = self.last_change.as_mut() {
+                change.inserted_text = Some(self.insert_session_text.clone());
+            }
+        }
+        if !is_insert_session(old_mode) && is_insert_session(mode) {
+            self.insert_session_text.clear();
+            self.insert_session_start = old_cursor;
+        } else if is_insert_session(old_mode)
+            && !is_insert_session(mode)
+            && !self.insert_session_text.is_empty()
+        {
+            // Sessions that typed nothing (like the ones pastes run in) keep the
+            // previous text and marks
+            let end = LogicalPosition::new(old_cursor.line, old_cursor.column.saturating_sub(1));
+            self.change_marks = Some(LogicalRange::new(self.insert_session_start, end));
+            self.last_inserted_text = std::mem::take(&mut self.insert_session_text);
+        }
+
         // Update status line mode
         self.status_line.set_editor_mode(mode);
 
         // Clear command buffer when exiting Command mode (e.g., when pressing Escape)
+        let mut wildmenu_closed = false;
         if old_mode == EditorMode::Command && mode != EditorMode::Command {
             self.status_line.clear_command_buffer();
+            wildmenu_closed = self.close_wildmenu();
             tracing::debug!("Cleared command buffer when exiting Command mode");
         }
 
         // Handle visual mode selection state using PaneManager
         let mut events = mode_change_events; // Start with any cursor pullback events
-        let entering_visual_mode = matches!(
-            mode,
-            EditorMode::Visual | EditorMode::VisualLine | EditorMode::VisualBlock
-        );
-        let exiting_visual_mode = matches!(
-            old_mode,
-            EditorMode::Visual | EditorMode::VisualLine | EditorMode::VisualBlock
-        );
+        events.extend(selection_events);
 
         if entering_visual_mode && !exiting_visual_mode {
             // Entering any visual mode from non-visual mode
@@ -124,14 +169,27 @@ impl ViewModel {
                 events.extend(self.pane_manager.start_visual_selection());
             }
         } else if exiting_visual_mode && !entering_visual_mode {
-            // Exiting visual mode to non-visual mode
-            events.extend(self.pane_manager.end_visual_selection());
+            // Like Vim, `:` from a selection starts a command on the selected lines
+            if mode == EditorMode::Command {
+                self.status_line
+                    .set_command_buffer(VISUAL_RANGE.to_string());
+            }
         }
         // Note: switching between visual modes (v ↔ V ↔ Ctrl+V) maintains selection
 
-        // Add standard mode change events
+        // Without a status line the command line overlays the last content row,
+        // as does the completion menu, so the panes have to be redrawn once it is gone
+        let leaving_command_line = matches!(old_mode, EditorMode::Command | EditorMode::Search)
+            && !matches!(mode, EditorMode::Command | EditorMode::Search);
+        if leaving_command_line && (!self.pane_manager.is_status_line_visible() || wildmenu_closed)
+        {
+            events.push(ViewEvent::FullRedrawRequired);
+        }
+
+        // Add standard mode change events; line numbers are colored by mode
         events.extend([
             ViewEvent::StatusBarUpdateRequired,
+            ViewEvent::GutterRedrawRequired,
             ViewEvent::ActiveCursorUpdateRequired,
         ]);
 
@@ -145,11 +203,37 @@ impl ViewModel {
         Ok(())
     }
 
+    /// Get the number of display columns spanned by the current visual block selection
+    ///
+    /// The widest row decides, so a block of double-width characters pastes
+    /// as wide as it is shown.
+    pub fn get_visual_block_width(&self) -> usize {
+        match self.pane_manager.get_visual_selection() {
+            (Some(start), Some(end), _) => {
+                let left = start.column.min(end.column);
+                let right = start.column.max(end.column);
+                (start.line.min(end.line)..=start.line.max(end.line))
+                    .map(|line| {
+                        self.pane_manager
+                            .get_current_columns_width(line, left, right)
+                    })
+                    .max()
+                    .unwrap_or(0)
+            }
+            _ => 0,
+        }
+    }
+
     /// Get visual selection state
     pub fn get_visual_selection(&self) -> VisualSelectionState {
         self.pane_manager.get_visual_selection()
     }
 
+    /// Get the first and last line of the last visual selection (`'<,'>`)
+    pub fn get_last_visual_line_range(&self) -> Option<LineSpan> {
+        self.pane_manager.get_last_visual_line_range()
+    }
+
     /// Check if there's an active visual selection
     pub fn has_visual_selection(&self) -> bool {
         self.pane_manager.has_visual_selection()
@@ -176,4 +260,132 @@ impl ViewModel {
         self.emit_view_event(events)?;
         Ok(())
     }
+
+    /// Get the count typed so far, if any
+    pub fn get_pending_count(&self) -> Option<usize> {
+        self.pending_count
+    }
+
+    /// Append a digit to the pending count
+    pub fn push_count_digit(&mut self, digit: usize) {
+        let count = self.pending_count.unwrap_or(0);
+        self.pending_count = Some(count.saturating_mul(10).saturating_add(digit));
+        let _ = self.emit_view_event([ViewEvent::StatusBarUpdateRequired]);
+    }
+
+    /// Forget the pending count once a command has consumed it
+    pub fn clear_pending_count(&mut self) {
+        if self.pending_count.take().is_some() {
+            let _ = self.emit_view_event([ViewEvent::StatusBarUpdateRequired]);
+        }
+    }
+
+    /// Get the register chosen for the next delete, yank or paste, if any
+    pub fn get_pending_register(&self) -> Option<char> {
+        self.pending_register
+    }
+
+    /// Use `register` for the next delete, yank or paste
+    pub fn select_register(&mut self, register: char) {
+        self.pending_register = Some(register);
+        let _ = self.emit_view_event([ViewEvent::StatusBarUpdateRequired]);
+    }
+
+    /// Forget the chosen register once a command has run
+    pub fn clear_pending_register(&mut self) {
+        if self.pending_register.take().is_some() {
+            let _ = self.emit_view_event([ViewEvent::StatusBarUpdateRequired]);
+        }
+    }
+
+    /// Check whether Normal mode is waiting for the second key of a sequence
+    pub fn is_awaiting_key_sequence(&self) -> bool {
+        matches!(
+            self.mode(),
+            EditorMode::GPrefix
+                | EditorMode::DPrefix
+                | EditorMode::YPrefix
+                | EditorMode::ZPrefix
+                | EditorMode::ScrollPrefix
+                | EditorMode::QPrefix
+                | EditorMode::CPrefix
+                | EditorMode::FPrefix
+                | EditorMode::DFPrefix
+                | EditorMode::DAPrefix
+                | EditorMode::RegisterPrefix
+                | EditorMode::EqualPrefix
+                | EditorMode::MarkPrefix
+                | EditorMode::MarkLinePrefix
+                | EditorMode::SetMarkPrefix
+                | EditorMode::IndentPrefix
+                | EditorMode::DedentPrefix
+                | EditorMode::LowerCasePrefix
+                | EditorMode::UpperCasePrefix
+                | EditorMode::ToggleCasePrefix
+        )
+    }
+
+    /// Give up on a key sequence that timed out, leaving the first key to act alone
+    ///
+    /// None of the prefix keys does anything by itself, so the sequence
+    /// resolves to a no-op back in Normal mode.
+    ///
+    /// # Returns
+    /// Whether a pending sequence was abandoned.
+    pub fn resolve_pending_key_sequence(&mut self) -> Result<bool> {
+        if !self.is_awaiting_key_sequence() {
+            return Ok(false);
+        }
+        self.clear_pending_count();
+        self.clear_pending_register();
+        self.change_mode(EditorMode::Normal)?;
+        Ok(true)
+    }
+
+    /// Keys typed towards a command that is not complete yet (Vim's showcmd)
+    ///
+    /// Combines the chosen register and the pending count with the prefix key
+    /// of the current mode, so the segment is empty again as soon as the
+    /// command completes.
+    pub fn get_pending_keys(&self) -> String {
+        let prefix = match self.mode() {
+            EditorMode::GPrefix => "g",
+            EditorMode::DPrefix => "d",
+            EditorMode::YPrefix => "y",
+            EditorMode::ZPrefix => "Z",
+            EditorMode::ScrollPrefix => "z",
+            EditorMode::QPrefix => "q",
+            EditorMode::CPrefix => "c",
+            EditorMode::FPrefix => self.pending_find.key(),
+            EditorMode::DFPrefix => "df",
+            EditorMode::DAPrefix => "da",
+            EditorMode::RegisterPrefix => "\"",
+            EditorMode::EqualPrefix => "=",
+            EditorMode::MarkPrefix => "`",
+            EditorMode::MarkLinePrefix => "'",
+            EditorMode::SetMarkPrefix => "m",
+            EditorMode::IndentPrefix => ">",
+            EditorMode::DedentPrefix => "<",
+            EditorMode::LowerCasePrefix => "gu",
+            EditorMode::UpperCasePrefix => "gU",
+            EditorMode::ToggleCasePrefix => "g~",
+            EditorMode::InsertRegisterPrefix => "^R",
+            EditorMode::InsertLiteralPrefix => "^V",
+            _ => "",
+        };
+        let code = self
+            .pending_literal_code
+            .as_ref()
+            .map(|code| format!("u{code}"))
+            .unwrap_or_default();
+        let register = self
+            .pending_register
+            .map(|register| format!("\"{register}"))
+            .unwrap_or_default();
+        let count = self
+            .pending_count
+            .map(|count| count.to_string())
+            .unwrap_or_default();
+        format!("{register}{count}{prefix}{code}")
+    }
 }
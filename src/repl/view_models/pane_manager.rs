@@ -33,9 +33,29 @@
 //! 5. Settings Management: Handles display settings (wrap, line numbers, tab width) that affect all panes
 
 use crate::repl::events::{EditorMode, LogicalPosition, Pane, PaneCapabilities, ViewEvent};
+use crate::repl::models::display_char::char_width;
 use crate::repl::models::geometry::Position;
+use crate::repl::models::LineSpan;
 use crate::repl::view_models::pane_state::{PaneState, VisualSelectionRestoreResult};
 
+/// Terminal heights below this show only the focused pane, as with `:set compact`
+pub const COMPACT_HEIGHT_THRESHOLD: u16 = 12;
+
+/// Fewest rows a pane is laid out with, however short the terminal
+pub const MIN_PANE_HEIGHT: u16 = 1;
+
+/// Rows the request header takes out of a request pane `request_rows` tall
+///
+/// The header showing the method and URL is left out when the pane has no
+/// row to spare for it.
+fn request_header_rows(request_rows: u16) -> u16 {
+    u16::from(request_rows > MIN_PANE_HEIGHT)
+}
+
+/// Fewest text columns left after the gutter and scrollbar; below this the
+/// terminal is too small to show the panes at all
+pub const MIN_CONTENT_WIDTH: usize = 1;
+
 /// Type alias for visual selection state to reduce complexity
 type VisualSelectionState = (
     Option<LogicalPosition>,
@@ -58,11 +78,19 @@ pub struct PaneManager {
     panes: [PaneState; 2], // Private - no external access
     current_pane: Pane,
     wrap_enabled: bool,
+    response_wrap: bool, // If false, the response pane scrolls sideways instead of wrapping
     show_line_numbers: bool,
-    tab_width: usize,                    // Number of spaces per tab stop (default 4)
-    expand_tab: bool,                    // If true, insert spaces instead of tab character
+    show_scrollbar: bool,     // If true, the rightmost column shows a scrollbar
+    show_cursor_column: bool, // If true, the cursor's screen column is tinted
+    tab_width: usize,         // Number of spaces per tab stop (default 4)
+    expand_tab: bool,         // If true, insert spaces instead of tab character
+    join_spaces: bool,        // If true, J inserts two spaces after a sentence end
+    equal_always: bool,       // If true, resizing rebalances the split to equal sizes
+    show_status_line: bool,   // If false, the status line row is given to the panes
+    compact: bool,            // If true, only the focused pane is shown, full-screen
     pub terminal_dimensions: (u16, u16), // Public for ViewModel access
     request_pane_height: u16,
+    split_active: bool, // Whether the last layout split the screen for a response
 }
 
 impl PaneManager {
@@ -111,11 +139,19 @@ impl PaneManager {
             panes: [request_pane, response_pane],
             current_pane: Pane::Request,
             wrap_enabled: false,
+            response_wrap: true, // Default to following `wrap` in the response pane
             show_line_numbers: true, // Default to showing line numbers
-            tab_width: 4,            // Default tab width of 4 spaces
-            expand_tab: false,       // Default to inserting real tabs, not spaces
+            show_scrollbar: false, // Default to no scrollbar column
+            show_cursor_column: false, // Default to no cursor column highlight
+            show_status_line: true, // Default to showing the status line
+            tab_width: 4,        // Default tab width of 4 spaces
+            expand_tab: false,   // Default to inserting real tabs, not spaces
+            join_spaces: false,  // Default to a single space when joining lines
+            equal_always: true,  // Default to balanced splits after resize
+            compact: false,      // Default to splitting when a response exists
             terminal_dimensions,
             request_pane_height: terminal_dimensions.1 / 2,
+            split_active: false,
         }
     }
 
@@ -133,11 +169,7 @@ impl PaneManager {
         };
 
         if old_pane != self.current_pane {
-            vec![
-                ViewEvent::FocusSwitched,
-                ViewEvent::StatusBarUpdateRequired,
-                ViewEvent::ActiveCursorUpdateRequired,
-            ]
+            self.focus_switch_events()
         } else {
             vec![]
         }
@@ -147,11 +179,7 @@ impl PaneManager {
     pub fn switch_to_request_pane(&mut self) -> Vec<ViewEvent> {
         if self.current_pane != Pane::Request {
             self.current_pane = Pane::Request;
-            vec![
-                ViewEvent::FocusSwitched,
-                ViewEvent::StatusBarUpdateRequired,
-                ViewEvent::ActiveCursorUpdateRequired,
-            ]
+            self.focus_switch_events()
         } else {
             vec![]
         }
@@ -161,16 +189,25 @@ impl PaneManager {
     pub fn switch_to_response_pane(&mut self) -> Vec<ViewEvent> {
         if self.current_pane != Pane::Response {
             self.current_pane = Pane::Response;
-            vec![
-                ViewEvent::FocusSwitched,
-                ViewEvent::StatusBarUpdateRequired,
-                ViewEvent::ActiveCursorUpdateRequired,
-            ]
+            self.focus_switch_events()
         } else {
             vec![]
         }
     }
 
+    /// Events for a focus switch; the compact layout shows the other pane instead
+    fn focus_switch_events(&self) -> Vec<ViewEvent> {
+        let mut events = vec![
+            ViewEvent::FocusSwitched,
+            ViewEvent::StatusBarUpdateRequired,
+            ViewEvent::ActiveCursorUpdateRequired,
+        ];
+        if self.is_compact_layout() {
+            events.push(ViewEvent::FullRedrawRequired);
+        }
+        events
+    }
+
     /// Check if currently in Request pane
     pub fn is_in_request_pane(&self) -> bool {
         self.current_pane == Pane::Request
@@ -186,6 +223,11 @@ impl PaneManager {
         self.panes[self.current_pane].buffer.cursor()
     }
 
+    /// Get the Request pane cursor position, whichever pane is active
+    pub fn get_request_cursor_position(&self) -> LogicalPosition {
+        self.panes[Pane::Request].buffer.cursor()
+    }
+
     /// Get visual selection state for current pane
     pub fn get_visual_selection(&self) -> VisualSelectionState {
         let (start, end) = self.panes[self.current_pane].get_visual_selection();
@@ -244,6 +286,11 @@ impl PaneManager {
         self.panes[self.current_pane].update_visual_selection_on_cursor_move(new_position)
     }
 
+    /// Get the first and last line of the last visual selection in the current pane
+    pub fn get_last_visual_line_range(&self) -> Option<LineSpan> {
+        self.panes[self.current_pane].last_visual_line_range()
+    }
+
     /// Restore the last visual selection (for 'gv' command)
     /// Returns the mode and view events if restoration successful
     pub fn restore_last_visual_selection(&mut self) -> VisualSelectionRestoreResult {
@@ -280,8 +327,9 @@ impl PaneManager {
         &mut self,
         start_position: LogicalPosition,
         block_lines: &[&str],
+        block_width: usize,
     ) -> Vec<ViewEvent> {
-        self.panes[self.current_pane].insert_block_wise(start_position, block_lines)
+        self.panes[self.current_pane].insert_block_wise(start_position, block_lines, block_width)
     }
 
     /// Get the length of the current line in the current pane
@@ -307,7 +355,7 @@ impl PaneManager {
             self.terminal_dimensions
                 .1
                 .saturating_sub(self.request_pane_height)
-                .saturating_sub(2) // -2 for separator and status
+                .saturating_sub(1 + self.status_line_height()) // separator and status
         } else {
             0
         }
@@ -332,6 +380,24 @@ impl PaneManager {
         );
     }
 
+    /// Check whether the response pane wraps its lines while `wrap` is on
+    pub fn is_response_wrap_enabled(&self) -> bool {
+        self.response_wrap
+    }
+
+    /// Set whether the response pane wraps its lines while `wrap` is on
+    pub fn set_response_wrap_enabled(&mut self, enabled: bool) {
+        self.response_wrap = enabled;
+    }
+
+    /// Check whether lines in `pane` are wrapped
+    ///
+    /// The response pane only wraps while both `wrap` and `responsewrap` are
+    /// on; otherwise its long lines are scrolled into view sideways.
+    pub fn is_wrap_enabled_for(&self, pane: Pane) -> bool {
+        self.wrap_enabled && (pane == Pane::Request || self.response_wrap)
+    }
+
     /// Get line number visibility state
     pub fn is_line_numbers_visible(&self) -> bool {
         self.show_line_numbers
@@ -351,6 +417,31 @@ impl PaneManager {
         );
     }
 
+    /// Get scrollbar visibility state
+    pub fn is_scrollbar_visible(&self) -> bool {
+        self.show_scrollbar
+    }
+
+    /// Set scrollbar visibility state
+    pub fn set_scrollbar_visible(&mut self, visible: bool) {
+        self.show_scrollbar = visible;
+    }
+
+    /// Check if the cursor column is highlighted
+    pub fn is_cursor_column_visible(&self) -> bool {
+        self.show_cursor_column
+    }
+
+    /// Set cursor column highlight state
+    pub fn set_cursor_column_visible(&mut self, visible: bool) {
+        self.show_cursor_column = visible;
+    }
+
+    /// Number of columns taken by the scrollbar (0 when hidden)
+    pub fn scrollbar_width(&self) -> usize {
+        usize::from(self.show_scrollbar)
+    }
+
     /// Get tab width (number of spaces per tab stop)
     pub fn get_tab_width(&self) -> usize {
         self.tab_width
@@ -392,30 +483,116 @@ impl PaneManager {
         );
     }
 
+    /// Get join spaces setting (whether J inserts two spaces after `.`, `!` or `?`)
+    pub fn get_join_spaces(&self) -> bool {
+        self.join_spaces
+    }
+
+    /// Set join spaces setting (whether J inserts two spaces after `.`, `!` or `?`)
+    pub fn set_join_spaces(&mut self, join_spaces: bool) {
+        tracing::debug!(
+            "🔧 PaneManager::set_join_spaces: changing from {} to {}",
+            self.join_spaces,
+            join_spaces
+        );
+        self.join_spaces = join_spaces;
+    }
+
+    /// Get equalalways setting
+    pub fn is_equal_always(&self) -> bool {
+        self.equal_always
+    }
+
+    /// Set equalalways setting (rebalance the split on resize)
+    pub fn set_equal_always(&mut self, equal_always: bool) {
+        self.equal_always = equal_always;
+    }
+
+    /// Check if the status line is shown (`laststatus=2`)
+    pub fn is_status_line_visible(&self) -> bool {
+        self.show_status_line
+    }
+
+    /// Show or hide the status line (`laststatus=2` / `laststatus=0`)
+    ///
+    /// Call [`Self::update_terminal_size`] afterwards to resize the panes.
+    pub fn set_status_line_visible(&mut self, visible: bool) {
+        self.show_status_line = visible;
+    }
+
+    /// Check if the compact single-pane layout is forced (`:set compact`)
+    pub fn is_compact(&self) -> bool {
+        self.compact
+    }
+
+    /// Force the compact single-pane layout on or off (`:set compact on/off`)
+    ///
+    /// Call [`Self::update_terminal_size`] afterwards to resize the panes.
+    pub fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+    }
+
+    /// Check if only the focused pane is shown, either forced by `:set compact`
+    /// or because the terminal is shorter than [`COMPACT_HEIGHT_THRESHOLD`]
+    pub fn is_compact_layout(&self) -> bool {
+        self.compact || self.terminal_dimensions.1 < COMPACT_HEIGHT_THRESHOLD
+    }
+
+    /// Check whether the terminal is too small to lay out even one pane
+    ///
+    /// That is when no text column is left beside the gutter and scrollbar, or
+    /// no row above the status line. The panes then keep their minimum sizes
+    /// and the renderer shows a "terminal too small" message instead.
+    pub fn is_terminal_too_small(&self) -> bool {
+        self.get_content_width() < MIN_CONTENT_WIDTH
+            || self.terminal_dimensions.1 < MIN_PANE_HEIGHT + self.status_line_height()
+    }
+
+    /// Number of rows taken by the status line (0 when hidden)
+    pub fn status_line_height(&self) -> u16 {
+        u16::from(self.show_status_line)
+    }
+
     /// Update terminal size and recalculate pane dimensions
+    ///
+    /// With `equalalways` the split is rebalanced to equal sizes. Otherwise an
+    /// existing split keeps its request pane height, as far as the new height allows.
+    /// In the compact layout both panes take the whole content area, since only
+    /// the focused one is drawn.
     pub fn update_terminal_size(&mut self, width: u16, height: u16, has_response: bool) {
         self.terminal_dimensions = (width, height);
 
         // Calculate request pane height (split screen when response exists)
-        self.request_pane_height = if has_response {
-            height / 2
+        let status_height = self.status_line_height();
+        self.request_pane_height = if !has_response {
+            height.saturating_sub(status_height).max(MIN_PANE_HEIGHT) // Reserve space for status bar
+        } else if self.equal_always || !self.split_active {
+            (height / 2).max(MIN_PANE_HEIGHT)
         } else {
-            height - 1 // Reserve space for status bar
+            // Leave room for the separator, status bar and one response line
+            self.request_pane_height
+                .clamp(1, height.saturating_sub(2 + status_height).max(1))
         };
+        self.split_active = has_response;
 
         // Recalculate pane dimensions
-        let content_width = if self.show_line_numbers {
-            (width as usize).saturating_sub(4) // Account for line numbers
-        } else {
-            width as usize
-        };
+        let content_width = self.get_content_width();
         let request_pane_height = self.request_pane_height as usize;
         let response_pane_height = (height as usize)
             .saturating_sub(self.request_pane_height as usize)
-            .saturating_sub(2) // -2 for separator and status
-            .max(1); // Ensure minimum height of 1
+            .saturating_sub(1 + status_height as usize) // separator and status
+            .max(MIN_PANE_HEIGHT as usize);
+        let (request_pane_height, response_pane_height) = if self.is_compact_layout() {
+            let content_height =
+                (height.saturating_sub(status_height) as usize).max(MIN_PANE_HEIGHT as usize);
+            (content_height, content_height)
+        } else {
+            (request_pane_height, response_pane_height)
+        };
 
-        // Update pane dimensions
+        // Update pane dimensions, the request pane giving its top row to the header
+        let request_pane_height =
+            request_pane_height - request_header_rows(request_pane_height as u16) as usize;
         self.panes[Pane::Request].update_dimensions(content_width, request_pane_height);
         self.panes[Pane::Response].update_dimensions(content_width, response_pane_height);
 
@@ -425,17 +602,13 @@ impl PaneManager {
         self.panes[Pane::Request].display_cache.invalidate();
         self.panes[Pane::Response].display_cache.invalidate();
 
-        // Rebuild both caches with the new dimensions
-        self.panes[Pane::Request].build_display_cache(
-            content_width,
-            self.wrap_enabled,
-            self.tab_width,
-        );
-        self.panes[Pane::Response].build_display_cache(
-            content_width,
-            self.wrap_enabled,
-            self.tab_width,
-        );
+        // Rebuild both caches with the new dimensions, then move each cursor to
+        // its new display position and scroll it back into its resized pane
+        self.rebuild_display_caches(content_width);
+        self.sync_display_cursors();
+        for pane in [Pane::Request, Pane::Response] {
+            self.panes[pane].ensure_cursor_visible(content_width);
+        }
 
         tracing::debug!(
             "Terminal size updated: {}x{}, pane dimensions: Request={}x{}, Response={}x{}",
@@ -450,16 +623,10 @@ impl PaneManager {
 
     /// Rebuild display caches for both panes with provided content width
     pub fn rebuild_display_caches(&mut self, content_width: usize) {
-        self.panes[Pane::Request].build_display_cache(
-            content_width,
-            self.wrap_enabled,
-            self.tab_width,
-        );
-        self.panes[Pane::Response].build_display_cache(
-            content_width,
-            self.wrap_enabled,
-            self.tab_width,
-        );
+        for pane in [Pane::Request, Pane::Response] {
+            let wrap_enabled = self.is_wrap_enabled_for(pane);
+            self.panes[pane].build_display_cache(content_width, wrap_enabled, self.tab_width);
+        }
     }
 
     /// Rebuild display caches for both panes and sync cursors (complete rebuild process)
@@ -561,18 +728,31 @@ impl PaneManager {
             .join("\n")
     }
 
+    /// Get the response pane text from its first line through `last_line`
+    ///
+    /// Lookups that only depend on the text before a position, like the JSON
+    /// path under the cursor, use this to skip the rest of a large response.
+    pub fn get_response_text_through(&self, last_line: usize) -> String {
+        let content = self.panes[Pane::Response].buffer.content();
+        (0..=last_line)
+            .map_while(|index| content.get_line(index))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     /// Insert character at current cursor position using generic delegation
     ///
     /// This method delegates to the current pane's insert_char() method,
     /// which handles capability checking and text insertion logic.
     pub fn insert_char(&mut self, ch: char) -> Vec<ViewEvent> {
         let content_width = self.get_content_width();
+        let wrap_enabled = self.is_wrap_enabled_for(self.current_pane);
 
         // Delegate to current pane with capability checking
         let mut events = self.panes[self.current_pane].insert_char(
             ch,
             content_width,
-            self.wrap_enabled,
+            wrap_enabled,
             self.tab_width,
         );
 
@@ -585,17 +765,45 @@ impl PaneManager {
         events
     }
 
+    /// Insert text into the current pane with a single display cache rebuild
+    pub fn insert_text(&mut self, text: &str) -> Vec<ViewEvent> {
+        let content_width = self.get_content_width();
+        let wrap_enabled = self.is_wrap_enabled_for(self.current_pane);
+
+        // Delegate to current pane with capability checking
+        let mut events = self.panes[self.current_pane].insert_text(
+            text,
+            content_width,
+            wrap_enabled,
+            self.tab_width,
+        );
+
+        // Ensure cursor is visible after insertion if events were generated
+        if !events.is_empty() {
+            let visibility_events = self.ensure_current_cursor_visible(content_width);
+            events.extend(visibility_events);
+        }
+
+        events
+    }
+
+    /// Get how many times a pane's display cache has been rebuilt or patched
+    pub fn display_cache_update_count(&self, pane: Pane) -> usize {
+        self.panes[pane].display_cache_updates
+    }
+
     /// Delete character before cursor using generic delegation
     ///
     /// This method delegates to the current pane's delete_char_before_cursor() method,
     /// which handles capability checking and deletion logic.
     pub fn delete_char_before_cursor(&mut self) -> Vec<ViewEvent> {
         let content_width = self.get_content_width();
+        let wrap_enabled = self.is_wrap_enabled_for(self.current_pane);
 
         // Delegate to current pane with capability checking
         self.panes[self.current_pane].delete_char_before_cursor(
             content_width,
-            self.wrap_enabled,
+            wrap_enabled,
             self.tab_width,
         )
     }
@@ -603,11 +811,12 @@ impl PaneManager {
     /// Delete character after cursor (generic method for any pane)
     pub fn delete_char_after_cursor(&mut self) -> Vec<ViewEvent> {
         let content_width = self.get_content_width();
+        let wrap_enabled = self.is_wrap_enabled_for(self.current_pane);
 
         // Delegate to current pane with capability checking
         self.panes[self.current_pane].delete_char_after_cursor(
             content_width,
-            self.wrap_enabled,
+            wrap_enabled,
             self.tab_width,
         )
     }
@@ -615,11 +824,12 @@ impl PaneManager {
     /// Delete character after cursor without line joining (safe for Visual Block Insert mode)
     pub fn delete_char_after_cursor_visual_block_safe(&mut self) -> Vec<ViewEvent> {
         let content_width = self.get_content_width();
+        let wrap_enabled = self.is_wrap_enabled_for(self.current_pane);
 
         // Delegate to current pane with line joining disabled
         self.panes[self.current_pane].delete_char_after_cursor_no_join(
             content_width,
-            self.wrap_enabled,
+            wrap_enabled,
             self.tab_width,
         )
     }
@@ -627,11 +837,12 @@ impl PaneManager {
     /// Cut (delete and yank) character at cursor position, returning deleted character
     pub fn cut_char_at_cursor(&mut self) -> Option<String> {
         let content_width = self.get_content_width();
+        let wrap_enabled = self.is_wrap_enabled_for(self.current_pane);
 
         // Delegate to current pane with capability checking
         self.panes[self.current_pane].delete_char_at_cursor_with_return(
             content_width,
-            self.wrap_enabled,
+            wrap_enabled,
             self.tab_width,
         )
     }
@@ -639,11 +850,12 @@ impl PaneManager {
     /// Cut (delete and yank) from cursor to end of line, returning deleted text
     pub fn cut_to_end_of_line(&mut self) -> Option<String> {
         let content_width = self.get_content_width();
+        let wrap_enabled = self.is_wrap_enabled_for(self.current_pane);
 
         // Delegate to current pane with capability checking
         self.panes[self.current_pane].cut_to_end_of_line_with_return(
             content_width,
-            self.wrap_enabled,
+            wrap_enabled,
             self.tab_width,
         )
     }
@@ -651,11 +863,52 @@ impl PaneManager {
     /// Cut (delete and yank) entire current line, returning deleted text
     pub fn cut_current_line(&mut self) -> Option<String> {
         let content_width = self.get_content_width();
+        let wrap_enabled = self.is_wrap_enabled_for(self.current_pane);
 
         // Delegate to current pane with capability checking
         self.panes[self.current_pane].cut_current_line_with_return(
             content_width,
-            self.wrap_enabled,
+            wrap_enabled,
+            self.tab_width,
+        )
+    }
+
+    /// Delete from cursor back to the start of the previous word in current area
+    pub fn delete_word_before_cursor(&mut self) -> Vec<ViewEvent> {
+        let content_width = self.get_content_width();
+        let wrap_enabled = self.is_wrap_enabled_for(self.current_pane);
+
+        // Delegate to current pane with capability checking
+        self.panes[self.current_pane].delete_word_before_cursor(
+            content_width,
+            wrap_enabled,
+            self.tab_width,
+        )
+    }
+
+    /// Delete from cursor back to the first non-blank character in current area
+    pub fn delete_to_line_start(&mut self) -> Vec<ViewEvent> {
+        let content_width = self.get_content_width();
+        let wrap_enabled = self.is_wrap_enabled_for(self.current_pane);
+
+        // Delegate to current pane with capability checking
+        self.panes[self.current_pane].delete_to_line_start(
+            content_width,
+            wrap_enabled,
+            self.tab_width,
+        )
+    }
+
+    /// Join the current line with the next line (J command)
+    pub fn join_lines(&mut self) -> Vec<ViewEvent> {
+        let content_width = self.get_content_width();
+        let wrap_enabled = self.is_wrap_enabled_for(self.current_pane);
+
+        // Delegate to current pane with capability checking
+        self.panes[self.current_pane].join_lines(
+            self.join_spaces,
+            content_width,
+            wrap_enabled,
             self.tab_width,
         )
     }
@@ -670,6 +923,48 @@ impl PaneManager {
             .map(|line| line.to_string())
     }
 
+    /// Get the display width of columns `left` through `right` of a line in the current pane
+    ///
+    /// Columns past the end of the line count as one display column each, as
+    /// visual block selections extend over short lines.
+    pub fn get_current_columns_width(&self, line: usize, left: usize, right: usize) -> usize {
+        let text = self.panes[self.current_pane]
+            .buffer
+            .content()
+            .get_line(line)
+            .unwrap_or_default();
+        let mut chars = text.chars().skip(left);
+        (left..=right)
+            .map(|_| chars.next().map_or(1, char_width))
+            .sum()
+    }
+
+    /// Remember the cursor position in the current area as `mark`
+    pub fn set_current_mark(&mut self, mark: char) {
+        let cursor = self.get_current_cursor_position();
+        self.panes[self.current_pane].set_mark(mark, cursor);
+    }
+
+    /// Get where `mark` is in the current area, if it is set there
+    ///
+    /// With `line_wise` this is the first non-blank of the mark's line. A mark
+    /// past the end of text that was edited since is moved back onto it.
+    pub fn get_current_mark(&self, mark: char, line_wise: bool) -> Option<LogicalPosition> {
+        let pane = &self.panes[self.current_pane];
+        let content = pane.buffer.content();
+        let position = content.clamp_position(pane.get_mark(mark)?);
+        let line = content.get_line(position.line).unwrap_or_default();
+        let column = if line_wise {
+            line.chars().take_while(|ch| ch.is_whitespace()).count()
+        } else {
+            position.column
+        };
+        Some(LogicalPosition::new(
+            position.line,
+            column.min(line.chars().count().saturating_sub(1)),
+        ))
+    }
+
     /// Set cursor position in current area
     pub fn set_current_cursor_position(&mut self, position: LogicalPosition) -> Vec<ViewEvent> {
         self.panes[self.current_pane].set_current_cursor_position(position)
@@ -695,11 +990,8 @@ impl PaneManager {
         } else {
             self.terminal_dimensions.0 as usize
         };
-        self.panes[Pane::Response].build_display_cache(
-            content_width,
-            self.wrap_enabled,
-            self.tab_width,
-        );
+        let wrap_enabled = self.is_wrap_enabled_for(Pane::Response);
+        self.panes[Pane::Response].build_display_cache(content_width, wrap_enabled, self.tab_width);
 
         events
     }
@@ -774,6 +1066,7 @@ impl PaneManager {
         }];
 
         if result.cursor_moved {
+            self.sync_display_cursors();
             events.push(ViewEvent::ActiveCursorUpdateRequired);
         }
 
@@ -805,31 +1098,34 @@ impl PaneManager {
     pub fn get_content_width(&self) -> usize {
         // Use current pane's line number width calculation
         // This is a simplified version - should be improved later
-        if self.show_line_numbers {
+        let width = if self.show_line_numbers {
             (self.terminal_dimensions.0 as usize).saturating_sub(4) // Account for line numbers
         } else {
             self.terminal_dimensions.0 as usize // Full width when line numbers are hidden
-        }
+        };
+        width.saturating_sub(self.scrollbar_width())
     }
 
     /// Move cursor left in current area
     ///
     /// Delegates to PaneState for business logic with capability checking.
-    pub fn move_cursor_left(&mut self) -> Vec<ViewEvent> {
+    /// The cursor only moves onto the previous line with `wrap`.
+    pub fn move_cursor_left(&mut self, wrap: bool) -> Vec<ViewEvent> {
         let content_width = self.get_content_width();
-        self.panes[self.current_pane].move_cursor_left(content_width)
+        self.panes[self.current_pane].move_cursor_left(content_width, wrap)
     }
 
     /// Move cursor right in current area
     ///
     /// HIGH-LEVEL LOGIC:
     /// 1. Check if cursor can move right within current line (mode-aware boundary check)
-    /// 2. If not, check if cursor can move to next line (line wrap navigation)
+    /// 2. If not, check if cursor can move to next line (only with `wrap`, or
+    ///    onto the next segment of a wrapped line)
     /// 3. Perform the actual cursor movement using character-aware positioning
     /// 4. Sync display cursor with logical cursor and update visual selections
-    pub fn move_cursor_right(&mut self) -> Vec<ViewEvent> {
+    pub fn move_cursor_right(&mut self, wrap: bool) -> Vec<ViewEvent> {
         let content_width = self.get_content_width();
-        self.panes[self.current_pane].move_cursor_right(content_width)
+        self.panes[self.current_pane].move_cursor_right(content_width, wrap)
     }
 
     /// Move cursor up in current area
@@ -849,6 +1145,18 @@ impl PaneManager {
         self.panes[self.current_pane].move_cursor_down(content_width)
     }
 
+    /// Move cursor up one display line in current area (gk)
+    pub fn move_cursor_display_line_up(&mut self) -> Vec<ViewEvent> {
+        let content_width = self.get_content_width();
+        self.panes[self.current_pane].move_cursor_display_line_up(content_width)
+    }
+
+    /// Move cursor down one display line in current area (gj)
+    pub fn move_cursor_display_line_down(&mut self) -> Vec<ViewEvent> {
+        let content_width = self.get_content_width();
+        self.panes[self.current_pane].move_cursor_display_line_down(content_width)
+    }
+
     /// Move cursor to start of current line
     ///
     /// Delegates to PaneState for business logic with capability checking.
@@ -927,9 +1235,21 @@ impl PaneManager {
 
     /// Calculate pane boundaries for rendering
     /// Returns (request_height, response_start, response_height)
+    ///
+    /// In the compact layout the focused pane gets the whole content area and
+    /// the other pane a height of 0.
     #[allow(clippy::type_complexity)]
     pub fn get_pane_boundaries(&self, has_response: bool) -> (u16, u16, u16) {
-        if has_response {
+        if has_response && self.is_compact_layout() {
+            let content_height = self
+                .terminal_dimensions
+                .1
+                .saturating_sub(self.status_line_height());
+            match self.current_pane {
+                Pane::Request => (content_height, content_height, 0),
+                Pane::Response => (0, 0, content_height),
+            }
+        } else if has_response {
             // When response exists, split the space
             let request_height = self.request_pane_height();
             let response_start = request_height + 1; // +1 for separator
@@ -937,13 +1257,26 @@ impl PaneManager {
             (request_height, response_start, response_height)
         } else {
             // When no response, request pane uses full available space
-            let request_height = self.terminal_dimensions.1 - 1; // -1 for status bar
+            let request_height = self
+                .terminal_dimensions
+                .1
+                .saturating_sub(self.status_line_height()); // status bar
             let response_start = request_height + 1; // Won't be used
             let response_height = 0; // Hidden
             (request_height, response_start, response_height)
         }
     }
 
+    /// Get the rows of the request pane's text below the request header
+    ///
+    /// Returns the first row and the number of rows, out of the request pane
+    /// given by [`Self::get_pane_boundaries`].
+    pub fn get_request_text_area(&self, has_response: bool) -> (u16, u16) {
+        let (request_height, _, _) = self.get_pane_boundaries(has_response);
+        let header_rows = request_header_rows(request_height);
+        (header_rows, request_height - header_rows)
+    }
+
     // Per-pane mode management methods
     /// Get current editor mode for the currently active pane
     pub fn get_current_pane_mode(&self) -> EditorMode {
@@ -975,6 +1308,19 @@ impl PaneManager {
 mod tests {
     use super::*;
 
+    #[test]
+    fn get_response_text_through_should_stop_at_last_line() {
+        let mut manager = PaneManager::new((80, 24));
+        manager.set_response_content("{\n  \"a\": 1\n}");
+
+        assert_eq!(manager.get_response_text_through(0), "{");
+        assert_eq!(manager.get_response_text_through(1), "{\n  \"a\": 1");
+        assert_eq!(
+            manager.get_response_text_through(5),
+            manager.get_response_text()
+        );
+    }
+
     #[test]
     fn move_cursor_page_down_should_work() {
         let mut manager = PaneManager::new((80, 24));
@@ -1120,6 +1466,203 @@ mod tests {
         assert_eq!(new_cursor.column, 0); // Should be at start of new line
     }
 
+    #[test]
+    fn scrollbar_should_take_one_column_from_content_width() {
+        let mut manager = PaneManager::new((80, 24));
+        let width = manager.get_content_width();
+
+        manager.set_scrollbar_visible(true);
+        assert_eq!(manager.get_content_width(), width - 1);
+
+        manager.set_scrollbar_visible(false);
+        assert_eq!(manager.get_content_width(), width);
+    }
+
+    #[test]
+    fn hiding_status_line_should_give_its_row_to_content() {
+        let mut manager = PaneManager::new((80, 24));
+        manager.update_terminal_size(80, 24, false);
+        assert_eq!(manager.get_pane_boundaries(false).0, 23);
+
+        manager.set_status_line_visible(false);
+        manager.update_terminal_size(80, 24, false);
+        assert_eq!(manager.request_pane_height(), 24);
+        assert_eq!(manager.get_pane_boundaries(false).0, 24);
+
+        // With a split the response pane gets the extra row
+        manager.update_terminal_size(80, 24, true);
+        assert_eq!(manager.response_pane_height(true), 11);
+        manager.set_status_line_visible(true);
+        manager.update_terminal_size(80, 24, true);
+        assert_eq!(manager.response_pane_height(true), 10);
+    }
+
+    #[test]
+    fn short_terminal_should_give_content_area_to_focused_pane_only() {
+        let mut manager = PaneManager::new((80, 24));
+        manager.update_terminal_size(80, COMPACT_HEIGHT_THRESHOLD - 1, true);
+        assert!(manager.is_compact_layout());
+        assert_eq!(manager.get_pane_boundaries(true), (10, 10, 0));
+
+        let events = manager.switch_to_other_area();
+        assert!(events.contains(&ViewEvent::FullRedrawRequired));
+        assert_eq!(manager.get_pane_boundaries(true), (0, 0, 10));
+
+        // At the threshold the screen is split again
+        manager.update_terminal_size(80, COMPACT_HEIGHT_THRESHOLD, true);
+        assert!(!manager.is_compact_layout());
+        assert_eq!(manager.get_pane_boundaries(true), (6, 7, 4));
+    }
+
+    #[test]
+    fn tiny_terminal_should_get_valid_layout_or_be_too_small() {
+        let mut manager = PaneManager::new((80, 24));
+        for has_response in [false, true] {
+            manager.update_terminal_size(10, 3, has_response);
+            assert!(!manager.is_terminal_too_small());
+            let (request_height, _, response_height) = manager.get_pane_boundaries(has_response);
+            assert_eq!(request_height + response_height, 2);
+            assert!(manager.get_content_width() >= MIN_CONTENT_WIDTH);
+        }
+
+        // Every size down to nothing is laid out without panicking
+        for width in 0..=10 {
+            for height in 0..=3 {
+                manager.update_terminal_size(width, height, true);
+                let (request_height, _, response_height) = manager.get_pane_boundaries(true);
+                if !manager.is_terminal_too_small() {
+                    assert!(request_height + response_height < height);
+                }
+            }
+        }
+
+        manager.update_terminal_size(10, 1, true);
+        assert!(manager.is_terminal_too_small());
+        manager.set_line_numbers_visible(true);
+        manager.update_terminal_size(4, 3, true);
+        assert!(manager.is_terminal_too_small());
+    }
+
+    #[test]
+    fn horizontal_scroll_should_move_unwrapped_response_view_and_keep_cursor_visible() {
+        let mut manager = PaneManager::new((40, 10));
+        manager.set_wrap_enabled(true);
+        manager.set_response_wrap_enabled(false);
+        manager.update_terminal_size(40, 10, true);
+        let long_line = "x".repeat(100);
+        manager.set_request_content(&long_line);
+        manager.set_response_content(&format!("{long_line}\nshort"));
+        manager.rebuild_display_caches(manager.get_content_width());
+
+        // The request pane still wraps while the response keeps whole lines
+        assert!(
+            manager
+                .get_display_cache(Pane::Request)
+                .display_line_count()
+                > 1
+        );
+        assert_eq!(
+            manager
+                .get_display_cache(Pane::Response)
+                .display_line_count(),
+            2
+        );
+
+        manager.switch_to_response_pane();
+        let cache_updates = manager.display_cache_update_count(Pane::Response);
+        let width = manager.panes[Pane::Response].get_content_width();
+
+        // Scrolling right drags the cursor along from the left edge
+        manager.scroll_current_horizontally(1, 10);
+        assert_eq!(manager.get_current_scroll_offset().col, 10);
+        assert_eq!(
+            manager.get_current_cursor_position(),
+            LogicalPosition::new(0, 10)
+        );
+        assert_eq!(manager.get_current_display_cursor().col, 10);
+
+        // The view stops with the last character of the cursor line in sight
+        manager.scroll_current_horizontally(1, 500);
+        assert_eq!(manager.get_current_scroll_offset().col, 99);
+        assert_eq!(manager.get_current_cursor_position().column, 99);
+
+        // Scrolling left pulls the cursor back to the right edge
+        manager.scroll_current_horizontally(-1, 95);
+        assert_eq!(manager.get_current_scroll_offset().col, 4);
+        assert_eq!(manager.get_current_cursor_position().column, 4 + width - 1);
+
+        // Only the offset changed, the display cache was left alone
+        assert_eq!(
+            manager.display_cache_update_count(Pane::Response),
+            cache_updates
+        );
+
+        // The wrapped request pane does not scroll sideways
+        manager.switch_to_request_pane();
+        manager.scroll_current_horizontally(1, 10);
+        assert_eq!(manager.get_current_scroll_offset().col, 0);
+    }
+
+    #[test]
+    fn compact_setting_should_show_one_pane_on_tall_terminal() {
+        let mut manager = PaneManager::new((80, 24));
+        manager.set_compact(true);
+        manager.update_terminal_size(80, 24, true);
+
+        assert_eq!(manager.get_pane_boundaries(true), (23, 23, 0));
+        // Without a response there is only the request pane anyway
+        assert_eq!(manager.get_pane_boundaries(false).0, 23);
+    }
+
+    #[test]
+    fn update_terminal_size_with_equal_always_should_keep_split_ratio() {
+        let mut manager = PaneManager::new((80, 24));
+        manager.update_terminal_size(80, 24, true);
+        assert_eq!(manager.request_pane_height(), 12);
+
+        manager.update_terminal_size(80, 40, true);
+        assert_eq!(manager.request_pane_height(), 20);
+        assert_eq!(manager.response_pane_height(true), 18);
+
+        manager.update_terminal_size(80, 30, true);
+        assert_eq!(manager.request_pane_height(), 15);
+    }
+
+    #[test]
+    fn update_terminal_size_without_equal_always_should_preserve_request_height() {
+        let mut manager = PaneManager::new((80, 24));
+        manager.set_equal_always(false);
+        manager.update_terminal_size(80, 24, true);
+        assert_eq!(manager.request_pane_height(), 12);
+
+        manager.update_terminal_size(80, 40, true);
+        assert_eq!(manager.request_pane_height(), 12);
+
+        // Shrinking below the request height still leaves a response line
+        manager.update_terminal_size(80, 10, true);
+        assert_eq!(manager.request_pane_height(), 7);
+    }
+
+    #[test]
+    fn update_terminal_size_should_keep_cursors_visible_in_resized_panes() {
+        let mut manager = PaneManager::new((80, 24));
+        manager.update_terminal_size(80, 24, false);
+        let content: Vec<String> = (0..30).map(|line| format!("line {line}")).collect();
+        manager.set_request_content(&content.join("\n"));
+        manager.update_terminal_size(80, 24, false);
+        manager.set_current_cursor_position(LogicalPosition::new(20, 0));
+
+        // A response arriving splits the screen, then the terminal shrinks
+        manager.update_terminal_size(80, 24, true);
+        manager.update_terminal_size(80, 16, true);
+
+        let height = manager.request_pane_height() as usize;
+        let cursor_row = manager.get_current_display_cursor().row;
+        let scroll_row = manager.get_current_scroll_offset().row;
+        assert_eq!(height, 8);
+        assert!(scroll_row <= cursor_row && cursor_row < scroll_row + height);
+    }
+
     #[test]
     fn move_cursor_should_maintain_virtual_column_vim_style() {
         let mut manager = PaneManager::new((80, 24));
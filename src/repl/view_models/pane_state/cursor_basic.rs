@@ -2,6 +2,7 @@
 //!
 //! This module contains methods for:
 //! - Basic directional cursor movement (left, right, up, down)
+//! - Display line movement within wrapped lines (gj, gk)
 //! - Capability checking for navigation
 //! - Visual selection handling during movement
 //! - Virtual column management for Vim-style navigation
@@ -13,7 +14,11 @@ use super::PaneState;
 
 impl PaneState {
     /// Move cursor left with capability checking and visual selection support
-    pub fn move_cursor_left(&mut self, content_width: usize) -> Vec<ViewEvent> {
+    ///
+    /// From the start of a line the cursor only goes on to the end of the
+    /// previous line with `wrap` (`:set whichwrap`); the segments of a wrapped
+    /// line are always crossed.
+    pub fn move_cursor_left(&mut self, content_width: usize, wrap: bool) -> Vec<ViewEvent> {
         // Check if navigation is allowed on this pane
         if !self.capabilities.contains(PaneCapabilities::NAVIGABLE) {
             return vec![]; // Navigation not allowed on this pane
@@ -35,13 +40,25 @@ impl PaneState {
                 moved = true;
             }
         } else if current_display_pos.row > 0 {
+            let starts_line = self
+                .display_cache
+                .get_display_line(current_display_pos.row)
+                .is_some_and(|line| !line.is_continuation);
             // VISUAL BLOCK FIX: In Visual Block mode, prevent moving to previous line
-            if self.editor_mode != EditorMode::VisualBlock {
+            if self.editor_mode != EditorMode::VisualBlock && (wrap || !starts_line) {
                 // Move to end of previous display line
-                let prev_display_line = current_display_pos.row - 1;
+                let mut prev_display_line = current_display_pos.row - 1;
+                if self.skips_empty_segment(prev_display_line) && prev_display_line > 0 {
+                    prev_display_line -= 1;
+                }
                 if let Some(prev_line) = self.display_cache.get_display_line(prev_display_line) {
                     // Use display width instead of character count for proper multibyte character support
-                    let new_col = prev_line.display_width().saturating_sub(1);
+                    // Insert mode may sit after the last character of a line
+                    let new_col = if starts_line && self.editor_mode == EditorMode::Insert {
+                        prev_line.display_width()
+                    } else {
+                        prev_line.display_width().saturating_sub(1)
+                    };
                     let new_display_pos = Position::new(prev_display_line, new_col);
                     self.display_cursor = new_display_pos;
                     // Update virtual column for horizontal movement
@@ -81,8 +98,25 @@ impl PaneState {
         }
     }
 
+    /// Check whether horizontal moves step over the display line at `row`
+    ///
+    /// A line that exactly fills the pane gets an empty continuation segment
+    /// for the Insert mode cursor past its end; in other modes it maps back to
+    /// the start of the line, so the cursor must not land on it.
+    fn skips_empty_segment(&self, row: usize) -> bool {
+        self.editor_mode != EditorMode::Insert
+            && self
+                .display_cache
+                .get_display_line(row)
+                .is_some_and(|line| line.is_continuation && line.char_count() == 0)
+    }
+
     /// Move cursor right with capability checking and visual selection support
-    pub fn move_cursor_right(&mut self, content_width: usize) -> Vec<ViewEvent> {
+    ///
+    /// From the end of a line the cursor only goes on to the start of the next
+    /// line with `wrap` (`:set whichwrap`); the segments of a wrapped line are
+    /// always crossed.
+    pub fn move_cursor_right(&mut self, content_width: usize, wrap: bool) -> Vec<ViewEvent> {
         // Check if navigation is allowed on this pane
         if !self.capabilities.contains(PaneCapabilities::NAVIGABLE) {
             return vec![]; // Navigation not allowed on this pane
@@ -122,15 +156,18 @@ impl PaneState {
         };
 
         // Check if cursor can move to next line
+        let mut next_display_line = current_display_pos.row + 1;
+        if self.skips_empty_segment(next_display_line) {
+            next_display_line += 1;
+        }
         let can_move_to_next_line = if !can_move_right_in_line {
             // VISUAL BLOCK FIX: In Visual Block mode, prevent moving to next line
             if self.editor_mode == EditorMode::VisualBlock {
                 false
             } else {
-                let next_display_line = current_display_pos.row + 1;
                 self.display_cache
                     .get_display_line(next_display_line)
-                    .is_some()
+                    .is_some_and(|line| wrap || line.is_continuation)
             }
         } else {
             false
@@ -148,7 +185,6 @@ impl PaneState {
             }
         } else if can_move_to_next_line {
             // Move to beginning of next line
-            let next_display_line = current_display_pos.row + 1;
             self.display_cursor = Position::new(next_display_line, 0);
             self.update_virtual_column();
             moved = true;
@@ -184,119 +220,126 @@ impl PaneState {
         }
     }
 
-    /// Move cursor up with capability checking and virtual column support
+    /// Move cursor up one logical line (k) with virtual column support
+    ///
+    /// With wrapping enabled the cursor lands on the first display line of the
+    /// previous logical line instead of the previous wrapped segment.
     pub fn move_cursor_up(&mut self, content_width: usize) -> Vec<ViewEvent> {
-        // Check if navigation is allowed on this pane
-        if !self.capabilities.contains(PaneCapabilities::NAVIGABLE) {
-            return vec![]; // Navigation not allowed on this pane
+        let current_row = self.display_cursor.row;
+        let Some(current_line) = self
+            .display_cache
+            .get_display_line(current_row)
+            .map(|display_line| display_line.logical_line)
+        else {
+            return vec![];
+        };
+        if current_line == 0 {
+            return vec![];
         }
 
-        let current_display_pos = self.display_cursor;
-
-        if current_display_pos.row > 0 {
-            let new_line = current_display_pos.row - 1;
-
-            // Vim-style virtual column: try to restore the desired column position
-            let virtual_col = self.virtual_column;
-            let new_col = if let Some(display_line) = self.display_cache.get_display_line(new_line)
-            {
-                let line_char_count = display_line.char_count();
-                let max_col = if self.editor_mode == EditorMode::Insert {
-                    line_char_count // Insert mode: can be positioned after last character
-                } else {
-                    line_char_count.saturating_sub(1) // Normal/Visual: stop at last character
-                };
-                let clamped_col = virtual_col.min(max_col);
-                // Snap to character boundary to handle DBCS characters
-                display_line.snap_to_character_boundary(clamped_col)
-            } else {
-                virtual_col
-            };
-
-            let new_display_pos = Position::new(new_line, new_col);
-            self.display_cursor = new_display_pos;
-
-            // Sync logical cursor with new display position
-            if let Some(logical_pos) = self
-                .display_cache
-                .display_to_logical_position(new_display_pos.row, new_display_pos.col)
-            {
-                let new_logical_pos = LogicalPosition::new(logical_pos.row, logical_pos.col);
-                self.buffer.set_cursor(new_logical_pos);
+        match self
+            .display_cache
+            .logical_to_display_position(current_line - 1, 0)
+        {
+            Some(target) => self.move_cursor_to_display_line(target.row, content_width),
+            None => vec![],
+        }
+    }
 
-                // Update visual selection if active
-                self.update_visual_selection_on_cursor_move(new_logical_pos);
-            }
+    /// Move cursor down one logical line (j) with virtual column support
+    ///
+    /// With wrapping enabled the cursor skips the remaining wrapped segments of
+    /// the current logical line.
+    pub fn move_cursor_down(&mut self, content_width: usize) -> Vec<ViewEvent> {
+        let current_row = self.display_cursor.row;
+        let Some(current_line) = self
+            .display_cache
+            .get_display_line(current_row)
+            .map(|display_line| display_line.logical_line)
+        else {
+            return vec![];
+        };
 
-            let mut events = vec![
-                ViewEvent::ActiveCursorUpdateRequired,
-                ViewEvent::PositionIndicatorUpdateRequired,
-                ViewEvent::CurrentAreaRedrawRequired,
-            ];
+        let next_row = (current_row + 1..self.display_cache.display_line_count()).find(|&row| {
+            self.display_cache
+                .get_display_line(row)
+                .is_some_and(|display_line| display_line.logical_line != current_line)
+        });
 
-            // Ensure cursor is visible and add visibility events
-            let visibility_events = self.ensure_cursor_visible_with_events(content_width);
-            events.extend(visibility_events);
+        match next_row {
+            Some(row) => self.move_cursor_to_display_line(row, content_width),
+            None => vec![],
+        }
+    }
 
-            events
-        } else {
-            vec![]
+    /// Move cursor up one display line (gk), staying within a wrapped logical line
+    pub fn move_cursor_display_line_up(&mut self, content_width: usize) -> Vec<ViewEvent> {
+        match self.display_cursor.row.checked_sub(1) {
+            Some(row) => self.move_cursor_to_display_line(row, content_width),
+            None => vec![],
         }
     }
 
-    /// Move cursor down with capability checking and virtual column support
-    pub fn move_cursor_down(&mut self, content_width: usize) -> Vec<ViewEvent> {
+    /// Move cursor down one display line (gj), staying within a wrapped logical line
+    pub fn move_cursor_display_line_down(&mut self, content_width: usize) -> Vec<ViewEvent> {
+        let next_row = self.display_cursor.row + 1;
+        self.move_cursor_to_display_line(next_row, content_width)
+    }
+
+    /// Move cursor to a display line with capability checking and virtual column support
+    fn move_cursor_to_display_line(
+        &mut self,
+        display_row: usize,
+        content_width: usize,
+    ) -> Vec<ViewEvent> {
         // Check if navigation is allowed on this pane
         if !self.capabilities.contains(PaneCapabilities::NAVIGABLE) {
             return vec![]; // Navigation not allowed on this pane
         }
 
-        let current_display_pos = self.display_cursor;
-        let next_display_line = current_display_pos.row + 1;
-
-        // Check if the next display line actually exists
-        if let Some(display_line) = self.display_cache.get_display_line(next_display_line) {
-            // Vim-style virtual column: try to restore the desired column position
-            let virtual_col = self.virtual_column;
-            let line_char_count = display_line.char_count();
-            let max_col = if self.editor_mode == EditorMode::Insert {
-                line_char_count // Insert mode: can be positioned after last character
-            } else {
-                line_char_count.saturating_sub(1) // Normal/Visual: stop at last character
-            };
-            let clamped_col = virtual_col.min(max_col);
-            // Snap to character boundary to handle DBCS characters
-            let new_col = display_line.snap_to_character_boundary(clamped_col);
-            let new_display_pos = Position::new(next_display_line, new_col);
+        // Check if the target display line actually exists
+        let Some(display_line) = self.display_cache.get_display_line(display_row) else {
+            return vec![];
+        };
 
-            self.display_cursor = new_display_pos;
+        // Vim-style virtual column: try to restore the desired column position
+        let virtual_col = self.virtual_column;
+        let line_char_count = display_line.char_count();
+        let max_col = if self.editor_mode == EditorMode::Insert {
+            line_char_count // Insert mode: can be positioned after last character
+        } else {
+            line_char_count.saturating_sub(1) // Normal/Visual: stop at last character
+        };
+        let clamped_col = virtual_col.min(max_col);
+        // Snap to character boundary to handle DBCS characters
+        let new_col = display_line.snap_to_character_boundary(clamped_col);
+        let new_display_pos = Position::new(display_row, new_col);
 
-            // Sync logical cursor with new display position
-            if let Some(logical_pos) = self
-                .display_cache
-                .display_to_logical_position(new_display_pos.row, new_display_pos.col)
-            {
-                let new_logical_pos = LogicalPosition::new(logical_pos.row, logical_pos.col);
-                self.buffer.set_cursor(new_logical_pos);
+        self.display_cursor = new_display_pos;
 
-                // Update visual selection if active
-                self.update_visual_selection_on_cursor_move(new_logical_pos);
-            }
+        // Sync logical cursor with new display position
+        if let Some(logical_pos) = self
+            .display_cache
+            .display_to_logical_position(new_display_pos.row, new_display_pos.col)
+        {
+            let new_logical_pos = LogicalPosition::new(logical_pos.row, logical_pos.col);
+            self.buffer.set_cursor(new_logical_pos);
 
-            let mut events = vec![
-                ViewEvent::ActiveCursorUpdateRequired,
-                ViewEvent::PositionIndicatorUpdateRequired,
-                ViewEvent::CurrentAreaRedrawRequired,
-            ];
+            // Update visual selection if active
+            self.update_visual_selection_on_cursor_move(new_logical_pos);
+        }
 
-            // Ensure cursor is visible and add visibility events
-            let visibility_events = self.ensure_cursor_visible_with_events(content_width);
-            events.extend(visibility_events);
+        let mut events = vec![
+            ViewEvent::ActiveCursorUpdateRequired,
+            ViewEvent::PositionIndicatorUpdateRequired,
+            ViewEvent::CurrentAreaRedrawRequired,
+        ];
 
-            events
-        } else {
-            vec![]
-        }
+        // Ensure cursor is visible and add visibility events
+        let visibility_events = self.ensure_cursor_visible_with_events(content_width);
+        events.extend(visibility_events);
+
+        events
     }
 
     /// Set cursor to specific position with capability checking
@@ -418,4 +461,66 @@ mod tests {
             "Cursor should be clamped to line length but virtual column preserved"
         );
     }
+
+    fn create_wrapped_pane_state(text: &str) -> PaneState {
+        let mut pane_state = PaneState::new(
+            Pane::Request,
+            10,
+            24,
+            true,
+            PaneCapabilities::EDITABLE | PaneCapabilities::NAVIGABLE,
+        );
+        pane_state.buffer.insert_text(text);
+        pane_state.build_display_cache(10, true, 4);
+        let _ = pane_state.set_current_cursor_position(LogicalPosition::new(0, 3));
+        pane_state
+    }
+
+    #[test]
+    fn move_cursor_display_line_down_should_stay_within_wrapped_line() {
+        // First logical line wraps into "abcdefghij" and "klmnopqrst"
+        let mut pane_state = create_wrapped_pane_state("abcdefghijklmnopqrst\nxyz");
+
+        let events = pane_state.move_cursor_display_line_down(10);
+
+        assert!(!events.is_empty());
+        assert_eq!(pane_state.display_cursor, Position::new(1, 3));
+        assert_eq!(pane_state.buffer.cursor(), LogicalPosition::new(0, 13));
+
+        let _ = pane_state.move_cursor_display_line_up(10);
+        assert_eq!(pane_state.buffer.cursor(), LogicalPosition::new(0, 3));
+    }
+
+    #[test]
+    fn move_cursor_down_should_skip_wrapped_segments() {
+        let mut pane_state = create_wrapped_pane_state("abcdefghijklmnopqrst\nxyz");
+
+        let _ = pane_state.move_cursor_down(10);
+        assert_eq!(pane_state.buffer.cursor(), LogicalPosition::new(1, 2));
+
+        // Virtual column restores the original column on the way back up
+        let _ = pane_state.move_cursor_up(10);
+        assert_eq!(pane_state.buffer.cursor(), LogicalPosition::new(0, 3));
+    }
+
+    #[test]
+    fn move_cursor_right_should_cross_line_end_only_with_wrap() {
+        let mut pane_state = create_wrapped_pane_state("abcdefghijklmnopqrst\nxyz");
+
+        // The segments of a wrapped line are crossed either way
+        let _ = pane_state.set_current_cursor_position(LogicalPosition::new(0, 9));
+        let _ = pane_state.move_cursor_right(10, false);
+        assert_eq!(pane_state.buffer.cursor(), LogicalPosition::new(0, 10));
+
+        let _ = pane_state.set_current_cursor_position(LogicalPosition::new(0, 19));
+        assert!(pane_state.move_cursor_right(10, false).is_empty());
+        assert_eq!(pane_state.buffer.cursor(), LogicalPosition::new(0, 19));
+
+        let _ = pane_state.move_cursor_right(10, true);
+        assert_eq!(pane_state.buffer.cursor(), LogicalPosition::new(1, 0));
+
+        assert!(pane_state.move_cursor_left(10, false).is_empty());
+        let _ = pane_state.move_cursor_left(10, true);
+        assert_eq!(pane_state.buffer.cursor(), LogicalPosition::new(0, 19));
+    }
 }
@@ -0,0 +1,134 @@
+//! Letter marks for PaneState
+//!
+//! This module contains methods for:
+//! - Setting and looking up the marks set with `m`
+//! - Moving marks along with their lines when lines above them are
+//!   inserted or deleted
+
+use crate::repl::events::LogicalPosition;
+use crate::repl::models::BufferLine;
+use std::collections::HashMap;
+
+use super::PaneState;
+
+impl PaneState {
+    /// Remember `position` as `mark`, replacing any position it had
+    pub fn set_mark(&mut self, mark: char, position: LogicalPosition) {
+        self.marks.insert(mark, position);
+    }
+
+    /// Get the position of `mark`, if it is set
+    pub fn get_mark(&self, mark: char) -> Option<LogicalPosition> {
+        self.marks.get(&mark).copied()
+    }
+}
+
+/// Move `marks` along with their lines after `old` lines starting at `first_line` became `new`
+///
+/// Lines both versions start and end with are left alone, so only the lines
+/// that really changed count. Marks below them move by the change in line
+/// count, marks on deleted lines are dropped, and marks on lines that were
+/// edited in place stay where they are.
+pub(super) fn follow_line_changes(
+    marks: &mut HashMap<char, LogicalPosition>,
+    first_line: usize,
+    old: &[BufferLine],
+    new: &[BufferLine],
+) {
+    if marks.is_empty() {
+        return;
+    }
+    let same = |(a, b): (&BufferLine, &BufferLine)| {
+        a.chars()
+            .iter()
+            .map(|c| c.ch)
+            .eq(b.chars().iter().map(|c| c.ch))
+    };
+    let prefix = old.iter().zip(new).take_while(|&pair| same(pair)).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|&pair| same(pair))
+        .count();
+
+    let start = first_line + prefix;
+    let old_end = first_line + old.len() - suffix;
+    let new_end = first_line + new.len() - suffix;
+    marks.retain(|_, position| {
+        if position.line < start {
+            true
+        } else if position.line >= old_end {
+            position.line = position.line - old_end + new_end;
+            true
+        } else if new_end > start {
+            position.line = position.line.min(new_end - 1);
+            true
+        } else {
+            false
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(texts: &[&str]) -> Vec<BufferLine> {
+        texts
+            .iter()
+            .map(|text| BufferLine::from_string(text))
+            .collect()
+    }
+
+    fn marks_on(lines: &[usize]) -> HashMap<char, LogicalPosition> {
+        ('a'..)
+            .zip(lines)
+            .map(|(mark, &line)| (mark, LogicalPosition::new(line, 2)))
+            .collect()
+    }
+
+    fn mark_lines(marks: &HashMap<char, LogicalPosition>) -> Vec<(char, usize)> {
+        let mut lines: Vec<_> = marks
+            .iter()
+            .map(|(&mark, position)| (mark, position.line))
+            .collect();
+        lines.sort();
+        lines
+    }
+
+    #[test]
+    fn follow_line_changes_should_shift_marks_below_inserted_lines() {
+        let mut marks = marks_on(&[0, 1, 2]);
+
+        // `O` on line 1 opens a line above it
+        follow_line_changes(&mut marks, 1, &lines(&["b"]), &lines(&["", "b"]));
+
+        assert_eq!(mark_lines(&marks), vec![('a', 0), ('b', 2), ('c', 3)]);
+        assert_eq!(marks[&'c'].column, 2);
+    }
+
+    #[test]
+    fn follow_line_changes_should_drop_marks_on_deleted_lines() {
+        let mut marks = marks_on(&[0, 1, 2, 3]);
+
+        follow_line_changes(
+            &mut marks,
+            0,
+            &lines(&["a", "b", "c", "d"]),
+            &lines(&["a", "d"]),
+        );
+
+        assert_eq!(mark_lines(&marks), vec![('a', 0), ('d', 1)]);
+    }
+
+    #[test]
+    fn follow_line_changes_should_keep_marks_on_lines_edited_in_place() {
+        let mut marks = marks_on(&[0, 1]);
+
+        // `J` joins both lines into the first
+        follow_line_changes(&mut marks, 0, &lines(&["a", "b"]), &lines(&["a b"]));
+
+        assert_eq!(mark_lines(&marks), vec![('a', 0), ('b', 0)]);
+    }
+}
@@ -25,6 +25,7 @@ use crate::repl::events::{EditorMode, LogicalPosition, Pane, PaneCapabilities};
 use crate::repl::models::geometry::{Dimensions, Position};
 use crate::repl::models::{BufferModel, DisplayCache};
 use crate::repl::view_models::Selection;
+use std::collections::HashMap;
 use std::ops::{Index, IndexMut};
 
 // Re-export all modules
@@ -33,6 +34,7 @@ pub mod content;
 pub mod cursor_basic;
 pub mod cursor_line;
 pub mod display;
+pub mod marks;
 pub mod scrolling;
 pub mod selection_methods;
 pub mod text_operations;
@@ -45,15 +47,6 @@ pub use visual_selection::VisualSelectionRestoreResult;
 /// Minimum width for line number column as specified in requirements
 const MIN_LINE_NUMBER_WIDTH: usize = 3;
 
-/// Information about a wrapped line segment
-#[derive(Debug, Clone)]
-struct WrappedSegment {
-    #[allow(dead_code)] // Used for debug/display purposes
-    content: String,
-    logical_start: usize,
-    logical_end: usize,
-}
-
 /// Type alias for optional position
 pub type OptionalPosition = Option<Position>;
 
@@ -114,6 +107,8 @@ pub struct PaneState {
     pub line_number_width: usize,             // Width needed for line numbers display
     pub virtual_column: usize,                // Vim-style virtual column - desired cursor position
     pub capabilities: PaneCapabilities,       // What operations are allowed on this pane
+    pub display_cache_updates: usize,         // How many times the display cache was updated
+    pub marks: HashMap<char, LogicalPosition>, // Letter marks set with 'm'
 }
 
 impl PaneState {
@@ -140,6 +135,8 @@ impl PaneState {
             line_number_width: MIN_LINE_NUMBER_WIDTH, // Start with minimum width
             virtual_column: 0,               // Start at column 0
             capabilities,                    // Set capabilities based on pane type
+            display_cache_updates: 0,
+            marks: HashMap::new(),
         };
         pane_state.build_display_cache(pane_width, wrap_enabled, 4); // Default tab width, will be updated later
                                                                      // Calculate initial line number width based on content
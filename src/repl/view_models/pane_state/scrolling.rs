@@ -13,10 +13,28 @@ use super::{CursorMoveResult, PaneState, ScrollAdjustResult, ScrollResult};
 
 impl PaneState {
     /// Handle horizontal scrolling within this pane
+    ///
+    /// Only the scroll offset changes, so the display cache is left as it is.
+    /// Wrapped lines always fit the pane, so nothing scrolls while wrapping;
+    /// otherwise the view stops once the end of the cursor line reaches the
+    /// left edge, keeping the cursor on screen.
     pub fn scroll_horizontally(&mut self, direction: i32, amount: usize) -> ScrollResult {
         let old_offset = self.scroll_offset.col; // horizontal offset
+        if self.display_cache.wrap_enabled {
+            return ScrollResult {
+                old_offset,
+                new_offset: old_offset,
+                cursor_moved: false,
+            };
+        }
+
+        let cursor_line_width = self
+            .display_cache
+            .logical_to_display_position(self.buffer.cursor().line, 0)
+            .and_then(|display_pos| self.display_cache.get_display_line(display_pos.row))
+            .map_or(0, |display_line| display_line.display_width());
         let new_offset = if direction > 0 {
-            old_offset + amount
+            (old_offset + amount).min(cursor_line_width.saturating_sub(1).max(old_offset))
         } else {
             old_offset.saturating_sub(amount)
         };
@@ -9,7 +9,9 @@
 use crate::repl::events::{
     EditorMode, LogicalPosition, LogicalRange, ModelEvent, PaneCapabilities, ViewEvent,
 };
+use crate::repl::models::display_char::char_width;
 use crate::repl::models::geometry::Position;
+use std::ops::Range;
 
 use super::PaneState;
 
@@ -180,19 +182,43 @@ impl PaneState {
         content_width: usize,
         wrap_enabled: bool,
         tab_width: usize,
+    ) -> Vec<ViewEvent> {
+        self.insert_text(&ch.to_string(), content_width, wrap_enabled, tab_width)
+    }
+
+    /// Insert text at current cursor position with capability checking
+    ///
+    /// The whole text goes into the buffer before the display cache is rebuilt,
+    /// so a burst of typed or pasted characters costs one rebuild, not one per
+    /// character.
+    ///
+    /// # Returns
+    /// Vector of ViewEvents to update the display, or empty if operation not allowed
+    pub fn insert_text(
+        &mut self,
+        text: &str,
+        content_width: usize,
+        wrap_enabled: bool,
+        tab_width: usize,
     ) -> Vec<ViewEvent> {
         // Check if editing is allowed on this pane
         if !self.capabilities.contains(PaneCapabilities::EDITABLE) {
             return vec![]; // Editing not allowed on this pane
         }
 
-        // Insert character into buffer
-        let _event = self.buffer.insert_char(ch);
+        // Insert text into buffer
+        let edited_line = self.buffer.cursor().line;
+        let _event = self.buffer.insert_text(text);
 
-        // Rebuild display cache to ensure rendering sees the updated content
-        self.build_display_cache(content_width, wrap_enabled, tab_width);
+        // Relay out the cursor line and any lines the text split off from it
+        self.update_display_lines(
+            edited_line..edited_line + 1,
+            content_width,
+            wrap_enabled,
+            tab_width,
+        );
 
-        // Sync display cursor after cache rebuild
+        // Sync display cursor after cache update
         let logical = self.buffer.cursor();
         if let Some(display_pos) = self
             .display_cache
@@ -786,7 +812,13 @@ impl PaneState {
         );
 
         // Rebuild display cache and sync cursor
-        self.rebuild_display_and_sync_cursor(new_cursor, content_width, wrap_enabled, tab_width);
+        self.rebuild_display_and_sync_cursor(
+            current_cursor.line..current_cursor.line + 1,
+            new_cursor,
+            content_width,
+            wrap_enabled,
+            tab_width,
+        );
 
         vec![
             ViewEvent::RequestContentChanged,
@@ -827,6 +859,7 @@ impl PaneState {
 
         // Rebuild display cache and sync cursor
         self.rebuild_display_and_sync_cursor(
+            current_cursor.line..current_cursor.line + 1,
             current_cursor,
             content_width,
             wrap_enabled,
@@ -840,6 +873,222 @@ impl PaneState {
         ]
     }
 
+    /// Delete from cursor back to the start of the previous word (Ctrl-w)
+    ///
+    /// Whitespace before the cursor is removed along with the word, as in a shell.
+    /// At the beginning of a line the line is joined with the previous one.
+    pub fn delete_word_before_cursor(
+        &mut self,
+        content_width: usize,
+        wrap_enabled: bool,
+        tab_width: usize,
+    ) -> Vec<ViewEvent> {
+        // Check if editing is allowed on this pane
+        if !self.capabilities.contains(PaneCapabilities::EDITABLE) {
+            return vec![]; // Editing not allowed on this pane
+        }
+
+        let current_cursor = self.buffer.cursor();
+        if current_cursor.column == 0 {
+            if current_cursor.line == 0 {
+                return vec![]; // Nothing before the cursor
+            }
+            return self.join_with_previous_line(
+                current_cursor,
+                content_width,
+                wrap_enabled,
+                tab_width,
+            );
+        }
+
+        let word_start = self
+            .buffer
+            .content()
+            .character_buffer()
+            .get_line(current_cursor.line)
+            .and_then(|line| line.find_previous_word_start(current_cursor.column))
+            .unwrap_or(0);
+
+        // Delete the whole span in one operation
+        let new_cursor = LogicalPosition::new(current_cursor.line, word_start);
+        let delete_range = LogicalRange::new(new_cursor, current_cursor);
+        let pane_type = self.buffer.pane();
+        let Some(_event) = self
+            .buffer
+            .content_mut()
+            .delete_range(pane_type, delete_range)
+        else {
+            return vec![];
+        };
+
+        tracing::debug!(
+            "🗑️  Deleted word before cursor, new cursor: {:?}",
+            new_cursor
+        );
+
+        self.buffer.set_cursor(new_cursor);
+        self.rebuild_display_and_sync_cursor(
+            current_cursor.line..current_cursor.line + 1,
+            new_cursor,
+            content_width,
+            wrap_enabled,
+            tab_width,
+        );
+
+        vec![
+            ViewEvent::RequestContentChanged,
+            ViewEvent::ActiveCursorUpdateRequired,
+            ViewEvent::CurrentAreaRedrawRequired,
+        ]
+    }
+
+    /// Delete from cursor back to the first non-blank character (Ctrl-u)
+    ///
+    /// Leading indentation is kept unless the cursor is already inside it,
+    /// in which case the deletion extends to column 0. The span is removed in
+    /// a single edit. Does nothing at column 0.
+    pub fn delete_to_line_start(
+        &mut self,
+        content_width: usize,
+        wrap_enabled: bool,
+        tab_width: usize,
+    ) -> Vec<ViewEvent> {
+        // Check if editing is allowed on this pane
+        if !self.capabilities.contains(PaneCapabilities::EDITABLE) {
+            return vec![]; // Editing not allowed on this pane
+        }
+
+        let current_cursor = self.buffer.cursor();
+        if current_cursor.column == 0 {
+            return vec![]; // Nothing before the cursor
+        }
+
+        let first_non_blank = self
+            .buffer
+            .content()
+            .get_line(current_cursor.line)
+            .map(|line| line.chars().take_while(|ch| ch.is_whitespace()).count())
+            .unwrap_or(0);
+        let start_column = if current_cursor.column > first_non_blank {
+            first_non_blank
+        } else {
+            0
+        };
+
+        let new_cursor = LogicalPosition::new(current_cursor.line, start_column);
+        let delete_range = LogicalRange::new(new_cursor, current_cursor);
+        let pane_type = self.buffer.pane();
+        let Some(_event) = self
+            .buffer
+            .content_mut()
+            .delete_range(pane_type, delete_range)
+        else {
+            return vec![];
+        };
+
+        tracing::debug!("🗑️  Deleted to line start, new cursor: {:?}", new_cursor);
+
+        self.buffer.set_cursor(new_cursor);
+        self.rebuild_display_and_sync_cursor(
+            current_cursor.line..current_cursor.line + 1,
+            new_cursor,
+            content_width,
+            wrap_enabled,
+            tab_width,
+        );
+
+        vec![
+            ViewEvent::RequestContentChanged,
+            ViewEvent::ActiveCursorUpdateRequired,
+            ViewEvent::CurrentAreaRedrawRequired,
+        ]
+    }
+
+    /// Join current line with next line, separating them vim-style (J command)
+    ///
+    /// Leading whitespace of the next line is removed and replaced by a separator:
+    /// none when the next line is empty or starts with `)`, two spaces after `.`, `!`
+    /// or `?` when `join_spaces` is on, and a single space otherwise.
+    /// The cursor is placed at the join point.
+    pub fn join_lines(
+        &mut self,
+        join_spaces: bool,
+        content_width: usize,
+        wrap_enabled: bool,
+        tab_width: usize,
+    ) -> Vec<ViewEvent> {
+        // Check if editing is allowed on this pane
+        if !self.capabilities.contains(PaneCapabilities::EDITABLE) {
+            return vec![]; // Editing not allowed on this pane
+        }
+
+        let current_cursor = self.buffer.cursor();
+        let content = self.buffer.content();
+        let (Some(current_line), Some(next_line)) = (
+            content.get_line(current_cursor.line),
+            content.get_line(current_cursor.line + 1),
+        ) else {
+            tracing::debug!("🔗 No next line to join with");
+            return vec![];
+        };
+
+        let line_length = current_line.chars().count();
+        let leading_whitespace = next_line
+            .chars()
+            .take_while(|ch| ch.is_whitespace())
+            .count();
+        let separator = join_separator(
+            &current_line,
+            &next_line
+                .chars()
+                .skip(leading_whitespace)
+                .collect::<String>(),
+            join_spaces,
+        );
+
+        // Remove the newline together with the next line's indentation
+        let delete_range = LogicalRange::new(
+            LogicalPosition::new(current_cursor.line, line_length),
+            LogicalPosition::new(current_cursor.line + 1, leading_whitespace),
+        );
+        let pane_type = self.buffer.pane();
+        let Some(_event) = self
+            .buffer
+            .content_mut()
+            .delete_range(pane_type, delete_range)
+        else {
+            return vec![];
+        };
+
+        let join_point = LogicalPosition::new(current_cursor.line, line_length);
+        if !separator.is_empty() {
+            self.buffer
+                .content_mut()
+                .insert_text(pane_type, join_point, separator);
+        }
+
+        tracing::debug!(
+            "🔗 Joined lines with separator {:?}, cursor at: {:?}",
+            separator,
+            join_point
+        );
+
+        self.buffer.set_cursor(join_point);
+        self.rebuild_display_and_sync_cursor(
+            current_cursor.line..current_cursor.line + 2,
+            join_point,
+            content_width,
+            wrap_enabled,
+            tab_width,
+        );
+
+        vec![
+            ViewEvent::RequestContentChanged,
+            ViewEvent::ActiveCursorUpdateRequired,
+            ViewEvent::CurrentAreaRedrawRequired,
+        ]
+    }
+
     /// Join current line with previous line (backspace at beginning of line)
     fn join_with_previous_line(
         &mut self,
@@ -880,7 +1129,13 @@ impl PaneState {
         tracing::debug!("🗑️  Joined lines, new cursor: {:?}", new_cursor);
 
         // Rebuild display cache and sync cursor
-        self.rebuild_display_and_sync_cursor(new_cursor, content_width, wrap_enabled, tab_width);
+        self.rebuild_display_and_sync_cursor(
+            current_cursor.line - 1..current_cursor.line + 1,
+            new_cursor,
+            content_width,
+            wrap_enabled,
+            tab_width,
+        );
 
         vec![
             ViewEvent::RequestContentChanged,
@@ -919,6 +1174,7 @@ impl PaneState {
 
         // Rebuild display cache and sync cursor
         self.rebuild_display_and_sync_cursor(
+            current_cursor.line..current_cursor.line + 2,
             current_cursor,
             content_width,
             wrap_enabled,
@@ -932,18 +1188,19 @@ impl PaneState {
         ]
     }
 
-    /// Helper to rebuild display cache and sync cursor position
+    /// Helper to update the display cache for the edited lines and sync cursor position
     fn rebuild_display_and_sync_cursor(
         &mut self,
+        edited_lines: Range<usize>,
         new_cursor: LogicalPosition,
         content_width: usize,
         wrap_enabled: bool,
         tab_width: usize,
     ) {
-        // Rebuild display cache since content changed
-        self.build_display_cache(content_width, wrap_enabled, tab_width);
+        // Relay out the lines that changed
+        self.update_display_lines(edited_lines, content_width, wrap_enabled, tab_width);
 
-        // Sync display cursor with new logical position after cache rebuild
+        // Sync display cursor with new logical position after cache update
         match self
             .display_cache
             .logical_to_display_position(new_cursor.line, new_cursor.column)
@@ -1089,6 +1346,7 @@ impl PaneState {
         &mut self,
         start_position: LogicalPosition,
         block_lines: &[&str],
+        block_width: usize,
     ) -> Vec<ViewEvent> {
         if block_lines.is_empty() {
             return vec![];
@@ -1135,10 +1393,21 @@ impl PaneState {
                     .insert_text(pane_type, line_end, &padding);
             }
 
+            // Pad short block rows to the block width when text follows the insertion
+            // point, so the rest of the line stays aligned like the other rows
+            let row_length: usize = line_content.chars().map(char_width).sum();
+            let has_trailing_text =
+                self.buffer.content().line_length(target_position.line) > target_position.column;
+            let row = if has_trailing_text && row_length < block_width {
+                format!("{line_content}{}", " ".repeat(block_width - row_length))
+            } else {
+                line_content.to_string()
+            };
+
             // Insert the line content at the target position
             self.buffer
                 .content_mut()
-                .insert_text(pane_type, target_position, line_content);
+                .insert_text(pane_type, target_position, &row);
         }
 
         // Restore original cursor position (block paste shouldn't move cursor)
@@ -1164,12 +1433,28 @@ impl PaneState {
     }
 }
 
+/// Choose the text inserted between two lines joined by the J command
+fn join_separator(current_line: &str, next_line: &str, join_spaces: bool) -> &'static str {
+    if next_line.is_empty()
+        || next_line.starts_with(')')
+        || current_line.is_empty()
+        || current_line.ends_with(char::is_whitespace)
+    {
+        ""
+    } else if join_spaces && current_line.ends_with(['.', '!', '?']) {
+        "  "
+    } else {
+        " "
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::repl::events::{EditorMode, LogicalPosition, Pane, PaneCapabilities};
     use crate::repl::models::geometry::{Dimensions, Position};
     use crate::repl::models::{BufferModel, DisplayCache};
+    use std::collections::HashMap;
 
     fn create_test_pane_state_with_content(content: &str) -> PaneState {
         let mut buffer = BufferModel::new(Pane::Request);
@@ -1195,6 +1480,8 @@ mod tests {
                 | PaneCapabilities::SELECTABLE,
             line_number_width: 3,
             virtual_column: 0,
+            display_cache_updates: 0,
+            marks: HashMap::new(),
         }
     }
 
@@ -1271,4 +1558,174 @@ mod tests {
         let result = pane_state.get_selected_text();
         assert_eq!(result, Some("あい".to_string())); // Should clamp to line length
     }
+
+    #[test]
+    fn test_join_lines_after_period_with_join_spaces_off() {
+        let mut pane_state = create_test_pane_state_with_content("End.\n    Next");
+
+        pane_state.join_lines(false, 80, false, 4);
+
+        assert_eq!(pane_state.buffer.content().get_text(), "End. Next");
+        assert_eq!(pane_state.buffer.cursor(), LogicalPosition::new(0, 4));
+    }
+
+    #[test]
+    fn test_join_lines_after_period_with_join_spaces_on() {
+        let mut pane_state = create_test_pane_state_with_content("End.\n    Next");
+
+        pane_state.join_lines(true, 80, false, 4);
+
+        assert_eq!(pane_state.buffer.content().get_text(), "End.  Next");
+    }
+
+    #[test]
+    fn test_join_lines_without_sentence_end_ignores_join_spaces() {
+        let mut pane_state = create_test_pane_state_with_content("foo\nbar");
+
+        pane_state.join_lines(true, 80, false, 4);
+
+        assert_eq!(pane_state.buffer.content().get_text(), "foo bar");
+    }
+
+    #[test]
+    fn test_join_lines_before_closing_paren_inserts_no_space() {
+        let mut pane_state = create_test_pane_state_with_content("(foo\n  )");
+
+        pane_state.join_lines(false, 80, false, 4);
+
+        assert_eq!(pane_state.buffer.content().get_text(), "(foo)");
+    }
+
+    #[test]
+    fn test_join_lines_on_last_line_does_nothing() {
+        let mut pane_state = create_test_pane_state_with_content("only line");
+
+        let events = pane_state.join_lines(false, 80, false, 4);
+
+        assert!(events.is_empty());
+        assert_eq!(pane_state.buffer.content().get_text(), "only line");
+    }
+
+    #[test]
+    fn test_delete_word_before_cursor_removes_trailing_word() {
+        let mut pane_state = create_test_pane_state_with_content("GET /api users");
+        pane_state.buffer.set_cursor(LogicalPosition::new(0, 14));
+
+        pane_state.delete_word_before_cursor(80, false, 4);
+
+        assert_eq!(pane_state.buffer.content().get_text(), "GET /api ");
+        assert_eq!(pane_state.buffer.cursor(), LogicalPosition::new(0, 9));
+    }
+
+    #[test]
+    fn test_delete_word_before_cursor_includes_whitespace_before_cursor() {
+        let mut pane_state = create_test_pane_state_with_content("hello world  ");
+        pane_state.buffer.set_cursor(LogicalPosition::new(0, 13));
+
+        pane_state.delete_word_before_cursor(80, false, 4);
+
+        assert_eq!(pane_state.buffer.content().get_text(), "hello ");
+    }
+
+    #[test]
+    fn test_delete_word_before_cursor_stops_at_line_start() {
+        let mut pane_state = create_test_pane_state_with_content("first\nsecond");
+        pane_state.buffer.set_cursor(LogicalPosition::new(1, 6));
+
+        pane_state.delete_word_before_cursor(80, false, 4);
+
+        assert_eq!(pane_state.buffer.content().get_text(), "first\n");
+        assert_eq!(pane_state.buffer.cursor(), LogicalPosition::new(1, 0));
+    }
+
+    #[test]
+    fn test_delete_word_before_cursor_at_line_start_joins_previous_line() {
+        let mut pane_state = create_test_pane_state_with_content("first\nsecond");
+        pane_state.buffer.set_cursor(LogicalPosition::new(1, 0));
+
+        pane_state.delete_word_before_cursor(80, false, 4);
+
+        assert_eq!(pane_state.buffer.content().get_text(), "firstsecond");
+        assert_eq!(pane_state.buffer.cursor(), LogicalPosition::new(0, 5));
+    }
+
+    #[test]
+    fn test_delete_word_before_cursor_at_buffer_start_is_noop() {
+        let mut pane_state = create_test_pane_state_with_content("first");
+
+        let events = pane_state.delete_word_before_cursor(80, false, 4);
+
+        assert!(events.is_empty());
+        assert_eq!(pane_state.buffer.content().get_text(), "first");
+    }
+
+    #[test]
+    fn test_delete_to_line_start_clears_typed_text() {
+        let mut pane_state = create_test_pane_state_with_content("GET /api/users");
+        pane_state.buffer.set_cursor(LogicalPosition::new(0, 14));
+
+        pane_state.delete_to_line_start(80, false, 4);
+
+        assert_eq!(pane_state.buffer.content().get_text(), "");
+        assert_eq!(pane_state.buffer.cursor(), LogicalPosition::new(0, 0));
+    }
+
+    #[test]
+    fn test_delete_to_line_start_keeps_leading_indentation() {
+        let mut pane_state = create_test_pane_state_with_content("{\n  \"name\": 1");
+        pane_state.buffer.set_cursor(LogicalPosition::new(1, 11));
+
+        pane_state.delete_to_line_start(80, false, 4);
+
+        assert_eq!(pane_state.buffer.content().get_text(), "{\n  ");
+        assert_eq!(pane_state.buffer.cursor(), LogicalPosition::new(1, 2));
+
+        // A second Ctrl-u from inside the indentation clears to column 0
+        pane_state.delete_to_line_start(80, false, 4);
+
+        assert_eq!(pane_state.buffer.content().get_text(), "{\n");
+        assert_eq!(pane_state.buffer.cursor(), LogicalPosition::new(1, 0));
+    }
+
+    #[test]
+    fn test_delete_to_line_start_at_column_zero_is_noop() {
+        let mut pane_state = create_test_pane_state_with_content("first\nsecond");
+        pane_state.buffer.set_cursor(LogicalPosition::new(1, 0));
+
+        let events = pane_state.delete_to_line_start(80, false, 4);
+
+        assert!(events.is_empty());
+        assert_eq!(pane_state.buffer.content().get_text(), "first\nsecond");
+    }
+
+    #[test]
+    fn test_edits_patch_display_cache_to_match_full_rebuild() {
+        let content: Vec<String> = (0..1000).map(|i| format!("line {i}")).collect();
+        let mut pane_state = create_test_pane_state_with_content(&content.join("\n"));
+        pane_state.build_display_cache(20, true, 4);
+
+        pane_state.buffer.set_cursor(LogicalPosition::new(4, 6));
+        pane_state.insert_text(" now wraps\nonto two lines", 20, true, 4);
+        pane_state.buffer.set_cursor(LogicalPosition::new(5, 0));
+        pane_state.delete_char_before_cursor(20, true, 4);
+
+        let mut rebuilt =
+            create_test_pane_state_with_content(&pane_state.buffer.content().get_text());
+        rebuilt.build_display_cache(20, true, 4);
+        let patched = &pane_state.display_cache;
+        let expected = &rebuilt.display_cache;
+        assert_eq!(patched.display_line_count(), expected.display_line_count());
+        for row in 0..expected.display_line_count() {
+            let (patched_line, expected_line) = (
+                patched.get_display_line(row).unwrap(),
+                expected.get_display_line(row).unwrap(),
+            );
+            assert_eq!(patched_line.content(), expected_line.content());
+            assert_eq!(patched_line.logical_line, expected_line.logical_line);
+        }
+        assert_eq!(
+            patched.logical_to_display_position(999, 3),
+            expected.logical_to_display_position(999, 3)
+        );
+    }
 }
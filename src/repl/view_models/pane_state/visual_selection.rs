@@ -7,6 +7,7 @@
 //! - Updating selections during cursor movement
 
 use crate::repl::events::{EditorMode, LogicalPosition, PaneCapabilities, ViewEvent};
+use crate::repl::models::LineSpan;
 
 use super::PaneState;
 
@@ -98,6 +99,13 @@ impl PaneState {
         (self.visual_selection_start, self.visual_selection_end)
     }
 
+    /// Get the first and last line of the last visual selection (`'<` and `'>`)
+    pub fn last_visual_line_range(&self) -> Option<LineSpan> {
+        let start = self.last_visual_selection_start?;
+        let end = self.last_visual_selection_end?;
+        Some((start.line.min(end.line), start.line.max(end.line)))
+    }
+
     /// Check if a position is within the current visual selection
     pub fn is_position_selected(&self, position: LogicalPosition) -> bool {
         // Early return if no selection exists
@@ -15,6 +15,9 @@ impl ViewModel {
         let event_vec: Vec<ViewEvent> = events.into_iter().collect();
         if !event_vec.is_empty() {
             for event in event_vec {
+                if event == ViewEvent::RequestContentChanged {
+                    self.request_modified = true;
+                }
                 self.pending_view_events.push(event);
                 tracing::debug!("View event emitted: {:?}", self.pending_view_events.last());
             }
@@ -0,0 +1,608 @@
+//! # Search Management
+//!
+//! Handles `/` and `?` search input: collecting the query, previewing matches
+//! while typing when `incsearch` is enabled, and restoring the cursor on cancel.
+//! After an operator (`d/`, `y/`, `c/`) the search acts as its motion.
+//! `n` and `N` repeat the last search using a cached list of its matches,
+//! reporting when the search wraps around the end of the buffer. In large
+//! buffers the list is built on a background thread as soon as the search is
+//! made, so the first `n` does not have to scan.
+
+use crate::repl::commands::Operator;
+use crate::repl::error::AppError;
+use crate::repl::events::{EditorMode, LogicalPosition, Pane, ViewEvent};
+use crate::repl::text::search::{char_offset, find_backward, find_forward, SearchMatches};
+use crate::repl::view_models::core::{CachedSearchMatches, ViewModel};
+use crate::repl::view_models::YankType;
+use anyhow::Result;
+use std::thread::JoinHandle;
+
+/// Buffers at least this large have their matches found in the background
+const SEARCH_PREFETCH_MIN_BYTES: usize = 64 * 1024;
+
+/// Matches of a pattern being found on a background thread
+pub(super) struct SearchPrefetch {
+    pane: Pane,
+    updates: usize,
+    pattern: String,
+    handle: JoinHandle<SearchMatches>,
+}
+
+impl ViewModel {
+    /// Get the search query typed so far
+    pub fn get_search_buffer(&self) -> &str {
+        &self.search_buffer
+    }
+
+    /// Check whether matches are previewed while typing a search query
+    pub fn is_incsearch_enabled(&self) -> bool {
+        self.incsearch_enabled
+    }
+
+    /// Check whether the search being typed, or else the last one, goes forward
+    pub fn is_search_forward(&self) -> bool {
+        self.search_forward
+    }
+
+    /// Enter search mode, remembering the cursor position to search from
+    ///
+    /// `forward` is false for `?`, which searches toward the start of the buffer.
+    pub fn start_search(&mut self, forward: bool) -> Result<()> {
+        self.search_origin = Some(self.get_cursor_position());
+        self.search_buffer.clear();
+        self.search_forward = forward;
+        self.change_mode(EditorMode::Search)
+    }
+
+    /// Enter search mode as the motion of `operator` (`d/pattern<CR>`)
+    pub fn start_operator_search(&mut self, operator: Operator) -> Result<()> {
+        if !self.is_in_request_pane() {
+            return Ok(());
+        }
+        self.start_search(true)?;
+        self.search_operator = Some(operator);
+        Ok(())
+    }
+
+    /// Add character to the search query
+    pub fn add_search_char(&mut self, ch: char) -> Result<()> {
+        self.search_buffer.push(ch);
+        self.emit_view_event([ViewEvent::StatusBarUpdateRequired])?;
+        self.preview_search()
+    }
+
+    /// Remove last character from the search query
+    pub fn backspace_search(&mut self) -> Result<()> {
+        self.search_buffer.pop();
+        self.emit_view_event([ViewEvent::StatusBarUpdateRequired])?;
+        self.preview_search()
+    }
+
+    /// Finish the search and move the cursor to the first match after the origin,
+    /// or before it for `?`
+    ///
+    /// After an operator, the text from the origin up to the match start is
+    /// operated on instead.
+    pub fn execute_search(&mut self) -> Result<()> {
+        let origin = self.take_search_origin();
+        let pattern = std::mem::take(&mut self.search_buffer);
+        let operator = self.search_operator.take();
+        self.change_mode(self.get_previous_mode())?;
+        if !pattern.is_empty() {
+            self.last_search_pattern = Some(pattern.clone());
+            self.prefetch_search_matches(&pattern);
+        }
+
+        match self.find_search_match(&pattern, origin) {
+            Some(position) => {
+                self.report_search_wrap(self.search_forward, origin, position);
+                match operator {
+                    Some(operator) => self.apply_operator(operator, origin, position),
+                    None => self.set_cursor_position(position),
+                }
+            }
+            None => {
+                self.set_cursor_position(origin)?;
+                if !pattern.is_empty() {
+                    self.set_status_message(format!("Pattern not found: {pattern}"));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Jump to the next match of the last search (`n`), or the previous one (`N`)
+    ///
+    /// Next and previous follow the direction of the last search, so after `?`
+    /// `n` goes backward. Both wrap around the end of the buffer.
+    pub fn search_next(&mut self, forward: bool) -> Result<()> {
+        let Some(pattern) = self.last_search_pattern.clone() else {
+            self.set_error_message(&AppError::User(
+                "E35: No previous regular expression".to_string(),
+            ));
+            return Ok(());
+        };
+
+        let forward = forward == self.search_forward;
+        let cursor = self.get_cursor_position();
+        let matches = self.search_matches_for(&pattern);
+        let target = if forward {
+            matches.next_after(cursor.line, cursor.column)
+        } else {
+            matches.previous_before(cursor.line, cursor.column)
+        };
+
+        match target {
+            Some((line, column)) => {
+                let position = LogicalPosition::new(line, column);
+                self.report_search_wrap(forward, cursor, position);
+                self.set_cursor_position(position)
+            }
+            None => {
+                self.set_status_message(format!("Pattern not found: {pattern}"));
+                Ok(())
+            }
+        }
+    }
+
+    /// Get the matches of `pattern` in the current pane, scanning only when needed
+    ///
+    /// The cached matches are reused until the pattern or pane changes, or the
+    /// pane's display cache is updated, which every edit does.
+    fn search_matches_for(&mut self, pattern: &str) -> &SearchMatches {
+        let pane = self.get_current_pane();
+        let updates = self.pane_manager.display_cache_update_count(pane);
+        let cached = self
+            .search_matches
+            .take()
+            .filter(|(cached_pane, cached_updates, matches)| {
+                *cached_pane == pane && *cached_updates == updates && matches.pattern() == pattern
+            });
+        let entry = cached
+            .or_else(|| self.take_prefetched_matches(pane, updates, pattern))
+            .unwrap_or_else(|| {
+                let text = if pane == Pane::Request {
+                    self.get_request_text()
+                } else {
+                    self.get_response_text()
+                };
+                (pane, updates, SearchMatches::new(&text, pattern))
+            });
+
+        let (_, _, matches) = self.search_matches.insert(entry);
+        matches
+    }
+
+    /// Start finding every match of `pattern` in a large current pane in the background
+    ///
+    /// Smaller panes are scanned when `n` or `N` first needs the matches.
+    fn prefetch_search_matches(&mut self, pattern: &str) {
+        self.search_prefetch = None;
+        let text = self.pane_manager.get_current_text();
+        if text.len() < SEARCH_PREFETCH_MIN_BYTES {
+            return;
+        }
+
+        let pane = self.get_current_pane();
+        let thread_pattern = pattern.to_string();
+        self.search_prefetch = Some(SearchPrefetch {
+            pane,
+            updates: self.pane_manager.display_cache_update_count(pane),
+            pattern: pattern.to_string(),
+            handle: std::thread::spawn(move || SearchMatches::new(&text, &thread_pattern)),
+        });
+    }
+
+    /// Take the background matches if they were found for this pattern and pane state
+    ///
+    /// Waits for the scan when it has not finished yet. Matches of text that
+    /// was edited since are dropped.
+    fn take_prefetched_matches(
+        &mut self,
+        pane: Pane,
+        updates: usize,
+        pattern: &str,
+    ) -> Option<CachedSearchMatches> {
+        let prefetch = self.search_prefetch.take()?;
+        if prefetch.pane != pane || prefetch.updates != updates || prefetch.pattern != pattern {
+            return None;
+        }
+        let matches = prefetch.handle.join().ok()?;
+        Some((pane, updates, matches))
+    }
+
+    /// Abandon the search and return the cursor to where the search started
+    pub fn cancel_search(&mut self) -> Result<()> {
+        let origin = self.take_search_origin();
+        self.search_buffer.clear();
+        self.search_operator = None;
+        self.change_mode(self.get_previous_mode())?;
+        self.set_cursor_position(origin)
+    }
+
+    /// Apply `operator` to the request text between two positions, characterwise
+    ///
+    /// The range is exclusive of its end, and a match that wrapped around to
+    /// before the origin is operated on backwards. The cursor lands on the start
+    /// of the range, in insert mode for `Change`.
+    fn apply_operator(
+        &mut self,
+        operator: Operator,
+        origin: LogicalPosition,
+        target: LogicalPosition,
+    ) -> Result<()> {
+        let (start, end) = if target < origin {
+            (target, origin)
+        } else {
+            (origin, target)
+        };
+        let text = self.get_request_text();
+        let start_offset = char_offset(&text, start.line, start.column);
+        let end_offset = char_offset(&text, end.line, end.column);
+        let covered: String = text
+            .chars()
+            .skip(start_offset)
+            .take(end_offset - start_offset)
+            .collect();
+        self.yank_to_buffer_with_type(covered, YankType::Character)?;
+
+        if operator == Operator::Yank && end_offset > start_offset {
+            // The covered text stops just before `end`
+            let last = match end.column {
+                0 => {
+                    let line = end.line - 1;
+                    let width = text.split('\n').nth(line).map_or(0, |l| l.chars().count());
+                    LogicalPosition::new(line, width)
+                }
+                column => LogicalPosition::new(end.line, column - 1),
+            };
+            self.highlight_yank(Pane::Request, start, last, YankType::Character)?;
+        }
+        if operator != Operator::Yank {
+            let remaining: String = text
+                .chars()
+                .take(start_offset)
+                .chain(text.chars().skip(end_offset))
+                .collect();
+            let mut events = self.pane_manager.set_request_content(&remaining);
+            events.extend(self.pane_manager.rebuild_display_caches_and_sync());
+            events.push(ViewEvent::CurrentAreaRedrawRequired);
+            self.emit_view_event(events)?;
+        }
+        if operator == Operator::Change {
+            self.change_mode(EditorMode::Insert)?;
+        }
+        self.set_cursor_position(start)
+    }
+
+    /// Move the cursor to the match for the current query (incsearch)
+    ///
+    /// The search always starts from the origin so a longer query narrows the
+    /// match instead of skipping ahead. Without a match the cursor goes back to
+    /// the origin.
+    fn preview_search(&mut self) -> Result<()> {
+        if !self.incsearch_enabled {
+            return Ok(());
+        }
+
+        let Some(origin) = self.search_origin else {
+            return Ok(());
+        };
+        let position = self
+            .find_search_match(&self.search_buffer, origin)
+            .unwrap_or(origin);
+        self.set_cursor_position(position)
+    }
+
+    /// Find the first match of `pattern` from `from` in the search direction in the current pane
+    fn find_search_match(&self, pattern: &str, from: LogicalPosition) -> Option<LogicalPosition> {
+        let text = if self.is_in_request_pane() {
+            self.get_request_text()
+        } else {
+            self.get_response_text()
+        };
+
+        let find = if self.search_forward {
+            find_forward
+        } else {
+            find_backward
+        };
+        find(&text, pattern, from.line, from.column)
+            .map(|(line, column)| LogicalPosition::new(line, column))
+    }
+
+    /// Tell the user when a match was only found by wrapping around the buffer
+    fn report_search_wrap(&mut self, forward: bool, from: LogicalPosition, to: LogicalPosition) {
+        if forward && to <= from {
+            self.set_status_message("search hit BOTTOM, continuing at TOP");
+        } else if !forward && to >= from {
+            self.set_status_message("search hit TOP, continuing at BOTTOM");
+        }
+    }
+
+    /// Take the search origin, falling back to the current cursor position
+    fn take_search_origin(&mut self) -> LogicalPosition {
+        self.search_origin
+            .take()
+            .unwrap_or_else(|| self.get_cursor_position())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repl::commands::{Setting, SettingValue};
+
+    fn create_view_model_with_request(text: &str) -> ViewModel {
+        let mut vm = ViewModel::new();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text(text).unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+        vm.set_cursor_position(LogicalPosition::new(0, 0)).unwrap();
+        vm
+    }
+
+    fn type_query(vm: &mut ViewModel, query: &str) {
+        for ch in query.chars() {
+            vm.add_search_char(ch).unwrap();
+        }
+    }
+
+    #[test]
+    fn incsearch_should_narrow_preview_as_query_grows() {
+        let mut vm = create_view_model_with_request("GET /api/pets\n\n{\"pet\": \"penguin\"}");
+        vm.apply_setting(Setting::IncSearch, SettingValue::On)
+            .unwrap();
+
+        vm.start_search(true).unwrap();
+        type_query(&mut vm, "p");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 6));
+
+        type_query(&mut vm, "e");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 9));
+
+        type_query(&mut vm, "n");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(2, 9));
+
+        vm.execute_search().unwrap();
+        assert_eq!(vm.get_mode(), EditorMode::Normal);
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(2, 9));
+    }
+
+    #[test]
+    fn escape_should_restore_search_origin() {
+        let mut vm = create_view_model_with_request("GET /api/pets\nAccept: */*");
+        vm.apply_setting(Setting::IncSearch, SettingValue::On)
+            .unwrap();
+        vm.set_cursor_position(LogicalPosition::new(0, 2)).unwrap();
+
+        vm.start_search(true).unwrap();
+        type_query(&mut vm, "Accept");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(1, 0));
+
+        vm.cancel_search().unwrap();
+        assert_eq!(vm.get_mode(), EditorMode::Normal);
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 2));
+        assert_eq!(vm.get_search_buffer(), "");
+    }
+
+    #[test]
+    fn search_without_incsearch_should_only_move_on_enter() {
+        let mut vm = create_view_model_with_request("GET /api/pets");
+
+        vm.start_search(true).unwrap();
+        type_query(&mut vm, "pets");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 0));
+
+        vm.execute_search().unwrap();
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 9));
+    }
+
+    #[test]
+    fn search_should_report_missing_pattern() {
+        let mut vm = create_view_model_with_request("GET /api/pets");
+
+        vm.start_search(true).unwrap();
+        type_query(&mut vm, "users");
+        vm.execute_search().unwrap();
+
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 0));
+        assert_eq!(vm.get_status_message(), Some("Pattern not found: users"));
+    }
+
+    #[test]
+    fn delete_search_should_delete_up_to_match() {
+        let mut vm = create_view_model_with_request("foo baz\nqux bar end");
+        vm.set_cursor_position(LogicalPosition::new(0, 4)).unwrap();
+
+        // d/bar<CR>
+        vm.start_operator_search(Operator::Delete).unwrap();
+        type_query(&mut vm, "bar");
+        vm.execute_search().unwrap();
+
+        assert_eq!(vm.get_request_text(), "foo bar end");
+        assert_eq!(vm.get_yanked_text(), Some("baz\nqux ".to_string()));
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 4));
+        assert_eq!(vm.get_mode(), EditorMode::Normal);
+    }
+
+    #[test]
+    fn yank_and_change_search_should_act_up_to_match() {
+        let mut vm = create_view_model_with_request("GET /api/pets?limit=10");
+
+        vm.start_operator_search(Operator::Yank).unwrap();
+        type_query(&mut vm, "?");
+        vm.execute_search().unwrap();
+        assert_eq!(vm.get_yanked_text(), Some("GET /api/pets".to_string()));
+        assert_eq!(vm.get_request_text(), "GET /api/pets?limit=10");
+
+        vm.start_operator_search(Operator::Change).unwrap();
+        type_query(&mut vm, "/pets");
+        vm.execute_search().unwrap();
+        assert_eq!(vm.get_request_text(), "/pets?limit=10");
+        assert_eq!(vm.get_mode(), EditorMode::Insert);
+    }
+
+    #[test]
+    fn operator_search_without_match_should_leave_buffer_alone() {
+        let mut vm = create_view_model_with_request("GET /api/pets");
+
+        vm.start_operator_search(Operator::Delete).unwrap();
+        type_query(&mut vm, "users");
+        vm.execute_search().unwrap();
+
+        assert_eq!(vm.get_request_text(), "GET /api/pets");
+        assert_eq!(vm.get_status_message(), Some("Pattern not found: users"));
+
+        // The operator does not leak into the next plain search
+        vm.start_search(true).unwrap();
+        type_query(&mut vm, "pets");
+        vm.execute_search().unwrap();
+        assert_eq!(vm.get_request_text(), "GET /api/pets");
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 9));
+    }
+
+    #[test]
+    fn search_next_should_cycle_through_matches_both_ways() {
+        let mut vm = create_view_model_with_request("GET /api/pets\n\n{\"pet\": \"pets\"}");
+
+        vm.start_search(true).unwrap();
+        type_query(&mut vm, "pet");
+        vm.execute_search().unwrap();
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 9));
+
+        vm.search_next(true).unwrap();
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(2, 2));
+        vm.search_next(true).unwrap();
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(2, 9));
+        vm.search_next(true).unwrap();
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 9));
+
+        vm.search_next(false).unwrap();
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(2, 9));
+    }
+
+    #[test]
+    fn forward_search_should_report_wrap_both_ways() {
+        let mut vm = create_view_model_with_request("GET /api/pets\n\n{\"pet\": 1}");
+
+        vm.start_search(true).unwrap();
+        type_query(&mut vm, "pet");
+        vm.execute_search().unwrap();
+        vm.search_next(true).unwrap();
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(2, 2));
+        assert_eq!(vm.get_status_message(), None);
+
+        vm.search_next(true).unwrap();
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 9));
+        assert_eq!(
+            vm.get_status_message(),
+            Some("search hit BOTTOM, continuing at TOP")
+        );
+
+        vm.search_next(false).unwrap();
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(2, 2));
+        assert_eq!(
+            vm.get_status_message(),
+            Some("search hit TOP, continuing at BOTTOM")
+        );
+    }
+
+    #[test]
+    fn backward_search_should_reverse_n_and_report_wrap() {
+        let mut vm = create_view_model_with_request("GET /api/pets\n\n{\"名前\": \"pets\"}");
+        vm.set_cursor_position(LogicalPosition::new(2, 3)).unwrap();
+
+        vm.start_search(false).unwrap();
+        assert!(!vm.is_search_forward());
+        type_query(&mut vm, "pets");
+        vm.execute_search().unwrap();
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 9));
+        assert_eq!(vm.get_status_message(), None);
+
+        // `n` keeps going backward and wraps to the last match
+        vm.search_next(true).unwrap();
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(2, 8));
+        assert_eq!(
+            vm.get_status_message(),
+            Some("search hit TOP, continuing at BOTTOM")
+        );
+
+        vm.search_next(false).unwrap();
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 9));
+        assert_eq!(
+            vm.get_status_message(),
+            Some("search hit BOTTOM, continuing at TOP")
+        );
+    }
+
+    #[test]
+    fn search_next_should_rescan_after_edit() {
+        let mut vm = create_view_model_with_request("GET /api/pets\n\n{\"pet\": 1}");
+        vm.start_search(true).unwrap();
+        type_query(&mut vm, "pet");
+        vm.execute_search().unwrap();
+        vm.search_next(true).unwrap();
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(2, 2));
+
+        vm.set_cursor_position(LogicalPosition::new(1, 0)).unwrap();
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("petshop").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+
+        vm.set_cursor_position(LogicalPosition::new(0, 9)).unwrap();
+        vm.search_next(true).unwrap();
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(1, 0));
+        let (_, _, matches) = vm.search_matches.as_ref().unwrap();
+        assert_eq!(matches.positions(), [(0, 9), (1, 0), (2, 2)]);
+    }
+
+    #[test]
+    fn search_should_prefetch_matches_of_large_buffer() {
+        let text = vec!["{\"pet\": 1}"; SEARCH_PREFETCH_MIN_BYTES / 10].join("\n");
+        let mut vm = ViewModel::new();
+        vm.set_request_text(&text).unwrap();
+
+        vm.start_search(true).unwrap();
+        type_query(&mut vm, "pet");
+        vm.execute_search().unwrap();
+        assert!(vm.search_prefetch.is_some());
+
+        vm.search_next(true).unwrap();
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(1, 2));
+        assert!(vm.search_prefetch.is_none());
+        let (_, _, matches) = vm.search_matches.as_ref().unwrap();
+        assert_eq!(matches, &SearchMatches::new(&text, "pet"));
+    }
+
+    #[test]
+    fn prefetched_matches_should_be_dropped_after_edit() {
+        let text = vec!["{\"pet\": 1}"; SEARCH_PREFETCH_MIN_BYTES / 10].join("\n");
+        let mut vm = ViewModel::new();
+        vm.set_request_text(&text).unwrap();
+        vm.start_search(true).unwrap();
+        type_query(&mut vm, "pet");
+        vm.execute_search().unwrap();
+
+        vm.change_mode(EditorMode::Insert).unwrap();
+        vm.insert_text("pet").unwrap();
+        vm.change_mode(EditorMode::Normal).unwrap();
+
+        vm.search_next(true).unwrap();
+        let (_, _, matches) = vm.search_matches.as_ref().unwrap();
+        assert_eq!(matches, &SearchMatches::new(&vm.get_request_text(), "pet"));
+    }
+
+    #[test]
+    fn search_next_without_previous_search_should_report_error() {
+        let mut vm = create_view_model_with_request("GET /api/pets");
+
+        vm.search_next(true).unwrap();
+
+        assert_eq!(vm.get_cursor_position(), LogicalPosition::new(0, 0));
+        assert_eq!(
+            vm.get_status_message(),
+            Some("Error: E35: No previous regular expression")
+        );
+    }
+}
@@ -7,7 +7,79 @@ use crate::repl::events::ViewEvent;
 use crate::repl::view_models::core::ViewModel;
 use anyhow::Result;
 
+/// Format a boolean setting the way `:set` accepts it
+pub fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
+
 impl ViewModel {
+    /// Current values of the settings owned by the ViewModel (for `:set all`)
+    ///
+    /// Each entry is in `:set` syntax, e.g. `wrap on` or `tabstop 4`. Clipboard
+    /// settings live in `YankService` and are added by the controller.
+    pub fn settings_listing(&self) -> Vec<String> {
+        let pane_manager = &self.pane_manager;
+        vec![
+            format!("wrap {}", on_off(pane_manager.is_wrap_enabled())),
+            format!(
+                "responsewrap {}",
+                on_off(pane_manager.is_response_wrap_enabled())
+            ),
+            format!("number {}", on_off(pane_manager.is_line_numbers_visible())),
+            format!("scrollbar {}", on_off(pane_manager.is_scrollbar_visible())),
+            format!(
+                "cursorcolumn {}",
+                on_off(pane_manager.is_cursor_column_visible())
+            ),
+            format!("tabstop {}", pane_manager.get_tab_width()),
+            format!("expandtab {}", on_off(pane_manager.get_expand_tab())),
+            format!("joinspaces {}", on_off(pane_manager.get_join_spaces())),
+            format!("spell {}", on_off(self.is_spell_enabled())),
+            format!("strictheaders {}", on_off(self.is_strict_headers_enabled())),
+            format!("backup {}", on_off(self.is_backup_enabled())),
+            format!(
+                "backupcopy={}",
+                if self.is_backupcopy_enabled() {
+                    "yes"
+                } else {
+                    "no"
+                }
+            ),
+            format!("confirm {}", on_off(self.is_confirm_enabled())),
+            format!("hlyank {}", on_off(self.is_hlyank_enabled())),
+            format!("showmatch {}", on_off(self.is_showmatch_enabled())),
+            format!("incsearch {}", on_off(self.is_incsearch_enabled())),
+            format!("foldrequest {}", on_off(self.is_fold_request_enabled())),
+            format!("maxresponse={}", self.get_max_response_bytes()),
+            format!("equalalways {}", on_off(pane_manager.is_equal_always())),
+            format!("compact {}", on_off(pane_manager.is_compact())),
+            format!("wildmenu {}", on_off(self.wildmenu_enabled)),
+            format!("equalprg={}", self.equalprg.replace(' ', "\\ ")),
+            format!("shell={}", self.shell.replace(' ', "\\ ")),
+            format!("title {}", on_off(self.title_enabled)),
+            format!(
+                "showmode {}",
+                on_off(self.status_line.is_show_mode_enabled())
+            ),
+            format!("titlestring={}", self.titlestring.replace(' ', "\\ ")),
+            format!("whichwrap={}", self.whichwrap),
+            format!("fileencoding={}", self.fileencoding),
+            format!("executekey={}", self.execute_key),
+            format!(
+                "laststatus={}",
+                if pane_manager.is_status_line_visible() {
+                    2
+                } else {
+                    0
+                }
+            ),
+        ]
+    }
+
     /// Apply a setting change from an ex command
     pub fn apply_setting(&mut self, setting: Setting, value: SettingValue) -> Result<()> {
         match setting {
@@ -20,6 +92,15 @@ impl ViewModel {
                 let _ = self.emit_view_event(events);
                 Ok(())
             }
+            Setting::ResponseWrap => {
+                self.pane_manager
+                    .set_response_wrap_enabled(value == SettingValue::On);
+                let visibility_events = self.pane_manager.rebuild_display_caches_and_sync();
+                let mut events = vec![ViewEvent::FullRedrawRequired];
+                events.extend(visibility_events);
+                let _ = self.emit_view_event(events);
+                Ok(())
+            }
             Setting::LineNumbers => {
                 let enable = value == SettingValue::On;
                 self.pane_manager.set_line_numbers_visible(enable);
@@ -29,11 +110,28 @@ impl ViewModel {
                 let _ = self.emit_view_event(events);
                 Ok(())
             }
+            Setting::Scrollbar => {
+                let enable = value == SettingValue::On;
+                self.pane_manager.set_scrollbar_visible(enable);
+                let visibility_events = self.pane_manager.rebuild_display_caches_and_sync();
+                let mut events = vec![ViewEvent::FullRedrawRequired];
+                events.extend(visibility_events);
+                let _ = self.emit_view_event(events);
+                Ok(())
+            }
+            Setting::CursorColumn => {
+                let enable = value == SettingValue::On;
+                self.pane_manager.set_cursor_column_visible(enable);
+                let _ = self.emit_view_event([ViewEvent::FullRedrawRequired]);
+                Ok(())
+            }
             Setting::Clipboard => {
                 let enable = value == SettingValue::On;
                 self.set_clipboard_enabled(enable)?;
                 Ok(())
             }
+            // Clipboard sync lives in YankService, applied by the controller
+            Setting::ClipboardUnnamedPlus => Ok(()),
             Setting::TabStop => {
                 if let SettingValue::Number(width) = value {
                     self.pane_manager.set_tab_width(width);
@@ -44,6 +142,15 @@ impl ViewModel {
                 }
                 Ok(())
             }
+            Setting::LastStatus => {
+                if let SettingValue::Number(value) = value {
+                    self.pane_manager.set_status_line_visible(value != 0);
+                    let (width, height) = self.pane_manager.terminal_dimensions;
+                    self.update_terminal_size(width, height);
+                    let _ = self.emit_view_event([ViewEvent::FullRedrawRequired]);
+                }
+                Ok(())
+            }
             Setting::ExpandTab => {
                 let enable = value == SettingValue::On;
                 self.pane_manager.set_expand_tab(enable);
@@ -58,6 +165,140 @@ impl ViewModel {
                 }
                 Ok(())
             }
+            Setting::JoinSpaces => {
+                let enable = value == SettingValue::On;
+                self.pane_manager.set_join_spaces(enable);
+                Ok(())
+            }
+            Setting::Spell => {
+                self.spell_enabled = value == SettingValue::On;
+                Ok(())
+            }
+            Setting::StrictHeaders => {
+                self.strict_headers_enabled = value == SettingValue::On;
+                Ok(())
+            }
+            Setting::Backup => {
+                self.backup_enabled = value == SettingValue::On;
+                Ok(())
+            }
+            Setting::BackupCopy => {
+                self.backupcopy_enabled = value == SettingValue::On;
+                Ok(())
+            }
+            Setting::Confirm => {
+                self.confirm_enabled = value == SettingValue::On;
+                Ok(())
+            }
+            Setting::HlYank => {
+                self.hlyank_enabled = value == SettingValue::On;
+                if !self.hlyank_enabled {
+                    self.clear_yank_highlight()?;
+                }
+                Ok(())
+            }
+            Setting::ShowMatch => {
+                self.showmatch_enabled = value == SettingValue::On;
+                if !self.showmatch_enabled {
+                    self.clear_match_highlight()?;
+                }
+                Ok(())
+            }
+            // Autosave, autoread and timeouts live in services, applied by the controller
+            Setting::AutoSave
+            | Setting::AutoSaveInterval
+            | Setting::AutoWrite
+            | Setting::AutoRead
+            | Setting::TimeoutLen
+            | Setting::MatchTime => Ok(()),
+            Setting::IncSearch => {
+                self.incsearch_enabled = value == SettingValue::On;
+                Ok(())
+            }
+            Setting::WildMenu => {
+                self.wildmenu_enabled = value == SettingValue::On;
+                Ok(())
+            }
+            Setting::Title => {
+                self.title_enabled = value == SettingValue::On;
+                Ok(())
+            }
+            Setting::ShowMode => {
+                self.status_line.set_show_mode(value == SettingValue::On);
+                let _ = self.emit_view_event([ViewEvent::StatusBarUpdateRequired]);
+                Ok(())
+            }
+            Setting::Compact => {
+                self.pane_manager.set_compact(value == SettingValue::On);
+                let (width, height) = self.pane_manager.terminal_dimensions;
+                self.update_terminal_size(width, height);
+                let _ = self.emit_view_event([ViewEvent::FullRedrawRequired]);
+                Ok(())
+            }
+            Setting::EqualAlways => {
+                self.pane_manager
+                    .set_equal_always(value == SettingValue::On);
+                Ok(())
+            }
+            Setting::FoldRequest => {
+                self.set_fold_request_enabled(value == SettingValue::On);
+                Ok(())
+            }
+            Setting::MaxResponse => {
+                if let SettingValue::Number(bytes) = value {
+                    self.set_max_response_bytes(bytes);
+                }
+                Ok(())
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_listing_should_reflect_changed_values() {
+        let mut vm = ViewModel::new();
+        assert!(vm.settings_listing().contains(&"tabstop 4".to_string()));
+        assert!(vm.settings_listing().contains(&"number on".to_string()));
+
+        vm.apply_setting(Setting::TabStop, SettingValue::Number(2))
+            .unwrap();
+        vm.apply_setting(Setting::LineNumbers, SettingValue::Off)
+            .unwrap();
+
+        let listing = vm.settings_listing();
+        assert!(listing.contains(&"tabstop 2".to_string()));
+        assert!(listing.contains(&"number off".to_string()));
+    }
+
+    #[test]
+    fn compact_should_give_whole_content_area_to_focused_pane() {
+        let mut vm = ViewModel::new();
+        vm.update_terminal_size(80, 24);
+        vm.set_response(200, "{}".to_string());
+
+        vm.apply_setting(Setting::Compact, SettingValue::On)
+            .unwrap();
+        assert_eq!(vm.pane_manager().get_pane_boundaries(true), (23, 23, 0));
+        assert!(vm.settings_listing().contains(&"compact on".to_string()));
+
+        vm.apply_setting(Setting::Compact, SettingValue::Off)
+            .unwrap();
+        assert_eq!(vm.pane_manager().get_pane_boundaries(true), (12, 13, 10));
+    }
+
+    #[test]
+    fn laststatus_zero_should_add_a_content_row() {
+        let mut vm = ViewModel::new();
+        vm.update_terminal_size(80, 24);
+        let height = vm.pane_manager().get_pane_boundaries(false).0;
+
+        vm.apply_setting(Setting::LastStatus, SettingValue::Number(0))
+            .unwrap();
+        assert_eq!(vm.pane_manager().get_pane_boundaries(false).0, height + 1);
+        assert!(vm.settings_listing().contains(&"laststatus=0".to_string()));
+    }
+}
@@ -146,6 +146,22 @@ pub const CURSOR_UNDERLINE_STEADY: &str = "\x1b[3 q"; // Steady underline cursor
 pub const CURSOR_BAR: &str = "\x1b[6 q"; // Blinking bar cursor (I-beam)
 pub const CURSOR_BAR_STEADY: &str = "\x1b[5 q"; // Steady bar cursor (I-beam)
 
+// ============================================================================
+// TERMINAL REPORTING
+// ============================================================================
+
+pub const FOCUS_REPORTING_ON: &str = "\x1b[?1004h"; // Report focus gained/lost events
+pub const FOCUS_REPORTING_OFF: &str = "\x1b[?1004l"; // Stop reporting focus events
+
+// ============================================================================
+// WINDOW TITLE
+// ============================================================================
+
+pub const SAVE_TITLE: &str = "\x1b[22;0t"; // Push the window title onto the terminal's stack
+pub const RESTORE_TITLE: &str = "\x1b[23;0t"; // Pop the saved window title back
+pub const SET_TITLE_START: &str = "\x1b]2;"; // OSC 2: set the window title, ended by BEL
+pub const SET_TITLE_END: &str = "\x07";
+
 // ============================================================================
 // LINE CONTROL
 // ============================================================================
@@ -169,6 +185,39 @@ pub const FG_INFO: &str = FG_BLUE; // Info text
 
 pub const FG_SEPARATOR: &str = FG_256_DEEP_SKY_BLUE; // Pane separator/boundary (blueline)
 
+// Scrollbar (rightmost pane column when `:set scrollbar on`)
+pub const FG_SCROLLBAR_THUMB: &str = FG_SEPARATOR;
+pub const FG_SCROLLBAR_TRACK: &str = FG_DIM_TEXT;
+pub const SCROLLBAR_THUMB: &str = "█";
+pub const SCROLLBAR_TRACK: &str = "│";
+
+// Side-scroll markers at the edges of unwrapped lines with text out of view
+pub const FG_SIDE_SCROLL: &str = FG_DIM_TEXT;
+pub const SIDE_SCROLL_PRECEDES: &str = "<";
+pub const SIDE_SCROLL_EXTENDS: &str = ">";
+
+// Line number gutter of the focused pane, following its mode
+pub const FG_GUTTER_NORMAL: &str = DIM;
+pub const FG_GUTTER_INSERT: &str = FG_SEPARATOR;
+pub const FG_GUTTER_VISUAL: &str = FG_MAGENTA;
+
+// Cursor column highlight (`:set cursorcolumn on`)
+pub const BG_CURSOR_COLUMN: &str = BG_256_VERY_DARK_BLUE;
+
+// Flash over just-yanked text (`:set hlyank on`)
+pub const BG_YANK_HIGHLIGHT: &str = BG_256_STEEL_BLUE;
+
+// Flash over the bracket matching one just typed (`:set showmatch on`)
+pub const BG_MATCH_HIGHLIGHT: &str = BG_256_SLATE_BLUE;
+
+// HTTP method colors in the status line
+pub const FG_METHOD_GET: &str = FG_GREEN;
+pub const FG_METHOD_POST: &str = FG_BLUE;
+pub const FG_METHOD_PUT: &str = FG_YELLOW;
+pub const FG_METHOD_PATCH: &str = FG_MAGENTA;
+pub const FG_METHOD_DELETE: &str = FG_RED;
+pub const FG_METHOD_DEFAULT: &str = FG_CYAN; // HEAD, OPTIONS and unknown methods
+
 // Background semantic colors
 pub const BG_NORMAL: &str = ""; // Normal background (no color change)
 pub const BG_SELECTED: &str = BG_256_DEEP_SKY_BLUE; // Selected background (customize this!)
@@ -192,6 +241,18 @@ pub const STATUS_BULLET_DEFAULT: &str = "● "; // Default bullet
 // HELPER FUNCTIONS (Optional utility functions)
 // ============================================================================
 
+/// Foreground color for an HTTP method in the status line
+pub fn method_color(method: &str) -> &'static str {
+    match method {
+        "GET" => FG_METHOD_GET,
+        "POST" => FG_METHOD_POST,
+        "PUT" => FG_METHOD_PUT,
+        "PATCH" => FG_METHOD_PATCH,
+        "DELETE" => FG_METHOD_DELETE,
+        _ => FG_METHOD_DEFAULT,
+    }
+}
+
 /// Create a 256-color foreground escape code
 pub fn fg_256(color: u8) -> String {
     format!("\x1b[38;5;{color}m")
@@ -3,6 +3,7 @@
 //! Contains all view-related components for rendering the terminal interface.
 
 pub mod ansi_escape_codes;
+pub mod scratch_overlay;
 pub mod terminal_renderer;
 
 // Re-export main types for convenience
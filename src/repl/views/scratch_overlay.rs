@@ -0,0 +1,72 @@
+//! # Scratch Overlay
+//!
+//! Lays out the scratch overlay that listings such as `:messages`,
+//! `:registers` and `:set all` are shown in. The overlay covers the bottom of
+//! the screen above the status line, so the panes underneath keep their
+//! content and come back once it is closed with `q`.
+
+use crate::repl::models::display_char::char_width;
+
+/// Last row of the overlay, telling how to close it
+pub const SCRATCH_CLOSE_HINT: &str = "-- Press q to close --";
+
+/// Lay out the overlay rows for `lines` on a screen `width` columns wide
+///
+/// At most `max_rows` rows are used, the last one for the close hint, so
+/// listings longer than the screen are cut short. Each row is cut to the
+/// width and padded to it so nothing from the panes shows through.
+pub fn overlay_rows(lines: &[String], width: usize, max_rows: usize) -> Vec<String> {
+    if max_rows == 0 {
+        return Vec::new();
+    }
+    lines
+        .iter()
+        .take(max_rows - 1)
+        .map(String::as_str)
+        .chain(std::iter::once(SCRATCH_CLOSE_HINT))
+        .map(|line| fit_to_width(line, width))
+        .collect()
+}
+
+/// Cut `line` to `width` display columns and pad it with spaces up to it
+pub(super) fn fit_to_width(line: &str, width: usize) -> String {
+    let mut fitted = String::new();
+    let mut used = 0;
+    for ch in line.chars() {
+        let ch_width = char_width(ch);
+        if used + ch_width > width {
+            break;
+        }
+        fitted.push(ch);
+        used += ch_width;
+    }
+    fitted.push_str(&" ".repeat(width - used));
+    fitted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlay_rows_should_fit_lines_to_width_and_end_with_hint() {
+        let lines = vec!["--- Registers ---".to_string(), "\"a   日本語".to_string()];
+
+        let rows = overlay_rows(&lines, 8, 10);
+
+        // The wide character that would straddle the edge is left out
+        assert_eq!(rows, vec!["--- Regi", "\"a   日 ", "-- Press"]);
+    }
+
+    #[test]
+    fn overlay_rows_should_leave_room_for_hint_when_screen_is_short() {
+        let lines: Vec<String> = (1..=5).map(|n| n.to_string()).collect();
+
+        let rows = overlay_rows(&lines, 30, 3);
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1].trim_end(), "2");
+        assert_eq!(rows[2].trim_end(), SCRATCH_CLOSE_HINT);
+        assert!(overlay_rows(&lines, 30, 0).is_empty());
+    }
+}
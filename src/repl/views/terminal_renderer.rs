@@ -4,11 +4,146 @@
 //! They subscribe to view events and update the display accordingly.
 
 use crate::repl::events::{EditorMode, Pane, ViewEvent};
-use crate::repl::io::RenderStream;
+use crate::repl::io::{restore_terminal, RenderStream};
+use crate::repl::models::display_char::{caret_notation, char_width};
+use crate::repl::models::yank_buffer::osc52_sequence;
+use crate::repl::text::request_line::RequestTarget;
 use crate::repl::view_models::ViewModel;
 use anyhow::Result;
 // Import ANSI escape codes from the separate module
 use super::ansi_escape_codes as ansi;
+use super::scratch_overlay;
+
+/// Status line segment showing the request method in its color
+fn method_status_segment(method: &str) -> String {
+    format!("{}{method}{}", ansi::method_color(method), ansi::RESET)
+}
+
+/// Shown in the request header when the request line does not parse
+const INVALID_REQUEST_HEADER: &str = "(invalid request)";
+
+/// Request header row: the method in its color and the resolved URL, fitted to `width`
+fn request_header_line(target: Option<&RequestTarget>, width: usize) -> String {
+    let Some(RequestTarget { method, url }) = target else {
+        let text = scratch_overlay::fit_to_width(INVALID_REQUEST_HEADER, width);
+        return format!("{}{text}{}", ansi::DIM, ansi::RESET);
+    };
+    let text = scratch_overlay::fit_to_width(&format!("{method} {url}"), width);
+    match text.strip_prefix(method.as_str()) {
+        Some(rest) => format!("{}{rest}", method_status_segment(method)),
+        None => text,
+    }
+}
+
+/// Status line segment counting the errors not yet seen in `:messages`
+fn error_count_segment(count: usize) -> String {
+    let errors = if count == 1 { "error" } else { "errors" };
+    format!("{}{count} {errors}{}", ansi::FG_ERROR, ansi::RESET)
+}
+
+/// Line number color of the focused pane in the given mode
+fn gutter_color(mode: EditorMode) -> &'static str {
+    match mode {
+        EditorMode::Insert
+        | EditorMode::InsertRegisterPrefix
+        | EditorMode::InsertLiteralPrefix
+        | EditorMode::VisualBlockInsert => ansi::FG_GUTTER_INSERT,
+        EditorMode::Visual | EditorMode::VisualLine | EditorMode::VisualBlock => {
+            ansi::FG_GUTTER_VISUAL
+        }
+        _ => ansi::FG_GUTTER_NORMAL,
+    }
+}
+
+/// Scrollbar thumb for a viewport as (first row, row count) within the track
+///
+/// The thumb fills the track when everything fits, and otherwise is sized by
+/// the visible share of the lines and positioned by the scroll offset.
+fn scrollbar_thumb(total_lines: usize, height: usize, scroll_row: usize) -> (usize, usize) {
+    if total_lines <= height {
+        return (0, height);
+    }
+
+    let size = (height * height / total_lines).clamp(1, height);
+    let max_start = height - size;
+    let max_scroll = total_lines - height;
+    let start = (scroll_row.min(max_scroll) * max_start + max_scroll / 2) / max_scroll;
+    (start, size)
+}
+
+/// Shown instead of the panes when the terminal cannot fit them
+const TERMINAL_TOO_SMALL_MESSAGE: &str = "Terminal too small";
+
+/// Cut `message` after the last whole word that fits in `width` columns,
+/// falling back to a plain cut when not even the first word fits
+fn fit_words(message: &str, width: usize) -> String {
+    let mut fitted = String::new();
+    for word in message.split(' ') {
+        let candidate = if fitted.is_empty() {
+            word.to_string()
+        } else {
+            format!("{fitted} {word}")
+        };
+        if candidate.chars().count() > width {
+            break;
+        }
+        fitted = candidate;
+    }
+    if fitted.is_empty() {
+        return message.chars().take(width).collect();
+    }
+    fitted
+}
+
+/// Gap between candidates in the completion menu
+const WILDMENU_SEPARATOR: &str = "  ";
+
+/// Range of completion candidates that fit in `width` columns around the selection
+///
+/// Candidates before the selected one are dropped until it fits, then as many
+/// following candidates as there is room for are added.
+fn wildmenu_window(widths: &[usize], selected: usize, width: usize) -> std::ops::Range<usize> {
+    let span = |range: std::ops::Range<usize>| {
+        widths[range.clone()].iter().sum::<usize>()
+            + WILDMENU_SEPARATOR.len() * range.len().saturating_sub(1)
+    };
+
+    let mut start = 0;
+    while start < selected && span(start..selected + 1) > width {
+        start += 1;
+    }
+    let mut end = selected + 1;
+    while end < widths.len() && span(start..end + 1) <= width {
+        end += 1;
+    }
+    start..end
+}
+
+/// Cell of rendered text covering a display column as (start column, cell text)
+///
+/// A column inside a wide character maps to the start of that character, and a
+/// column past the end of the text maps to a blank cell. Tabs render as
+/// `tab_width` spaces, matching `render_text_with_selection`.
+fn cell_at_display_column(text: &str, column: usize, tab_width: usize) -> (usize, String) {
+    let mut current_col = 0;
+    for ch in text.chars() {
+        let char_width = match ch {
+            '\t' => tab_width,
+            _ => char_width(ch),
+        };
+        if column < current_col + char_width {
+            return match ch {
+                '\t' => (column, " ".to_string()),
+                _ => (
+                    current_col,
+                    caret_notation(ch).unwrap_or_else(|| ch.to_string()),
+                ),
+            };
+        }
+        current_col += char_width;
+    }
+    (column, " ".to_string())
+}
 
 // Type alias for display line data to reduce complexity
 type DisplayLineData = Option<(String, Option<usize>, bool, usize, usize)>;
@@ -78,8 +213,6 @@ macro_rules! safe_flush {
     };
 }
 
-use unicode_width::UnicodeWidthChar;
-
 /// Trait for rendering views
 pub trait ViewRenderer {
     /// Initialize the terminal for rendering
@@ -108,9 +241,18 @@ pub trait ViewRenderer {
     /// Render only position indicator in status bar (for reduced flickering)
     fn render_position_indicator(&mut self, view_model: &ViewModel) -> Result<()>;
 
+    /// Redraw only the line number gutter of a pane (for mode-colored numbers)
+    fn render_gutter(&mut self, view_model: &ViewModel, pane: Pane) -> Result<()>;
+
     /// Handle view events
     fn handle_view_event(&mut self, event: &ViewEvent, view_model: &ViewModel) -> Result<()>;
 
+    /// Set the terminal window title when `:set title` asks for a different one
+    fn render_title(&mut self, view_model: &ViewModel) -> Result<()>;
+
+    /// Copy text to the terminal's clipboard with OSC 52
+    fn render_clipboard(&mut self, text: &str) -> Result<()>;
+
     /// Cleanup terminal on exit
     fn cleanup(&mut self) -> Result<()>;
 }
@@ -119,6 +261,8 @@ pub trait ViewRenderer {
 pub struct TerminalRenderer<RS: RenderStream> {
     render_stream: RS,
     terminal_size: (u16, u16),
+    /// Window title last set, `None` while the terminal shows its own title
+    window_title: Option<String>,
 }
 
 impl<RS: RenderStream> TerminalRenderer<RS> {
@@ -128,6 +272,7 @@ impl<RS: RenderStream> TerminalRenderer<RS> {
         Ok(Self {
             render_stream,
             terminal_size,
+            window_title: None,
         })
     }
 
@@ -162,12 +307,9 @@ impl<RS: RenderStream> TerminalRenderer<RS> {
                         // This maintains backward compatibility
                     }
                     _ => {
-                        // Use unicode-width to get proper display width
-                        // Most double-byte characters (CJK) have width 2
-                        if let Some(w) = unicode_width::UnicodeWidthChar::width(ch) {
-                            length += w;
-                        }
-                        // Control characters and zero-width characters have no width
+                        // Most double-byte characters (CJK) have width 2, and
+                        // control characters show in caret notation
+                        length += char_width(ch);
                     }
                 }
             }
@@ -186,6 +328,45 @@ impl<RS: RenderStream> TerminalRenderer<RS> {
         self.terminal_size
     }
 
+    /// Render the line number column of a line at the current cursor position
+    ///
+    /// Numbers in the focused pane take the color of its mode, so Insert and
+    /// Visual mode show at a glance in the gutter.
+    fn render_line_number(
+        &mut self,
+        view_model: &ViewModel,
+        pane: Pane,
+        line_info: &LineInfo,
+        line_num_width: usize,
+    ) -> Result<()> {
+        if let Some(num) = line_info.line_number {
+            let color = if pane == view_model.get_current_pane() {
+                gutter_color(view_model.mode())
+            } else {
+                ansi::FG_GUTTER_NORMAL
+            };
+            // Render line number with right alignment (minimum width 3)
+            write!(
+                self.render_stream,
+                "{color}{num:>line_num_width$} {}",
+                ansi::RESET
+            )?;
+        } else if line_info.is_continuation {
+            // Continuation line of wrapped text - show blank space
+            write!(self.render_stream, "{} ", " ".repeat(line_num_width))?;
+        } else {
+            // Show tilda for empty lines beyond content (vim-style) with darker gray color
+            write!(
+                self.render_stream,
+                "{}~{} {}",
+                ansi::DIM,
+                " ".repeat(line_num_width.saturating_sub(1)),
+                ansi::RESET
+            )?;
+        }
+        Ok(())
+    }
+
     /// Render a single line of text at position with line number, with visual selection support
     fn render_line_with_number(
         &mut self,
@@ -200,28 +381,7 @@ impl<RS: RenderStream> TerminalRenderer<RS> {
 
         // Only render line numbers if they are visible
         if view_model.pane_manager().is_line_numbers_visible() {
-            #[allow(unused_variables)]
-            if let Some(num) = line_info.line_number {
-                // Render line number with dimmed style and right alignment (minimum width 3)
-                write!(
-                    self.render_stream,
-                    "{}{num:>line_num_width$} {}",
-                    ansi::DIM,
-                    ansi::RESET
-                )?;
-            } else if line_info.is_continuation {
-                // Continuation line of wrapped text - show blank space
-                write!(self.render_stream, "{} ", " ".repeat(line_num_width))?;
-            } else {
-                // Show tilda for empty lines beyond content (vim-style) with darker gray color
-                write!(
-                    self.render_stream,
-                    "{}~{} {}",
-                    ansi::DIM,
-                    " ".repeat(line_num_width.saturating_sub(1)),
-                    ansi::RESET
-                )?;
-            }
+            self.render_line_number(view_model, pane, line_info, line_num_width)?;
         }
 
         // Calculate how much space is available for text after line number
@@ -230,7 +390,8 @@ impl<RS: RenderStream> TerminalRenderer<RS> {
         } else {
             0 // No space used when line numbers are hidden
         };
-        let available_width = (self.terminal_size.0 as usize).saturating_sub(used_width);
+        let available_width = (self.terminal_size.0 as usize)
+            .saturating_sub(used_width + view_model.pane_manager().scrollbar_width());
 
         // Truncate text to fit within terminal width, accounting for double-byte characters and tabs
         let tab_width = view_model.pane_manager().get_tab_width();
@@ -245,7 +406,7 @@ impl<RS: RenderStream> TerminalRenderer<RS> {
                             // Calculate spaces to next tab stop
                             tab_width - (current_width % tab_width)
                         }
-                        _ => UnicodeWidthChar::width(ch).unwrap_or(0),
+                        _ => char_width(ch),
                     };
                     if current_width + char_width > available_width {
                         break;
@@ -271,6 +432,55 @@ impl<RS: RenderStream> TerminalRenderer<RS> {
         // Clear rest of line
         write!(self.render_stream, "{}", ansi::CLEAR_LINE)?;
 
+        if line_info.line_number.is_some() && available_width > 0 {
+            let (hidden_left, hidden_right) = view_model.get_side_scroll_markers(
+                pane,
+                line_info.logical_line,
+                line_info.logical_start_col,
+                display_text.chars().count(),
+            );
+            if hidden_left {
+                self.render_stream.move_cursor(used_width as u16, row)?;
+                write!(
+                    self.render_stream,
+                    "{}{}{}",
+                    ansi::FG_SIDE_SCROLL,
+                    ansi::SIDE_SCROLL_PRECEDES,
+                    ansi::RESET
+                )?;
+            }
+            if hidden_right {
+                self.render_stream
+                    .move_cursor((used_width + available_width - 1) as u16, row)?;
+                write!(
+                    self.render_stream,
+                    "{}{}{}",
+                    ansi::FG_SIDE_SCROLL,
+                    ansi::SIDE_SCROLL_EXTENDS,
+                    ansi::RESET
+                )?;
+            }
+        }
+
+        let has_text = line_info.line_number.is_some() || line_info.is_continuation;
+        if has_text
+            && pane == view_model.get_current_pane()
+            && view_model.pane_manager().is_cursor_column_visible()
+        {
+            let (_, cursor_col) = view_model.get_cursor_for_rendering(pane);
+            if cursor_col < available_width {
+                let (cell_col, cell) = cell_at_display_column(&display_text, cursor_col, tab_width);
+                self.render_stream
+                    .move_cursor((used_width + cell_col) as u16, row)?;
+                write!(
+                    self.render_stream,
+                    "{}{cell}{}",
+                    ansi::BG_CURSOR_COLUMN,
+                    ansi::RESET
+                )?;
+            }
+        }
+
         // Flush to ensure content is displayed
         safe_flush!(self.render_stream)?;
 
@@ -378,6 +588,7 @@ impl<RS: RenderStream> TerminalRenderer<RS> {
                         }
                         _ => {
                             // Regular character handling
+                            let ch = caret_notation(*ch).unwrap_or_else(|| ch.to_string());
                             if is_selected {
                                 tracing::debug!(
                                     "render_text_with_selection: highlighting character '{}' at {:?}",
@@ -409,8 +620,27 @@ impl<RS: RenderStream> TerminalRenderer<RS> {
         }
 
         // No selection or not in visual mode - render normally, but expand tabs
+        // and flash any text that was just yanked or a bracket matching one typed
         let tab_width = view_model.pane_manager().get_tab_width();
-        for ch in text.chars() {
+        let yank_highlight = view_model.get_yank_highlight();
+        let match_highlight = view_model
+            .get_match_highlight()
+            .filter(|_| pane == Pane::Request);
+        for (col_index, ch) in text.chars().enumerate() {
+            let position = crate::repl::events::LogicalPosition::new(
+                logical_line,
+                logical_start_col + col_index,
+            );
+            let flash = if match_highlight == Some(position) {
+                Some(ansi::BG_MATCH_HIGHLIGHT)
+            } else if yank_highlight.is_some_and(|h| h.contains(pane, position)) {
+                Some(ansi::BG_YANK_HIGHLIGHT)
+            } else {
+                None
+            };
+            if let Some(background) = flash {
+                write!(self.render_stream, "{background}")?;
+            }
             match ch {
                 '\t' => {
                     // Expand tabs to spaces
@@ -418,9 +648,13 @@ impl<RS: RenderStream> TerminalRenderer<RS> {
                         write!(self.render_stream, " ")?;
                     }
                 }
-                _ => {
-                    write!(self.render_stream, "{ch}")?;
-                }
+                _ => match caret_notation(ch) {
+                    Some(notation) => write!(self.render_stream, "{notation}")?,
+                    None => write!(self.render_stream, "{ch}")?,
+                },
+            }
+            if flash.is_some() {
+                write!(self.render_stream, "{}", ansi::RESET)?;
             }
         }
         Ok(())
@@ -451,6 +685,54 @@ impl<RS: RenderStream> TerminalRenderer<RS> {
             )?;
         }
 
+        self.render_scrollbar(view_model, pane, start_row, height)?;
+
+        Ok(())
+    }
+
+    /// Render the header above the request pane's text, showing where the request would be sent
+    ///
+    /// Nothing is drawn when the layout leaves no `header_rows` for it.
+    fn render_request_header(&mut self, view_model: &ViewModel, header_rows: u16) -> Result<()> {
+        if header_rows == 0 {
+            return Ok(());
+        }
+        let header = request_header_line(
+            view_model.get_request_target().as_ref(),
+            self.terminal_size.0 as usize,
+        );
+        self.render_stream.move_cursor(0, 0)?;
+        write!(self.render_stream, "{header}")?;
+        Ok(())
+    }
+
+    /// Render the scrollbar in the rightmost column of a pane, when enabled
+    fn render_scrollbar(
+        &mut self,
+        view_model: &ViewModel,
+        pane: Pane,
+        start_row: u16,
+        height: u16,
+    ) -> Result<()> {
+        if !view_model.pane_manager().is_scrollbar_visible() || height == 0 {
+            return Ok(());
+        }
+
+        let (scroll_row, total_lines) = view_model.get_scroll_extent(pane);
+        let (thumb_start, thumb_size) = scrollbar_thumb(total_lines, height as usize, scroll_row);
+        let column = self.terminal_size.0.saturating_sub(1);
+
+        for row in 0..height as usize {
+            let (color, symbol) = if (thumb_start..thumb_start + thumb_size).contains(&row) {
+                (ansi::FG_SCROLLBAR_THUMB, ansi::SCROLLBAR_THUMB)
+            } else {
+                (ansi::FG_SCROLLBAR_TRACK, ansi::SCROLLBAR_TRACK)
+            };
+            self.render_stream
+                .move_cursor(column, start_row + row as u16)?;
+            write!(self.render_stream, "{color}{symbol}{}", ansi::RESET)?;
+        }
+
         Ok(())
     }
 
@@ -481,6 +763,9 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
         // Set initial cursor style to steady block (Normal mode default)
         // This prevents flickering on first mode change
         write!(self.render_stream, "{}", ansi::CURSOR_BLOCK_STEADY)?;
+        // Ask for focus events so autosave can write when the terminal loses focus
+        // and autoread can check the request file when it comes back
+        write!(self.render_stream, "{}", ansi::FOCUS_REPORTING_ON)?;
         // Don't hide cursor initially - let render_cursor handle visibility
         // This prevents the need to change visibility state on first mode switch
         Ok(())
@@ -492,23 +777,42 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
         self.render_stream.hide_cursor()?;
         self.render_stream.clear_screen()?;
 
-        let (request_height, response_start, response_height) = view_model
+        // Nothing but the message fits until the terminal is made larger
+        if view_model.pane_manager().is_terminal_too_small() {
+            let message = fit_words(TERMINAL_TOO_SMALL_MESSAGE, self.terminal_size.0 as usize);
+            self.render_stream.move_cursor(0, 0)?;
+            write!(self.render_stream, "{message}")?;
+            safe_flush!(self.render_stream)?;
+            return Ok(());
+        }
+
+        let has_response = view_model.get_response_status_code().is_some();
+        let (request_height, response_start, response_height) =
+            view_model.pane_manager().get_pane_boundaries(has_response);
+        let (request_start, request_text_height) = view_model
             .pane_manager()
-            .get_pane_boundaries(view_model.get_response_status_code().is_some());
+            .get_request_text_area(has_response);
 
-        // Render request pane
-        self.render_buffer_content(view_model, Pane::Request, 0, request_height)?;
+        // Render request pane under its header
+        self.render_request_header(view_model, request_start)?;
+        self.render_buffer_content(
+            view_model,
+            Pane::Request,
+            request_start,
+            request_text_height,
+        )?;
 
         // Only render separator and response pane if there's an HTTP response
-        let has_response = view_model.get_response_status_code().is_some();
         tracing::debug!(
             "render_full: has_response = {}, rendering response pane = {}",
             has_response,
             has_response
         );
         if has_response {
-            // Render separator
-            self.render_separator(request_height)?;
+            // Render separator, unless the compact layout shows a single pane
+            if request_height > 0 && response_height > 0 {
+                self.render_separator(request_height)?;
+            }
 
             // Render response pane
             self.render_buffer_content(
@@ -522,6 +826,13 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
         // Render status bar
         self.render_status_bar(view_model)?;
 
+        // A listing in the scratch overlay covers the panes and takes the cursor away
+        if view_model.get_scratch().is_some() {
+            self.render_scratch_overlay(view_model)?;
+            safe_flush!(self.render_stream)?;
+            return Ok(());
+        }
+
         // Render cursor (this will show cursor in correct position)
         self.render_cursor(view_model)?;
         safe_flush!(self.render_stream)?;
@@ -533,13 +844,22 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
         // Temporarily hide cursor during pane rendering to prevent ghost cursors
         self.render_stream.hide_cursor()?;
 
-        let (request_height, response_start, response_height) = view_model
-            .pane_manager()
-            .get_pane_boundaries(view_model.get_response_status_code().is_some());
+        let has_response = view_model.get_response_status_code().is_some();
+        let (_request_height, response_start, response_height) =
+            view_model.pane_manager().get_pane_boundaries(has_response);
 
         match pane {
             Pane::Request => {
-                self.render_buffer_content(view_model, Pane::Request, 0, request_height)?;
+                let (request_start, request_text_height) = view_model
+                    .pane_manager()
+                    .get_request_text_area(has_response);
+                self.render_request_header(view_model, request_start)?;
+                self.render_buffer_content(
+                    view_model,
+                    Pane::Request,
+                    request_start,
+                    request_text_height,
+                )?;
             }
             Pane::Response => {
                 // Only render response pane if there's an HTTP response
@@ -568,18 +888,25 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
         // Hide cursor before any rendering to prevent ghost cursors
         self.render_stream.hide_cursor()?;
 
-        let (request_height, response_start, response_height) = view_model
-            .pane_manager()
-            .get_pane_boundaries(view_model.get_response_status_code().is_some());
+        let has_response = view_model.get_response_status_code().is_some();
+        let (_request_height, response_start, response_height) =
+            view_model.pane_manager().get_pane_boundaries(has_response);
 
         // Early return for response pane without content
-        if pane == Pane::Response && view_model.get_response_status_code().is_none() {
+        if pane == Pane::Response && !has_response {
             return Ok(());
         }
 
         // Calculate pane-specific parameters
         let (pane_height, row_offset) = match pane {
-            Pane::Request => (request_height as usize, 0u16),
+            Pane::Request => {
+                let (request_start, request_text_height) = view_model
+                    .pane_manager()
+                    .get_request_text_area(has_response);
+                // Edits anywhere may change the request line, so the header goes too
+                self.render_request_header(view_model, request_start)?;
+                (request_text_height as usize, request_start)
+            }
             Pane::Response => (response_height as usize, response_start),
         };
 
@@ -609,6 +936,40 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
             )?;
         }
 
+        // Cleared lines lose their scrollbar cell, so redraw the whole bar
+        self.render_scrollbar(view_model, pane, row_offset, pane_height as u16)?;
+
+        // Don't render cursor here - let the controller handle it once at the end
+        safe_flush!(self.render_stream)?;
+        Ok(())
+    }
+
+    fn render_gutter(&mut self, view_model: &ViewModel, pane: Pane) -> Result<()> {
+        if !view_model.pane_manager().is_line_numbers_visible()
+            || (pane == Pane::Response && view_model.get_response_status_code().is_none())
+        {
+            return Ok(());
+        }
+        self.render_stream.hide_cursor()?;
+
+        let has_response = view_model.get_response_status_code().is_some();
+        let (_request_height, response_start, response_height) =
+            view_model.pane_manager().get_pane_boundaries(has_response);
+        let (start_row, height) = match pane {
+            Pane::Request => view_model
+                .pane_manager()
+                .get_request_text_area(has_response),
+            Pane::Response => (response_start, response_height),
+        };
+        let display_lines = view_model.get_display_lines_for_rendering(pane, 0, height as usize);
+        let line_num_width = view_model.pane_manager().get_line_number_width(pane);
+
+        for (row, display_data) in display_lines.iter().enumerate() {
+            let line_info = LineInfo::from_display_data_string(display_data, pane, row, 0);
+            self.render_stream.move_cursor(0, start_row + row as u16)?;
+            self.render_line_number(view_model, pane, &line_info, line_num_width)?;
+        }
+
         // Don't render cursor here - let the controller handle it once at the end
         safe_flush!(self.render_stream)?;
         Ok(())
@@ -616,8 +977,11 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
 
     fn render_cursor(&mut self, view_model: &ViewModel) -> Result<()> {
         // Cursor should be visible in normal editing modes
-        // Only hide cursor in command mode when showing command line cursor
-        let should_hide_cursor = view_model.get_mode() == EditorMode::Command;
+        // Only hide cursor in command and search modes when showing command line cursor
+        let should_hide_cursor = matches!(
+            view_model.get_mode(),
+            EditorMode::Command | EditorMode::Search
+        );
         tracing::debug!(
             "render_cursor: mode = {:?}, should_hide_cursor = {}",
             view_model.get_mode(),
@@ -646,10 +1010,13 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
         // Get scroll offset to calculate viewport-relative position
         let scroll_offset = view_model.pane_manager().get_current_scroll_offset();
 
-        // Get pane boundaries to calculate response pane offset
-        let (_request_height, response_start, _response_height) = view_model
+        // Get pane boundaries to calculate the offset of the pane's text
+        let has_response = view_model.get_response_status_code().is_some();
+        let (_request_height, response_start, _response_height) =
+            view_model.pane_manager().get_pane_boundaries(has_response);
+        let (request_start, _request_text_height) = view_model
             .pane_manager()
-            .get_pane_boundaries(view_model.get_response_status_code().is_some());
+            .get_request_text_area(has_response);
 
         // Calculate viewport-relative position by subtracting scroll offset
         let viewport_relative_row = display_cursor.row.saturating_sub(scroll_offset.row);
@@ -664,7 +1031,7 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
             display_cursor.col.saturating_sub(scroll_offset.col) // Just subtract horizontal scroll offset
         };
         let screen_row = match current_pane {
-            Pane::Request => viewport_relative_row,
+            Pane::Request => viewport_relative_row + request_start as usize,
             Pane::Response => viewport_relative_row + response_start as usize,
         };
 
@@ -675,7 +1042,8 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
         );
 
         // Validate and clamp cursor coordinates to terminal bounds
-        let max_row = (terminal_size.1 as usize).saturating_sub(2); // Leave room for status bar
+        let status_height = view_model.pane_manager().status_line_height() as usize;
+        let max_row = (terminal_size.1 as usize).saturating_sub(1 + status_height); // Leave room for status bar
         if screen_col >= terminal_size.0 as usize || screen_row >= terminal_size.1 as usize {
             tracing::warn!(
                 "render_cursor: cursor position ({}, {}) is outside terminal bounds ({}, {}), clamping", 
@@ -707,9 +1075,29 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
             EditorMode::VisualBlock => ansi::CURSOR_BLOCK_STEADY, // Steady block for visual block mode
             EditorMode::VisualBlockInsert => ansi::CURSOR_BAR_STEADY, // Steady I-beam for visual block insert mode
             EditorMode::Command => ansi::CURSOR_BAR_STEADY, // Steady I-beam for command mode
+            EditorMode::Search => ansi::CURSOR_BAR_STEADY,  // Steady I-beam for search mode
             EditorMode::GPrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for g-prefix mode
             EditorMode::DPrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for d-prefix mode
             EditorMode::YPrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for y-prefix mode
+            EditorMode::ZPrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for z-prefix mode
+            EditorMode::ScrollPrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for scroll-prefix mode
+            EditorMode::QPrefix => ansi::CURSOR_BLOCK_STEADY,      // Steady block for q-prefix mode
+            EditorMode::CPrefix => ansi::CURSOR_BLOCK_STEADY,      // Steady block for c-prefix mode
+            EditorMode::FPrefix => ansi::CURSOR_BLOCK_STEADY,      // Steady block for f-prefix mode
+            EditorMode::DFPrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for df-prefix mode
+            EditorMode::DAPrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for da-prefix mode
+            EditorMode::RegisterPrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for register-prefix mode
+            EditorMode::EqualPrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for =-prefix mode
+            EditorMode::MarkPrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for mark-prefix mode
+            EditorMode::MarkLinePrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for mark-line-prefix mode
+            EditorMode::SetMarkPrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for set-mark-prefix mode
+            EditorMode::IndentPrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for indent-prefix mode
+            EditorMode::DedentPrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for dedent-prefix mode
+            EditorMode::LowerCasePrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for gu-prefix mode
+            EditorMode::UpperCasePrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for gU-prefix mode
+            EditorMode::ToggleCasePrefix => ansi::CURSOR_BLOCK_STEADY, // Steady block for g~-prefix mode
+            EditorMode::InsertRegisterPrefix => ansi::CURSOR_BAR_STEADY, // Steady I-beam while waiting for register
+            EditorMode::InsertLiteralPrefix => ansi::CURSOR_BAR_STEADY, // Steady I-beam while waiting for a literal key
         };
 
         // Position cursor, set style, and show
@@ -726,6 +1114,26 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
     fn render_status_bar(&mut self, view_model: &ViewModel) -> Result<()> {
         let status_row = self.terminal_size.1 - 1;
 
+        // Check if we're in command or search mode and need to show the typed input
+        let command_line = match view_model.get_mode() {
+            EditorMode::Command => Some(format!(":{}", view_model.get_ex_command_buffer())),
+            EditorMode::Search => Some(format!(
+                "{}{}",
+                if view_model.is_search_forward() {
+                    '/'
+                } else {
+                    '?'
+                },
+                view_model.get_search_buffer()
+            )),
+            _ => None,
+        };
+
+        // With laststatus=0 the row belongs to the panes, except while typing a command
+        if !view_model.pane_manager().is_status_line_visible() && command_line.is_none() {
+            return Ok(());
+        }
+
         // Clear the status bar first
         self.render_stream.move_cursor(0, status_row)?;
         write!(
@@ -734,9 +1142,11 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
             " ".repeat(self.terminal_size.0 as usize)
         )?;
 
-        // Check if we're in command mode and need to show ex command buffer
-        if view_model.get_mode() == EditorMode::Command {
-            let ex_command_text = format!(":{}", view_model.get_ex_command_buffer());
+        if let Some(ex_command_text) = command_line {
+            if view_model.get_mode() == EditorMode::Command {
+                self.render_wildmenu(view_model, status_row)?;
+            }
+
             self.render_stream.move_cursor(0, status_row)?;
             write!(self.render_stream, "{}", &ex_command_text)?;
 
@@ -758,41 +1168,11 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
             let mut left_status_text = String::new();
             let mut right_status_text = String::new();
 
-            // Left side: Vim-style mode indicators (highest priority)
-            match view_model.get_mode() {
-                EditorMode::Insert => {
-                    left_status_text.push_str(&format!(
-                        "{}-- INSERT --{}",
-                        ansi::BOLD,
-                        ansi::RESET
-                    ));
-                }
-                EditorMode::Visual => {
-                    left_status_text.push_str(&format!(
-                        "{}-- VISUAL --{}",
-                        ansi::BOLD,
-                        ansi::RESET
-                    ));
-                }
-                EditorMode::VisualLine => {
-                    left_status_text.push_str(&format!(
-                        "{}-- VISUAL LINE --{}",
-                        ansi::BOLD,
-                        ansi::RESET
-                    ));
-                }
-                EditorMode::VisualBlock => {
-                    left_status_text.push_str(&format!(
-                        "{}-- VISUAL BLOCK --{}",
-                        ansi::BOLD,
-                        ansi::RESET
-                    ));
-                }
-                _ => {
-                    // Normal mode shows no status message (following Vim exactly)
-                    // Command mode shows ex command buffer (handled above)
-                    // GPrefix mode shows no status message
-                }
+            // Left side: Vim-style mode indicators (highest priority), unless
+            // `noshowmode` hides them. Normal mode shows no indicator (following
+            // Vim exactly) and command mode shows the ex command buffer (above)
+            if let Some(indicator) = view_model.get_mode_indicator() {
+                left_status_text.push_str(&format!("{}{indicator}{}", ansi::BOLD, ansi::RESET));
             }
 
             // If no vim mode indicator and we have custom status message, show it
@@ -807,7 +1187,24 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
                 }
             }
 
-            // Right side: HTTP response info (optional, when present)
+            // Right side: pending keys (showcmd), unseen errors, request method,
+            // then HTTP response info
+            let pending_keys = view_model.get_pending_keys();
+            if !pending_keys.is_empty() {
+                right_status_text.push_str(&format!("{pending_keys} | "));
+            }
+
+            let unseen_errors = view_model.unseen_error_count();
+            if unseen_errors > 0 {
+                right_status_text.push_str(&error_count_segment(unseen_errors));
+                right_status_text.push_str(" | ");
+            }
+
+            if let Some(method) = view_model.get_request_method() {
+                right_status_text.push_str(&method_status_segment(&method));
+                right_status_text.push_str(" | ");
+            }
+
             if let Some(status_code) = view_model.get_response_status_code() {
                 let status_message_opt = view_model.get_response_status_message();
                 let status_message = status_message_opt.as_deref().unwrap_or("");
@@ -881,6 +1278,10 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
     }
 
     fn render_position_indicator(&mut self, view_model: &ViewModel) -> Result<()> {
+        if !view_model.pane_manager().is_status_line_visible() {
+            return Ok(());
+        }
+
         let status_row = self.terminal_size.1 - 1;
         let cursor = view_model.get_cursor_position();
 
@@ -920,6 +1321,18 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
         // Build the right portion of the status bar, including HTTP info if present
         let mut right_text = String::new();
 
+        // Add pending keys of an incomplete command (showcmd)
+        let pending_keys = view_model.get_pending_keys();
+        if !pending_keys.is_empty() {
+            right_text.push_str(&format!("{pending_keys} | "));
+        }
+
+        // Add request method if present
+        if let Some(method) = view_model.get_request_method() {
+            right_text.push_str(&method_status_segment(&method));
+            right_text.push_str(" | ");
+        }
+
         // Add HTTP response info if present
         if let Some(status_code) = view_model.get_response_status_code() {
             let status_message_opt = view_model.get_response_status_message();
@@ -1024,6 +1437,17 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
                 };
                 self.render_pane(view_model, secondary_pane)?;
             }
+            ViewEvent::GutterRedrawRequired => {
+                self.render_gutter(view_model, Pane::Request)?;
+                self.render_gutter(view_model, Pane::Response)?;
+                self.render_cursor(view_model)?;
+            }
+            ViewEvent::YankHighlighted { highlight } => {
+                self.render_pane(view_model, highlight.pane)?;
+            }
+            ViewEvent::MatchHighlighted { .. } => {
+                self.render_pane(view_model, Pane::Request)?;
+            }
             ViewEvent::FocusSwitched => {
                 // Focus switched - update cursor and status bar
                 self.render_cursor(view_model)?;
@@ -1055,17 +1479,105 @@ impl<RS: RenderStream> ViewRenderer for TerminalRenderer<RS> {
         Ok(())
     }
 
+    fn render_title(&mut self, view_model: &ViewModel) -> Result<()> {
+        let title = view_model.get_window_title();
+        if title == self.window_title {
+            return Ok(());
+        }
+        match &title {
+            Some(title) => {
+                // Save the terminal's own title the first time so it can be put back
+                if self.window_title.is_none() {
+                    write!(self.render_stream, "{}", ansi::SAVE_TITLE)?;
+                }
+                write!(
+                    self.render_stream,
+                    "{}{title}{}",
+                    ansi::SET_TITLE_START,
+                    ansi::SET_TITLE_END
+                )?;
+            }
+            None => write!(self.render_stream, "{}", ansi::RESTORE_TITLE)?,
+        }
+        self.window_title = title;
+        safe_flush!(self.render_stream)
+    }
+
+    fn render_clipboard(&mut self, text: &str) -> Result<()> {
+        write!(self.render_stream, "{}", osc52_sequence(text))?;
+        safe_flush!(self.render_stream)
+    }
+
     fn cleanup(&mut self) -> Result<()> {
         // Clean up terminal state on exit
-        self.render_stream.show_cursor()?;
-        self.render_stream.leave_alternate_screen()?;
-        self.render_stream.disable_raw_mode()?;
-        Ok(())
+        write!(self.render_stream, "{}", ansi::FOCUS_REPORTING_OFF)?;
+        if self.window_title.take().is_some() {
+            write!(self.render_stream, "{}", ansi::RESTORE_TITLE)?;
+        }
+        restore_terminal(&mut self.render_stream)
     }
 }
 
 // Private implementation methods for TerminalRenderer
 impl<RS: RenderStream> TerminalRenderer<RS> {
+    /// Render the completion candidates on the row above the command line
+    fn render_wildmenu(&mut self, view_model: &ViewModel, status_row: u16) -> Result<()> {
+        let Some((candidates, selected)) = view_model.get_wildmenu() else {
+            return Ok(());
+        };
+        if status_row == 0 {
+            return Ok(());
+        }
+
+        let widths: Vec<usize> = candidates
+            .iter()
+            .map(|candidate| self.visual_length(candidate))
+            .collect();
+        let width = self.terminal_size.0 as usize;
+        let window = wildmenu_window(&widths, selected, width);
+
+        let mut line = String::new();
+        for index in window.clone() {
+            if index > window.start {
+                line.push_str(WILDMENU_SEPARATOR);
+            }
+            if index == selected {
+                line.push_str(&format!(
+                    "{}{}{}",
+                    ansi::REVERSE,
+                    candidates[index],
+                    ansi::RESET
+                ));
+            } else {
+                line.push_str(&candidates[index]);
+            }
+        }
+
+        self.render_stream.move_cursor(0, status_row - 1)?;
+        write!(self.render_stream, "{line}{}", ansi::CLEAR_LINE)?;
+        Ok(())
+    }
+
+    /// Render the scratch overlay over the bottom of the panes, above the status line
+    fn render_scratch_overlay(&mut self, view_model: &ViewModel) -> Result<()> {
+        let Some(lines) = view_model.get_scratch() else {
+            return Ok(());
+        };
+        let status_row = self.terminal_size.1.saturating_sub(1) as usize;
+        let rows = scratch_overlay::overlay_rows(lines, self.terminal_size.0 as usize, status_row);
+
+        let top = status_row - rows.len();
+        for (offset, row) in rows.iter().enumerate() {
+            self.render_stream.move_cursor(0, (top + offset) as u16)?;
+            if offset + 1 == rows.len() {
+                write!(self.render_stream, "{}{row}{}", ansi::REVERSE, ansi::RESET)?;
+            } else {
+                write!(self.render_stream, "{row}")?;
+            }
+        }
+        Ok(())
+    }
+
     /// Render multiple cursors for Visual Block Insert mode
     fn render_multi_cursors(&mut self, view_model: &ViewModel) -> Result<()> {
         let cursor_positions = view_model.get_visual_block_insert_cursors();
@@ -1086,9 +1598,12 @@ impl<RS: RenderStream> TerminalRenderer<RS> {
                 .pane_manager()
                 .get_line_number_width(current_pane);
             let scroll_offset = view_model.pane_manager().get_current_scroll_offset();
-            let (_request_height, response_start, _response_height) = view_model
+            let has_response = view_model.get_response_status_code().is_some();
+            let (_request_height, response_start, _response_height) =
+                view_model.pane_manager().get_pane_boundaries(has_response);
+            let (request_start, _request_text_height) = view_model
                 .pane_manager()
-                .get_pane_boundaries(view_model.get_response_status_code().is_some());
+                .get_request_text_area(has_response);
 
             // Calculate screen position for the primary cursor
             let viewport_relative_row = first_pos.line.saturating_sub(scroll_offset.row);
@@ -1098,12 +1613,13 @@ impl<RS: RenderStream> TerminalRenderer<RS> {
                 first_pos.column.saturating_sub(scroll_offset.col)
             };
             let screen_row = match current_pane {
-                Pane::Request => viewport_relative_row,
+                Pane::Request => viewport_relative_row + request_start as usize,
                 Pane::Response => viewport_relative_row + response_start as usize,
             };
 
             let terminal_size = self.terminal_size;
-            let max_row = (terminal_size.1 as usize).saturating_sub(2);
+            let status_height = view_model.pane_manager().status_line_height() as usize;
+            let max_row = (terminal_size.1 as usize).saturating_sub(1 + status_height);
             let clamped_col = (screen_col).min(terminal_size.0 as usize - 1);
             let clamped_row = screen_row.min(max_row);
 
@@ -1133,7 +1649,8 @@ impl<RS: RenderStream> TerminalRenderer<RS> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::repl::io::mock::MockRenderStream;
+    use crate::repl::commands::{Setting, SettingValue};
+    use crate::repl::io::mock::{MockRenderStream, RenderCommand};
     use crate::repl::view_models::ViewModel;
 
     // Note: Testing terminal rendering is complex and typically done with integration tests
@@ -1170,6 +1687,85 @@ mod tests {
         }
     }
 
+    #[test]
+    fn method_status_segment_should_use_method_color() {
+        assert_eq!(
+            method_status_segment("POST"),
+            format!("{}POST{}", ansi::FG_METHOD_POST, ansi::RESET)
+        );
+        assert_eq!(
+            method_status_segment("GET"),
+            format!("{}GET{}", ansi::FG_METHOD_GET, ansi::RESET)
+        );
+    }
+
+    #[test]
+    fn method_status_segment_should_use_default_color_for_unknown_method() {
+        assert_eq!(
+            method_status_segment("PURGE"),
+            format!("{}PURGE{}", ansi::FG_METHOD_DEFAULT, ansi::RESET)
+        );
+    }
+
+    #[test]
+    fn error_count_segment_should_use_error_color() {
+        assert_eq!(
+            error_count_segment(1),
+            format!("{}1 error{}", ansi::FG_ERROR, ansi::RESET)
+        );
+        assert_eq!(
+            error_count_segment(3),
+            format!("{}3 errors{}", ansi::FG_ERROR, ansi::RESET)
+        );
+    }
+
+    #[test]
+    fn cell_at_display_column_should_skip_wide_character_before_cursor() {
+        // "名" takes columns 0-1, so the cursor on "a" sits at display column 2
+        assert_eq!(cell_at_display_column("名a", 2, 4), (2, "a".to_string()));
+    }
+
+    #[test]
+    fn cell_at_display_column_should_cover_whole_wide_character() {
+        assert_eq!(cell_at_display_column("a名b", 2, 4), (1, "名".to_string()));
+        assert_eq!(cell_at_display_column("a名b", 3, 4), (3, "b".to_string()));
+    }
+
+    #[test]
+    fn cell_at_display_column_should_pad_past_end_of_text() {
+        assert_eq!(cell_at_display_column("ab", 5, 4), (5, " ".to_string()));
+        assert_eq!(cell_at_display_column("\tb", 2, 4), (2, " ".to_string()));
+    }
+
+    #[test]
+    fn wildmenu_window_should_keep_selection_visible() {
+        let widths = [8, 8, 8, 8];
+
+        assert_eq!(wildmenu_window(&widths, 0, 80), 0..4);
+        assert_eq!(wildmenu_window(&widths, 0, 20), 0..2);
+        assert_eq!(wildmenu_window(&widths, 3, 20), 2..4);
+        assert_eq!(wildmenu_window(&widths, 2, 5), 2..3);
+    }
+
+    #[test]
+    fn scrollbar_thumb_should_fill_track_when_content_fits() {
+        assert_eq!(scrollbar_thumb(5, 10, 0), (0, 10));
+        assert_eq!(scrollbar_thumb(10, 10, 0), (0, 10));
+    }
+
+    #[test]
+    fn scrollbar_thumb_should_follow_scroll_offset() {
+        // 100 lines in a 10 row viewport: a one row thumb
+        assert_eq!(scrollbar_thumb(100, 10, 0), (0, 1));
+        assert_eq!(scrollbar_thumb(100, 10, 45), (5, 1));
+        assert_eq!(scrollbar_thumb(100, 10, 90), (9, 1));
+
+        // 20 lines in a 10 row viewport: a half height thumb
+        assert_eq!(scrollbar_thumb(20, 10, 0), (0, 5));
+        assert_eq!(scrollbar_thumb(20, 10, 5), (3, 5));
+        assert_eq!(scrollbar_thumb(20, 10, 10), (5, 5));
+    }
+
     #[test]
     fn visual_length_should_exclude_ansi_codes() {
         let render_stream = MockRenderStream::new();
@@ -1270,7 +1866,7 @@ mod tests {
             let mut current_width = 0;
 
             for ch in mixed_text.chars() {
-                let char_width = UnicodeWidthChar::width(ch).unwrap_or(0);
+                let char_width = unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
                 if current_width + char_width > available_width {
                     break;
                 }
@@ -1288,6 +1884,212 @@ mod tests {
         }
     }
 
+    #[test]
+    fn render_title_should_set_window_title_and_restore_it() {
+        let mut view_model = ViewModel::new();
+        view_model.set_profile_info("default".to_string(), String::new());
+        view_model.set_request_text("GET /users").unwrap();
+        let mut renderer =
+            TerminalRenderer::with_render_stream(MockRenderStream::with_size((80, 24))).unwrap();
+
+        // Nothing is written while `title` is off
+        renderer.render_title(&view_model).unwrap();
+        assert_eq!(renderer.render_stream.get_buffer_string(), "");
+
+        view_model
+            .apply_setting(Setting::Title, SettingValue::On)
+            .unwrap();
+        renderer.render_title(&view_model).unwrap();
+        renderer.render_title(&view_model).unwrap();
+        assert_eq!(
+            renderer.render_stream.get_buffer_string(),
+            "\x1b[22;0t\x1b]2;blueline - default - GET /users\x07"
+        );
+
+        renderer.cleanup().unwrap();
+        assert!(renderer
+            .render_stream
+            .get_buffer_string()
+            .ends_with(ansi::RESTORE_TITLE));
+    }
+
+    #[test]
+    fn render_clipboard_should_write_osc52_sequence_to_render_stream() {
+        let mut renderer =
+            TerminalRenderer::with_render_stream(MockRenderStream::with_size((80, 24))).unwrap();
+
+        renderer.render_clipboard("hi").unwrap();
+        assert_eq!(
+            renderer.render_stream.get_buffer_string(),
+            "\x1b]52;c;aGk=\x07"
+        );
+    }
+
+    #[test]
+    fn render_full_should_show_message_when_terminal_is_too_small() {
+        let mut view_model = ViewModel::new();
+        view_model.set_request_text("GET /a").unwrap();
+        let render_full = |view_model: &ViewModel, size: (u16, u16)| {
+            let mut renderer =
+                TerminalRenderer::with_render_stream(MockRenderStream::with_size(size)).unwrap();
+            renderer.render_full(view_model).unwrap();
+            renderer.render_stream.get_buffer_string()
+        };
+
+        view_model.update_terminal_size(10, 3);
+        assert!(render_full(&view_model, (10, 3)).contains("GET /a"));
+
+        view_model.update_terminal_size(10, 1);
+        assert_eq!(render_full(&view_model, (10, 1)), "Terminal");
+    }
+
+    #[test]
+    fn render_full_should_mark_unwrapped_lines_with_text_out_of_view() {
+        let mut view_model = ViewModel::new();
+        view_model.update_terminal_size(20, 10);
+        view_model
+            .set_request_text(&format!("GET /{}\nshort", "a".repeat(30)))
+            .unwrap();
+        let render_full = |view_model: &ViewModel| {
+            let mut renderer =
+                TerminalRenderer::with_render_stream(MockRenderStream::with_size((20, 10)))
+                    .unwrap();
+            renderer.render_full(view_model).unwrap();
+            renderer.render_stream.get_buffer_string()
+        };
+        let precedes = format!("{}{}", ansi::FG_SIDE_SCROLL, ansi::SIDE_SCROLL_PRECEDES);
+        let extends = format!("{}{}", ansi::FG_SIDE_SCROLL, ansi::SIDE_SCROLL_EXTENDS);
+
+        let output = render_full(&view_model);
+        assert!(output.contains(&extends));
+        assert!(!output.contains(&precedes));
+
+        view_model.scroll_horizontally(1, 5).unwrap();
+        let output = render_full(&view_model);
+        assert!(output.contains(&extends));
+        assert!(output.contains(&precedes));
+
+        // Wrapped lines always fit, so no markers are drawn
+        view_model.set_wrap_enabled(true).unwrap();
+        let output = render_full(&view_model);
+        assert!(!output.contains(&extends));
+        assert!(!output.contains(&precedes));
+    }
+
+    #[test]
+    fn request_header_line_should_color_method_and_fit_to_width() {
+        let target = RequestTarget {
+            method: "POST".to_string(),
+            url: "https://api.example.com/users".to_string(),
+        };
+
+        assert_eq!(
+            request_header_line(Some(&target), 12),
+            format!("{}POST{} https:/", ansi::method_color("POST"), ansi::RESET)
+        );
+        // Too narrow for the whole method, so nothing is colored
+        assert_eq!(request_header_line(Some(&target), 2), "PO");
+        assert_eq!(
+            request_header_line(None, 20),
+            format!("{}(invalid request)   {}", ansi::DIM, ansi::RESET)
+        );
+    }
+
+    #[test]
+    fn noshowmode_should_leave_mode_out_of_status_bar() {
+        let mut view_model = ViewModel::new();
+        view_model.update_terminal_size(40, 10);
+        view_model.change_mode(EditorMode::Insert).unwrap();
+        let render_status = |view_model: &ViewModel| {
+            let mut renderer =
+                TerminalRenderer::with_render_stream(MockRenderStream::with_size((40, 10)))
+                    .unwrap();
+            renderer.render_status_bar(view_model).unwrap();
+            renderer.render_stream.get_buffer_string()
+        };
+        assert!(render_status(&view_model).contains("-- INSERT --"));
+
+        view_model
+            .apply_setting(Setting::ShowMode, SettingValue::Off)
+            .unwrap();
+        let output = render_status(&view_model);
+        assert!(!output.contains("-- INSERT --"));
+        assert!(output.contains("REQUEST 1:1"));
+    }
+
+    #[test]
+    fn request_header_should_sit_above_request_text_and_follow_edits() {
+        let mut view_model = ViewModel::new();
+        view_model.update_terminal_size(40, 10);
+        view_model.set_request_text("get /users").unwrap();
+        let header = |method: &str, url: &str| {
+            let target = RequestTarget {
+                method: method.to_string(),
+                url: url.to_string(),
+            };
+            request_header_line(Some(&target), 40)
+        };
+        let render = |view_model: &ViewModel, partial: bool| {
+            let mut renderer =
+                TerminalRenderer::with_render_stream(MockRenderStream::with_size((40, 10)))
+                    .unwrap();
+            if partial {
+                renderer
+                    .render_pane_partial(view_model, Pane::Request, 0)
+                    .unwrap();
+            } else {
+                renderer.render_full(view_model).unwrap();
+            }
+            (
+                renderer.render_stream.get_buffer_string(),
+                renderer.render_stream.get_commands(),
+            )
+        };
+
+        let (output, commands) = render(&view_model, false);
+        assert!(output.contains(&header("GET", "/users")));
+        // The cursor on the first line of text sits one row below the header
+        let cursor_row = commands.iter().rev().find_map(|command| match command {
+            RenderCommand::MoveCursor(_, row) => Some(*row),
+            _ => None,
+        });
+        assert_eq!(cursor_row, Some(1));
+
+        view_model.change_mode(EditorMode::Insert).unwrap();
+        view_model
+            .set_cursor_position(crate::repl::events::LogicalPosition::new(0, 4))
+            .unwrap();
+        view_model.insert_text("/v1").unwrap();
+        let (output, _) = render(&view_model, true);
+        assert!(output.contains(&header("GET", "/v1/users")));
+
+        view_model.set_request_text("/users").unwrap();
+        let (output, _) = render(&view_model, true);
+        assert!(output.contains(&request_header_line(None, 40)));
+    }
+
+    #[test]
+    fn render_gutter_should_color_line_numbers_by_mode() {
+        let mut view_model = ViewModel::new();
+        view_model.update_terminal_size(80, 24);
+        let render_gutter = |view_model: &ViewModel| {
+            let mut renderer =
+                TerminalRenderer::with_render_stream(MockRenderStream::with_size((80, 24)))
+                    .unwrap();
+            renderer.render_gutter(view_model, Pane::Request).unwrap();
+            renderer.render_stream.get_buffer_string()
+        };
+
+        let normal = render_gutter(&view_model);
+        view_model.change_mode(EditorMode::Insert).unwrap();
+        let insert = render_gutter(&view_model);
+
+        assert_ne!(ansi::FG_GUTTER_NORMAL, ansi::FG_GUTTER_INSERT);
+        assert!(normal.contains(&format!("{}  1 ", ansi::FG_GUTTER_NORMAL)));
+        assert!(insert.contains(&format!("{}  1 ", ansi::FG_GUTTER_INSERT)));
+        assert!(!insert.contains(&format!("{}  1 ", ansi::FG_GUTTER_NORMAL)));
+    }
+
     #[test]
     fn response_pane_boundaries_should_calculate_correctly() {
         let render_stream = MockRenderStream::with_size((80, 40));